@@ -15,7 +15,7 @@ impl<T: Field> Encode<T> for Inputs<T> {
 }
 
 use std::fmt;
-use zokrates_ast::typed::types::{ConcreteType, UBitwidth};
+use zokrates_ast::typed::types::{ConcreteArrayType, ConcreteEnumType, ConcreteType, UBitwidth};
 
 use zokrates_field::Field;
 
@@ -42,11 +42,20 @@ pub enum Value<T> {
     U16(u16),
     U32(u32),
     U64(u64),
+    U128(u128),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
     Field(T),
     Boolean(bool),
     Array(Vec<Value<T>>),
+    /// The elements of a bounded array, and the type of the array of its capacity holding them
+    BoundedArray(Vec<Value<T>>, ConcreteArrayType),
     Struct(Vec<(String, Value<T>)>),
     Tuple(Vec<Value<T>>),
+    /// The index of a variant, its payload, and the type of the enum
+    Enum(u32, Vec<Value<T>>, ConcreteEnumType),
 }
 
 #[derive(PartialEq, Debug)]
@@ -60,8 +69,13 @@ impl<T: Field> fmt::Display for Value<T> {
             Value::U16(v) => write!(f, "{:#06x}", v),
             Value::U32(v) => write!(f, "{:#010x}", v),
             Value::U64(v) => write!(f, "{:#018x}", v),
+            Value::U128(v) => write!(f, "{:#034x}", v),
+            Value::I8(v) => write!(f, "{}", v),
+            Value::I16(v) => write!(f, "{}", v),
+            Value::I32(v) => write!(f, "{}", v),
+            Value::I64(v) => write!(f, "{}", v),
             Value::Boolean(v) => write!(f, "{}", v),
-            Value::Array(v) => write!(
+            Value::Array(v) | Value::BoundedArray(v, _) => write!(
                 f,
                 "[{}]",
                 v.iter()
@@ -93,6 +107,26 @@ impl<T: Field> fmt::Display for Value<T> {
                 }?;
                 write!(f, ")")
             }
+            Value::Enum(variant, payload, enum_ty) => {
+                write!(
+                    f,
+                    "{}::{}",
+                    enum_ty.name(),
+                    enum_ty.variants[*variant as usize].id
+                )?;
+                match payload.len() {
+                    0 => Ok(()),
+                    _ => write!(
+                        f,
+                        "({})",
+                        payload
+                            .iter()
+                            .map(|e| e.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                }
+            }
         }
     }
 }
@@ -115,10 +149,40 @@ impl<T: Field> Encode<T> for Value<T> {
             Value::U16(v) => vec![T::from(v)],
             Value::U32(v) => vec![T::from(v)],
             Value::U64(v) => vec![T::from(v)],
+            Value::U128(v) => vec![T::from(v)],
+            // signed integers are represented by their two's complement bits
+            Value::I8(v) => vec![T::from(v as u8)],
+            Value::I16(v) => vec![T::from(v as u16)],
+            Value::I32(v) => vec![T::from(v as u32)],
+            Value::I64(v) => vec![T::from(v as u64)],
             Value::Boolean(b) => vec![T::from(b)],
             Value::Array(a) => a.into_iter().flat_map(|v| v.encode()).collect(),
+            // the length, followed by the elements padded with zeroes up to the capacity
+            Value::BoundedArray(a, data_ty) => {
+                let padding = (*data_ty.size as usize - a.len()) * data_ty.ty.get_primitive_count();
+                std::iter::once(T::from(a.len()))
+                    .chain(a.into_iter().flat_map(|v| v.encode()))
+                    .chain(std::iter::repeat(T::zero()).take(padding))
+                    .collect()
+            }
             Value::Tuple(t) => t.into_iter().flat_map(|v| v.encode()).collect(),
             Value::Struct(s) => s.into_iter().flat_map(|(_, v)| v.encode()).collect(),
+            // the index of the variant, followed by the payload slots, the ones the variant does not use being zero
+            Value::Enum(variant, payload, enum_ty) => {
+                let (slots, variants) = enum_ty.layout();
+                let mut slots: Vec<Vec<T>> = slots
+                    .into_iter()
+                    .map(|ty| vec![T::zero(); ty.get_primitive_count()])
+                    .collect();
+
+                for (slot, v) in variants[variant as usize].iter().zip(payload) {
+                    slots[*slot] = v.encode();
+                }
+
+                std::iter::once(T::from(variant))
+                    .chain(slots.into_iter().flatten())
+                    .collect()
+            }
         }
     }
 }
@@ -162,6 +226,21 @@ impl<T: Field> Decode<T> for Value<T> {
             ConcreteType::Uint(UBitwidth::B64) => {
                 Value::U64(raw.pop().unwrap().to_dec_string().parse().unwrap())
             }
+            ConcreteType::Uint(UBitwidth::B128) => {
+                Value::U128(raw.pop().unwrap().to_dec_string().parse().unwrap())
+            }
+            ConcreteType::Uint(UBitwidth::I8) => {
+                Value::I8(raw.pop().unwrap().to_dec_string().parse::<u8>().unwrap() as i8)
+            }
+            ConcreteType::Uint(UBitwidth::I16) => {
+                Value::I16(raw.pop().unwrap().to_dec_string().parse::<u16>().unwrap() as i16)
+            }
+            ConcreteType::Uint(UBitwidth::I32) => {
+                Value::I32(raw.pop().unwrap().to_dec_string().parse::<u32>().unwrap() as i32)
+            }
+            ConcreteType::Uint(UBitwidth::I64) => {
+                Value::I64(raw.pop().unwrap().to_dec_string().parse::<u64>().unwrap() as i64)
+            }
             ConcreteType::Boolean => {
                 let v = raw.pop().unwrap();
                 Value::Boolean(if v == 0.into() {
@@ -177,6 +256,17 @@ impl<T: Field> Decode<T> for Value<T> {
                     .map(|c| Value::decode(c.to_vec(), *array_type.ty.clone()))
                     .collect(),
             ),
+            ConcreteType::BoundedArray(data_ty) => {
+                let len: usize = raw[0].to_dec_string().parse().unwrap();
+                Value::BoundedArray(
+                    raw[1..]
+                        .chunks(data_ty.ty.get_primitive_count())
+                        .take(len)
+                        .map(|c| Value::decode(c.to_vec(), *data_ty.ty.clone()))
+                        .collect(),
+                    data_ty,
+                )
+            }
             ConcreteType::Struct(members) => Value::Struct(
                 members
                     .into_iter()
@@ -200,6 +290,30 @@ impl<T: Field> Decode<T> for Value<T> {
                     })
                     .collect(),
             ),
+            ConcreteType::Enum(enum_ty) => {
+                let variant: u32 = raw[0].to_dec_string().parse().unwrap();
+                let (slots, variants) = enum_ty.layout();
+                let offsets: Vec<usize> = slots
+                    .iter()
+                    .scan(1, |state, ty| {
+                        let offset = *state;
+                        *state += ty.get_primitive_count();
+                        Some(offset)
+                    })
+                    .collect();
+
+                let payload = variants[variant as usize]
+                    .iter()
+                    .map(|slot| {
+                        let ty = slots[*slot].clone();
+                        let offset = offsets[*slot];
+                        let size = ty.get_primitive_count();
+                        Value::decode(raw[offset..offset + size].to_vec(), ty)
+                    })
+                    .collect();
+
+                Value::Enum(variant, payload, enum_ty)
+            }
         }
     }
 }
@@ -218,8 +332,13 @@ impl<T: Field> Value<T> {
             Value::U16(u) => serde_json::Value::String(format!("{:#06x}", u)),
             Value::U32(u) => serde_json::Value::String(format!("{:#010x}", u)),
             Value::U64(u) => serde_json::Value::String(format!("{:#018x}", u)),
+            Value::U128(u) => serde_json::Value::String(format!("{:#034x}", u)),
+            Value::I8(i) => serde_json::Value::String(i.to_string()),
+            Value::I16(i) => serde_json::Value::String(i.to_string()),
+            Value::I32(i) => serde_json::Value::String(i.to_string()),
+            Value::I64(i) => serde_json::Value::String(i.to_string()),
             Value::Boolean(b) => serde_json::Value::Bool(b),
-            Value::Array(a) => {
+            Value::Array(a) | Value::BoundedArray(a, _) => {
                 serde_json::Value::Array(a.into_iter().map(|e| e.into_serde_json()).collect())
             }
             Value::Tuple(t) => {
@@ -230,6 +349,22 @@ impl<T: Field> Value<T> {
                     .map(|(k, v)| (k, v.into_serde_json()))
                     .collect(),
             ),
+            // variants without payload are represented by their name, others by `{ "name": [payload] }`
+            Value::Enum(variant, payload, enum_ty) => {
+                let id = enum_ty.variants[variant as usize].id.clone();
+                match payload.len() {
+                    0 => serde_json::Value::String(id),
+                    _ => serde_json::Value::Object(
+                        std::iter::once((
+                            id,
+                            serde_json::Value::Array(
+                                payload.into_iter().map(|e| e.into_serde_json()).collect(),
+                            ),
+                        ))
+                        .collect(),
+                    ),
+                }
+            }
         }
     }
 }
@@ -275,6 +410,44 @@ fn parse_value<T: Field>(
             .or_else(|_| u64::from_str_radix(s.as_str().trim_start_matches("0x"), 16))
             .map(Value::U64)
             .map_err(|_| Error::Type(format!("Could not parse `{}` to u64 type", s))),
+        (ConcreteType::Uint(UBitwidth::B128), serde_json::Value::String(s)) => s
+            .as_str()
+            .parse::<u128>()
+            .or_else(|_| u128::from_str_radix(s.as_str().trim_start_matches("0x"), 16))
+            .map(Value::U128)
+            .map_err(|_| Error::Type(format!("Could not parse `{}` to u128 type", s))),
+        (ConcreteType::Uint(UBitwidth::I8), serde_json::Value::String(s)) => s
+            .as_str()
+            .parse::<i8>()
+            .or_else(|_| {
+                u8::from_str_radix(s.as_str().trim_start_matches("0x"), 16).map(|u| u as i8)
+            })
+            .map(Value::I8)
+            .map_err(|_| Error::Type(format!("Could not parse `{}` to i8 type", s))),
+        (ConcreteType::Uint(UBitwidth::I16), serde_json::Value::String(s)) => s
+            .as_str()
+            .parse::<i16>()
+            .or_else(|_| {
+                u16::from_str_radix(s.as_str().trim_start_matches("0x"), 16).map(|u| u as i16)
+            })
+            .map(Value::I16)
+            .map_err(|_| Error::Type(format!("Could not parse `{}` to i16 type", s))),
+        (ConcreteType::Uint(UBitwidth::I32), serde_json::Value::String(s)) => s
+            .as_str()
+            .parse::<i32>()
+            .or_else(|_| {
+                u32::from_str_radix(s.as_str().trim_start_matches("0x"), 16).map(|u| u as i32)
+            })
+            .map(Value::I32)
+            .map_err(|_| Error::Type(format!("Could not parse `{}` to i32 type", s))),
+        (ConcreteType::Uint(UBitwidth::I64), serde_json::Value::String(s)) => s
+            .as_str()
+            .parse::<i64>()
+            .or_else(|_| {
+                u64::from_str_radix(s.as_str().trim_start_matches("0x"), 16).map(|u| u as i64)
+            })
+            .map(Value::I64)
+            .map_err(|_| Error::Type(format!("Could not parse `{}` to i64 type", s))),
         (ConcreteType::Boolean, serde_json::Value::Bool(b)) => Ok(Value::Boolean(b)),
         (ConcreteType::Array(array_type), serde_json::Value::Array(a)) => {
             let size = *array_type.size;
@@ -291,6 +464,21 @@ fn parse_value<T: Field>(
                     .map(Value::Array)
             }
         }
+        (ConcreteType::BoundedArray(data_ty), serde_json::Value::Array(a)) => {
+            let capacity = *data_ty.size;
+            if a.len() > capacity as usize {
+                Err(Error::Type(format!(
+                    "Expected bounded array of capacity {}, found array of size {}",
+                    capacity,
+                    a.len()
+                )))
+            } else {
+                a.into_iter()
+                    .map(|v| parse_value(v, *data_ty.ty.clone()))
+                    .collect::<Result<_, _>>()
+                    .map(|a| Value::BoundedArray(a, data_ty.clone()))
+            }
+        }
         (ConcreteType::Tuple(tuple_type), serde_json::Value::Array(a)) => {
             let size = tuple_type.elements.len();
             if a.len() != size {
@@ -332,6 +520,19 @@ fn parse_value<T: Field>(
                 ))
             }
         }
+        (ConcreteType::Enum(enum_ty), serde_json::Value::String(id)) => {
+            parse_variant(enum_ty, id, vec![])
+        }
+        (ConcreteType::Enum(enum_ty), serde_json::Value::Object(o)) if o.len() == 1 => {
+            let (id, payload) = o.into_iter().next().unwrap();
+            match payload {
+                serde_json::Value::Array(payload) => parse_variant(enum_ty, id, payload),
+                payload => Err(Error::Type(format!(
+                    "Expected an array of values as the payload of variant `{}`, found `{}`",
+                    id, payload
+                ))),
+            }
+        }
         (_, serde_json::Value::Number(n)) => Err(Error::Conversion(format!(
             "Value `{}` isn't allowed, did you mean `\"{}\"`?",
             n, n
@@ -343,6 +544,41 @@ fn parse_value<T: Field>(
     }
 }
 
+fn parse_variant<T: Field>(
+    enum_ty: &ConcreteEnumType,
+    id: String,
+    payload: Vec<serde_json::Value>,
+) -> Result<Value<T>, Error> {
+    let variant = enum_ty.variant_index(&id).ok_or_else(|| {
+        Error::Type(format!(
+            "Variant `{}` not found in enum `{}`",
+            id,
+            enum_ty.name()
+        ))
+    })?;
+
+    let types = &enum_ty.variants[variant as usize].types;
+
+    if payload.len() != types.len() {
+        return Err(Error::Type(format!(
+            "Expected {} value(s) in the payload of variant `{}`, found {}",
+            types.len(),
+            id,
+            payload.len()
+        )));
+    }
+
+    Ok(Value::Enum(
+        variant,
+        payload
+            .into_iter()
+            .zip(types.iter())
+            .map(|(v, ty)| parse_value(v, ty.clone()))
+            .collect::<Result<_, _>>()?,
+        enum_ty.clone(),
+    ))
+}
+
 pub fn parse_strict<T: Field>(s: &str, types: Vec<ConcreteType>) -> Result<Values<T>, Error> {
     let values: serde_json::Value =
         serde_json::from_str(s).map_err(|e| Error::Json(e.to_string()))?;
@@ -380,7 +616,9 @@ pub fn parse_strict_json<T: Field>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use zokrates_ast::typed::types::{ConcreteStructMember, ConcreteStructType, ConcreteType};
+    use zokrates_ast::typed::types::{
+        ConcreteEnumVariant, ConcreteStructMember, ConcreteStructType, ConcreteType,
+    };
     use zokrates_field::Bn128Field;
 
     #[test]
@@ -433,6 +671,22 @@ mod tests {
             ])
         );
 
+        let s = r#"["0x12345678123456781234567812345678", "42"]"#;
+        assert_eq!(
+            parse_strict::<Bn128Field>(
+                s,
+                vec![
+                    ConcreteType::Uint(UBitwidth::B128),
+                    ConcreteType::Uint(UBitwidth::B128)
+                ]
+            )
+            .unwrap(),
+            Values(vec![
+                Value::U128(0x12345678123456781234567812345678u128),
+                Value::U128(42u128)
+            ])
+        );
+
         let s = r#"["0x1234"]"#;
         assert_eq!(
             parse_strict::<Bn128Field>(s, vec![ConcreteType::Uint(UBitwidth::B32)]).unwrap(),
@@ -446,6 +700,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ints() {
+        let s = r#"["-128", "0x7fff", "-2147483648", "9223372036854775807"]"#;
+        assert_eq!(
+            parse_strict::<Bn128Field>(
+                s,
+                vec![
+                    ConcreteType::Uint(UBitwidth::I8),
+                    ConcreteType::Uint(UBitwidth::I16),
+                    ConcreteType::Uint(UBitwidth::I32),
+                    ConcreteType::Uint(UBitwidth::I64)
+                ]
+            )
+            .unwrap(),
+            Values(vec![
+                Value::I8(-128i8),
+                Value::I16(32767i16),
+                Value::I32(-2147483648i32),
+                Value::I64(9223372036854775807i64)
+            ])
+        );
+
+        let s = r#"["0xff"]"#;
+        assert_eq!(
+            parse_strict::<Bn128Field>(s, vec![ConcreteType::Uint(UBitwidth::I8)]).unwrap(),
+            Values(vec![Value::I8(-1i8)])
+        );
+
+        let s = r#"["128"]"#;
+        assert_eq!(
+            parse_strict::<Bn128Field>(s, vec![ConcreteType::Uint(UBitwidth::I8)]).unwrap_err(),
+            Error::Type("Could not parse `128` to i8 type".into())
+        );
+    }
+
+    #[test]
+    fn ints_encode_decode() {
+        let values = Values::<Bn128Field>(vec![Value::I8(-1), Value::I64(-42)]);
+        let encoded = values.encode();
+        assert_eq!(
+            encoded,
+            vec![Bn128Field::from(255u8), Bn128Field::from(u64::MAX - 41)]
+        );
+        assert_eq!(
+            Values::decode(
+                encoded,
+                vec![
+                    ConcreteType::Uint(UBitwidth::I8),
+                    ConcreteType::Uint(UBitwidth::I64)
+                ]
+            )
+            .into_serde_json(),
+            serde_json::json!(["-1", "-42"])
+        );
+    }
+
     #[test]
     fn bools() {
         let s = "[true, false]";
@@ -469,6 +779,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bounded_array() {
+        let ty =
+            ConcreteType::bounded_array(ConcreteArrayType::new(ConcreteType::FieldElement, 3u32));
+
+        let s = r#"[["1", "2"]]"#;
+        let values = parse_strict::<Bn128Field>(s, vec![ty.clone()]).unwrap();
+        assert_eq!(
+            values,
+            Values(vec![Value::BoundedArray(
+                vec![Value::Field(1.into()), Value::Field(2.into())],
+                ConcreteArrayType::new(ConcreteType::FieldElement, 3u32)
+            )])
+        );
+
+        let encoded = values.encode();
+        assert_eq!(encoded, vec![2.into(), 1.into(), 2.into(), 0.into()]);
+        assert_eq!(
+            Values::decode(encoded, vec![ty.clone()]).into_serde_json(),
+            serde_json::json!([["1", "2"]])
+        );
+
+        let s = r#"[["1", "2", "3", "4"]]"#;
+        assert_eq!(
+            parse_strict::<Bn128Field>(s, vec![ty]).unwrap_err(),
+            Error::Type("Expected bounded array of capacity 3, found array of size 4".into())
+        );
+    }
+
+    #[test]
+    fn enumeration() {
+        let ty = ConcreteType::Enum(ConcreteEnumType::new(
+            "".into(),
+            "Op".into(),
+            vec![
+                ConcreteEnumVariant::new("Nop".into(), vec![]),
+                ConcreteEnumVariant::new("Add".into(), vec![ConcreteType::Uint(UBitwidth::B32); 2]),
+                ConcreteEnumVariant::new("Neg".into(), vec![ConcreteType::FieldElement]),
+            ],
+        ));
+
+        let s = r#"[{"Neg": ["3"]}, "Nop"]"#;
+        let values = parse_strict::<Bn128Field>(s, vec![ty.clone(), ty.clone()]).unwrap();
+
+        let encoded = values.encode();
+        assert_eq!(
+            encoded,
+            vec![
+                2.into(),
+                0.into(),
+                0.into(),
+                3.into(),
+                0.into(),
+                0.into(),
+                0.into(),
+                0.into()
+            ]
+        );
+        assert_eq!(
+            Values::decode(encoded, vec![ty.clone(), ty.clone()]).into_serde_json(),
+            serde_json::json!([{"Neg": ["3"]}, "Nop"])
+        );
+
+        let s = r#"[{"Add": ["0x00000001"]}]"#;
+        assert_eq!(
+            parse_strict::<Bn128Field>(s, vec![ty.clone()]).unwrap_err(),
+            Error::Type("Expected 2 value(s) in the payload of variant `Add`, found 1".into())
+        );
+
+        let s = r#"["Mul"]"#;
+        assert_eq!(
+            parse_strict::<Bn128Field>(s, vec![ty]).unwrap_err(),
+            Error::Type("Variant `Mul` not found in enum `Op`".into())
+        );
+    }
+
     #[test]
     fn struc() {
         let s = r#"[{"a": "42"}]"#;
@@ -0,0 +1,483 @@
+//! Module containing the `CommonSubexpressionEliminator` to reuse the values of expressions which were already defined
+// ```
+// field c = a * b;
+// field d = a * b + 1;
+// field e = c;
+// field f = e * 2;
+// ```
+// becomes
+// ```
+// field c = a * b;
+// field d = c + 1;
+// field e = c;
+// field f = c * 2;
+// ```
+
+// ZIR expressions are pure, as embed calls only appear in multiple definitions. An expression which can panic, such as a
+// division, is only replaced by a variable defined earlier in the same or in an enclosing branch, so the first
+// evaluation already failed in that case.
+// Variables defined in a branch of an `IfElse` statement are not available outside of it: when branches are isolated,
+// their value is arbitrary if the branch is not taken. Therefore, the expressions defined in a branch are forgotten
+// when leaving it.
+// Assembly blocks are left untouched, as their constraints are written explicitly.
+// Some variables are defined more than once, for example when an element of an array is updated. Their value depends on
+// the position in the program, so they are never used as a replacement, and the expressions using them are never reused.
+
+use std::collections::{HashMap, HashSet};
+use zokrates_ast::zir::{
+    folder::*, BooleanExpression, Constant, Expr, FieldElementExpression, Id, Identifier,
+    IdentifierExpression, IdentifierOrExpression, Parameter, UExpression, UExpressionInner,
+    ZirAssemblyStatement, ZirExpression, ZirFunction, ZirProgram, ZirStatement,
+};
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct CommonSubexpressionEliminator<'ast, T> {
+    /// The variable each expression was assigned to
+    expressions: HashMap<ZirExpression<'ast, T>, Identifier<'ast>>,
+    /// The variables defined as another variable
+    aliases: HashMap<Identifier<'ast>, Identifier<'ast>>,
+    /// The expressions defined in the current branches, in order
+    defined_expressions: Vec<ZirExpression<'ast, T>>,
+    /// The aliases defined in the current branches, in order
+    defined_aliases: Vec<Identifier<'ast>>,
+    /// The variables which are defined more than once
+    redefined: HashSet<Identifier<'ast>>,
+    /// Whether the expression being folded uses a variable which is defined more than once
+    uses_redefined: bool,
+}
+
+impl<'ast, T: Field> CommonSubexpressionEliminator<'ast, T> {
+    pub fn eliminate(p: ZirProgram<'ast, T>) -> ZirProgram<'ast, T> {
+        Self::default().fold_program(p)
+    }
+
+    // fold the statements of a branch, forgetting what they define afterwards
+    fn fold_branch(
+        &mut self,
+        statements: Vec<ZirStatement<'ast, T>>,
+    ) -> Vec<ZirStatement<'ast, T>> {
+        let expression_count = self.defined_expressions.len();
+        let alias_count = self.defined_aliases.len();
+
+        let statements = statements
+            .into_iter()
+            .flat_map(|s| self.fold_statement(s))
+            .collect();
+
+        for e in self.defined_expressions.drain(expression_count..) {
+            self.expressions.remove(&e);
+        }
+        for id in self.defined_aliases.drain(alias_count..) {
+            self.aliases.remove(&id);
+        }
+
+        statements
+    }
+
+    // replace an expression by the variable it was assigned to, if any
+    fn reuse(&self, e: ZirExpression<'ast, T>) -> ZirExpression<'ast, T> {
+        match self.expressions.get(&e) {
+            Some(id) => match e {
+                ZirExpression::FieldElement(_) => {
+                    FieldElementExpression::<T>::identifier(id.clone()).into()
+                }
+                ZirExpression::Boolean(_) => BooleanExpression::<T>::identifier(id.clone()).into(),
+                ZirExpression::Uint(e) => UExpression::<T>::identifier(id.clone())
+                    .annotate(e.bitwidth)
+                    .into(),
+            },
+            None => e,
+        }
+    }
+}
+
+// the variables which are defined more than once in a function
+fn redefined<'ast, T>(
+    arguments: &[Parameter<'ast>],
+    statements: &[ZirStatement<'ast, T>],
+) -> HashSet<Identifier<'ast>> {
+    fn visit<'a, 'ast, T>(
+        statements: &'a [ZirStatement<'ast, T>],
+        defined: &mut HashSet<&'a Identifier<'ast>>,
+        redefined: &mut HashSet<Identifier<'ast>>,
+    ) {
+        for s in statements {
+            let assignees: Vec<_> = match s {
+                ZirStatement::Definition(a, _) => vec![a],
+                ZirStatement::MultipleDefinition(assignees, _) => assignees.iter().collect(),
+                ZirStatement::Assembly(statements) => statements
+                    .iter()
+                    .flat_map(|s| match s {
+                        ZirAssemblyStatement::Assignment(assignees, _) => {
+                            assignees.iter().collect()
+                        }
+                        ZirAssemblyStatement::Constraint(..) => vec![],
+                    })
+                    .collect(),
+                ZirStatement::IfElse(_, consequence, alternative, _) => {
+                    visit(consequence, defined, redefined);
+                    visit(alternative, defined, redefined);
+                    vec![]
+                }
+                _ => vec![],
+            };
+
+            for a in assignees {
+                if !defined.insert(&a.id) {
+                    redefined.insert(a.id.clone());
+                }
+            }
+        }
+    }
+
+    let mut defined: HashSet<_> = arguments.iter().map(|p| &p.id.id).collect();
+    let mut redefined = HashSet::new();
+    visit(statements, &mut defined, &mut redefined);
+    redefined
+}
+
+fn as_identifier<'a, 'ast, T>(e: &'a ZirExpression<'ast, T>) -> Option<&'a Identifier<'ast>> {
+    match e {
+        ZirExpression::FieldElement(FieldElementExpression::Identifier(e)) => Some(&e.id),
+        ZirExpression::Boolean(BooleanExpression::Identifier(e)) => Some(&e.id),
+        ZirExpression::Uint(UExpression {
+            inner: UExpressionInner::Identifier(e),
+            ..
+        }) => Some(&e.id),
+        _ => None,
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for CommonSubexpressionEliminator<'ast, T> {
+    fn fold_function(&mut self, f: ZirFunction<'ast, T>) -> ZirFunction<'ast, T> {
+        self.redefined = redefined(&f.arguments, &f.statements);
+        fold_function(self, f)
+    }
+
+    fn fold_statement(&mut self, s: ZirStatement<'ast, T>) -> Vec<ZirStatement<'ast, T>> {
+        match s {
+            ZirStatement::Definition(a, e) => {
+                self.uses_redefined = false;
+                let e = self.fold_expression(e);
+
+                match as_identifier(&e) {
+                    _ if self.uses_redefined || self.redefined.contains(&a.id) => {}
+                    Some(id) => {
+                        self.aliases.insert(a.id.clone(), id.clone());
+                        self.defined_aliases.push(a.id.clone());
+                    }
+                    None if !e.is_constant() => {
+                        self.expressions.insert(e.clone(), a.id.clone());
+                        self.defined_expressions.push(e.clone());
+                    }
+                    None => {}
+                }
+
+                vec![ZirStatement::Definition(a, e)]
+            }
+            ZirStatement::IfElse(condition, consequence, alternative, isolated) => {
+                let condition = self.fold_boolean_expression(condition);
+                let consequence = self.fold_branch(consequence);
+                let alternative = self.fold_branch(alternative);
+
+                vec![ZirStatement::IfElse(
+                    condition,
+                    consequence,
+                    alternative,
+                    isolated,
+                )]
+            }
+            s => fold_statement(self, s),
+        }
+    }
+
+    fn fold_assembly_statement(
+        &mut self,
+        s: ZirAssemblyStatement<'ast, T>,
+    ) -> Vec<ZirAssemblyStatement<'ast, T>> {
+        vec![s]
+    }
+
+    fn fold_identifier_expression<E: Expr<'ast, T> + Id<'ast, T>>(
+        &mut self,
+        _: &E::Ty,
+        e: IdentifierExpression<'ast, E>,
+    ) -> IdentifierOrExpression<'ast, T, E> {
+        if self.redefined.contains(&e.id) {
+            self.uses_redefined = true;
+        }
+
+        match self.aliases.get(&e.id) {
+            Some(id) => IdentifierOrExpression::Identifier(IdentifierExpression::new(id.clone())),
+            None => IdentifierOrExpression::Identifier(e),
+        }
+    }
+
+    fn fold_field_expression(
+        &mut self,
+        e: FieldElementExpression<'ast, T>,
+    ) -> FieldElementExpression<'ast, T> {
+        let e = fold_field_expression(self, e);
+        self.reuse(e.into()).into()
+    }
+
+    fn fold_boolean_expression(
+        &mut self,
+        e: BooleanExpression<'ast, T>,
+    ) -> BooleanExpression<'ast, T> {
+        let e = fold_boolean_expression(self, e);
+        self.reuse(e.into()).into()
+    }
+
+    fn fold_uint_expression(&mut self, e: UExpression<'ast, T>) -> UExpression<'ast, T> {
+        let e = fold_uint_expression(self, e);
+        self.reuse(e.into()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_ast::zir::{types::Signature, Conditional, RuntimeError, Variable};
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn reuse_definition() {
+        // field c = a * b
+        // field d = a * b + 1
+        // assert(d == a * b)
+
+        // ->
+
+        // field c = a * b
+        // field d = c + 1
+        // assert(d == c)
+
+        let a_times_b = || {
+            FieldElementExpression::<Bn128Field>::Mult(
+                box FieldElementExpression::identifier("a".into()),
+                box FieldElementExpression::identifier("b".into()),
+            )
+        };
+
+        let statements = vec![
+            ZirStatement::Definition(Variable::field_element("c"), a_times_b().into()),
+            ZirStatement::Definition(
+                Variable::field_element("d"),
+                FieldElementExpression::Add(
+                    box a_times_b(),
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                )
+                .into(),
+            ),
+            ZirStatement::Assertion(
+                BooleanExpression::FieldEq(
+                    box FieldElementExpression::identifier("d".into()),
+                    box a_times_b(),
+                ),
+                RuntimeError::mock(),
+            ),
+        ];
+
+        let expected = vec![
+            ZirStatement::Definition(Variable::field_element("c"), a_times_b().into()),
+            ZirStatement::Definition(
+                Variable::field_element("d"),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::identifier("c".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                )
+                .into(),
+            ),
+            ZirStatement::Assertion(
+                BooleanExpression::FieldEq(
+                    box FieldElementExpression::identifier("d".into()),
+                    box FieldElementExpression::identifier("c".into()),
+                ),
+                RuntimeError::mock(),
+            ),
+        ];
+
+        let mut eliminator = CommonSubexpressionEliminator::default();
+        let statements: Vec<_> = statements
+            .into_iter()
+            .flat_map(|s| eliminator.fold_statement(s))
+            .collect();
+
+        assert_eq!(statements, expected);
+    }
+
+    #[test]
+    fn reuse_through_alias() {
+        // u32 c = a + b
+        // u32 d = a + b
+        // u32 e = d ^ a
+        // u32 f = c ^ a
+
+        // ->
+
+        // u32 c = a + b
+        // u32 d = c
+        // u32 e = c ^ a
+        // u32 f = e
+
+        let id =
+            |name: &'static str| UExpression::<Bn128Field>::identifier(name.into()).annotate(32);
+
+        let statements = vec![
+            ZirStatement::Definition(
+                Variable::uint("c".into(), 32),
+                UExpressionInner::Add(box id("a"), box id("b"))
+                    .annotate(32)
+                    .into(),
+            ),
+            ZirStatement::Definition(
+                Variable::uint("d".into(), 32),
+                UExpressionInner::Add(box id("a"), box id("b"))
+                    .annotate(32)
+                    .into(),
+            ),
+            ZirStatement::Definition(
+                Variable::uint("e".into(), 32),
+                UExpressionInner::Xor(box id("d"), box id("a"))
+                    .annotate(32)
+                    .into(),
+            ),
+            ZirStatement::Definition(
+                Variable::uint("f".into(), 32),
+                UExpressionInner::Xor(box id("c"), box id("a"))
+                    .annotate(32)
+                    .into(),
+            ),
+        ];
+
+        let expected = vec![
+            ZirStatement::Definition(
+                Variable::uint("c".into(), 32),
+                UExpressionInner::Add(box id("a"), box id("b"))
+                    .annotate(32)
+                    .into(),
+            ),
+            ZirStatement::Definition(Variable::uint("d".into(), 32), id("c").into()),
+            ZirStatement::Definition(
+                Variable::uint("e".into(), 32),
+                UExpressionInner::Xor(box id("c"), box id("a"))
+                    .annotate(32)
+                    .into(),
+            ),
+            ZirStatement::Definition(Variable::uint("f".into(), 32), id("e").into()),
+        ];
+
+        let mut eliminator = CommonSubexpressionEliminator::default();
+        let statements: Vec<_> = statements
+            .into_iter()
+            .flat_map(|s| eliminator.fold_statement(s))
+            .collect();
+
+        assert_eq!(statements, expected);
+    }
+
+    #[test]
+    fn ignore_redefined_variables() {
+        // field c = a + 1
+        // field c = c + 1
+        // field d = a + 1
+        // field e = c
+        // field f = e * 2
+
+        // `c` is redefined, so neither `a + 1` nor `e` can be replaced by it
+
+        let a_plus_one = || {
+            FieldElementExpression::<Bn128Field>::Add(
+                box FieldElementExpression::identifier("a".into()),
+                box FieldElementExpression::Number(Bn128Field::from(1)),
+            )
+        };
+
+        let statements = vec![
+            ZirStatement::Definition(Variable::field_element("c"), a_plus_one().into()),
+            ZirStatement::Definition(
+                Variable::field_element("c"),
+                FieldElementExpression::Add(
+                    box FieldElementExpression::identifier("c".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(1)),
+                )
+                .into(),
+            ),
+            ZirStatement::Definition(Variable::field_element("d"), a_plus_one().into()),
+            ZirStatement::Definition(
+                Variable::field_element("e"),
+                FieldElementExpression::identifier("c".into()).into(),
+            ),
+            ZirStatement::Definition(
+                Variable::field_element("f"),
+                FieldElementExpression::Mult(
+                    box FieldElementExpression::identifier("e".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(2)),
+                )
+                .into(),
+            ),
+        ];
+
+        let f = ZirFunction {
+            arguments: vec![],
+            statements: statements.clone(),
+            signature: Signature::new(),
+        };
+
+        let f = CommonSubexpressionEliminator::default().fold_function(f);
+
+        assert_eq!(f.statements, statements);
+    }
+
+    #[test]
+    fn forget_branches() {
+        // if x then
+        //    field c = a / b
+        // else
+        // fi
+        // field d = a / b
+        // field e = x ? c : 0
+
+        // `a / b` is only defined in a branch, so it is not reused after it
+
+        let a_div_b = || {
+            FieldElementExpression::<Bn128Field>::Div(
+                box FieldElementExpression::identifier("a".into()),
+                box FieldElementExpression::identifier("b".into()),
+            )
+        };
+
+        let statements = vec![
+            ZirStatement::IfElse(
+                BooleanExpression::identifier("x".into()),
+                vec![ZirStatement::Definition(
+                    Variable::field_element("c"),
+                    a_div_b().into(),
+                )],
+                vec![],
+                false,
+            ),
+            ZirStatement::Definition(Variable::field_element("d"), a_div_b().into()),
+            ZirStatement::Definition(
+                Variable::field_element("e"),
+                FieldElementExpression::conditional(
+                    BooleanExpression::identifier("x".into()),
+                    FieldElementExpression::identifier("c".into()),
+                    FieldElementExpression::Number(Bn128Field::from(0)),
+                )
+                .into(),
+            ),
+        ];
+
+        let mut eliminator = CommonSubexpressionEliminator::default();
+        let result: Vec<_> = statements
+            .clone()
+            .into_iter()
+            .flat_map(|s| eliminator.fold_statement(s))
+            .collect();
+
+        assert_eq!(result, statements);
+    }
+}
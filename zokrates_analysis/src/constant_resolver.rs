@@ -68,8 +68,9 @@ impl<'ast, T: Field> Folder<'ast, T> for ConstantResolver<'ast, T> {
     }
 
     fn fold_module_id(&mut self, id: OwnedTypedModuleId) -> OwnedTypedModuleId {
-        // anytime we encounter a module id, visit the corresponding module if it hasn't been done yet
-        if !self.treated(&id) {
+        // anytime we encounter a module id, visit the corresponding module if it hasn't been done yet. The keys of
+        // function parameters point to no module, as they are only resolved when inlining
+        if !self.treated(&id) && self.modules.contains_key(&id) {
             let current_m_id = self.change_location(id.clone());
             let m = self.modules.remove(&id).unwrap();
             let m = self.fold_module(m);
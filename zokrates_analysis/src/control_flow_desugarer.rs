@@ -0,0 +1,800 @@
+// Desugar `if` statements, bounded `while` loops, `break`, `continue` and early `return` into straight-line code and constant `for` loops.
+// Each definition and assertion is guarded by a boolean flag which tracks whether it is logically executed, so that all
+// loops can still be fully unrolled and the constraint cost only depends on the loop bounds.
+// The guarded expressions are only evaluated when their guard holds: their branches are isolated even if branch isolation
+// is disabled, so that an out of bounds index or a failing assertion in a called function does not panic once the
+// statement is logically skipped.
+
+// For example, `for u32 i in 0..4 { if c { break; } a = a + 1; }` becomes
+//
+// bool mut #GUARD_0 = false;
+// for u32 i in 0..4 {
+//     bool mut #GUARD_1 = c;
+//     #GUARD_0 = #GUARD_0 || (#GUARD_1 && !#GUARD_0);
+//     a = if !#GUARD_0 { a + 1 } else { a };
+// }
+//
+// and `while c bounded 4 { ... }` becomes a `for` loop of 4 iterations which sets its `break` flag as soon as `c` is false,
+// followed by an assertion that `c` is false after the last iteration if the loop was not exited.
+//
+// In functions which return from inside a block, each `return` assigns the returned value to a variable and sets a "has returned"
+// flag which guards all subsequent statements. The function then ends with a single `return` of that variable.
+//
+// `for T x in a { ... }` loops are lowered here into a `for` loop over the indices of `a` rather than in the reducer like
+// array combinators: their body can contain `break`, `continue` and `return`, which must be desugared along with the loop
+// before reduction. The resulting loop is then unrolled by the reducer like a hand-written index loop, at the same cost.
+
+use std::collections::HashMap;
+use std::fmt;
+use zokrates_ast::typed::{
+    result_folder::ResultFolder, types::try_from_g_type, zero_value, ArrayExpression, Block,
+    BooleanExpression, BoundedArrayExpression, Conditional, ConditionalKind, CoreIdentifier,
+    DefinitionRhs, EnumExpression, Expr, FieldElementExpression, Id, Identifier, Select,
+    StructExpression, TupleExpression, Type, Typed, TypedAssignee, TypedExpression, TypedFunction,
+    TypedProgram, TypedStatement, UBitwidth, UExpression, Variable,
+};
+use zokrates_field::Field;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// the flags under which a statement is executed
+#[derive(Clone, Default)]
+struct Context<'ast> {
+    // the condition under which the enclosing block is entered
+    condition: Option<Identifier<'ast>>,
+    // set when the innermost loop is exited
+    broken: Option<Identifier<'ast>>,
+    // set when the current iteration of the innermost loop is skipped
+    continued: Option<Identifier<'ast>>,
+    // set when the function has returned
+    returned: Option<Identifier<'ast>>,
+}
+
+impl<'ast> Context<'ast> {
+    fn is_conditional(&self) -> bool {
+        self.condition.is_some()
+            || self.broken.is_some()
+            || self.continued.is_some()
+            || self.returned.is_some()
+    }
+
+    // the guard of a statement in this context, `None` if the statement is always executed
+    fn guard<T: Field>(&self) -> Option<BooleanExpression<'ast, T>> {
+        let flags = self
+            .condition
+            .iter()
+            .map(|id| BooleanExpression::identifier(id.clone()))
+            .chain(
+                self.broken
+                    .iter()
+                    .chain(&self.continued)
+                    .chain(&self.returned)
+                    .map(|id| {
+                        BooleanExpression::Not(box BooleanExpression::identifier(id.clone()))
+                    }),
+            );
+
+        flags.reduce(|acc, e| BooleanExpression::And(box acc, box e))
+    }
+}
+
+#[derive(Default)]
+pub struct ControlFlowDesugarer<'ast, T> {
+    index: usize,
+    // the variables defined in the current scope, with their type if they are not function parameters
+    defined: HashMap<Identifier<'ast>, Option<Type<'ast, T>>>,
+    // the variable holding the returned value, if the current function returns early
+    return_value: Option<Variable<'ast, T>>,
+}
+
+impl<'ast, T: Field> ControlFlowDesugarer<'ast, T> {
+    pub fn desugar(p: TypedProgram<'ast, T>) -> Result<TypedProgram<'ast, T>, Error> {
+        Self::default().fold_program(p)
+    }
+
+    fn fresh(&mut self) -> Identifier<'ast> {
+        let id = Identifier::from(CoreIdentifier::Guard(self.index));
+        self.index += 1;
+        id
+    }
+
+    // a new variable used to iterate a loop
+    fn fresh_loop_variable(&mut self) -> Identifier<'ast> {
+        let id = Identifier::from(CoreIdentifier::Loop(self.index));
+        self.index += 1;
+        id
+    }
+
+    // define a new flag with value `e`
+    fn define(
+        &mut self,
+        e: BooleanExpression<'ast, T>,
+        statements: &mut Vec<TypedStatement<'ast, T>>,
+    ) -> Identifier<'ast> {
+        let id = self.fresh();
+        statements.push(TypedStatement::definition(
+            Variable::mutable(id.clone(), Type::Boolean).into(),
+            e.into(),
+        ));
+        id
+    }
+
+    // whether defining `v` declares a new variable rather than assigning to an existing one
+    fn is_declaration(&self, v: &Variable<'ast, T>) -> bool {
+        !v.is_mutable
+            || match self.defined.get(&v.id) {
+                None => true,
+                Some(None) => false,
+                Some(Some(ty)) => *ty != v._type,
+            }
+    }
+
+    fn desugar_block(
+        &mut self,
+        statements: Vec<TypedStatement<'ast, T>>,
+        context: &Context<'ast>,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, Error> {
+        let defined = self.defined.clone();
+
+        let mut res = vec![];
+        for s in statements {
+            res.extend(self.desugar_statement(s, context)?);
+        }
+
+        self.defined = defined;
+
+        Ok(res)
+    }
+
+    // desugar the body of a loop, setting up the flags of the loop if required
+    fn desugar_loop_body(
+        &mut self,
+        statements: Vec<TypedStatement<'ast, T>>,
+        context: &Context<'ast>,
+        broken: Option<Identifier<'ast>>,
+        res: &mut Vec<TypedStatement<'ast, T>>,
+    ) -> Result<(Context<'ast>, Vec<TypedStatement<'ast, T>>), Error> {
+        // the loop is entered under the guard of the enclosing context
+        let condition = context.guard().map(|g| self.define(g, res));
+
+        let broken = broken.or_else(|| {
+            contains(&statements, &TypedStatement::Break).then(|| self.define(false.into(), res))
+        });
+
+        let mut body = vec![];
+
+        let continued = contains(&statements, &TypedStatement::Continue)
+            .then(|| self.define(false.into(), &mut body));
+
+        let context = Context {
+            condition,
+            broken,
+            continued,
+            returned: context.returned.clone(),
+        };
+
+        body.extend(self.desugar_block(statements, &context)?);
+
+        Ok((context, body))
+    }
+
+    fn desugar_statement(
+        &mut self,
+        s: TypedStatement<'ast, T>,
+        context: &Context<'ast>,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, Error> {
+        match s {
+            // declarations always define the declared variable, to a zero value if they are not executed, which is never read
+            // as the declared variable is not visible outside of this block
+            TypedStatement::Definition(TypedAssignee::Identifier(v), rhs)
+                if self.is_declaration(&v) =>
+            {
+                self.defined.insert(v.id.clone(), Some(v._type.clone()));
+                let rhs = match (context.guard(), rhs) {
+                    (Some(guard), DefinitionRhs::Expression(e)) => {
+                        guarded(guard, e, zero_value(&v._type)).into()
+                    }
+                    (_, rhs) => rhs,
+                };
+                Ok(vec![TypedStatement::Definition(
+                    TypedAssignee::Identifier(v),
+                    rhs,
+                )])
+            }
+            TypedStatement::Definition(assignee, DefinitionRhs::Expression(e)) => {
+                Ok(vec![match context.guard() {
+                    None => TypedStatement::definition(assignee, e),
+                    Some(guard) => {
+                        let e = guarded(guard, e, current_value(assignee.clone()));
+                        TypedStatement::definition(assignee, e)
+                    }
+                }])
+            }
+            TypedStatement::Definition(_, DefinitionRhs::EmbedCall(..)) => {
+                unreachable!("embed calls are introduced after control flow is desugared")
+            }
+            TypedStatement::Assertion(e, error) => Ok(vec![match context.guard() {
+                None => TypedStatement::Assertion(e, error),
+                Some(guard) => TypedStatement::Assertion(
+                    guarded(guard, e.into(), BooleanExpression::Value(true).into()).into(),
+                    error,
+                ),
+            }]),
+            TypedStatement::If(condition, consequence, alternative) => {
+                let mut res = vec![];
+
+                // both branch conditions are computed before any branch is executed, as the branches can modify `condition`
+                let with_condition = |c: BooleanExpression<'ast, T>| match &context.condition {
+                    None => c,
+                    Some(id) => {
+                        BooleanExpression::And(box BooleanExpression::identifier(id.clone()), box c)
+                    }
+                };
+
+                let consequence_condition =
+                    self.define(with_condition(condition.clone()), &mut res);
+                let alternative_condition = (!alternative.is_empty()).then(|| {
+                    self.define(
+                        with_condition(BooleanExpression::Not(box condition)),
+                        &mut res,
+                    )
+                });
+
+                res.extend(self.desugar_block(
+                    consequence,
+                    &Context {
+                        condition: Some(consequence_condition),
+                        ..context.clone()
+                    },
+                )?);
+
+                if let Some(alternative_condition) = alternative_condition {
+                    res.extend(self.desugar_block(
+                        alternative,
+                        &Context {
+                            condition: Some(alternative_condition),
+                            ..context.clone()
+                        },
+                    )?);
+                }
+
+                Ok(res)
+            }
+            // only one branch is kept during reduction, so both are executed under the guard of the enclosing block
+            TypedStatement::ComptimeIf(condition, consequence, alternative, metadata) => {
+                Ok(vec![TypedStatement::ComptimeIf(
+                    condition,
+                    self.desugar_block(consequence, context)?,
+                    self.desugar_block(alternative, context)?,
+                    metadata,
+                )])
+            }
+            TypedStatement::For(v, from, to, statements) => {
+                // loops without `break` or `continue` are executed under the guard of the enclosing block
+                if !contains(&statements, &TypedStatement::Break)
+                    && !contains(&statements, &TypedStatement::Continue)
+                {
+                    let statements = self.desugar_block(statements, context)?;
+                    return Ok(vec![TypedStatement::For(v, from, to, statements)]);
+                }
+
+                let mut res = vec![];
+                let (_, body) = self.desugar_loop_body(statements, context, None, &mut res)?;
+                res.push(TypedStatement::For(v, from, to, body));
+
+                Ok(res)
+            }
+            // `for T x in a { ... }` defines a variable for `a`, then loops over its indices. The definition of `a` is
+            // guarded like any other declaration, so that `a` is only evaluated if the loop is executed
+            TypedStatement::ForEach(v, array, statements) => {
+                let ty = array.ty().clone();
+                let array_variable =
+                    Variable::new(self.fresh_loop_variable(), Type::Array(ty.clone()), false);
+                let index = Variable::uint(self.fresh_loop_variable(), UBitwidth::B32);
+
+                let element = TypedExpression::select(
+                    ArrayExpression::identifier(array_variable.id.clone())
+                        .annotate(*ty.ty, *ty.size.clone()),
+                    UExpression::identifier(index.id.clone()).annotate(UBitwidth::B32),
+                );
+
+                let mut res = self.desugar_statement(
+                    TypedStatement::definition(array_variable.into(), array.into()),
+                    context,
+                )?;
+
+                res.extend(
+                    self.desugar_statement(
+                        TypedStatement::For(
+                            index,
+                            0u32.into(),
+                            *ty.size,
+                            std::iter::once(TypedStatement::definition(v.into(), element))
+                                .chain(statements)
+                                .collect(),
+                        ),
+                        context,
+                    )?,
+                );
+
+                Ok(res)
+            }
+            TypedStatement::While(condition, bound, statements, error) => {
+                let mut res = vec![];
+
+                let broken = self.define(false.into(), &mut res);
+
+                let (inner, body) =
+                    self.desugar_loop_body(statements, context, Some(broken.clone()), &mut res)?;
+
+                // the loop is exited as soon as its condition is false, which is only evaluated while the loop runs
+                let exit = TypedStatement::definition(
+                    Variable::mutable(broken.clone(), Type::Boolean).into(),
+                    guarded(
+                        inner.guard().unwrap(),
+                        BooleanExpression::Not(box condition.clone()).into(),
+                        BooleanExpression::identifier(broken.clone()).into(),
+                    ),
+                );
+
+                // the `continue` flag is reset before checking the condition
+                let body = match inner.continued {
+                    Some(_) => {
+                        let mut body = body;
+                        body.insert(1, exit);
+                        body
+                    }
+                    None => std::iter::once(exit).chain(body).collect(),
+                };
+
+                let index = Variable::uint(self.fresh_loop_variable(), UBitwidth::B32);
+                res.push(TypedStatement::For(index, 0u32.into(), bound, body));
+
+                // if the loop was entered and is still running, its condition must be false after the last iteration
+                let running = Context {
+                    condition: inner.condition,
+                    broken: Some(broken),
+                    continued: None,
+                    returned: context.returned.clone(),
+                };
+                res.push(TypedStatement::Assertion(
+                    guarded(
+                        running.guard().unwrap(),
+                        BooleanExpression::Not(box condition).into(),
+                        BooleanExpression::Value(true).into(),
+                    )
+                    .into(),
+                    error,
+                ));
+
+                Ok(res)
+            }
+            TypedStatement::Break => {
+                let broken = context.broken.clone().unwrap();
+                Ok(vec![set(broken, context.guard().unwrap())])
+            }
+            TypedStatement::Continue => {
+                let continued = context.continued.clone().unwrap();
+                Ok(vec![set(continued, context.guard().unwrap())])
+            }
+            TypedStatement::Return(e) => match (&context.returned, &self.return_value) {
+                (Some(returned), Some(value)) => {
+                    let guard = context.guard().unwrap();
+                    let assignee = TypedAssignee::Identifier(value.clone());
+                    let e = guarded(guard.clone(), e, current_value(assignee.clone()));
+                    Ok(vec![
+                        TypedStatement::definition(assignee, e),
+                        set(returned.clone(), guard),
+                    ])
+                }
+                _ => Ok(vec![TypedStatement::Return(e)]),
+            },
+            TypedStatement::Assembly(..) if context.is_conditional() => Err(Error(
+                "Assembly blocks are not supported in conditional blocks".into(),
+            )),
+            // logs are printed whenever the statement is reached during execution, so they cannot be guarded
+            TypedStatement::Log(..) if context.is_conditional() => {
+                Err(Error("Logs are not supported in conditional blocks".into()))
+            }
+            s => Ok(vec![s]),
+        }
+    }
+}
+
+impl<'ast, T: Field> ResultFolder<'ast, T> for ControlFlowDesugarer<'ast, T> {
+    type Error = Error;
+
+    fn fold_function(
+        &mut self,
+        f: TypedFunction<'ast, T>,
+    ) -> Result<TypedFunction<'ast, T>, Self::Error> {
+        self.defined = f
+            .arguments
+            .iter()
+            .map(|a| (a.id.id.clone(), None))
+            .collect();
+
+        let mut statements = vec![];
+
+        let context = if f.statements.iter().any(returns_early) {
+            let ty = try_from_g_type(*f.signature.output.clone()).unwrap();
+            let returned = self.define(false.into(), &mut statements);
+            let value = Variable::mutable(self.fresh(), ty);
+            statements.push(TypedStatement::definition(
+                value.clone().into(),
+                zero_value(&value._type),
+            ));
+            self.return_value = Some(value);
+
+            Context {
+                returned: Some(returned),
+                ..Context::default()
+            }
+        } else {
+            self.return_value = None;
+            Context::default()
+        };
+
+        statements.extend(self.desugar_block(f.statements, &context)?);
+
+        if let Some(value) = self.return_value.take() {
+            statements.push(TypedStatement::Return(value.into()));
+        }
+
+        Ok(TypedFunction { statements, ..f })
+    }
+}
+
+// `flag = flag || guard`
+fn set<'ast, T: Field>(
+    flag: Identifier<'ast>,
+    guard: BooleanExpression<'ast, T>,
+) -> TypedStatement<'ast, T> {
+    TypedStatement::definition(
+        Variable::mutable(flag.clone(), Type::Boolean).into(),
+        BooleanExpression::Or(box BooleanExpression::identifier(flag), box guard).into(),
+    )
+}
+
+// `if guard { e } else { alternative }`, where `e` is only evaluated if `guard` holds
+fn guarded<'ast, T: Field>(
+    guard: BooleanExpression<'ast, T>,
+    e: TypedExpression<'ast, T>,
+    alternative: TypedExpression<'ast, T>,
+) -> TypedExpression<'ast, T> {
+    fn isolated<
+        'ast,
+        T: Field,
+        E: Conditional<'ast, T> + Block<'ast, T> + From<TypedExpression<'ast, T>>,
+    >(
+        guard: BooleanExpression<'ast, T>,
+        e: E,
+        alternative: TypedExpression<'ast, T>,
+    ) -> E {
+        E::conditional(
+            guard,
+            E::block(vec![], e),
+            alternative.into(),
+            ConditionalKind::Guard,
+        )
+    }
+
+    match e {
+        TypedExpression::FieldElement(e) => isolated(guard, e, alternative).into(),
+        TypedExpression::Boolean(e) => isolated(guard, e, alternative).into(),
+        TypedExpression::Uint(e) => isolated(guard, e, alternative).into(),
+        TypedExpression::Array(e) => isolated(guard, e, alternative).into(),
+        TypedExpression::Struct(e) => isolated(guard, e, alternative).into(),
+        TypedExpression::Tuple(e) => isolated(guard, e, alternative).into(),
+        TypedExpression::Enum(e) => isolated(guard, e, alternative).into(),
+        TypedExpression::BoundedArray(e) => isolated(guard, e, alternative).into(),
+        TypedExpression::Int(_) => unreachable!("integer expressions cannot be assigned"),
+    }
+}
+
+// the current value of `assignee`
+fn current_value<'ast, T: Field>(assignee: TypedAssignee<'ast, T>) -> TypedExpression<'ast, T> {
+    match assignee.get_type() {
+        Type::FieldElement => FieldElementExpression::from(assignee).into(),
+        Type::Boolean => BooleanExpression::from(assignee).into(),
+        Type::Uint(_) => UExpression::from(assignee).into(),
+        Type::Array(_) => ArrayExpression::from(assignee).into(),
+        Type::Struct(_) => StructExpression::from(assignee).into(),
+        Type::Tuple(_) => TupleExpression::from(assignee).into(),
+        Type::Enum(_) => EnumExpression::from(assignee).into(),
+        Type::BoundedArray(_) => BoundedArrayExpression::from(assignee).into(),
+        Type::Int => unreachable!("integer expressions cannot be assigned"),
+    }
+}
+
+// whether `s` contains a `return` statement inside a nested block
+fn returns_early<T>(s: &TypedStatement<'_, T>) -> bool {
+    fn returns<T>(statements: &[TypedStatement<'_, T>]) -> bool {
+        statements.iter().any(|s| match s {
+            TypedStatement::Return(..) => true,
+            s => returns_early(s),
+        })
+    }
+
+    match s {
+        TypedStatement::If(_, consequence, alternative)
+        | TypedStatement::ComptimeIf(_, consequence, alternative, _) => {
+            returns(consequence) || returns(alternative)
+        }
+        TypedStatement::For(.., statements)
+        | TypedStatement::ForEach(.., statements)
+        | TypedStatement::While(_, _, statements, _) => returns(statements),
+        _ => false,
+    }
+}
+
+// whether `statement` appears in `statements`, outside of nested loops
+fn contains<'ast, T: PartialEq>(
+    statements: &[TypedStatement<'ast, T>],
+    statement: &TypedStatement<'ast, T>,
+) -> bool {
+    statements.iter().any(|s| match s {
+        TypedStatement::If(_, consequence, alternative)
+        | TypedStatement::ComptimeIf(_, consequence, alternative, _) => {
+            contains(consequence, statement) || contains(alternative, statement)
+        }
+        s => s == statement,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_ast::typed::{
+        DeclarationParameter, DeclarationSignature, DeclarationType, DeclarationVariable,
+    };
+    use zokrates_field::Bn128Field;
+
+    fn guard(i: usize) -> Identifier<'static> {
+        CoreIdentifier::Guard(i).into()
+    }
+
+    fn function(
+        statements: Vec<TypedStatement<'static, Bn128Field>>,
+    ) -> TypedFunction<'static, Bn128Field> {
+        // def main(field mut a, bool c)
+        TypedFunction {
+            arguments: vec![
+                DeclarationParameter {
+                    id: DeclarationVariable::new("a", DeclarationType::FieldElement, true),
+                    private: true,
+                },
+                DeclarationParameter {
+                    id: DeclarationVariable::new("c", DeclarationType::Boolean, false),
+                    private: true,
+                },
+            ],
+            statements,
+            signature: DeclarationSignature::new(),
+        }
+    }
+
+    #[test]
+    fn loop_without_control_flow() {
+        // for u32 i in 0..4 {
+        //   a = a + 1;
+        // }
+        // should be left unchanged
+
+        let f = function(vec![TypedStatement::For(
+            Variable::uint("i", UBitwidth::B32),
+            0u32.into(),
+            4u32.into(),
+            vec![TypedStatement::definition(
+                Variable::mutable("a", Type::FieldElement).into(),
+                (FieldElementExpression::identifier("a".into())
+                    + FieldElementExpression::Number(Bn128Field::from(1)))
+                .into(),
+            )],
+        )]);
+
+        assert_eq!(
+            ControlFlowDesugarer::default().fold_function(f.clone()),
+            Ok(f)
+        );
+    }
+
+    #[test]
+    fn break_in_for() {
+        // for u32 i in 0..4 {
+        //   if c { break; }
+        //   a = a + 1;
+        // }
+        // should become
+        // bool mut #GUARD_0 = false;
+        // for u32 i in 0..4 {
+        //   bool mut #GUARD_1 = c;
+        //   #GUARD_0 = #GUARD_0 || (#GUARD_1 && !#GUARD_0);
+        //   a = if !#GUARD_0 { a + 1 } else { a };
+        // }
+        // where the branches of the conditional are isolated
+
+        let a_plus_one = FieldElementExpression::identifier("a".into())
+            + FieldElementExpression::Number(Bn128Field::from(1));
+
+        let f = function(vec![TypedStatement::For(
+            Variable::uint("i", UBitwidth::B32),
+            0u32.into(),
+            4u32.into(),
+            vec![
+                TypedStatement::If(
+                    BooleanExpression::identifier("c".into()),
+                    vec![TypedStatement::Break],
+                    vec![],
+                ),
+                TypedStatement::definition(
+                    Variable::mutable("a", Type::FieldElement).into(),
+                    a_plus_one.clone().into(),
+                ),
+            ],
+        )]);
+
+        let expected = function(vec![
+            TypedStatement::definition(
+                Variable::mutable(guard(0), Type::Boolean).into(),
+                BooleanExpression::Value(false).into(),
+            ),
+            TypedStatement::For(
+                Variable::uint("i", UBitwidth::B32),
+                0u32.into(),
+                4u32.into(),
+                vec![
+                    TypedStatement::definition(
+                        Variable::mutable(guard(1), Type::Boolean).into(),
+                        BooleanExpression::identifier("c".into()).into(),
+                    ),
+                    TypedStatement::definition(
+                        Variable::mutable(guard(0), Type::Boolean).into(),
+                        BooleanExpression::Or(
+                            box BooleanExpression::identifier(guard(0)),
+                            box BooleanExpression::And(
+                                box BooleanExpression::identifier(guard(1)),
+                                box BooleanExpression::Not(box BooleanExpression::identifier(
+                                    guard(0),
+                                )),
+                            ),
+                        )
+                        .into(),
+                    ),
+                    TypedStatement::definition(
+                        Variable::mutable("a", Type::FieldElement).into(),
+                        FieldElementExpression::conditional(
+                            BooleanExpression::Not(box BooleanExpression::identifier(guard(0))),
+                            FieldElementExpression::block(vec![], a_plus_one),
+                            FieldElementExpression::identifier("a".into()),
+                            ConditionalKind::Guard,
+                        )
+                        .into(),
+                    ),
+                ],
+            ),
+        ]);
+
+        assert_eq!(
+            ControlFlowDesugarer::default().fold_function(f),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn log_in_if() {
+        // if c { log("a is {}", a); }
+        // should be rejected, as the log would be printed even if `c` is false
+
+        let f = function(vec![TypedStatement::If(
+            BooleanExpression::identifier("c".into()),
+            vec![TypedStatement::Log(
+                "a is {}".into(),
+                vec![FieldElementExpression::identifier("a".into()).into()],
+            )],
+            vec![],
+        )]);
+
+        assert_eq!(
+            ControlFlowDesugarer::default().fold_function(f),
+            Err(Error("Logs are not supported in conditional blocks".into()))
+        );
+    }
+
+    #[test]
+    fn early_return() {
+        // if c { return a; }
+        // return 0;
+        // should become
+        // bool mut #GUARD_0 = false;
+        // field mut #GUARD_1 = 0;
+        // bool mut #GUARD_2 = c;
+        // #GUARD_1 = if #GUARD_2 && !#GUARD_0 { a } else { #GUARD_1 };
+        // #GUARD_0 = #GUARD_0 || (#GUARD_2 && !#GUARD_0);
+        // #GUARD_1 = if !#GUARD_0 { 0 } else { #GUARD_1 };
+        // #GUARD_0 = #GUARD_0 || !#GUARD_0;
+        // return #GUARD_1;
+
+        let signature = DeclarationSignature::new().output(DeclarationType::FieldElement);
+
+        let f = TypedFunction {
+            signature: signature.clone(),
+            ..function(vec![
+                TypedStatement::If(
+                    BooleanExpression::identifier("c".into()),
+                    vec![TypedStatement::Return(
+                        FieldElementExpression::identifier("a".into()).into(),
+                    )],
+                    vec![],
+                ),
+                TypedStatement::Return(FieldElementExpression::Number(Bn128Field::from(0)).into()),
+            ])
+        };
+
+        let not_returned = BooleanExpression::Not(box BooleanExpression::identifier(guard(0)));
+        let in_consequence = BooleanExpression::And(
+            box BooleanExpression::identifier(guard(2)),
+            box not_returned.clone(),
+        );
+        let value = Variable::mutable(guard(1), Type::FieldElement);
+
+        let expected = TypedFunction {
+            signature,
+            ..function(vec![
+                TypedStatement::definition(
+                    Variable::mutable(guard(0), Type::Boolean).into(),
+                    BooleanExpression::Value(false).into(),
+                ),
+                TypedStatement::definition(
+                    value.clone().into(),
+                    FieldElementExpression::Number(Bn128Field::from(0)).into(),
+                ),
+                TypedStatement::definition(
+                    Variable::mutable(guard(2), Type::Boolean).into(),
+                    BooleanExpression::identifier("c".into()).into(),
+                ),
+                TypedStatement::definition(
+                    value.clone().into(),
+                    FieldElementExpression::conditional(
+                        in_consequence.clone(),
+                        FieldElementExpression::block(
+                            vec![],
+                            FieldElementExpression::identifier("a".into()),
+                        ),
+                        FieldElementExpression::identifier(guard(1)),
+                        ConditionalKind::Guard,
+                    )
+                    .into(),
+                ),
+                set(guard(0), in_consequence),
+                TypedStatement::definition(
+                    value.clone().into(),
+                    FieldElementExpression::conditional(
+                        not_returned.clone(),
+                        FieldElementExpression::block(
+                            vec![],
+                            FieldElementExpression::Number(Bn128Field::from(0)),
+                        ),
+                        FieldElementExpression::identifier(guard(1)),
+                        ConditionalKind::Guard,
+                    )
+                    .into(),
+                ),
+                set(guard(0), not_returned),
+                TypedStatement::Return(value.into()),
+            ])
+        };
+
+        assert_eq!(
+            ControlFlowDesugarer::default().fold_function(f),
+            Ok(expected)
+        );
+    }
+}
@@ -38,7 +38,7 @@ impl<'ast, T: Field> Folder<'ast, T> for DeadCodeEliminator<'ast> {
                     vec![]
                 }
             }
-            ZirStatement::IfElse(condition, consequence, alternative) => {
+            ZirStatement::IfElse(condition, consequence, alternative, isolated) => {
                 let condition = self.fold_boolean_expression(condition);
 
                 let mut consequence: Vec<_> = consequence
@@ -55,7 +55,12 @@ impl<'ast, T: Field> Folder<'ast, T> for DeadCodeEliminator<'ast> {
                     .collect();
                 alternative.reverse();
 
-                vec![ZirStatement::IfElse(condition, consequence, alternative)]
+                vec![ZirStatement::IfElse(
+                    condition,
+                    consequence,
+                    alternative,
+                    isolated,
+                )]
             }
             s => fold_statement(self, s),
         }
@@ -1,6 +1,8 @@
+use crate::reducer::instance_name;
 use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::marker::PhantomData;
+use zokrates_ast::common::SourceMetadata;
 use zokrates_ast::typed::types::{ConcreteArrayType, IntoType, UBitwidth};
 use zokrates_ast::typed::{self, Expr, Typed};
 use zokrates_ast::zir::IntoType as ZirIntoType;
@@ -9,6 +11,13 @@ use zokrates_field::Field;
 
 #[derive(Default)]
 pub struct Flattener<T: Field> {
+    // the index of the next intermediate variable introduced when lowering signed integer operations, bounded arrays
+    // and guarded branches
+    signed_index: usize,
+    // the position in the source of the statements being flattened, if known
+    metadata: Option<SourceMetadata>,
+    // the inlined calls we are in, outermost first, with the position of the statement they were called from
+    call_stack: Vec<(String, Option<SourceMetadata>)>,
     phantom: PhantomData<T>,
 }
 
@@ -55,6 +64,32 @@ fn flatten_identifier_rec<'ast>(
                 flatten_identifier_rec(zir::SourceIdentifier::Element(box id.clone(), i as u32), ty)
             })
             .collect(),
+        typed::types::ConcreteType::Enum(enum_ty) => std::iter::once(zir::Variable {
+            id: zir::Identifier::Source(enum_tag_identifier(id.clone())),
+            _type: zir::Type::uint(32),
+        })
+        .chain(
+            enum_ty
+                .layout()
+                .0
+                .into_iter()
+                .enumerate()
+                .flat_map(|(i, ty)| {
+                    flatten_identifier_rec(enum_slot_identifier(id.clone(), i), ty)
+                }),
+        )
+        .collect(),
+        typed::types::ConcreteType::BoundedArray(array_type) => std::iter::once(zir::Variable {
+            id: zir::Identifier::Source(bounded_array_len_identifier(id.clone())),
+            _type: zir::Type::uint(32),
+        })
+        .chain((0..*array_type.size).flat_map(|i| {
+            flatten_identifier_rec(
+                zir::SourceIdentifier::Select(box id.clone(), i),
+                &array_type.ty,
+            )
+        }))
+        .collect(),
     }
 }
 
@@ -103,6 +138,201 @@ fn flatten_identifier_to_expression_rec<'ast, T: Field>(
                 )
             })
             .collect(),
+        typed::types::ConcreteType::Enum(enum_ty) => std::iter::once(
+            zir::UExpression::identifier(zir::Identifier::Source(enum_tag_identifier(id.clone())))
+                .annotate(32)
+                .into(),
+        )
+        .chain(
+            enum_ty
+                .layout()
+                .0
+                .into_iter()
+                .enumerate()
+                .flat_map(|(i, ty)| {
+                    flatten_identifier_to_expression_rec(enum_slot_identifier(id.clone(), i), ty)
+                }),
+        )
+        .collect(),
+        typed::types::ConcreteType::BoundedArray(array_type) => std::iter::once(
+            zir::UExpression::identifier(zir::Identifier::Source(bounded_array_len_identifier(
+                id.clone(),
+            )))
+            .annotate(32)
+            .into(),
+        )
+        .chain((0..*array_type.size).flat_map(|i| {
+            flatten_identifier_to_expression_rec(
+                zir::SourceIdentifier::Select(box id.clone(), i),
+                &array_type.ty,
+            )
+        }))
+        .collect(),
+    }
+}
+
+// the tag of an enum value `id` is called `id.tag`, and its payload slots `id._0`, `id._1`...
+fn enum_tag_identifier(id: zir::SourceIdentifier) -> zir::SourceIdentifier {
+    zir::SourceIdentifier::Member(box id, "tag".into())
+}
+
+fn enum_slot_identifier(id: zir::SourceIdentifier, slot: usize) -> zir::SourceIdentifier {
+    zir::SourceIdentifier::Member(box id, format!("_{}", slot))
+}
+
+// the length of a bounded array `id` is called `id.len`, and its elements `id[0]`, `id[1]`...
+fn bounded_array_len_identifier(id: zir::SourceIdentifier) -> zir::SourceIdentifier {
+    zir::SourceIdentifier::Member(box id, "len".into())
+}
+
+// the range of each payload slot of an enum in its flattened values, after the tag
+fn enum_slot_ranges(enum_ty: &typed::types::ConcreteEnumType) -> Vec<std::ops::Range<usize>> {
+    enum_ty
+        .layout()
+        .0
+        .into_iter()
+        .scan(1, |offset, ty| {
+            let start = *offset;
+            *offset += ty.get_primitive_count();
+            Some(start..*offset)
+        })
+        .collect()
+}
+
+// the checks on a main argument of type `ty` flattened to `values`, so that enums hold a valid variant and
+// the payload slots their variant does not use are zero, and bounded arrays do not exceed their capacity and
+// are zero past their length
+fn argument_checks<'ast, T: Field>(
+    ty: &typed::types::ConcreteType,
+    values: &[zir::ZirExpression<'ast, T>],
+) -> Vec<(zir::BooleanExpression<'ast, T>, zir::RuntimeError)> {
+    match ty {
+        typed::ConcreteType::Int => unreachable!(),
+        typed::ConcreteType::FieldElement
+        | typed::ConcreteType::Boolean
+        | typed::ConcreteType::Uint(..) => vec![],
+        typed::ConcreteType::Array(array_ty) => values
+            .chunks(array_ty.ty.get_primitive_count().max(1))
+            .flat_map(|values| argument_checks(&array_ty.ty, values))
+            .collect(),
+        typed::ConcreteType::Struct(struct_ty) => struct_ty
+            .members
+            .iter()
+            .scan(0, |offset, member| {
+                let start = *offset;
+                *offset += member.ty.get_primitive_count();
+                Some(argument_checks(&member.ty, &values[start..*offset]))
+            })
+            .flatten()
+            .collect(),
+        typed::ConcreteType::Tuple(tuple_ty) => tuple_ty
+            .elements
+            .iter()
+            .scan(0, |offset, ty| {
+                let start = *offset;
+                *offset += ty.get_primitive_count();
+                Some(argument_checks(ty, &values[start..*offset]))
+            })
+            .flatten()
+            .collect(),
+        typed::ConcreteType::Enum(enum_ty) => {
+            let tag = zir::UExpression::try_from(values[0].clone()).unwrap();
+            let (slots, variants) = enum_ty.layout();
+            let ranges = enum_slot_ranges(enum_ty);
+
+            let in_range = zir::BooleanExpression::UintLt(
+                box tag.clone(),
+                box zir::UExpressionInner::Value(variants.len() as u128).annotate(32),
+            );
+
+            // the slots a variant does not use must be zero when the tag is this variant
+            let unused_slots = variants.iter().enumerate().flat_map(|(variant, used)| {
+                let tag = tag.clone();
+                ranges
+                    .iter()
+                    .enumerate()
+                    .filter(move |(slot, _)| !used.contains(slot))
+                    .flat_map(move |(_, range)| {
+                        let tag = tag.clone();
+                        values[range.clone()].iter().map(move |value| {
+                            zir::BooleanExpression::Or(
+                                box zir::BooleanExpression::Not(
+                                    box zir::BooleanExpression::UintEq(
+                                        box tag.clone(),
+                                        box zir::UExpressionInner::Value(variant as u128)
+                                            .annotate(32),
+                                    ),
+                                ),
+                                box is_zero(value.clone()),
+                            )
+                        })
+                    })
+            });
+
+            let nested = slots
+                .into_iter()
+                .zip(ranges.iter())
+                .flat_map(|(ty, range)| argument_checks(ty, &values[range.clone()]));
+
+            std::iter::once(in_range)
+                .chain(unused_slots)
+                .map(|check| (check, zir::RuntimeError::EnumArgument))
+                .chain(nested)
+                .collect()
+        }
+        typed::ConcreteType::BoundedArray(array_ty) => {
+            let len = zir::UExpression::try_from(values[0].clone()).unwrap();
+
+            let in_capacity = zir::BooleanExpression::UintLe(
+                box len.clone(),
+                box zir::UExpressionInner::Value(*array_ty.size as u128).annotate(32),
+            );
+
+            let slots: Vec<_> = values[1..]
+                .chunks(array_ty.ty.get_primitive_count().max(1))
+                .collect();
+
+            // the slots past the length must be zero
+            let padding = slots.iter().enumerate().flat_map(|(i, slot)| {
+                let len = len.clone();
+                slot.iter().map(move |value| {
+                    zir::BooleanExpression::Or(
+                        box zir::BooleanExpression::UintLt(
+                            box zir::UExpressionInner::Value(i as u128).annotate(32),
+                            box len.clone(),
+                        ),
+                        box is_zero(value.clone()),
+                    )
+                })
+            });
+
+            let nested = slots
+                .iter()
+                .flat_map(|slot| argument_checks(&array_ty.ty, slot));
+
+            std::iter::once(in_capacity)
+                .chain(padding)
+                .map(|check| (check, zir::RuntimeError::BoundedArrayArgument))
+                .chain(nested)
+                .collect()
+        }
+    }
+}
+
+fn is_zero<T: Field>(e: zir::ZirExpression<T>) -> zir::BooleanExpression<T> {
+    match e {
+        zir::ZirExpression::FieldElement(e) => zir::BooleanExpression::FieldEq(
+            box e,
+            box zir::FieldElementExpression::Number(T::zero()),
+        ),
+        zir::ZirExpression::Boolean(e) => zir::BooleanExpression::Not(box e),
+        zir::ZirExpression::Uint(e) => {
+            let bitwidth = e.bitwidth;
+            zir::BooleanExpression::UintEq(
+                box e,
+                box zir::UExpressionInner::Value(0).annotate(bitwidth),
+            )
+        }
     }
 }
 
@@ -174,6 +404,26 @@ impl<'ast, T: Field> Flatten<'ast, T> for typed::TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Flatten<'ast, T> for typed::EnumExpression<'ast, T> {
+    fn flatten(
+        self,
+        f: &mut Flattener<T>,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+    ) -> Vec<zir::ZirExpression<'ast, T>> {
+        f.fold_enum_expression(statements_buffer, self)
+    }
+}
+
+impl<'ast, T: Field> Flatten<'ast, T> for typed::BoundedArrayExpression<'ast, T> {
+    fn flatten(
+        self,
+        f: &mut Flattener<T>,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+    ) -> Vec<zir::ZirExpression<'ast, T>> {
+        f.fold_bounded_array_expression(statements_buffer, self)
+    }
+}
+
 impl<'ast, T: Field> Flattener<T> {
     pub fn flatten(p: typed::TypedProgram<T>) -> zir::ZirProgram<T> {
         let mut f = Flattener::default();
@@ -322,6 +572,10 @@ impl<'ast, T: Field> Flattener<T> {
             typed::TypedExpression::Array(e) => self.fold_array_expression(statements_buffer, e),
             typed::TypedExpression::Struct(e) => self.fold_struct_expression(statements_buffer, e),
             typed::TypedExpression::Tuple(e) => self.fold_tuple_expression(statements_buffer, e),
+            typed::TypedExpression::Enum(e) => self.fold_enum_expression(statements_buffer, e),
+            typed::TypedExpression::BoundedArray(e) => {
+                self.fold_bounded_array_expression(statements_buffer, e)
+            }
             typed::TypedExpression::Int(_) => unreachable!(),
         }
     }
@@ -358,6 +612,22 @@ impl<'ast, T: Field> Flattener<T> {
         fold_tuple_expression(self, statements_buffer, e)
     }
 
+    fn fold_enum_expression(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        e: typed::EnumExpression<'ast, T>,
+    ) -> Vec<zir::ZirExpression<'ast, T>> {
+        fold_enum_expression(self, statements_buffer, e)
+    }
+
+    fn fold_bounded_array_expression(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        e: typed::BoundedArrayExpression<'ast, T>,
+    ) -> Vec<zir::ZirExpression<'ast, T>> {
+        fold_bounded_array_expression(self, statements_buffer, e)
+    }
+
     fn fold_conditional_expression<E: Flatten<'ast, T>>(
         &mut self,
         statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
@@ -429,6 +699,131 @@ impl<'ast, T: Field> Flattener<T> {
         fold_uint_expression_inner(self, statements_buffer, bitwidth, e)
     }
 
+    // define `e` as an intermediate variable unless it is already an identifier or a value, so that it can be
+    // used many times without being duplicated
+    fn define_uint(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        e: zir::UExpression<'ast, T>,
+    ) -> zir::UExpression<'ast, T> {
+        match e.as_inner() {
+            zir::UExpressionInner::Identifier(_) | zir::UExpressionInner::Value(_) => e,
+            _ => {
+                let bitwidth = e.bitwidth;
+                let id = self.fresh_signed_identifier();
+                statements_buffer.push(zir::ZirStatement::Definition(
+                    zir::Variable::with_id_and_type(id.clone(), zir::Type::Uint(bitwidth)),
+                    e.into(),
+                ));
+                zir::UExpression::identifier(id).annotate(bitwidth)
+            }
+        }
+    }
+
+    // define `e` as an intermediate variable unless it is already an identifier or a value
+    fn define(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        e: zir::ZirExpression<'ast, T>,
+    ) -> zir::ZirExpression<'ast, T> {
+        match e {
+            zir::ZirExpression::Uint(e) => self.define_uint(statements_buffer, e).into(),
+            zir::ZirExpression::Boolean(
+                e @ (zir::BooleanExpression::Identifier(_) | zir::BooleanExpression::Value(_)),
+            ) => e.into(),
+            zir::ZirExpression::Boolean(e) => self.define_boolean(statements_buffer, e).into(),
+            zir::ZirExpression::FieldElement(
+                e @ (zir::FieldElementExpression::Identifier(_)
+                | zir::FieldElementExpression::Number(_)),
+            ) => e.into(),
+            zir::ZirExpression::FieldElement(e) => {
+                let id = self.fresh_signed_identifier();
+                statements_buffer.push(zir::ZirStatement::Definition(
+                    zir::Variable::with_id_and_type(id.clone(), zir::Type::FieldElement),
+                    e.into(),
+                ));
+                zir::FieldElementExpression::identifier(id).into()
+            }
+        }
+    }
+
+    fn define_boolean(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        e: zir::BooleanExpression<'ast, T>,
+    ) -> zir::BooleanExpression<'ast, T> {
+        let id = self.fresh_signed_identifier();
+        statements_buffer.push(zir::ZirStatement::Definition(
+            zir::Variable::with_id_and_type(id.clone(), zir::Type::Boolean),
+            e.into(),
+        ));
+        zir::BooleanExpression::identifier(id)
+    }
+
+    fn fresh_signed_identifier(&mut self) -> zir::Identifier<'ast> {
+        let id = zir::Identifier::Source(zir::SourceIdentifier::Basic(
+            typed::CoreIdentifier::Signed(self.signed_index).into(),
+        ));
+        self.signed_index += 1;
+        id
+    }
+
+    // truncated signed division: `|a| / |b|`, negated if the signs of `a` and `b` differ
+    fn signed_div(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        left: zir::UExpression<'ast, T>,
+        right: zir::UExpression<'ast, T>,
+    ) -> zir::UExpression<'ast, T> {
+        let (left_is_negative, left) = self.signed_abs(statements_buffer, left);
+        let (right_is_negative, right) = self.signed_abs(statements_buffer, right);
+
+        let quotient = self.define_uint(statements_buffer, left.div(right));
+
+        zir::Conditional::conditional(
+            zir::BooleanExpression::Not(box zir::BooleanExpression::BoolEq(
+                box left_is_negative,
+                box right_is_negative,
+            )),
+            negate(quotient.clone()),
+            quotient,
+        )
+    }
+
+    // truncated signed remainder: `|a| % |b|`, with the sign of `a`
+    fn signed_rem(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        left: zir::UExpression<'ast, T>,
+        right: zir::UExpression<'ast, T>,
+    ) -> zir::UExpression<'ast, T> {
+        let (left_is_negative, left) = self.signed_abs(statements_buffer, left);
+        let (_, right) = self.signed_abs(statements_buffer, right);
+
+        let remainder = self.define_uint(statements_buffer, left.rem(right));
+
+        zir::Conditional::conditional(left_is_negative, negate(remainder.clone()), remainder)
+    }
+
+    // returns whether `e` is negative when interpreted in two's complement, and its absolute value
+    fn signed_abs(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        e: zir::UExpression<'ast, T>,
+    ) -> (zir::BooleanExpression<'ast, T>, zir::UExpression<'ast, T>) {
+        let bitwidth = e.bitwidth;
+        let e = self.define_uint(statements_buffer, e);
+
+        let is_negative = self.define_boolean(
+            statements_buffer,
+            zir::BooleanExpression::UintLe(box sign_bit(bitwidth.to_usize()), box e.clone()),
+        );
+
+        let abs = zir::Conditional::conditional(is_negative.clone(), negate(e.clone()), e);
+
+        (is_negative, self.define_uint(statements_buffer, abs))
+    }
+
     fn fold_array_expression_inner(
         &mut self,
         statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
@@ -456,6 +851,39 @@ impl<'ast, T: Field> Flattener<T> {
     ) -> Vec<zir::ZirExpression<'ast, T>> {
         fold_tuple_expression_inner(self, statements_buffer, ty, e)
     }
+
+    fn fold_enum_expression_inner(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        ty: typed::types::ConcreteEnumType,
+        e: typed::EnumExpressionInner<'ast, T>,
+    ) -> Vec<zir::ZirExpression<'ast, T>> {
+        fold_enum_expression_inner(self, statements_buffer, ty, e)
+    }
+
+    fn fold_bounded_array_expression_inner(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        ty: ConcreteArrayType,
+        e: typed::BoundedArrayExpressionInner<'ast, T>,
+    ) -> Vec<zir::ZirExpression<'ast, T>> {
+        fold_bounded_array_expression_inner(self, statements_buffer, ty, e)
+    }
+
+    fn fold_variant_expression(
+        &mut self,
+        statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+        e: typed::VariantExpression<'ast, T>,
+    ) -> (
+        typed::types::ConcreteEnumType,
+        Vec<zir::ZirExpression<'ast, T>>,
+    ) {
+        let ty = typed::types::ConcreteEnumType::try_from(e.enumeration.ty().clone()).unwrap();
+        (
+            ty,
+            self.fold_enum_expression(statements_buffer, *e.enumeration),
+        )
+    }
 }
 
 // This finder looks for identifiers that were not defined in some block of statements
@@ -536,6 +964,8 @@ fn fold_assembly_statement<'ast, T: Field>(
             let mut statements_buffer: Vec<zir::ZirStatement<'ast, T>> = statements_buffer
                 .into_iter()
                 .rev()
+                // the function only computes a witness and does not produce constraints, so positions are not kept
+                .filter(|s| !matches!(s, zir::ZirStatement::SourceMetadata(..)))
                 .flat_map(|s| finder.fold_statement(s))
                 .collect();
             statements_buffer.reverse();
@@ -629,9 +1059,39 @@ fn fold_statement<'ast, T: Field>(
                 })
                 .collect(),
         )],
-        typed::TypedStatement::PushCallLog(..) => vec![],
-        typed::TypedStatement::PopCallLog => vec![],
-        typed::TypedStatement::For(..) => unreachable!(),
+        // the statements which follow are marked with this position and the calls which led to it
+        typed::TypedStatement::SourceMetadata(metadata) => {
+            let metadata = metadata.call_stack(
+                f.call_stack
+                    .iter()
+                    .map(|(function, _)| function.clone())
+                    .collect(),
+            );
+            f.metadata = Some(metadata.clone());
+            vec![zir::ZirStatement::SourceMetadata(Some(metadata))]
+        }
+        typed::TypedStatement::PushCallLog(key, generics) => {
+            f.call_stack
+                .push((instance_name(&key, &generics), f.metadata.clone()));
+            vec![]
+        }
+        // the statements which follow the call come from the caller again
+        typed::TypedStatement::PopCallLog => {
+            let (_, caller) = f.call_stack.pop().unwrap();
+            f.metadata = caller.clone();
+            caller
+                .map(|metadata| zir::ZirStatement::SourceMetadata(Some(metadata)))
+                .into_iter()
+                .collect()
+        }
+        typed::TypedStatement::For(..)
+        | typed::TypedStatement::ForEach(..)
+        | typed::TypedStatement::While(..)
+        | typed::TypedStatement::If(..)
+        | typed::TypedStatement::ComptimeIf(..)
+        | typed::TypedStatement::StaticAssertion(..)
+        | typed::TypedStatement::Break
+        | typed::TypedStatement::Continue => unreachable!(),
     };
 
     statements_buffer.extend(res);
@@ -701,9 +1161,22 @@ fn fold_array_expression_inner<'ast, T: Field>(
                 _ => unreachable!(),
             }
         }
+        typed::ArrayExpressionInner::Bytes(bytes) => bytes
+            .0
+            .into_iter()
+            .map(|b| {
+                zir::UExpressionInner::Value(b as u128)
+                    .annotate(zir::UBitwidth::B8)
+                    .into()
+            })
+            .collect(),
         typed::ArrayExpressionInner::Element(element) => {
             f.fold_element_expression(statements_buffer, element)
         }
+        typed::ArrayExpressionInner::Data(box array) => {
+            let mut array = f.fold_bounded_array_expression(statements_buffer, array);
+            array.split_off(1)
+        }
     }
 }
 
@@ -770,6 +1243,160 @@ fn fold_tuple_expression_inner<'ast, T: Field>(
         typed::TupleExpressionInner::Element(element) => {
             f.fold_element_expression(statements_buffer, element)
         }
+        typed::TupleExpressionInner::Payload(payload) => {
+            let variant = payload.variant as usize;
+            let (enum_ty, e) = f.fold_variant_expression(statements_buffer, payload);
+            let ranges = enum_slot_ranges(&enum_ty);
+
+            enum_ty.layout().1[variant]
+                .iter()
+                .flat_map(|slot| e[ranges[*slot].clone()].to_vec())
+                .collect()
+        }
+    }
+}
+
+fn fold_enum_expression_inner<'ast, T: Field>(
+    f: &mut Flattener<T>,
+    statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+    ty: typed::types::ConcreteEnumType,
+    enumeration: typed::EnumExpressionInner<'ast, T>,
+) -> Vec<zir::ZirExpression<'ast, T>> {
+    match enumeration {
+        typed::EnumExpressionInner::Block(block) => {
+            block
+                .statements
+                .into_iter()
+                .for_each(|s| f.fold_statement(statements_buffer, s));
+            f.fold_enum_expression(statements_buffer, *block.value)
+        }
+        typed::EnumExpressionInner::Identifier(id) => f.fold_identifier_expression(ty, id),
+        // the slots which the variant does not use are zero
+        typed::EnumExpressionInner::Value(variant, exprs) => {
+            let (slots, variants) = ty.layout();
+
+            let mut slots: Vec<_> = slots
+                .into_iter()
+                .map(|ty| {
+                    f.fold_expression(statements_buffer, typed::zero_value(&ty.clone().into()))
+                })
+                .collect();
+
+            for (slot, e) in variants[variant as usize].iter().zip(exprs) {
+                slots[*slot] = f.fold_expression(statements_buffer, e);
+            }
+
+            std::iter::once(
+                zir::UExpressionInner::Value(variant as u128)
+                    .annotate(32)
+                    .into(),
+            )
+            .chain(slots.into_iter().flatten())
+            .collect()
+        }
+        typed::EnumExpressionInner::FunctionCall(..) => unreachable!(),
+        typed::EnumExpressionInner::Conditional(c) => {
+            f.fold_conditional_expression(statements_buffer, c)
+        }
+        typed::EnumExpressionInner::Member(m) => f.fold_member_expression(statements_buffer, m),
+        typed::EnumExpressionInner::Select(select) => {
+            f.fold_select_expression(statements_buffer, select)
+        }
+        typed::EnumExpressionInner::Element(element) => {
+            f.fold_element_expression(statements_buffer, element)
+        }
+    }
+}
+
+fn fold_bounded_array_expression_inner<'ast, T: Field>(
+    f: &mut Flattener<T>,
+    statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+    ty: ConcreteArrayType,
+    array: typed::BoundedArrayExpressionInner<'ast, T>,
+) -> Vec<zir::ZirExpression<'ast, T>> {
+    let capacity = *ty.size;
+    let element_size = ty.ty.get_primitive_count();
+
+    match array {
+        typed::BoundedArrayExpressionInner::Block(block) => {
+            block
+                .statements
+                .into_iter()
+                .for_each(|s| f.fold_statement(statements_buffer, s));
+            f.fold_bounded_array_expression(statements_buffer, *block.value)
+        }
+        // the concrete type of a bounded array expression is that of its data, so the length is added here
+        typed::BoundedArrayExpressionInner::Identifier(id) => flatten_identifier_to_expression_rec(
+            f.fold_name(id.id),
+            &typed::types::ConcreteType::BoundedArray(ty),
+        ),
+        // the length is followed by the elements, and by zeroes up to the capacity
+        typed::BoundedArrayExpressionInner::Value(box array, _) => {
+            let size: u32 = array.size().try_into().unwrap();
+
+            // an excess is reported by semantic analysis, or by propagation when it depends on generics
+            assert!(size <= capacity);
+
+            let elements = f.fold_array_expression(statements_buffer, array);
+            let zero = f.fold_expression(statements_buffer, typed::zero_value(&(*ty.ty).into()));
+
+            std::iter::once(
+                zir::UExpressionInner::Value(size as u128)
+                    .annotate(32)
+                    .into(),
+            )
+            .chain(elements)
+            .chain((size..capacity).flat_map(|_| zero.clone()))
+            .collect()
+        }
+        // the element is written at the index given by the length, which must be within the capacity
+        typed::BoundedArrayExpressionInner::Push(box array, box e) => {
+            let mut array = f.fold_bounded_array_expression(statements_buffer, array);
+            let e = f.fold_expression(statements_buffer, e);
+
+            let data = array.split_off(1);
+            let len = zir::UExpression::try_from(array.pop().unwrap()).unwrap();
+            let len = f.define_uint(statements_buffer, len);
+
+            statements_buffer.push(zir::ZirStatement::Assertion(
+                zir::BooleanExpression::UintLt(
+                    box len.clone(),
+                    box zir::UExpressionInner::Value(capacity as u128).annotate(32),
+                ),
+                zir::RuntimeError::BoundedArrayCapacity,
+            ));
+
+            let elements = data
+                .chunks(element_size.max(1))
+                .enumerate()
+                .flat_map(|(i, slot)| {
+                    let is_last = zir::BooleanExpression::UintEq(
+                        box len.clone(),
+                        box zir::UExpressionInner::Value(i as u128).annotate(32),
+                    );
+                    slot.iter()
+                        .zip(e.iter())
+                        .map(move |(s, e)| conditional(is_last.clone(), e.clone(), s.clone()))
+                })
+                .collect::<Vec<_>>();
+
+            std::iter::once(len.add(zir::UExpressionInner::Value(1).annotate(32)).into())
+                .chain(elements)
+                .collect()
+        }
+        typed::BoundedArrayExpressionInner::FunctionCall(..) => unreachable!(),
+        typed::BoundedArrayExpressionInner::Conditional(c) => {
+            f.fold_conditional_expression(statements_buffer, c)
+        }
+        typed::BoundedArrayExpressionInner::Member(m) => {
+            f.fold_member_expression(statements_buffer, m)
+        }
+        typed::BoundedArrayExpressionInner::Select(select) => {
+            f.fold_select_expression(statements_buffer, select)
+        }
+        typed::BoundedArrayExpressionInner::Element(element) => {
+            f.fold_element_expression(statements_buffer, element)
+        }
     }
 }
 
@@ -853,13 +1480,39 @@ fn fold_select_expression<'ast, T: Field, E>(
     statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
     select: typed::SelectExpression<'ast, T, E>,
 ) -> Vec<zir::ZirExpression<'ast, T>> {
-    let size = typed::types::ConcreteType::try_from(*select.array.ty().clone().ty)
+    let array_ty = select.array.ty().clone();
+
+    let size = typed::types::ConcreteType::try_from(*array_ty.ty.clone())
         .unwrap()
         .get_primitive_count();
 
-    let array = f.fold_array_expression(statements_buffer, *select.array);
+    // selecting in the elements of a bounded array requires the index to be within its length
+    let (array, len) = match select.array.into_inner() {
+        typed::ArrayExpressionInner::Data(box array) => {
+            let mut array = f.fold_bounded_array_expression(statements_buffer, array);
+            let data = array.split_off(1);
+            (
+                data,
+                Some(zir::UExpression::try_from(array.pop().unwrap()).unwrap()),
+            )
+        }
+        array => (
+            f.fold_array_expression(
+                statements_buffer,
+                array.annotate(*array_ty.ty, *array_ty.size),
+            ),
+            None,
+        ),
+    };
     let index = f.fold_uint_expression(statements_buffer, *select.index);
 
+    if let Some(len) = len {
+        statements_buffer.push(zir::ZirStatement::Assertion(
+            zir::BooleanExpression::UintLt(box index.clone(), box len),
+            zir::RuntimeError::BoundedArrayAccess,
+        ));
+    }
+
     match index.as_inner() {
         zir::UExpressionInner::Value(v) => {
             let v = *v as usize;
@@ -930,40 +1583,66 @@ fn fold_conditional_expression<'ast, T: Field, E: Flatten<'ast, T>>(
     let mut consequence_statements = vec![];
     let mut alternative_statements = vec![];
 
+    let isolated = c.kind == typed::ConditionalKind::Guard;
+
     let condition = f.fold_boolean_expression(statements_buffer, *c.condition);
     let consequence = c.consequence.flatten(f, &mut consequence_statements);
     let alternative = c.alternative.flatten(f, &mut alternative_statements);
 
     assert_eq!(consequence.len(), alternative.len());
 
+    // the values of guarded branches are computed inside them, so that they can only panic if their branch is taken
+    let (consequence, alternative): (Vec<_>, Vec<_>) = match isolated {
+        true => (
+            consequence
+                .into_iter()
+                .map(|e| f.define(&mut consequence_statements, e))
+                .collect(),
+            alternative
+                .into_iter()
+                .map(|e| f.define(&mut alternative_statements, e))
+                .collect(),
+        ),
+        false => (consequence, alternative),
+    };
+
     if !consequence_statements.is_empty() || !alternative_statements.is_empty() {
         statements_buffer.push(zir::ZirStatement::IfElse(
             condition.clone(),
             consequence_statements,
             alternative_statements,
+            isolated,
         ));
     }
 
-    use zokrates_ast::zir::Conditional;
-
     consequence
         .into_iter()
         .zip(alternative.into_iter())
-        .map(|(c, a)| match (c, a) {
-            (zir::ZirExpression::FieldElement(c), zir::ZirExpression::FieldElement(a)) => {
-                zir::FieldElementExpression::conditional(condition.clone(), c, a).into()
-            }
-            (zir::ZirExpression::Boolean(c), zir::ZirExpression::Boolean(a)) => {
-                zir::BooleanExpression::conditional(condition.clone(), c, a).into()
-            }
-            (zir::ZirExpression::Uint(c), zir::ZirExpression::Uint(a)) => {
-                zir::UExpression::conditional(condition.clone(), c, a).into()
-            }
-            _ => unreachable!(),
-        })
+        .map(|(c, a)| conditional(condition.clone(), c, a))
         .collect()
 }
 
+fn conditional<'ast, T: Field>(
+    condition: zir::BooleanExpression<'ast, T>,
+    consequence: zir::ZirExpression<'ast, T>,
+    alternative: zir::ZirExpression<'ast, T>,
+) -> zir::ZirExpression<'ast, T> {
+    use zokrates_ast::zir::Conditional;
+
+    match (consequence, alternative) {
+        (zir::ZirExpression::FieldElement(c), zir::ZirExpression::FieldElement(a)) => {
+            zir::FieldElementExpression::conditional(condition, c, a).into()
+        }
+        (zir::ZirExpression::Boolean(c), zir::ZirExpression::Boolean(a)) => {
+            zir::BooleanExpression::conditional(condition, c, a).into()
+        }
+        (zir::ZirExpression::Uint(c), zir::ZirExpression::Uint(a)) => {
+            zir::UExpression::conditional(condition, c, a).into()
+        }
+        _ => unreachable!(),
+    }
+}
+
 fn fold_identifier_expression<'ast, T: Field, E: Expr<'ast, T>>(
     f: &mut Flattener<T>,
     ty: E::ConcreteTy,
@@ -1150,6 +1829,16 @@ fn fold_boolean_expression<'ast, T: Field>(
         typed::BooleanExpression::ArrayEq(e) => f.fold_eq_expression(statements_buffer, e),
         typed::BooleanExpression::StructEq(e) => f.fold_eq_expression(statements_buffer, e),
         typed::BooleanExpression::TupleEq(e) => f.fold_eq_expression(statements_buffer, e),
+        typed::BooleanExpression::EnumEq(e) => f.fold_eq_expression(statements_buffer, e),
+        typed::BooleanExpression::IsVariant(v) => {
+            let variant = v.variant;
+            let (_, e) = f.fold_variant_expression(statements_buffer, v);
+            let tag = zir::UExpression::try_from(e[0].clone()).unwrap();
+            zir::BooleanExpression::UintEq(
+                box tag,
+                box zir::UExpressionInner::Value(variant as u128).annotate(32),
+            )
+        }
         typed::BooleanExpression::UintEq(e) => f.fold_eq_expression(statements_buffer, e),
         typed::BooleanExpression::FieldLt(box e1, box e2) => {
             let e1 = f.fold_field_expression(statements_buffer, e1);
@@ -1172,23 +1861,31 @@ fn fold_boolean_expression<'ast, T: Field>(
             zir::BooleanExpression::FieldLe(box e2, box e1)
         }
         typed::BooleanExpression::UintLt(box e1, box e2) => {
+            let signed = e1.bitwidth().is_signed();
             let e1 = f.fold_uint_expression(statements_buffer, e1);
             let e2 = f.fold_uint_expression(statements_buffer, e2);
+            let (e1, e2) = bias_signed_operands(signed, e1, e2);
             zir::BooleanExpression::UintLt(box e1, box e2)
         }
         typed::BooleanExpression::UintLe(box e1, box e2) => {
+            let signed = e1.bitwidth().is_signed();
             let e1 = f.fold_uint_expression(statements_buffer, e1);
             let e2 = f.fold_uint_expression(statements_buffer, e2);
+            let (e1, e2) = bias_signed_operands(signed, e1, e2);
             zir::BooleanExpression::UintLe(box e1, box e2)
         }
         typed::BooleanExpression::UintGt(box e1, box e2) => {
+            let signed = e1.bitwidth().is_signed();
             let e1 = f.fold_uint_expression(statements_buffer, e1);
             let e2 = f.fold_uint_expression(statements_buffer, e2);
+            let (e1, e2) = bias_signed_operands(signed, e1, e2);
             zir::BooleanExpression::UintLt(box e2, box e1)
         }
         typed::BooleanExpression::UintGe(box e1, box e2) => {
+            let signed = e1.bitwidth().is_signed();
             let e1 = f.fold_uint_expression(statements_buffer, e1);
             let e2 = f.fold_uint_expression(statements_buffer, e2);
+            let (e1, e2) = bias_signed_operands(signed, e1, e2);
             zir::BooleanExpression::UintLe(box e2, box e1)
         }
         typed::BooleanExpression::Or(box e1, box e2) => {
@@ -1258,6 +1955,12 @@ fn fold_uint_expression_inner<'ast, T: Field>(
                 .into_inner()
         }
         typed::UExpressionInner::Value(v) => zir::UExpressionInner::Value(v),
+        typed::UExpressionInner::Len(box array) => zir::UExpression::try_from(
+            f.fold_bounded_array_expression(statements_buffer, array)
+                .swap_remove(0),
+        )
+        .unwrap()
+        .into_inner(),
         typed::UExpressionInner::Identifier(id) => {
             zir::UExpression::try_from(f.fold_identifier_expression(bitwidth, id).pop().unwrap())
                 .unwrap()
@@ -1286,13 +1989,19 @@ fn fold_uint_expression_inner<'ast, T: Field>(
             let left = f.fold_uint_expression(statements_buffer, left);
             let right = f.fold_uint_expression(statements_buffer, right);
 
-            zir::UExpressionInner::Div(box left, box right)
+            match bitwidth.is_signed() {
+                true => f.signed_div(statements_buffer, left, right).into_inner(),
+                false => zir::UExpressionInner::Div(box left, box right),
+            }
         }
         typed::UExpressionInner::Rem(box left, box right) => {
             let left = f.fold_uint_expression(statements_buffer, left);
             let right = f.fold_uint_expression(statements_buffer, right);
 
-            zir::UExpressionInner::Rem(box left, box right)
+            match bitwidth.is_signed() {
+                true => f.signed_rem(statements_buffer, left, right).into_inner(),
+                false => zir::UExpressionInner::Rem(box left, box right),
+            }
         }
         typed::UExpressionInner::Xor(box left, box right) => {
             let left = f.fold_uint_expression(statements_buffer, left);
@@ -1330,7 +2039,10 @@ fn fold_uint_expression_inner<'ast, T: Field>(
                 _ => unreachable!("static analysis should have made sure that this is constant"),
             };
 
-            zir::UExpressionInner::RightShift(box e, *by as u32)
+            match bitwidth.is_signed() {
+                true => signed_right_shift(e, *by as u32).into_inner(),
+                false => zir::UExpressionInner::RightShift(box e, *by as u32),
+            }
         }
         typed::UExpressionInner::Not(box e) => {
             let e = f.fold_uint_expression(statements_buffer, e);
@@ -1386,6 +2098,40 @@ fn fold_uint_expression_inner<'ast, T: Field>(
     }
 }
 
+// the value of the sign bit of an integer of `bitwidth` bits
+fn sign_bit<'ast, T: Field>(bitwidth: usize) -> zir::UExpression<'ast, T> {
+    zir::UExpressionInner::Value(1 << (bitwidth - 1)).annotate(bitwidth)
+}
+
+fn negate<T: Field>(e: zir::UExpression<T>) -> zir::UExpression<T> {
+    let bitwidth = e.bitwidth;
+    zir::UExpressionInner::Value(0).annotate(bitwidth).sub(e)
+}
+
+// flipping the sign bit of both operands maps two's complement ordering to unsigned ordering
+fn bias_signed_operands<'ast, T: Field>(
+    signed: bool,
+    e1: zir::UExpression<'ast, T>,
+    e2: zir::UExpression<'ast, T>,
+) -> (zir::UExpression<'ast, T>, zir::UExpression<'ast, T>) {
+    match signed {
+        true => {
+            let bitwidth = e1.bitwidth.to_usize();
+            (e1.xor(sign_bit(bitwidth)), e2.xor(sign_bit(bitwidth)))
+        }
+        false => (e1, e2),
+    }
+}
+
+// arithmetic right shift: shift logically, then sign extend using `(x ^ m) - m` where `m` is the shifted sign bit
+fn signed_right_shift<T: Field>(e: zir::UExpression<T>, by: u32) -> zir::UExpression<T> {
+    let bitwidth = e.bitwidth.to_usize();
+    let by = by.min(bitwidth as u32 - 1);
+    let mask = zir::UExpressionInner::Value(1 << (bitwidth as u32 - 1 - by)).annotate(bitwidth);
+
+    e.right_shift(by).xor(mask.clone()).sub(mask)
+}
+
 fn fold_function<'ast, T: Field>(
     f: &mut Flattener<T>,
     fun: typed::TypedFunction<'ast, T>,
@@ -1453,6 +2199,30 @@ fn fold_tuple_expression<'ast, T: Field>(
     )
 }
 
+fn fold_enum_expression<'ast, T: Field>(
+    f: &mut Flattener<T>,
+    statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+    e: typed::EnumExpression<'ast, T>,
+) -> Vec<zir::ZirExpression<'ast, T>> {
+    f.fold_enum_expression_inner(
+        statements_buffer,
+        typed::types::ConcreteEnumType::try_from(e.ty().clone()).unwrap(),
+        e.into_inner(),
+    )
+}
+
+fn fold_bounded_array_expression<'ast, T: Field>(
+    f: &mut Flattener<T>,
+    statements_buffer: &mut Vec<zir::ZirStatement<'ast, T>>,
+    e: typed::BoundedArrayExpression<'ast, T>,
+) -> Vec<zir::ZirExpression<'ast, T>> {
+    f.fold_bounded_array_expression_inner(
+        statements_buffer,
+        ConcreteArrayType::try_from(e.ty().clone()).unwrap(),
+        e.into_inner(),
+    )
+}
+
 fn fold_program<'ast, T: Field>(
     f: &mut Flattener<T>,
     mut p: typed::TypedProgram<'ast, T>,
@@ -1469,7 +2239,22 @@ fn fold_program<'ast, T: Field>(
         _ => unreachable!(),
     };
 
-    zir::ZirProgram {
-        main: f.fold_function(main_function),
-    }
+    // the arguments of main come from the prover, so enums and bounded arrays are checked to hold valid values
+    let argument_checks: Vec<_> = main_function
+        .arguments
+        .iter()
+        .flat_map(|a| {
+            let v: typed::Variable<T> =
+                zokrates_ast::typed::variable::try_from_g_variable(a.id.clone()).unwrap();
+            let ty = typed::types::ConcreteType::try_from(v.get_type()).unwrap();
+            let values = flatten_identifier_to_expression_rec(f.fold_name(v.id), &ty);
+            argument_checks(&ty, &values)
+        })
+        .map(|(check, error)| zir::ZirStatement::Assertion(check, error))
+        .collect();
+
+    let mut main = f.fold_function(main_function);
+    main.statements.splice(0..0, argument_checks);
+
+    zir::ZirProgram { main }
 }
@@ -9,9 +9,11 @@
 mod assembly_transformer;
 mod boolean_array_comparator;
 mod branch_isolator;
+mod common_subexpression;
 mod condition_redefiner;
 mod constant_argument_checker;
 mod constant_resolver;
+mod control_flow_desugarer;
 mod dead_code;
 mod expression_validator;
 mod flat_propagation;
@@ -28,6 +30,7 @@ mod zir_propagation;
 
 use self::boolean_array_comparator::BooleanArrayComparator;
 use self::branch_isolator::Isolator;
+use self::common_subexpression::CommonSubexpressionEliminator;
 use self::condition_redefiner::ConditionRedefiner;
 use self::constant_argument_checker::ConstantArgumentChecker;
 use self::flatten_complex_types::Flattener;
@@ -40,11 +43,13 @@ use self::uint_optimizer::UintOptimizer;
 use self::variable_write_remover::VariableWriteRemover;
 use crate::assembly_transformer::AssemblyTransformer;
 use crate::constant_resolver::ConstantResolver;
+use crate::control_flow_desugarer::ControlFlowDesugarer;
 use crate::dead_code::DeadCodeEliminator;
 use crate::expression_validator::ExpressionValidator;
 use crate::panic_extractor::PanicExtractor;
 pub use crate::zir_propagation::ZirPropagator;
 use std::fmt;
+use zokrates_ast::common::Warnings;
 use zokrates_ast::typed::{abi::Abi, TypedProgram};
 use zokrates_ast::zir::ZirProgram;
 use zokrates_common::CompileConfig;
@@ -60,6 +65,7 @@ pub enum Error {
     Assembly(self::assembly_transformer::Error),
     VariableIndex(self::variable_write_remover::Error),
     InvalidExpression(self::expression_validator::Error),
+    ControlFlow(self::control_flow_desugarer::Error),
 }
 
 impl From<reducer::Error> for Error {
@@ -110,6 +116,25 @@ impl From<expression_validator::Error> for Error {
     }
 }
 
+impl From<control_flow_desugarer::Error> for Error {
+    fn from(e: control_flow_desugarer::Error) -> Self {
+        Error::ControlFlow(e)
+    }
+}
+
+impl Error {
+    /// Whether this error is an assertion which fails whatever the inputs of the program
+    pub fn is_assertion_failure(&self) -> bool {
+        matches!(
+            self,
+            Error::Reducer(reducer::Error::Propagation(
+                propagation::Error::AssertionFailed(..)
+            )) | Error::Propagation(propagation::Error::AssertionFailed(..))
+                | Error::ZirPropagation(zir_propagation::Error::AssertionFailed(..))
+        )
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -121,6 +146,7 @@ impl fmt::Display for Error {
             Error::Assembly(e) => write!(f, "{}", e),
             Error::VariableIndex(e) => write!(f, "{}", e),
             Error::InvalidExpression(e) => write!(f, "{}", e),
+            Error::ControlFlow(e) => write!(f, "{}", e),
         }
     }
 }
@@ -128,12 +154,18 @@ impl fmt::Display for Error {
 pub fn analyse<'ast, T: Field>(
     p: TypedProgram<'ast, T>,
     config: &CompileConfig,
+    warnings: &mut Warnings,
 ) -> Result<(ZirProgram<'ast, T>, Abi), Error> {
     // inline user-defined constants
     log::debug!("Static analyser: Inline constants");
     let r = ConstantResolver::inline(p);
     log::trace!("\n{}", r);
 
+    // desugar control flow statements
+    log::debug!("Static analyser: Desugar control flow");
+    let r = ControlFlowDesugarer::desugar(r).map_err(Error::from)?;
+    log::trace!("\n{}", r);
+
     // isolate branches
     let r = if config.isolate_branches {
         log::debug!("Static analyser: Isolate branches");
@@ -158,7 +190,7 @@ pub fn analyse<'ast, T: Field>(
 
     // reduce the program to a single function
     log::debug!("Static analyser: Reduce program");
-    let r = reduce_program(r).map_err(Error::from)?;
+    let r = reduce_program(r, config.max_recursion_depth, warnings).map_err(Error::from)?;
     log::trace!("\n{}", r);
 
     log::debug!("Static analyser: Propagate");
@@ -217,6 +249,17 @@ pub fn analyse<'ast, T: Field>(
     let zir = ZirPropagator::propagate(zir).map_err(Error::from)?;
     log::trace!("\n{}", zir);
 
+    // reuse the values of expressions which were already defined, unless optimization is disabled
+    let zir = if config.opt_level >= 1 {
+        log::debug!("Static analyser: Eliminate common subexpressions");
+        let zir = CommonSubexpressionEliminator::eliminate(zir);
+        log::trace!("\n{}", zir);
+        zir
+    } else {
+        log::debug!("Static analyser: Common subexpression elimination skipped");
+        zir
+    };
+
     log::debug!("Static analyser: Extract panics");
     let zir = PanicExtractor::extract(zir);
     log::trace!("\n{}", zir);
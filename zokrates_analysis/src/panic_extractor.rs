@@ -20,7 +20,7 @@ impl<'ast, T: Field> PanicExtractor<'ast, T> {
 impl<'ast, T: Field> Folder<'ast, T> for PanicExtractor<'ast, T> {
     fn fold_statement(&mut self, s: ZirStatement<'ast, T>) -> Vec<ZirStatement<'ast, T>> {
         match s {
-            ZirStatement::IfElse(condition, consequence, alternative) => {
+            ZirStatement::IfElse(condition, consequence, alternative, isolated) => {
                 let condition = self.fold_boolean_expression(condition);
                 let mut consequence_extractor = Self::default();
                 let consequence = consequence
@@ -41,6 +41,7 @@ impl<'ast, T: Field> Folder<'ast, T> for PanicExtractor<'ast, T> {
                         condition,
                         consequence,
                         alternative,
+                        isolated,
                     )))
                     .collect()
             }
@@ -93,6 +94,7 @@ impl<'ast, T: Field> Folder<'ast, T> for PanicExtractor<'ast, T> {
                 condition.clone(),
                 consequence_panics,
                 alternative_panics,
+                false,
             ));
         }
 
@@ -9,11 +9,12 @@
 
 use num::traits::Pow;
 use num_bigint::BigUint;
+use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::convert::{TryFrom, TryInto};
+use std::convert::TryFrom;
 use std::fmt;
 use std::ops::{BitAnd, BitOr, BitXor, Shl, Shr, Sub};
-use zokrates_ast::common::FlatEmbed;
+use zokrates_ast::common::{FlatEmbed, Lint, SourceMetadata, Warning, Warnings};
 use zokrates_ast::typed::result_folder::*;
 use zokrates_ast::typed::types::Type;
 use zokrates_ast::typed::*;
@@ -21,12 +22,13 @@ use zokrates_field::Field;
 
 pub type Constants<'ast, T> = HashMap<Identifier<'ast>, TypedExpression<'ast, T>>;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     Type(String),
     AssertionFailed(RuntimeError),
     InvalidValue(String),
     OutOfBounds(u128, u128),
+    BoundedArrayCapacity(u128, u128, SourceMetadata),
 }
 
 impl fmt::Display for Error {
@@ -40,29 +42,56 @@ impl fmt::Display for Error {
                 "Out of bounds index ({} >= {}) found during static analysis",
                 index, size
             ),
+            Error::BoundedArrayCapacity(size, capacity, metadata) => write!(
+                f,
+                "Cannot assign an array of size {} to a bounded array of capacity {} at {}",
+                size,
+                capacity,
+                metadata.location()
+            ),
         }
     }
 }
 
+// compare two constant integers of the same bitwidth, taking signedness into account
+fn compare_uint(bitwidth: UBitwidth, v1: u128, v2: u128) -> Ordering {
+    match bitwidth.is_signed() {
+        true => bitwidth.signed_value(v1).cmp(&bitwidth.signed_value(v2)),
+        false => v1.cmp(&v2),
+    }
+}
+
 #[derive(Debug)]
 pub struct Propagator<'ast, 'a, T: Field> {
     // constants keeps track of constant expressions
     // we currently do not support partially constant expressions: `field [x, 1][1]` is not considered constant, `field [0, 1][1]` is
     constants: &'a mut Constants<'ast, T>,
+    // warnings keeps track of the constraints which always hold, if any
+    warnings: Option<&'a mut Warnings>,
 }
 
 impl<'ast, 'a, T: Field> Propagator<'ast, 'a, T> {
     pub fn with_constants(constants: &'a mut Constants<'ast, T>) -> Self {
-        Propagator { constants }
+        Propagator {
+            constants,
+            warnings: None,
+        }
+    }
+
+    pub fn with_constants_and_warnings(
+        constants: &'a mut Constants<'ast, T>,
+        warnings: &'a mut Warnings,
+    ) -> Self {
+        Propagator {
+            constants,
+            warnings: Some(warnings),
+        }
     }
 
     pub fn propagate(p: TypedProgram<'ast, T>) -> Result<TypedProgram<'ast, T>, Error> {
         let mut constants = Constants::new();
 
-        Propagator {
-            constants: &mut constants,
-        }
-        .fold_program(p)
+        Propagator::with_constants(&mut constants).fold_program(p)
     }
 
     // get a mutable reference to the constant corresponding to a given assignee if any, otherwise
@@ -183,26 +212,24 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
         _: &E::Ty,
         e: ConditionalExpression<'ast, T, E>,
     ) -> Result<ConditionalOrExpression<'ast, T, E>, Self::Error> {
-        Ok(
-            match (
-                self.fold_boolean_expression(*e.condition)?,
-                e.consequence.fold(self)?,
-                e.alternative.fold(self)?,
-            ) {
-                (BooleanExpression::Value(true), consequence, _) => {
-                    ConditionalOrExpression::Expression(consequence.into_inner())
-                }
-                (BooleanExpression::Value(false), _, alternative) => {
-                    ConditionalOrExpression::Expression(alternative.into_inner())
-                }
-                (_, consequence, alternative) if consequence == alternative => {
+        // only the branch which is taken is folded when the condition is known, so that the other one
+        // can be invalid for this instance, for example the recursive case of a function called on its base case
+        Ok(match self.fold_boolean_expression(*e.condition)? {
+            BooleanExpression::Value(true) => {
+                ConditionalOrExpression::Expression(e.consequence.fold(self)?.into_inner())
+            }
+            BooleanExpression::Value(false) => {
+                ConditionalOrExpression::Expression(e.alternative.fold(self)?.into_inner())
+            }
+            condition => match (e.consequence.fold(self)?, e.alternative.fold(self)?) {
+                (consequence, alternative) if consequence == alternative => {
                     ConditionalOrExpression::Expression(consequence.into_inner())
                 }
-                (condition, consequence, alternative) => ConditionalOrExpression::Conditional(
+                (consequence, alternative) => ConditionalOrExpression::Conditional(
                     ConditionalExpression::new(condition, consequence, alternative, e.kind),
                 ),
             },
-        )
+        })
     }
 
     fn fold_assembly_statement(
@@ -267,7 +294,20 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 let is_equal = self.fold_boolean_expression(is_equal)?;
 
                 match is_equal {
-                    BooleanExpression::Value(true) => Ok(vec![]),
+                    BooleanExpression::Value(true) => {
+                        if let Some(warnings) = self.warnings.as_mut() {
+                            warnings.push(Warning::new(
+                                Lint::ConstantConditions,
+                                &metadata.file,
+                                metadata.position,
+                                format!(
+                                    "Constraint always holds: both sides evaluate to `{}`",
+                                    left
+                                ),
+                            ));
+                        }
+                        Ok(vec![])
+                    }
                     BooleanExpression::Value(false) => {
                         Err(Error::AssertionFailed(RuntimeError::SourceAssertion(
                             metadata
@@ -288,9 +328,32 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
     ) -> Result<Vec<TypedStatement<'ast, T>>, Error> {
         match s {
             TypedStatement::Assembly(statements) => {
+                // the constraint introduced by `<==` directly follows the assignment it constrains, and holds
+                // trivially when the value assigned is constant, so we do not warn about it
+                let implied: Vec<_> = std::iter::once(false)
+                    .chain(statements.windows(2).map(|w| match (&w[0], &w[1]) {
+                        (
+                            TypedAssemblyStatement::Assignment(assignee, expr),
+                            TypedAssemblyStatement::Constraint(left, right, _),
+                        ) => {
+                            *left == FieldElementExpression::from(assignee.clone())
+                                && *expr == TypedExpression::from(right.clone())
+                        }
+                        _ => false,
+                    }))
+                    .collect();
+
                 let statements: Vec<_> = statements
                     .into_iter()
-                    .map(|s| self.fold_assembly_statement(s))
+                    .zip(implied)
+                    .map(|(s, implied)| {
+                        let warnings = if implied { self.warnings.take() } else { None };
+                        let res = self.fold_assembly_statement(s);
+                        if implied {
+                            self.warnings = warnings;
+                        }
+                        res
+                    })
                     .collect::<Result<Vec<_>, _>>()?
                     .into_iter()
                     .flatten()
@@ -365,6 +428,17 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
 
                 Ok(vec![TypedStatement::For(v, from, to, statements)])
             }
+            // we do not visit the branches either, as only one of them is kept
+            TypedStatement::ComptimeIf(condition, consequence, alternative, metadata) => {
+                let condition = self.fold_boolean_expression(condition)?;
+
+                Ok(vec![TypedStatement::ComptimeIf(
+                    condition,
+                    consequence,
+                    alternative,
+                    metadata,
+                )])
+            }
             TypedStatement::Definition(assignee, DefinitionRhs::EmbedCall(embed_call)) => {
                 let assignee = self.fold_assignee(assignee)?;
                 let embed_call = self.fold_embed_call(embed_call)?;
@@ -396,11 +470,7 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                             .enumerate()
                             .fold(0, |acc, (i, v)| {
                                 if v {
-                                    acc + 2u128.pow(
-                                        (bitwidth.to_usize() - i - 1)
-                                            .try_into()
-                                            .unwrap(),
-                                    )
+                                    acc + (1 << (bitwidth.to_usize() - i - 1))
                                 } else {
                                     acc
                                 }
@@ -426,9 +496,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                             let mut num = v;
                             let mut res = vec![];
 
-                            for i in (0..bitwidth as u32).rev() {
-                                if 2u128.pow(i) <= num {
-                                    num -= 2u128.pow(i);
+                            for i in (0..bitwidth.to_usize() as u32).rev() {
+                                if 1 << i <= num {
+                                    num -= 1 << i;
                                     res.push(true);
                                 } else {
                                     res.push(false);
@@ -473,6 +543,10 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                                     _ => Ok(None),
                                 }
                             }
+                            FlatEmbed::U128FromBits => Ok(Some(process_u_from_bits(
+                                &embed_call.arguments,
+                                UBitwidth::B128,
+                            ))),
                             FlatEmbed::U64FromBits => Ok(Some(process_u_from_bits(
                                 &embed_call.arguments,
                                 UBitwidth::B64,
@@ -489,6 +563,10 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                                 &embed_call.arguments,
                                 UBitwidth::B8,
                             ))),
+                            FlatEmbed::U128ToBits => Ok(Some(process_u_to_bits(
+                                &embed_call.arguments,
+                                UBitwidth::B128,
+                            ))),
                             FlatEmbed::U64ToBits => Ok(Some(process_u_to_bits(
                                 &embed_call.arguments,
                                 UBitwidth::B64,
@@ -505,6 +583,38 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                                 &embed_call.arguments,
                                 UBitwidth::B8,
                             ))),
+                            FlatEmbed::I64FromBits => Ok(Some(process_u_from_bits(
+                                &embed_call.arguments,
+                                UBitwidth::I64,
+                            ))),
+                            FlatEmbed::I32FromBits => Ok(Some(process_u_from_bits(
+                                &embed_call.arguments,
+                                UBitwidth::I32,
+                            ))),
+                            FlatEmbed::I16FromBits => Ok(Some(process_u_from_bits(
+                                &embed_call.arguments,
+                                UBitwidth::I16,
+                            ))),
+                            FlatEmbed::I8FromBits => Ok(Some(process_u_from_bits(
+                                &embed_call.arguments,
+                                UBitwidth::I8,
+                            ))),
+                            FlatEmbed::I64ToBits => Ok(Some(process_u_to_bits(
+                                &embed_call.arguments,
+                                UBitwidth::I64,
+                            ))),
+                            FlatEmbed::I32ToBits => Ok(Some(process_u_to_bits(
+                                &embed_call.arguments,
+                                UBitwidth::I32,
+                            ))),
+                            FlatEmbed::I16ToBits => Ok(Some(process_u_to_bits(
+                                &embed_call.arguments,
+                                UBitwidth::I16,
+                            ))),
+                            FlatEmbed::I8ToBits => Ok(Some(process_u_to_bits(
+                                &embed_call.arguments,
+                                UBitwidth::I8,
+                            ))),
                             FlatEmbed::Unpack => {
                                 assert_eq!(embed_call.arguments.len(), 1);
                                 assert_eq!(embed_call.generics.len(), 1);
@@ -645,11 +755,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_uint_expression(e1)?.into_inner(),
                 self.fold_uint_expression(e2)?.into_inner(),
             ) {
-                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    Ok(UExpressionInner::Value(
-                        (v1 + v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    ))
-                }
+                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => Ok(
+                    UExpressionInner::Value(v1.wrapping_add(v2) & bitwidth.max_value()),
+                ),
                 (e, UExpressionInner::Value(v)) | (UExpressionInner::Value(v), e) => match v {
                     0 => Ok(e),
                     _ => Ok(UExpressionInner::Add(
@@ -666,11 +774,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_uint_expression(e1)?.into_inner(),
                 self.fold_uint_expression(e2)?.into_inner(),
             ) {
-                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    Ok(UExpressionInner::Value(
-                        (v1.wrapping_sub(v2)) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    ))
-                }
+                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => Ok(
+                    UExpressionInner::Value(v1.wrapping_sub(v2) & bitwidth.max_value()),
+                ),
                 (e, UExpressionInner::Value(v)) => match v {
                     0 => Ok(e),
                     _ => Ok(UExpressionInner::Sub(
@@ -687,11 +793,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_uint_expression(e1)?.into_inner(),
                 self.fold_uint_expression(e2)?.into_inner(),
             ) {
-                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    Ok(UExpressionInner::Value(
-                        v1.saturating_sub(v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    ))
-                }
+                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => Ok(
+                    UExpressionInner::Value(v1.saturating_sub(v2) & bitwidth.max_value()),
+                ),
                 (e, UExpressionInner::Value(v)) => match v {
                     0 => Ok(e),
                     _ => Ok(UExpressionInner::FloorSub(
@@ -708,11 +812,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_uint_expression(e1)?.into_inner(),
                 self.fold_uint_expression(e2)?.into_inner(),
             ) {
-                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    Ok(UExpressionInner::Value(
-                        (v1 * v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    ))
-                }
+                (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => Ok(
+                    UExpressionInner::Value(v1.wrapping_mul(v2) & bitwidth.max_value()),
+                ),
                 (e, UExpressionInner::Value(v)) | (UExpressionInner::Value(v), e) => match v {
                     0 => Ok(UExpressionInner::Value(0)),
                     1 => Ok(e),
@@ -731,9 +833,12 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_uint_expression(e2)?.into_inner(),
             ) {
                 (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    Ok(UExpressionInner::Value(
-                        (v1 / v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    ))
+                    Ok(UExpressionInner::Value(match bitwidth.is_signed() {
+                        true => bitwidth.wrap_signed_value(
+                            bitwidth.signed_value(v1) / bitwidth.signed_value(v2),
+                        ),
+                        false => v1 / v2,
+                    }))
                 }
                 (e, UExpressionInner::Value(v)) => match v {
                     1 => Ok(e),
@@ -752,9 +857,12 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 self.fold_uint_expression(e2)?.into_inner(),
             ) {
                 (UExpressionInner::Value(v1), UExpressionInner::Value(v2)) => {
-                    Ok(UExpressionInner::Value(
-                        (v1 % v2) % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
-                    ))
+                    Ok(UExpressionInner::Value(match bitwidth.is_signed() {
+                        true => bitwidth.wrap_signed_value(
+                            bitwidth.signed_value(v1) % bitwidth.signed_value(v2),
+                        ),
+                        false => v1 % v2,
+                    }))
                 }
                 (e, UExpressionInner::Value(v)) => match v {
                     1 => Ok(UExpressionInner::Value(0)),
@@ -773,7 +881,12 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 let by = self.fold_uint_expression(by)?;
                 match (e.into_inner(), by.into_inner()) {
                     (UExpressionInner::Value(v), UExpressionInner::Value(by)) => {
-                        Ok(UExpressionInner::Value(v >> by))
+                        Ok(UExpressionInner::Value(match bitwidth.is_signed() {
+                            true => bitwidth.wrap_signed_value(
+                                bitwidth.signed_value(v) >> by.min(bitwidth.to_usize() as u128 - 1),
+                            ),
+                            false => v.checked_shr(by as u32).unwrap_or(0),
+                        }))
                     }
                     (e, by) => Ok(UExpressionInner::RightShift(
                         box e.annotate(bitwidth),
@@ -785,9 +898,11 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 let e = self.fold_uint_expression(e)?;
                 let by = self.fold_uint_expression(by)?;
                 match (e.into_inner(), by.into_inner()) {
-                    (UExpressionInner::Value(v), UExpressionInner::Value(by)) => Ok(
-                        UExpressionInner::Value((v << by) & (2_u128.pow(bitwidth as u32) - 1)),
-                    ),
+                    (UExpressionInner::Value(v), UExpressionInner::Value(by)) => {
+                        Ok(UExpressionInner::Value(
+                            v.checked_shl(by as u32).unwrap_or(0) & bitwidth.max_value(),
+                        ))
+                    }
                     (e, by) => Ok(UExpressionInner::LeftShift(
                         box e.annotate(bitwidth),
                         box by.annotate(UBitwidth::B32),
@@ -832,9 +947,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
             UExpressionInner::Not(box e) => {
                 let e = self.fold_uint_expression(e)?.into_inner();
                 match e {
-                    UExpressionInner::Value(v) => Ok(UExpressionInner::Value(
-                        (!v) & (2_u128.pow(bitwidth as u32) - 1),
-                    )),
+                    UExpressionInner::Value(v) => {
+                        Ok(UExpressionInner::Value((!v) & bitwidth.max_value()))
+                    }
                     e => Ok(UExpressionInner::Not(box e.annotate(bitwidth))),
                 }
             }
@@ -842,8 +957,7 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                 let e = self.fold_uint_expression(e)?.into_inner();
                 match e {
                     UExpressionInner::Value(v) => Ok(UExpressionInner::Value(
-                        (0u128.wrapping_sub(v))
-                            % 2_u128.pow(bitwidth.to_usize().try_into().unwrap()),
+                        0u128.wrapping_sub(v) & bitwidth.max_value(),
                     )),
                     e => Ok(UExpressionInner::Neg(box e.annotate(bitwidth))),
                 }
@@ -1288,10 +1402,51 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
 
                 Ok(TupleExpressionInner::Value(v))
             }
+            TupleExpressionInner::Payload(v) => {
+                let v = self.fold_variant_expression(v)?;
+                let enum_ty = v.enumeration.ty().clone();
+                match v.enumeration.into_inner() {
+                    EnumExpressionInner::Value(variant, payload) if variant == v.variant => {
+                        Ok(TupleExpressionInner::Value(payload))
+                    }
+                    inner => Ok(TupleExpressionInner::Payload(VariantExpression::new(
+                        inner.annotate(enum_ty),
+                        v.variant,
+                    ))),
+                }
+            }
             e => fold_tuple_expression_inner(self, ty, e),
         }
     }
 
+    fn fold_bounded_array_expression_inner(
+        &mut self,
+        ty: &ArrayType<'ast, T>,
+        e: BoundedArrayExpressionInner<'ast, T>,
+    ) -> Result<BoundedArrayExpressionInner<'ast, T>, Error> {
+        match e {
+            // the capacity check deferred by semantics, now that the generics are resolved
+            BoundedArrayExpressionInner::Value(box array, Some(metadata)) => {
+                let array = self.fold_array_expression(array)?;
+
+                match (array.size().into_inner(), ty.size.as_inner()) {
+                    (UExpressionInner::Value(size), UExpressionInner::Value(capacity)) => {
+                        if size > *capacity {
+                            Err(Error::BoundedArrayCapacity(size, *capacity, metadata))
+                        } else {
+                            Ok(BoundedArrayExpressionInner::Value(box array, None))
+                        }
+                    }
+                    _ => Ok(BoundedArrayExpressionInner::Value(
+                        box array,
+                        Some(metadata),
+                    )),
+                }
+            }
+            e => fold_bounded_array_expression_inner(self, ty, e),
+        }
+    }
+
     fn fold_eq_expression<
         E: Expr<'ast, T> + PartialEq + Constant + Typed<'ast, T> + ResultFold<'ast, T>,
     >(
@@ -1391,7 +1546,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
 
                 match (e1.as_inner(), e2.as_inner()) {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
-                        Ok(BooleanExpression::Value(n1 < n2))
+                        Ok(BooleanExpression::Value(
+                            compare_uint(e1.bitwidth(), *n1, *n2) < Ordering::Equal,
+                        ))
                     }
                     _ => Ok(BooleanExpression::UintLt(box e1, box e2)),
                 }
@@ -1402,7 +1559,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
 
                 match (e1.as_inner(), e2.as_inner()) {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
-                        Ok(BooleanExpression::Value(n1 <= n2))
+                        Ok(BooleanExpression::Value(
+                            compare_uint(e1.bitwidth(), *n1, *n2) <= Ordering::Equal,
+                        ))
                     }
                     _ => Ok(BooleanExpression::UintLe(box e1, box e2)),
                 }
@@ -1413,7 +1572,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
 
                 match (e1.as_inner(), e2.as_inner()) {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
-                        Ok(BooleanExpression::Value(n1 > n2))
+                        Ok(BooleanExpression::Value(
+                            compare_uint(e1.bitwidth(), *n1, *n2) > Ordering::Equal,
+                        ))
                     }
                     _ => Ok(BooleanExpression::UintGt(box e1, box e2)),
                 }
@@ -1424,7 +1585,9 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
 
                 match (e1.as_inner(), e2.as_inner()) {
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
-                        Ok(BooleanExpression::Value(n1 >= n2))
+                        Ok(BooleanExpression::Value(
+                            compare_uint(e1.bitwidth(), *n1, *n2) >= Ordering::Equal,
+                        ))
                     }
                     _ => Ok(BooleanExpression::UintGe(box e1, box e2)),
                 }
@@ -1476,6 +1639,15 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Propagator<'ast, 'a, T> {
                     e => Ok(BooleanExpression::Not(box e)),
                 }
             }
+            BooleanExpression::IsVariant(v) => {
+                let v = self.fold_variant_expression(v)?;
+                match v.enumeration.as_inner() {
+                    EnumExpressionInner::Value(variant, _) => {
+                        Ok(BooleanExpression::Value(*variant == v.variant))
+                    }
+                    _ => Ok(BooleanExpression::IsVariant(v)),
+                }
+            }
             e => fold_boolean_expression(self, e),
         }
     }
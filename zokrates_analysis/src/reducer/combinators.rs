@@ -0,0 +1,272 @@
+// Expand a call to an array combinator into the body of a function for the types of the call site, which is then
+// inlined like any other. The loop over the array is unrolled by the reducer, so combinators cost what the equivalent
+// loop costs.
+
+// For example, `a.map(f)` on an array `a` of type `field[3]`, where `f` returns a `bool`, is a call to
+// ```
+// def map(field[3] array) -> bool[3] {
+//     bool[3] mut result = [false, false, false];
+//     for u32 i in 0..3 {
+//         result[i] = f(array[i]);
+//     }
+//     return result;
+// }
+// ```
+
+use crate::reducer::inline::function_call;
+use zokrates_ast::typed::{
+    zero_value, ArrayExpression, Combinator, ConcreteSignature, ConcreteType,
+    DeclarationFunctionKey, DeclarationVariable, Id, Select, TupleExpressionInner, Type,
+    TypedAssignee, TypedExpression, TypedFunction, TypedStatement, UBitwidth, UExpression,
+    Variable,
+};
+use zokrates_field::Field;
+
+pub fn expand<'ast, T: Field>(
+    combinator: Combinator,
+    function_arguments: &[DeclarationFunctionKey<'ast, T>],
+    signature: &ConcreteSignature,
+) -> TypedFunction<'ast, T> {
+    let (element_type, size) = match &signature.inputs[0] {
+        ConcreteType::Array(array_type) => (Type::from(*array_type.ty.clone()), *array_type.size),
+        _ => unreachable!(),
+    };
+
+    let index = Variable::uint("i", UBitwidth::B32);
+    let index_expression = UExpression::identifier(index.id.clone()).annotate(UBitwidth::B32);
+
+    let element = |id: &'ast str, ty: &Type<'ast, T>| {
+        TypedExpression::select(
+            ArrayExpression::identifier(id.into()).annotate(ty.clone(), size),
+            index_expression.clone(),
+        )
+    };
+
+    let output_type = Type::from(*signature.output.clone());
+
+    let (parameters, result, init, update) = match combinator {
+        Combinator::Fold => {
+            let accumulator = Variable::new("accumulator", output_type.clone(), true);
+
+            let value = function_call(
+                &output_type,
+                function_arguments[0].clone(),
+                vec![],
+                vec![accumulator.clone().into(), element("array", &element_type)],
+                vec![],
+            );
+
+            (
+                vec!["array", "init"],
+                accumulator.clone(),
+                Variable::new("init", output_type, false).into(),
+                TypedStatement::definition(accumulator.into(), value),
+            )
+        }
+        _ => {
+            let value_type = match &output_type {
+                Type::Array(array_type) => *array_type.ty.clone(),
+                _ => unreachable!(),
+            };
+
+            let tuple_type = || match &value_type {
+                Type::Tuple(tuple_type) => tuple_type.clone(),
+                _ => unreachable!(),
+            };
+
+            let value = match combinator {
+                Combinator::Map => function_call(
+                    &value_type,
+                    function_arguments[0].clone(),
+                    vec![],
+                    vec![element("array", &element_type)],
+                    vec![],
+                ),
+                Combinator::Zip => {
+                    let other_type = match &signature.inputs[1] {
+                        ConcreteType::Array(array_type) => Type::from(*array_type.ty.clone()),
+                        _ => unreachable!(),
+                    };
+
+                    TupleExpressionInner::Value(vec![
+                        element("array", &element_type),
+                        element("other", &other_type),
+                    ])
+                    .annotate(tuple_type())
+                    .into()
+                }
+                Combinator::Enumerate => TupleExpressionInner::Value(vec![
+                    index_expression.clone().into(),
+                    element("array", &element_type),
+                ])
+                .annotate(tuple_type())
+                .into(),
+                Combinator::Fold => unreachable!(),
+            };
+
+            let result = Variable::new("result", output_type.clone(), true);
+
+            (
+                match combinator {
+                    Combinator::Zip => vec!["array", "other"],
+                    _ => vec!["array"],
+                },
+                result.clone(),
+                zero_value(&output_type),
+                TypedStatement::definition(
+                    TypedAssignee::Select(
+                        box TypedAssignee::Identifier(result),
+                        box index_expression,
+                    ),
+                    value,
+                ),
+            )
+        }
+    };
+
+    TypedFunction {
+        arguments: parameters
+            .into_iter()
+            .zip(signature.inputs.iter())
+            .map(|(id, ty)| DeclarationVariable::new(id, ty.clone().into(), false).into())
+            .collect(),
+        statements: vec![
+            TypedStatement::definition(result.clone().into(), init),
+            TypedStatement::For(index, 0u32.into(), size.into(), vec![update]),
+            TypedStatement::Return(result.into()),
+        ],
+        signature: signature.clone().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_ast::typed::{
+        BooleanExpression, ConcreteTupleType, DeclarationSignature, DeclarationType,
+        FieldElementExpression, FunctionCallExpression, TupleType,
+    };
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn map() {
+        // `map` on a `field[2]` with a function `f` returning a `bool` expands to
+        // def map(field[2] array) -> bool[2] {
+        //     bool[2] mut result = [false, false];
+        //     for u32 i in 0..2 {
+        //         result[i] = f(array[i]);
+        //     }
+        //     return result;
+        // }
+
+        let f = DeclarationFunctionKey::with_location("main", "f").signature(
+            DeclarationSignature::new()
+                .inputs(vec![DeclarationType::FieldElement])
+                .output(DeclarationType::Boolean),
+        );
+
+        let signature = ConcreteSignature::new()
+            .inputs(vec![ConcreteType::array((
+                ConcreteType::FieldElement,
+                2u32,
+            ))])
+            .output(ConcreteType::array((ConcreteType::Boolean, 2u32)));
+
+        let function = expand::<Bn128Field>(Combinator::Map, &[f.clone()], &signature);
+
+        let i = UExpression::identifier("i".into()).annotate(UBitwidth::B32);
+        let result = Variable::new("result", Type::array((Type::Boolean, 2u32)), true);
+
+        assert_eq!(
+            function,
+            TypedFunction {
+                arguments: vec![DeclarationVariable::new(
+                    "array",
+                    DeclarationType::array((DeclarationType::FieldElement, 2u32)),
+                    false
+                )
+                .into()],
+                statements: vec![
+                    TypedStatement::definition(
+                        result.clone().into(),
+                        zero_value(&Type::array((Type::Boolean, 2u32)))
+                    ),
+                    TypedStatement::For(
+                        Variable::uint("i", UBitwidth::B32),
+                        0u32.into(),
+                        2u32.into(),
+                        vec![TypedStatement::definition(
+                            TypedAssignee::Select(
+                                box TypedAssignee::Identifier(result.clone()),
+                                box i.clone()
+                            ),
+                            BooleanExpression::FunctionCall(FunctionCallExpression::new(
+                                f,
+                                vec![],
+                                vec![FieldElementExpression::select(
+                                    ArrayExpression::identifier("array".into())
+                                        .annotate(Type::FieldElement, 2u32),
+                                    i
+                                )
+                                .into()]
+                            ))
+                            .into()
+                        )]
+                    ),
+                    TypedStatement::Return(result.into())
+                ],
+                signature: signature.into()
+            }
+        );
+    }
+
+    #[test]
+    fn enumerate() {
+        // `enumerate` on a `bool[2]` returns pairs of the index and the element
+
+        let signature = ConcreteSignature::new()
+            .inputs(vec![ConcreteType::array((ConcreteType::Boolean, 2u32))])
+            .output(ConcreteType::array((
+                ConcreteType::Tuple(ConcreteTupleType::new(vec![
+                    ConcreteType::Uint(UBitwidth::B32),
+                    ConcreteType::Boolean,
+                ])),
+                2u32,
+            )));
+
+        let function = expand::<Bn128Field>(Combinator::Enumerate, &[], &signature);
+
+        let i = UExpression::identifier("i".into()).annotate(UBitwidth::B32);
+
+        match &function.statements[1] {
+            TypedStatement::For(_, _, _, statements) => assert_eq!(
+                statements[0],
+                TypedStatement::definition(
+                    TypedAssignee::Select(
+                        box TypedAssignee::Identifier(Variable::new(
+                            "result",
+                            Type::from(*signature.output.clone()),
+                            true
+                        )),
+                        box i.clone()
+                    ),
+                    TupleExpressionInner::Value(vec![
+                        i.clone().into(),
+                        BooleanExpression::select(
+                            ArrayExpression::identifier("array".into())
+                                .annotate(Type::Boolean, 2u32),
+                            i
+                        )
+                        .into()
+                    ])
+                    .annotate(TupleType::new(vec![
+                        Type::Uint(UBitwidth::B32),
+                        Type::Boolean
+                    ]))
+                    .into()
+                )
+            ),
+            s => panic!("expected a loop, found {}", s),
+        }
+    }
+}
@@ -49,6 +49,9 @@ impl<'a, 'ast, T: Field> Folder<'ast, T> for ConstantsReader<'a, 'ast, T> {
                     None => DeclarationConstant::Constant(c),
                 }
             }
+            DeclarationConstant::Expression(e) => DeclarationConstant::from_uint_expression(
+                UExpression::try_from(self.fold_expression(e)).unwrap(),
+            ),
             c => fold_declaration_constant(self, c),
         }
     }
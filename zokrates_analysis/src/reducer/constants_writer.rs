@@ -4,10 +4,12 @@ use crate::reducer::{
     constants_reader::ConstantsReader, reduce_function, ConstantDefinitions, Error,
 };
 use std::collections::{BTreeMap, HashSet};
+use zokrates_ast::common::{SourceMetadata, Warnings};
 use zokrates_ast::typed::{
-    result_folder::*, types::ConcreteGenericsAssignment, Constant, OwnedTypedModuleId, Typed,
-    TypedConstant, TypedConstantSymbol, TypedConstantSymbolDeclaration, TypedModuleId,
-    TypedProgram, TypedSymbolDeclaration, UExpression,
+    result_folder::*, types::ConcreteGenericsAssignment, BooleanExpression, Constant,
+    DeclarationSignature, OwnedTypedModuleId, Typed, TypedConstant, TypedConstantSymbol,
+    TypedConstantSymbolDeclaration, TypedExpression, TypedFunction, TypedModuleId, TypedProgram,
+    TypedStatement, TypedSymbolDeclaration, UExpression,
 };
 use zokrates_field::Field;
 
@@ -16,15 +18,17 @@ pub struct ConstantsWriter<'ast, T> {
     constants: ConstantDefinitions<'ast, T>,
     location: OwnedTypedModuleId,
     program: TypedProgram<'ast, T>,
+    max_recursion_depth: usize,
 }
 
 impl<'ast, T: Field> ConstantsWriter<'ast, T> {
-    pub fn with_program(program: TypedProgram<'ast, T>) -> Self {
+    pub fn with_program(program: TypedProgram<'ast, T>, max_recursion_depth: usize) -> Self {
         ConstantsWriter {
             constants: ConstantDefinitions::default(),
             location: program.main.clone(),
             treated: HashSet::default(),
             program,
+            max_recursion_depth,
         }
     }
 
@@ -48,6 +52,33 @@ impl<'ast, T: Field> ConstantsWriter<'ast, T> {
         self.program = ConstantsReader::with_constants(&self.constants).read_into_program(p);
     }
 
+    fn check_static_assertion(
+        &self,
+        e: BooleanExpression<'ast, T>,
+        metadata: SourceMetadata,
+    ) -> Result<TypedSymbolDeclaration<'ast, T>, Error> {
+        // wrap the assertion in a function, which fails to reduce if the assertion does not hold
+        let wrapper = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::StaticAssertion(e.clone(), metadata.clone()),
+                TypedStatement::Return(TypedExpression::empty_tuple()),
+            ],
+            signature: DeclarationSignature::new(),
+        };
+
+        // a static assertion which holds is expected to be always satisfied, so warnings are dropped
+        reduce_function(
+            wrapper,
+            ConcreteGenericsAssignment::default(),
+            &self.program,
+            self.max_recursion_depth,
+            &mut Warnings::default(),
+        )?;
+
+        Ok(TypedSymbolDeclaration::StaticAssertion(e, metadata))
+    }
+
     fn update_symbol_declaration(
         &self,
         d: TypedSymbolDeclaration<'ast, T>,
@@ -63,8 +94,9 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ConstantsWriter<'ast, T> {
         &mut self,
         id: OwnedTypedModuleId,
     ) -> Result<OwnedTypedModuleId, Self::Error> {
-        // anytime we encounter a module id, visit the corresponding module if it hasn't been done yet
-        if !self.treated(&id) {
+        // anytime we encounter a module id, visit the corresponding module if it hasn't been done yet. The keys of
+        // function parameters point to no module, as they are only resolved when inlining
+        if !self.treated(&id) && self.program.modules.contains_key(&id) {
             let current_m_id = self.change_location(id.clone());
             // I did not find a way to achieve this without cloning the module. Assuming we do not clone:
             // to fold the module, we need to consume it, so it gets removed from the modules
@@ -87,7 +119,12 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ConstantsWriter<'ast, T> {
         // before we treat the symbol, propagate the constants into it, as it may be using constants defined earlier in this module.
         let s = self.update_symbol_declaration(s);
 
-        let s = fold_symbol_declaration(self, s)?;
+        let s = match s {
+            TypedSymbolDeclaration::StaticAssertion(e, metadata) => {
+                self.check_static_assertion(e, metadata)?
+            }
+            s => fold_symbol_declaration(self, s)?,
+        };
 
         // after we treat the symbol, propagate again, as treating this symbol may have triggered checking another module, resolving new constants which this symbol may be using.
         Ok(self.update_symbol_declaration(s))
@@ -109,8 +146,6 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ConstantsWriter<'ast, T> {
 
                 let c = ConstantsReader::with_constants(&self.constants).fold_constant(c);
 
-                use zokrates_ast::typed::{DeclarationSignature, TypedFunction, TypedStatement};
-
                 // wrap this expression in a function
                 let wrapper = TypedFunction {
                     arguments: vec![],
@@ -118,10 +153,13 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ConstantsWriter<'ast, T> {
                     signature: DeclarationSignature::new().output(c.ty.clone()),
                 };
 
+                // constants do not generate constraints, so there is nothing to warn about
                 let mut inlined_wrapper = reduce_function(
                     wrapper,
                     ConcreteGenericsAssignment::default(),
                     &self.program,
+                    self.max_recursion_depth,
+                    &mut Warnings::default(),
                 )?;
 
                 if let TypedStatement::Return(expression) =
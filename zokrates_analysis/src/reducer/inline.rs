@@ -25,19 +25,36 @@
 // Notes:
 // - The body of the function is in SSA form
 // - The return value(s) are assigned to internal variables
+// - The function parameters of the function are replaced by the functions passed at the call site, so that calls to
+//   them are inlined in turn
+// - The type parameters of the function are replaced by the types of the call site
 
+use crate::reducer::combinators;
+use crate::reducer::instance_name;
 use crate::reducer::Output;
 use crate::reducer::ShallowTransformer;
 use crate::reducer::Versions;
 
+use std::collections::HashMap;
 use zokrates_ast::common::FlatEmbed;
-use zokrates_ast::typed::types::{ConcreteGenericsAssignment, IntoType};
+use zokrates_ast::typed::folder::{
+    fold_boolean_expression, fold_declaration_type, fold_expression, fold_type,
+};
+use zokrates_ast::typed::types::{
+    ConcreteGenericsAssignment, GenericsError, IntoType, StructLocation,
+};
 use zokrates_ast::typed::CoreIdentifier;
+use zokrates_ast::typed::Folder;
 use zokrates_ast::typed::Identifier;
 use zokrates_ast::typed::{
-    ConcreteFunctionKey, ConcreteSignature, ConcreteVariable, DeclarationFunctionKey, Expr,
-    Signature, Type, TypedExpression, TypedFunctionSymbol, TypedFunctionSymbolDeclaration,
-    TypedProgram, TypedStatement, UExpression, UExpressionInner, Variable,
+    ArrayExpression, ArrayExpressionInner, Block, BooleanExpression, BoundedArrayExpression,
+    BoundedArrayExpressionInner, ConcreteFunctionKey, ConcreteSignature, ConcreteType,
+    ConcreteVariable, Conditional, DeclarationFunctionKey, DeclarationSignature, DeclarationType,
+    Element, EnumExpression, EnumExpressionInner, EqExpression, Expr, FieldElementExpression,
+    FunctionCallExpression, Select, Signature, StructExpression, StructExpressionInner,
+    TupleExpression, TupleExpressionInner, Type, TypedExpression, TypedFunctionSymbol,
+    TypedFunctionSymbolDeclaration, TypedProgram, TypedStatement, UExpression, UExpressionInner,
+    Variable,
 };
 use zokrates_field::Field;
 
@@ -53,11 +70,292 @@ pub enum InlineError<'ast, T> {
         DeclarationFunctionKey<'ast, T>,
         Vec<Option<UExpression<'ast, T>>>,
         Vec<TypedExpression<'ast, T>>,
+        Vec<DeclarationFunctionKey<'ast, T>>,
         Type<'ast, T>,
     ),
+    Instance(String, GenericsError<'ast>),
+}
+
+// The types bound to the type parameters of a function at a call site, by the location of the opaque type standing for
+// each of them
+type TypeArguments<'ast> = HashMap<StructLocation, ConcreteType>;
+
+// Bind the opaque types found in a declared type to the matching parts of the type of a call site
+fn bind_type_arguments<'ast, T>(
+    declared: &DeclarationType<'ast, T>,
+    ty: &ConcreteType,
+    type_arguments: &mut TypeArguments<'ast>,
+) {
+    match (declared, ty) {
+        (DeclarationType::Struct(s), ty) if s.is_type_parameter() => {
+            type_arguments.insert(s.canonical_location.clone(), ty.clone());
+        }
+        (DeclarationType::Array(declared), ConcreteType::Array(ty))
+        | (DeclarationType::BoundedArray(declared), ConcreteType::BoundedArray(ty)) => {
+            bind_type_arguments(&declared.ty, &ty.ty, type_arguments)
+        }
+        (DeclarationType::Tuple(declared), ConcreteType::Tuple(ty)) => declared
+            .elements
+            .iter()
+            .zip(ty.elements.iter())
+            .for_each(|(declared, ty)| bind_type_arguments(declared, ty, type_arguments)),
+        (DeclarationType::Enum(declared), ConcreteType::Enum(ty)) => declared
+            .variants
+            .iter()
+            .zip(ty.variants.iter())
+            .flat_map(|(declared, ty)| declared.types.iter().zip(ty.types.iter()))
+            .for_each(|(declared, ty)| bind_type_arguments(declared, ty, type_arguments)),
+        _ => {}
+    }
+}
+
+fn type_arguments<'ast, T>(
+    signature: &DeclarationSignature<'ast, T>,
+    inferred_signature: &ConcreteSignature,
+) -> TypeArguments<'ast> {
+    let mut type_arguments = TypeArguments::default();
+
+    for (declared, ty) in signature
+        .inputs
+        .iter()
+        .chain(std::iter::once(&*signature.output))
+        .zip(
+            inferred_signature
+                .inputs
+                .iter()
+                .chain(std::iter::once(&*inferred_signature.output)),
+        )
+    {
+        bind_type_arguments(declared, ty, &mut type_arguments);
+    }
+
+    type_arguments
 }
 
-fn get_canonical_function<'ast, T: Field>(
+// A call of type `ty` to the function at `key`
+pub(super) fn function_call<'ast, T: Field>(
+    ty: &Type<'ast, T>,
+    key: DeclarationFunctionKey<'ast, T>,
+    generics: Vec<Option<UExpression<'ast, T>>>,
+    arguments: Vec<TypedExpression<'ast, T>>,
+    function_arguments: Vec<DeclarationFunctionKey<'ast, T>>,
+) -> TypedExpression<'ast, T> {
+    macro_rules! call {
+        () => {
+            FunctionCallExpression::new(key, generics, arguments)
+                .function_arguments(function_arguments)
+        };
+    }
+
+    match ty {
+        Type::FieldElement => FieldElementExpression::FunctionCall(call!()).into(),
+        Type::Boolean => BooleanExpression::FunctionCall(call!()).into(),
+        Type::Uint(bitwidth) => UExpressionInner::FunctionCall(call!())
+            .annotate(*bitwidth)
+            .into(),
+        Type::Array(array_type) => ArrayExpressionInner::FunctionCall(call!())
+            .annotate(*array_type.ty.clone(), *array_type.size.clone())
+            .into(),
+        Type::Struct(struct_type) => StructExpressionInner::FunctionCall(call!())
+            .annotate(struct_type.clone())
+            .into(),
+        Type::Tuple(tuple_type) => TupleExpressionInner::FunctionCall(call!())
+            .annotate(tuple_type.clone())
+            .into(),
+        Type::Enum(enum_type) => EnumExpressionInner::FunctionCall(call!())
+            .annotate(enum_type.clone())
+            .into(),
+        Type::BoundedArray(array_type) => BoundedArrayExpressionInner::FunctionCall(call!())
+            .annotate(array_type.clone())
+            .into(),
+        Type::Int => unreachable!(),
+    }
+}
+
+// Specialize a function for a call site: its function parameters are replaced by the functions passed for them, and
+// the opaque types standing for its type parameters by the types bound to them
+struct Specializer<'a, 'ast, T> {
+    key: &'a DeclarationFunctionKey<'ast, T>,
+    function_arguments: &'a [DeclarationFunctionKey<'ast, T>],
+    type_arguments: &'a TypeArguments<'ast>,
+}
+
+impl<'a, 'ast, T: Field> Specializer<'a, 'ast, T> {
+    // an expression of an opaque type, as an expression of the type bound to it
+    fn specialize_expression<E>(&mut self, ty: &Type<'ast, T>, e: StructExpression<'ast, T>) -> E
+    where
+        E: Expr<'ast, T>
+            + Block<'ast, T>
+            + Conditional<'ast, T>
+            + Select<'ast, T>
+            + Element<'ast, T>,
+    {
+        match e.into_inner() {
+            StructExpressionInner::Identifier(e) => {
+                TypedExpression::from(Variable::new(e.id, ty.clone(), false)).into()
+            }
+            StructExpressionInner::FunctionCall(e) => {
+                let key = self.fold_declaration_function_key(*e.function_key);
+                let generics = e
+                    .generics
+                    .into_iter()
+                    .map(|g| g.map(|g| self.fold_uint_expression(g)))
+                    .collect();
+                let arguments = e
+                    .arguments
+                    .into_iter()
+                    .map(|a| self.fold_expression(a))
+                    .collect();
+                let function_arguments = e
+                    .function_arguments
+                    .into_iter()
+                    .map(|k| self.fold_declaration_function_key(k))
+                    .collect();
+
+                function_call(ty, key, generics, arguments, function_arguments).into()
+            }
+            StructExpressionInner::Block(e) => E::block(
+                e.statements
+                    .into_iter()
+                    .flat_map(|s| self.fold_statement(s))
+                    .collect(),
+                self.specialize_expression(ty, *e.value),
+            ),
+            StructExpressionInner::Conditional(e) => E::conditional(
+                self.fold_boolean_expression(*e.condition),
+                self.specialize_expression(ty, *e.consequence),
+                self.specialize_expression(ty, *e.alternative),
+                e.kind,
+            ),
+            StructExpressionInner::Select(e) => E::select(
+                self.fold_array_expression(*e.array),
+                self.fold_uint_expression(*e.index),
+            ),
+            StructExpressionInner::Element(e) => {
+                E::element(self.fold_tuple_expression(*e.tuple), e.index)
+            }
+            // opaque types have no members and no values
+            StructExpressionInner::Member(..) | StructExpressionInner::Value(..) => unreachable!(),
+        }
+    }
+}
+
+impl<'a, 'ast, T: Field> Folder<'ast, T> for Specializer<'a, 'ast, T> {
+    // keys other than function parameters are left untouched, as they point to declarations
+    fn fold_declaration_function_key(
+        &mut self,
+        key: DeclarationFunctionKey<'ast, T>,
+    ) -> DeclarationFunctionKey<'ast, T> {
+        match self.key.function_parameter_index(&key) {
+            Some(index) => self.function_arguments[index].clone(),
+            None => key,
+        }
+    }
+
+    fn fold_type(&mut self, t: Type<'ast, T>) -> Type<'ast, T> {
+        match t {
+            Type::Struct(s) if s.is_type_parameter() => {
+                self.type_arguments[&s.canonical_location].clone().into()
+            }
+            t => fold_type(self, t),
+        }
+    }
+
+    fn fold_declaration_type(&mut self, t: DeclarationType<'ast, T>) -> DeclarationType<'ast, T> {
+        match t {
+            DeclarationType::Struct(s) if s.is_type_parameter() => {
+                self.type_arguments[&s.canonical_location].clone().into()
+            }
+            t => fold_declaration_type(self, t),
+        }
+    }
+
+    fn fold_expression(&mut self, e: TypedExpression<'ast, T>) -> TypedExpression<'ast, T> {
+        match e {
+            TypedExpression::Struct(e) if e.ty().is_type_parameter() => {
+                let ty = self.fold_type(Type::Struct(e.ty().clone()));
+
+                match ty {
+                    Type::FieldElement => self
+                        .specialize_expression::<FieldElementExpression<'ast, T>>(&ty, e)
+                        .into(),
+                    Type::Boolean => self
+                        .specialize_expression::<BooleanExpression<'ast, T>>(&ty, e)
+                        .into(),
+                    Type::Uint(..) => self
+                        .specialize_expression::<UExpression<'ast, T>>(&ty, e)
+                        .into(),
+                    Type::Array(..) => self
+                        .specialize_expression::<ArrayExpression<'ast, T>>(&ty, e)
+                        .into(),
+                    Type::Struct(..) => self
+                        .specialize_expression::<StructExpression<'ast, T>>(&ty, e)
+                        .into(),
+                    Type::Tuple(..) => self
+                        .specialize_expression::<TupleExpression<'ast, T>>(&ty, e)
+                        .into(),
+                    Type::Enum(..) => self
+                        .specialize_expression::<EnumExpression<'ast, T>>(&ty, e)
+                        .into(),
+                    Type::BoundedArray(..) => self
+                        .specialize_expression::<BoundedArrayExpression<'ast, T>>(&ty, e)
+                        .into(),
+                    Type::Int => unreachable!(),
+                }
+            }
+            e => fold_expression(self, e),
+        }
+    }
+
+    fn fold_boolean_expression(
+        &mut self,
+        e: BooleanExpression<'ast, T>,
+    ) -> BooleanExpression<'ast, T> {
+        match e {
+            BooleanExpression::StructEq(e) if e.left.ty().is_type_parameter() => {
+                let ty = self.fold_type(Type::Struct(e.left.ty().clone()));
+
+                match ty {
+                    Type::FieldElement => BooleanExpression::FieldEq(EqExpression::new(
+                        self.specialize_expression(&ty, *e.left),
+                        self.specialize_expression(&ty, *e.right),
+                    )),
+                    Type::Boolean => BooleanExpression::BoolEq(EqExpression::new(
+                        self.specialize_expression(&ty, *e.left),
+                        self.specialize_expression(&ty, *e.right),
+                    )),
+                    Type::Uint(..) => BooleanExpression::UintEq(EqExpression::new(
+                        self.specialize_expression(&ty, *e.left),
+                        self.specialize_expression(&ty, *e.right),
+                    )),
+                    Type::Array(..) => BooleanExpression::ArrayEq(EqExpression::new(
+                        self.specialize_expression(&ty, *e.left),
+                        self.specialize_expression(&ty, *e.right),
+                    )),
+                    Type::Struct(..) => BooleanExpression::StructEq(EqExpression::new(
+                        self.specialize_expression(&ty, *e.left),
+                        self.specialize_expression(&ty, *e.right),
+                    )),
+                    Type::Tuple(..) => BooleanExpression::TupleEq(EqExpression::new(
+                        self.specialize_expression(&ty, *e.left),
+                        self.specialize_expression(&ty, *e.right),
+                    )),
+                    Type::Enum(..) => BooleanExpression::EnumEq(EqExpression::new(
+                        self.specialize_expression(&ty, *e.left),
+                        self.specialize_expression(&ty, *e.right),
+                    )),
+                    Type::BoundedArray(..) => self
+                        .specialize_expression::<BoundedArrayExpression<'ast, T>>(&ty, *e.left)
+                        .equals(self.specialize_expression(&ty, *e.right)),
+                    Type::Int => unreachable!(),
+                }
+            }
+            e => fold_boolean_expression(self, e),
+        }
+    }
+}
+
+pub(super) fn get_canonical_function<'ast, T: Field>(
     function_key: DeclarationFunctionKey<'ast, T>,
     program: &TypedProgram<'ast, T>,
 ) -> TypedFunctionSymbolDeclaration<'ast, T> {
@@ -75,6 +373,39 @@ fn get_canonical_function<'ast, T: Field>(
     }
 }
 
+// The declaration of the function called at `function_key` with the types of `signature`. Calls to generic functions
+// and to the functions of traits are made through keys which are not declared: they resolve to the function of the
+// same name whose signature matches once its type parameters are bound
+fn get_called_function<'ast, T: Field>(
+    function_key: DeclarationFunctionKey<'ast, T>,
+    generics: &[Option<u32>],
+    signature: &ConcreteSignature,
+    program: &TypedProgram<'ast, T>,
+) -> Option<TypedFunctionSymbolDeclaration<'ast, T>> {
+    let module = program.modules.get(&function_key.module).unwrap();
+
+    if module.functions_iter().any(|d| d.key == function_key) {
+        return Some(get_canonical_function(function_key, program));
+    }
+
+    module
+        .functions_iter()
+        .filter(|d| d.key.id == function_key.id)
+        .find(|d| {
+            let type_arguments = type_arguments(&d.key.signature, signature);
+
+            Specializer {
+                key: &d.key,
+                function_arguments: &[],
+                type_arguments: &type_arguments,
+            }
+            .fold_signature(d.key.signature.clone())
+            .specialize(generics.to_vec(), signature)
+            .is_ok()
+        })
+        .map(|d| get_canonical_function(d.key.clone(), program))
+}
+
 type InlineResult<'ast, T> = Result<
     Output<(Vec<TypedStatement<'ast, T>>, TypedExpression<'ast, T>), Vec<Versions<'ast>>>,
     InlineError<'ast, T>,
@@ -84,6 +415,7 @@ pub fn inline_call<'a, 'ast, T: Field, E: Expr<'ast, T>>(
     k: DeclarationFunctionKey<'ast, T>,
     generics: Vec<Option<UExpression<'ast, T>>>,
     arguments: Vec<TypedExpression<'ast, T>>,
+    function_arguments: Vec<DeclarationFunctionKey<'ast, T>>,
     output: &E::Ty,
     program: &TypedProgram<'ast, T>,
     versions: &'a mut Versions<'ast>,
@@ -108,6 +440,7 @@ pub fn inline_call<'a, 'ast, T: Field, E: Expr<'ast, T>>(
                 k.clone(),
                 generics.clone(),
                 arguments.clone(),
+                function_arguments.clone(),
                 output_type.clone(),
             )
         })?;
@@ -127,30 +460,53 @@ pub fn inline_call<'a, 'ast, T: Field, E: Expr<'ast, T>>(
                 k,
                 generics,
                 arguments,
+                function_arguments,
                 output_type,
             ));
         }
     };
 
-    let decl = get_canonical_function(k.clone(), program);
+    let generic_error = |decl_key: &DeclarationFunctionKey<'ast, T>| {
+        InlineError::Generic(
+            k.clone(),
+            ConcreteFunctionKey {
+                module: decl_key.module.clone(),
+                id: decl_key.id,
+                signature: inferred_signature.clone(),
+            },
+        )
+    };
+
+    let decl = get_called_function(k.clone(), &generics_values, &inferred_signature, program)
+        .ok_or_else(|| generic_error(&k))?;
+
+    // bind the type parameters of the function to the types of this call site
+    let type_arguments = type_arguments(&decl.key.signature, &inferred_signature);
+
+    let mut specializer = Specializer {
+        key: &decl.key,
+        function_arguments: &function_arguments,
+        type_arguments: &type_arguments,
+    };
+
+    let signature = specializer.fold_signature(decl.key.signature.clone());
 
     // get an assignment of generics for this call site
-    let assignment: ConcreteGenericsAssignment<'ast> = k
-        .signature
+    let assignment: ConcreteGenericsAssignment<'ast> = signature
         .specialize(generics_values, &inferred_signature)
-        .map_err(|_| {
-            InlineError::Generic(
-                k.clone(),
-                ConcreteFunctionKey {
-                    module: decl.key.module.clone(),
-                    id: decl.key.id,
-                    signature: inferred_signature.clone(),
-                },
-            )
-        })?;
+        .map_err(|_| generic_error(&decl.key))?;
+
+    signature
+        .check_instance(&assignment)
+        .map_err(|e| InlineError::Instance(instance_name(&decl.key, &assignment), e))?;
 
     let f = match decl.symbol {
         TypedFunctionSymbol::Here(f) => Ok(f),
+        TypedFunctionSymbol::Combinator(c) => Ok(combinators::expand(
+            c,
+            &function_arguments,
+            &inferred_signature,
+        )),
         TypedFunctionSymbol::Flat(e) => Err(InlineError::Flat(
             e,
             e.generics::<T>(&assignment),
@@ -162,6 +518,8 @@ pub fn inline_call<'a, 'ast, T: Field, E: Expr<'ast, T>>(
 
     assert_eq!(f.arguments.len(), arguments.len());
 
+    let f = specializer.fold_function(f);
+
     let (ssa_f, incomplete_data) = match ShallowTransformer::transform(f, &assignment, versions) {
         Output::Complete(v) => (v, None),
         Output::Incomplete(statements, for_loop_versions) => (statements, Some(for_loop_versions)),
@@ -218,3 +576,70 @@ pub fn inline_call<'a, 'ast, T: Field, E: Expr<'ast, T>>(
         .map(|d| Output::Incomplete((statements.clone(), expression.clone()), d))
         .unwrap_or_else(|| Output::Complete((statements, expression))))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_ast::typed::types::{
+        ConcreteArrayType, ConcreteEnumType, DeclarationArrayType, DeclarationEnumType,
+        DeclarationStructType, GEnumVariant, StructKind,
+    };
+    use zokrates_ast::typed::DeclarationConstant;
+    use zokrates_field::Bn128Field;
+
+    fn type_parameter() -> DeclarationType<'static, Bn128Field> {
+        DeclarationType::Struct(
+            DeclarationStructType::new("main".into(), "f::T".into(), vec![], vec![])
+                .kind(StructKind::TypeParameter),
+        )
+    }
+
+    fn location() -> StructLocation {
+        StructLocation {
+            module: "main".into(),
+            name: "f::T".into(),
+        }
+    }
+
+    #[test]
+    fn bind_in_enum_payload() {
+        // enum E { A(field), B(T) } bound to enum E { A(field), B(bool) }
+        let declared = DeclarationType::Enum(DeclarationEnumType::new(
+            "main".into(),
+            "E".into(),
+            vec![
+                GEnumVariant::new("A".into(), vec![DeclarationType::FieldElement]),
+                GEnumVariant::new("B".into(), vec![type_parameter()]),
+            ],
+        ));
+        let ty = ConcreteType::Enum(ConcreteEnumType::new(
+            "main".into(),
+            "E".into(),
+            vec![
+                GEnumVariant::new("A".into(), vec![ConcreteType::FieldElement]),
+                GEnumVariant::new("B".into(), vec![ConcreteType::Boolean]),
+            ],
+        ));
+
+        let mut type_arguments = TypeArguments::default();
+        bind_type_arguments(&declared, &ty, &mut type_arguments);
+
+        assert_eq!(type_arguments[&location()], ConcreteType::Boolean);
+    }
+
+    #[test]
+    fn bind_in_bounded_array() {
+        // T[..4] bound to field[..4]
+        let declared = DeclarationType::BoundedArray(DeclarationArrayType::new(
+            type_parameter(),
+            DeclarationConstant::Concrete(4),
+        ));
+        let ty =
+            ConcreteType::BoundedArray(ConcreteArrayType::new(ConcreteType::FieldElement, 4u32));
+
+        let mut type_arguments = TypeArguments::default();
+        bind_type_arguments(&declared, &ty, &mut type_arguments);
+
+        assert_eq!(type_arguments[&location()], ConcreteType::FieldElement);
+    }
+}
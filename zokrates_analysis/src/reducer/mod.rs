@@ -11,12 +11,13 @@
 // - unroll loops
 // - inline function calls. This includes applying shallow-ssa on the target function
 
+mod combinators;
 mod constants_reader;
 mod constants_writer;
 mod inline;
 mod shallow_ssa;
 
-use self::inline::{inline_call, InlineError};
+use self::inline::{get_canonical_function, inline_call, InlineError};
 use std::collections::HashMap;
 use zokrates_ast::typed::result_folder::*;
 use zokrates_ast::typed::types::ConcreteGenericsAssignment;
@@ -24,11 +25,13 @@ use zokrates_ast::typed::types::GGenericsAssignment;
 use zokrates_ast::typed::Folder;
 use zokrates_ast::typed::{CanonicalConstantIdentifier, EmbedCall, Variable};
 
+use zokrates_ast::common::{SourceMetadata, Warnings};
 use zokrates_ast::typed::{
-    ArrayExpressionInner, ArrayType, BlockExpression, CoreIdentifier, Expr, FunctionCall,
-    FunctionCallExpression, FunctionCallOrExpression, Id, Identifier, OwnedTypedModuleId,
-    TypedExpression, TypedFunction, TypedFunctionSymbol, TypedFunctionSymbolDeclaration,
-    TypedModule, TypedProgram, TypedStatement, UExpression, UExpressionInner,
+    ArrayExpressionInner, ArrayType, BlockExpression, BooleanExpression, CoreIdentifier,
+    DeclarationFunctionKey, Expr, FunctionCall, FunctionCallExpression, FunctionCallOrExpression,
+    Id, Identifier, OwnedTypedModuleId, TypedExpression, TypedFunction, TypedFunctionSymbol,
+    TypedFunctionSymbolDeclaration, TypedModule, TypedProgram, TypedStatement, UExpression,
+    UExpressionInner,
 };
 
 use zokrates_field::Field;
@@ -59,12 +62,18 @@ pub enum Output<U, V> {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
     Incompatible(String),
+    Propagation(crate::propagation::Error),
     GenericsInMain,
     // TODO: give more details about what's blocking the progress
     NoProgress,
     LoopTooLarge(u128),
     ConstantReduction(String, OwnedTypedModuleId),
     Type(String),
+    RecursionTooDeep(String, usize),
+    GenericInstantiation(String, String),
+    StaticAssertion(SourceMetadata, Option<String>),
+    NonConstantStaticAssertion(SourceMetadata, Option<String>),
+    NonConstantComptimeCondition(SourceMetadata, Option<String>),
 }
 
 impl fmt::Display for Error {
@@ -75,15 +84,39 @@ impl fmt::Display for Error {
                 "{}",
                 s
             ),
+            Error::Propagation(e) => write!(f, "{}", e),
             Error::GenericsInMain => write!(f, "Cannot generate code for generic function"),
             Error::NoProgress => write!(f, "Failed to unroll or inline program. Check that main function arguments aren't used as array size or for-loop bounds"),
             Error::LoopTooLarge(size) => write!(f, "Found a loop of size {}, which is larger than the maximum allowed of {}. Check the loop bounds, especially for underflows", size, MAX_FOR_LOOP_SIZE),
             Error::ConstantReduction(name, module) => write!(f, "Failed to reduce constant `{}` in module `{}` to a literal, try simplifying its declaration", name, module.display()),
             Error::Type(message) => write!(f, "{}", message),
+            Error::GenericInstantiation(message, instance) => write!(f, "{} when instantiating `{}`", message, instance),
+            Error::RecursionTooDeep(name, depth) => write!(f, "Recursive calls to `{}` exceed the maximum depth of {}. Check that the recursion reaches a base case for constant generic values, or increase the maximum depth", name, depth),
+            Error::StaticAssertion(metadata, instance) => write!(f, "Static assertion failed at {}{}", metadata, in_instance(instance)),
+            Error::NonConstantStaticAssertion(metadata, instance) => write!(f, "Static assertion at {} cannot be evaluated at compile time{}. Check that it only depends on constants and generic parameters", metadata, in_instance(instance)),
+            Error::NonConstantComptimeCondition(metadata, instance) => write!(f, "Condition of `comptime if` at {} cannot be evaluated at compile time{}. Check that it only depends on constants and generic parameters", metadata, in_instance(instance)),
         }
     }
 }
 
+fn in_instance(instance: &Option<String>) -> String {
+    instance
+        .as_ref()
+        .map(|i| format!(" when instantiating `{}`", i))
+        .unwrap_or_default()
+}
+
+// a readable name for a function instance, such as `foo::<N: 3>`
+pub fn instance_name<T>(
+    key: &DeclarationFunctionKey<'_, T>,
+    generics: &ConcreteGenericsAssignment<'_>,
+) -> String {
+    match generics.0.is_empty() {
+        true => key.id.to_string(),
+        false => format!("{}::<{}>", key.id, generics),
+    }
+}
+
 #[derive(Debug, Default)]
 struct Substitutions<'ast>(HashMap<CoreIdentifier<'ast>, HashMap<usize, usize>>);
 
@@ -179,6 +212,12 @@ struct Reducer<'ast, 'a, T> {
     program: &'a TypedProgram<'ast, T>,
     versions: &'a mut Versions<'ast>,
     substitutions: &'a mut Substitutions<'ast>,
+    // the function instances whose inlined body we are currently in, innermost last
+    call_stack: Vec<(
+        DeclarationFunctionKey<'ast, T>,
+        ConcreteGenericsAssignment<'ast>,
+    )>,
+    max_recursion_depth: usize,
     complete: bool,
 }
 
@@ -188,6 +227,7 @@ impl<'ast, 'a, T: Field> Reducer<'ast, 'a, T> {
         versions: &'a mut Versions<'ast>,
         substitutions: &'a mut Substitutions<'ast>,
         for_loop_versions: Vec<Versions<'ast>>,
+        max_recursion_depth: usize,
     ) -> Self {
         // we reverse the vector as it's cheaper to `pop` than to take from
         // the head
@@ -202,9 +242,86 @@ impl<'ast, 'a, T: Field> Reducer<'ast, 'a, T> {
             substitutions,
             program,
             versions,
+            call_stack: vec![],
+            max_recursion_depth,
             complete: true,
         }
     }
+
+    // check that inlining a call which produced `statements` does not nest the callee in itself too deeply
+    fn check_recursion_depth(&self, statements: &[TypedStatement<'ast, T>]) -> Result<(), Error> {
+        match statements.first() {
+            Some(TypedStatement::PushCallLog(key, _)) => {
+                let depth = self.call_stack.iter().filter(|(k, _)| k == key).count();
+
+                if depth >= self.max_recursion_depth {
+                    Err(Error::RecursionTooDeep(
+                        key.id.to_string(),
+                        self.max_recursion_depth,
+                    ))
+                } else {
+                    Ok(())
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // the name of the innermost function instance we are in, if any
+    fn current_instance(&self) -> Option<String> {
+        self.call_stack
+            .last()
+            .map(|(key, generics)| instance_name(key, generics))
+    }
+
+    // unroll `iterations` one after the other, starting from `versions_before`
+    fn unroll(
+        &mut self,
+        versions_before: Versions<'ast>,
+        iterations: impl IntoIterator<Item = Vec<TypedStatement<'ast, T>>>,
+    ) -> Vec<TypedStatement<'ast, T>> {
+        let mut out_statements = vec![];
+
+        // get a fresh set of versions for all variables to use as a starting point inside the loop
+        self.versions.values_mut().for_each(|v| *v += 1);
+
+        // add this set of versions to the substitution, pointing to the versions before the loop
+        register(self.substitutions, self.versions, &versions_before);
+
+        // the versions after the loop are found by applying an offset of 1 to the versions before the loop
+        let versions_after = versions_before
+            .into_iter()
+            .map(|(k, v)| (k, v + 1))
+            .collect();
+
+        let mut transformer = ShallowTransformer::with_versions(self.versions);
+
+        for statements in iterations {
+            let statements: Vec<TypedStatement<_>> = statements
+                .into_iter()
+                .flat_map(|s| transformer.fold_statement(s))
+                .collect();
+
+            out_statements.extend(statements);
+        }
+
+        let backups = transformer.for_loop_backups;
+        let blocked = transformer.blocked;
+
+        // we know the final versions of the variables after full unrolling of the loop
+        // the versions after the loop need to point to these, so we add to the substitutions
+        register(self.substitutions, &versions_after, self.versions);
+
+        // we may have found new for loops when unrolling this one, which means new backed up versions
+        // we insert these in our backup list and update our cursor
+
+        self.for_loop_versions_after.extend(backups);
+
+        // if the ssa transform got blocked, the reduction is not complete
+        self.complete &= !blocked;
+
+        out_statements
+    }
 }
 
 impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
@@ -230,9 +347,10 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
             .collect::<Result<_, _>>()?;
 
         let res = inline_call::<_, E>(
-            e.function_key.clone(),
+            *e.function_key.clone(),
             generics,
             arguments,
+            e.function_arguments,
             ty,
             self.program,
             self.versions,
@@ -240,6 +358,7 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
 
         match res {
             Ok(Output::Complete((statements, expression))) => {
+                self.check_recursion_depth(&statements)?;
                 self.complete &= true;
                 self.statement_buffer.extend(statements);
                 Ok(FunctionCallOrExpression::Expression(
@@ -247,6 +366,7 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                 ))
             }
             Ok(Output::Incomplete((statements, expression), delta_for_loop_versions)) => {
+                self.check_recursion_depth(&statements)?;
                 self.complete = false;
                 self.statement_buffer.extend(statements);
                 self.for_loop_versions_after.extend(delta_for_loop_versions);
@@ -254,16 +374,20 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                     E::from(expression.clone()).into_inner(),
                 ))
             }
+            Err(InlineError::Instance(instance, e)) => {
+                Err(Error::GenericInstantiation(e.to_string(), instance))
+            }
             Err(InlineError::Generic(decl, conc)) => Err(Error::Incompatible(format!(
                 "Call site `{}` incompatible with declaration `{}`",
                 conc, decl
             ))),
-            Err(InlineError::NonConstant(key, generics, arguments, _)) => {
+            Err(InlineError::NonConstant(key, generics, arguments, function_arguments, _)) => {
                 self.complete = false;
 
-                Ok(FunctionCallOrExpression::Expression(E::function_call(
-                    key, generics, arguments,
-                )))
+                Ok(FunctionCallOrExpression::FunctionCall(
+                    FunctionCallExpression::new(key, generics, arguments)
+                        .function_arguments(function_arguments),
+                ))
             }
             Err(InlineError::Flat(embed, generics, arguments, output_type)) => {
                 let identifier = Identifier::from(CoreIdentifier::Call(0)).version(
@@ -324,61 +448,33 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
         s: TypedStatement<'ast, T>,
     ) -> Result<Vec<TypedStatement<'ast, T>>, Self::Error> {
         let res = match s {
+            TypedStatement::PushCallLog(key, generics) => {
+                self.call_stack.push((key.clone(), generics.clone()));
+                Ok(vec![TypedStatement::PushCallLog(key, generics)])
+            }
+            TypedStatement::PopCallLog => {
+                self.call_stack.pop();
+                Ok(vec![TypedStatement::PopCallLog])
+            }
             TypedStatement::For(v, from, to, statements) => {
                 let versions_before = self.for_loop_versions.pop().unwrap();
 
                 match (from.as_inner(), to.as_inner()) {
                     (UExpressionInner::Value(from), UExpressionInner::Value(to)) => {
-                        let mut out_statements = vec![];
-
-                        // get a fresh set of versions for all variables to use as a starting point inside the loop
-                        self.versions.values_mut().for_each(|v| *v += 1);
-
-                        // add this set of versions to the substitution, pointing to the versions before the loop
-                        register(self.substitutions, self.versions, &versions_before);
-
-                        // the versions after the loop are found by applying an offset of 1 to the versions before the loop
-                        let versions_after = versions_before
-                            .clone()
-                            .into_iter()
-                            .map(|(k, v)| (k, v + 1))
-                            .collect();
-
-                        let mut transformer = ShallowTransformer::with_versions(self.versions);
-
                         if to - from > MAX_FOR_LOOP_SIZE {
                             return Err(Error::LoopTooLarge(to.saturating_sub(*from)));
                         }
 
-                        for index in *from..*to {
-                            let statements: Vec<TypedStatement<_>> =
-                                std::iter::once(TypedStatement::definition(
-                                    v.clone().into(),
-                                    UExpression::from(index as u32).into(),
-                                ))
-                                .chain(statements.clone().into_iter())
-                                .flat_map(|s| transformer.fold_statement(s))
-                                .collect();
-
-                            out_statements.extend(statements);
-                        }
-
-                        let backups = transformer.for_loop_backups;
-                        let blocked = transformer.blocked;
-
-                        // we know the final versions of the variables after full unrolling of the loop
-                        // the versions after the loop need to point to these, so we add to the substitutions
-                        register(self.substitutions, &versions_after, self.versions);
-
-                        // we may have found new for loops when unrolling this one, which means new backed up versions
-                        // we insert these in our backup list and update our cursor
+                        let iterations = (*from..*to).map(|index| {
+                            std::iter::once(TypedStatement::definition(
+                                v.clone().into(),
+                                UExpression::from(index as u32).into(),
+                            ))
+                            .chain(statements.clone())
+                            .collect()
+                        });
 
-                        self.for_loop_versions_after.extend(backups);
-
-                        // if the ssa transform got blocked, the reduction is not complete
-                        self.complete &= !blocked;
-
-                        Ok(out_statements)
+                        Ok(self.unroll(versions_before, iterations))
                     }
                     _ => {
                         let from = self.fold_uint_expression(from)?;
@@ -389,6 +485,45 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
                     }
                 }
             }
+            TypedStatement::ComptimeIf(condition, consequence, alternative, metadata) => {
+                let versions_before = self.for_loop_versions.pop().unwrap();
+
+                match condition {
+                    // the selected branch is unrolled like a loop of a single iteration, the other one is dropped
+                    BooleanExpression::Value(condition) => {
+                        let branch = match condition {
+                            true => consequence,
+                            false => alternative,
+                        };
+
+                        Ok(self.unroll(versions_before, std::iter::once(branch)))
+                    }
+                    condition => {
+                        let condition = self.fold_boolean_expression(condition)?;
+                        self.complete = false;
+                        self.for_loop_versions_after.push(versions_before);
+                        Ok(vec![TypedStatement::ComptimeIf(
+                            condition,
+                            consequence,
+                            alternative,
+                            metadata,
+                        )])
+                    }
+                }
+            }
+            TypedStatement::StaticAssertion(e, metadata) => {
+                match self.fold_boolean_expression(e)? {
+                    BooleanExpression::Value(true) => Ok(vec![]),
+                    BooleanExpression::Value(false) => {
+                        Err(Error::StaticAssertion(metadata, self.current_instance()))
+                    }
+                    // the assertion may become constant once more values are propagated
+                    e => {
+                        self.complete = false;
+                        Ok(vec![TypedStatement::StaticAssertion(e, metadata)])
+                    }
+                }
+            }
             s => fold_statement(self, s),
         };
 
@@ -421,10 +556,14 @@ impl<'ast, 'a, T: Field> ResultFolder<'ast, T> for Reducer<'ast, 'a, T> {
     }
 }
 
-pub fn reduce_program<T: Field>(p: TypedProgram<T>) -> Result<TypedProgram<T>, Error> {
+pub fn reduce_program<'ast, T: Field>(
+    p: TypedProgram<'ast, T>,
+    max_recursion_depth: usize,
+    warnings: &mut Warnings,
+) -> Result<TypedProgram<'ast, T>, Error> {
     // inline all constants and replace them in the program
 
-    let mut constants_writer = ConstantsWriter::with_program(p.clone());
+    let mut constants_writer = ConstantsWriter::with_program(p.clone(), max_recursion_depth);
 
     let p = constants_writer.fold_program(p)?;
 
@@ -436,14 +575,21 @@ pub fn reduce_program<T: Field>(p: TypedProgram<T>) -> Result<TypedProgram<T>, E
         .find(|d| d.key.id == "main")
         .unwrap();
 
-    let main_function = match &decl.symbol {
-        TypedFunctionSymbol::Here(f) => f.clone(),
+    // `main` may be imported from another module, for example when compiling a test
+    let main_function = match get_canonical_function(decl.key.clone(), &p).symbol {
+        TypedFunctionSymbol::Here(f) => f,
         _ => unreachable!(),
     };
 
     match main_function.signature.generics.len() {
         0 => {
-            let main_function = reduce_function(main_function, GGenericsAssignment::default(), &p)?;
+            let main_function = reduce_function(
+                main_function,
+                GGenericsAssignment::default(),
+                &p,
+                max_recursion_depth,
+                warnings,
+            )?;
 
             Ok(TypedProgram {
                 main: p.main.clone(),
@@ -469,6 +615,8 @@ fn reduce_function<'ast, T: Field>(
     f: TypedFunction<'ast, T>,
     generics: ConcreteGenericsAssignment<'ast>,
     program: &TypedProgram<'ast, T>,
+    max_recursion_depth: usize,
+    warnings: &mut Warnings,
 ) -> Result<TypedFunction<'ast, T>, Error> {
     let mut versions = Versions::default();
 
@@ -491,6 +639,7 @@ fn reduce_function<'ast, T: Field>(
                     &mut versions,
                     &mut substitutions,
                     for_loop_versions,
+                    max_recursion_depth,
                 );
 
                 let new_f = TypedFunction {
@@ -513,9 +662,10 @@ fn reduce_function<'ast, T: Field>(
 
                         let new_f = Sub::new(&substitutions).fold_function(new_f);
 
-                        let new_f = Propagator::with_constants(&mut constants)
-                            .fold_function(new_f)
-                            .map_err(|e| Error::Incompatible(format!("{}", e)))?;
+                        let new_f =
+                            Propagator::with_constants_and_warnings(&mut constants, warnings)
+                                .fold_function(new_f)
+                                .map_err(Error::Propagation)?;
 
                         break Ok(new_f);
                     }
@@ -524,14 +674,16 @@ fn reduce_function<'ast, T: Field>(
 
                         let new_f = Sub::new(&substitutions).fold_function(new_f);
 
-                        f = Propagator::with_constants(&mut constants)
+                        f = Propagator::with_constants_and_warnings(&mut constants, warnings)
                             .fold_function(new_f)
-                            .map_err(|e| Error::Incompatible(format!("{}", e)))?;
+                            .map_err(Error::Propagation)?;
 
                         let new_hash = Some(compute_hash(&f));
 
                         if new_hash == hash {
-                            break Err(Error::NoProgress);
+                            break Err(
+                                non_constant_static_condition(&f).unwrap_or(Error::NoProgress)
+                            );
                         } else {
                             hash = new_hash
                         }
@@ -541,9 +693,40 @@ fn reduce_function<'ast, T: Field>(
         }
     }?;
 
-    Propagator::with_constants(&mut constants)
+    Propagator::with_constants_and_warnings(&mut constants, warnings)
         .fold_function(f)
-        .map_err(|e| Error::Incompatible(format!("{}", e)))
+        .map_err(Error::Propagation)
+}
+
+// a static assertion or `comptime if` left in a function which cannot be reduced further depends on runtime values
+fn non_constant_static_condition<T>(f: &TypedFunction<T>) -> Option<Error> {
+    let mut call_stack = vec![];
+
+    for s in &f.statements {
+        match s {
+            TypedStatement::PushCallLog(key, generics) => {
+                call_stack.push(instance_name(key, generics))
+            }
+            TypedStatement::PopCallLog => {
+                call_stack.pop();
+            }
+            TypedStatement::StaticAssertion(_, metadata) => {
+                return Some(Error::NonConstantStaticAssertion(
+                    metadata.clone(),
+                    call_stack.last().cloned(),
+                ))
+            }
+            TypedStatement::ComptimeIf(.., metadata) => {
+                return Some(Error::NonConstantComptimeCondition(
+                    metadata.clone(),
+                    call_stack.last().cloned(),
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    None
 }
 
 fn compute_hash<T: Field>(f: &TypedFunction<T>) -> u64 {
@@ -565,6 +748,8 @@ mod tests {
         OwnedTypedModuleId, Select, TupleExpressionInner, TupleType, Type, TypedExpression,
         TypedExpressionOrSpread, UBitwidth, UExpressionInner, Variable,
     };
+    use zokrates_ast::untyped::Position;
+    use zokrates_common::constants::DEFAULT_MAX_RECURSION_DEPTH;
     use zokrates_field::Bn128Field;
 
     use lazy_static::lazy_static;
@@ -682,7 +867,7 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let reduced = reduce_program(p, DEFAULT_MAX_RECURSION_DEPTH, &mut Warnings::default());
 
         let expected_main = TypedFunction {
             arguments: vec![DeclarationVariable::field_element("a").into()],
@@ -884,7 +1069,7 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let reduced = reduce_program(p, DEFAULT_MAX_RECURSION_DEPTH, &mut Warnings::default());
 
         let expected_main = TypedFunction {
             arguments: vec![DeclarationVariable::field_element("a").into()],
@@ -1117,7 +1302,7 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let reduced = reduce_program(p, DEFAULT_MAX_RECURSION_DEPTH, &mut Warnings::default());
 
         let expected_main = TypedFunction {
             arguments: vec![DeclarationVariable::field_element("a").into()],
@@ -1387,7 +1572,7 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let reduced = reduce_program(p, DEFAULT_MAX_RECURSION_DEPTH, &mut Warnings::default());
 
         let expected_main = TypedFunction {
             arguments: vec![],
@@ -1537,11 +1722,283 @@ mod tests {
             .collect(),
         };
 
-        let reduced = reduce_program(p);
+        let reduced = reduce_program(p, DEFAULT_MAX_RECURSION_DEPTH, &mut Warnings::default());
 
         assert_eq!(
             reduced,
             Err(Error::Incompatible("Call site `main/foo<_>(field[0]) -> field[1]` incompatible with declaration `main/foo<K>(field[K]) -> field[K]`".into()))
         );
     }
+
+    #[test]
+    fn recursion_too_deep() {
+        // def foo(field a) -> field {
+        //     return foo(a);
+        // }
+        // def main(field a) -> field {
+        //     return foo(a);
+        // }
+
+        // expected an error as the recursion never reaches a base case
+
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .output(DeclarationType::FieldElement);
+
+        let call = FieldElementExpression::function_call(
+            DeclarationFunctionKey::with_location("main", "foo").signature(signature.clone()),
+            vec![],
+            vec![FieldElementExpression::identifier("a".into()).into()],
+        );
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(call.clone().into())],
+            signature: signature.clone(),
+        };
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(call.into())],
+            signature: signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    symbols: vec![
+                        TypedFunctionSymbolDeclaration::new(
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(signature.clone()),
+                            TypedFunctionSymbol::Here(foo),
+                        )
+                        .into(),
+                        TypedFunctionSymbolDeclaration::new(
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(signature),
+                            TypedFunctionSymbol::Here(main),
+                        )
+                        .into(),
+                    ],
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, 3, &mut Warnings::default());
+
+        assert_eq!(reduced, Err(Error::RecursionTooDeep("foo".into(), 3)));
+    }
+
+    #[test]
+    fn function_argument() {
+        // def id(field a) -> field {
+        //     return a;
+        // }
+        // def apply(field a, (field) -> field f) -> field {
+        //     return f(a);
+        // }
+        // def main(field a) -> field {
+        //     return apply(a, id);
+        // }
+
+        // expected the call to `f` to be inlined as a call to `id`
+
+        let signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .output(DeclarationType::FieldElement);
+
+        let id_key =
+            DeclarationFunctionKey::with_location("main", "id").signature(signature.clone());
+        let apply_key =
+            DeclarationFunctionKey::with_location("main", "apply").signature(signature.clone());
+        let main_key =
+            DeclarationFunctionKey::with_location("main", "main").signature(signature.clone());
+
+        let function = |e: FieldElementExpression<'static, Bn128Field>| TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(e.into())],
+            signature: signature.clone(),
+        };
+
+        let call =
+            |key: DeclarationFunctionKey<'static, Bn128Field>,
+             function_arguments: Vec<DeclarationFunctionKey<'static, Bn128Field>>| {
+                FieldElementExpression::FunctionCall(
+                    FunctionCallExpression::new(
+                        key,
+                        vec![],
+                        vec![FieldElementExpression::identifier("a".into()).into()],
+                    )
+                    .function_arguments(function_arguments),
+                )
+            };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    symbols: vec![
+                        TypedFunctionSymbolDeclaration::new(
+                            id_key.clone(),
+                            TypedFunctionSymbol::Here(function(
+                                FieldElementExpression::identifier("a".into()),
+                            )),
+                        )
+                        .into(),
+                        TypedFunctionSymbolDeclaration::new(
+                            apply_key.clone(),
+                            TypedFunctionSymbol::Here(function(call(
+                                apply_key.function_parameter(0, signature.clone()),
+                                vec![],
+                            ))),
+                        )
+                        .into(),
+                        TypedFunctionSymbolDeclaration::new(
+                            main_key,
+                            TypedFunctionSymbol::Here(function(call(
+                                apply_key.clone(),
+                                vec![id_key.clone()],
+                            ))),
+                        )
+                        .into(),
+                    ],
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced =
+            reduce_program(p, DEFAULT_MAX_RECURSION_DEPTH, &mut Warnings::default()).unwrap();
+
+        let main = match reduced.modules[&*MAIN_MODULE_ID]
+            .functions_iter()
+            .next()
+            .unwrap()
+            .symbol
+            .clone()
+        {
+            TypedFunctionSymbol::Here(f) => f,
+            _ => unreachable!(),
+        };
+
+        let calls: Vec<_> = main
+            .statements
+            .iter()
+            .filter_map(|s| match s {
+                TypedStatement::PushCallLog(key, _) => Some(key.clone()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(calls, vec![apply_key, id_key]);
+    }
+
+    #[test]
+    fn static_assertion() {
+        // def foo<K>(field[K] a) -> field {
+        //     static_assert(K > 1, "too small");
+        //     return 0;
+        // }
+        // def main(field a) -> field {
+        //     return foo([a]);
+        // }
+
+        // expected an error naming the instance of `foo`
+
+        let foo_signature = DeclarationSignature::new()
+            .generics(vec![Some(
+                GenericIdentifier::with_name("K").with_index(0).into(),
+            )])
+            .inputs(vec![DeclarationType::array((
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("K").with_index(0),
+            ))])
+            .output(DeclarationType::FieldElement);
+
+        let metadata =
+            SourceMetadata::new("main".into(), Position::mock()).message(Some("too small".into()));
+
+        let foo: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::array(
+                "a",
+                DeclarationType::FieldElement,
+                GenericIdentifier::with_name("K").with_index(0),
+            )
+            .into()],
+            statements: vec![
+                TypedStatement::StaticAssertion(
+                    BooleanExpression::UintGt(
+                        box UExpression::identifier("K".into()).annotate(UBitwidth::B32),
+                        box 1u32.into(),
+                    ),
+                    metadata.clone(),
+                ),
+                TypedStatement::Return(FieldElementExpression::Number(0.into()).into()),
+            ],
+            signature: foo_signature.clone(),
+        };
+
+        let main_signature = DeclarationSignature::new()
+            .inputs(vec![DeclarationType::FieldElement])
+            .output(DeclarationType::FieldElement);
+
+        let main: TypedFunction<Bn128Field> = TypedFunction {
+            arguments: vec![DeclarationVariable::field_element("a").into()],
+            statements: vec![TypedStatement::Return(
+                FieldElementExpression::function_call(
+                    DeclarationFunctionKey::with_location("main", "foo")
+                        .signature(foo_signature.clone()),
+                    vec![None],
+                    vec![ArrayExpressionInner::Value(
+                        vec![TypedExpressionOrSpread::Expression(
+                            FieldElementExpression::identifier("a".into()).into(),
+                        )]
+                        .into(),
+                    )
+                    .annotate(Type::FieldElement, 1u32)
+                    .into()],
+                )
+                .into(),
+            )],
+            signature: main_signature.clone(),
+        };
+
+        let p = TypedProgram {
+            main: "main".into(),
+            modules: vec![(
+                "main".into(),
+                TypedModule {
+                    symbols: vec![
+                        TypedFunctionSymbolDeclaration::new(
+                            DeclarationFunctionKey::with_location("main", "foo")
+                                .signature(foo_signature),
+                            TypedFunctionSymbol::Here(foo),
+                        )
+                        .into(),
+                        TypedFunctionSymbolDeclaration::new(
+                            DeclarationFunctionKey::with_location("main", "main")
+                                .signature(main_signature),
+                            TypedFunctionSymbol::Here(main),
+                        )
+                        .into(),
+                    ],
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let reduced = reduce_program(p, DEFAULT_MAX_RECURSION_DEPTH, &mut Warnings::default());
+
+        assert_eq!(
+            reduced,
+            Err(Error::StaticAssertion(metadata, Some("foo::<K: 1>".into())))
+        );
+    }
 }
@@ -38,9 +38,10 @@ use super::{Output, Versions};
 pub struct ShallowTransformer<'ast, 'a> {
     // version index for any variable name
     pub versions: &'a mut Versions<'ast>,
-    // A backup of the versions before each for-loop
+    // A backup of the versions before each for-loop and `comptime if`
     pub for_loop_backups: Vec<Versions<'ast>>,
-    // whether all statements could be unrolled so far. Loops with variable bounds cannot.
+    // whether all statements could be unrolled so far. Loops with variable bounds cannot, and compile-time conditions are
+    // only evaluated by the reducer.
     pub blocked: bool,
 }
 
@@ -167,6 +168,23 @@ impl<'ast, 'a, T: Field> Folder<'ast, T> for ShallowTransformer<'ast, 'a> {
                 self.for_loop_backups.push(versions_before_loop);
                 vec![TypedStatement::For(v, from, to, stats)]
             }
+            // like a loop, the branches of a `comptime if` are only transformed once one of them is selected
+            TypedStatement::ComptimeIf(condition, consequence, alternative, metadata) => {
+                let condition = self.fold_boolean_expression(condition);
+                self.blocked = true;
+                let versions_before = self.create_version_gap();
+                self.for_loop_backups.push(versions_before);
+                vec![TypedStatement::ComptimeIf(
+                    condition,
+                    consequence,
+                    alternative,
+                    metadata,
+                )]
+            }
+            s @ TypedStatement::StaticAssertion(..) => {
+                self.blocked = true;
+                fold_statement(self, s)
+            }
             s => fold_statement(self, s),
         }
     }
@@ -111,9 +111,11 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
 
         let range = e.bitwidth.to_usize();
 
-        let range_max: T = (2_u128.pow(range as u32) - 1).into();
+        let range_max: T = e.bitwidth.max_value().into();
 
-        assert!(range < max_bitwidth / 2);
+        // products of two terms must not overflow the field. The only exception is u128 on fields of less than 258 bits,
+        // whose products are computed on limbs and checked separately
+        assert!(range < max_bitwidth / 2 || e.bitwidth == UBitwidth::B128);
 
         let inner = e.inner;
 
@@ -275,6 +277,25 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
 
                 UExpression::or(force_reduce(left), force_reduce(right)).with_max(range_max)
             }
+            Mult(box left, box right) if !e.bitwidth.fits_product::<T>() => {
+                // the product does not fit in the field, so it is computed on two limbs of `range / 2` bits
+                // `a * b = a_0 * b_0 + (a_1 * b_0 + a_0 * b_1) * 2**(range / 2) mod 2**range`
+                // this requires both terms to be reduced
+                assert_eq!(e.bitwidth, UBitwidth::B128);
+
+                let left = self.fold_uint_expression(left);
+                let right = self.fold_uint_expression(right);
+
+                let limb_max = T::from(2).pow(range / 2) - T::from(1);
+                let limb_product_max = limb_max.clone() * limb_max;
+                let max = limb_product_max.clone()
+                    + (limb_product_max.clone() + limb_product_max) * T::from(2).pow(range / 2);
+
+                // the sum of the limb products must not overflow the field
+                assert!(max.bits() < max_bitwidth as u32);
+
+                UExpression::mult(force_reduce(left), force_reduce(right)).with_max(max)
+            }
             Mult(box left, box right) => {
                 // reduce the two terms
                 let left = self.fold_uint_expression(left);
@@ -337,9 +358,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                 let e = self.fold_uint_expression(e);
 
                 let e_max: num_bigint::BigUint = e.metadata.as_ref().unwrap().max.to_biguint();
-                let max = e_max
-                    .shl(by as usize)
-                    .bitand(&(2_u128.pow(range as u32) - 1).into());
+                let max = e_max.shl(by as usize).bitand(&range_max.to_biguint());
 
                 let max = T::try_from(max).unwrap();
 
@@ -350,9 +369,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                 let e = self.fold_uint_expression(e);
 
                 let e_max: num_bigint::BigUint = e.metadata.as_ref().unwrap().max.to_biguint();
-                let max = e_max
-                    .bitand(&(2_u128.pow(range as u32) - 1).into())
-                    .shr(by as usize);
+                let max = e_max.bitand(&range_max.to_biguint()).shr(by as usize);
 
                 let max = T::try_from(max).unwrap();
 
@@ -418,7 +435,17 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                 ZirExpressionList::EmbedCall(embed, generics, arguments),
             ) => {
                 match embed {
-                    FlatEmbed::U64FromBits => {
+                    FlatEmbed::U128FromBits => {
+                        assert_eq!(lhs.len(), 1);
+                        self.register(
+                            lhs[0].clone(),
+                            UMetadata {
+                                max: T::from(2).pow(128) - T::from(1),
+                                should_reduce: ShouldReduce::False,
+                            },
+                        );
+                    }
+                    FlatEmbed::U64FromBits | FlatEmbed::I64FromBits => {
                         assert_eq!(lhs.len(), 1);
                         self.register(
                             lhs[0].clone(),
@@ -428,7 +455,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                             },
                         );
                     }
-                    FlatEmbed::U32FromBits => {
+                    FlatEmbed::U32FromBits | FlatEmbed::I32FromBits => {
                         assert_eq!(lhs.len(), 1);
                         self.register(
                             lhs[0].clone(),
@@ -438,7 +465,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                             },
                         );
                     }
-                    FlatEmbed::U16FromBits => {
+                    FlatEmbed::U16FromBits | FlatEmbed::I16FromBits => {
                         assert_eq!(lhs.len(), 1);
                         self.register(
                             lhs[0].clone(),
@@ -448,7 +475,7 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                             },
                         );
                     }
-                    FlatEmbed::U8FromBits => {
+                    FlatEmbed::U8FromBits | FlatEmbed::I8FromBits => {
                         assert_eq!(lhs.len(), 1);
                         self.register(
                             lhs[0].clone(),
@@ -465,7 +492,12 @@ impl<'ast, T: Field> Folder<'ast, T> for UintOptimizer<'ast, T> {
                     FlatEmbed::U8ToBits
                     | FlatEmbed::U16ToBits
                     | FlatEmbed::U32ToBits
-                    | FlatEmbed::U64ToBits => {
+                    | FlatEmbed::U64ToBits
+                    | FlatEmbed::U128ToBits
+                    | FlatEmbed::I8ToBits
+                    | FlatEmbed::I16ToBits
+                    | FlatEmbed::I32ToBits
+                    | FlatEmbed::I64ToBits => {
                         vec![ZirStatement::MultipleDefinition(
                             lhs,
                             ZirExpressionList::EmbedCall(
@@ -127,6 +127,54 @@ impl<'ast> VariableWriteRemover {
                                             ConditionalKind::IfElse,
                                         )
                                         .into(),
+                                        Type::Enum(..) => EnumExpression::conditional(
+                                            BooleanExpression::UintEq(EqExpression::new(
+                                                i.into(),
+                                                head.clone(),
+                                            )),
+                                            match Self::choose_many(
+                                                EnumExpression::select(base.clone(), i).into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            ) {
+                                                TypedExpression::Enum(e) => e,
+                                                e => unreachable!(
+                                            "the interior was expected to be an enum, was {}",
+                                            e.get_type()
+                                        ),
+                                            },
+                                            EnumExpression::select(base.clone(), i),
+                                            ConditionalKind::IfElse,
+                                        )
+                                        .into(),
+                                        Type::BoundedArray(..) => {
+                                            BoundedArrayExpression::conditional(
+                                                BooleanExpression::UintEq(EqExpression::new(
+                                                    i.into(),
+                                                    head.clone(),
+                                                )),
+                                                match Self::choose_many(
+                                                    BoundedArrayExpression::select(
+                                                        base.clone(),
+                                                        i,
+                                                    )
+                                                    .into(),
+                                                    tail.clone(),
+                                                    new_expression.clone(),
+                                                    statements,
+                                                ) {
+                                                    TypedExpression::BoundedArray(e) => e,
+                                                    e => unreachable!(
+                                            "the interior was expected to be a bounded array, was {}",
+                                            e.get_type()
+                                        ),
+                                                },
+                                                BoundedArrayExpression::select(base.clone(), i),
+                                                ConditionalKind::IfElse,
+                                            )
+                                            .into()
+                                        }
                                         Type::FieldElement => FieldElementExpression::conditional(
                                             BooleanExpression::UintEq(EqExpression::new(
                                                 i.into(),
@@ -306,6 +354,36 @@ impl<'ast> VariableWriteRemover {
                                             TupleExpression::member(base.clone(), member.id).into()
                                         }
                                     }
+                                    Type::Enum(..) => {
+                                        if member.id == head {
+                                            Self::choose_many(
+                                                EnumExpression::member(base.clone(), head.clone())
+                                                    .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            )
+                                        } else {
+                                            EnumExpression::member(base.clone(), member.id).into()
+                                        }
+                                    }
+                                    Type::BoundedArray(..) => {
+                                        if member.id == head {
+                                            Self::choose_many(
+                                                BoundedArrayExpression::member(
+                                                    base.clone(),
+                                                    head.clone(),
+                                                )
+                                                .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            )
+                                        } else {
+                                            BoundedArrayExpression::member(base.clone(), member.id)
+                                                .into()
+                                        }
+                                    }
                                 })
                                 .collect(),
                         )
@@ -408,6 +486,31 @@ impl<'ast> VariableWriteRemover {
                                             TupleExpression::element(base.clone(), i).into()
                                         }
                                     }
+                                    Type::Enum(..) => {
+                                        if i == head {
+                                            Self::choose_many(
+                                                EnumExpression::element(base.clone(), head).into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            )
+                                        } else {
+                                            EnumExpression::element(base.clone(), i).into()
+                                        }
+                                    }
+                                    Type::BoundedArray(..) => {
+                                        if i == head {
+                                            Self::choose_many(
+                                                BoundedArrayExpression::element(base.clone(), head)
+                                                    .into(),
+                                                tail.clone(),
+                                                new_expression.clone(),
+                                                statements,
+                                            )
+                                        } else {
+                                            BoundedArrayExpression::element(base.clone(), i).into()
+                                        }
+                                    }
                                 })
                                 .collect(),
                         )
@@ -516,6 +619,14 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for VariableWriteRemover {
                         Type::Tuple(tuple_ty) => TupleExpression::identifier(variable.id.clone())
                             .annotate(tuple_ty)
                             .into(),
+                        Type::Enum(enum_ty) => EnumExpression::identifier(variable.id.clone())
+                            .annotate(enum_ty)
+                            .into(),
+                        Type::BoundedArray(array_ty) => {
+                            BoundedArrayExpression::identifier(variable.id.clone())
+                                .annotate(array_ty)
+                                .into()
+                        }
                     };
 
                     let base = self.fold_expression(base)?;
@@ -169,7 +169,7 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                     }
                 }
             }
-            ZirStatement::IfElse(e, consequence, alternative) => {
+            ZirStatement::IfElse(e, consequence, alternative, isolated) => {
                 match self.fold_boolean_expression(e)? {
                     BooleanExpression::Value(true) => Ok(consequence
                         .into_iter()
@@ -201,6 +201,7 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                             .into_iter()
                             .flatten()
                             .collect(),
+                        isolated,
                     )]),
                 }
             }
@@ -641,7 +642,7 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                 match (e1.into_inner(), e2.into_inner()) {
                     (UExpressionInner::Value(0), e) | (e, UExpressionInner::Value(0)) => Ok(e),
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => Ok(
-                        UExpressionInner::Value((n1 + n2) % 2_u128.pow(bitwidth.to_usize() as u32)),
+                        UExpressionInner::Value(n1.wrapping_add(n2) & bitwidth.max_value()),
                     ),
                     (e1, e2) => Ok(UExpressionInner::Add(
                         box e1.annotate(bitwidth),
@@ -655,11 +656,9 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
 
                 match (e1.into_inner(), e2.into_inner()) {
                     (e, UExpressionInner::Value(0)) => Ok(e),
-                    (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
-                        Ok(UExpressionInner::Value(
-                            n1.wrapping_sub(n2) % 2_u128.pow(bitwidth.to_usize() as u32),
-                        ))
-                    }
+                    (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => Ok(
+                        UExpressionInner::Value(n1.wrapping_sub(n2) & bitwidth.max_value()),
+                    ),
                     (e1, e2) => Ok(UExpressionInner::Sub(
                         box e1.annotate(bitwidth),
                         box e2.annotate(bitwidth),
@@ -676,7 +675,7 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                     }
                     (e, UExpressionInner::Value(1)) | (UExpressionInner::Value(1), e) => Ok(e),
                     (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => Ok(
-                        UExpressionInner::Value((n1 * n2) % 2_u128.pow(bitwidth.to_usize() as u32)),
+                        UExpressionInner::Value(n1.wrapping_mul(n2) & bitwidth.max_value()),
                     ),
                     (e1, e2) => Ok(UExpressionInner::Mult(
                         box e1.annotate(bitwidth),
@@ -691,9 +690,9 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                 match (e1.into_inner(), e2.into_inner()) {
                     (_, UExpressionInner::Value(n)) if n == 0 => Err(Error::DivisionByZero),
                     (e, UExpressionInner::Value(n)) if n == 1 => Ok(e),
-                    (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => Ok(
-                        UExpressionInner::Value((n1 / n2) % 2_u128.pow(bitwidth.to_usize() as u32)),
-                    ),
+                    (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
+                        Ok(UExpressionInner::Value(n1 / n2))
+                    }
                     (e1, e2) => Ok(UExpressionInner::Div(
                         box e1.annotate(bitwidth),
                         box e2.annotate(bitwidth),
@@ -705,9 +704,9 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                 let e2 = self.fold_uint_expression(e2)?;
 
                 match (e1.into_inner(), e2.into_inner()) {
-                    (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => Ok(
-                        UExpressionInner::Value((n1 % n2) % 2_u128.pow(bitwidth.to_usize() as u32)),
-                    ),
+                    (UExpressionInner::Value(n1), UExpressionInner::Value(n2)) => {
+                        Ok(UExpressionInner::Value(n1 % n2))
+                    }
                     (e1, e2) => Ok(UExpressionInner::Rem(
                         box e1.annotate(bitwidth),
                         box e2.annotate(bitwidth),
@@ -735,7 +734,7 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
 
                 match (e1.into_inner(), e2.into_inner()) {
                     (e, UExpressionInner::Value(n)) | (UExpressionInner::Value(n), e)
-                        if n == 2_u128.pow(bitwidth.to_usize() as u32) - 1 =>
+                        if n == bitwidth.max_value() =>
                     {
                         Ok(e)
                     }
@@ -758,7 +757,7 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                 match (e1.into_inner(), e2.into_inner()) {
                     (e, UExpressionInner::Value(0)) | (UExpressionInner::Value(0), e) => Ok(e),
                     (_, UExpressionInner::Value(n)) | (UExpressionInner::Value(n), _)
-                        if n == 2_u128.pow(bitwidth.to_usize() as u32) - 1 =>
+                        if n == bitwidth.max_value() =>
                     {
                         Ok(UExpressionInner::Value(n))
                     }
@@ -776,9 +775,9 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
                 match (e.into_inner(), by) {
                     (e, 0) => Ok(e),
                     (_, by) if by >= bitwidth as u32 => Ok(UExpressionInner::Value(0)),
-                    (UExpressionInner::Value(n), by) => Ok(UExpressionInner::Value(
-                        (n << by) & (2_u128.pow(bitwidth as u32) - 1),
-                    )),
+                    (UExpressionInner::Value(n), by) => {
+                        Ok(UExpressionInner::Value((n << by) & bitwidth.max_value()))
+                    }
                     (e, by) => Ok(UExpressionInner::LeftShift(box e.annotate(bitwidth), by)),
                 }
             }
@@ -794,9 +793,9 @@ impl<'ast, T: Field> ResultFolder<'ast, T> for ZirPropagator<'ast, T> {
             UExpressionInner::Not(box e) => {
                 let e = self.fold_uint_expression(e)?;
                 match e.into_inner() {
-                    UExpressionInner::Value(n) => Ok(UExpressionInner::Value(
-                        !n & (2_u128.pow(bitwidth as u32) - 1),
-                    )),
+                    UExpressionInner::Value(n) => {
+                        Ok(UExpressionInner::Value(!n & bitwidth.max_value()))
+                    }
                     e => Ok(UExpressionInner::Not(box e.annotate(bitwidth))),
                 }
             }
@@ -112,7 +112,7 @@ impl<'a, T: Field + ArkFieldExtensions, I: IntoIterator<Item = Statement<'a, T>>
                 }));
 
                 for statement in self.program.statements {
-                    if let Statement::Constraint(quad, lin, _) = statement {
+                    if let Statement::Constraint(quad, lin, ..) = statement {
                         let a = ark_combination(
                             quad.left.clone().into_canonical(),
                             &mut cs,
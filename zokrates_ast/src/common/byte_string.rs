@@ -0,0 +1,59 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// The bytes of a byte string literal, such as `b"abc"` or `hex"616263"`
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct ByteString(pub Vec<u8>);
+
+impl fmt::Display for ByteString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // display the literal the way it could have been written
+        match self
+            .0
+            .iter()
+            .all(|b| (b.is_ascii_graphic() && *b != b'"') || *b == b' ')
+        {
+            true => write!(f, "b\"{}\"", String::from_utf8_lossy(&self.0)),
+            false => write!(
+                f,
+                "hex\"{}\"",
+                self.0
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect::<String>()
+            ),
+        }
+    }
+}
+
+impl ByteString {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<&str> for ByteString {
+    fn from(s: &str) -> Self {
+        ByteString(s.as_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ByteString::from("hello world").to_string(),
+            "b\"hello world\""
+        );
+        assert_eq!(ByteString(vec![0x00, 0xab]).to_string(), "hex\"00ab\"");
+        assert_eq!(ByteString::from("\"").to_string(), "hex\"22\"");
+    }
+}
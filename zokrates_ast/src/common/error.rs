@@ -31,6 +31,10 @@ pub enum RuntimeError {
     SourceAssemblyConstraint(SourceMetadata),
     ArgumentBitness,
     SelectRangeCheck,
+    EnumArgument,
+    BoundedArrayAccess,
+    BoundedArrayCapacity,
+    BoundedArrayArgument,
 }
 
 impl From<crate::zir::RuntimeError> for RuntimeError {
@@ -44,6 +48,10 @@ impl From<crate::zir::RuntimeError> for RuntimeError {
             crate::zir::RuntimeError::IncompleteDynamicRange => {
                 RuntimeError::IncompleteDynamicRange
             }
+            crate::zir::RuntimeError::EnumArgument => RuntimeError::EnumArgument,
+            crate::zir::RuntimeError::BoundedArrayAccess => RuntimeError::BoundedArrayAccess,
+            crate::zir::RuntimeError::BoundedArrayCapacity => RuntimeError::BoundedArrayCapacity,
+            crate::zir::RuntimeError::BoundedArrayArgument => RuntimeError::BoundedArrayArgument,
         }
     }
 }
@@ -59,6 +67,10 @@ impl RuntimeError {
                 | Inverse
                 | SelectRangeCheck
                 | ArgumentBitness
+                | EnumArgument
+                | BoundedArrayAccess
+                | BoundedArrayCapacity
+                | BoundedArrayArgument
                 | IncompleteDynamicRange
         )
     }
@@ -104,6 +116,10 @@ impl fmt::Display for RuntimeError {
             }
             ArgumentBitness => "Argument bitness check failed",
             SelectRangeCheck => "Out of bounds array access",
+            EnumArgument => "Argument is not a valid enum value",
+            BoundedArrayAccess => "Out of bounds bounded array access",
+            BoundedArrayCapacity => "Bounded array capacity exceeded",
+            BoundedArrayArgument => "Argument is not a valid bounded array",
         };
 
         write!(f, "{}", msg)
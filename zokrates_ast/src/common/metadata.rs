@@ -7,6 +7,9 @@ pub struct SourceMetadata {
     pub file: String,
     pub position: Position,
     pub message: Option<String>,
+    /// The functions called to reach this position, outermost first. Only known once calls are inlined
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub call_stack: Vec<String>,
 }
 
 impl SourceMetadata {
@@ -15,12 +18,25 @@ impl SourceMetadata {
             file,
             position,
             message: None,
+            call_stack: vec![],
         }
     }
     pub fn message(mut self, message: Option<String>) -> Self {
         self.message = message;
         self
     }
+    pub fn call_stack(mut self, call_stack: Vec<String>) -> Self {
+        self.call_stack = call_stack;
+        self
+    }
+
+    /// The position followed by the functions called to reach it, innermost first
+    pub fn location(&self) -> String {
+        std::iter::once(format!("{}:{}", self.file, self.position))
+            .chain(self.call_stack.iter().rev().map(|f| format!("in {}", f)))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
 }
 
 impl fmt::Display for SourceMetadata {
@@ -1,3 +1,4 @@
+mod byte_string;
 pub mod embed;
 mod error;
 mod format_string;
@@ -5,11 +6,14 @@ mod metadata;
 mod parameter;
 mod solvers;
 mod variable;
+mod warning;
 
+pub use self::byte_string::ByteString;
 pub use self::embed::FlatEmbed;
 pub use self::error::RuntimeError;
 pub use self::metadata::SourceMetadata;
 pub use self::parameter::Parameter;
 pub use self::solvers::Solver;
 pub use self::variable::Variable;
+pub use self::warning::{Lint, Warning, Warnings};
 pub use format_string::FormatString;
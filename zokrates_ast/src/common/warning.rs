@@ -0,0 +1,174 @@
+use crate::untyped::Position;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// A category of warnings, which can be allowed or denied as a whole
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Lint {
+    /// A variable or a parameter which is never read
+    UnusedVariables,
+    /// An import none of whose symbols is used
+    UnusedImports,
+    /// A private function which is never called
+    UnusedFunctions,
+    /// A variable defined from a function call which is never read
+    UnusedReturnValues,
+    /// A value assigned with `<--` in an `asm` block which is never constrained
+    UnconstrainedAssignments,
+    /// A variable declared with the name of another one in scope
+    Shadowing,
+    /// A condition or a constraint which does not depend on any variable
+    ConstantConditions,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 7] = [
+        Lint::UnusedVariables,
+        Lint::UnusedImports,
+        Lint::UnusedFunctions,
+        Lint::UnusedReturnValues,
+        Lint::UnconstrainedAssignments,
+        Lint::Shadowing,
+        Lint::ConstantConditions,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lint::UnusedVariables => "unused_variables",
+            Lint::UnusedImports => "unused_imports",
+            Lint::UnusedFunctions => "unused_functions",
+            Lint::UnusedReturnValues => "unused_return_values",
+            Lint::UnconstrainedAssignments => "unconstrained_assignments",
+            Lint::Shadowing => "shadowing",
+            Lint::ConstantConditions => "constant_conditions",
+        }
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Lint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .iter()
+            .find(|l| l.name() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown lint `{}`", s))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Warning {
+    pub file: PathBuf,
+    pub position: Position,
+    pub lint: Lint,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new<F: Into<PathBuf>, S: Into<String>>(
+        lint: Lint,
+        file: F,
+        position: Position,
+        message: S,
+    ) -> Self {
+        Warning {
+            file: file.into(),
+            position,
+            lint,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}\n\twarning[{}]: {}",
+            self.file.display(),
+            self.position,
+            self.lint,
+            self.message
+        )
+    }
+}
+
+/// The warnings found during compilation, ordered by location. The same warning can be found more than once, for
+/// example when a function is checked for different type arguments, and is only kept once
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Warnings(BTreeSet<Warning>);
+
+impl Warnings {
+    pub fn push(&mut self, warning: Warning) {
+        self.0.insert(warning);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Warning> {
+        self.0.iter()
+    }
+}
+
+impl Extend<Warning> for Warnings {
+    fn extend<I: IntoIterator<Item = Warning>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for Warnings {
+    type Item = Warning;
+    type IntoIter = std::collections::btree_set::IntoIter<Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lint_names() {
+        for lint in Lint::ALL.iter() {
+            assert_eq!(lint.name().parse::<Lint>(), Ok(*lint));
+        }
+
+        assert_eq!(
+            "unused_things".parse::<Lint>(),
+            Err("Unknown lint `unused_things`".to_string())
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        let warning = Warning::new(
+            Lint::Shadowing,
+            "foo.zok",
+            Position { line: 1, col: 1 },
+            "Variable `a` shadows a previous declaration",
+        );
+
+        let mut warnings = Warnings::default();
+        warnings.push(warning.clone());
+        warnings.push(warning);
+
+        assert_eq!(warnings.len(), 1);
+    }
+}
@@ -76,6 +76,7 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
                 .map(|(t, e)| (t, e.into_iter().map(|e| f.fold_expression(e)).collect()))
                 .collect(),
         )],
+        FlatStatement::SourceMetadata(metadata) => vec![FlatStatement::SourceMetadata(metadata)],
     }
 }
 
@@ -8,10 +8,10 @@
 pub mod folder;
 pub mod utils;
 
-use crate::common::FormatString;
 pub use crate::common::Parameter;
 pub use crate::common::RuntimeError;
 pub use crate::common::Variable;
+use crate::common::{FormatString, SourceMetadata};
 
 pub use utils::{
     flat_expression_from_bits, flat_expression_from_expression_summands,
@@ -87,6 +87,8 @@ pub enum FlatStatement<'ast, T> {
     Definition(Variable, FlatExpression<T>),
     Directive(FlatDirective<'ast, T>),
     Log(FormatString, Vec<(ConcreteType, Vec<FlatExpression<T>>)>),
+    /// The position in the source of the statements which follow, if known
+    SourceMetadata(Option<SourceMetadata>),
 }
 
 impl<'ast, T: Field> fmt::Display for FlatStatement<'ast, T> {
@@ -120,6 +122,10 @@ impl<'ast, T: Field> fmt::Display for FlatStatement<'ast, T> {
                     .collect::<Vec<_>>()
                     .join(", ")
             ),
+            FlatStatement::SourceMetadata(Some(ref metadata)) => {
+                write!(f, "// {}", metadata.location())
+            }
+            FlatStatement::SourceMetadata(None) => write!(f, "// unknown position"),
         }
     }
 }
@@ -176,6 +182,7 @@ impl<'ast, T: Field> FlatStatement<'ast, T> {
                     })
                     .collect(),
             ),
+            FlatStatement::SourceMetadata(metadata) => FlatStatement::SourceMetadata(metadata),
         }
     }
 }
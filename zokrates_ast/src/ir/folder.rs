@@ -64,13 +64,16 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
                 .flat_map(|s| f.fold_statement(s))
                 .collect(),
         )],
-        Statement::Constraint(quad, lin, message) => vec![Statement::Constraint(
+        Statement::Constraint(quad, lin, message, metadata) => vec![Statement::Constraint(
             f.fold_quadratic_combination(quad),
             f.fold_linear_combination(lin),
             message,
+            metadata,
         )],
-        Statement::Directive(dir) => vec![Statement::Directive(f.fold_directive(dir))],
-        Statement::Log(l, e) => vec![Statement::Log(
+        Statement::Directive(dir, metadata) => {
+            vec![Statement::Directive(f.fold_directive(dir), metadata)]
+        }
+        Statement::Log(l, e, metadata) => vec![Statement::Log(
             l,
             e.into_iter()
                 .map(|(t, e)| {
@@ -82,6 +85,7 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
                     )
                 })
                 .collect(),
+            metadata,
         )],
     }
 }
@@ -1,3 +1,4 @@
+use crate::common::SourceMetadata;
 use crate::flat::{FlatDirective, FlatExpression, FlatProgIterator, FlatStatement, Variable};
 use crate::ir::{Directive, LinComb, ProgIterator, QuadComb, Statement};
 use zokrates_field::Field;
@@ -20,8 +21,13 @@ impl<T: Field> QuadComb<T> {
 pub fn from_flat<'ast, T: Field, I: IntoIterator<Item = FlatStatement<'ast, T>>>(
     flat_prog_iterator: FlatProgIterator<'ast, T, I>,
 ) -> ProgIterator<T, impl IntoIterator<Item = Statement<'ast, T>>> {
+    let mut metadata = None;
+
     ProgIterator {
-        statements: flat_prog_iterator.statements.into_iter().map(Into::into),
+        statements: flat_prog_iterator
+            .statements
+            .into_iter()
+            .filter_map(move |s| from_flat_statement(s, &mut metadata)),
         arguments: flat_prog_iterator.arguments,
         return_count: flat_prog_iterator.return_count,
     }
@@ -52,37 +58,54 @@ impl<T: Field> From<FlatExpression<T>> for LinComb<T> {
     }
 }
 
-impl<'ast, T: Field> From<FlatStatement<'ast, T>> for Statement<'ast, T> {
-    fn from(flat_statement: FlatStatement<'ast, T>) -> Statement<'ast, T> {
-        match flat_statement {
-            FlatStatement::Block(statements) => {
-                Statement::Block(statements.into_iter().map(Statement::from).collect())
-            }
-            FlatStatement::Condition(linear, quadratic, message) => match quadratic {
-                FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
-                    QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
-                    linear.into(),
-                    Some(message),
-                ),
-                e => Statement::Constraint(LinComb::from(e).into(), linear.into(), Some(message)),
-            },
-            FlatStatement::Definition(var, quadratic) => match quadratic {
-                FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
-                    QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
-                    var.into(),
-                    None,
-                ),
-                e => Statement::Constraint(LinComb::from(e).into(), var.into(), None),
-            },
-            FlatStatement::Directive(ds) => Statement::Directive(ds.into()),
-            FlatStatement::Log(l, expressions) => Statement::Log(
-                l,
-                expressions
-                    .into_iter()
-                    .map(|(t, e)| (t, e.into_iter().map(LinComb::from).collect()))
-                    .collect(),
-            ),
+// convert a flat statement, attaching the position of the last `SourceMetadata` statement found, which is removed
+fn from_flat_statement<'ast, T: Field>(
+    flat_statement: FlatStatement<'ast, T>,
+    metadata: &mut Option<SourceMetadata>,
+) -> Option<Statement<'ast, T>> {
+    match flat_statement {
+        FlatStatement::SourceMetadata(m) => {
+            *metadata = m;
+            None
         }
+        FlatStatement::Block(statements) => Some(Statement::Block(
+            statements
+                .into_iter()
+                .filter_map(|s| from_flat_statement(s, metadata))
+                .collect(),
+        )),
+        FlatStatement::Condition(linear, quadratic, message) => Some(match quadratic {
+            FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
+                QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
+                linear.into(),
+                Some(message),
+                metadata.clone(),
+            ),
+            e => Statement::Constraint(
+                LinComb::from(e).into(),
+                linear.into(),
+                Some(message),
+                metadata.clone(),
+            ),
+        }),
+        FlatStatement::Definition(var, quadratic) => Some(match quadratic {
+            FlatExpression::Mult(box lhs, box rhs) => Statement::Constraint(
+                QuadComb::from_linear_combinations(lhs.into(), rhs.into()),
+                var.into(),
+                None,
+                metadata.clone(),
+            ),
+            e => Statement::Constraint(LinComb::from(e).into(), var.into(), None, metadata.clone()),
+        }),
+        FlatStatement::Directive(ds) => Some(Statement::Directive(ds.into(), metadata.clone())),
+        FlatStatement::Log(l, expressions) => Some(Statement::Log(
+            l,
+            expressions
+                .into_iter()
+                .map(|(t, e)| (t, e.into_iter().map(LinComb::from).collect()))
+                .collect(),
+            metadata.clone(),
+        )),
     }
 }
 
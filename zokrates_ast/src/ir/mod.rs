@@ -1,4 +1,4 @@
-use crate::common::FormatString;
+use crate::common::{FormatString, SourceMetadata};
 use crate::typed::ConcreteType;
 use derivative::Derivative;
 use serde::{Deserialize, Serialize};
@@ -27,6 +27,8 @@ pub use crate::common::Variable;
 
 pub use self::witness::Witness;
 
+/// A statement of the IR. Each statement can carry the position in the source it was compiled from. This position
+/// is ignored when comparing statements, and is serialized separately, see `ProgIterator::serialize`
 #[derive(Debug, Serialize, Deserialize, Clone, Derivative)]
 #[derivative(Hash, PartialEq, Eq)]
 pub enum Statement<'ast, T> {
@@ -36,21 +38,64 @@ pub enum Statement<'ast, T> {
         QuadComb<T>,
         LinComb<T>,
         #[derivative(Hash = "ignore")] Option<RuntimeError>,
+        #[derivative(Hash = "ignore", PartialEq = "ignore")]
+        #[serde(skip)]
+        Option<SourceMetadata>,
+    ),
+    Directive(
+        #[serde(borrow)] Directive<'ast, T>,
+        #[derivative(Hash = "ignore", PartialEq = "ignore")]
+        #[serde(skip)]
+        Option<SourceMetadata>,
+    ),
+    Log(
+        FormatString,
+        Vec<(ConcreteType, Vec<LinComb<T>>)>,
+        #[derivative(Hash = "ignore", PartialEq = "ignore")]
+        #[serde(skip)]
+        Option<SourceMetadata>,
     ),
-    #[serde(borrow)]
-    Directive(Directive<'ast, T>),
-    Log(FormatString, Vec<(ConcreteType, Vec<LinComb<T>>)>),
 }
 
 pub type PublicInputs = BTreeSet<Variable>;
 
 impl<'ast, T: Field> Statement<'ast, T> {
     pub fn definition<U: Into<QuadComb<T>>>(v: Variable, e: U) -> Self {
-        Statement::Constraint(e.into(), v.into(), None)
+        Statement::Constraint(e.into(), v.into(), None, None)
     }
 
     pub fn constraint<U: Into<QuadComb<T>>, V: Into<LinComb<T>>>(quad: U, lin: V) -> Self {
-        Statement::Constraint(quad.into(), lin.into(), None)
+        Statement::Constraint(quad.into(), lin.into(), None, None)
+    }
+}
+
+impl<'ast, T> Statement<'ast, T> {
+    /// The position in the source this statement was compiled from, if known
+    pub fn metadata(&self) -> Option<&SourceMetadata> {
+        match self {
+            Statement::Block(..) => None,
+            Statement::Constraint(.., metadata)
+            | Statement::Directive(_, metadata)
+            | Statement::Log(.., metadata) => metadata.as_ref(),
+        }
+    }
+
+    /// Set the position in the source this statement was compiled from. Blocks have no position of their own, so the
+    /// statements they contain are updated instead
+    pub fn with_metadata(self, metadata: Option<SourceMetadata>) -> Self {
+        match self {
+            Statement::Block(statements) => Statement::Block(
+                statements
+                    .into_iter()
+                    .map(|s| s.with_metadata(metadata.clone()))
+                    .collect(),
+            ),
+            Statement::Constraint(quad, lin, error, _) => {
+                Statement::Constraint(quad, lin, error, metadata)
+            }
+            Statement::Directive(d, _) => Statement::Directive(d, metadata),
+            Statement::Log(l, expressions, _) => Statement::Log(l, expressions, metadata),
+        }
     }
 }
 
@@ -82,17 +127,27 @@ impl<'ast, T: Field> fmt::Display for Directive<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Statement<'ast, T> {
+    /// Display this statement followed by its position in the source, if known
+    fn display_with_location(&self) -> String {
+        match self.metadata() {
+            Some(metadata) => format!("{} // {}", self, metadata.location()),
+            None => self.to_string(),
+        }
+    }
+}
+
 impl<'ast, T: Field> fmt::Display for Statement<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Statement::Block(ref statements) => {
                 writeln!(f, "{{")?;
                 for s in statements {
-                    writeln!(f, "{}", s)?;
+                    writeln!(f, "{}", s.display_with_location())?;
                 }
                 write!(f, "}}")
             }
-            Statement::Constraint(ref quad, ref lin, ref error) => write!(
+            Statement::Constraint(ref quad, ref lin, ref error, _) => write!(
                 f,
                 "{} == {}{}",
                 quad,
@@ -102,8 +157,8 @@ impl<'ast, T: Field> fmt::Display for Statement<'ast, T> {
                     .map(|e| format!(" // {}", e))
                     .unwrap_or_else(|| "".to_string())
             ),
-            Statement::Directive(ref s) => write!(f, "{}", s),
-            Statement::Log(ref s, ref expressions) => write!(
+            Statement::Directive(ref s, _) => write!(f, "{}", s),
+            Statement::Log(ref s, ref expressions, _) => write!(
                 f,
                 "log(\"{}\", {})",
                 s,
@@ -215,7 +270,7 @@ impl<'ast, T: Field> fmt::Display for Prog<'ast, T> {
             returns,
         )?;
         for s in &self.statements {
-            writeln!(f, "\t{}", s)?;
+            writeln!(f, "\t{}", s.display_with_location())?;
         }
 
         writeln!(f, "\treturn {}", returns)?;
@@ -226,6 +281,7 @@ impl<'ast, T: Field> fmt::Display for Prog<'ast, T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::untyped::Position;
     use zokrates_field::Bn128Field;
 
     mod statement {
@@ -240,8 +296,37 @@ mod tests {
                 ),
                 Variable::new(42).into(),
                 None,
+                None,
             );
             assert_eq!(format!("{}", c), "(1 * _42) * (1 * _42) == 1 * _42")
         }
     }
+
+    #[test]
+    fn print_source_positions() {
+        // the position of a statement is printed alongside it, with the calls which led to it, including inside blocks
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![],
+            statements: vec![
+                Statement::definition(Variable::public(0), Variable::one()).with_metadata(Some(
+                    SourceMetadata::new("foo.zok".into(), Position { line: 2, col: 5 })
+                        .call_stack(vec!["bar".into(), "foo".into()]),
+                )),
+                Statement::Block(vec![Statement::definition(
+                    Variable::new(0),
+                    Variable::one(),
+                )])
+                .with_metadata(Some(SourceMetadata::new(
+                    "foo.zok".into(),
+                    Position { line: 3, col: 5 },
+                ))),
+            ],
+            return_count: 1,
+        };
+
+        assert_eq!(
+            format!("{}", p),
+            "def main() -> (~out_0) {\n\t(1 * ~one) * (1 * ~one) == 1 * ~out_0 // foo.zok:2:5, in foo, in bar\n\t{\n(1 * ~one) * (1 * ~one) == 1 * _0 // foo.zok:3:5\n}\n\treturn ~out_0\n}\n"
+        );
+    }
 }
@@ -1,7 +1,11 @@
 use crate::ir::check::UnconstrainedVariableDetector;
 
-use super::{ProgIterator, Statement};
+use super::{Directive, LinComb, ProgIterator, QuadComb, RuntimeError, Statement};
+use crate::common::{FormatString, SourceMetadata};
+use crate::typed::ConcreteType;
+use serde::{Deserialize, Serialize};
 use serde_cbor::{self, StreamDeserializer};
+use std::collections::HashMap;
 use std::io::{Read, Write};
 use zokrates_field::*;
 
@@ -9,6 +13,37 @@ type DynamicError = Box<dyn std::error::Error>;
 
 const ZOKRATES_MAGIC: &[u8; 4] = &[0x5a, 0x4f, 0x4b, 0];
 const ZOKRATES_VERSION_2: &[u8; 4] = &[0, 0, 0, 2];
+const ZOKRATES_VERSION_3: &[u8; 4] = &[0, 0, 0, 3];
+
+/// An entry of the stream of statements. The positions in the source of the statements form a side-table: each of
+/// them is written once, before the first statement which refers to it by its index in the table
+#[derive(Serialize, Deserialize)]
+enum Entry<'ast, T> {
+    Source(SourceMetadata),
+    Statement(#[serde(borrow)] Statement<'ast, T>, Option<usize>),
+}
+
+/// A statement as written in version 2, before statements carried their position in the source
+#[derive(Deserialize)]
+#[cfg_attr(test, derive(Serialize))]
+enum StatementV2<'ast, T> {
+    Constraint(QuadComb<T>, LinComb<T>, Option<RuntimeError>),
+    #[serde(borrow)]
+    Directive(Directive<'ast, T>),
+    Log(FormatString, Vec<(ConcreteType, Vec<LinComb<T>>)>),
+}
+
+impl<'ast, T> From<StatementV2<'ast, T>> for Statement<'ast, T> {
+    fn from(s: StatementV2<'ast, T>) -> Self {
+        match s {
+            StatementV2::Constraint(quad, lin, error) => {
+                Statement::Constraint(quad, lin, error, None)
+            }
+            StatementV2::Directive(d) => Statement::Directive(d, None),
+            StatementV2::Log(l, expressions) => Statement::Log(l, expressions, None),
+        }
+    }
+}
 
 #[derive(PartialEq, Eq, Debug)]
 pub enum ProgEnum<
@@ -61,11 +96,28 @@ impl<
 impl<'ast, T: Field, I: IntoIterator<Item = Statement<'ast, T>>> ProgIterator<'ast, T, I> {
     /// serialize a program iterator, returning the number of constraints serialized
     /// Note that we only return constraints, not other statements such as directives
-    pub fn serialize<W: Write>(self, mut w: W) -> Result<usize, DynamicError> {
+    pub fn serialize<W: Write>(self, w: W) -> Result<usize, DynamicError> {
+        self.serialize_version(w, false)
+    }
+
+    /// serialize a program iterator along with the positions in the source of its statements, which requires
+    /// version 3 of the format
+    pub fn serialize_with_source_map<W: Write>(self, w: W) -> Result<usize, DynamicError> {
+        self.serialize_version(w, true)
+    }
+
+    fn serialize_version<W: Write>(
+        self,
+        mut w: W,
+        source_map: bool,
+    ) -> Result<usize, DynamicError> {
         use super::folder::Folder;
 
         w.write_all(ZOKRATES_MAGIC)?;
-        w.write_all(ZOKRATES_VERSION_2)?;
+        w.write_all(match source_map {
+            true => ZOKRATES_VERSION_3,
+            false => ZOKRATES_VERSION_2,
+        })?;
         w.write_all(&T::id())?;
 
         serde_cbor::to_writer(&mut w, &self.arguments)?;
@@ -75,6 +127,8 @@ impl<'ast, T: Field, I: IntoIterator<Item = Statement<'ast, T>>> ProgIterator<'a
 
         let statements = self.statements.into_iter();
 
+        let mut source_map_indices = HashMap::new();
+
         let mut count = 0;
         for s in statements {
             if matches!(s, Statement::Constraint(..)) {
@@ -82,7 +136,25 @@ impl<'ast, T: Field, I: IntoIterator<Item = Statement<'ast, T>>> ProgIterator<'a
             }
             let s = unconstrained_variable_detector.fold_statement(s);
             for s in s {
-                serde_cbor::to_writer(&mut w, &s)?;
+                // in version 2, statements are written without their position
+                if !source_map {
+                    serde_cbor::to_writer(&mut w, &s)?;
+                    continue;
+                }
+
+                let index = match s.metadata() {
+                    Some(metadata) => Some(match source_map_indices.get(metadata) {
+                        Some(index) => *index,
+                        None => {
+                            serde_cbor::to_writer(&mut w, &Entry::<T>::Source(metadata.clone()))?;
+                            let index = source_map_indices.len();
+                            source_map_indices.insert(metadata.clone(), index);
+                            index
+                        }
+                    }),
+                    None => None,
+                };
+                serde_cbor::to_writer(&mut w, &Entry::Statement(s, index))?;
             }
         }
 
@@ -93,27 +165,53 @@ impl<'ast, T: Field, I: IntoIterator<Item = Statement<'ast, T>>> ProgIterator<'a
     }
 }
 
+enum Stream<'de, R, T> {
+    V2(StreamDeserializer<'de, R, StatementV2<'de, T>>),
+    V3(StreamDeserializer<'de, R, Entry<'de, T>>),
+}
+
+/// The statements of a serialized program. In version 3, their positions in the source are resolved from the side-table
 pub struct UnwrappedStreamDeserializer<'de, R, T> {
-    s: StreamDeserializer<'de, R, T>,
+    s: Stream<'de, R, T>,
+    source_map: Vec<SourceMetadata>,
+}
+
+impl<'de, R, T> UnwrappedStreamDeserializer<'de, R, T> {
+    fn new(s: Stream<'de, R, T>) -> Self {
+        UnwrappedStreamDeserializer {
+            s,
+            source_map: vec![],
+        }
+    }
 }
 
 impl<'de, R: serde_cbor::de::Read<'de>, T: serde::Deserialize<'de>> Iterator
     for UnwrappedStreamDeserializer<'de, R, T>
 {
-    type Item = T;
-
-    fn next(&mut self) -> Option<T> {
-        self.s.next().transpose().unwrap()
+    type Item = Statement<'de, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.s {
+            Stream::V2(s) => s.next().transpose().unwrap().map(Statement::from),
+            Stream::V3(s) => loop {
+                match s.next().transpose().unwrap()? {
+                    Entry::Source(metadata) => self.source_map.push(metadata),
+                    Entry::Statement(s, index) => {
+                        return Some(s.with_metadata(index.map(|i| self.source_map[i].clone())))
+                    }
+                }
+            },
+        }
     }
 }
 
 impl<'de, R: Read>
     ProgEnum<
         'de,
-        UnwrappedStreamDeserializer<'de, serde_cbor::de::IoRead<R>, Statement<'de, Bls12_381Field>>,
-        UnwrappedStreamDeserializer<'de, serde_cbor::de::IoRead<R>, Statement<'de, Bn128Field>>,
-        UnwrappedStreamDeserializer<'de, serde_cbor::de::IoRead<R>, Statement<'de, Bls12_377Field>>,
-        UnwrappedStreamDeserializer<'de, serde_cbor::de::IoRead<R>, Statement<'de, Bw6_761Field>>,
+        UnwrappedStreamDeserializer<'de, serde_cbor::de::IoRead<R>, Bls12_381Field>,
+        UnwrappedStreamDeserializer<'de, serde_cbor::de::IoRead<R>, Bn128Field>,
+        UnwrappedStreamDeserializer<'de, serde_cbor::de::IoRead<R>, Bls12_377Field>,
+        UnwrappedStreamDeserializer<'de, serde_cbor::de::IoRead<R>, Bw6_761Field>,
     >
 {
     pub fn deserialize(mut r: R) -> Result<Self, String> {
@@ -123,12 +221,12 @@ impl<'de, R: Read>
             .map_err(|_| String::from("Cannot read magic number"))?;
 
         if &magic == ZOKRATES_MAGIC {
-            // Check the version, 2
+            // Check the version, 2 or 3
             let mut version = [0; 4];
             r.read_exact(&mut version)
                 .map_err(|_| String::from("Cannot read version"))?;
 
-            if &version == ZOKRATES_VERSION_2 {
+            if &version == ZOKRATES_VERSION_2 || &version == ZOKRATES_VERSION_3 {
                 // Check the curve identifier, deserializing accordingly
                 let mut curve = [0; 4];
                 r.read_exact(&mut curve)
@@ -193,38 +291,50 @@ impl<'de, R: Read>
 
                 match curve {
                     m if m == Bls12_381Field::id() => {
-                        let s = p.into_iter::<Statement<Bls12_381Field>>();
+                        let s = match &version {
+                            ZOKRATES_VERSION_2 => Stream::V2(p.into_iter()),
+                            _ => Stream::V3(p.into_iter()),
+                        };
 
                         Ok(ProgEnum::Bls12_381Program(ProgIterator::new(
                             arguments,
-                            UnwrappedStreamDeserializer { s },
+                            UnwrappedStreamDeserializer::new(s),
                             return_count,
                         )))
                     }
                     m if m == Bn128Field::id() => {
-                        let s = p.into_iter::<Statement<Bn128Field>>();
+                        let s = match &version {
+                            ZOKRATES_VERSION_2 => Stream::V2(p.into_iter()),
+                            _ => Stream::V3(p.into_iter()),
+                        };
 
                         Ok(ProgEnum::Bn128Program(ProgIterator::new(
                             arguments,
-                            UnwrappedStreamDeserializer { s },
+                            UnwrappedStreamDeserializer::new(s),
                             return_count,
                         )))
                     }
                     m if m == Bls12_377Field::id() => {
-                        let s = p.into_iter::<Statement<Bls12_377Field>>();
+                        let s = match &version {
+                            ZOKRATES_VERSION_2 => Stream::V2(p.into_iter()),
+                            _ => Stream::V3(p.into_iter()),
+                        };
 
                         Ok(ProgEnum::Bls12_377Program(ProgIterator::new(
                             arguments,
-                            UnwrappedStreamDeserializer { s },
+                            UnwrappedStreamDeserializer::new(s),
                             return_count,
                         )))
                     }
                     m if m == Bw6_761Field::id() => {
-                        let s = p.into_iter::<Statement<Bw6_761Field>>();
+                        let s = match &version {
+                            ZOKRATES_VERSION_2 => Stream::V2(p.into_iter()),
+                            _ => Stream::V3(p.into_iter()),
+                        };
 
                         Ok(ProgEnum::Bw6_761Program(ProgIterator::new(
                             arguments,
-                            UnwrappedStreamDeserializer { s },
+                            UnwrappedStreamDeserializer::new(s),
                             return_count,
                         )))
                     }
@@ -242,7 +352,8 @@ impl<'de, R: Read>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ir::Prog;
+    use crate::ir::{LinComb, Parameter, Prog, QuadComb, Variable};
+    use crate::untyped::Position;
     use std::io::{Cursor, Seek, SeekFrom};
     use zokrates_field::{Bls12_381Field, Bn128Field};
 
@@ -274,4 +385,127 @@ mod tests {
 
         assert_eq!(ProgEnum::Bls12_381Program(p), deserialized_p.collect());
     }
+
+    #[test]
+    fn deser_v2() {
+        // programs serialized before the source map was introduced can still be read
+        let x = Parameter::private(Variable::new(0));
+        let out = Variable::public(0);
+
+        let mut buffer = Cursor::new(vec![]);
+        buffer.write_all(ZOKRATES_MAGIC).unwrap();
+        buffer.write_all(ZOKRATES_VERSION_2).unwrap();
+        buffer.write_all(&Bn128Field::id()).unwrap();
+        serde_cbor::to_writer(&mut buffer, &vec![x]).unwrap();
+        serde_cbor::to_writer(&mut buffer, &1usize).unwrap();
+        serde_cbor::to_writer(
+            &mut buffer,
+            &StatementV2::<Bn128Field>::Constraint(
+                QuadComb::from_linear_combinations(x.id.into(), x.id.into()),
+                out.into(),
+                None,
+            ),
+        )
+        .unwrap();
+
+        // rewind back to the beginning of the file
+        buffer.seek(SeekFrom::Start(0)).unwrap();
+
+        // deserialize
+        let deserialized_p = ProgEnum::deserialize(buffer).unwrap();
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![x],
+            statements: vec![Statement::constraint(
+                QuadComb::from_linear_combinations(x.id.into(), x.id.into()),
+                out,
+            )],
+            return_count: 1,
+        };
+
+        assert_eq!(ProgEnum::Bn128Program(p), deserialized_p.collect());
+    }
+
+    #[test]
+    fn ser_deser_source_map() {
+        let x = Parameter::private(Variable::new(0));
+        let y = Variable::new(1);
+        let out = Variable::public(0);
+
+        let square = SourceMetadata::new("foo.zok".into(), Position { line: 2, col: 5 })
+            .call_stack(vec!["foo".into()]);
+        let ret = SourceMetadata::new("main.zok".into(), Position { line: 8, col: 5 });
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![x],
+            statements: vec![
+                Statement::definition(
+                    y,
+                    QuadComb::from_linear_combinations(x.id.into(), x.id.into()),
+                )
+                .with_metadata(Some(square.clone())),
+                Statement::constraint(QuadComb::from_linear_combinations(y.into(), y.into()), x.id)
+                    .with_metadata(Some(square.clone())),
+                Statement::definition(out, LinComb::from(y)).with_metadata(Some(ret.clone())),
+                Statement::constraint(LinComb::from(out), y),
+            ],
+            return_count: 1,
+        };
+
+        let mut buffer = Cursor::new(vec![]);
+        p.clone().serialize_with_source_map(&mut buffer).unwrap();
+
+        // rewind back to the beginning of the file
+        buffer.seek(SeekFrom::Start(0)).unwrap();
+
+        // deserialize
+        let deserialized_p = match ProgEnum::deserialize(buffer).unwrap().collect() {
+            ProgEnum::Bn128Program(p) => p,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(deserialized_p, p);
+        assert_eq!(
+            deserialized_p
+                .statements
+                .iter()
+                .map(|s| s.metadata().cloned())
+                .collect::<Vec<_>>(),
+            vec![Some(square.clone()), Some(square), Some(ret), None]
+        );
+    }
+
+    #[test]
+    fn ser_without_source_map() {
+        // without a source map, programs are written in version 2 and the positions of their statements are dropped
+        let x = Parameter::private(Variable::new(0));
+        let out = Variable::public(0);
+
+        let metadata = SourceMetadata::new("main.zok".into(), Position { line: 2, col: 5 });
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![x],
+            statements: vec![
+                Statement::definition(out, LinComb::from(x.id)).with_metadata(Some(metadata))
+            ],
+            return_count: 1,
+        };
+
+        let mut buffer = Cursor::new(vec![]);
+        p.clone().serialize(&mut buffer).unwrap();
+
+        assert_eq!(&buffer.get_ref()[4..8], ZOKRATES_VERSION_2);
+
+        // rewind back to the beginning of the file
+        buffer.seek(SeekFrom::Start(0)).unwrap();
+
+        // deserialize
+        let deserialized_p = match ProgEnum::deserialize(buffer).unwrap().collect() {
+            ProgEnum::Bn128Program(p) => p,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(deserialized_p, p);
+        assert_eq!(deserialized_p.statements[0].metadata(), None);
+    }
 }
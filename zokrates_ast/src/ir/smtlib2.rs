@@ -79,14 +79,14 @@ impl<'ast, T: Field> SMTLib2 for Statement<'ast, T> {
     fn to_smtlib2(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             Statement::Block(..) => unreachable!(),
-            Statement::Constraint(ref quad, ref lin, _) => {
+            Statement::Constraint(ref quad, ref lin, ..) => {
                 write!(f, "(= (mod ")?;
                 quad.to_smtlib2(f)?;
                 write!(f, " |~prime|) (mod ")?;
                 lin.to_smtlib2(f)?;
                 write!(f, " |~prime|))")
             }
-            Statement::Directive(ref s) => s.to_smtlib2(f),
+            Statement::Directive(ref s, _) => s.to_smtlib2(f),
             Statement::Log(..) => write!(f, ""),
         }
     }
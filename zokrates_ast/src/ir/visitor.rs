@@ -58,15 +58,15 @@ pub fn visit_statement<T: Field, F: Visitor<T>>(f: &mut F, s: &Statement<T>) {
                 f.visit_statement(s);
             }
         }
-        Statement::Constraint(quad, lin, error) => {
+        Statement::Constraint(quad, lin, error, _) => {
             f.visit_quadratic_combination(quad);
             f.visit_linear_combination(lin);
             if let Some(error) = error.as_ref() {
                 f.visit_runtime_error(error);
             }
         }
-        Statement::Directive(dir) => f.visit_directive(dir),
-        Statement::Log(_, expressions) => {
+        Statement::Directive(dir, _) => f.visit_directive(dir),
+        Statement::Log(_, expressions, _) => {
             for (_, e) in expressions {
                 for e in e {
                     f.visit_linear_combination(e);
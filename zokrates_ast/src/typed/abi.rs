@@ -31,8 +31,8 @@ impl Abi {
 mod tests {
     use super::*;
     use crate::typed::types::{
-        ConcreteArrayType, ConcreteFunctionKey, ConcreteStructMember, ConcreteStructType,
-        GTupleType, UBitwidth,
+        ConcreteArrayType, ConcreteEnumType, ConcreteEnumVariant, ConcreteFunctionKey,
+        ConcreteStructMember, ConcreteStructType, GTupleType, UBitwidth,
     };
     use crate::typed::DeclarationType;
     use crate::typed::{
@@ -545,4 +545,63 @@ mod tests {
         let de_abi: Abi = serde_json::from_str(json.as_ref()).unwrap();
         assert_eq!(de_abi, abi);
     }
+
+    #[test]
+    fn serialize_enum() {
+        let abi: Abi = Abi {
+            inputs: vec![AbiInput {
+                name: String::from("a"),
+                public: false,
+                ty: ConcreteType::Enum(ConcreteEnumType::new(
+                    "".into(),
+                    "Op".into(),
+                    vec![
+                        ConcreteEnumVariant::new(String::from("Nop"), vec![]),
+                        ConcreteEnumVariant::new(
+                            String::from("Neg"),
+                            vec![ConcreteType::FieldElement],
+                        ),
+                    ],
+                )),
+            }],
+            output: ConcreteType::Boolean,
+        };
+
+        let json = serde_json::to_string_pretty(&abi).unwrap();
+        assert_eq!(
+            &json,
+            r#"{
+  "inputs": [
+    {
+      "name": "a",
+      "public": false,
+      "type": "enum",
+      "components": {
+        "name": "Op",
+        "variants": [
+          {
+            "name": "Nop",
+            "types": []
+          },
+          {
+            "name": "Neg",
+            "types": [
+              {
+                "type": "field"
+              }
+            ]
+          }
+        ]
+      }
+    }
+  ],
+  "output": {
+    "type": "bool"
+  }
+}"#
+        );
+
+        let de_abi: Abi = serde_json::from_str(json.as_ref()).unwrap();
+        assert_eq!(de_abi, abi);
+    }
 }
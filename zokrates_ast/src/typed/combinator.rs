@@ -0,0 +1,124 @@
+use crate::typed::types::{
+    DeclarationConstant, DeclarationFunctionKey, DeclarationSignature, DeclarationStructType,
+    DeclarationType, GTupleType, GenericIdentifier, StructKind, UBitwidth,
+};
+use crate::typed::TypedModuleId;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A function called as a method of arrays, such as `a.map(f)`. Combinators are generic over the types of the elements
+/// and take the function they apply as a function parameter. Their body is generated when a call to them is inlined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Combinator {
+    Map,
+    Fold,
+    Zip,
+    Enumerate,
+}
+
+impl Combinator {
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "map" => Some(Combinator::Map),
+            "fold" => Some(Combinator::Fold),
+            "zip" => Some(Combinator::Zip),
+            "enumerate" => Some(Combinator::Enumerate),
+            _ => None,
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Combinator::Map => "map",
+            Combinator::Fold => "fold",
+            Combinator::Zip => "zip",
+            Combinator::Enumerate => "enumerate",
+        }
+    }
+
+    /// The key of this combinator when used in `module`
+    pub fn key<'ast, T>(&self, module: &TypedModuleId) -> DeclarationFunctionKey<'ast, T> {
+        DeclarationFunctionKey::with_location(module.join("array"), self.id())
+            .signature(self.signature())
+    }
+
+    /// The signature of this combinator, generic over the size `N` of the array
+    pub fn signature<'ast, T>(&self) -> DeclarationSignature<'ast, T> {
+        let t = self.type_parameter("T");
+        let u = self.type_parameter("U");
+
+        let signature = DeclarationSignature::new()
+            .generics(vec![Some(DeclarationConstant::Generic(Self::size()))]);
+
+        match self {
+            Combinator::Map => signature
+                .inputs(vec![DeclarationType::array((t, Self::size()))])
+                .output(DeclarationType::array((u, Self::size()))),
+            Combinator::Fold => signature
+                .inputs(vec![
+                    DeclarationType::array((t, Self::size())),
+                    self.type_parameter("U"),
+                ])
+                .output(u),
+            Combinator::Zip => signature
+                .inputs(vec![
+                    DeclarationType::array((self.type_parameter("T"), Self::size())),
+                    DeclarationType::array((self.type_parameter("U"), Self::size())),
+                ])
+                .output(DeclarationType::array((
+                    DeclarationType::Tuple(GTupleType::new(vec![t, u])),
+                    Self::size(),
+                ))),
+            Combinator::Enumerate => signature
+                .inputs(vec![DeclarationType::array((
+                    self.type_parameter("T"),
+                    Self::size(),
+                ))])
+                .output(DeclarationType::array((
+                    DeclarationType::Tuple(GTupleType::new(vec![
+                        DeclarationType::Uint(UBitwidth::B32),
+                        t,
+                    ])),
+                    Self::size(),
+                ))),
+        }
+    }
+
+    /// The signatures of the function parameters of this combinator
+    pub fn function_parameters<'ast, T>(&self) -> Vec<DeclarationSignature<'ast, T>> {
+        let t = self.type_parameter("T");
+        let u = self.type_parameter("U");
+
+        match self {
+            Combinator::Map => vec![DeclarationSignature::new().inputs(vec![t]).output(u)],
+            Combinator::Fold => vec![DeclarationSignature::new()
+                .inputs(vec![self.type_parameter("U"), t])
+                .output(u)],
+            Combinator::Zip | Combinator::Enumerate => vec![],
+        }
+    }
+
+    /// The generic size of the arrays a combinator is called on
+    pub fn size<'ast>() -> GenericIdentifier<'ast> {
+        GenericIdentifier::with_name("N").with_index(0)
+    }
+
+    // the opaque type standing for the type parameter `id` of this combinator
+    fn type_parameter<'ast, T>(&self, id: &str) -> DeclarationType<'ast, T> {
+        DeclarationType::Struct(
+            DeclarationStructType::new(
+                PathBuf::from("array"),
+                format!("{}::{}", self.id(), id),
+                vec![],
+                vec![],
+            )
+            .kind(StructKind::TypeParameter),
+        )
+    }
+}
+
+impl fmt::Display for Combinator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id())
+    }
+}
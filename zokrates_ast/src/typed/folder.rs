@@ -44,6 +44,18 @@ impl<'ast, T: Field> Fold<'ast, T> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Fold<'ast, T> for EnumExpression<'ast, T> {
+    fn fold<F: Folder<'ast, T>>(self, f: &mut F) -> Self {
+        f.fold_enum_expression(self)
+    }
+}
+
+impl<'ast, T: Field> Fold<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn fold<F: Folder<'ast, T>>(self, f: &mut F) -> Self {
+        f.fold_bounded_array_expression(self)
+    }
+}
+
 pub trait Folder<'ast, T: Field>: Sized {
     fn fold_program(&mut self, p: TypedProgram<'ast, T>) -> TypedProgram<'ast, T> {
         fold_program(self, p)
@@ -158,14 +170,7 @@ pub trait Folder<'ast, T: Field>: Sized {
     }
 
     fn fold_type(&mut self, t: Type<'ast, T>) -> Type<'ast, T> {
-        use self::GType::*;
-
-        match t {
-            Array(array_type) => Array(self.fold_array_type(array_type)),
-            Struct(struct_type) => Struct(self.fold_struct_type(struct_type)),
-            Tuple(tuple_type) => Tuple(self.fold_tuple_type(tuple_type)),
-            t => t,
-        }
+        fold_type(self, t)
     }
 
     fn fold_array_type(&mut self, t: ArrayType<'ast, T>) -> ArrayType<'ast, T> {
@@ -181,6 +186,20 @@ pub trait Folder<'ast, T: Field>: Sized {
         }
     }
 
+    fn fold_enum_type(&mut self, t: EnumType<'ast, T>) -> EnumType<'ast, T> {
+        EnumType {
+            variants: t
+                .variants
+                .into_iter()
+                .map(|v| EnumVariant {
+                    types: v.types.into_iter().map(|t| self.fold_type(t)).collect(),
+                    ..v
+                })
+                .collect(),
+            ..t
+        }
+    }
+
     fn fold_struct_type(&mut self, t: StructType<'ast, T>) -> StructType<'ast, T> {
         StructType {
             generics: t
@@ -201,14 +220,7 @@ pub trait Folder<'ast, T: Field>: Sized {
     }
 
     fn fold_declaration_type(&mut self, t: DeclarationType<'ast, T>) -> DeclarationType<'ast, T> {
-        use self::GType::*;
-
-        match t {
-            Array(array_type) => Array(self.fold_declaration_array_type(array_type)),
-            Struct(struct_type) => Struct(self.fold_declaration_struct_type(struct_type)),
-            Tuple(tuple_type) => Tuple(self.fold_declaration_tuple_type(tuple_type)),
-            t => t,
-        }
+        fold_declaration_type(self, t)
     }
 
     fn fold_declaration_array_type(
@@ -234,6 +246,27 @@ pub trait Folder<'ast, T: Field>: Sized {
         }
     }
 
+    fn fold_declaration_enum_type(
+        &mut self,
+        t: DeclarationEnumType<'ast, T>,
+    ) -> DeclarationEnumType<'ast, T> {
+        DeclarationEnumType {
+            variants: t
+                .variants
+                .into_iter()
+                .map(|v| DeclarationEnumVariant {
+                    types: v
+                        .types
+                        .into_iter()
+                        .map(|t| self.fold_declaration_type(t))
+                        .collect(),
+                    ..v
+                })
+                .collect(),
+            ..t
+        }
+    }
+
     fn fold_declaration_struct_type(
         &mut self,
         t: DeclarationStructType<'ast, T>,
@@ -410,6 +443,24 @@ pub trait Folder<'ast, T: Field>: Sized {
         fold_tuple_expression(self, e)
     }
 
+    fn fold_enum_expression(&mut self, e: EnumExpression<'ast, T>) -> EnumExpression<'ast, T> {
+        fold_enum_expression(self, e)
+    }
+
+    fn fold_bounded_array_expression(
+        &mut self,
+        e: BoundedArrayExpression<'ast, T>,
+    ) -> BoundedArrayExpression<'ast, T> {
+        fold_bounded_array_expression(self, e)
+    }
+
+    fn fold_variant_expression(
+        &mut self,
+        e: VariantExpression<'ast, T>,
+    ) -> VariantExpression<'ast, T> {
+        fold_variant_expression(self, e)
+    }
+
     fn fold_int_expression(&mut self, e: IntExpression<'ast, T>) -> IntExpression<'ast, T> {
         fold_int_expression(self, e)
     }
@@ -463,6 +514,22 @@ pub trait Folder<'ast, T: Field>: Sized {
     ) -> StructExpressionInner<'ast, T> {
         fold_struct_expression_inner(self, ty, e)
     }
+
+    fn fold_enum_expression_inner(
+        &mut self,
+        ty: &EnumType<'ast, T>,
+        e: EnumExpressionInner<'ast, T>,
+    ) -> EnumExpressionInner<'ast, T> {
+        fold_enum_expression_inner(self, ty, e)
+    }
+
+    fn fold_bounded_array_expression_inner(
+        &mut self,
+        ty: &ArrayType<'ast, T>,
+        e: BoundedArrayExpressionInner<'ast, T>,
+    ) -> BoundedArrayExpressionInner<'ast, T> {
+        fold_bounded_array_expression_inner(self, ty, e)
+    }
 }
 
 pub fn fold_module<'ast, T: Field, F: Folder<'ast, T>>(
@@ -489,6 +556,9 @@ pub fn fold_symbol_declaration<'ast, T: Field, F: Folder<'ast, T>>(
         TypedSymbolDeclaration::Constant(d) => {
             TypedSymbolDeclaration::Constant(f.fold_constant_symbol_declaration(d))
         }
+        TypedSymbolDeclaration::StaticAssertion(e, metadata) => {
+            TypedSymbolDeclaration::StaticAssertion(f.fold_boolean_expression(e), metadata)
+        }
     }
 }
 
@@ -555,6 +625,9 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
         TypedStatement::Assertion(e, error) => {
             TypedStatement::Assertion(f.fold_boolean_expression(e), error)
         }
+        TypedStatement::StaticAssertion(e, metadata) => {
+            TypedStatement::StaticAssertion(f.fold_boolean_expression(e), metadata)
+        }
         TypedStatement::For(v, from, to, statements) => TypedStatement::For(
             f.fold_variable(v),
             f.fold_uint_expression(from),
@@ -564,6 +637,48 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
                 .flat_map(|s| f.fold_statement(s))
                 .collect(),
         ),
+        TypedStatement::ForEach(v, array, statements) => TypedStatement::ForEach(
+            f.fold_variable(v),
+            f.fold_array_expression(array),
+            statements
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
+        ),
+        TypedStatement::While(condition, bound, statements, error) => TypedStatement::While(
+            f.fold_boolean_expression(condition),
+            f.fold_uint_expression(bound),
+            statements
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
+            error,
+        ),
+        TypedStatement::If(condition, consequence, alternative) => TypedStatement::If(
+            f.fold_boolean_expression(condition),
+            consequence
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
+            alternative
+                .into_iter()
+                .flat_map(|s| f.fold_statement(s))
+                .collect(),
+        ),
+        TypedStatement::ComptimeIf(condition, consequence, alternative, metadata) => {
+            TypedStatement::ComptimeIf(
+                f.fold_boolean_expression(condition),
+                consequence
+                    .into_iter()
+                    .flat_map(|s| f.fold_statement(s))
+                    .collect(),
+                alternative
+                    .into_iter()
+                    .flat_map(|s| f.fold_statement(s))
+                    .collect(),
+                metadata,
+            )
+        }
         TypedStatement::Log(s, e) => {
             TypedStatement::Log(s, e.into_iter().map(|e| f.fold_expression(e)).collect())
         }
@@ -605,6 +720,35 @@ pub fn fold_embed_call<'ast, T: Field, F: Folder<'ast, T>>(
     }
 }
 
+pub fn fold_type<'ast, T: Field, F: Folder<'ast, T>>(f: &mut F, t: Type<'ast, T>) -> Type<'ast, T> {
+    use self::GType::*;
+
+    match t {
+        Array(array_type) => Array(f.fold_array_type(array_type)),
+        Struct(struct_type) => Struct(f.fold_struct_type(struct_type)),
+        Tuple(tuple_type) => Tuple(f.fold_tuple_type(tuple_type)),
+        Enum(enum_type) => Enum(f.fold_enum_type(enum_type)),
+        BoundedArray(array_type) => BoundedArray(f.fold_array_type(array_type)),
+        t => t,
+    }
+}
+
+pub fn fold_declaration_type<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    t: DeclarationType<'ast, T>,
+) -> DeclarationType<'ast, T> {
+    use self::GType::*;
+
+    match t {
+        Array(array_type) => Array(f.fold_declaration_array_type(array_type)),
+        Struct(struct_type) => Struct(f.fold_declaration_struct_type(struct_type)),
+        Tuple(tuple_type) => Tuple(f.fold_declaration_tuple_type(tuple_type)),
+        Enum(enum_type) => Enum(f.fold_declaration_enum_type(enum_type)),
+        BoundedArray(array_type) => BoundedArray(f.fold_declaration_array_type(array_type)),
+        t => t,
+    }
+}
+
 pub fn fold_expression<'ast, T: Field, F: Folder<'ast, T>>(
     f: &mut F,
     e: TypedExpression<'ast, T>,
@@ -616,6 +760,8 @@ pub fn fold_expression<'ast, T: Field, F: Folder<'ast, T>>(
         TypedExpression::Array(e) => f.fold_array_expression(e).into(),
         TypedExpression::Tuple(e) => f.fold_tuple_expression(e).into(),
         TypedExpression::Struct(e) => f.fold_struct_expression(e).into(),
+        TypedExpression::Enum(e) => f.fold_enum_expression(e).into(),
+        TypedExpression::BoundedArray(e) => f.fold_bounded_array_expression(e).into(),
         TypedExpression::Int(e) => f.fold_int_expression(e).into(),
     }
 }
@@ -666,10 +812,12 @@ pub fn fold_array_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             let count = f.fold_uint_expression(count);
             Repeat(box e, box count)
         }
+        Bytes(bytes) => Bytes(bytes),
         Element(m) => match f.fold_element_expression(ty, m) {
             ElementOrExpression::Element(m) => Element(m),
             ElementOrExpression::Expression(u) => u,
         },
+        Data(box array) => Data(box f.fold_bounded_array_expression(array)),
     }
 }
 
@@ -744,9 +892,99 @@ pub fn fold_tuple_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             ElementOrExpression::Element(m) => Element(m),
             ElementOrExpression::Expression(u) => u,
         },
+        Payload(v) => Payload(f.fold_variant_expression(v)),
     }
 }
 
+pub fn fold_enum_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    ty: &EnumType<'ast, T>,
+    e: EnumExpressionInner<'ast, T>,
+) -> EnumExpressionInner<'ast, T> {
+    use EnumExpressionInner::*;
+
+    match e {
+        Block(block) => Block(f.fold_block_expression(block)),
+        Identifier(id) => match f.fold_identifier_expression(ty, id) {
+            IdentifierOrExpression::Identifier(i) => Identifier(i),
+            IdentifierOrExpression::Expression(u) => u,
+        },
+        Value(variant, exprs) => Value(
+            variant,
+            exprs.into_iter().map(|e| f.fold_expression(e)).collect(),
+        ),
+        FunctionCall(function_call) => match f.fold_function_call_expression(ty, function_call) {
+            FunctionCallOrExpression::FunctionCall(function_call) => FunctionCall(function_call),
+            FunctionCallOrExpression::Expression(u) => u,
+        },
+        Conditional(c) => match f.fold_conditional_expression(ty, c) {
+            ConditionalOrExpression::Conditional(s) => Conditional(s),
+            ConditionalOrExpression::Expression(u) => u,
+        },
+        Select(select) => match f.fold_select_expression(ty, select) {
+            SelectOrExpression::Select(s) => Select(s),
+            SelectOrExpression::Expression(u) => u,
+        },
+        Member(m) => match f.fold_member_expression(ty, m) {
+            MemberOrExpression::Member(m) => Member(m),
+            MemberOrExpression::Expression(u) => u,
+        },
+        Element(m) => match f.fold_element_expression(ty, m) {
+            ElementOrExpression::Element(m) => Element(m),
+            ElementOrExpression::Expression(u) => u,
+        },
+    }
+}
+
+pub fn fold_bounded_array_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    ty: &ArrayType<'ast, T>,
+    e: BoundedArrayExpressionInner<'ast, T>,
+) -> BoundedArrayExpressionInner<'ast, T> {
+    use BoundedArrayExpressionInner::*;
+
+    match e {
+        Block(block) => Block(f.fold_block_expression(block)),
+        Identifier(id) => match f.fold_identifier_expression(ty, id) {
+            IdentifierOrExpression::Identifier(i) => Identifier(i),
+            IdentifierOrExpression::Expression(u) => u,
+        },
+        Value(box array, metadata) => Value(box f.fold_array_expression(array), metadata),
+        Push(box array, box e) => {
+            let array = f.fold_bounded_array_expression(array);
+            let e = f.fold_expression(e);
+            Push(box array, box e)
+        }
+        FunctionCall(function_call) => match f.fold_function_call_expression(ty, function_call) {
+            FunctionCallOrExpression::FunctionCall(function_call) => FunctionCall(function_call),
+            FunctionCallOrExpression::Expression(u) => u,
+        },
+        Conditional(c) => match f.fold_conditional_expression(ty, c) {
+            ConditionalOrExpression::Conditional(s) => Conditional(s),
+            ConditionalOrExpression::Expression(u) => u,
+        },
+        Select(select) => match f.fold_select_expression(ty, select) {
+            SelectOrExpression::Select(s) => Select(s),
+            SelectOrExpression::Expression(u) => u,
+        },
+        Member(m) => match f.fold_member_expression(ty, m) {
+            MemberOrExpression::Member(m) => Member(m),
+            MemberOrExpression::Expression(u) => u,
+        },
+        Element(m) => match f.fold_element_expression(ty, m) {
+            ElementOrExpression::Element(m) => Element(m),
+            ElementOrExpression::Expression(u) => u,
+        },
+    }
+}
+
+pub fn fold_variant_expression<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    e: VariantExpression<'ast, T>,
+) -> VariantExpression<'ast, T> {
+    VariantExpression::new(f.fold_enum_expression(*e.enumeration), e.variant)
+}
+
 pub fn fold_field_expression<'ast, T: Field, F: Folder<'ast, T>>(
     f: &mut F,
     e: FieldElementExpression<'ast, T>,
@@ -965,6 +1203,10 @@ pub fn fold_boolean_expression<'ast, T: Field, F: Folder<'ast, T>>(
             EqOrBoolean::Eq(e) => BooleanExpression::TupleEq(e),
             EqOrBoolean::Boolean(u) => u,
         },
+        EnumEq(e) => match f.fold_eq_expression(e) {
+            EqOrBoolean::Eq(e) => BooleanExpression::EnumEq(e),
+            EqOrBoolean::Boolean(u) => u,
+        },
         UintEq(e) => match f.fold_eq_expression(e) {
             EqOrBoolean::Eq(e) => BooleanExpression::UintEq(e),
             EqOrBoolean::Boolean(u) => u,
@@ -1023,6 +1265,7 @@ pub fn fold_boolean_expression<'ast, T: Field, F: Folder<'ast, T>>(
             let e = f.fold_boolean_expression(e);
             Not(box e)
         }
+        IsVariant(v) => IsVariant(f.fold_variant_expression(v)),
         FunctionCall(function_call) => {
             match f.fold_function_call_expression(&Type::Boolean, function_call) {
                 FunctionCallOrExpression::FunctionCall(function_call) => {
@@ -1175,6 +1418,7 @@ pub fn fold_uint_expression_inner<'ast, T: Field, F: Folder<'ast, T>>(
             ElementOrExpression::Element(m) => Element(m),
             ElementOrExpression::Expression(u) => u,
         },
+        Len(box array) => Len(box f.fold_bounded_array_expression(array)),
     }
 }
 
@@ -1213,17 +1457,25 @@ pub fn fold_function_call_expression<
     _: &E::Ty,
     e: FunctionCallExpression<'ast, T, E>,
 ) -> FunctionCallOrExpression<'ast, T, E> {
-    FunctionCallOrExpression::FunctionCall(FunctionCallExpression::new(
-        f.fold_declaration_function_key(e.function_key),
-        e.generics
-            .into_iter()
-            .map(|g| g.map(|g| f.fold_uint_expression(g)))
-            .collect(),
-        e.arguments
-            .into_iter()
-            .map(|e| f.fold_expression(e))
-            .collect(),
-    ))
+    FunctionCallOrExpression::FunctionCall(
+        FunctionCallExpression::new(
+            f.fold_declaration_function_key(*e.function_key),
+            e.generics
+                .into_iter()
+                .map(|g| g.map(|g| f.fold_uint_expression(g)))
+                .collect(),
+            e.arguments
+                .into_iter()
+                .map(|e| f.fold_expression(e))
+                .collect(),
+        )
+        .function_arguments(
+            e.function_arguments
+                .into_iter()
+                .map(|k| f.fold_declaration_function_key(k))
+                .collect(),
+        ),
+    )
 }
 
 pub fn fold_function<'ast, T: Field, F: Folder<'ast, T>>(
@@ -1265,7 +1517,8 @@ pub fn fold_declaration_constant<'ast, T: Field, F: Folder<'ast, T>>(
     c: DeclarationConstant<'ast, T>,
 ) -> DeclarationConstant<'ast, T> {
     match c {
-        DeclarationConstant::Expression(e) => DeclarationConstant::Expression(f.fold_expression(e)),
+        // generic expressions only refer to generics and constants, so they are not visited by default
+        // to avoid confusing them with variables in scope
         DeclarationConstant::Constant(c) => {
             DeclarationConstant::Constant(f.fold_canonical_constant_identifier(c))
         }
@@ -1307,6 +1560,28 @@ pub fn fold_tuple_expression<'ast, T: Field, F: Folder<'ast, T>>(
     }
 }
 
+pub fn fold_enum_expression<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    e: EnumExpression<'ast, T>,
+) -> EnumExpression<'ast, T> {
+    let ty = f.fold_enum_type(e.ty);
+    EnumExpression {
+        inner: f.fold_enum_expression_inner(&ty, e.inner),
+        ty,
+    }
+}
+
+pub fn fold_bounded_array_expression<'ast, T: Field, F: Folder<'ast, T>>(
+    f: &mut F,
+    e: BoundedArrayExpression<'ast, T>,
+) -> BoundedArrayExpression<'ast, T> {
+    let ty = f.fold_array_type(e.ty);
+    BoundedArrayExpression {
+        inner: f.fold_bounded_array_expression_inner(&ty, e.inner),
+        ty,
+    }
+}
+
 pub fn fold_constant<'ast, T: Field, F: Folder<'ast, T>>(
     f: &mut F,
     c: TypedConstant<'ast, T>,
@@ -11,6 +11,9 @@ pub enum CoreIdentifier<'ast> {
     Call(usize),
     Constant(CanonicalConstantIdentifier<'ast>),
     Condition(usize),
+    Signed(usize),
+    Guard(usize),
+    Loop(usize),
 }
 
 impl<'ast> fmt::Display for CoreIdentifier<'ast> {
@@ -20,6 +23,9 @@ impl<'ast> fmt::Display for CoreIdentifier<'ast> {
             CoreIdentifier::Call(i) => write!(f, "#CALL_RETURN_AT_INDEX_{}", i),
             CoreIdentifier::Constant(c) => write!(f, "{}/{}", c.module.display(), c.id),
             CoreIdentifier::Condition(i) => write!(f, "#CONDITION_{}", i),
+            CoreIdentifier::Signed(i) => write!(f, "#SIGNED_{}", i),
+            CoreIdentifier::Guard(i) => write!(f, "#GUARD_{}", i),
+            CoreIdentifier::Loop(i) => write!(f, "#LOOP_{}", i),
         }
     }
 }
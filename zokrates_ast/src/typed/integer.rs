@@ -1,14 +1,15 @@
 use crate::typed::types::{
-    ArrayType, DeclarationArrayType, DeclarationConstant, DeclarationStructMember,
-    DeclarationStructType, DeclarationTupleType, DeclarationType, GArrayType, GStructType,
-    GTupleType, GType, GenericIdentifier, StructType, TupleType, Type,
+    ArrayType, DeclarationArrayType, DeclarationConstant, DeclarationEnumType,
+    DeclarationEnumVariant, DeclarationStructMember, DeclarationStructType, DeclarationTupleType,
+    DeclarationType, EnumType, GArrayType, GEnumType, GStructType, GTupleType, GType,
+    GenericIdentifier, StructType, TupleType, Type,
 };
 use crate::typed::UBitwidth;
 use crate::typed::{
-    ArrayExpression, ArrayExpressionInner, BooleanExpression, Conditional, ConditionalExpression,
-    Expr, FieldElementExpression, Select, SelectExpression, StructExpression,
-    StructExpressionInner, TupleExpression, TupleExpressionInner, Typed, TypedExpression,
-    TypedExpressionOrSpread, TypedSpread, UExpression, UExpressionInner,
+    ArrayExpression, ArrayExpressionInner, BooleanExpression, BoundedArrayExpression, Conditional,
+    ConditionalExpression, EnumExpression, Expr, FieldElementExpression, Select, SelectExpression,
+    StructExpression, StructExpressionInner, TupleExpression, TupleExpressionInner, Typed,
+    TypedExpression, TypedExpressionOrSpread, TypedSpread, UExpression, UExpressionInner,
 };
 use num_bigint::BigUint;
 use std::convert::TryFrom;
@@ -71,6 +72,14 @@ impl<'ast, T: Clone> IntegerInference for Type<'ast, T> {
                 t.get_common_pattern(u)
                     .map_err(|(t, u)| (Type::Tuple(t), Type::Tuple(u)))?,
             )),
+            (Type::Enum(t), Type::Enum(u)) => Ok(DeclarationType::Enum(
+                t.get_common_pattern(u)
+                    .map_err(|(t, u)| (Type::Enum(t), Type::Enum(u)))?,
+            )),
+            (Type::BoundedArray(t), Type::BoundedArray(u)) => Ok(DeclarationType::BoundedArray(
+                t.get_common_pattern(u)
+                    .map_err(|(t, u)| (Type::BoundedArray(t), Type::BoundedArray(u)))?,
+            )),
             (t, u) => Err((t, u)),
         }
     }
@@ -113,6 +122,7 @@ impl<'ast, T: Clone> IntegerInference for StructType<'ast, T> {
                 .collect::<Vec<_>>(),
             canonical_location: self.canonical_location,
             location: self.location,
+            kind: self.kind,
             generics: self
                 .generics
                 .into_iter()
@@ -124,6 +134,37 @@ impl<'ast, T: Clone> IntegerInference for StructType<'ast, T> {
     }
 }
 
+impl<'ast, T: Clone> IntegerInference for EnumType<'ast, T> {
+    type Pattern = DeclarationEnumType<'ast, T>;
+
+    fn get_common_pattern(self, other: Self) -> Result<Self::Pattern, (Self, Self)> {
+        if self != other {
+            return Err((self, other));
+        }
+
+        // payloads are aligned when values are constructed, so they never contain integers
+        Ok(DeclarationEnumType {
+            variants: self
+                .variants
+                .into_iter()
+                .map(|v| DeclarationEnumVariant {
+                    types: v
+                        .types
+                        .into_iter()
+                        .map(|t| match t.clone().get_common_pattern(t) {
+                            Ok(t) => t,
+                            Err(..) => unreachable!("enum payloads should not contain integers"),
+                        })
+                        .collect(),
+                    id: v.id,
+                })
+                .collect(),
+            canonical_location: self.canonical_location,
+            location: self.location,
+        })
+    }
+}
+
 impl<'ast, T: Clone> IntegerInference for TupleType<'ast, T> {
     type Pattern = DeclarationTupleType<'ast, T>;
 
@@ -237,6 +278,20 @@ impl<'ast, T: Field> TypedExpression<'ast, T> {
                         .into(),
                 ))
             }
+            (Enum(lhs), Enum(rhs)) => {
+                if lhs.ty == rhs.ty {
+                    Ok((lhs.into(), rhs.into()))
+                } else {
+                    Err((lhs.into(), rhs.into()))
+                }
+            }
+            (BoundedArray(lhs), BoundedArray(rhs)) => {
+                if lhs.inner_type() == rhs.inner_type() {
+                    Ok((lhs.into(), rhs.into()))
+                } else {
+                    Err((lhs.into(), rhs.into()))
+                }
+            }
             (Uint(lhs), Uint(rhs)) => Ok((lhs.into(), rhs.into())),
             (Boolean(lhs), Boolean(rhs)) => Ok((lhs.into(), rhs.into())),
             (FieldElement(lhs), FieldElement(rhs)) => Ok((lhs.into(), rhs.into())),
@@ -266,6 +321,12 @@ impl<'ast, T: Field> TypedExpression<'ast, T> {
             GType::Tuple(tuple_ty) => {
                 TupleExpression::try_from_typed(e, tuple_ty).map(TypedExpression::from)
             }
+            GType::Enum(enum_ty) => {
+                EnumExpression::try_from_typed(e, enum_ty).map(TypedExpression::from)
+            }
+            GType::BoundedArray(array_ty) => {
+                BoundedArrayExpression::try_from_typed(e, array_ty).map(TypedExpression::from)
+            }
             GType::Int => Err(e),
         }
         .map_err(|e| (e, ty))
@@ -539,7 +600,12 @@ impl<'ast, T: Field> UExpression<'ast, T> {
 
         match i {
             Value(i) => {
-                if i <= BigUint::from(2u128.pow(bitwidth.to_usize() as u32) - 1) {
+                let max = match bitwidth.is_signed() {
+                    true => 2u128.pow(bitwidth.to_usize() as u32 - 1) - 1,
+                    false => bitwidth.max_value(),
+                };
+
+                if i <= BigUint::from(max) {
                     Ok(UExpressionInner::Value(
                         u128::from_str_radix(&i.to_str_radix(16), 16).unwrap(),
                     )
@@ -548,6 +614,16 @@ impl<'ast, T: Field> UExpression<'ast, T> {
                     Err(Value(i))
                 }
             }
+            // a signed literal may only reach `2^(n-1)` when it is negated, which gives the minimum value
+            Neg(box Value(i))
+                if bitwidth.is_signed()
+                    && i == BigUint::from(2u128.pow(bitwidth.to_usize() as u32 - 1)) =>
+            {
+                Ok(Self::neg(
+                    UExpressionInner::Value(2u128.pow(bitwidth.to_usize() as u32 - 1))
+                        .annotate(*bitwidth),
+                ))
+            }
             Add(box e1, box e2) => {
                 Ok(Self::try_from_int(e1, bitwidth)? + Self::try_from_int(e2, bitwidth)?)
             }
@@ -800,6 +876,30 @@ impl<'ast, T: Field> TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> EnumExpression<'ast, T> {
+    pub fn try_from_typed<S>(
+        e: TypedExpression<'ast, T>,
+        target_enum_ty: &GEnumType<S>,
+    ) -> Result<Self, TypedExpression<'ast, T>> {
+        match e {
+            TypedExpression::Enum(e) if e.ty == *target_enum_ty => Ok(e),
+            e => Err(e),
+        }
+    }
+}
+
+impl<'ast, T: Field> BoundedArrayExpression<'ast, T> {
+    pub fn try_from_typed<S: PartialEq<UExpression<'ast, T>>>(
+        e: TypedExpression<'ast, T>,
+        target_array_ty: &GArrayType<S>,
+    ) -> Result<Self, TypedExpression<'ast, T>> {
+        match e {
+            TypedExpression::BoundedArray(e) if *target_array_ty.ty == *e.inner_type() => Ok(e),
+            e => Err(e),
+        }
+    }
+}
+
 impl<'ast, T> From<BigUint> for IntExpression<'ast, T> {
     fn from(v: BigUint) -> Self {
         IntExpression::Value(v)
@@ -938,4 +1038,22 @@ mod tests {
             assert!(e.is_err());
         }
     }
+
+    #[test]
+    fn signed_from_int() {
+        let max: IntExpression<Bn128Field> = BigUint::from(127usize).into();
+        let min: IntExpression<Bn128Field> = BigUint::from(128usize).into();
+
+        assert_eq!(
+            UExpression::try_from_int(max.clone(), &UBitwidth::I8),
+            Ok(UExpressionInner::Value(127).annotate(UBitwidth::I8))
+        );
+        // `2^(n-1)` is only accepted as the operand of a negation
+        assert_eq!(
+            UExpression::try_from_int(-min.clone(), &UBitwidth::I8),
+            Ok(-UExpressionInner::Value(128).annotate(UBitwidth::I8))
+        );
+        assert!(UExpression::try_from_int(min.clone(), &UBitwidth::I8).is_err());
+        assert!(UExpression::try_from_int(max + min, &UBitwidth::I8).is_err());
+    }
 }
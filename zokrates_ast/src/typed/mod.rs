@@ -6,6 +6,7 @@
 //! @date 2017
 
 pub mod abi;
+mod combinator;
 pub mod folder;
 pub mod identifier;
 pub mod result_folder;
@@ -17,16 +18,17 @@ mod uint;
 pub mod utils;
 pub mod variable;
 
+pub use self::combinator::Combinator;
 pub use self::identifier::{CoreIdentifier, ShadowedIdentifier, SourceIdentifier};
 pub use self::parameter::{DeclarationParameter, GParameter};
 pub use self::types::{
     CanonicalConstantIdentifier, ConcreteFunctionKey, ConcreteSignature, ConcreteTupleType,
     ConcreteType, ConstantIdentifier, DeclarationArrayType, DeclarationConstant,
-    DeclarationFunctionKey, DeclarationSignature, DeclarationStructType, DeclarationType,
-    GArrayType, GStructType, GType, GenericIdentifier, Signature, StructType, TupleType, Type,
-    UBitwidth,
+    DeclarationEnumType, DeclarationFunctionKey, DeclarationSignature, DeclarationStructType,
+    DeclarationType, EnumType, GArrayType, GEnumType, GStructType, GType, GenericIdentifier,
+    GenericsError, Signature, StructKind, StructType, TupleType, Type, UBitwidth,
 };
-use self::types::{ConcreteArrayType, ConcreteStructType};
+use self::types::{ConcreteArrayType, ConcreteEnumType, ConcreteStructType};
 use crate::typed::types::{ConcreteGenericsAssignment, IntoType};
 
 pub use self::variable::{ConcreteVariable, DeclarationVariable, GVariable, Variable};
@@ -36,7 +38,7 @@ use std::path::{Path, PathBuf};
 pub use crate::typed::integer::IntExpression;
 pub use crate::typed::uint::{bitwidth, UExpression, UExpressionInner, UMetadata};
 
-use crate::common::{FlatEmbed, FormatString, SourceMetadata};
+use crate::common::{ByteString, FlatEmbed, FormatString, SourceMetadata};
 
 use std::collections::BTreeMap;
 use std::convert::{TryFrom, TryInto};
@@ -183,6 +185,7 @@ impl<'ast, T> TypedConstantSymbolDeclaration<'ast, T> {
 pub enum TypedSymbolDeclaration<'ast, T> {
     Function(TypedFunctionSymbolDeclaration<'ast, T>),
     Constant(TypedConstantSymbolDeclaration<'ast, T>),
+    StaticAssertion(BooleanExpression<'ast, T>, SourceMetadata),
 }
 
 impl<'ast, T> From<TypedFunctionSymbolDeclaration<'ast, T>> for TypedSymbolDeclaration<'ast, T> {
@@ -202,6 +205,13 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedSymbolDeclaration<'ast, T> {
         match self {
             TypedSymbolDeclaration::Function(fun) => write!(f, "{}", fun),
             TypedSymbolDeclaration::Constant(c) => write!(f, "{}", c),
+            TypedSymbolDeclaration::StaticAssertion(e, metadata) => {
+                write!(f, "static_assert({}", e)?;
+                match &metadata.message {
+                    Some(m) => write!(f, ", \"{}\");", m),
+                    None => write!(f, ");"),
+                }
+            }
         }
     }
 }
@@ -237,6 +247,7 @@ pub enum TypedFunctionSymbol<'ast, T> {
     Here(TypedFunction<'ast, T>),
     There(DeclarationFunctionKey<'ast, T>),
     Flat(FlatEmbed),
+    Combinator(Combinator),
 }
 
 impl<'ast, T: Field> TypedFunctionSymbol<'ast, T> {
@@ -255,6 +266,7 @@ impl<'ast, T: Field> TypedFunctionSymbol<'ast, T> {
                 .symbol
                 .signature(modules),
             TypedFunctionSymbol::Flat(flat_fun) => flat_fun.typed_signature(),
+            TypedFunctionSymbol::Combinator(combinator) => combinator.signature(),
         }
     }
 }
@@ -298,6 +310,14 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedFunctionSymbolDeclaration<'ast
                     flat_fun.typed_signature::<T>()
                 )
             }
+            TypedFunctionSymbol::Combinator(ref combinator) => {
+                write!(
+                    f,
+                    "def {}{} {{\n\t// built-in\n}}",
+                    self.key.id,
+                    combinator.signature::<T>()
+                )
+            }
         }
     }
 }
@@ -492,6 +512,18 @@ impl<'ast, T> From<TupleExpression<'ast, T>> for TypedExpressionOrSpread<'ast, T
     }
 }
 
+impl<'ast, T> From<EnumExpression<'ast, T>> for TypedExpressionOrSpread<'ast, T> {
+    fn from(e: EnumExpression<'ast, T>) -> Self {
+        TypedExpressionOrSpread::Expression(e.into())
+    }
+}
+
+impl<'ast, T> From<BoundedArrayExpression<'ast, T>> for TypedExpressionOrSpread<'ast, T> {
+    fn from(e: BoundedArrayExpression<'ast, T>) -> Self {
+        TypedExpressionOrSpread::Expression(e.into())
+    }
+}
+
 impl<'ast, T> From<TypedExpression<'ast, T>> for TypedExpressionOrSpread<'ast, T> {
     fn from(e: TypedExpression<'ast, T>) -> Self {
         TypedExpressionOrSpread::Expression(e)
@@ -688,12 +720,37 @@ pub enum TypedStatement<'ast, T> {
     Return(TypedExpression<'ast, T>),
     Definition(TypedAssignee<'ast, T>, DefinitionRhs<'ast, T>),
     Assertion(BooleanExpression<'ast, T>, RuntimeError),
+    StaticAssertion(BooleanExpression<'ast, T>, SourceMetadata),
     For(
         Variable<'ast, T>,
         UExpression<'ast, T>,
         UExpression<'ast, T>,
         Vec<TypedStatement<'ast, T>>,
     ),
+    ForEach(
+        Variable<'ast, T>,
+        ArrayExpression<'ast, T>,
+        Vec<TypedStatement<'ast, T>>,
+    ),
+    While(
+        BooleanExpression<'ast, T>,
+        UExpression<'ast, T>,
+        Vec<TypedStatement<'ast, T>>,
+        RuntimeError,
+    ),
+    If(
+        BooleanExpression<'ast, T>,
+        Vec<TypedStatement<'ast, T>>,
+        Vec<TypedStatement<'ast, T>>,
+    ),
+    ComptimeIf(
+        BooleanExpression<'ast, T>,
+        Vec<TypedStatement<'ast, T>>,
+        Vec<TypedStatement<'ast, T>>,
+        SourceMetadata,
+    ),
+    Break,
+    Continue,
     Log(FormatString, Vec<TypedExpression<'ast, T>>),
     // Aux
     PushCallLog(
@@ -701,6 +758,8 @@ pub enum TypedStatement<'ast, T> {
         ConcreteGenericsAssignment<'ast>,
     ),
     PopCallLog,
+    /// The position in the source of the statements which follow, only kept when a source map is requested
+    SourceMetadata(SourceMetadata),
     Assembly(Vec<TypedAssemblyStatement<'ast, T>>),
 }
 
@@ -726,6 +785,46 @@ impl<'ast, T: fmt::Display> TypedStatement<'ast, T> {
                 }
                 write!(f, "{}}}", "\t".repeat(depth))
             }
+            TypedStatement::ForEach(variable, array, statements) => {
+                write!(f, "{}", "\t".repeat(depth))?;
+                writeln!(f, "for {} in {} {{", variable, array)?;
+                for s in statements {
+                    s.fmt_indented(f, depth + 1)?;
+                    writeln!(f)?;
+                }
+                write!(f, "{}}}", "\t".repeat(depth))
+            }
+            TypedStatement::While(condition, bound, statements, _) => {
+                write!(f, "{}", "\t".repeat(depth))?;
+                writeln!(f, "while {} bounded {} {{", condition, bound)?;
+                for s in statements {
+                    s.fmt_indented(f, depth + 1)?;
+                    writeln!(f)?;
+                }
+                write!(f, "{}}}", "\t".repeat(depth))
+            }
+            TypedStatement::If(condition, consequence, alternative)
+            | TypedStatement::ComptimeIf(condition, consequence, alternative, _) => {
+                write!(f, "{}", "\t".repeat(depth))?;
+                if let TypedStatement::ComptimeIf(..) = self {
+                    write!(f, "comptime ")?;
+                }
+                writeln!(f, "if {} {{", condition)?;
+                for s in consequence {
+                    s.fmt_indented(f, depth + 1)?;
+                    writeln!(f)?;
+                }
+                write!(f, "{}}}", "\t".repeat(depth))?;
+                if !alternative.is_empty() {
+                    writeln!(f, " else {{")?;
+                    for s in alternative {
+                        s.fmt_indented(f, depth + 1)?;
+                        writeln!(f)?;
+                    }
+                    write!(f, "{}}}", "\t".repeat(depth))?;
+                }
+                Ok(())
+            }
             TypedStatement::Assembly(statements) => {
                 write!(f, "{}", "\t".repeat(depth))?;
                 writeln!(f, "asm {{")?;
@@ -756,6 +855,13 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedStatement<'ast, T> {
                     error => write!(f, "); // {}", error),
                 }
             }
+            TypedStatement::StaticAssertion(ref e, ref metadata) => {
+                write!(f, "static_assert({}", e)?;
+                match &metadata.message {
+                    Some(m) => write!(f, ", \"{}\");", m),
+                    None => write!(f, ");"),
+                }
+            }
             TypedStatement::For(ref var, ref start, ref stop, ref list) => {
                 writeln!(f, "for {} in {}..{} {{", var, start, stop)?;
                 for l in list {
@@ -763,6 +869,41 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedStatement<'ast, T> {
                 }
                 write!(f, "\t}}")
             }
+            TypedStatement::ForEach(ref var, ref array, ref list) => {
+                writeln!(f, "for {} in {} {{", var, array)?;
+                for l in list {
+                    writeln!(f, "\t\t{}", l)?;
+                }
+                write!(f, "\t}}")
+            }
+            TypedStatement::While(ref condition, ref bound, ref list, _) => {
+                writeln!(f, "while {} bounded {} {{", condition, bound)?;
+                for l in list {
+                    writeln!(f, "\t\t{}", l)?;
+                }
+                write!(f, "\t}}")
+            }
+            TypedStatement::If(ref condition, ref consequence, ref alternative)
+            | TypedStatement::ComptimeIf(ref condition, ref consequence, ref alternative, _) => {
+                if let TypedStatement::ComptimeIf(..) = self {
+                    write!(f, "comptime ")?;
+                }
+                writeln!(f, "if {} {{", condition)?;
+                for c in consequence {
+                    writeln!(f, "\t\t{}", c)?;
+                }
+                write!(f, "\t}}")?;
+                if !alternative.is_empty() {
+                    writeln!(f, " else {{")?;
+                    for a in alternative {
+                        writeln!(f, "\t\t{}", a)?;
+                    }
+                    write!(f, "\t}}")?;
+                }
+                Ok(())
+            }
+            TypedStatement::Break => write!(f, "break;"),
+            TypedStatement::Continue => write!(f, "continue;"),
             TypedStatement::Log(ref l, ref expressions) => write!(
                 f,
                 "log({}, {})",
@@ -781,6 +922,7 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedStatement<'ast, T> {
                 generics,
             ),
             TypedStatement::PopCallLog => write!(f, "// POP CALL",),
+            TypedStatement::SourceMetadata(ref metadata) => write!(f, "// {}", metadata.location()),
             TypedStatement::Assembly(ref statements) => {
                 writeln!(f, "asm {{")?;
                 for s in statements {
@@ -806,6 +948,8 @@ pub enum TypedExpression<'ast, T> {
     Array(ArrayExpression<'ast, T>),
     Struct(StructExpression<'ast, T>),
     Tuple(TupleExpression<'ast, T>),
+    Enum(EnumExpression<'ast, T>),
+    BoundedArray(BoundedArrayExpression<'ast, T>),
     Int(IntExpression<'ast, T>),
 }
 
@@ -815,6 +959,43 @@ impl<'ast, T> TypedExpression<'ast, T> {
     }
 }
 
+/// The zero value of type `ty`
+pub fn zero_value<'ast, T: Field>(ty: &Type<'ast, T>) -> TypedExpression<'ast, T> {
+    match ty {
+        Type::Int => unreachable!(),
+        Type::FieldElement => FieldElementExpression::Number(T::zero()).into(),
+        Type::Boolean => BooleanExpression::Value(false).into(),
+        Type::Uint(bitwidth) => UExpressionInner::Value(0).annotate(*bitwidth).into(),
+        Type::Array(array_ty) => {
+            ArrayExpressionInner::Repeat(box zero_value(&array_ty.ty), array_ty.size.clone())
+                .annotate(*array_ty.ty.clone(), *array_ty.size.clone())
+                .into()
+        }
+        Type::Struct(struct_ty) => {
+            StructExpressionInner::Value(struct_ty.iter().map(|m| zero_value(&m.ty)).collect())
+                .annotate(struct_ty.clone())
+                .into()
+        }
+        Type::Tuple(tuple_ty) => {
+            TupleExpressionInner::Value(tuple_ty.elements.iter().map(zero_value).collect())
+                .annotate(tuple_ty.clone())
+                .into()
+        }
+        Type::Enum(enum_ty) => EnumExpressionInner::Value(
+            0,
+            enum_ty.variants[0].types.iter().map(zero_value).collect(),
+        )
+        .annotate(enum_ty.clone())
+        .into(),
+        Type::BoundedArray(array_ty) => BoundedArrayExpressionInner::Value(
+            box ArrayExpressionInner::Value(vec![].into()).annotate(*array_ty.ty.clone(), 0u32),
+            None,
+        )
+        .annotate(array_ty.clone())
+        .into(),
+    }
+}
+
 impl<'ast, T> From<BooleanExpression<'ast, T>> for TypedExpression<'ast, T> {
     fn from(e: BooleanExpression<'ast, T>) -> TypedExpression<T> {
         TypedExpression::Boolean(e)
@@ -857,6 +1038,18 @@ impl<'ast, T> From<StructExpression<'ast, T>> for TypedExpression<'ast, T> {
     }
 }
 
+impl<'ast, T> From<EnumExpression<'ast, T>> for TypedExpression<'ast, T> {
+    fn from(e: EnumExpression<'ast, T>) -> TypedExpression<T> {
+        TypedExpression::Enum(e)
+    }
+}
+
+impl<'ast, T> From<BoundedArrayExpression<'ast, T>> for TypedExpression<'ast, T> {
+    fn from(e: BoundedArrayExpression<'ast, T>) -> TypedExpression<T> {
+        TypedExpression::BoundedArray(e)
+    }
+}
+
 impl<'ast, T: fmt::Display> fmt::Display for TypedExpression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -866,6 +1059,8 @@ impl<'ast, T: fmt::Display> fmt::Display for TypedExpression<'ast, T> {
             TypedExpression::Array(ref e) => write!(f, "{}", e),
             TypedExpression::Struct(ref s) => write!(f, "{}", s),
             TypedExpression::Tuple(ref t) => write!(f, "{}", t),
+            TypedExpression::Enum(ref e) => write!(f, "{}", e),
+            TypedExpression::BoundedArray(ref e) => write!(f, "{}", e),
             TypedExpression::Int(ref s) => write!(f, "{}", s),
         }
     }
@@ -914,6 +1109,8 @@ impl<'ast, T: Clone> Typed<'ast, T> for TypedExpression<'ast, T> {
             TypedExpression::Uint(ref e) => e.get_type(),
             TypedExpression::Struct(ref s) => s.get_type(),
             TypedExpression::Tuple(ref s) => s.get_type(),
+            TypedExpression::Enum(ref e) => e.get_type(),
+            TypedExpression::BoundedArray(ref e) => e.get_type(),
             TypedExpression::Int(_) => Type::Int,
         }
     }
@@ -937,6 +1134,18 @@ impl<'ast, T: Clone> Typed<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Clone> Typed<'ast, T> for EnumExpression<'ast, T> {
+    fn get_type(&self) -> Type<'ast, T> {
+        Type::Enum(self.ty.clone())
+    }
+}
+
+impl<'ast, T: Clone> Typed<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn get_type(&self) -> Type<'ast, T> {
+        Type::BoundedArray(self.ty.clone())
+    }
+}
+
 impl<'ast, T: Clone> Typed<'ast, T> for FieldElementExpression<'ast, T> {
     fn get_type(&self) -> Type<'ast, T> {
         Type::FieldElement
@@ -1081,10 +1290,40 @@ impl<'ast, T: fmt::Display, E> fmt::Display for ElementExpression<'ast, T, E> {
     }
 }
 
+/// A variant of an enum value, used to check which variant the value holds and to access its payload
+#[derive(Clone, PartialEq, Debug, Hash, Eq, PartialOrd, Ord)]
+pub struct VariantExpression<'ast, T> {
+    pub enumeration: Box<EnumExpression<'ast, T>>,
+    pub variant: u32,
+}
+
+impl<'ast, T> VariantExpression<'ast, T> {
+    pub fn new(enumeration: EnumExpression<'ast, T>, variant: u32) -> Self {
+        VariantExpression {
+            enumeration: box enumeration,
+            variant,
+        }
+    }
+}
+
+impl<'ast, T: fmt::Display> fmt::Display for VariantExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let enum_ty = self.enumeration.ty();
+        write!(
+            f,
+            "{}::{}",
+            enum_ty.name(),
+            enum_ty.variants[self.variant as usize].id
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Hash, Eq, PartialOrd, Ord)]
 pub enum ConditionalKind {
     IfElse,
     Ternary,
+    /// A conditional introduced when desugaring control flow, whose branches are always isolated
+    Guard,
 }
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq, PartialOrd, Ord)]
@@ -1114,7 +1353,7 @@ impl<'ast, T, E> ConditionalExpression<'ast, T, E> {
 impl<'ast, T: fmt::Display, E: fmt::Display> fmt::Display for ConditionalExpression<'ast, T, E> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.kind {
-            ConditionalKind::IfElse => {
+            ConditionalKind::IfElse | ConditionalKind::Guard => {
                 write!(
                     f,
                     "if {} {} else {}",
@@ -1132,9 +1371,12 @@ impl<'ast, T: fmt::Display, E: fmt::Display> fmt::Display for ConditionalExpress
 
 #[derive(Clone, PartialEq, Debug, Hash, Eq, PartialOrd, Ord)]
 pub struct FunctionCallExpression<'ast, T, E> {
-    pub function_key: DeclarationFunctionKey<'ast, T>,
+    // boxed so that calls do not grow the size of every expression
+    pub function_key: Box<DeclarationFunctionKey<'ast, T>>,
     pub generics: Vec<Option<UExpression<'ast, T>>>,
     pub arguments: Vec<TypedExpression<'ast, T>>,
+    // the functions passed for the function parameters of the callee, which are resolved when inlining the call
+    pub function_arguments: Vec<DeclarationFunctionKey<'ast, T>>,
     ty: PhantomData<E>,
 }
 
@@ -1145,12 +1387,21 @@ impl<'ast, T, E> FunctionCallExpression<'ast, T, E> {
         arguments: Vec<TypedExpression<'ast, T>>,
     ) -> Self {
         FunctionCallExpression {
-            function_key,
+            function_key: Box::new(function_key),
             generics,
             arguments,
+            function_arguments: vec![],
             ty: PhantomData,
         }
     }
+
+    pub fn function_arguments(
+        mut self,
+        function_arguments: Vec<DeclarationFunctionKey<'ast, T>>,
+    ) -> Self {
+        self.function_arguments = function_arguments;
+        self
+    }
 }
 
 impl<'ast, T: fmt::Display, E> fmt::Display for FunctionCallExpression<'ast, T, E> {
@@ -1176,6 +1427,7 @@ impl<'ast, T: fmt::Display, E> fmt::Display for FunctionCallExpression<'ast, T,
             self.arguments
                 .iter()
                 .map(|a| a.to_string())
+                .chain(self.function_arguments.iter().map(|k| k.id.to_string()))
                 .collect::<Vec<_>>()
                 .join(",")
         )
@@ -1254,6 +1506,44 @@ impl<'ast, T: Field> From<TypedAssignee<'ast, T>> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> From<TypedAssignee<'ast, T>> for EnumExpression<'ast, T> {
+    fn from(assignee: TypedAssignee<'ast, T>) -> Self {
+        match assignee {
+            TypedAssignee::Identifier(v) => {
+                let inner = EnumExpression::identifier(v.id);
+                match v._type {
+                    GType::Enum(enum_ty) => inner.annotate(enum_ty),
+                    _ => unreachable!(),
+                }
+            }
+            TypedAssignee::Select(box a, box index) => EnumExpression::select(a.into(), index),
+            TypedAssignee::Member(box a, id) => EnumExpression::member(a.into(), id),
+            TypedAssignee::Element(box a, index) => EnumExpression::element(a.into(), index),
+        }
+    }
+}
+
+impl<'ast, T: Field> From<TypedAssignee<'ast, T>> for BoundedArrayExpression<'ast, T> {
+    fn from(assignee: TypedAssignee<'ast, T>) -> Self {
+        match assignee {
+            TypedAssignee::Identifier(v) => {
+                let inner = BoundedArrayExpression::identifier(v.id);
+                match v._type {
+                    GType::BoundedArray(array_ty) => inner.annotate(array_ty),
+                    _ => unreachable!(),
+                }
+            }
+            TypedAssignee::Select(box a, box index) => {
+                BoundedArrayExpression::select(a.into(), index)
+            }
+            TypedAssignee::Member(box a, id) => BoundedArrayExpression::member(a.into(), id),
+            TypedAssignee::Element(box a, index) => {
+                BoundedArrayExpression::element(a.into(), index)
+            }
+        }
+    }
+}
+
 impl<'ast, T: Field> From<TypedAssignee<'ast, T>> for StructExpression<'ast, T> {
     fn from(assignee: TypedAssignee<'ast, T>) -> Self {
         match assignee {
@@ -1303,6 +1593,34 @@ impl<'ast, T: Field> From<TypedAssignee<'ast, T>> for FieldElementExpression<'as
     }
 }
 
+impl<'ast, T: Field> From<TypedAssignee<'ast, T>> for BooleanExpression<'ast, T> {
+    fn from(assignee: TypedAssignee<'ast, T>) -> Self {
+        match assignee {
+            TypedAssignee::Identifier(v) => BooleanExpression::identifier(v.id),
+            TypedAssignee::Element(box a, index) => BooleanExpression::element(a.into(), index),
+            TypedAssignee::Member(box a, id) => BooleanExpression::member(a.into(), id),
+            TypedAssignee::Select(box a, box index) => BooleanExpression::select(a.into(), index),
+        }
+    }
+}
+
+impl<'ast, T: Field> From<TypedAssignee<'ast, T>> for UExpression<'ast, T> {
+    fn from(assignee: TypedAssignee<'ast, T>) -> Self {
+        match assignee {
+            TypedAssignee::Identifier(v) => {
+                let inner = UExpression::identifier(v.id);
+                match v._type {
+                    GType::Uint(bitwidth) => inner.annotate(bitwidth),
+                    _ => unreachable!(),
+                }
+            }
+            TypedAssignee::Element(box a, index) => UExpression::element(a.into(), index),
+            TypedAssignee::Member(box a, id) => UExpression::member(a.into(), id),
+            TypedAssignee::Select(box a, box index) => UExpression::select(a.into(), index),
+        }
+    }
+}
+
 impl<'ast, T> Add for FieldElementExpression<'ast, T> {
     type Output = Self;
 
@@ -1378,6 +1696,7 @@ pub enum BooleanExpression<'ast, T> {
     ArrayEq(EqExpression<ArrayExpression<'ast, T>>),
     StructEq(EqExpression<StructExpression<'ast, T>>),
     TupleEq(EqExpression<TupleExpression<'ast, T>>),
+    EnumEq(EqExpression<EnumExpression<'ast, T>>),
     UintEq(EqExpression<UExpression<'ast, T>>),
     Or(
         Box<BooleanExpression<'ast, T>>,
@@ -1388,6 +1707,7 @@ pub enum BooleanExpression<'ast, T> {
         Box<BooleanExpression<'ast, T>>,
     ),
     Not(Box<BooleanExpression<'ast, T>>),
+    IsVariant(VariantExpression<'ast, T>),
     Conditional(ConditionalExpression<'ast, T, Self>),
     Member(MemberExpression<'ast, T, Self>),
     FunctionCall(FunctionCallExpression<'ast, T, Self>),
@@ -1505,6 +1825,10 @@ pub enum ArrayExpressionInner<'ast, T> {
         Box<UExpression<'ast, T>>,
     ),
     Repeat(Box<TypedExpression<'ast, T>>, Box<UExpression<'ast, T>>),
+    Bytes(ByteString),
+    /// The elements of a bounded array, padded with zeroes up to its capacity. Selecting an element of it checks that
+    /// the index is within the length of the bounded array
+    Data(Box<BoundedArrayExpression<'ast, T>>),
 }
 
 impl<'ast, T> ArrayExpressionInner<'ast, T> {
@@ -1606,6 +1930,7 @@ pub enum TupleExpressionInner<'ast, T> {
     Member(MemberExpression<'ast, T, TupleExpression<'ast, T>>),
     Select(SelectExpression<'ast, T, TupleExpression<'ast, T>>),
     Element(ElementExpression<'ast, T, TupleExpression<'ast, T>>),
+    Payload(VariantExpression<'ast, T>),
 }
 
 impl<'ast, T> TupleExpressionInner<'ast, T> {
@@ -1614,6 +1939,197 @@ impl<'ast, T> TupleExpressionInner<'ast, T> {
     }
 }
 
+impl<'ast, T: Clone> TupleExpression<'ast, T> {
+    /// The payload of an enum value, which must be of the given variant
+    pub fn payload(enumeration: EnumExpression<'ast, T>, variant: u32) -> Self {
+        let ty = enumeration.ty().payload_type(variant);
+        TupleExpressionInner::Payload(VariantExpression::new(enumeration, variant)).annotate(ty)
+    }
+}
+
+/// An expression of an enum type
+#[derive(Clone, PartialEq, Debug, Hash, Eq, PartialOrd, Ord)]
+pub struct EnumExpression<'ast, T> {
+    ty: EnumType<'ast, T>,
+    inner: EnumExpressionInner<'ast, T>,
+}
+
+impl<'ast, T> EnumExpression<'ast, T> {
+    pub fn ty(&self) -> &EnumType<'ast, T> {
+        &self.ty
+    }
+
+    pub fn as_inner(&self) -> &EnumExpressionInner<'ast, T> {
+        &self.inner
+    }
+
+    pub fn as_inner_mut(&mut self) -> &mut EnumExpressionInner<'ast, T> {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> EnumExpressionInner<'ast, T> {
+        self.inner
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Hash, Eq, PartialOrd, Ord)]
+pub enum EnumExpressionInner<'ast, T> {
+    Block(BlockExpression<'ast, T, EnumExpression<'ast, T>>),
+    Identifier(IdentifierExpression<'ast, EnumExpression<'ast, T>>),
+    /// A variant, given by its index in the declaration, and its payload
+    Value(u32, Vec<TypedExpression<'ast, T>>),
+    FunctionCall(FunctionCallExpression<'ast, T, EnumExpression<'ast, T>>),
+    Conditional(ConditionalExpression<'ast, T, EnumExpression<'ast, T>>),
+    Member(MemberExpression<'ast, T, EnumExpression<'ast, T>>),
+    Select(SelectExpression<'ast, T, EnumExpression<'ast, T>>),
+    Element(ElementExpression<'ast, T, EnumExpression<'ast, T>>),
+}
+
+impl<'ast, T> EnumExpressionInner<'ast, T> {
+    pub fn annotate(self, ty: EnumType<'ast, T>) -> EnumExpression<'ast, T> {
+        EnumExpression { ty, inner: self }
+    }
+}
+
+impl<'ast, T: fmt::Display> fmt::Display for EnumExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            EnumExpressionInner::Block(ref block) => write!(f, "{}", block),
+            EnumExpressionInner::Identifier(ref var) => write!(f, "{}", var),
+            EnumExpressionInner::Value(variant, ref values) => {
+                write!(
+                    f,
+                    "{}::{}",
+                    self.ty.name(),
+                    self.ty.variants[variant as usize].id
+                )?;
+                if !values.is_empty() {
+                    write!(
+                        f,
+                        "({})",
+                        values
+                            .iter()
+                            .map(|v| v.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )?;
+                }
+                Ok(())
+            }
+            EnumExpressionInner::FunctionCall(ref function_call) => {
+                write!(f, "{}", function_call)
+            }
+            EnumExpressionInner::Conditional(ref c) => write!(f, "{}", c),
+            EnumExpressionInner::Member(ref m) => write!(f, "{}", m),
+            EnumExpressionInner::Select(ref select) => write!(f, "{}", select),
+            EnumExpressionInner::Element(ref element) => write!(f, "{}", element),
+        }
+    }
+}
+
+/// An expression of a bounded array type
+#[derive(Clone, PartialEq, Debug, Hash, Eq, PartialOrd, Ord)]
+pub struct BoundedArrayExpression<'ast, T> {
+    ty: ArrayType<'ast, T>,
+    inner: BoundedArrayExpressionInner<'ast, T>,
+}
+
+impl<'ast, T> BoundedArrayExpression<'ast, T> {
+    pub fn ty(&self) -> &ArrayType<'ast, T> {
+        &self.ty
+    }
+
+    pub fn as_inner(&self) -> &BoundedArrayExpressionInner<'ast, T> {
+        &self.inner
+    }
+
+    pub fn as_inner_mut(&mut self) -> &mut BoundedArrayExpressionInner<'ast, T> {
+        &mut self.inner
+    }
+
+    pub fn into_inner(self) -> BoundedArrayExpressionInner<'ast, T> {
+        self.inner
+    }
+}
+
+impl<'ast, T: Clone> BoundedArrayExpression<'ast, T> {
+    pub fn inner_type(&self) -> &Type<'ast, T> {
+        &self.ty.ty
+    }
+
+    pub fn capacity(&self) -> UExpression<'ast, T> {
+        *self.ty.size.clone()
+    }
+
+    /// The number of elements of this bounded array
+    pub fn len(self) -> UExpression<'ast, T> {
+        UExpressionInner::Len(box self).annotate(UBitwidth::B32)
+    }
+
+    /// The elements of this bounded array, padded with zeroes up to its capacity
+    pub fn data(self) -> ArrayExpression<'ast, T> {
+        let ty = self.inner_type().clone();
+        let capacity = self.capacity();
+        ArrayExpressionInner::Data(box self).annotate(ty, capacity)
+    }
+
+    /// Two bounded arrays are equal if both their lengths and their padded elements are
+    pub fn equals(self, other: Self) -> BooleanExpression<'ast, T> {
+        BooleanExpression::And(
+            box BooleanExpression::UintEq(EqExpression::new(
+                self.clone().len(),
+                other.clone().len(),
+            )),
+            box BooleanExpression::ArrayEq(EqExpression::new(self.data(), other.data())),
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Hash, Eq, PartialOrd, Ord)]
+pub enum BoundedArrayExpressionInner<'ast, T> {
+    Block(BlockExpression<'ast, T, BoundedArrayExpression<'ast, T>>),
+    Identifier(IdentifierExpression<'ast, BoundedArrayExpression<'ast, T>>),
+    /// The elements of an array whose size does not exceed the capacity, along with where to report
+    /// an excess if the size or the capacity is only known once generics are resolved
+    Value(Box<ArrayExpression<'ast, T>>, Option<SourceMetadata>),
+    /// A bounded array with an element added at the end, which must not be full
+    Push(
+        Box<BoundedArrayExpression<'ast, T>>,
+        Box<TypedExpression<'ast, T>>,
+    ),
+    FunctionCall(FunctionCallExpression<'ast, T, BoundedArrayExpression<'ast, T>>),
+    Conditional(ConditionalExpression<'ast, T, BoundedArrayExpression<'ast, T>>),
+    Member(MemberExpression<'ast, T, BoundedArrayExpression<'ast, T>>),
+    Select(SelectExpression<'ast, T, BoundedArrayExpression<'ast, T>>),
+    Element(ElementExpression<'ast, T, BoundedArrayExpression<'ast, T>>),
+}
+
+impl<'ast, T> BoundedArrayExpressionInner<'ast, T> {
+    pub fn annotate(self, ty: ArrayType<'ast, T>) -> BoundedArrayExpression<'ast, T> {
+        BoundedArrayExpression { ty, inner: self }
+    }
+}
+
+impl<'ast, T: fmt::Display> fmt::Display for BoundedArrayExpression<'ast, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner {
+            BoundedArrayExpressionInner::Block(ref block) => write!(f, "{}", block),
+            BoundedArrayExpressionInner::Identifier(ref var) => write!(f, "{}", var),
+            BoundedArrayExpressionInner::Value(ref array, _) => write!(f, "{}", array),
+            BoundedArrayExpressionInner::Push(ref array, ref e) => {
+                write!(f, "{}.push({})", array, e)
+            }
+            BoundedArrayExpressionInner::FunctionCall(ref function_call) => {
+                write!(f, "{}", function_call)
+            }
+            BoundedArrayExpressionInner::Conditional(ref c) => write!(f, "{}", c),
+            BoundedArrayExpressionInner::Member(ref m) => write!(f, "{}", m),
+            BoundedArrayExpressionInner::Select(ref select) => write!(f, "{}", select),
+            BoundedArrayExpressionInner::Element(ref element) => write!(f, "{}", element),
+        }
+    }
+}
+
 impl<'ast, T: fmt::Display> fmt::Display for TupleExpression<'ast, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.inner {
@@ -1642,6 +2158,9 @@ impl<'ast, T: fmt::Display> fmt::Display for TupleExpression<'ast, T> {
             TupleExpressionInner::Member(ref m) => write!(f, "{}", m),
             TupleExpressionInner::Select(ref select) => write!(f, "{}", select),
             TupleExpressionInner::Element(ref element) => write!(f, "{}", element),
+            TupleExpressionInner::Payload(ref variant) => {
+                write!(f, "{}.payload({})", variant.enumeration, variant)
+            }
         }
     }
 }
@@ -1713,6 +2232,24 @@ impl<'ast, T> From<TypedExpression<'ast, T>> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T> From<TypedExpression<'ast, T>> for EnumExpression<'ast, T> {
+    fn from(te: TypedExpression<'ast, T>) -> EnumExpression<'ast, T> {
+        match te {
+            TypedExpression::Enum(e) => e,
+            _ => unreachable!("downcast failed"),
+        }
+    }
+}
+
+impl<'ast, T> From<TypedExpression<'ast, T>> for BoundedArrayExpression<'ast, T> {
+    fn from(te: TypedExpression<'ast, T>) -> BoundedArrayExpression<'ast, T> {
+        match te {
+            TypedExpression::BoundedArray(e) => e,
+            _ => unreachable!("downcast failed"),
+        }
+    }
+}
+
 impl<'ast, T> From<TypedConstant<'ast, T>> for FieldElementExpression<'ast, T> {
     fn from(tc: TypedConstant<'ast, T>) -> FieldElementExpression<'ast, T> {
         tc.expression.into()
@@ -1749,6 +2286,18 @@ impl<'ast, T> From<TypedConstant<'ast, T>> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T> From<TypedConstant<'ast, T>> for EnumExpression<'ast, T> {
+    fn from(tc: TypedConstant<'ast, T>) -> EnumExpression<'ast, T> {
+        tc.expression.into()
+    }
+}
+
+impl<'ast, T> From<TypedConstant<'ast, T>> for BoundedArrayExpression<'ast, T> {
+    fn from(tc: TypedConstant<'ast, T>) -> BoundedArrayExpression<'ast, T> {
+        tc.expression.into()
+    }
+}
+
 impl<'ast, T> From<TypedConstant<'ast, T>> for IntExpression<'ast, T> {
     fn from(tc: TypedConstant<'ast, T>) -> IntExpression<'ast, T> {
         tc.expression.into()
@@ -1826,6 +2375,7 @@ impl<'ast, T: fmt::Display> fmt::Display for UExpression<'ast, T> {
             UExpressionInner::Conditional(ref c) => write!(f, "{}", c),
             UExpressionInner::Member(ref m) => write!(f, "{}", m),
             UExpressionInner::Element(ref element) => write!(f, "{}", element),
+            UExpressionInner::Len(ref array) => write!(f, "{}.len()", array),
         }
     }
 }
@@ -1848,10 +2398,14 @@ impl<'ast, T: fmt::Display> fmt::Display for BooleanExpression<'ast, T> {
             BooleanExpression::ArrayEq(ref e) => write!(f, "{}", e),
             BooleanExpression::StructEq(ref e) => write!(f, "{}", e),
             BooleanExpression::TupleEq(ref e) => write!(f, "{}", e),
+            BooleanExpression::EnumEq(ref e) => write!(f, "{}", e),
             BooleanExpression::UintEq(ref e) => write!(f, "{}", e),
             BooleanExpression::Or(ref lhs, ref rhs) => write!(f, "({} || {})", lhs, rhs),
             BooleanExpression::And(ref lhs, ref rhs) => write!(f, "({} && {})", lhs, rhs),
             BooleanExpression::Not(ref exp) => write!(f, "!{}", exp),
+            BooleanExpression::IsVariant(ref variant) => {
+                write!(f, "({} is {})", variant.enumeration, variant)
+            }
             BooleanExpression::Value(b) => write!(f, "{}", b),
             BooleanExpression::FunctionCall(ref function_call) => write!(f, "{}", function_call),
             BooleanExpression::Conditional(ref c) => write!(f, "{}", c),
@@ -1886,6 +2440,8 @@ impl<'ast, T: fmt::Display> fmt::Display for ArrayExpressionInner<'ast, T> {
             ArrayExpressionInner::Repeat(ref e, ref count) => {
                 write!(f, "[{}; {}]", e, count)
             }
+            ArrayExpressionInner::Bytes(ref bytes) => write!(f, "{}", bytes),
+            ArrayExpressionInner::Data(ref array) => write!(f, "{}.data", array),
             ArrayExpressionInner::Element(ref element) => write!(f, "{}", element),
         }
     }
@@ -1903,6 +2459,8 @@ impl<'ast, T: Field> From<Variable<'ast, T>> for TypedExpression<'ast, T> {
                 .into(),
             Type::Struct(ty) => StructExpression::identifier(v.id).annotate(ty).into(),
             Type::Tuple(ty) => TupleExpression::identifier(v.id).annotate(ty).into(),
+            Type::Enum(ty) => EnumExpression::identifier(v.id).annotate(ty).into(),
+            Type::BoundedArray(ty) => BoundedArrayExpression::identifier(v.id).annotate(ty).into(),
             Type::Uint(w) => UExpression::identifier(v.id).annotate(w).into(),
             Type::Int => unreachable!(),
         }
@@ -2057,6 +2615,50 @@ impl<'ast, T: Field> Expr<'ast, T> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Expr<'ast, T> for BoundedArrayExpression<'ast, T> {
+    type Inner = BoundedArrayExpressionInner<'ast, T>;
+    type Ty = ArrayType<'ast, T>;
+    type ConcreteTy = ConcreteArrayType;
+
+    fn ty(&self) -> &Self::Ty {
+        &self.ty
+    }
+
+    fn into_inner(self) -> Self::Inner {
+        self.inner
+    }
+
+    fn as_inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn as_inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.inner
+    }
+}
+
+impl<'ast, T: Field> Expr<'ast, T> for EnumExpression<'ast, T> {
+    type Inner = EnumExpressionInner<'ast, T>;
+    type Ty = EnumType<'ast, T>;
+    type ConcreteTy = ConcreteEnumType;
+
+    fn ty(&self) -> &Self::Ty {
+        &self.ty
+    }
+
+    fn into_inner(self) -> Self::Inner {
+        self.inner
+    }
+
+    fn as_inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    fn as_inner_mut(&mut self) -> &mut Self::Inner {
+        &mut self.inner
+    }
+}
+
 impl<'ast, T: Field> Expr<'ast, T> for IntExpression<'ast, T> {
     type Inner = Self;
     type Ty = Type<'ast, T>;
@@ -2246,6 +2848,42 @@ impl<'ast, T: Clone> Conditional<'ast, T> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Clone> Conditional<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn conditional(
+        condition: BooleanExpression<'ast, T>,
+        consequence: Self,
+        alternative: Self,
+        kind: ConditionalKind,
+    ) -> Self {
+        let ty = consequence.ty().clone();
+        BoundedArrayExpressionInner::Conditional(ConditionalExpression::new(
+            condition,
+            consequence,
+            alternative,
+            kind,
+        ))
+        .annotate(ty)
+    }
+}
+
+impl<'ast, T: Clone> Conditional<'ast, T> for EnumExpression<'ast, T> {
+    fn conditional(
+        condition: BooleanExpression<'ast, T>,
+        consequence: Self,
+        alternative: Self,
+        kind: ConditionalKind,
+    ) -> Self {
+        let ty = consequence.ty().clone();
+        EnumExpressionInner::Conditional(ConditionalExpression::new(
+            condition,
+            consequence,
+            alternative,
+            kind,
+        ))
+        .annotate(ty)
+    }
+}
+
 pub trait Select<'ast, T> {
     fn select<I: Into<UExpression<'ast, T>>>(array: ArrayExpression<'ast, T>, index: I) -> Self;
 }
@@ -2274,6 +2912,8 @@ impl<'ast, T: Field> Select<'ast, T> for TypedExpression<'ast, T> {
             Type::Array(..) => ArrayExpression::select(array, index).into(),
             Type::Struct(..) => StructExpression::select(array, index).into(),
             Type::Tuple(..) => TupleExpression::select(array, index).into(),
+            Type::Enum(..) => EnumExpression::select(array, index).into(),
+            Type::BoundedArray(..) => BoundedArrayExpression::select(array, index).into(),
             Type::FieldElement => FieldElementExpression::select(array, index).into(),
             Type::Boolean => BooleanExpression::select(array, index).into(),
             Type::Int => IntExpression::select(array, index).into(),
@@ -2327,6 +2967,29 @@ impl<'ast, T: Clone> Select<'ast, T> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Clone> Select<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn select<I: Into<UExpression<'ast, T>>>(array: ArrayExpression<'ast, T>, index: I) -> Self {
+        let array_ty = match array.inner_type().clone() {
+            Type::BoundedArray(array_ty) => array_ty,
+            _ => unreachable!(),
+        };
+
+        BoundedArrayExpressionInner::Select(SelectExpression::new(array, index.into()))
+            .annotate(array_ty)
+    }
+}
+
+impl<'ast, T: Clone> Select<'ast, T> for EnumExpression<'ast, T> {
+    fn select<I: Into<UExpression<'ast, T>>>(array: ArrayExpression<'ast, T>, index: I) -> Self {
+        let enum_ty = match array.inner_type().clone() {
+            Type::Enum(enum_ty) => enum_ty,
+            _ => unreachable!(),
+        };
+
+        EnumExpressionInner::Select(SelectExpression::new(array, index.into())).annotate(enum_ty)
+    }
+}
+
 pub trait Member<'ast, T>: Sized {
     fn member(s: StructExpression<'ast, T>, id: MemberId) -> Self;
 }
@@ -2399,6 +3062,34 @@ impl<'ast, T: Clone> Member<'ast, T> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Clone> Member<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn member(s: StructExpression<'ast, T>, id: MemberId) -> Self {
+        let ty = s.ty().members.iter().find(|member| id == member.id);
+        let array_ty = match ty {
+            Some(crate::typed::types::StructMember {
+                ty: box Type::BoundedArray(array_ty),
+                ..
+            }) => array_ty.clone(),
+            _ => unreachable!(),
+        };
+        BoundedArrayExpressionInner::Member(MemberExpression::new(s, id)).annotate(array_ty)
+    }
+}
+
+impl<'ast, T: Clone> Member<'ast, T> for EnumExpression<'ast, T> {
+    fn member(s: StructExpression<'ast, T>, id: MemberId) -> Self {
+        let ty = s.ty().members.iter().find(|member| id == member.id);
+        let enum_ty = match ty {
+            Some(crate::typed::types::StructMember {
+                ty: box Type::Enum(enum_ty),
+                ..
+            }) => enum_ty.clone(),
+            _ => unreachable!(),
+        };
+        EnumExpressionInner::Member(MemberExpression::new(s, id)).annotate(enum_ty)
+    }
+}
+
 pub trait Element<'ast, T>: Sized {
     fn element(s: TupleExpression<'ast, T>, id: u32) -> Self;
 }
@@ -2459,6 +3150,28 @@ impl<'ast, T: Clone> Element<'ast, T> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Clone> Element<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn element(s: TupleExpression<'ast, T>, id: u32) -> Self {
+        let ty = &s.ty().elements[id as usize];
+        let array_ty = match ty {
+            Type::BoundedArray(array_ty) => array_ty.clone(),
+            _ => unreachable!(),
+        };
+        BoundedArrayExpressionInner::Element(ElementExpression::new(s, id)).annotate(array_ty)
+    }
+}
+
+impl<'ast, T: Clone> Element<'ast, T> for EnumExpression<'ast, T> {
+    fn element(s: TupleExpression<'ast, T>, id: u32) -> Self {
+        let ty = &s.ty().elements[id as usize];
+        let enum_ty = match ty {
+            Type::Enum(enum_ty) => enum_ty.clone(),
+            _ => unreachable!(),
+        };
+        EnumExpressionInner::Element(ElementExpression::new(s, id)).annotate(enum_ty)
+    }
+}
+
 pub trait Id<'ast, T>: Expr<'ast, T> {
     fn identifier(id: Identifier<'ast>) -> Self::Inner;
 }
@@ -2499,6 +3212,18 @@ impl<'ast, T: Field> Id<'ast, T> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Id<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn identifier(id: Identifier<'ast>) -> Self::Inner {
+        BoundedArrayExpressionInner::Identifier(IdentifierExpression::new(id))
+    }
+}
+
+impl<'ast, T: Field> Id<'ast, T> for EnumExpression<'ast, T> {
+    fn identifier(id: Identifier<'ast>) -> Self::Inner {
+        EnumExpressionInner::Identifier(IdentifierExpression::new(id))
+    }
+}
+
 pub trait FunctionCall<'ast, T>: Expr<'ast, T> {
     fn function_call(
         key: DeclarationFunctionKey<'ast, T>,
@@ -2567,6 +3292,28 @@ impl<'ast, T: Field> FunctionCall<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> FunctionCall<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn function_call(
+        key: DeclarationFunctionKey<'ast, T>,
+        generics: Vec<Option<UExpression<'ast, T>>>,
+        arguments: Vec<TypedExpression<'ast, T>>,
+    ) -> Self::Inner {
+        BoundedArrayExpressionInner::FunctionCall(FunctionCallExpression::new(
+            key, generics, arguments,
+        ))
+    }
+}
+
+impl<'ast, T: Field> FunctionCall<'ast, T> for EnumExpression<'ast, T> {
+    fn function_call(
+        key: DeclarationFunctionKey<'ast, T>,
+        generics: Vec<Option<UExpression<'ast, T>>>,
+        arguments: Vec<TypedExpression<'ast, T>>,
+    ) -> Self::Inner {
+        EnumExpressionInner::FunctionCall(FunctionCallExpression::new(key, generics, arguments))
+    }
+}
+
 pub trait Block<'ast, T> {
     fn block(statements: Vec<TypedStatement<'ast, T>>, value: Self) -> Self;
 }
@@ -2613,6 +3360,23 @@ impl<'ast, T: Field> Block<'ast, T> for StructExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Block<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn block(statements: Vec<TypedStatement<'ast, T>>, value: Self) -> Self {
+        let array_ty = value.ty().clone();
+
+        BoundedArrayExpressionInner::Block(BlockExpression::new(statements, value))
+            .annotate(array_ty)
+    }
+}
+
+impl<'ast, T: Field> Block<'ast, T> for EnumExpression<'ast, T> {
+    fn block(statements: Vec<TypedStatement<'ast, T>>, value: Self) -> Self {
+        let enum_ty = value.ty().clone();
+
+        EnumExpressionInner::Block(BlockExpression::new(statements, value)).annotate(enum_ty)
+    }
+}
+
 pub trait Constant: Sized {
     // return whether this is constant
     fn is_constant(&self) -> bool;
@@ -2655,6 +3419,7 @@ impl<'ast, T: Field> Constant for ArrayExpression<'ast, T> {
             ArrayExpressionInner::Repeat(box e, box count) => {
                 count.is_constant() && e.is_constant()
             }
+            ArrayExpressionInner::Bytes(..) => true,
             _ => false,
         }
     }
@@ -2700,6 +3465,11 @@ impl<'ast, T: Field> Constant for ArrayExpression<'ast, T> {
 
                         vec![e.into_canonical_constant(); count as usize]
                     }
+                    ArrayExpressionInner::Bytes(bytes) => bytes
+                        .0
+                        .into_iter()
+                        .map(|b| UExpression::from(b).into())
+                        .collect(),
                     a => unreachable!("{}", a),
                 },
             }
@@ -2756,6 +3526,15 @@ impl<'ast, T: Field> Constant for ArrayExpression<'ast, T> {
                 )
                 .annotate(*array_ty.ty, *array_ty.size)
             }
+            ArrayExpressionInner::Bytes(bytes) => ArrayExpressionInner::Value(
+                bytes
+                    .0
+                    .into_iter()
+                    .map(|b| TypedExpressionOrSpread::Expression(UExpression::from(b).into()))
+                    .collect::<Vec<_>>()
+                    .into(),
+            )
+            .annotate(*array_ty.ty, *array_ty.size),
             _ => unreachable!(),
         }
     }
@@ -2809,6 +3588,52 @@ impl<'ast, T: Field> Constant for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> Constant for BoundedArrayExpression<'ast, T> {
+    fn is_constant(&self) -> bool {
+        match self.as_inner() {
+            BoundedArrayExpressionInner::Value(array, _) => array.is_constant(),
+            _ => false,
+        }
+    }
+
+    fn into_canonical_constant(self) -> Self {
+        let array_ty = self.ty().clone();
+
+        match self.into_inner() {
+            BoundedArrayExpressionInner::Value(box array, metadata) => {
+                BoundedArrayExpressionInner::Value(box array.into_canonical_constant(), metadata)
+                    .annotate(array_ty)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'ast, T: Field> Constant for EnumExpression<'ast, T> {
+    fn is_constant(&self) -> bool {
+        match self.as_inner() {
+            EnumExpressionInner::Value(_, v) => v.iter().all(|e| e.is_constant()),
+            _ => false,
+        }
+    }
+
+    fn into_canonical_constant(self) -> Self {
+        let enum_ty = self.ty().clone();
+
+        match self.into_inner() {
+            EnumExpressionInner::Value(variant, expressions) => EnumExpressionInner::Value(
+                variant,
+                expressions
+                    .into_iter()
+                    .map(|e| e.into_canonical_constant())
+                    .collect(),
+            )
+            .annotate(enum_ty),
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl<'ast, T: Field> Constant for TypedExpression<'ast, T> {
     fn is_constant(&self) -> bool {
         match self {
@@ -2817,6 +3642,8 @@ impl<'ast, T: Field> Constant for TypedExpression<'ast, T> {
             TypedExpression::Array(e) => e.is_constant(),
             TypedExpression::Struct(e) => e.is_constant(),
             TypedExpression::Tuple(e) => e.is_constant(),
+            TypedExpression::Enum(e) => e.is_constant(),
+            TypedExpression::BoundedArray(e) => e.is_constant(),
             TypedExpression::Uint(e) => e.is_constant(),
             _ => unreachable!(),
         }
@@ -2829,6 +3656,8 @@ impl<'ast, T: Field> Constant for TypedExpression<'ast, T> {
             TypedExpression::Array(e) => e.into_canonical_constant().into(),
             TypedExpression::Struct(e) => e.into_canonical_constant().into(),
             TypedExpression::Tuple(e) => e.into_canonical_constant().into(),
+            TypedExpression::Enum(e) => e.into_canonical_constant().into(),
+            TypedExpression::BoundedArray(e) => e.into_canonical_constant().into(),
             TypedExpression::Uint(e) => e.into_canonical_constant().into(),
             _ => unreachable!(),
         }
@@ -44,6 +44,18 @@ impl<'ast, T: Field> ResultFold<'ast, T> for TupleExpression<'ast, T> {
     }
 }
 
+impl<'ast, T: Field> ResultFold<'ast, T> for EnumExpression<'ast, T> {
+    fn fold<F: ResultFolder<'ast, T>>(self, f: &mut F) -> Result<Self, F::Error> {
+        f.fold_enum_expression(self)
+    }
+}
+
+impl<'ast, T: Field> ResultFold<'ast, T> for BoundedArrayExpression<'ast, T> {
+    fn fold<F: ResultFolder<'ast, T>>(self, f: &mut F) -> Result<Self, F::Error> {
+        f.fold_bounded_array_expression(self)
+    }
+}
+
 pub trait ResultFolder<'ast, T: Field>: Sized {
     type Error;
 
@@ -192,6 +204,8 @@ pub trait ResultFolder<'ast, T: Field>: Sized {
             Array(array_type) => Ok(Array(self.fold_array_type(array_type)?)),
             Struct(struct_type) => Ok(Struct(self.fold_struct_type(struct_type)?)),
             Tuple(tuple_type) => Ok(Tuple(self.fold_tuple_type(tuple_type)?)),
+            Enum(enum_type) => Ok(Enum(self.fold_enum_type(enum_type)?)),
+            BoundedArray(array_type) => Ok(BoundedArray(self.fold_array_type(array_type)?)),
             t => Ok(t),
         }
     }
@@ -296,6 +310,26 @@ pub trait ResultFolder<'ast, T: Field>: Sized {
         })
     }
 
+    fn fold_enum_type(&mut self, t: EnumType<'ast, T>) -> Result<EnumType<'ast, T>, Self::Error> {
+        Ok(EnumType {
+            variants: t
+                .variants
+                .into_iter()
+                .map(|v| {
+                    Ok(EnumVariant {
+                        types: v
+                            .types
+                            .into_iter()
+                            .map(|t| self.fold_type(t))
+                            .collect::<Result<_, _>>()?,
+                        ..v
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            ..t
+        })
+    }
+
     fn fold_struct_type(
         &mut self,
         t: StructType<'ast, T>,
@@ -329,6 +363,10 @@ pub trait ResultFolder<'ast, T: Field>: Sized {
             Array(array_type) => Ok(Array(self.fold_declaration_array_type(array_type)?)),
             Struct(struct_type) => Ok(Struct(self.fold_declaration_struct_type(struct_type)?)),
             Tuple(tuple_type) => Ok(Tuple(self.fold_declaration_tuple_type(tuple_type)?)),
+            Enum(enum_type) => Ok(Enum(self.fold_declaration_enum_type(enum_type)?)),
+            BoundedArray(array_type) => {
+                Ok(BoundedArray(self.fold_declaration_array_type(array_type)?))
+            }
             t => Ok(t),
         }
     }
@@ -356,6 +394,29 @@ pub trait ResultFolder<'ast, T: Field>: Sized {
         })
     }
 
+    fn fold_declaration_enum_type(
+        &mut self,
+        t: DeclarationEnumType<'ast, T>,
+    ) -> Result<DeclarationEnumType<'ast, T>, Self::Error> {
+        Ok(DeclarationEnumType {
+            variants: t
+                .variants
+                .into_iter()
+                .map(|v| {
+                    Ok(DeclarationEnumVariant {
+                        types: v
+                            .types
+                            .into_iter()
+                            .map(|t| self.fold_declaration_type(t))
+                            .collect::<Result<_, _>>()?,
+                        ..v
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            ..t
+        })
+    }
+
     fn fold_declaration_struct_type(
         &mut self,
         t: DeclarationStructType<'ast, T>,
@@ -465,6 +526,27 @@ pub trait ResultFolder<'ast, T: Field>: Sized {
         fold_tuple_expression(self, e)
     }
 
+    fn fold_enum_expression(
+        &mut self,
+        e: EnumExpression<'ast, T>,
+    ) -> Result<EnumExpression<'ast, T>, Self::Error> {
+        fold_enum_expression(self, e)
+    }
+
+    fn fold_bounded_array_expression(
+        &mut self,
+        e: BoundedArrayExpression<'ast, T>,
+    ) -> Result<BoundedArrayExpression<'ast, T>, Self::Error> {
+        fold_bounded_array_expression(self, e)
+    }
+
+    fn fold_variant_expression(
+        &mut self,
+        e: VariantExpression<'ast, T>,
+    ) -> Result<VariantExpression<'ast, T>, Self::Error> {
+        fold_variant_expression(self, e)
+    }
+
     fn fold_int_expression(
         &mut self,
         e: IntExpression<'ast, T>,
@@ -521,6 +603,22 @@ pub trait ResultFolder<'ast, T: Field>: Sized {
     ) -> Result<TupleExpressionInner<'ast, T>, Self::Error> {
         fold_tuple_expression_inner(self, ty, e)
     }
+
+    fn fold_enum_expression_inner(
+        &mut self,
+        ty: &EnumType<'ast, T>,
+        e: EnumExpressionInner<'ast, T>,
+    ) -> Result<EnumExpressionInner<'ast, T>, Self::Error> {
+        fold_enum_expression_inner(self, ty, e)
+    }
+
+    fn fold_bounded_array_expression_inner(
+        &mut self,
+        ty: &ArrayType<'ast, T>,
+        e: BoundedArrayExpressionInner<'ast, T>,
+    ) -> Result<BoundedArrayExpressionInner<'ast, T>, Self::Error> {
+        fold_bounded_array_expression_inner(self, ty, e)
+    }
 }
 
 pub fn fold_assembly_statement<'ast, T: Field, F: ResultFolder<'ast, T>>(
@@ -556,6 +654,9 @@ pub fn fold_statement<'ast, T: Field, F: ResultFolder<'ast, T>>(
         TypedStatement::Assertion(e, error) => {
             TypedStatement::Assertion(f.fold_boolean_expression(e)?, error)
         }
+        TypedStatement::StaticAssertion(e, metadata) => {
+            TypedStatement::StaticAssertion(f.fold_boolean_expression(e)?, metadata)
+        }
         TypedStatement::For(v, from, to, statements) => TypedStatement::For(
             f.fold_variable(v)?,
             f.fold_uint_expression(from)?,
@@ -568,6 +669,66 @@ pub fn fold_statement<'ast, T: Field, F: ResultFolder<'ast, T>>(
                 .flatten()
                 .collect(),
         ),
+        TypedStatement::ForEach(v, array, statements) => TypedStatement::ForEach(
+            f.fold_variable(v)?,
+            f.fold_array_expression(array)?,
+            statements
+                .into_iter()
+                .map(|s| f.fold_statement(s))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect(),
+        ),
+        TypedStatement::While(condition, bound, statements, error) => TypedStatement::While(
+            f.fold_boolean_expression(condition)?,
+            f.fold_uint_expression(bound)?,
+            statements
+                .into_iter()
+                .map(|s| f.fold_statement(s))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect(),
+            error,
+        ),
+        TypedStatement::If(condition, consequence, alternative) => TypedStatement::If(
+            f.fold_boolean_expression(condition)?,
+            consequence
+                .into_iter()
+                .map(|s| f.fold_statement(s))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect(),
+            alternative
+                .into_iter()
+                .map(|s| f.fold_statement(s))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .flatten()
+                .collect(),
+        ),
+        TypedStatement::ComptimeIf(condition, consequence, alternative, metadata) => {
+            TypedStatement::ComptimeIf(
+                f.fold_boolean_expression(condition)?,
+                consequence
+                    .into_iter()
+                    .map(|s| f.fold_statement(s))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                alternative
+                    .into_iter()
+                    .map(|s| f.fold_statement(s))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                metadata,
+            )
+        }
         TypedStatement::Log(s, e) => TypedStatement::Log(
             s,
             e.into_iter()
@@ -658,10 +819,12 @@ pub fn fold_array_expression_inner<'ast, T: Field, F: ResultFolder<'ast, T>>(
             let count = f.fold_uint_expression(count)?;
             Repeat(box e, box count)
         }
+        Bytes(bytes) => Bytes(bytes),
         Element(element) => match f.fold_element_expression(ty, element)? {
             ElementOrExpression::Element(m) => Element(m),
             ElementOrExpression::Expression(u) => u,
         },
+        Data(box array) => Data(box f.fold_bounded_array_expression(array)?),
     };
     Ok(e)
 }
@@ -765,10 +928,108 @@ pub fn fold_tuple_expression_inner<'ast, T: Field, F: ResultFolder<'ast, T>>(
             ElementOrExpression::Element(m) => Element(m),
             ElementOrExpression::Expression(u) => u,
         },
+        Payload(v) => Payload(f.fold_variant_expression(v)?),
     };
     Ok(e)
 }
 
+pub fn fold_enum_expression_inner<'ast, T: Field, F: ResultFolder<'ast, T>>(
+    f: &mut F,
+    ty: &EnumType<'ast, T>,
+    e: EnumExpressionInner<'ast, T>,
+) -> Result<EnumExpressionInner<'ast, T>, F::Error> {
+    use EnumExpressionInner::*;
+
+    let e = match e {
+        Block(block) => Block(f.fold_block_expression(block)?),
+        Identifier(id) => match f.fold_identifier_expression(ty, id)? {
+            IdentifierOrExpression::Identifier(i) => Identifier(i),
+            IdentifierOrExpression::Expression(u) => u,
+        },
+        Value(variant, exprs) => Value(
+            variant,
+            exprs
+                .into_iter()
+                .map(|e| f.fold_expression(e))
+                .collect::<Result<_, _>>()?,
+        ),
+        FunctionCall(function_call) => match f.fold_function_call_expression(ty, function_call)? {
+            FunctionCallOrExpression::FunctionCall(c) => FunctionCall(c),
+            FunctionCallOrExpression::Expression(u) => u,
+        },
+        Conditional(c) => match f.fold_conditional_expression(ty, c)? {
+            ConditionalOrExpression::Conditional(c) => Conditional(c),
+            ConditionalOrExpression::Expression(u) => u,
+        },
+        Member(m) => match f.fold_member_expression(ty, m)? {
+            MemberOrExpression::Member(m) => Member(m),
+            MemberOrExpression::Expression(u) => u,
+        },
+        Select(select) => match f.fold_select_expression(ty, select)? {
+            SelectOrExpression::Select(m) => Select(m),
+            SelectOrExpression::Expression(u) => u,
+        },
+        Element(element) => match f.fold_element_expression(ty, element)? {
+            ElementOrExpression::Element(m) => Element(m),
+            ElementOrExpression::Expression(u) => u,
+        },
+    };
+    Ok(e)
+}
+
+pub fn fold_bounded_array_expression_inner<'ast, T: Field, F: ResultFolder<'ast, T>>(
+    f: &mut F,
+    ty: &ArrayType<'ast, T>,
+    e: BoundedArrayExpressionInner<'ast, T>,
+) -> Result<BoundedArrayExpressionInner<'ast, T>, F::Error> {
+    use BoundedArrayExpressionInner::*;
+
+    let e = match e {
+        Block(block) => Block(f.fold_block_expression(block)?),
+        Identifier(id) => match f.fold_identifier_expression(ty, id)? {
+            IdentifierOrExpression::Identifier(i) => Identifier(i),
+            IdentifierOrExpression::Expression(u) => u,
+        },
+        Value(box array, metadata) => Value(box f.fold_array_expression(array)?, metadata),
+        Push(box array, box e) => {
+            let array = f.fold_bounded_array_expression(array)?;
+            let e = f.fold_expression(e)?;
+            Push(box array, box e)
+        }
+        FunctionCall(function_call) => match f.fold_function_call_expression(ty, function_call)? {
+            FunctionCallOrExpression::FunctionCall(c) => FunctionCall(c),
+            FunctionCallOrExpression::Expression(u) => u,
+        },
+        Conditional(c) => match f.fold_conditional_expression(ty, c)? {
+            ConditionalOrExpression::Conditional(c) => Conditional(c),
+            ConditionalOrExpression::Expression(u) => u,
+        },
+        Member(m) => match f.fold_member_expression(ty, m)? {
+            MemberOrExpression::Member(m) => Member(m),
+            MemberOrExpression::Expression(u) => u,
+        },
+        Select(select) => match f.fold_select_expression(ty, select)? {
+            SelectOrExpression::Select(m) => Select(m),
+            SelectOrExpression::Expression(u) => u,
+        },
+        Element(element) => match f.fold_element_expression(ty, element)? {
+            ElementOrExpression::Element(m) => Element(m),
+            ElementOrExpression::Expression(u) => u,
+        },
+    };
+    Ok(e)
+}
+
+pub fn fold_variant_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
+    f: &mut F,
+    e: VariantExpression<'ast, T>,
+) -> Result<VariantExpression<'ast, T>, F::Error> {
+    Ok(VariantExpression::new(
+        f.fold_enum_expression(*e.enumeration)?,
+        e.variant,
+    ))
+}
+
 pub fn fold_field_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
     f: &mut F,
     e: FieldElementExpression<'ast, T>,
@@ -1007,17 +1268,25 @@ pub fn fold_function_call_expression<
     _: &E::Ty,
     e: FunctionCallExpression<'ast, T, E>,
 ) -> Result<FunctionCallOrExpression<'ast, T, E>, F::Error> {
-    Ok(FunctionCallOrExpression::Expression(E::function_call(
-        f.fold_declaration_function_key(e.function_key)?,
-        e.generics
-            .into_iter()
-            .map(|g| g.map(|g| f.fold_uint_expression(g)).transpose())
-            .collect::<Result<_, _>>()?,
-        e.arguments
-            .into_iter()
-            .map(|e| f.fold_expression(e))
-            .collect::<Result<_, _>>()?,
-    )))
+    Ok(FunctionCallOrExpression::FunctionCall(
+        FunctionCallExpression::new(
+            f.fold_declaration_function_key(*e.function_key)?,
+            e.generics
+                .into_iter()
+                .map(|g| g.map(|g| f.fold_uint_expression(g)).transpose())
+                .collect::<Result<_, _>>()?,
+            e.arguments
+                .into_iter()
+                .map(|e| f.fold_expression(e))
+                .collect::<Result<_, _>>()?,
+        )
+        .function_arguments(
+            e.function_arguments
+                .into_iter()
+                .map(|k| f.fold_declaration_function_key(k))
+                .collect::<Result<_, _>>()?,
+        ),
+    ))
 }
 
 pub fn fold_boolean_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
@@ -1053,6 +1322,10 @@ pub fn fold_boolean_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
             EqOrBoolean::Eq(e) => TupleEq(e),
             EqOrBoolean::Boolean(u) => u,
         },
+        EnumEq(e) => match f.fold_eq_expression(e)? {
+            EqOrBoolean::Eq(e) => EnumEq(e),
+            EqOrBoolean::Boolean(u) => u,
+        },
         UintEq(e) => match f.fold_eq_expression(e)? {
             EqOrBoolean::Eq(e) => UintEq(e),
             EqOrBoolean::Boolean(u) => u,
@@ -1111,6 +1384,7 @@ pub fn fold_boolean_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
             let e = f.fold_boolean_expression(e)?;
             Not(box e)
         }
+        IsVariant(v) => IsVariant(f.fold_variant_expression(v)?),
         FunctionCall(function_call) => {
             match f.fold_function_call_expression(&Type::Boolean, function_call)? {
                 FunctionCallOrExpression::FunctionCall(c) => FunctionCall(c),
@@ -1264,6 +1538,7 @@ pub fn fold_uint_expression_inner<'ast, T: Field, F: ResultFolder<'ast, T>>(
             ElementOrExpression::Element(m) => Element(m),
             ElementOrExpression::Expression(u) => u,
         },
+        Len(box array) => Len(box f.fold_bounded_array_expression(array)?),
     };
     Ok(e)
 }
@@ -1325,9 +1600,8 @@ pub fn fold_declaration_constant<'ast, T: Field, F: ResultFolder<'ast, T>>(
     c: DeclarationConstant<'ast, T>,
 ) -> Result<DeclarationConstant<'ast, T>, F::Error> {
     match c {
-        DeclarationConstant::Expression(e) => {
-            Ok(DeclarationConstant::Expression(f.fold_expression(e)?))
-        }
+        // generic expressions only refer to generics and constants, so they are not visited by default
+        // to avoid confusing them with variables in scope
         DeclarationConstant::Constant(c) => Ok(DeclarationConstant::Constant(
             f.fold_canonical_constant_identifier(c)?,
         )),
@@ -1346,6 +1620,8 @@ pub fn fold_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
         TypedExpression::Array(e) => Ok(f.fold_array_expression(e)?.into()),
         TypedExpression::Struct(e) => Ok(f.fold_struct_expression(e)?.into()),
         TypedExpression::Tuple(e) => Ok(f.fold_tuple_expression(e)?.into()),
+        TypedExpression::Enum(e) => Ok(f.fold_enum_expression(e)?.into()),
+        TypedExpression::BoundedArray(e) => Ok(f.fold_bounded_array_expression(e)?.into()),
         TypedExpression::Int(e) => Ok(f.fold_int_expression(e)?.into()),
     }
 }
@@ -1384,6 +1660,28 @@ pub fn fold_tuple_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
     })
 }
 
+pub fn fold_enum_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
+    f: &mut F,
+    e: EnumExpression<'ast, T>,
+) -> Result<EnumExpression<'ast, T>, F::Error> {
+    let ty = f.fold_enum_type(e.ty)?;
+    Ok(EnumExpression {
+        inner: f.fold_enum_expression_inner(&ty, e.inner)?,
+        ty,
+    })
+}
+
+pub fn fold_bounded_array_expression<'ast, T: Field, F: ResultFolder<'ast, T>>(
+    f: &mut F,
+    e: BoundedArrayExpression<'ast, T>,
+) -> Result<BoundedArrayExpression<'ast, T>, F::Error> {
+    let ty = f.fold_array_type(e.ty)?;
+    Ok(BoundedArrayExpression {
+        inner: f.fold_bounded_array_expression_inner(&ty, e.inner)?,
+        ty,
+    })
+}
+
 pub fn fold_constant<'ast, T: Field, F: ResultFolder<'ast, T>>(
     f: &mut F,
     c: TypedConstant<'ast, T>,
@@ -1430,6 +1728,9 @@ pub fn fold_symbol_declaration<'ast, T: Field, F: ResultFolder<'ast, T>>(
         TypedSymbolDeclaration::Constant(d) => {
             TypedSymbolDeclaration::Constant(f.fold_constant_symbol_declaration(d)?)
         }
+        TypedSymbolDeclaration::StaticAssertion(e, metadata) => {
+            TypedSymbolDeclaration::StaticAssertion(f.fold_boolean_expression(e)?, metadata)
+        }
     })
 }
 
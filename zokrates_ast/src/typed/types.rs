@@ -37,6 +37,12 @@ impl<S> IntoType<S> for GTupleType<S> {
     }
 }
 
+impl<S> IntoType<S> for GEnumType<S> {
+    fn into_type(self) -> GType<S> {
+        GType::Enum(self)
+    }
+}
+
 impl<S> IntoType<S> for UBitwidth {
     fn into_type(self) -> GType<S> {
         GType::Uint(self)
@@ -118,6 +124,36 @@ impl<'ast> fmt::Display for GenericIdentifier<'ast> {
     }
 }
 
+/// The reason why a generic type cannot be instantiated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenericsError<'ast> {
+    /// The value of a generic parameter is not known
+    Unknown(GenericIdentifier<'ast>),
+    /// A generic expression overflows or divides by zero once its generic parameters are known
+    Overflow(String),
+}
+
+impl<'ast> From<GenericIdentifier<'ast>> for GenericsError<'ast> {
+    fn from(g: GenericIdentifier<'ast>) -> Self {
+        GenericsError::Unknown(g)
+    }
+}
+
+impl<'ast> fmt::Display for GenericsError<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenericsError::Unknown(g) => write!(
+                f,
+                "Failed to infer value for generic parameter `{}`, try providing an explicit value",
+                g
+            ),
+            GenericsError::Overflow(e) => {
+                write!(f, "Generic value `{}` overflows or divides by zero", e)
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SpecializationError;
 
@@ -150,32 +186,57 @@ pub enum DeclarationConstant<'ast, T> {
     Expression(TypedExpression<'ast, T>),
 }
 
-impl<'ast, T> DeclarationConstant<'ast, T> {
-    pub fn map<S: From<CanonicalConstantIdentifier<'ast>> + From<u32> + Clone>(
+impl<'ast, T: Field> DeclarationConstant<'ast, T> {
+    pub fn map<
+        S: From<CanonicalConstantIdentifier<'ast>> + From<u32> + GenericValue<'ast, T> + Clone,
+    >(
         self,
         generics: &GGenericsAssignment<'ast, S>,
-    ) -> Result<S, GenericIdentifier<'ast>> {
+    ) -> Result<S, GenericsError<'ast>> {
         match self {
-            DeclarationConstant::Generic(g) => generics.0.get(&g).cloned().ok_or(g),
+            DeclarationConstant::Generic(g) => generics.0.get(&g).cloned().ok_or(g.into()),
             DeclarationConstant::Concrete(v) => Ok(v.into()),
             DeclarationConstant::Constant(c) => Ok(c.into()),
-            DeclarationConstant::Expression(_) => unreachable!(),
+            DeclarationConstant::Expression(e) => {
+                let e = specialize_generic_expression(e.try_into().unwrap(), generics)?;
+                Ok(S::from_expression(e).unwrap())
+            }
         }
     }
 
-    pub fn map_concrete<S: From<u32> + Clone>(
+    pub fn map_concrete<S: From<u32> + GenericValue<'ast, T> + Clone>(
         self,
         generics: &GGenericsAssignment<'ast, S>,
-    ) -> Result<S, GenericIdentifier<'ast>> {
+    ) -> Result<S, GenericsError<'ast>> {
         match self {
             DeclarationConstant::Constant(_) => unreachable!(
                 "called map_concrete on a constant, it should have been resolved before"
             ),
-            DeclarationConstant::Generic(g) => generics.0.get(&g).cloned().ok_or(g),
+            DeclarationConstant::Generic(g) => generics.0.get(&g).cloned().ok_or(g.into()),
             DeclarationConstant::Concrete(v) => Ok(v.into()),
-            DeclarationConstant::Expression(_) => unreachable!(),
+            DeclarationConstant::Expression(e) => {
+                let e = specialize_generic_expression(e.try_into().unwrap(), generics)?;
+                Ok(S::from_expression(e).expect(
+                    "called map_concrete on an expression which does not evaluate to a constant",
+                ))
+            }
+        }
+    }
+
+    /// Whether this constant is an expression which depends on generic parameters
+    pub fn is_generic_expression(&self) -> bool {
+        match self {
+            DeclarationConstant::Expression(TypedExpression::Uint(e)) => depends_on_generics(e),
+            _ => false,
         }
     }
+
+    /// Build a declaration constant from a `u32` expression, evaluating it if possible
+    pub fn from_uint_expression(e: UExpression<'ast, T>) -> Self {
+        // an expression which overflows or divides by zero is kept as is, to be reported by the caller
+        let e = substitute_generics(e.clone(), &|_| Ok(None)).unwrap_or(e);
+        Self::from_expression(e).unwrap()
+    }
 }
 
 impl<'ast, T: PartialEq> PartialEq<UExpression<'ast, T>> for DeclarationConstant<'ast, T> {
@@ -189,7 +250,13 @@ impl<'ast, T: PartialEq> PartialEq<UExpression<'ast, T>> for DeclarationConstant
                     ..
                 },
             ) => *c == *v as u32,
-            (DeclarationConstant::Expression(TypedExpression::Uint(e0)), e1) => e0 == e1,
+            (DeclarationConstant::Expression(TypedExpression::Uint(e0)), e1) => {
+                // expressions can only be compared once they are evaluated
+                match (e0.as_inner(), e1.as_inner()) {
+                    (UExpressionInner::Value(v0), UExpressionInner::Value(v1)) => v0 == v1,
+                    _ => true,
+                }
+            }
             (DeclarationConstant::Expression(..), _) => false, // type error
             _ => true,
         }
@@ -273,6 +340,15 @@ impl<'ast, T> TryInto<usize> for DeclarationConstant<'ast, T> {
     fn try_into(self) -> Result<usize, Self::Error> {
         match self {
             DeclarationConstant::Concrete(v) => Ok(v as usize),
+            DeclarationConstant::Expression(TypedExpression::Uint(e)) => {
+                match substitute_generics(e, &|_| Ok(None))
+                    .map_err(|_| SpecializationError)?
+                    .into_inner()
+                {
+                    UExpressionInner::Value(v) => Ok(v as usize),
+                    _ => Err(SpecializationError),
+                }
+            }
             _ => Err(SpecializationError),
         }
     }
@@ -342,27 +418,32 @@ impl<S, R: PartialEq<S>> PartialEq<GArrayType<S>> for GArrayType<R> {
 
 impl<S: fmt::Display> fmt::Display for GArrayType<S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fn fmt_aux<'a, S: fmt::Display>(
-            f: &mut fmt::Formatter,
-            t: &'a GArrayType<S>,
-            mut acc: Vec<&'a S>,
-        ) -> fmt::Result {
-            acc.push(&t.size);
-            match &*t.ty {
-                GType::Array(array_type) => fmt_aux(f, array_type, acc),
-                t => {
-                    write!(f, "{}", t)?;
-                    for i in acc {
-                        write!(f, "[{}]", i)?;
-                    }
-                    write!(f, "")
+        fmt_dimensions(f, self, false, vec![])
+    }
+}
+
+// write an array type as its innermost element type followed by its dimensions, from the outermost one.
+// The dimensions of bounded arrays are written `[..N]`
+fn fmt_dimensions<'a, S: fmt::Display>(
+    f: &mut fmt::Formatter,
+    t: &'a GArrayType<S>,
+    bounded: bool,
+    mut acc: Vec<(bool, &'a S)>,
+) -> fmt::Result {
+    acc.push((bounded, &t.size));
+    match &*t.ty {
+        GType::Array(array_type) => fmt_dimensions(f, array_type, false, acc),
+        GType::BoundedArray(array_type) => fmt_dimensions(f, array_type, true, acc),
+        t => {
+            write!(f, "{}", t)?;
+            for (bounded, i) in acc {
+                match bounded {
+                    true => write!(f, "[..{}]", i)?,
+                    false => write!(f, "[{}]", i)?,
                 }
             }
+            write!(f, "")
         }
-
-        let acc = vec![];
-
-        fmt_aux(f, self, acc)
     }
 }
 
@@ -481,12 +562,27 @@ impl<'ast, T> From<ConcreteArrayType> for DeclarationArrayType<'ast, T> {
     }
 }
 
+/// What a struct type stands for. Besides the structs declared in programs, the compiler uses struct types for types
+/// which are only known in later phases
+#[derive(
+    Debug, Clone, Copy, Default, Hash, Serialize, Deserialize, PartialOrd, Ord, Eq, PartialEq,
+)]
+pub enum StructKind {
+    #[default]
+    Struct,
+    /// An opaque type standing for a type parameter in the body of a function, replaced by the type it is bound to
+    /// when a call to the function is inlined
+    TypeParameter,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct GStructType<S> {
     #[serde(flatten)]
     pub canonical_location: StructLocation,
     #[serde(skip)]
     pub location: Option<StructLocation>,
+    #[serde(skip)]
+    pub kind: StructKind,
     pub generics: Vec<Option<S>>,
     pub members: Vec<GStructMember<S>>,
 }
@@ -524,6 +620,7 @@ fn try_from_g_struct_type<T: TryInto<U>, U>(
     Ok(GStructType {
         location: t.location,
         canonical_location: t.canonical_location,
+        kind: t.kind,
         generics: t
             .generics
             .into_iter()
@@ -570,11 +667,21 @@ impl<S> GStructType<S> {
         GStructType {
             canonical_location: StructLocation { module, name },
             location: None,
+            kind: StructKind::Struct,
             generics,
             members,
         }
     }
 
+    pub fn kind(mut self, kind: StructKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn is_type_parameter(&self) -> bool {
+        self.kind == StructKind::TypeParameter
+    }
+
     pub fn members_count(&self) -> usize {
         self.members.len()
     }
@@ -605,21 +712,258 @@ impl<S> IntoIterator for GStructType<S> {
     }
 }
 
+#[allow(clippy::derive_hash_xor_eq)]
+#[derive(Debug, Clone, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct GEnumVariant<S> {
+    #[serde(rename = "name")]
+    pub id: String,
+    pub types: Vec<GType<S>>,
+}
+
+impl<S, R: PartialEq<S>> PartialEq<GEnumVariant<S>> for GEnumVariant<R> {
+    fn eq(&self, other: &GEnumVariant<S>) -> bool {
+        self.id == other.id && self.types == other.types
+    }
+}
+
+impl<S> GEnumVariant<S> {
+    pub fn new(id: String, types: Vec<GType<S>>) -> Self {
+        GEnumVariant { id, types }
+    }
+}
+
+pub type DeclarationEnumVariant<'ast, T> = GEnumVariant<DeclarationConstant<'ast, T>>;
+pub type ConcreteEnumVariant = GEnumVariant<u32>;
+pub type EnumVariant<'ast, T> = GEnumVariant<UExpression<'ast, T>>;
+
+fn try_from_g_enum_variant<T: TryInto<U>, U>(
+    t: GEnumVariant<T>,
+) -> Result<GEnumVariant<U>, SpecializationError> {
+    Ok(GEnumVariant {
+        id: t.id,
+        types: t
+            .types
+            .into_iter()
+            .map(try_from_g_type)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct GEnumType<S> {
+    #[serde(flatten)]
+    pub canonical_location: StructLocation,
+    #[serde(skip)]
+    pub location: Option<StructLocation>,
+    pub variants: Vec<GEnumVariant<S>>,
+}
+
+pub type DeclarationEnumType<'ast, T> = GEnumType<DeclarationConstant<'ast, T>>;
+pub type ConcreteEnumType = GEnumType<u32>;
+pub type EnumType<'ast, T> = GEnumType<UExpression<'ast, T>>;
+
+impl<S, R> PartialEq<GEnumType<S>> for GEnumType<R> {
+    fn eq(&self, other: &GEnumType<S>) -> bool {
+        // enums are not generic, so they are identified by their declaration
+        self.canonical_location == other.canonical_location
+    }
+}
+
+impl<S> Hash for GEnumType<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_location.hash(state);
+    }
+}
+
+impl<S> Eq for GEnumType<S> {}
+
+fn try_from_g_enum_type<T: TryInto<U>, U>(
+    t: GEnumType<T>,
+) -> Result<GEnumType<U>, SpecializationError> {
+    Ok(GEnumType {
+        location: t.location,
+        canonical_location: t.canonical_location,
+        variants: t
+            .variants
+            .into_iter()
+            .map(try_from_g_enum_variant)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+impl<'ast, T> TryFrom<EnumType<'ast, T>> for ConcreteEnumType {
+    type Error = SpecializationError;
+
+    fn try_from(t: EnumType<'ast, T>) -> Result<Self, Self::Error> {
+        try_from_g_enum_type(t)
+    }
+}
+
+impl<'ast, T> From<ConcreteEnumType> for EnumType<'ast, T> {
+    fn from(t: ConcreteEnumType) -> Self {
+        try_from_g_enum_type(t).unwrap()
+    }
+}
+
+impl<S> GEnumType<S> {
+    pub fn new(module: PathBuf, name: String, variants: Vec<GEnumVariant<S>>) -> Self {
+        GEnumType {
+            canonical_location: StructLocation { module, name },
+            location: None,
+            variants,
+        }
+    }
+
+    fn location(&self) -> &StructLocation {
+        self.location.as_ref().unwrap_or(&self.canonical_location)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.location().name
+    }
+
+    pub fn module(&self) -> &Path {
+        &self.location().module
+    }
+
+    /// The index of the variant called `id`, if any
+    pub fn variant_index(&self, id: &str) -> Option<u32> {
+        self.variants
+            .iter()
+            .position(|v| v.id == id)
+            .map(|index| index as u32)
+    }
+}
+
+impl<S: Clone> GEnumType<S> {
+    /// The payload of a variant, as a tuple
+    pub fn payload_type(&self, variant: u32) -> GTupleType<S> {
+        GTupleType::new(self.variants[variant as usize].types.clone())
+    }
+}
+
+impl<S: PartialEq> GEnumType<S> {
+    /// The layout of the values of this enum once flattened, after a `u32` tag holding the index of the variant.
+    /// Returns the types of the payload slots and, for each variant, the slots holding the elements of its payload.
+    /// Variants share the slots of the same type, so that the size of a value is not the sum of the sizes of all payloads.
+    pub fn layout(&self) -> (Vec<&GType<S>>, Vec<Vec<usize>>) {
+        let mut slots: Vec<&GType<S>> = vec![];
+
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let mut variant_slots: Vec<usize> = vec![];
+
+                for ty in &variant.types {
+                    // reuse the first slot of the same type which this variant does not use yet
+                    let slot = slots
+                        .iter()
+                        .enumerate()
+                        .position(|(index, slot)| *slot == ty && !variant_slots.contains(&index))
+                        .unwrap_or_else(|| {
+                            slots.push(ty);
+                            slots.len() - 1
+                        });
+                    variant_slots.push(slot);
+                }
+
+                variant_slots
+            })
+            .collect();
+
+        (slots, variants)
+    }
+}
+
+impl<S: fmt::Display> fmt::Display for GEnumType<S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Copy)]
 pub enum UBitwidth {
     #[serde(rename = "8")]
-    B8 = 8,
+    B8,
     #[serde(rename = "16")]
-    B16 = 16,
+    B16,
     #[serde(rename = "32")]
-    B32 = 32,
+    B32,
     #[serde(rename = "64")]
-    B64 = 64,
+    B64,
+    #[serde(rename = "128")]
+    B128,
+    #[serde(rename = "i8")]
+    I8,
+    #[serde(rename = "i16")]
+    I16,
+    #[serde(rename = "i32")]
+    I32,
+    #[serde(rename = "i64")]
+    I64,
 }
 
 impl UBitwidth {
     pub fn to_usize(self) -> usize {
-        self as usize
+        match self {
+            UBitwidth::B8 | UBitwidth::I8 => 8,
+            UBitwidth::B16 | UBitwidth::I16 => 16,
+            UBitwidth::B32 | UBitwidth::I32 => 32,
+            UBitwidth::B64 | UBitwidth::I64 => 64,
+            UBitwidth::B128 => 128,
+        }
+    }
+
+    /// The largest unsigned value representable on this bitwidth, `2**bitwidth - 1`
+    pub fn max_value(self) -> u128 {
+        u128::MAX >> (128 - self.to_usize())
+    }
+
+    /// Whether values of this bitwidth are interpreted in two's complement
+    pub fn is_signed(self) -> bool {
+        matches!(
+            self,
+            UBitwidth::I8 | UBitwidth::I16 | UBitwidth::I32 | UBitwidth::I64
+        )
+    }
+
+    /// The signed bitwidth with the same number of bits
+    pub fn to_signed(self) -> Self {
+        match self.to_usize() {
+            8 => UBitwidth::I8,
+            16 => UBitwidth::I16,
+            32 => UBitwidth::I32,
+            64 => UBitwidth::I64,
+            _ => unreachable!(),
+        }
+    }
+
+    /// The unsigned bitwidth with the same number of bits
+    pub fn to_unsigned(self) -> Self {
+        UBitwidth::from(self.to_usize())
+    }
+
+    /// Interprets the bits of a value of this bitwidth in two's complement
+    pub fn signed_value(self, v: u128) -> i128 {
+        let bits = self.to_usize();
+        match v >> (bits - 1) {
+            0 => v as i128,
+            _ => v as i128 - (1i128 << bits),
+        }
+    }
+
+    /// Returns the two's complement bits of `v` truncated to this bitwidth
+    pub fn wrap_signed_value(self, v: i128) -> u128 {
+        (v as u128) & self.max_value()
+    }
+
+    /// The prefix used when displaying a type of this bitwidth, `u` or `i`
+    pub fn prefix(self) -> char {
+        match self.is_signed() {
+            true => 'i',
+            false => 'u',
+        }
     }
 }
 
@@ -630,6 +974,7 @@ impl From<usize> for UBitwidth {
             16 => UBitwidth::B16,
             32 => UBitwidth::B32,
             64 => UBitwidth::B64,
+            128 => UBitwidth::B128,
             _ => unreachable!(),
         }
     }
@@ -647,8 +992,11 @@ pub enum GType<S> {
     FieldElement,
     Boolean,
     Array(GArrayType<S>),
+    /// An array of up to `size` elements, whose length is only known at runtime
+    BoundedArray(GArrayType<S>),
     Struct(GStructType<S>),
     Tuple(GTupleType<S>),
+    Enum(GEnumType<S>),
     Uint(UBitwidth),
     Int,
 }
@@ -669,6 +1017,12 @@ impl<Z: Serialize> Serialize for GType<Z> {
                 map.serialize_entry("components", array_type)?;
                 map.end()
             }
+            GType::BoundedArray(array_type) => {
+                let mut map = s.serialize_map(Some(2))?;
+                map.serialize_entry("type", "bounded_array")?;
+                map.serialize_entry("components", array_type)?;
+                map.end()
+            }
             GType::Struct(struct_type) => {
                 let mut map = s.serialize_map(Some(2))?;
                 map.serialize_entry("type", "struct")?;
@@ -681,11 +1035,17 @@ impl<Z: Serialize> Serialize for GType<Z> {
                 map.serialize_entry("components", tuple_type)?;
                 map.end()
             }
+            GType::Enum(enum_type) => {
+                let mut map = s.serialize_map(Some(2))?;
+                map.serialize_entry("type", "enum")?;
+                map.serialize_entry("components", enum_type)?;
+                map.end()
+            }
             GType::Uint(width) => s.serialize_newtype_variant(
                 "Type",
                 4,
                 "type",
-                format!("u{}", width.to_usize()).as_str(),
+                format!("{}{}", width.prefix(), width.to_usize()).as_str(),
             ),
             GType::Int => Err(S::Error::custom(
                 "Cannot serialize Int type as it's not allowed in function signatures".to_string(),
@@ -705,6 +1065,7 @@ impl<'de, S: Deserialize<'de>> Deserialize<'de> for GType<S> {
             Array(GArrayType<S>),
             Struct(GStructType<S>),
             Tuple(GTupleType<S>),
+            Enum(GEnumType<S>),
         }
 
         #[derive(Deserialize)]
@@ -738,6 +1099,15 @@ impl<'de, S: Deserialize<'de>> Deserialize<'de> for GType<S> {
                     _ => Err(D::Error::custom("invalid `components` variant".to_string())),
                 }
             }
+            "bounded_array" => {
+                let components = mapping
+                    .components
+                    .ok_or_else(|| D::Error::custom("missing `components` field".to_string()))?;
+                match components {
+                    Components::Array(array_type) => Ok(GType::BoundedArray(array_type)),
+                    _ => Err(D::Error::custom("invalid `components` variant".to_string())),
+                }
+            }
             "struct" => {
                 let components = mapping
                     .components
@@ -756,10 +1126,24 @@ impl<'de, S: Deserialize<'de>> Deserialize<'de> for GType<S> {
                     _ => Err(D::Error::custom("invalid `components` variant".to_string())),
                 }
             }
+            "enum" => {
+                let components = mapping
+                    .components
+                    .ok_or_else(|| D::Error::custom("missing `components` field".to_string()))?;
+                match components {
+                    Components::Enum(enum_type) => Ok(GType::Enum(enum_type)),
+                    _ => Err(D::Error::custom("invalid `components` variant".to_string())),
+                }
+            }
             "u8" => strict_type(mapping, GType::Uint(UBitwidth::B8)),
             "u16" => strict_type(mapping, GType::Uint(UBitwidth::B16)),
             "u32" => strict_type(mapping, GType::Uint(UBitwidth::B32)),
             "u64" => strict_type(mapping, GType::Uint(UBitwidth::B64)),
+            "u128" => strict_type(mapping, GType::Uint(UBitwidth::B128)),
+            "i8" => strict_type(mapping, GType::Uint(UBitwidth::I8)),
+            "i16" => strict_type(mapping, GType::Uint(UBitwidth::I16)),
+            "i32" => strict_type(mapping, GType::Uint(UBitwidth::I32)),
+            "i64" => strict_type(mapping, GType::Uint(UBitwidth::I64)),
             t => Err(D::Error::custom(format!("invalid type `{}`", t))),
         }
     }
@@ -775,10 +1159,12 @@ impl<S, R: PartialEq<S>> PartialEq<GType<S>> for GType<R> {
 
         match (self, other) {
             (Array(l), Array(r)) => l == r,
+            (BoundedArray(l), BoundedArray(r)) => l == r,
             (Struct(l), Struct(r)) => l == r,
             (FieldElement, FieldElement) | (Boolean, Boolean) => true,
             (Uint(l), Uint(r)) => l == r,
             (Tuple(l), Tuple(r)) => l == r,
+            (Enum(l), Enum(r)) => l == r,
             _ => false,
         }
     }
@@ -791,8 +1177,12 @@ pub fn try_from_g_type<T: TryInto<U>, U>(t: GType<T>) -> Result<GType<U>, Specia
         GType::Int => Ok(GType::Int),
         GType::Uint(bitwidth) => Ok(GType::Uint(bitwidth)),
         GType::Array(array_type) => Ok(GType::Array(try_from_g_array_type(array_type)?)),
+        GType::BoundedArray(array_type) => {
+            Ok(GType::BoundedArray(try_from_g_array_type(array_type)?))
+        }
         GType::Struct(struct_type) => Ok(GType::Struct(try_from_g_struct_type(struct_type)?)),
         GType::Tuple(tuple_type) => Ok(GType::Tuple(try_from_g_tuple_type(tuple_type)?)),
+        GType::Enum(enum_type) => Ok(GType::Enum(try_from_g_enum_type(enum_type)?)),
     }
 }
 
@@ -848,11 +1238,13 @@ impl<S: fmt::Display> fmt::Display for GType<S> {
         match self {
             GType::FieldElement => write!(f, "field"),
             GType::Boolean => write!(f, "bool"),
-            GType::Uint(ref bitwidth) => write!(f, "u{}", bitwidth),
+            GType::Uint(ref bitwidth) => write!(f, "{}{}", bitwidth.prefix(), bitwidth),
             GType::Int => write!(f, "{{integer}}"),
             GType::Array(ref array_type) => write!(f, "{}", array_type),
+            GType::BoundedArray(ref array_type) => fmt_dimensions(f, array_type, true, vec![]),
             GType::Struct(ref struct_type) => write!(f, "{}", struct_type),
             GType::Tuple(ref tuple_type) => write!(f, "{}", tuple_type),
+            GType::Enum(ref enum_type) => write!(f, "{}", enum_type),
         }
     }
 }
@@ -887,6 +1279,10 @@ impl<S> GType<S> {
         GType::Array(array_ty.into())
     }
 
+    pub fn bounded_array<U: Into<GArrayType<S>>>(array_ty: U) -> Self {
+        GType::BoundedArray(array_ty.into())
+    }
+
     pub fn tuple<U: Into<GTupleType<S>>>(tuple_ty: U) -> Self {
         GType::Tuple(tuple_ty.into())
     }
@@ -913,19 +1309,21 @@ impl<'ast, T: fmt::Display + PartialEq + fmt::Debug> Type<'ast, T> {
         } else {
             match (self, other) {
                 (Int, FieldElement) | (Int, Uint(..)) => true,
-                (Array(l), Array(r)) => match l.ty.can_be_specialized_to(&r.ty) {
-                    true => {
-                        // check the size if types match
-                        match (&l.size.as_inner(), &*r.size) {
-                            // compare the sizes for concrete ones
-                            (UExpressionInner::Value(v), DeclarationConstant::Concrete(c)) => {
-                                (*v as u32) == *c
+                (Array(l), Array(r)) | (BoundedArray(l), BoundedArray(r)) => {
+                    match l.ty.can_be_specialized_to(&r.ty) {
+                        true => {
+                            // check the size if types match
+                            match (&l.size.as_inner(), &*r.size) {
+                                // compare the sizes for concrete ones
+                                (UExpressionInner::Value(v), DeclarationConstant::Concrete(c)) => {
+                                    (*v as u32) == *c
+                                }
+                                _ => true,
                             }
-                            _ => true,
                         }
+                        _ => false,
                     }
-                    _ => false,
-                },
+                }
                 (Struct(l), Struct(r)) => {
                     l.canonical_location == r.canonical_location
                         && l.members
@@ -949,6 +1347,10 @@ impl ConcreteType {
             GType::Array(array_type) => {
                 *array_type.size as usize * array_type.ty.get_primitive_count()
             }
+            // the length, followed by the elements
+            GType::BoundedArray(array_type) => {
+                1 + *array_type.size as usize * array_type.ty.get_primitive_count()
+            }
             GType::Tuple(tuple_type) => tuple_type
                 .elements
                 .iter()
@@ -959,6 +1361,14 @@ impl ConcreteType {
                 .iter()
                 .map(|member| member.ty.get_primitive_count())
                 .sum(),
+            GType::Enum(enum_type) => {
+                1 + enum_type
+                    .layout()
+                    .0
+                    .into_iter()
+                    .map(|slot| slot.get_primitive_count())
+                    .sum::<usize>()
+            }
         }
     }
 }
@@ -1008,7 +1418,7 @@ impl<'ast, S: fmt::Display> fmt::Display for GGenericsAssignment<'ast, S> {
     }
 }
 
-impl<'ast, T> PartialEq<DeclarationFunctionKey<'ast, T>> for ConcreteFunctionKey<'ast> {
+impl<'ast, T: Clone> PartialEq<DeclarationFunctionKey<'ast, T>> for ConcreteFunctionKey<'ast> {
     fn eq(&self, other: &DeclarationFunctionKey<'ast, T>) -> bool {
         self.module == other.module && self.id == other.id && self.signature == other.signature
     }
@@ -1070,14 +1480,308 @@ impl<'ast, S> GFunctionKey<'ast, S> {
         self.module = module.into();
         self
     }
+
+    /// The key standing for the function parameter at `index` in the body of this function. Calls to it are
+    /// resolved to the function passed at the call site when the call is inlined
+    pub fn function_parameter(&self, index: usize, signature: GSignature<S>) -> Self {
+        GFunctionKey {
+            module: self.module.join(self.id).join(index.to_string()),
+            id: self.id,
+            signature,
+        }
+    }
+
+    /// The index of the function parameter of this function which `key` stands for, if any
+    pub fn function_parameter_index(&self, key: &Self) -> Option<usize> {
+        match key.id == self.id {
+            true => key
+                .module
+                .strip_prefix(self.module.join(self.id))
+                .ok()
+                .and_then(|index| index.to_str())
+                .and_then(|index| index.parse().ok()),
+            false => None,
+        }
+    }
 }
 
 use std::collections::btree_map::Entry;
 
+/// A value which can be assigned to a generic parameter
+pub trait GenericValue<'ast, T>: Sized {
+    fn to_expression(&self) -> UExpression<'ast, T>;
+
+    fn from_expression(e: UExpression<'ast, T>) -> Option<Self>;
+}
+
+impl<'ast, T> GenericValue<'ast, T> for u32 {
+    fn to_expression(&self) -> UExpression<'ast, T> {
+        UExpression::from(*self)
+    }
+
+    fn from_expression(e: UExpression<'ast, T>) -> Option<Self> {
+        match e.into_inner() {
+            UExpressionInner::Value(v) => Some(v as u32),
+            _ => None,
+        }
+    }
+}
+
+impl<'ast, T: Clone> GenericValue<'ast, T> for UExpression<'ast, T> {
+    fn to_expression(&self) -> UExpression<'ast, T> {
+        self.clone()
+    }
+
+    fn from_expression(e: UExpression<'ast, T>) -> Option<Self> {
+        Some(e)
+    }
+}
+
+impl<'ast, T: Field> GenericValue<'ast, T> for DeclarationConstant<'ast, T> {
+    fn to_expression(&self) -> UExpression<'ast, T> {
+        self.clone().into()
+    }
+
+    fn from_expression(e: UExpression<'ast, T>) -> Option<Self> {
+        Some(match e.into_inner() {
+            UExpressionInner::Value(v) => DeclarationConstant::Concrete(v as u32),
+            e => DeclarationConstant::Expression(e.annotate(UBitwidth::B32).into()),
+        })
+    }
+}
+
+fn find_generic<'a, 'ast, S>(
+    generics: &'a GGenericsAssignment<'ast, S>,
+    name: &str,
+) -> Option<&'a S> {
+    generics
+        .0
+        .iter()
+        .find(|(g, _)| g.name == Some(name))
+        .map(|(_, v)| v)
+}
+
+// substitute the generic parameters of a generic expression, evaluating it as far as possible
+// `f` returns the value of a generic parameter given its name, `None` to leave it in place, or an error if it must be known
+// operations on values which overflow or divide by zero are errors, rather than wrapping around
+fn substitute_generics<'ast, T, F>(
+    e: UExpression<'ast, T>,
+    f: &F,
+) -> Result<UExpression<'ast, T>, GenericsError<'ast>>
+where
+    F: Fn(&SourceIdentifier<'ast>) -> Result<Option<UExpression<'ast, T>>, GenericIdentifier<'ast>>,
+{
+    let bitwidth = e.bitwidth;
+
+    Ok(match e.into_inner() {
+        UExpressionInner::Identifier(i) => {
+            let value = match &i.id.id {
+                CoreIdentifier::Source(s) => f(&s.id)?,
+                _ => None,
+            };
+            value.unwrap_or_else(|| UExpressionInner::Identifier(i).annotate(bitwidth))
+        }
+        UExpressionInner::Add(box l, box r) => evaluate_generic_operation(
+            substitute_generics(l, f)?,
+            substitute_generics(r, f)?,
+            (u32::checked_add, "+"),
+            UExpressionInner::Add,
+        )?,
+        UExpressionInner::Sub(box l, box r) => evaluate_generic_operation(
+            substitute_generics(l, f)?,
+            substitute_generics(r, f)?,
+            (u32::checked_sub, "-"),
+            UExpressionInner::Sub,
+        )?,
+        UExpressionInner::Mult(box l, box r) => evaluate_generic_operation(
+            substitute_generics(l, f)?,
+            substitute_generics(r, f)?,
+            (u32::checked_mul, "*"),
+            UExpressionInner::Mult,
+        )?,
+        UExpressionInner::Div(box l, box r) => evaluate_generic_operation(
+            substitute_generics(l, f)?,
+            substitute_generics(r, f)?,
+            (u32::checked_div, "/"),
+            UExpressionInner::Div,
+        )?,
+        UExpressionInner::Rem(box l, box r) => evaluate_generic_operation(
+            substitute_generics(l, f)?,
+            substitute_generics(r, f)?,
+            (u32::checked_rem, "%"),
+            UExpressionInner::Rem,
+        )?,
+        e => e.annotate(bitwidth),
+    })
+}
+
+fn depends_on_generics<T>(e: &UExpression<'_, T>) -> bool {
+    match e.as_inner() {
+        UExpressionInner::Identifier(i) => matches!(i.id.id, CoreIdentifier::Source(..)),
+        UExpressionInner::Add(l, r)
+        | UExpressionInner::Sub(l, r)
+        | UExpressionInner::Mult(l, r)
+        | UExpressionInner::Div(l, r)
+        | UExpressionInner::Rem(l, r) => depends_on_generics(l) || depends_on_generics(r),
+        _ => false,
+    }
+}
+
+type BinaryOperation<'ast, T> =
+    fn(Box<UExpression<'ast, T>>, Box<UExpression<'ast, T>>) -> UExpressionInner<'ast, T>;
+
+// apply `op` if both operands are values, otherwise build the expression using `build`
+// `op` comes with its symbol, to report the operations which have no value
+fn evaluate_generic_operation<'ast, T>(
+    left: UExpression<'ast, T>,
+    right: UExpression<'ast, T>,
+    (op, symbol): (fn(u32, u32) -> Option<u32>, &str),
+    build: BinaryOperation<'ast, T>,
+) -> Result<UExpression<'ast, T>, GenericsError<'ast>> {
+    match (left.as_inner(), right.as_inner()) {
+        (UExpressionInner::Value(l), UExpressionInner::Value(r)) => op(*l as u32, *r as u32)
+            .map(UExpression::from)
+            .ok_or_else(|| GenericsError::Overflow(format!("{} {} {}", l, symbol, r))),
+        _ => Ok(build(box left, box right).annotate(UBitwidth::B32)),
+    }
+}
+
+// specialize a generic expression, failing if one of the generics it uses is not assigned
+fn specialize_generic_expression<'ast, T, S: GenericValue<'ast, T>>(
+    e: UExpression<'ast, T>,
+    generics: &GGenericsAssignment<'ast, S>,
+) -> Result<UExpression<'ast, T>, GenericsError<'ast>> {
+    substitute_generics(e, &|name| match find_generic(generics, name) {
+        Some(v) => Ok(Some(v.to_expression())),
+        None => match name {
+            std::borrow::Cow::Borrowed(name) => Err(GenericIdentifier::with_name(*name)),
+            std::borrow::Cow::Owned(_) => {
+                unreachable!("generic identifiers are borrowed from the source")
+            }
+        },
+    })
+}
+
+// substitute the generics which are already known in a generic expression, leaving the others in place
+// if the expression overflows, it is left as is so that the error is reported when it is specialized
+fn substitute_known_generics<'ast, T: Clone, S: GenericValue<'ast, T>>(
+    e: &UExpression<'ast, T>,
+    generics: &GGenericsAssignment<'ast, S>,
+) -> UExpression<'ast, T> {
+    substitute_generics(e.clone(), &|name| {
+        Ok(find_generic(generics, name).map(|v| v.to_expression()))
+    })
+    .unwrap_or_else(|_| e.clone())
+}
+
+// decompose a generic expression as `a * name + b`, if it is linear in `name` and does not depend on other generics
+fn linear_form<'ast, T>(e: &UExpression<'ast, T>, name: &str) -> Option<(i128, i128)> {
+    match e.as_inner() {
+        UExpressionInner::Value(v) => Some((0, *v as i128)),
+        UExpressionInner::Identifier(i) => match &i.id.id {
+            CoreIdentifier::Source(s) if s.id == name => Some((1, 0)),
+            _ => None,
+        },
+        UExpressionInner::Add(l, r) => {
+            let (a0, b0) = linear_form(l, name)?;
+            let (a1, b1) = linear_form(r, name)?;
+            Some((a0.checked_add(a1)?, b0.checked_add(b1)?))
+        }
+        UExpressionInner::Sub(l, r) => {
+            let (a0, b0) = linear_form(l, name)?;
+            let (a1, b1) = linear_form(r, name)?;
+            Some((a0.checked_sub(a1)?, b0.checked_sub(b1)?))
+        }
+        UExpressionInner::Mult(l, r) => {
+            let (a0, b0) = linear_form(l, name)?;
+            let (a1, b1) = linear_form(r, name)?;
+            match (a0, a1) {
+                (0, _) => Some((b0.checked_mul(a1)?, b0.checked_mul(b1)?)),
+                (_, 0) => Some((a0.checked_mul(b1)?, b0.checked_mul(b1)?)),
+                // quadratic
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// try to infer the value of the generic `g` given the value of a declaration constant which is linear in `g`
+fn solve_generic<'ast, T: Clone, S: GenericValue<'ast, T>>(
+    generic: &DeclarationConstant<'ast, T>,
+    value: &S,
+    g: &GenericIdentifier<'ast>,
+    constants: &GGenericsAssignment<'ast, S>,
+) -> Option<S> {
+    let e = match generic {
+        DeclarationConstant::Expression(TypedExpression::Uint(e)) => {
+            substitute_known_generics(e, constants)
+        }
+        _ => return None,
+    };
+
+    let (a, b) = linear_form(&e, g.name())?;
+
+    match value.to_expression().into_inner() {
+        // solve `a * g + b == v` in the integers
+        UExpressionInner::Value(v) => {
+            let v = (v as i128).checked_sub(b)?;
+
+            if a == 0 || v % a != 0 {
+                return None;
+            }
+
+            u32::try_from(v / a)
+                .ok()
+                .and_then(|v| S::from_expression(v.into()))
+        }
+        // for symbolic values, only `g + b == v` is solved
+        v if a == 1 => {
+            let v = v.annotate(UBitwidth::B32);
+            match u32::try_from(b).ok()? {
+                0 => S::from_expression(v),
+                b => S::from_expression(v - b.into()),
+            }
+        }
+        _ => None,
+    }
+}
+
+// try to infer the value of the generic `g` from the generic expressions found in a declaration type
+fn solve_type<'ast, T: Clone, S: GenericValue<'ast, T>>(
+    decl_ty: &DeclarationType<'ast, T>,
+    ty: &GType<S>,
+    g: &GenericIdentifier<'ast>,
+    constants: &GGenericsAssignment<'ast, S>,
+) -> Option<S> {
+    match (decl_ty, ty) {
+        (DeclarationType::Array(t0), GType::Array(t1))
+        | (DeclarationType::BoundedArray(t0), GType::BoundedArray(t1)) => {
+            solve_generic(&t0.size, &*t1.size, g, constants)
+                .or_else(|| solve_type(&t0.ty, &*t1.ty, g, constants))
+        }
+        (DeclarationType::Struct(s0), GType::Struct(s1)) => s0
+            .generics
+            .iter()
+            .zip(s1.generics.iter())
+            .find_map(|(g0, g1)| solve_generic(g0.as_ref()?, g1.as_ref()?, g, constants)),
+        (DeclarationType::Tuple(s0), GType::Tuple(s1)) => s0
+            .elements
+            .iter()
+            .zip(s1.elements.iter())
+            .find_map(|(t0, t1)| solve_type(t0, t1, g, constants)),
+        _ => None,
+    }
+}
+
 // check an optional generic value against the corresponding declaration constant
 // if None is provided, return true
 // if some value is provided, insert it into the map or check that it doesn't conflict if a value is already thereq
-pub fn check_generic<'ast, T, S: Clone + PartialEq + PartialEq<u32>>(
+pub fn check_generic<
+    'ast,
+    T: Clone,
+    S: GenericValue<'ast, T> + Clone + PartialEq + PartialEq<u32>,
+>(
     generic: &DeclarationConstant<'ast, T>,
     value: Option<&S>,
     constants: &mut GGenericsAssignment<'ast, S>,
@@ -1097,24 +1801,29 @@ pub fn check_generic<'ast, T, S: Clone + PartialEq + PartialEq<u32>>(
             // in the case of a constant, we do not know the value yet, so we optimistically assume it's correct
             // if it does not match, it will be caught during inlining
             DeclarationConstant::Constant(..) => true,
+            // in the case of an expression, we substitute the generics we already know
+            // if some are still missing, we optimistically assume it's correct
             DeclarationConstant::Expression(e) => match e {
-                TypedExpression::Uint(e) => match e.as_inner() {
-                    UExpressionInner::Value(v) => *value == *v as u32,
-                    _ => true,
-                },
+                TypedExpression::Uint(e) => {
+                    match substitute_known_generics(e, constants).as_inner() {
+                        UExpressionInner::Value(v) => *value == *v as u32,
+                        _ => true,
+                    }
+                }
                 _ => unreachable!(),
             },
         })
         .unwrap_or(true)
 }
 
-pub fn check_type<'ast, T, S: Clone + PartialEq + PartialEq<u32>>(
+pub fn check_type<'ast, T: Clone, S: GenericValue<'ast, T> + Clone + PartialEq + PartialEq<u32>>(
     decl_ty: &DeclarationType<'ast, T>,
     ty: &GType<S>,
     constants: &mut GGenericsAssignment<'ast, S>,
 ) -> bool {
     match (decl_ty, ty) {
-        (DeclarationType::Array(t0), GType::Array(t1)) => {
+        (DeclarationType::Array(t0), GType::Array(t1))
+        | (DeclarationType::BoundedArray(t0), GType::BoundedArray(t1)) => {
             // both the inner type and the size must match
             check_type(&t0.ty, &t1.ty, constants)
                 && check_generic(&*t0.size, Some(&*t1.size), constants)
@@ -1130,6 +1839,9 @@ pub fn check_type<'ast, T, S: Clone + PartialEq + PartialEq<u32>>(
                     .zip(s1.generics.iter())
                     .all(|(g0, g1)| check_generic(g0.as_ref().unwrap(), g1.as_ref(), constants))
         }
+        (DeclarationType::Enum(e0), GType::Enum(e1)) => {
+            e0.canonical_location == e1.canonical_location
+        }
         (DeclarationType::Tuple(s0), GType::Tuple(s1)) => {
             s0.elements.len() == s1.elements.len()
                 && s0
@@ -1142,6 +1854,36 @@ pub fn check_type<'ast, T, S: Clone + PartialEq + PartialEq<u32>>(
     }
 }
 
+// check types against their declarations, inferring the values of `generics` along the way
+// generics which only appear in expressions are solved once a first pass is done, and the types are checked again
+fn check_types<'ast, T: Clone, S: GenericValue<'ast, T> + Clone + PartialEq + PartialEq<u32>>(
+    types: Vec<(&DeclarationType<'ast, T>, &GType<S>)>,
+    generics: &[GenericIdentifier<'ast>],
+    constants: &mut GGenericsAssignment<'ast, S>,
+) -> bool {
+    if !types
+        .iter()
+        .all(|(decl_ty, ty)| check_type(decl_ty, ty, constants))
+    {
+        return false;
+    }
+
+    for g in generics {
+        if !constants.0.contains_key(g) {
+            if let Some(value) = types
+                .iter()
+                .find_map(|(decl_ty, ty)| solve_type(decl_ty, ty, g, constants))
+            {
+                constants.0.insert(g.clone(), value);
+            }
+        }
+    }
+
+    types
+        .iter()
+        .all(|(decl_ty, ty)| check_type(decl_ty, ty, constants))
+}
+
 impl<'ast, T: Field> From<CanonicalConstantIdentifier<'ast>> for UExpression<'ast, T> {
     fn from(c: CanonicalConstantIdentifier<'ast>) -> Self {
         UExpression::identifier(Identifier::from(CoreIdentifier::Constant(c)))
@@ -1155,14 +1897,42 @@ impl<'ast, T> From<CanonicalConstantIdentifier<'ast>> for DeclarationConstant<'a
     }
 }
 
+// check that the generic expressions in `decl_ty` have a value for `assignment`
+fn check_type_instance<'ast, T: Field>(
+    decl_ty: &DeclarationType<'ast, T>,
+    assignment: &ConcreteGenericsAssignment<'ast>,
+) -> Result<(), GenericsError<'ast>> {
+    match decl_ty {
+        DeclarationType::Array(t) | DeclarationType::BoundedArray(t) => {
+            check_type_instance(&t.ty, assignment)?;
+            t.size.clone().map_concrete(assignment).map(|_| ())
+        }
+        DeclarationType::Tuple(t) => t
+            .elements
+            .iter()
+            .try_for_each(|e| check_type_instance(e, assignment)),
+        DeclarationType::Struct(t) => t
+            .generics
+            .iter()
+            .flatten()
+            .try_for_each(|g| g.clone().map_concrete(assignment).map(|_| ())),
+        DeclarationType::Enum(t) => t
+            .variants
+            .iter()
+            .flat_map(|v| v.types.iter())
+            .try_for_each(|ty| check_type_instance(ty, assignment)),
+        _ => Ok(()),
+    }
+}
+
 pub fn specialize_declaration_type<
     'ast,
-    T: Clone,
-    S: Clone + PartialEq + From<u32> + From<CanonicalConstantIdentifier<'ast>>,
+    T: Field,
+    S: Clone + PartialEq + From<u32> + From<CanonicalConstantIdentifier<'ast>> + GenericValue<'ast, T>,
 >(
     decl_ty: DeclarationType<'ast, T>,
     generics: &GGenericsAssignment<'ast, S>,
-) -> Result<GType<S>, GenericIdentifier<'ast>> {
+) -> Result<GType<S>, GenericsError<'ast>> {
     Ok(match decl_ty {
         DeclarationType::Int => unreachable!(),
         DeclarationType::Array(t0) => {
@@ -1171,6 +1941,12 @@ pub fn specialize_declaration_type<
 
             GType::Array(GArrayType::new(ty, size))
         }
+        DeclarationType::BoundedArray(t0) => {
+            let ty = specialize_declaration_type(*t0.ty, generics)?;
+            let size = t0.size.map(generics)?;
+
+            GType::BoundedArray(GArrayType::new(ty, size))
+        }
         DeclarationType::Tuple(t0) => {
             let elements = t0
                 .elements
@@ -1183,6 +1959,24 @@ pub fn specialize_declaration_type<
         DeclarationType::FieldElement => GType::FieldElement,
         DeclarationType::Boolean => GType::Boolean,
         DeclarationType::Uint(b0) => GType::Uint(b0),
+        DeclarationType::Enum(e0) => GType::Enum(GEnumType {
+            variants: e0
+                .variants
+                .into_iter()
+                .map(|v| {
+                    Ok(GEnumVariant {
+                        types: v
+                            .types
+                            .into_iter()
+                            .map(|t| specialize_declaration_type(t, generics))
+                            .collect::<Result<_, _>>()?,
+                        id: v.id,
+                    })
+                })
+                .collect::<Result<_, GenericsError>>()?,
+            canonical_location: e0.canonical_location,
+            location: e0.location,
+        }),
         DeclarationType::Struct(s0) => {
             // here we specialize Foo<Generics> {FooDef<InsideGenerics>} with some values for Generics
             // we need to remap these values for InsideGenerics to then visit the members
@@ -1221,6 +2015,7 @@ pub fn specialize_declaration_type<
                     .collect::<Result<_, _>>()?,
                 canonical_location: s0.canonical_location,
                 location: s0.location,
+                kind: s0.kind,
             })
         }
     })
@@ -1230,7 +2025,7 @@ pub use self::signature::{
     try_from_g_signature, ConcreteSignature, DeclarationSignature, GSignature, Signature,
 };
 
-use super::{Id, ShadowedIdentifier};
+use super::{Id, ShadowedIdentifier, SourceIdentifier};
 
 pub mod signature {
     use super::*;
@@ -1285,21 +2080,50 @@ pub mod signature {
     pub type ConcreteSignature = GSignature<u32>;
     pub type Signature<'ast, T> = GSignature<UExpression<'ast, T>>;
 
-    impl<'ast, T> PartialEq<DeclarationSignature<'ast, T>> for ConcreteSignature {
+    // the identifiers of the generic parameters of a declaration signature
+    fn generic_identifiers<'ast, T>(
+        generics: &[Option<DeclarationConstant<'ast, T>>],
+    ) -> Vec<GenericIdentifier<'ast>> {
+        generics
+            .iter()
+            .filter_map(|g| match g {
+                Some(DeclarationConstant::Generic(g)) => Some(g.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    impl<'ast, T: Clone> PartialEq<DeclarationSignature<'ast, T>> for ConcreteSignature {
         fn eq(&self, other: &DeclarationSignature<'ast, T>) -> bool {
             // we keep track of the value of constants in a map, as a given constant can only have one value
             let mut constants = ConcreteGenericsAssignment::default();
 
-            other
-                .inputs
-                .iter()
-                .chain(std::iter::once(&*other.output))
-                .zip(self.inputs.iter().chain(std::iter::once(&*self.output)))
-                .all(|(decl_ty, ty)| check_type::<T, u32>(decl_ty, ty, &mut constants))
+            check_types::<T, u32>(
+                other
+                    .inputs
+                    .iter()
+                    .chain(std::iter::once(&*other.output))
+                    .zip(self.inputs.iter().chain(std::iter::once(&*self.output)))
+                    .collect(),
+                &generic_identifiers(&other.generics),
+                &mut constants,
+            )
         }
     }
 
     impl<'ast, T: Field> DeclarationSignature<'ast, T> {
+        /// Check that the generic expressions in this signature have a value for `assignment`, which is not the case
+        /// of `field[N - 1]` when `N` is zero
+        pub fn check_instance(
+            &self,
+            assignment: &ConcreteGenericsAssignment<'ast>,
+        ) -> Result<(), GenericsError<'ast>> {
+            self.inputs
+                .iter()
+                .chain(std::iter::once(&*self.output))
+                .try_for_each(|ty| check_type_instance(ty, assignment))
+        }
+
         pub fn specialize(
             &self,
             values: Vec<Option<u32>>,
@@ -1311,28 +2135,30 @@ pub mod signature {
             assert_eq!(self.generics.len(), values.len());
             assert_eq!(self.inputs.len(), signature.inputs.len());
 
-            let decl_generics = self.generics.iter().map(|g| match g.clone().unwrap() {
-                DeclarationConstant::Generic(g) => g,
-                _ => unreachable!(),
-            });
+            let decl_generics = generic_identifiers(&self.generics);
 
             constants.0.extend(
                 decl_generics
+                    .iter()
+                    .cloned()
                     .zip(values.into_iter())
                     .filter_map(|(g, v)| v.map(|v| (g, v))),
             );
 
-            let condition = self
-                .inputs
-                .iter()
-                .chain(std::iter::once(&*self.output))
-                .zip(
-                    signature
-                        .inputs
-                        .iter()
-                        .chain(std::iter::once(&*signature.output)),
-                )
-                .all(|(decl_ty, ty)| check_type(decl_ty, ty, &mut constants));
+            let condition = check_types(
+                self.inputs
+                    .iter()
+                    .chain(std::iter::once(&*self.output))
+                    .zip(
+                        signature
+                            .inputs
+                            .iter()
+                            .chain(std::iter::once(&*signature.output)),
+                    )
+                    .collect(),
+                &decl_generics,
+                &mut constants,
+            );
 
             if constants.0.len() != self.generics.len() {
                 return Err(SpecializationError);
@@ -1348,32 +2174,28 @@ pub mod signature {
             &self,
             generics: Vec<Option<UExpression<'ast, T>>>,
             inputs: Vec<Type<'ast, T>>,
-        ) -> Result<Type<'ast, T>, GenericIdentifier<'ast>> {
+        ) -> Result<Type<'ast, T>, GenericsError<'ast>> {
             // we keep track of the value of constants in a map, as a given constant can only have one value
             let mut constants = GenericsAssignment::default();
 
+            let decl_generics = generic_identifiers(&self.generics);
+
             // initialise the map with the explicitly provided generics
-            constants
-                .0
-                .extend(self.generics.iter().zip(generics).filter_map(|(g, v)| {
+            constants.0.extend(
+                decl_generics
+                    .iter()
+                    .cloned()
+                    .zip(generics)
                     // only add to the map when there's indeed a generic value being provided
-                    v.map(|v| {
-                        (
-                            match g.clone().unwrap() {
-                                DeclarationConstant::Generic(g) => g,
-                                _ => unreachable!(),
-                            },
-                            v,
-                        )
-                    })
-                }));
+                    .filter_map(|(g, v)| v.map(|v| (g, v))),
+            );
 
             // fill the map with the inputs
-            let _ = self
-                .inputs
-                .iter()
-                .zip(inputs.iter())
-                .all(|(decl_ty, ty)| check_type(decl_ty, ty, &mut constants));
+            let _ = check_types(
+                self.inputs.iter().zip(inputs.iter()).collect(),
+                &decl_generics,
+                &mut constants,
+            );
 
             // get the specialized output
             specialize_declaration_type(*self.output.clone(), &constants)
@@ -1526,6 +2348,70 @@ pub mod signature {
             );
             assert_eq!(generic1.cmp(&generic2), std::cmp::Ordering::Equal);
         }
+
+        #[test]
+        fn specialize_generic_expressions() {
+            // <N>(field[N * 2]) -> field[N + 1]
+            let n = GenericIdentifier::with_name("N").with_index(0);
+            let n_expression =
+                || UExpression::<Bn128Field>::from(DeclarationConstant::from(n.clone()));
+
+            let s = DeclarationSignature::<Bn128Field>::new()
+                .generics(vec![Some(n.clone().into())])
+                .inputs(vec![DeclarationType::array((
+                    DeclarationType::FieldElement,
+                    DeclarationConstant::from_uint_expression(n_expression() * 2u32.into()),
+                ))])
+                .output(DeclarationType::array((
+                    DeclarationType::FieldElement,
+                    DeclarationConstant::from_uint_expression(n_expression() + 1u32.into()),
+                )));
+
+            let concrete = |input: u32, output: u32| {
+                ConcreteSignature::new()
+                    .inputs(vec![ConcreteType::array((
+                        ConcreteType::FieldElement,
+                        input,
+                    ))])
+                    .output(ConcreteType::array((ConcreteType::FieldElement, output)))
+            };
+
+            // N is inferred from the input
+            assert_eq!(
+                s.specialize(vec![None], &concrete(6, 4)).unwrap(),
+                GGenericsAssignment(vec![(n.clone(), 3)].into_iter().collect())
+            );
+            // the output does not match
+            assert!(s.specialize(vec![None], &concrete(6, 5)).is_err());
+            // no value of N matches both the input and the output
+            assert!(s.specialize(vec![None], &concrete(7, 8)).is_err());
+            // the explicit value does not match
+            assert!(s.specialize(vec![Some(2)], &concrete(6, 4)).is_err());
+        }
+
+        #[test]
+        fn check_overflowing_instance() {
+            // <N>() -> field[N - 1]
+            let n = GenericIdentifier::with_name("N").with_index(0);
+
+            let s = DeclarationSignature::<Bn128Field>::new()
+                .generics(vec![Some(n.clone().into())])
+                .output(DeclarationType::array((
+                    DeclarationType::FieldElement,
+                    DeclarationConstant::from_uint_expression(
+                        UExpression::from(DeclarationConstant::from(n.clone())) - 1u32.into(),
+                    ),
+                )));
+
+            let assignment =
+                |value: u32| GGenericsAssignment(vec![(n.clone(), value)].into_iter().collect());
+
+            assert_eq!(s.check_instance(&assignment(1)), Ok(()));
+            assert_eq!(
+                s.check_instance(&assignment(0)),
+                Err(GenericsError::Overflow("0 - 1".into()))
+            );
+        }
     }
 }
 
@@ -1548,4 +2434,39 @@ mod tests {
         ));
         assert_eq!(format!("{}", t), "field[1][2]");
     }
+
+    #[test]
+    fn enum_layout() {
+        // enum Foo { A(field, bool), B(field, field), C }
+        let t = ConcreteEnumType::new(
+            "".into(),
+            "Foo".into(),
+            vec![
+                ConcreteEnumVariant::new(
+                    "A".into(),
+                    vec![ConcreteType::FieldElement, ConcreteType::Boolean],
+                ),
+                ConcreteEnumVariant::new(
+                    "B".into(),
+                    vec![ConcreteType::FieldElement, ConcreteType::FieldElement],
+                ),
+                ConcreteEnumVariant::new("C".into(), vec![]),
+            ],
+        );
+
+        // variants share the slots of the same type
+        assert_eq!(
+            t.layout(),
+            (
+                vec![
+                    &ConcreteType::FieldElement,
+                    &ConcreteType::Boolean,
+                    &ConcreteType::FieldElement
+                ],
+                vec![vec![0, 1], vec![0, 2], vec![]]
+            )
+        );
+        // the tag followed by the slots
+        assert_eq!(ConcreteType::Enum(t).get_primitive_count(), 4);
+    }
 }
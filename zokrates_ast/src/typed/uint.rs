@@ -185,6 +185,7 @@ pub enum UExpressionInner<'ast, T> {
     Member(MemberExpression<'ast, T, UExpression<'ast, T>>),
     Select(SelectExpression<'ast, T, UExpression<'ast, T>>),
     Element(ElementExpression<'ast, T, UExpression<'ast, T>>),
+    Len(Box<BoundedArrayExpression<'ast, T>>),
 }
 
 impl<'ast, T> UExpressionInner<'ast, T> {
@@ -1,3 +1,4 @@
+use crate::common::ByteString;
 use crate::untyped::{self, ConditionalExpression, SymbolDefinition};
 use num_bigint::BigUint;
 use std::path::Path;
@@ -9,7 +10,11 @@ impl<'ast> From<pest::File<'ast>> for untyped::Module<'ast> {
             pest::SymbolDeclaration::Import(i) => import_directive_to_symbol_vec(i),
             pest::SymbolDeclaration::Constant(c) => vec![c.into()],
             pest::SymbolDeclaration::Struct(s) => vec![s.into()],
+            pest::SymbolDeclaration::Enum(e) => vec![e.into()],
             pest::SymbolDeclaration::Type(t) => vec![t.into()],
+            pest::SymbolDeclaration::StaticAssertion(a) => vec![a.into()],
+            pest::SymbolDeclaration::Trait(t) => vec![t.into()],
+            pest::SymbolDeclaration::Impl(i) => vec![i.into()],
             pest::SymbolDeclaration::Function(f) => vec![f.into()],
         }))
     }
@@ -24,24 +29,44 @@ fn import_directive_to_symbol_vec(
         pest::ImportDirective::Main(import) => {
             let span = import.span;
             let source = Path::new(import.source.raw.span.as_str());
-            let id = "main";
             let alias = import.alias.map(|a| a.span.as_str());
+            let is_public = import.public.is_some();
 
-            let import = untyped::CanonicalImport {
+            let import = untyped::ModuleImport {
                 source,
-                id: untyped::SymbolIdentifier::from(id).alias(alias),
+                kind: untyped::ModuleImportKind::Namespace(alias),
             }
             .span(span.clone());
 
             vec![untyped::SymbolDeclaration {
-                id: alias.unwrap_or(id),
-                symbol: untyped::Symbol::Here(untyped::SymbolDefinition::Import(import)),
+                id: alias.unwrap_or("main"),
+                symbol: untyped::Symbol::Here(untyped::SymbolDefinition::ModuleImport(import)),
+                is_public,
+            }
+            .span(span.clone())]
+        }
+        pest::ImportDirective::From(import) if import.all.is_some() => {
+            let span = import.span;
+            let source = Path::new(import.source.raw.span.as_str());
+            let is_public = import.public.is_some();
+
+            let import = untyped::ModuleImport {
+                source,
+                kind: untyped::ModuleImportKind::All,
+            }
+            .span(span.clone());
+
+            vec![untyped::SymbolDeclaration {
+                id: "*",
+                symbol: untyped::Symbol::Here(untyped::SymbolDefinition::ModuleImport(import)),
+                is_public,
             }
             .span(span.clone())]
         }
         pest::ImportDirective::From(import) => {
             let span = import.span;
             let source = Path::new(import.source.raw.span.as_str());
+            let is_public = import.public.is_some();
             import
                 .symbols
                 .into_iter()
@@ -62,6 +87,7 @@ fn import_directive_to_symbol_vec(
                     untyped::SymbolDeclaration {
                         id: alias,
                         symbol: untyped::Symbol::Here(untyped::SymbolDefinition::Import(import)),
+                        is_public,
                     }
                     .span(span.clone())
                 })
@@ -95,6 +121,7 @@ impl<'ast> From<pest::StructDefinition<'ast>> for untyped::SymbolDeclarationNode
         untyped::SymbolDeclaration {
             id,
             symbol: untyped::Symbol::Here(untyped::SymbolDefinition::Struct(ty)),
+            is_public: definition.public.is_some(),
         }
         .span(span)
     }
@@ -114,6 +141,50 @@ impl<'ast> From<pest::StructField<'ast>> for untyped::StructDefinitionFieldNode<
     }
 }
 
+impl<'ast> From<pest::EnumDefinition<'ast>> for untyped::SymbolDeclarationNode<'ast> {
+    fn from(definition: pest::EnumDefinition<'ast>) -> untyped::SymbolDeclarationNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        let span = definition.span;
+
+        let id = definition.id.span.as_str();
+
+        let ty = untyped::EnumDefinition {
+            variants: definition
+                .variants
+                .into_iter()
+                .map(untyped::EnumVariantNode::from)
+                .collect(),
+        }
+        .span(span.clone());
+
+        untyped::SymbolDeclaration {
+            id,
+            symbol: untyped::Symbol::Here(untyped::SymbolDefinition::Enum(ty)),
+            is_public: definition.public.is_some(),
+        }
+        .span(span)
+    }
+}
+
+impl<'ast> From<pest::EnumVariant<'ast>> for untyped::EnumVariantNode<'ast> {
+    fn from(variant: pest::EnumVariant<'ast>) -> untyped::EnumVariantNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        let span = variant.span;
+
+        let id = variant.id.span.as_str();
+
+        let types = variant
+            .types
+            .into_iter()
+            .map(untyped::UnresolvedTypeNode::from)
+            .collect();
+
+        untyped::EnumVariant { id, types }.span(span)
+    }
+}
+
 impl<'ast> From<pest::ConstantDefinition<'ast>> for untyped::SymbolDeclarationNode<'ast> {
     fn from(definition: pest::ConstantDefinition<'ast>) -> untyped::SymbolDeclarationNode<'ast> {
         use crate::untyped::NodeValue;
@@ -130,6 +201,7 @@ impl<'ast> From<pest::ConstantDefinition<'ast>> for untyped::SymbolDeclarationNo
         untyped::SymbolDeclaration {
             id,
             symbol: untyped::Symbol::Here(untyped::SymbolDefinition::Constant(ty)),
+            is_public: definition.public.is_some(),
         }
         .span(span)
     }
@@ -155,6 +227,110 @@ impl<'ast> From<pest::TypeDefinition<'ast>> for untyped::SymbolDeclarationNode<'
         untyped::SymbolDeclaration {
             id,
             symbol: untyped::Symbol::Here(SymbolDefinition::Type(ty)),
+            is_public: definition.public.is_some(),
+        }
+        .span(span)
+    }
+}
+
+impl<'ast> From<pest::TraitDefinition<'ast>> for untyped::SymbolDeclarationNode<'ast> {
+    fn from(definition: pest::TraitDefinition<'ast>) -> untyped::SymbolDeclarationNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        let span = definition.span;
+        let id = definition.id.span.as_str();
+
+        let t = untyped::TraitDefinition {
+            functions: definition
+                .functions
+                .into_iter()
+                .map(untyped::TraitFunctionNode::from)
+                .collect(),
+        }
+        .span(span.clone());
+
+        untyped::SymbolDeclaration {
+            id,
+            symbol: untyped::Symbol::Here(SymbolDefinition::Trait(t)),
+            is_public: definition.public.is_some(),
+        }
+        .span(span)
+    }
+}
+
+impl<'ast> From<pest::TraitFunction<'ast>> for untyped::TraitFunctionNode<'ast> {
+    fn from(function: pest::TraitFunction<'ast>) -> untyped::TraitFunctionNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        let is_method = function.self_parameter.is_some();
+
+        let signature = untyped::UnresolvedSignature::new().inputs(
+            function
+                .self_parameter
+                .map(|p| untyped::UnresolvedType::User("Self".into(), None).span(p.span))
+                .into_iter()
+                .chain(
+                    function
+                        .parameters
+                        .into_iter()
+                        .map(|p| untyped::UnresolvedTypeNode::from(p.ty)),
+                )
+                .collect(),
+        );
+
+        let signature = match function.return_type {
+            Some(ret_ty) => signature.output(untyped::UnresolvedTypeNode::from(ret_ty)),
+            None => signature,
+        };
+
+        untyped::TraitFunction {
+            id: function.id.span.as_str(),
+            is_method,
+            signature,
+        }
+        .span(function.span)
+    }
+}
+
+impl<'ast> From<pest::StaticAssertStatement<'ast>> for untyped::SymbolDeclarationNode<'ast> {
+    fn from(statement: pest::StaticAssertStatement<'ast>) -> untyped::SymbolDeclarationNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        // static assertions do not declare a symbol of their own
+        untyped::SymbolDeclaration {
+            id: "",
+            symbol: untyped::Symbol::Here(SymbolDefinition::StaticAssertion(
+                untyped::ExpressionNode::from(statement.expression),
+                statement.message.raw.value,
+            )),
+            is_public: false,
+        }
+        .span(statement.span)
+    }
+}
+
+impl<'ast> From<pest::ImplDefinition<'ast>> for untyped::SymbolDeclarationNode<'ast> {
+    fn from(definition: pest::ImplDefinition<'ast>) -> untyped::SymbolDeclarationNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        let span = definition.span;
+
+        let i = untyped::ImplDefinition {
+            trait_id: definition.trait_id.map(|t| t.id.span.as_str()),
+            ty: definition.ty.into(),
+            functions: definition
+                .functions
+                .into_iter()
+                .map(|f| (f.id.span.as_str(), untyped::FunctionNode::from(f)))
+                .collect(),
+        }
+        .span(span.clone());
+
+        // `impl` blocks do not declare a symbol of their own
+        untyped::SymbolDeclaration {
+            id: "",
+            symbol: untyped::Symbol::Here(SymbolDefinition::Impl(i)),
+            is_public: false,
         }
         .span(span)
     }
@@ -164,9 +340,48 @@ impl<'ast> From<pest::FunctionDefinition<'ast>> for untyped::SymbolDeclarationNo
     fn from(function: pest::FunctionDefinition<'ast>) -> untyped::SymbolDeclarationNode<'ast> {
         use crate::untyped::NodeValue;
 
+        let span = function.span.clone();
+
+        let id = function.id.span.as_str();
+        let is_public = function.public.is_some();
+
+        let function = untyped::FunctionNode::from(function);
+
+        untyped::SymbolDeclaration {
+            id,
+            symbol: untyped::Symbol::Here(untyped::SymbolDefinition::Function(function)),
+            is_public,
+        }
+        .span(span)
+    }
+}
+
+impl<'ast> From<pest::FunctionDefinition<'ast>> for untyped::FunctionNode<'ast> {
+    fn from(function: pest::FunctionDefinition<'ast>) -> untyped::FunctionNode<'ast> {
+        use crate::untyped::NodeValue;
+
         let span = function.span;
 
+        let arguments: Vec<_> = function
+            .self_parameter
+            .map(untyped::ParameterNode::from)
+            .into_iter()
+            .chain(
+                function
+                    .parameters
+                    .into_iter()
+                    .map(untyped::ParameterNode::from),
+            )
+            .collect();
+
         let signature = untyped::UnresolvedSignature::new()
+            .type_generics(
+                function
+                    .type_generics
+                    .into_iter()
+                    .map(untyped::TypeGenericNode::from)
+                    .collect(),
+            )
             .generics(
                 function
                     .generics
@@ -175,11 +390,9 @@ impl<'ast> From<pest::FunctionDefinition<'ast>> for untyped::SymbolDeclarationNo
                     .collect(),
             )
             .inputs(
-                function
-                    .parameters
-                    .clone()
-                    .into_iter()
-                    .map(|p| untyped::UnresolvedTypeNode::from(p.ty))
+                arguments
+                    .iter()
+                    .map(|a| a.value.id.value._type.clone())
                     .collect(),
             );
 
@@ -188,24 +401,24 @@ impl<'ast> From<pest::FunctionDefinition<'ast>> for untyped::SymbolDeclarationNo
             None => signature,
         };
 
-        let id = function.id.span.as_str();
-
-        let function = untyped::Function {
-            arguments: function
-                .parameters
-                .into_iter()
-                .map(untyped::ParameterNode::from)
-                .collect(),
+        untyped::Function {
+            arguments,
             statements: function.statements.into_iter().map(|s| s.into()).collect(),
             signature,
         }
-        .span(span.clone());
+        .span(span)
+    }
+}
 
-        untyped::SymbolDeclaration {
-            id,
-            symbol: untyped::Symbol::Here(untyped::SymbolDefinition::Function(function)),
+impl<'ast> From<pest::TypeGeneric<'ast>> for untyped::TypeGenericNode<'ast> {
+    fn from(g: pest::TypeGeneric<'ast>) -> untyped::TypeGenericNode<'ast> {
+        use untyped::NodeValue;
+
+        untyped::TypeGeneric {
+            id: g.id.span.as_str(),
+            bound: g.bound.span.as_str(),
         }
-        .span(span)
+        .span(g.span)
     }
 }
 
@@ -241,6 +454,22 @@ impl<'ast> From<pest::Parameter<'ast>> for untyped::ParameterNode<'ast> {
     }
 }
 
+// `self` is a parameter of type `Self`, which stands for the type of the `impl` block
+impl<'ast> From<pest::SelfParameter<'ast>> for untyped::ParameterNode<'ast> {
+    fn from(param: pest::SelfParameter<'ast>) -> untyped::ParameterNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        let variable = untyped::Variable::new(
+            "self",
+            untyped::UnresolvedType::User("Self".into(), None).span(param.span.clone()),
+            param.mutable.is_some(),
+        )
+        .span(param.span.clone());
+
+        untyped::Parameter::new(variable, None).span(param.span)
+    }
+}
+
 impl<'ast> From<pest::LogStatement<'ast>> for untyped::StatementNode<'ast> {
     fn from(statement: pest::LogStatement<'ast>) -> untyped::StatementNode<'ast> {
         use crate::untyped::NodeValue;
@@ -273,8 +502,22 @@ impl<'ast> From<pest::Statement<'ast>> for untyped::StatementNode<'ast> {
     fn from(statement: pest::Statement<'ast>) -> Self {
         match statement {
             pest::Statement::Definition(s) => untyped::StatementNode::from(s),
+            pest::Statement::Destructuring(s) => untyped::StatementNode::from(s),
             pest::Statement::Iteration(s) => untyped::StatementNode::from(s),
+            pest::Statement::ArrayIteration(s) => untyped::StatementNode::from(s),
+            pest::Statement::While(s) => untyped::StatementNode::from(s),
+            pest::Statement::If(s) => untyped::StatementNode::from(s),
+            pest::Statement::ComptimeIf(s) => untyped::StatementNode::from(s),
+            pest::Statement::Break(s) => {
+                use crate::untyped::NodeValue;
+                untyped::Statement::Break.span(s.span)
+            }
+            pest::Statement::Continue(s) => {
+                use crate::untyped::NodeValue;
+                untyped::Statement::Continue.span(s.span)
+            }
             pest::Statement::Assertion(s) => untyped::StatementNode::from(s),
+            pest::Statement::StaticAssertion(s) => untyped::StatementNode::from(s),
             pest::Statement::Return(s) => untyped::StatementNode::from(s),
             pest::Statement::Log(s) => untyped::StatementNode::from(s),
             pest::Statement::Assembly(s) => untyped::StatementNode::from(s),
@@ -307,6 +550,84 @@ impl<'ast> From<pest::DefinitionStatement<'ast>> for untyped::StatementNode<'ast
     }
 }
 
+impl<'ast> From<pest::DestructuringStatement<'ast>> for untyped::StatementNode<'ast> {
+    fn from(statement: pest::DestructuringStatement<'ast>) -> Self {
+        use crate::untyped::NodeValue;
+
+        let pattern = match statement.pattern {
+            pest::DestructuringPattern::Tuple(p) => untyped::PatternNode::from(p),
+            pest::DestructuringPattern::Struct(p) => untyped::PatternNode::from(p),
+        };
+
+        untyped::Statement::Destructuring(
+            pattern,
+            untyped::ExpressionNode::from(statement.expression),
+        )
+        .span(statement.span)
+    }
+}
+
+impl<'ast> From<pest::Pattern<'ast>> for untyped::PatternNode<'ast> {
+    fn from(pattern: pest::Pattern<'ast>) -> Self {
+        use crate::untyped::NodeValue;
+
+        match pattern {
+            pest::Pattern::Tuple(p) => untyped::PatternNode::from(p),
+            pest::Pattern::Struct(p) => untyped::PatternNode::from(p),
+            pest::Pattern::Wildcard(u) => untyped::Pattern::Wildcard.span(u.span),
+            pest::Pattern::TypedIdentifier(i) => {
+                let span = i.span.clone();
+                untyped::Pattern::Variable(untyped::VariableNode::from(i)).span(span)
+            }
+            pest::Pattern::Binding(b) => {
+                untyped::Pattern::Binding(b.identifier.span.as_str(), b.mutable.is_some())
+                    .span(b.span)
+            }
+        }
+    }
+}
+
+impl<'ast> From<pest::TuplePattern<'ast>> for untyped::PatternNode<'ast> {
+    fn from(pattern: pest::TuplePattern<'ast>) -> Self {
+        use crate::untyped::NodeValue;
+
+        untyped::Pattern::Tuple(
+            pattern
+                .elements
+                .into_iter()
+                .map(untyped::PatternNode::from)
+                .collect(),
+        )
+        .span(pattern.span)
+    }
+}
+
+impl<'ast> From<pest::StructPattern<'ast>> for untyped::PatternNode<'ast> {
+    fn from(pattern: pest::StructPattern<'ast>) -> Self {
+        use crate::untyped::NodeValue;
+
+        untyped::Pattern::Struct(
+            pattern.ty.span.as_str().to_string(),
+            pattern
+                .fields
+                .into_iter()
+                .map(|field| {
+                    let id = field.id.span.as_str();
+                    let pattern = match field.pattern {
+                        Some(p) => untyped::PatternNode::from(p),
+                        // `x` is a shorthand for `x: x`
+                        None => {
+                            untyped::Pattern::Binding(id, field.mutable.is_some()).span(field.span)
+                        }
+                    };
+                    (id, pattern)
+                })
+                .collect(),
+        )
+        .span(pattern.span)
+    }
+}
+
 impl<'ast> From<pest::ReturnStatement<'ast>> for untyped::StatementNode<'ast> {
     fn from(statement: pest::ReturnStatement<'ast>) -> untyped::StatementNode<'ast> {
         use crate::untyped::NodeValue;
@@ -328,6 +649,18 @@ impl<'ast> From<pest::AssertionStatement<'ast>> for untyped::StatementNode<'ast>
     }
 }
 
+impl<'ast> From<pest::StaticAssertStatement<'ast>> for untyped::StatementNode<'ast> {
+    fn from(statement: pest::StaticAssertStatement<'ast>) -> untyped::StatementNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        untyped::Statement::StaticAssertion(
+            untyped::ExpressionNode::from(statement.expression),
+            statement.message.raw.value,
+        )
+        .span(statement.span)
+    }
+}
+
 impl<'ast> From<pest::IterationStatement<'ast>> for untyped::StatementNode<'ast> {
     fn from(statement: pest::IterationStatement<'ast>) -> untyped::StatementNode<'ast> {
         use crate::untyped::NodeValue;
@@ -341,6 +674,76 @@ impl<'ast> From<pest::IterationStatement<'ast>> for untyped::StatementNode<'ast>
     }
 }
 
+impl<'ast> From<pest::ArrayIterationStatement<'ast>> for untyped::StatementNode<'ast> {
+    fn from(statement: pest::ArrayIterationStatement<'ast>) -> untyped::StatementNode<'ast> {
+        use crate::untyped::NodeValue;
+        let element = untyped::VariableNode::from(statement.element);
+        let array = untyped::ExpressionNode::from(statement.array);
+        let statements: Vec<untyped::StatementNode<'ast>> =
+            statement.statements.into_iter().map(|s| s.into()).collect();
+
+        untyped::Statement::ForEach(element, array, statements).span(statement.span)
+    }
+}
+
+impl<'ast> From<pest::WhileStatement<'ast>> for untyped::StatementNode<'ast> {
+    fn from(statement: pest::WhileStatement<'ast>) -> untyped::StatementNode<'ast> {
+        use crate::untyped::NodeValue;
+        let condition = untyped::ExpressionNode::from(statement.condition);
+        let bound = untyped::ExpressionNode::from(statement.bound);
+        let statements: Vec<untyped::StatementNode<'ast>> =
+            statement.statements.into_iter().map(|s| s.into()).collect();
+
+        untyped::Statement::While(condition, bound, statements).span(statement.span)
+    }
+}
+
+impl<'ast> From<pest::IfStatement<'ast>> for untyped::StatementNode<'ast> {
+    fn from(statement: pest::IfStatement<'ast>) -> untyped::StatementNode<'ast> {
+        use crate::untyped::NodeValue;
+        let condition = untyped::ExpressionNode::from(statement.condition);
+        let consequence: Vec<untyped::StatementNode<'ast>> = statement
+            .consequence
+            .statements
+            .into_iter()
+            .map(|s| s.into())
+            .collect();
+        // `else if` is represented as an `if` statement alone in the alternative
+        let alternative: Vec<untyped::StatementNode<'ast>> = match statement.alternative {
+            None => vec![],
+            Some(pest::ElseClause::If(box s)) => vec![s.into()],
+            Some(pest::ElseClause::Block(b)) => {
+                b.statements.into_iter().map(|s| s.into()).collect()
+            }
+        };
+
+        untyped::Statement::If(condition, consequence, alternative).span(statement.span)
+    }
+}
+
+impl<'ast> From<pest::ComptimeIfStatement<'ast>> for untyped::StatementNode<'ast> {
+    fn from(statement: pest::ComptimeIfStatement<'ast>) -> untyped::StatementNode<'ast> {
+        use crate::untyped::NodeValue;
+        let condition = untyped::ExpressionNode::from(statement.condition);
+        let consequence: Vec<untyped::StatementNode<'ast>> = statement
+            .consequence
+            .statements
+            .into_iter()
+            .map(|s| s.into())
+            .collect();
+        // `else comptime if` is represented as a `comptime if` statement alone in the alternative
+        let alternative: Vec<untyped::StatementNode<'ast>> = match statement.alternative {
+            None => vec![],
+            Some(pest::ComptimeElseClause::If(box s)) => vec![s.into()],
+            Some(pest::ComptimeElseClause::Block(b)) => {
+                b.statements.into_iter().map(|s| s.into()).collect()
+            }
+        };
+
+        untyped::Statement::ComptimeIf(condition, consequence, alternative).span(statement.span)
+    }
+}
+
 impl<'ast> From<pest::AssemblyStatement<'ast>> for untyped::StatementNode<'ast> {
     fn from(statement: pest::AssemblyStatement<'ast>) -> untyped::StatementNode<'ast> {
         use crate::untyped::NodeValue;
@@ -373,13 +776,17 @@ impl<'ast> From<pest::Expression<'ast>> for untyped::ExpressionNode<'ast> {
             pest::Expression::Binary(e) => untyped::ExpressionNode::from(e),
             pest::Expression::Ternary(e) => untyped::ExpressionNode::from(e),
             pest::Expression::IfElse(e) => untyped::ExpressionNode::from(e),
+            pest::Expression::Lambda(e) => untyped::ExpressionNode::from(e),
             pest::Expression::Literal(e) => untyped::ExpressionNode::from(e),
             pest::Expression::Identifier(e) => untyped::ExpressionNode::from(e),
             pest::Expression::Postfix(e) => untyped::ExpressionNode::from(e),
             pest::Expression::InlineArray(e) => untyped::ExpressionNode::from(e),
             pest::Expression::InlineTuple(e) => untyped::ExpressionNode::from(e),
             pest::Expression::InlineStruct(e) => untyped::ExpressionNode::from(e),
+            pest::Expression::EnumVariant(e) => untyped::ExpressionNode::from(e),
+            pest::Expression::Match(e) => untyped::ExpressionNode::from(e),
             pest::Expression::ArrayInitializer(e) => untyped::ExpressionNode::from(e),
+            pest::Expression::ByteString(e) => untyped::ExpressionNode::from(e),
             pest::Expression::Unary(e) => untyped::ExpressionNode::from(e),
         }
     }
@@ -497,6 +904,54 @@ impl<'ast> From<pest::IfElseExpression<'ast>> for untyped::ExpressionNode<'ast>
     }
 }
 
+// the body of an anonymous function is a block expression, which we turn into a function returning that expression
+impl<'ast> From<pest::LambdaExpression<'ast>> for untyped::ExpressionNode<'ast> {
+    fn from(expression: pest::LambdaExpression<'ast>) -> untyped::ExpressionNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        let span = expression.span;
+
+        let arguments: Vec<_> = expression
+            .parameters
+            .into_iter()
+            .map(untyped::ParameterNode::from)
+            .collect();
+
+        let signature = untyped::UnresolvedSignature::new()
+            .inputs(
+                arguments
+                    .iter()
+                    .map(|a| a.value.id.value._type.clone())
+                    .collect(),
+            )
+            .output(untyped::UnresolvedTypeNode::from(expression.return_type));
+
+        let return_span = expression.expression.span().clone();
+
+        let statements = expression
+            .statements
+            .into_iter()
+            .map(untyped::StatementNode::from)
+            .chain(std::iter::once(
+                untyped::Statement::Return(Some(untyped::ExpressionNode::from(
+                    *expression.expression,
+                )))
+                .span(return_span),
+            ))
+            .collect();
+
+        untyped::Expression::Lambda(
+            box untyped::Function {
+                arguments,
+                statements,
+                signature,
+            }
+            .span(span.clone()),
+        )
+        .span(span)
+    }
+}
+
 impl<'ast> From<pest::TernaryExpression<'ast>> for untyped::ExpressionNode<'ast> {
     fn from(expression: pest::TernaryExpression<'ast>) -> untyped::ExpressionNode<'ast> {
         use crate::untyped::NodeValue;
@@ -611,6 +1066,45 @@ impl<'ast> From<pest::InlineStructExpression<'ast>> for untyped::ExpressionNode<
     }
 }
 
+impl<'ast> From<pest::EnumVariantExpression<'ast>> for untyped::ExpressionNode<'ast> {
+    fn from(e: pest::EnumVariantExpression<'ast>) -> untyped::ExpressionNode<'ast> {
+        use crate::untyped::NodeValue;
+        untyped::Expression::EnumVariant(
+            e.ty.span.as_str().to_string(),
+            e.variant.span.as_str(),
+            e.arguments
+                .into_iter()
+                .map(untyped::ExpressionNode::from)
+                .collect(),
+        )
+        .span(e.span)
+    }
+}
+
+impl<'ast> From<pest::MatchExpression<'ast>> for untyped::ExpressionNode<'ast> {
+    fn from(e: pest::MatchExpression<'ast>) -> untyped::ExpressionNode<'ast> {
+        use crate::untyped::NodeValue;
+        untyped::Expression::Match(
+            box untyped::ExpressionNode::from(*e.expression),
+            e.arms
+                .into_iter()
+                .map(|arm| untyped::MatchArm {
+                    pattern: match arm.pattern {
+                        pest::MatchPattern::Wildcard(_) => untyped::MatchPattern::Wildcard,
+                        pest::MatchPattern::Variant(p) => untyped::MatchPattern::Variant(
+                            p.ty.span.as_str().to_string(),
+                            p.variant.span.as_str(),
+                            p.bindings.into_iter().map(|b| b.span.as_str()).collect(),
+                        ),
+                    },
+                    expression: untyped::ExpressionNode::from(arm.expression),
+                })
+                .collect(),
+        )
+        .span(e.span)
+    }
+}
+
 impl<'ast> From<pest::ArrayInitializerExpression<'ast>> for untyped::ExpressionNode<'ast> {
     fn from(initializer: pest::ArrayInitializerExpression<'ast>) -> untyped::ExpressionNode<'ast> {
         use crate::untyped::NodeValue;
@@ -657,6 +1151,9 @@ impl<'ast> From<pest::PostfixExpression<'ast>> for untyped::ExpressionNode<'ast>
                             .into_iter()
                             .map(|i| match i {
                                 pest::ConstantGenericValue::Underscore(_) => None,
+                                pest::ConstantGenericValue::Expression(e) => {
+                                    Some(untyped::ExpressionNode::from(e))
+                                }
                                 pest::ConstantGenericValue::Value(v) => {
                                     Some(untyped::ExpressionNode::from(v))
                                 }
@@ -700,6 +1197,9 @@ impl<'ast> From<pest::DecimalLiteralExpression<'ast>> for untyped::ExpressionNod
                 pest::DecimalSuffix::Field(_) => untyped::Expression::FieldConstant(
                     BigUint::parse_bytes(expression.value.span.as_str().as_bytes(), 10).unwrap(),
                 ),
+                pest::DecimalSuffix::U128(_) => untyped::Expression::U128Constant(
+                    expression.value.span.as_str().parse().unwrap(),
+                ),
                 pest::DecimalSuffix::U64(_) => untyped::Expression::U64Constant(
                     expression.value.span.as_str().parse().unwrap(),
                 ),
@@ -712,6 +1212,18 @@ impl<'ast> From<pest::DecimalLiteralExpression<'ast>> for untyped::ExpressionNod
                 pest::DecimalSuffix::U8(_) => {
                     untyped::Expression::U8Constant(expression.value.span.as_str().parse().unwrap())
                 }
+                pest::DecimalSuffix::I64(_) => untyped::Expression::I64Constant(
+                    BigUint::parse_bytes(expression.value.span.as_str().as_bytes(), 10).unwrap(),
+                ),
+                pest::DecimalSuffix::I32(_) => untyped::Expression::I32Constant(
+                    BigUint::parse_bytes(expression.value.span.as_str().as_bytes(), 10).unwrap(),
+                ),
+                pest::DecimalSuffix::I16(_) => untyped::Expression::I16Constant(
+                    BigUint::parse_bytes(expression.value.span.as_str().as_bytes(), 10).unwrap(),
+                ),
+                pest::DecimalSuffix::I8(_) => untyped::Expression::I8Constant(
+                    BigUint::parse_bytes(expression.value.span.as_str().as_bytes(), 10).unwrap(),
+                ),
             }
             .span(expression.span),
             None => untyped::Expression::IntConstant(
@@ -727,6 +1239,9 @@ impl<'ast> From<pest::HexLiteralExpression<'ast>> for untyped::ExpressionNode<'a
         use crate::untyped::NodeValue;
 
         match expression.value {
+            pest::HexNumberExpression::U128(e) => untyped::Expression::U128Constant(
+                u128::from_str_radix(e.span.as_str(), 16).unwrap(),
+            ),
             pest::HexNumberExpression::U64(e) => {
                 untyped::Expression::U64Constant(u64::from_str_radix(e.span.as_str(), 16).unwrap())
             }
@@ -744,6 +1259,27 @@ impl<'ast> From<pest::HexLiteralExpression<'ast>> for untyped::ExpressionNode<'a
     }
 }
 
+impl<'ast> From<pest::ByteStringExpression<'ast>> for untyped::ExpressionNode<'ast> {
+    fn from(expression: pest::ByteStringExpression<'ast>) -> untyped::ExpressionNode<'ast> {
+        use crate::untyped::NodeValue;
+
+        match expression {
+            pest::ByteStringExpression::Bytes(b) => {
+                untyped::Expression::ByteString(ByteString::from(b.value.raw.value.as_str()))
+                    .span(b.span)
+            }
+            pest::ByteStringExpression::Hex(h) => {
+                // the grammar guarantees an even number of hexadecimal digits
+                let bytes = (0..h.value.value.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&h.value.value[i..i + 2], 16).unwrap())
+                    .collect();
+                untyped::Expression::ByteString(ByteString(bytes)).span(h.span)
+            }
+        }
+    }
+}
+
 impl<'ast> From<pest::LiteralExpression<'ast>> for untyped::ExpressionNode<'ast> {
     fn from(expression: pest::LiteralExpression<'ast>) -> untyped::ExpressionNode<'ast> {
         use crate::untyped::NodeValue;
@@ -813,6 +1349,11 @@ impl<'ast> From<pest::Type<'ast>> for untyped::UnresolvedTypeNode<'ast> {
                 pest::BasicType::U16(t) => UnresolvedType::Uint(16).span(t.span),
                 pest::BasicType::U32(t) => UnresolvedType::Uint(32).span(t.span),
                 pest::BasicType::U64(t) => UnresolvedType::Uint(64).span(t.span),
+                pest::BasicType::U128(t) => UnresolvedType::Uint(128).span(t.span),
+                pest::BasicType::I8(t) => UnresolvedType::Int(8).span(t.span),
+                pest::BasicType::I16(t) => UnresolvedType::Int(16).span(t.span),
+                pest::BasicType::I32(t) => UnresolvedType::Int(32).span(t.span),
+                pest::BasicType::I64(t) => UnresolvedType::Int(64).span(t.span),
             },
             pest::Type::Array(t) => {
                 let inner_type = match t.ty {
@@ -823,6 +1364,11 @@ impl<'ast> From<pest::Type<'ast>> for untyped::UnresolvedTypeNode<'ast> {
                         pest::BasicType::U16(t) => UnresolvedType::Uint(16).span(t.span),
                         pest::BasicType::U32(t) => UnresolvedType::Uint(32).span(t.span),
                         pest::BasicType::U64(t) => UnresolvedType::Uint(64).span(t.span),
+                        pest::BasicType::U128(t) => UnresolvedType::Uint(128).span(t.span),
+                        pest::BasicType::I8(t) => UnresolvedType::Int(8).span(t.span),
+                        pest::BasicType::I16(t) => UnresolvedType::Int(16).span(t.span),
+                        pest::BasicType::I32(t) => UnresolvedType::Int(32).span(t.span),
+                        pest::BasicType::I64(t) => UnresolvedType::Int(64).span(t.span),
                     },
                     pest::BasicOrStructOrTupleType::Struct(t) => UnresolvedType::User(
                         t.id.span.as_str().to_string(),
@@ -832,6 +1378,9 @@ impl<'ast> From<pest::Type<'ast>> for untyped::UnresolvedTypeNode<'ast> {
                                 .into_iter()
                                 .map(|i| match i {
                                     pest::ConstantGenericValue::Underscore(_) => None,
+                                    pest::ConstantGenericValue::Expression(e) => {
+                                        Some(untyped::ExpressionNode::from(e))
+                                    }
                                     pest::ConstantGenericValue::Value(v) => {
                                         Some(untyped::ExpressionNode::from(v))
                                     }
@@ -857,11 +1406,17 @@ impl<'ast> From<pest::Type<'ast>> for untyped::UnresolvedTypeNode<'ast> {
 
                 t.dimensions
                     .into_iter()
-                    .map(untyped::ExpressionNode::from)
                     .rev()
-                    .fold(None, |acc, s| match acc {
-                        None => Some(UnresolvedType::array(inner_type.clone(), s)),
-                        Some(acc) => Some(UnresolvedType::array(acc.span(span.clone()), s)),
+                    .fold(None::<UnresolvedType>, |acc, d| {
+                        let ty = match acc {
+                            None => inner_type.clone(),
+                            Some(acc) => acc.span(span.clone()),
+                        };
+                        let size = untyped::ExpressionNode::from(d.size);
+                        Some(match d.bounded {
+                            Some(_) => UnresolvedType::bounded_array(ty, size),
+                            None => UnresolvedType::array(ty, size),
+                        })
                     })
                     .unwrap()
                     .span(span.clone())
@@ -874,6 +1429,9 @@ impl<'ast> From<pest::Type<'ast>> for untyped::UnresolvedTypeNode<'ast> {
                         .into_iter()
                         .map(|i| match i {
                             pest::ConstantGenericValue::Underscore(_) => None,
+                            pest::ConstantGenericValue::Expression(e) => {
+                                Some(untyped::ExpressionNode::from(e))
+                            }
                             pest::ConstantGenericValue::Value(v) => {
                                 Some(untyped::ExpressionNode::from(v))
                             }
@@ -892,6 +1450,15 @@ impl<'ast> From<pest::Type<'ast>> for untyped::UnresolvedTypeNode<'ast> {
                     .collect(),
             )
             .span(t.span),
+            pest::Type::Function(t) => UnresolvedType::Function(
+                t.inputs
+                    .types
+                    .into_iter()
+                    .map(untyped::UnresolvedTypeNode::from)
+                    .collect(),
+                box untyped::UnresolvedTypeNode::from(*t.output),
+            )
+            .span(t.span),
         }
     }
 }
@@ -922,6 +1489,7 @@ mod tests {
                     }
                     .into(),
                 )),
+                is_public: false,
             }
             .into()],
         };
@@ -948,12 +1516,29 @@ mod tests {
                     }
                     .into(),
                 )),
+                is_public: false,
             }
             .into()],
         };
         assert_eq!(untyped::Module::from(ast), expected);
     }
 
+    #[test]
+    fn visibility() {
+        // only the symbols marked `pub` are public, including imports which are then re-exported
+        let source = r#"pub from "./a" import B; from "./c" import D; pub const field A = 1; def main() -> field { return A; }"#;
+        let ast = pest::generate_ast(source).unwrap();
+        let module = untyped::Module::from(ast);
+        assert_eq!(
+            module
+                .symbols
+                .iter()
+                .map(|s| (s.value.id, s.value.is_public))
+                .collect::<Vec<_>>(),
+            vec![("B", true), ("D", false), ("A", true), ("main", false)]
+        );
+    }
+
     #[test]
     fn arguments() {
         let source = "def main(private field a, bool mut b) -> field { return 42; }";
@@ -993,6 +1578,7 @@ mod tests {
                     }
                     .into(),
                 )),
+                is_public: false,
             }
             .into()],
         };
@@ -1019,6 +1605,7 @@ mod tests {
                         }
                         .into(),
                     )),
+                    is_public: false,
                 }
                 .into()],
             }
@@ -1085,6 +1672,7 @@ mod tests {
                         }
                         .into(),
                     )),
+                    is_public: false,
                 }
                 .into()],
             }
@@ -17,7 +17,7 @@ pub use self::parameter::{Parameter, ParameterNode};
 pub use self::position::Position;
 use self::types::{UnresolvedSignature, UnresolvedType, UserTypeId};
 pub use self::variable::{Variable, VariableNode};
-use crate::common::FlatEmbed;
+use crate::common::{ByteString, FlatEmbed};
 use std::path::{Path, PathBuf};
 
 use std::fmt;
@@ -91,6 +91,37 @@ impl<'ast> fmt::Display for CanonicalImport<'ast> {
     }
 }
 
+/// The way a module import brings the symbols of a module into scope
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleImportKind<'ast> {
+    /// All public symbols except `main`, as in `from "x" import *`
+    All,
+    /// All public symbols under a namespace, as in `import "x" as y`. The namespace defaults to the name of the module
+    Namespace(Option<Identifier<'ast>>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleImport<'ast> {
+    pub source: &'ast Path,
+    pub kind: ModuleImportKind<'ast>,
+}
+
+pub type ModuleImportNode<'ast> = Node<ModuleImport<'ast>>;
+
+impl<'ast> fmt::Display for ModuleImport<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ModuleImportKind::All => write!(f, "from \"{}\" import *", self.source.display()),
+            ModuleImportKind::Namespace(namespace) => write!(
+                f,
+                "import \"{}\"{}",
+                self.source.display(),
+                namespace.map(|n| format!(" as {}", n)).unwrap_or_default()
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SymbolImport<'ast> {
     pub module_id: OwnedModuleId,
@@ -127,15 +158,22 @@ impl<'ast> fmt::Display for SymbolImport<'ast> {
 pub struct SymbolDeclaration<'ast> {
     pub id: Identifier<'ast>,
     pub symbol: Symbol<'ast>,
+    /// Whether the symbol can be imported from other modules
+    pub is_public: bool,
 }
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, PartialEq, Clone)]
 pub enum SymbolDefinition<'ast> {
     Import(CanonicalImportNode<'ast>),
+    ModuleImport(ModuleImportNode<'ast>),
     Struct(StructDefinitionNode<'ast>),
+    Enum(EnumDefinitionNode<'ast>),
     Constant(ConstantDefinitionNode<'ast>),
     Type(TypeDefinitionNode<'ast>),
+    StaticAssertion(ExpressionNode<'ast>, String),
+    Trait(TraitDefinitionNode<'ast>),
+    Impl(ImplDefinitionNode<'ast>),
     Function(FunctionNode<'ast>),
 }
 
@@ -157,7 +195,9 @@ impl<'ast> fmt::Display for SymbolDeclaration<'ast> {
                     i.value.source.display(),
                     i.value.id
                 ),
+                SymbolDefinition::ModuleImport(ref i) => write!(f, "{}", i),
                 SymbolDefinition::Struct(ref s) => write!(f, "struct {}{}", self.id, s),
+                SymbolDefinition::Enum(ref e) => write!(f, "enum {}{}", self.id, e),
                 SymbolDefinition::Constant(ref c) => write!(
                     f,
                     "const {} {} = {}",
@@ -179,6 +219,11 @@ impl<'ast> fmt::Display for SymbolDeclaration<'ast> {
                     }
                     write!(f, " = {}", t.value.ty)
                 }
+                SymbolDefinition::StaticAssertion(ref e, ref message) => {
+                    write!(f, "static_assert({}, \"{}\")", e, message)
+                }
+                SymbolDefinition::Trait(ref t) => write!(f, "trait {}{}", self.id, t),
+                SymbolDefinition::Impl(ref i) => write!(f, "impl {}", i),
                 SymbolDefinition::Function(ref func) => {
                     write!(f, "def {}{}", self.id, func)
                 }
@@ -261,6 +306,51 @@ impl<'ast> fmt::Display for StructDefinitionField<'ast> {
 
 type StructDefinitionFieldNode<'ast> = Node<StructDefinitionField<'ast>>;
 
+/// An enum type definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDefinition<'ast> {
+    pub variants: Vec<EnumVariantNode<'ast>>,
+}
+
+impl<'ast> fmt::Display for EnumDefinition<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, " {{")?;
+        for variant in &self.variants {
+            writeln!(f, "  {},", variant)?;
+        }
+        write!(f, "}}",)
+    }
+}
+
+pub type EnumDefinitionNode<'ast> = Node<EnumDefinition<'ast>>;
+
+/// A variant of an enum type definition, with an optional payload
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant<'ast> {
+    pub id: Identifier<'ast>,
+    pub types: Vec<UnresolvedTypeNode<'ast>>,
+}
+
+impl<'ast> fmt::Display for EnumVariant<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if !self.types.is_empty() {
+            write!(
+                f,
+                "({})",
+                self.types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub type EnumVariantNode<'ast> = Node<EnumVariant<'ast>>;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ConstantDefinition<'ast> {
     pub ty: UnresolvedTypeNode<'ast>,
@@ -302,6 +392,64 @@ impl<'ast> fmt::Display for TypeDefinition<'ast> {
     }
 }
 
+/// A trait definition, listing the functions an implementation must provide
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraitDefinition<'ast> {
+    pub functions: Vec<TraitFunctionNode<'ast>>,
+}
+
+pub type TraitDefinitionNode<'ast> = Node<TraitDefinition<'ast>>;
+
+impl<'ast> fmt::Display for TraitDefinition<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, " {{")?;
+        for function in &self.functions {
+            writeln!(f, "  {}", function)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// A function required by a trait
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraitFunction<'ast> {
+    pub id: Identifier<'ast>,
+    // whether the function takes `self`, which makes it callable as a method
+    pub is_method: bool,
+    pub signature: UnresolvedSignature<'ast>,
+}
+
+pub type TraitFunctionNode<'ast> = Node<TraitFunction<'ast>>;
+
+impl<'ast> fmt::Display for TraitFunction<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "def {}{};", self.id, self.signature)
+    }
+}
+
+/// A block of functions declared on a type, implementing a trait if `trait_id` is set
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplDefinition<'ast> {
+    pub trait_id: Option<Identifier<'ast>>,
+    pub ty: UnresolvedTypeNode<'ast>,
+    pub functions: Vec<(Identifier<'ast>, FunctionNode<'ast>)>,
+}
+
+pub type ImplDefinitionNode<'ast> = Node<ImplDefinition<'ast>>;
+
+impl<'ast> fmt::Display for ImplDefinition<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(trait_id) = self.trait_id {
+            write!(f, "{} for ", trait_id)?;
+        }
+        writeln!(f, "{} {{", self.ty)?;
+        for (id, function) in &self.functions {
+            writeln!(f, "  def {}{}", id, function)?;
+        }
+        write!(f, "}}")
+    }
+}
+
 impl<'ast> fmt::Display for Module<'ast> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let res = self
@@ -315,6 +463,21 @@ impl<'ast> fmt::Display for Module<'ast> {
 
 pub type ConstantGenericNode<'ast> = Node<Identifier<'ast>>;
 
+/// A generic parameter standing for a type implementing a trait, such as `H: Hasher`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeGeneric<'ast> {
+    pub id: Identifier<'ast>,
+    pub bound: Identifier<'ast>,
+}
+
+pub type TypeGenericNode<'ast> = Node<TypeGeneric<'ast>>;
+
+impl<'ast> fmt::Display for TypeGeneric<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.id, self.bound)
+    }
+}
+
 /// A function defined locally
 #[derive(Debug, Clone, PartialEq)]
 pub struct Function<'ast> {
@@ -328,16 +491,35 @@ pub struct Function<'ast> {
 
 pub type FunctionNode<'ast> = Node<Function<'ast>>;
 
+impl<'ast> Function<'ast> {
+    /// Whether the function takes `self` as its first parameter, which makes it callable as a method
+    pub fn is_method(&self) -> bool {
+        self.arguments
+            .first()
+            .map(|a| a.value.id.value.id == "self")
+            .unwrap_or(false)
+    }
+
+    /// Whether the function takes functions as parameters, which are resolved for each call
+    pub fn takes_functions(&self) -> bool {
+        self.signature
+            .inputs
+            .iter()
+            .any(|ty| matches!(ty.value, UnresolvedType::Function(..)))
+    }
+}
+
 impl<'ast> fmt::Display for Function<'ast> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        if !self.signature.generics.is_empty() {
+        if !self.signature.type_generics.is_empty() || !self.signature.generics.is_empty() {
             write!(
                 f,
                 "<{}>",
                 self.signature
-                    .generics
+                    .type_generics
                     .iter()
                     .map(|g| g.to_string())
+                    .chain(self.signature.generics.iter().map(|g| g.to_string()))
                     .collect::<Vec<_>>()
                     .join(", ")
             )?;
@@ -416,13 +598,37 @@ pub enum Statement<'ast> {
     Return(Option<ExpressionNode<'ast>>),
     Definition(VariableNode<'ast>, ExpressionNode<'ast>),
     Assignment(AssigneeNode<'ast>, ExpressionNode<'ast>),
+    Destructuring(PatternNode<'ast>, ExpressionNode<'ast>),
     Assertion(ExpressionNode<'ast>, Option<String>),
+    StaticAssertion(ExpressionNode<'ast>, String),
     For(
         VariableNode<'ast>,
         ExpressionNode<'ast>,
         ExpressionNode<'ast>,
         Vec<StatementNode<'ast>>,
     ),
+    ForEach(
+        VariableNode<'ast>,
+        ExpressionNode<'ast>,
+        Vec<StatementNode<'ast>>,
+    ),
+    While(
+        ExpressionNode<'ast>,
+        ExpressionNode<'ast>,
+        Vec<StatementNode<'ast>>,
+    ),
+    If(
+        ExpressionNode<'ast>,
+        Vec<StatementNode<'ast>>,
+        Vec<StatementNode<'ast>>,
+    ),
+    ComptimeIf(
+        ExpressionNode<'ast>,
+        Vec<StatementNode<'ast>>,
+        Vec<StatementNode<'ast>>,
+    ),
+    Break,
+    Continue,
     Log(&'ast str, Vec<ExpressionNode<'ast>>),
     Assembly(Vec<AssemblyStatementNode<'ast>>),
 }
@@ -443,6 +649,7 @@ impl<'ast> fmt::Display for Statement<'ast> {
                 write!(f, "{} = {};", var, rhs)
             }
             Statement::Assignment(ref lhs, ref rhs) => write!(f, "{} = {};", lhs, rhs),
+            Statement::Destructuring(ref pattern, ref rhs) => write!(f, "{} = {};", pattern, rhs),
             Statement::Assertion(ref e, ref message) => {
                 write!(f, "assert({}", e)?;
                 match message {
@@ -450,6 +657,9 @@ impl<'ast> fmt::Display for Statement<'ast> {
                     None => write!(f, ");"),
                 }
             }
+            Statement::StaticAssertion(ref e, ref message) => {
+                write!(f, "static_assert({}, \"{}\");", e, message)
+            }
             Statement::For(ref var, ref start, ref stop, ref list) => {
                 writeln!(f, "for {} in {}..{} {{", var, start, stop)?;
                 for l in list {
@@ -457,6 +667,41 @@ impl<'ast> fmt::Display for Statement<'ast> {
                 }
                 write!(f, "\t}}")
             }
+            Statement::ForEach(ref var, ref array, ref list) => {
+                writeln!(f, "for {} in {} {{", var, array)?;
+                for l in list {
+                    writeln!(f, "\t\t{}", l)?;
+                }
+                write!(f, "\t}}")
+            }
+            Statement::While(ref condition, ref bound, ref list) => {
+                writeln!(f, "while {} bounded {} {{", condition, bound)?;
+                for l in list {
+                    writeln!(f, "\t\t{}", l)?;
+                }
+                write!(f, "\t}}")
+            }
+            Statement::If(ref condition, ref consequence, ref alternative)
+            | Statement::ComptimeIf(ref condition, ref consequence, ref alternative) => {
+                if let Statement::ComptimeIf(..) = self {
+                    write!(f, "comptime ")?;
+                }
+                writeln!(f, "if {} {{", condition)?;
+                for c in consequence {
+                    writeln!(f, "\t\t{}", c)?;
+                }
+                write!(f, "\t}}")?;
+                if !alternative.is_empty() {
+                    writeln!(f, " else {{")?;
+                    for a in alternative {
+                        writeln!(f, "\t\t{}", a)?;
+                    }
+                    write!(f, "\t}}")?;
+                }
+                Ok(())
+            }
+            Statement::Break => write!(f, "break;"),
+            Statement::Continue => write!(f, "continue;"),
             Statement::Log(ref l, ref expressions) => write!(
                 f,
                 "log({}, {});",
@@ -478,6 +723,58 @@ impl<'ast> fmt::Display for Statement<'ast> {
     }
 }
 
+/// A pattern on the left-hand side of a destructuring statement
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern<'ast> {
+    Wildcard,
+    /// A variable with an explicit type, such as `field a`
+    Variable(VariableNode<'ast>),
+    /// A variable whose type is inferred from the destructured value, such as `x` or `mut x`
+    Binding(Identifier<'ast>, bool),
+    Tuple(Vec<PatternNode<'ast>>),
+    Struct(UserTypeId, Vec<(Identifier<'ast>, PatternNode<'ast>)>),
+}
+
+pub type PatternNode<'ast> = Node<Pattern<'ast>>;
+
+impl<'ast> fmt::Display for Pattern<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Variable(ref v) => write!(f, "{}", v),
+            Pattern::Binding(id, is_mutable) => {
+                write!(f, "{}{}", if is_mutable { "mut " } else { "" }, id)
+            }
+            Pattern::Tuple(ref elements) => {
+                write!(f, "(")?;
+                match elements.len() {
+                    1 => write!(f, "{},", elements[0]),
+                    _ => write!(
+                        f,
+                        "{}",
+                        elements
+                            .iter()
+                            .map(|e| e.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                }?;
+                write!(f, ")")
+            }
+            Pattern::Struct(ref ty, ref fields) => write!(
+                f,
+                "{} {{ {} }}",
+                ty,
+                fields
+                    .iter()
+                    .map(|(id, p)| format!("{}: {}", id, p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
 /// An element of an inline array, can be a spread `...a` or an expression `a`
 #[derive(Debug, Clone, PartialEq)]
 pub enum SpreadOrExpression<'ast> {
@@ -599,6 +896,40 @@ impl<'ast> fmt::Display for ConditionalExpression<'ast> {
     }
 }
 
+/// A pattern in an arm of a `match` expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchPattern<'ast> {
+    Wildcard,
+    Variant(UserTypeId, Identifier<'ast>, Vec<Identifier<'ast>>),
+}
+
+impl<'ast> fmt::Display for MatchPattern<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatchPattern::Wildcard => write!(f, "_"),
+            MatchPattern::Variant(ty, variant, bindings) => {
+                write!(f, "{}::{}", ty, variant)?;
+                if !bindings.is_empty() {
+                    write!(f, "({})", bindings.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm<'ast> {
+    pub pattern: MatchPattern<'ast>,
+    pub expression: ExpressionNode<'ast>,
+}
+
+impl<'ast> fmt::Display for MatchArm<'ast> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} => {}", self.pattern, self.expression)
+    }
+}
+
 /// An expression
 #[derive(Debug, Clone, PartialEq)]
 pub enum Expression<'ast> {
@@ -609,6 +940,11 @@ pub enum Expression<'ast> {
     U16Constant(u16),
     U32Constant(u32),
     U64Constant(u64),
+    U128Constant(u128),
+    I8Constant(BigUint),
+    I16Constant(BigUint),
+    I32Constant(BigUint),
+    I64Constant(BigUint),
     Identifier(Identifier<'ast>),
     Add(Box<ExpressionNode<'ast>>, Box<ExpressionNode<'ast>>),
     Sub(Box<ExpressionNode<'ast>>, Box<ExpressionNode<'ast>>),
@@ -632,9 +968,13 @@ pub enum Expression<'ast> {
     And(Box<ExpressionNode<'ast>>, Box<ExpressionNode<'ast>>),
     Not(Box<ExpressionNode<'ast>>),
     InlineArray(Vec<SpreadOrExpression<'ast>>),
+    ByteString(ByteString),
     ArrayInitializer(Box<ExpressionNode<'ast>>, Box<ExpressionNode<'ast>>),
     InlineStruct(UserTypeId, Vec<(Identifier<'ast>, ExpressionNode<'ast>)>),
     InlineTuple(Vec<ExpressionNode<'ast>>),
+    EnumVariant(UserTypeId, Identifier<'ast>, Vec<ExpressionNode<'ast>>),
+    Match(Box<ExpressionNode<'ast>>, Vec<MatchArm<'ast>>),
+    Lambda(Box<FunctionNode<'ast>>),
     Select(Box<ExpressionNode<'ast>>, Box<RangeOrExpression<'ast>>),
     Member(Box<ExpressionNode<'ast>>, Box<Identifier<'ast>>),
     Element(Box<ExpressionNode<'ast>>, u32),
@@ -656,6 +996,11 @@ impl<'ast> fmt::Display for Expression<'ast> {
             Expression::U16Constant(ref i) => write!(f, "{}", i),
             Expression::U32Constant(ref i) => write!(f, "{}", i),
             Expression::U64Constant(ref i) => write!(f, "{}", i),
+            Expression::U128Constant(ref i) => write!(f, "{}", i),
+            Expression::I8Constant(ref i) => write!(f, "{}", i),
+            Expression::I16Constant(ref i) => write!(f, "{}", i),
+            Expression::I32Constant(ref i) => write!(f, "{}", i),
+            Expression::I64Constant(ref i) => write!(f, "{}", i),
             Expression::IntConstant(ref i) => write!(f, "{}", i),
             Expression::Identifier(ref var) => write!(f, "{}", var),
             Expression::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
@@ -725,6 +1070,7 @@ impl<'ast> fmt::Display for Expression<'ast> {
                 write!(f, ")")
             }
             Expression::ArrayInitializer(ref e, ref count) => write!(f, "[{}; {}]", e, count),
+            Expression::ByteString(ref bytes) => write!(f, "{}", bytes),
             Expression::InlineStruct(ref id, ref members) => {
                 write!(f, "{} {{", id)?;
                 for (i, (member_id, e)) in members.iter().enumerate() {
@@ -735,6 +1081,47 @@ impl<'ast> fmt::Display for Expression<'ast> {
                 }
                 write!(f, "}}")
             }
+            Expression::EnumVariant(ref id, ref variant, ref arguments) => {
+                write!(f, "{}::{}", id, variant)?;
+                if !arguments.is_empty() {
+                    write!(
+                        f,
+                        "({})",
+                        arguments
+                            .iter()
+                            .map(|a| a.to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )?;
+                }
+                Ok(())
+            }
+            Expression::Match(ref e, ref arms) => {
+                writeln!(f, "match {} {{", e)?;
+                for arm in arms {
+                    writeln!(f, "\t{},", arm)?;
+                }
+                write!(f, "}}")
+            }
+            Expression::Lambda(ref function) => write!(
+                f,
+                "({}) -> {} {{\n{}\n}}",
+                function
+                    .value
+                    .arguments
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                function.value.signature.output.as_ref().unwrap(),
+                function
+                    .value
+                    .statements
+                    .iter()
+                    .map(|s| format!("\t{}", s))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
             Expression::Select(ref array, ref index) => write!(f, "{}[{}]", array, index),
             Expression::Member(ref struc, ref id) => write!(f, "{}.{}", struc, id),
             Expression::Element(ref tuple, ref id) => write!(f, "{}.{}", tuple, id),
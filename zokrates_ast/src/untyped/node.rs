@@ -84,17 +84,25 @@ use super::*;
 impl<'ast> NodeValue for Expression<'ast> {}
 impl<'ast> NodeValue for Assignee<'ast> {}
 impl<'ast> NodeValue for Statement<'ast> {}
+impl<'ast> NodeValue for Pattern<'ast> {}
 impl<'ast> NodeValue for AssemblyStatement<'ast> {}
 impl<'ast> NodeValue for SymbolDeclaration<'ast> {}
 impl<'ast> NodeValue for UnresolvedType<'ast> {}
 impl<'ast> NodeValue for StructDefinition<'ast> {}
 impl<'ast> NodeValue for StructDefinitionField<'ast> {}
+impl<'ast> NodeValue for EnumDefinition<'ast> {}
+impl<'ast> NodeValue for EnumVariant<'ast> {}
 impl<'ast> NodeValue for ConstantDefinition<'ast> {}
 impl<'ast> NodeValue for TypeDefinition<'ast> {}
+impl<'ast> NodeValue for TraitDefinition<'ast> {}
+impl<'ast> NodeValue for TraitFunction<'ast> {}
+impl<'ast> NodeValue for ImplDefinition<'ast> {}
+impl<'ast> NodeValue for TypeGeneric<'ast> {}
 impl<'ast> NodeValue for Function<'ast> {}
 impl<'ast> NodeValue for Module<'ast> {}
 impl<'ast> NodeValue for CanonicalImport<'ast> {}
 impl<'ast> NodeValue for SymbolImport<'ast> {}
+impl<'ast> NodeValue for ModuleImport<'ast> {}
 impl<'ast> NodeValue for Variable<'ast> {}
 impl<'ast> NodeValue for Parameter<'ast> {}
 impl<'ast> NodeValue for Spread<'ast> {}
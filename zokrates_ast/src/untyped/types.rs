@@ -11,9 +11,12 @@ pub enum UnresolvedType<'ast> {
     FieldElement,
     Boolean,
     Uint(usize),
+    Int(usize),
     Array(Box<UnresolvedTypeNode<'ast>>, ExpressionNode<'ast>),
+    BoundedArray(Box<UnresolvedTypeNode<'ast>>, ExpressionNode<'ast>),
     User(UserTypeId, Option<Vec<Option<ExpressionNode<'ast>>>>),
     Tuple(Vec<UnresolvedTypeNode<'ast>>),
+    Function(Vec<UnresolvedTypeNode<'ast>>, Box<UnresolvedTypeNode<'ast>>),
 }
 
 impl<'ast> fmt::Display for UnresolvedType<'ast> {
@@ -22,7 +25,11 @@ impl<'ast> fmt::Display for UnresolvedType<'ast> {
             UnresolvedType::FieldElement => write!(f, "field"),
             UnresolvedType::Boolean => write!(f, "bool"),
             UnresolvedType::Uint(bitwidth) => write!(f, "u{}", bitwidth),
+            UnresolvedType::Int(bitwidth) => write!(f, "i{}", bitwidth),
             UnresolvedType::Array(ref ty, ref size) => write!(f, "{}[{}]", ty, size),
+            UnresolvedType::BoundedArray(ref ty, ref capacity) => {
+                write!(f, "{}[..{}]", ty, capacity)
+            }
             UnresolvedType::Tuple(ref elements) => {
                 write!(f, "(")?;
                 match elements.len() {
@@ -39,6 +46,16 @@ impl<'ast> fmt::Display for UnresolvedType<'ast> {
                 }?;
                 write!(f, ")")
             }
+            UnresolvedType::Function(ref inputs, ref output) => write!(
+                f,
+                "({}) -> {}",
+                inputs
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                output
+            ),
             UnresolvedType::User(ref id, ref generics) => {
                 write!(
                     f,
@@ -71,18 +88,23 @@ impl<'ast> UnresolvedType<'ast> {
     pub fn array(ty: UnresolvedTypeNode<'ast>, size: ExpressionNode<'ast>) -> Self {
         UnresolvedType::Array(box ty, size)
     }
+
+    pub fn bounded_array(ty: UnresolvedTypeNode<'ast>, capacity: ExpressionNode<'ast>) -> Self {
+        UnresolvedType::BoundedArray(box ty, capacity)
+    }
 }
 
 pub use self::signature::UnresolvedSignature;
 
 mod signature {
-    use crate::untyped::ConstantGenericNode;
+    use crate::untyped::{ConstantGenericNode, TypeGenericNode};
     use std::fmt;
 
     use crate::untyped::UnresolvedTypeNode;
 
     #[derive(Clone, PartialEq, Default)]
     pub struct UnresolvedSignature<'ast> {
+        pub type_generics: Vec<TypeGenericNode<'ast>>,
         pub generics: Vec<ConstantGenericNode<'ast>>,
         pub inputs: Vec<UnresolvedTypeNode<'ast>>,
         pub output: Option<UnresolvedTypeNode<'ast>>,
@@ -119,6 +141,11 @@ mod signature {
             UnresolvedSignature::default()
         }
 
+        pub fn type_generics(mut self, type_generics: Vec<TypeGenericNode<'ast>>) -> Self {
+            self.type_generics = type_generics;
+            self
+        }
+
         pub fn generics(mut self, generics: Vec<ConstantGenericNode<'ast>>) -> Self {
             self.generics = generics;
             self
@@ -174,17 +174,20 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
         ZirStatement::Definition(a, e) => {
             ZirStatement::Definition(f.fold_assignee(a), f.fold_expression(e))
         }
-        ZirStatement::IfElse(condition, consequence, alternative) => ZirStatement::IfElse(
-            f.fold_boolean_expression(condition),
-            consequence
-                .into_iter()
-                .flat_map(|e| f.fold_statement(e))
-                .collect(),
-            alternative
-                .into_iter()
-                .flat_map(|e| f.fold_statement(e))
-                .collect(),
-        ),
+        ZirStatement::IfElse(condition, consequence, alternative, isolated) => {
+            ZirStatement::IfElse(
+                f.fold_boolean_expression(condition),
+                consequence
+                    .into_iter()
+                    .flat_map(|e| f.fold_statement(e))
+                    .collect(),
+                alternative
+                    .into_iter()
+                    .flat_map(|e| f.fold_statement(e))
+                    .collect(),
+                isolated,
+            )
+        }
         ZirStatement::Assertion(e, error) => {
             ZirStatement::Assertion(f.fold_boolean_expression(e), error)
         }
@@ -204,6 +207,7 @@ pub fn fold_statement<'ast, T: Field, F: Folder<'ast, T>>(
                 .flat_map(|s| f.fold_assembly_statement(s))
                 .collect(),
         ),
+        ZirStatement::SourceMetadata(metadata) => ZirStatement::SourceMetadata(metadata),
     };
     vec![res]
 }
@@ -29,5 +29,20 @@ fn from_type(t: typed_absy::types::ConcreteType) -> Vec<zir::types::Type> {
         typed_absy::types::ConcreteType::Tuple(tuple_ty) => {
             tuple_ty.elements.into_iter().flat_map(from_type).collect()
         }
+        typed_absy::types::ConcreteType::Enum(enum_ty) => std::iter::once(zir::Type::uint(32))
+            .chain(
+                enum_ty
+                    .layout()
+                    .0
+                    .into_iter()
+                    .flat_map(|slot| from_type(slot.clone())),
+            )
+            .collect(),
+        typed_absy::types::ConcreteType::BoundedArray(array_type) => {
+            let inner = from_type(*array_type.ty);
+            std::iter::once(zir::Type::uint(32))
+                .chain((0..*array_type.size).flat_map(|_| inner.clone()))
+                .collect()
+        }
     }
 }
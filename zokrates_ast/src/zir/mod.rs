@@ -98,6 +98,10 @@ pub enum RuntimeError {
     SelectRangeCheck,
     DivisionByZero,
     IncompleteDynamicRange,
+    EnumArgument,
+    BoundedArrayAccess,
+    BoundedArrayCapacity,
+    BoundedArrayArgument,
 }
 
 impl fmt::Display for RuntimeError {
@@ -107,6 +111,10 @@ impl fmt::Display for RuntimeError {
             RuntimeError::SelectRangeCheck => write!(f, "Range check on array access"),
             RuntimeError::DivisionByZero => write!(f, "Division by zero"),
             RuntimeError::IncompleteDynamicRange => write!(f, "Dynamic comparison is incomplete"),
+            RuntimeError::EnumArgument => write!(f, "Enum argument check"),
+            RuntimeError::BoundedArrayAccess => write!(f, "Range check on bounded array access"),
+            RuntimeError::BoundedArrayCapacity => write!(f, "Bounded array capacity check"),
+            RuntimeError::BoundedArrayArgument => write!(f, "Bounded array argument check"),
         }
     }
 }
@@ -156,10 +164,12 @@ impl<'ast, T: fmt::Display> fmt::Display for ZirAssemblyStatement<'ast, T> {
 pub enum ZirStatement<'ast, T> {
     Return(Vec<ZirExpression<'ast, T>>),
     Definition(ZirAssignee<'ast>, ZirExpression<'ast, T>),
+    /// The branches are isolated if branch isolation is enabled, or if the last field is set
     IfElse(
         BooleanExpression<'ast, T>,
         Vec<ZirStatement<'ast, T>>,
         Vec<ZirStatement<'ast, T>>,
+        bool,
     ),
     Assertion(BooleanExpression<'ast, T>, RuntimeError),
     MultipleDefinition(Vec<ZirAssignee<'ast>>, ZirExpressionList<'ast, T>),
@@ -169,6 +179,8 @@ pub enum ZirStatement<'ast, T> {
     ),
     #[serde(borrow)]
     Assembly(Vec<ZirAssemblyStatement<'ast, T>>),
+    /// The position in the source of the statements which follow, if known
+    SourceMetadata(Option<SourceMetadata>),
 }
 
 impl<'ast, T: fmt::Display> fmt::Display for ZirStatement<'ast, T> {
@@ -199,7 +211,7 @@ impl<'ast, T: fmt::Display> ZirStatement<'ast, T> {
             ZirStatement::Definition(ref lhs, ref rhs) => {
                 write!(f, "{} = {};", lhs, rhs)
             }
-            ZirStatement::IfElse(ref condition, ref consequence, ref alternative) => {
+            ZirStatement::IfElse(ref condition, ref consequence, ref alternative, _) => {
                 writeln!(f, "if {} {{", condition)?;
                 for s in consequence {
                     s.fmt_indented(f, depth + 1)?;
@@ -251,6 +263,10 @@ impl<'ast, T: fmt::Display> ZirStatement<'ast, T> {
                 }
                 write!(f, "{}}}", "\t".repeat(depth))
             }
+            ZirStatement::SourceMetadata(Some(ref metadata)) => {
+                write!(f, "// {}", metadata.location())
+            }
+            ZirStatement::SourceMetadata(None) => write!(f, "// unknown position"),
         }
     }
 }
@@ -194,23 +194,26 @@ pub fn fold_statement<'ast, T: Field, F: ResultFolder<'ast, T>>(
         ZirStatement::Definition(a, e) => {
             ZirStatement::Definition(f.fold_assignee(a)?, f.fold_expression(e)?)
         }
-        ZirStatement::IfElse(condition, consequence, alternative) => ZirStatement::IfElse(
-            f.fold_boolean_expression(condition)?,
-            consequence
-                .into_iter()
-                .map(|s| f.fold_statement(s))
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten()
-                .collect(),
-            alternative
-                .into_iter()
-                .map(|s| f.fold_statement(s))
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten()
-                .collect(),
-        ),
+        ZirStatement::IfElse(condition, consequence, alternative, isolated) => {
+            ZirStatement::IfElse(
+                f.fold_boolean_expression(condition)?,
+                consequence
+                    .into_iter()
+                    .map(|s| f.fold_statement(s))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                alternative
+                    .into_iter()
+                    .map(|s| f.fold_statement(s))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect(),
+                isolated,
+            )
+        }
         ZirStatement::Assertion(e, error) => {
             ZirStatement::Assertion(f.fold_boolean_expression(e)?, error)
         }
@@ -244,6 +247,7 @@ pub fn fold_statement<'ast, T: Field, F: ResultFolder<'ast, T>>(
                 .collect();
             ZirStatement::Assembly(statements)
         }
+        ZirStatement::SourceMetadata(metadata) => ZirStatement::SourceMetadata(metadata),
     };
     Ok(vec![res])
 }
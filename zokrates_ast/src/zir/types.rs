@@ -20,6 +20,8 @@ pub enum UBitwidth {
     B32 = 32,
     #[serde(rename = "64")]
     B64 = 64,
+    #[serde(rename = "128")]
+    B128 = 128,
 }
 
 impl UBitwidth {
@@ -29,8 +31,14 @@ impl UBitwidth {
             UBitwidth::B16 => 16,
             UBitwidth::B32 => 32,
             UBitwidth::B64 => 64,
+            UBitwidth::B128 => 128,
         }
     }
+
+    /// The largest value representable on this bitwidth, `2**bitwidth - 1`
+    pub fn max_value(self) -> u128 {
+        u128::MAX >> (128 - self.to_usize())
+    }
 }
 
 impl From<usize> for UBitwidth {
@@ -40,6 +48,7 @@ impl From<usize> for UBitwidth {
             16 => UBitwidth::B16,
             32 => UBitwidth::B32,
             64 => UBitwidth::B64,
+            128 => UBitwidth::B128,
             _ => unreachable!(),
         }
     }
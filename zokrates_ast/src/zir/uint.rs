@@ -136,6 +136,14 @@ impl ShouldReduce {
     }
 }
 
+impl UBitwidth {
+    /// Whether the product of two values of this bitwidth always fits in the field `T`.
+    /// If it does not, multiplication is done on limbs of half the bitwidth.
+    pub fn fits_product<T: Field>(self) -> bool {
+        self.to_usize() < (T::get_required_bits() - 1) / 2
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct UMetadata<T> {
     pub max: T,
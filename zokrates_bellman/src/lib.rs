@@ -126,7 +126,7 @@ impl<'a, T: BellmanFieldExtensions + Field, I: IntoIterator<Item = Statement<'a,
         }));
 
         for statement in self.program.statements {
-            if let Statement::Constraint(quad, lin, _) = statement {
+            if let Statement::Constraint(quad, lin, ..) = statement {
                 let a = &bellman_combination(
                     quad.left.into_canonical(),
                     cs,
@@ -37,11 +37,13 @@ mod tests {
                     ),
                     LinComb::from(Variable::new(0)),
                     None,
+                    None,
                 ),
                 Statement::Constraint(
                     (LinComb::from(Variable::new(0)) + LinComb::from(Variable::new(1))).into(),
                     Variable::public(0).into(),
                     None,
+                    None,
                 ),
             ],
         };
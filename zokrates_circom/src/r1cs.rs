@@ -70,7 +70,7 @@ pub fn r1cs_program<T: Field>(prog: Prog<T>) -> (Vec<Variable>, usize, Vec<Const
 
     // first pass through statements to populate `variables`
     for (quad, lin) in prog.statements.iter().filter_map(|s| match s {
-        Statement::Constraint(quad, lin, _) => Some((quad, lin)),
+        Statement::Constraint(quad, lin, ..) => Some((quad, lin)),
         Statement::Directive(..) => None,
         Statement::Block(..) => unreachable!(),
         Statement::Log(..) => None,
@@ -95,7 +95,7 @@ pub fn r1cs_program<T: Field>(prog: Prog<T>) -> (Vec<Variable>, usize, Vec<Const
 
     // second pass to convert program to raw sparse vectors
     for (quad, lin) in prog.statements.into_iter().filter_map(|s| match s {
-        Statement::Constraint(quad, lin, _) => Some((quad, lin)),
+        Statement::Constraint(quad, lin, ..) => Some((quad, lin)),
         Statement::Block(..) => unreachable!(),
         Statement::Directive(..) => None,
         Statement::Log(..) => None,
@@ -295,6 +295,7 @@ mod tests {
                 LinComb::one().into(),
                 Variable::public(0).into(),
                 None,
+                None,
             )],
         };
 
@@ -358,11 +359,13 @@ mod tests {
                     ),
                     LinComb::from(Variable::new(0)),
                     None,
+                    None,
                 ),
                 Statement::Constraint(
                     (LinComb::from(Variable::new(0)) + LinComb::from(Variable::new(1))).into(),
                     Variable::public(0).into(),
                     None,
+                    None,
                 ),
             ],
         };
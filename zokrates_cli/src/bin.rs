@@ -56,6 +56,7 @@ fn cli() -> Result<(), String> {
             generate_proof::subcommand(),
             generate_smtlib2::subcommand(),
             print_proof::subcommand(),
+            test::subcommand(),
             #[cfg(any(feature = "bellman", feature = "ark"))]
             verify::subcommand()])
         .get_matches();
@@ -76,6 +77,7 @@ fn cli() -> Result<(), String> {
         ("generate-proof", Some(sub_matches)) => generate_proof::exec(sub_matches),
         ("generate-smtlib2", Some(sub_matches)) => generate_smtlib2::exec(sub_matches),
         ("print-proof", Some(sub_matches)) => print_proof::exec(sub_matches),
+        ("test", Some(sub_matches)) => test::exec(sub_matches),
         #[cfg(any(feature = "bellman", feature = "ark"))]
         ("verify", Some(sub_matches)) => verify::exec(sub_matches),
         _ => unreachable!(),
@@ -122,7 +124,7 @@ mod tests {
     use std::string::String;
     use typed_arena::Arena;
     use zokrates_common::CompileConfig;
-    use zokrates_core::compile::{compile, CompilationArtifacts};
+    use zokrates_core::compile::{compile, find_tests, CompilationArtifacts};
     use zokrates_field::Bn128Field;
     use zokrates_fs_resolver::FileSystemResolver;
 
@@ -264,4 +266,46 @@ mod tests {
             assert!(res.is_err());
         }
     }
+
+    #[test]
+    fn execute_unit_tests() {
+        //the tests declared in these examples should pass
+        for p in glob("./examples/unit_tests/*").expect("Failed to read glob pattern") {
+            let path = match p {
+                Ok(x) => x,
+                Err(why) => panic!("Error: {:?}", why),
+            };
+
+            println!("Testing {:?}", path);
+            assert_eq!(path.extension().expect("extension expected"), "zok");
+
+            let file = File::open(path.clone()).unwrap();
+
+            let mut reader = BufReader::new(file);
+            let mut source = String::new();
+            reader.read_to_string(&mut source).unwrap();
+
+            let stdlib = std::fs::canonicalize("../zokrates_stdlib/stdlib").unwrap();
+            let resolver = FileSystemResolver::with_stdlib_root(stdlib.to_str().unwrap());
+
+            let tests = find_tests::<Bn128Field>(&source, &path).unwrap();
+            assert!(!tests.is_empty());
+
+            for test in tests {
+                println!("Running {}", test.id);
+
+                let outcome = crate::ops::test::execute_test::<Bn128Field>(
+                    source.clone(),
+                    path.clone(),
+                    &test,
+                    &resolver,
+                    CompileConfig::default(),
+                )
+                .map_err(|e| format!("{:?}", e))
+                .and_then(|execution| crate::ops::test::check_outcome(&test, execution));
+
+                assert!(outcome.is_ok(), "{}", outcome.unwrap_err());
+            }
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use zokrates_ast::common::Lint;
 use zokrates_common::constants::*;
 
 pub const FLATTENED_CODE_DEFAULT_PATH: &str = "out";
@@ -21,6 +22,9 @@ lazy_static! {
         .into_os_string()
         .into_string()
         .unwrap();
+    pub static ref MAX_RECURSION_DEPTH_DEFAULT: String = DEFAULT_MAX_RECURSION_DEPTH.to_string();
+    pub static ref OPT_LEVEL_DEFAULT: String = DEFAULT_OPT_LEVEL.to_string();
+    pub static ref LINTS: Vec<&'static str> = Lint::ALL.iter().map(Lint::name).collect();
 }
 
 #[cfg(any(feature = "bellman", feature = "ark"))]
@@ -36,6 +40,8 @@ pub const BACKENDS: &[&str] = if cfg!(feature = "ark") {
     &[]
 };
 
+pub const OPT_LEVELS: &[&str] = &["0", "1", "2"];
+
 pub const CURVES: &[&str] = &[BN128, BLS12_381, BLS12_377, BW6_761];
 
 pub const SCHEMES: &[&str] = &[G16, GM17, MARLIN];
@@ -1,4 +1,5 @@
 use crate::cli_constants;
+use crate::ops::warnings;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use std::convert::TryFrom;
 use std::fs::File;
@@ -47,6 +48,16 @@ pub fn subcommand() -> App<'static, 'static> {
             .help("Isolate the execution of branches: a panic in a branch only makes the program panic if this branch is being logically executed")
             .required(false)
         )
+        .arg(
+            Arg::with_name("max-recursion-depth")
+                .long("max-recursion-depth")
+                .help("Maximum number of nested calls of a recursive function")
+                .value_name("DEPTH")
+                .takes_value(true)
+                .required(false)
+                .default_value(cli_constants::MAX_RECURSION_DEPTH_DEFAULT.as_str()),
+        )
+        .args(&warnings::args())
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -90,11 +101,17 @@ fn cli_check<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
         )),
     }?;
 
-    let config =
-        CompileConfig::default().isolate_branches(sub_matches.is_present("isolate-branches"));
+    let max_recursion_depth = sub_matches.value_of("max-recursion-depth").unwrap();
+    let max_recursion_depth = max_recursion_depth
+        .parse::<usize>()
+        .map_err(|_| format!("Maximum recursion depth {} is invalid", max_recursion_depth))?;
+
+    let config = CompileConfig::default()
+        .isolate_branches(sub_matches.is_present("isolate-branches"))
+        .max_recursion_depth(max_recursion_depth);
 
     let resolver = FileSystemResolver::with_stdlib_root(stdlib_path);
-    check::<T, _>(source, path, Some(&resolver), &config).map_err(|e| {
+    let warnings = check::<T, _>(source, path, Some(&resolver), &config).map_err(|e| {
         format!(
             "Check failed:\n\n{}",
             e.0.iter()
@@ -104,6 +121,13 @@ fn cli_check<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
         )
     })?;
 
+    warnings::report(&warnings, sub_matches, stdlib_path).map_err(|count| {
+        format!(
+            "Check failed: {} warning(s) found and warnings are denied",
+            count
+        )
+    })?;
+
     println!("Program checked, no errors found.");
 
     Ok(())
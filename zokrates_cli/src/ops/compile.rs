@@ -1,4 +1,5 @@
 use crate::cli_constants;
+use crate::ops::warnings;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use serde_json::to_writer_pretty;
 use std::convert::TryFrom;
@@ -71,7 +72,31 @@ pub fn subcommand() -> App<'static, 'static> {
         .long("debug")
         .help("Include logs")
         .required(false)
-)
+    ).arg(Arg::with_name("source-map")
+        .long("source-map")
+        .help("Keep the position in the source of the compiled constraints")
+        .required(false)
+    ).arg(Arg::with_name("max-recursion-depth")
+        .long("max-recursion-depth")
+        .help("Maximum number of nested calls of a recursive function")
+        .value_name("DEPTH")
+        .takes_value(true)
+        .required(false)
+        .default_value(cli_constants::MAX_RECURSION_DEPTH_DEFAULT.as_str())
+    ).arg(Arg::with_name("opt-level")
+        .short("O")
+        .long("opt-level")
+        .help("Optimization level: 0 to skip optimization, 1 for a single pass, 2 for passes until no constraint can be removed and linear constraint elimination")
+        .value_name("LEVEL")
+        .takes_value(true)
+        .required(false)
+        .possible_values(cli_constants::OPT_LEVELS)
+        .default_value(cli_constants::OPT_LEVEL_DEFAULT.as_str())
+    ).arg(Arg::with_name("eliminate-linear-constraints")
+        .long("eliminate-linear-constraints")
+        .help("Eliminate linear constraints by substituting them into the other constraints")
+        .required(false)
+    ).args(&warnings::args())
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -120,9 +145,23 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
         )),
     }?;
 
+    let max_recursion_depth = sub_matches.value_of("max-recursion-depth").unwrap();
+    let max_recursion_depth = max_recursion_depth
+        .parse::<usize>()
+        .map_err(|_| format!("Maximum recursion depth {} is invalid", max_recursion_depth))?;
+
+    let opt_level = sub_matches.value_of("opt-level").unwrap();
+    let opt_level = opt_level
+        .parse::<u8>()
+        .map_err(|_| format!("Optimization level {} is invalid", opt_level))?;
+
     let config = CompileConfig::default()
         .isolate_branches(sub_matches.is_present("isolate-branches"))
-        .debug(sub_matches.is_present("debug"));
+        .debug(sub_matches.is_present("debug"))
+        .max_recursion_depth(max_recursion_depth)
+        .eliminate_linear_constraints(sub_matches.is_present("eliminate-linear-constraints"))
+        .opt_level(opt_level)?
+        .source_map(sub_matches.is_present("source-map"));
 
     let resolver = FileSystemResolver::with_stdlib_root(stdlib_path);
 
@@ -141,6 +180,15 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
             )
         })?;
 
+    warnings::report(artifacts.warnings(), sub_matches, stdlib_path).map_err(|count| {
+        format!(
+            "Compilation failed: {} warning(s) found and warnings are denied",
+            count
+        )
+    })?;
+
+    let eliminated_constraints = artifacts.eliminated_constraints();
+
     let (program_flattened, abi) = artifacts.into_inner();
 
     // serialize flattened program and write to binary file
@@ -158,7 +206,12 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
 
     write_r1cs(&mut r1cs_writer, program_flattened.clone()).unwrap();
 
-    match program_flattened.serialize(&mut bin_writer) {
+    let serialized = match config.source_map {
+        true => program_flattened.serialize_with_source_map(&mut bin_writer),
+        false => program_flattened.serialize(&mut bin_writer),
+    };
+
+    match serialized {
         Ok(constraint_count) => {
             // serialize ABI spec and write to JSON file
             log::debug!("Serialize ABI");
@@ -173,6 +226,13 @@ fn cli_compile<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
 
             println!("Number of constraints: {}", constraint_count);
 
+            if let Some(eliminated_constraints) = eliminated_constraints {
+                println!(
+                    "Number of linear constraints eliminated: {}",
+                    eliminated_constraints
+                );
+            }
+
             Ok(())
         }
         Err(e) => {
@@ -11,7 +11,9 @@ pub mod mpc;
 pub mod print_proof;
 #[cfg(any(feature = "bellman", feature = "ark"))]
 pub mod setup;
+pub mod test;
 #[cfg(feature = "ark")]
 pub mod universal_setup;
 #[cfg(any(feature = "bellman", feature = "ark"))]
 pub mod verify;
+pub mod warnings;
@@ -0,0 +1,218 @@
+use crate::cli_constants;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use typed_arena::Arena;
+use zokrates_common::constants::BN128;
+use zokrates_common::{helpers::CurveParameter, CompileConfig};
+use zokrates_core::compile::{compile_test, find_tests, CompileError, CompileErrors, TestFunction};
+use zokrates_field::{Bls12_377Field, Bls12_381Field, Bn128Field, Bw6_761Field, Field};
+use zokrates_fs_resolver::FileSystemResolver;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("test")
+        .about("Runs the functions marked with #[test] in a program")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the source code")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("stdlib-path")
+                .long("stdlib-path")
+                .help("Path to the standard library")
+                .value_name("PATH")
+                .takes_value(true)
+                .required(false)
+                .env("ZOKRATES_STDLIB")
+                .default_value(cli_constants::DEFAULT_STDLIB_PATH.as_str()),
+        )
+        .arg(
+            Arg::with_name("curve")
+                .short("c")
+                .long("curve")
+                .help("Curve to be used in the compilation")
+                .takes_value(true)
+                .required(false)
+                .possible_values(cli_constants::CURVES)
+                .default_value(BN128),
+        )
+        .arg(Arg::with_name("isolate-branches")
+            .long("isolate-branches")
+            .help("Isolate the execution of branches: a panic in a branch only makes the program panic if this branch is being logically executed")
+            .required(false)
+        )
+        .arg(
+            Arg::with_name("max-recursion-depth")
+                .long("max-recursion-depth")
+                .help("Maximum number of nested calls of a recursive function")
+                .value_name("DEPTH")
+                .takes_value(true)
+                .required(false)
+                .default_value(cli_constants::MAX_RECURSION_DEPTH_DEFAULT.as_str()),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    let curve = CurveParameter::try_from(sub_matches.value_of("curve").unwrap())?;
+    match curve {
+        CurveParameter::Bn128 => cli_test::<Bn128Field>(sub_matches),
+        CurveParameter::Bls12_377 => cli_test::<Bls12_377Field>(sub_matches),
+        CurveParameter::Bls12_381 => cli_test::<Bls12_381Field>(sub_matches),
+        CurveParameter::Bw6_761 => cli_test::<Bw6_761Field>(sub_matches),
+    }
+}
+
+fn cli_test<T: Field>(sub_matches: &ArgMatches) -> Result<(), String> {
+    let path = PathBuf::from(sub_matches.value_of("input").unwrap());
+
+    let file = File::open(path.clone())
+        .map_err(|why| format!("Could not open {}: {}", path.display(), why))?;
+
+    let mut reader = BufReader::new(file);
+    let mut source = String::new();
+    reader.read_to_string(&mut source).unwrap();
+
+    let fmt_error = |e: &CompileError| {
+        // errors found when analysing a test are reported in its entry point, which is not a file
+        let file = e.file().canonicalize().unwrap_or_else(|_| e.file().clone());
+        format!(
+            "{}:{}",
+            file.strip_prefix(std::env::current_dir().unwrap())
+                .unwrap_or(file.as_path())
+                .display(),
+            e.value()
+        )
+    };
+
+    let stdlib_path = sub_matches.value_of("stdlib-path").unwrap();
+    match Path::new(stdlib_path).exists() {
+        true => Ok(()),
+        _ => Err(format!(
+            "Invalid standard library source path: {}",
+            stdlib_path
+        )),
+    }?;
+
+    let max_recursion_depth = sub_matches.value_of("max-recursion-depth").unwrap();
+    let max_recursion_depth = max_recursion_depth
+        .parse::<usize>()
+        .map_err(|_| format!("Maximum recursion depth {} is invalid", max_recursion_depth))?;
+
+    let config = CompileConfig::default()
+        .isolate_branches(sub_matches.is_present("isolate-branches"))
+        .max_recursion_depth(max_recursion_depth);
+
+    let resolver = FileSystemResolver::with_stdlib_root(stdlib_path);
+
+    let tests = find_tests::<T>(&source, &path).map_err(|e| {
+        format!(
+            "Compilation failed:\n\n{}",
+            e.0.iter()
+                .map(|e| fmt_error(e))
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        )
+    })?;
+
+    println!("Running {} test(s) in {}\n", tests.len(), path.display());
+
+    let mut failures = vec![];
+
+    for test in &tests {
+        let result = execute_test::<T>(source.clone(), path.clone(), test, &resolver, config)
+            .map_err(|e| {
+                format!(
+                    "Compilation failed:\n\n{}",
+                    e.0.iter()
+                        .map(|e| fmt_error(e))
+                        .collect::<Vec<_>>()
+                        .join("\n\n")
+                )
+            })
+            .and_then(|execution| check_outcome(test, execution));
+
+        match result {
+            Ok(()) => println!("test {} ... ok", test.id),
+            Err(e) => {
+                println!("test {} ... FAILED", test.id);
+                failures.push((&test.id, e));
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\nfailures:");
+        for (id, e) in &failures {
+            println!("\n---- {} ----\n{}", id, e);
+        }
+    }
+
+    println!(
+        "\ntest result: {}. {} passed; {} failed",
+        if failures.is_empty() { "ok" } else { "FAILED" },
+        tests.len() - failures.len(),
+        failures.len()
+    );
+
+    match failures.is_empty() {
+        true => Ok(()),
+        false => Err(format!("{} test(s) failed", failures.len())),
+    }
+}
+
+/// Compile and execute the test `test`, returning the outcome of its execution
+///
+/// Tests take no inputs, so their assertions may be found to fail when compiling them: this is
+/// reported as a failed execution, so that tests marked with `#[should_panic]` can expect it
+pub fn execute_test<T: Field>(
+    source: String,
+    path: PathBuf,
+    test: &TestFunction,
+    resolver: &FileSystemResolver,
+    config: CompileConfig,
+) -> Result<Result<(), String>, CompileErrors> {
+    log::debug!("Compile test {}", test.id);
+
+    let arena = Arena::new();
+
+    let outcome = match compile_test::<T, _>(source, path, &test.id, Some(resolver), config, &arena)
+    {
+        Ok(artifacts) => {
+            log::debug!("Run test {}", test.id);
+
+            let interpreter = zokrates_interpreter::Interpreter::default();
+            let execution = interpreter.execute(artifacts.prog(), &[]);
+
+            Ok(execution.map(|_| ()).map_err(|e| e.to_string()))
+        }
+        Err(e) if e.0.iter().all(|e| e.is_assertion_failure()) => Ok(Err(e
+            .0
+            .iter()
+            .map(|e| e.value().to_string().trim_start().to_string())
+            .collect::<Vec<_>>()
+            .join("\n"))),
+        Err(e) => Err(e),
+    };
+
+    outcome
+}
+
+// a test passes if its execution succeeds, or fails if it is marked with `#[should_panic]`
+pub fn check_outcome(test: &TestFunction, execution: Result<(), String>) -> Result<(), String> {
+    match (execution, test.should_panic) {
+        (Ok(()), false) => Ok(()),
+        (Err(e), false) => Err(format!("Execution failed: {}", e)),
+        (Ok(()), true) => Err("Execution succeeded, but the test was expected to panic".into()),
+        (Err(e), true) => {
+            log::debug!("Test {} panicked as expected: {}", test.id, e);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::cli_constants;
+use clap::{Arg, ArgMatches};
+use std::path::Path;
+use zokrates_ast::common::{Lint, Warning, Warnings};
+
+/// The arguments controlling the warnings reported by a command
+pub fn args() -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name("deny-warnings")
+            .long("deny-warnings")
+            .help("Fail if any warning is found")
+            .required(false),
+        Arg::with_name("allow")
+            .long("allow")
+            .help("Lint whose warnings should not be reported, can be repeated")
+            .value_name("LINT")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .required(false)
+            .possible_values(&cli_constants::LINTS),
+    ]
+}
+
+/// Print the warnings which are not allowed and not in the standard library.
+/// Returns an error holding the number of printed warnings if warnings are denied and some were printed
+pub fn report(
+    warnings: &Warnings,
+    sub_matches: &ArgMatches,
+    stdlib_path: &str,
+) -> Result<(), usize> {
+    // the lints are validated by clap
+    let allowed: Vec<Lint> = sub_matches
+        .values_of("allow")
+        .map(|lints| lints.map(|l| l.parse().unwrap()).collect())
+        .unwrap_or_default();
+
+    // warnings in the standard library are not actionable by the user
+    let stdlib_root = Path::new(stdlib_path).canonicalize().ok();
+    let is_in_stdlib = |file: &Path| match (&stdlib_root, file.canonicalize()) {
+        (Some(root), Ok(file)) => file.starts_with(root),
+        _ => false,
+    };
+
+    let warnings: Vec<_> = warnings
+        .iter()
+        .filter(|w| !allowed.contains(&w.lint))
+        .filter(|w| !is_in_stdlib(&w.file))
+        .collect();
+
+    for w in &warnings {
+        println!("{}\n", fmt_warning(w));
+    }
+
+    match sub_matches.is_present("deny-warnings") && !warnings.is_empty() {
+        true => Err(warnings.len()),
+        false => Ok(()),
+    }
+}
+
+fn fmt_warning(w: &Warning) -> String {
+    let file = w.file.canonicalize().unwrap_or_else(|_| w.file.clone());
+    // paths are shown relative to the working directory when possible, and absolute otherwise
+    let file = std::env::current_dir()
+        .ok()
+        .and_then(|dir| file.strip_prefix(dir).ok().map(Path::to_path_buf))
+        .unwrap_or(file);
+    format!(
+        "{}:{}\n\twarning[{}]: {}",
+        file.display(),
+        w.position,
+        w.lint,
+        w.message
+    )
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use zokrates_ast::flat::*;
+use zokrates_ast::ir::{CanonicalLinComb, LinComb};
+use zokrates_field::Field;
+
+/// Bit decompositions of linear combinations, to avoid re-generating them.
+///
+/// Linear combinations are compared in their canonical form, so that `a + 1` and `1 + a` share their decomposition.
+/// When branches are isolated, the constraints of a branch only hold if it is taken, so the decompositions found in a
+/// branch are kept in a separate scope which is dropped when leaving it.
+#[derive(Debug)]
+pub struct BitsCache<T> {
+    scopes: Vec<HashMap<CanonicalLinComb<T>, Vec<FlatExpression<T>>>>,
+}
+
+impl<T> Default for BitsCache<T> {
+    fn default() -> Self {
+        BitsCache {
+            scopes: vec![HashMap::new()],
+        }
+    }
+}
+
+impl<T: Field> BitsCache<T> {
+    fn key(e: &FlatExpression<T>) -> Option<CanonicalLinComb<T>> {
+        e.is_linear()
+            .then(|| LinComb::from(e.clone()).into_canonical())
+    }
+
+    /// Get the known decomposition of an expression, if any
+    pub fn get(&self, e: &FlatExpression<T>) -> Option<&Vec<FlatExpression<T>>> {
+        let key = Self::key(e)?;
+        self.scopes.iter().rev().find_map(|scope| scope.get(&key))
+    }
+
+    /// Get the known decomposition of an expression on `bitwidth` bits, padded with zeroes on the left (big endian).
+    /// A decomposition with more than `bitwidth` bits is ignored, as it does not guarantee that the expression fits
+    pub fn get_padded(
+        &self,
+        e: &FlatExpression<T>,
+        bitwidth: usize,
+    ) -> Option<Vec<FlatExpression<T>>> {
+        self.get(e)
+            .filter(|bits| bits.len() <= bitwidth)
+            .map(|bits| {
+                (0..bitwidth - bits.len())
+                    .map(|_| FlatExpression::Number(T::zero()))
+                    .chain(bits.iter().cloned())
+                    .collect()
+            })
+    }
+
+    /// Register the decomposition of an expression. Non-linear expressions are ignored, and a known decomposition is
+    /// never replaced
+    pub fn insert(&mut self, e: FlatExpression<T>, bits: Vec<FlatExpression<T>>) {
+        if let Some(key) = Self::key(&e) {
+            if !self.scopes.iter().any(|scope| scope.contains_key(&key)) {
+                self.scopes.last_mut().unwrap().insert(key, bits);
+            }
+        }
+    }
+
+    /// Start a scope for the decompositions found in a branch
+    pub fn enter_branch(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    /// Forget the decompositions found since the matching call to `enter_branch`
+    pub fn exit_branch(&mut self) {
+        assert!(self.scopes.len() > 1);
+        self.scopes.pop();
+    }
+}
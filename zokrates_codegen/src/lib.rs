@@ -7,8 +7,10 @@
 //! @author Jacob Eberhardt <jacob.eberhardt@tu-berlin.de>
 //! @date 2017
 
+mod bits_cache;
 mod utils;
 
+use self::bits_cache::BitsCache;
 use self::utils::flat_expression_from_bits;
 use zokrates_ast::zir::{
     ConditionalExpression, SelectExpression, ShouldReduce, UMetadata, ZirAssemblyStatement,
@@ -16,10 +18,7 @@ use zokrates_ast::zir::{
 };
 use zokrates_interpreter::Interpreter;
 
-use std::collections::{
-    hash_map::{Entry, HashMap},
-    VecDeque,
-};
+use std::collections::{HashMap, VecDeque};
 use zokrates_ast::common::embed::*;
 use zokrates_ast::common::FlatEmbed;
 use zokrates_ast::common::{RuntimeError, Variable};
@@ -100,7 +99,7 @@ pub struct Flattener<'ast, T> {
     /// `Variable`s corresponding to each `Identifier`
     layout: HashMap<Identifier<'ast>, Variable>,
     /// Cached bit decompositions to avoid re-generating them
-    bits_cache: HashMap<FlatExpression<T>, Vec<FlatExpression<T>>>,
+    bits_cache: BitsCache<T>,
 }
 
 trait FlattenOutput<T: Field>: Sized {
@@ -219,7 +218,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             config,
             next_var_idx: 0,
             layout: HashMap::new(),
-            bits_cache: HashMap::new(),
+            bits_cache: BitsCache::default(),
         }
     }
 
@@ -598,11 +597,15 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         let (consequence, alternative) = if self.config.isolate_branches {
             let mut consequence_statements = VecDeque::new();
 
+            self.bits_cache.enter_branch();
             let consequence = consequence.flatten(self, &mut consequence_statements);
+            self.bits_cache.exit_branch();
 
             let mut alternative_statements = VecDeque::new();
 
+            self.bits_cache.enter_branch();
             let alternative = alternative.flatten(self, &mut alternative_statements);
+            self.bits_cache.exit_branch();
 
             let consequence_statements =
                 self.make_conditional(consequence_statements, condition_id.into());
@@ -784,16 +787,20 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 T::max_value() - constant,
             ),
             (lhs_flattened, rhs_flattened) => {
-                let lhs_id = self.define(lhs_flattened, statements_flattened);
-                let rhs_id = self.define(rhs_flattened, statements_flattened);
+                // linear operands are kept as they are, so that comparisons of the same values share their bits
+                let lhs = match lhs_flattened.is_linear() {
+                    true => lhs_flattened,
+                    false => self.define(lhs_flattened, statements_flattened).into(),
+                };
+                let rhs = match rhs_flattened.is_linear() {
+                    true => rhs_flattened,
+                    false => self.define(rhs_flattened, statements_flattened).into(),
+                };
 
                 // shifted_sub := 2**safe_width + lhs - rhs
                 let shifted_sub = FlatExpression::Add(
                     box FlatExpression::Number(T::from(2).pow(bit_width)),
-                    box FlatExpression::Sub(
-                        box FlatExpression::Identifier(lhs_id),
-                        box FlatExpression::Identifier(rhs_id),
-                    ),
+                    box FlatExpression::Sub(box lhs, box rhs),
                 );
 
                 let sub_width = bit_width + 1;
@@ -1050,22 +1057,32 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
         match embed {
             FlatEmbed::FieldToBoolUnsafe => vec![params.pop().unwrap()],
-            FlatEmbed::U8ToBits => self.u_to_bits(params.pop().unwrap(), 8.into()),
-            FlatEmbed::U16ToBits => self.u_to_bits(params.pop().unwrap(), 16.into()),
-            FlatEmbed::U32ToBits => self.u_to_bits(params.pop().unwrap(), 32.into()),
-            FlatEmbed::U64ToBits => self.u_to_bits(params.pop().unwrap(), 64.into()),
-            FlatEmbed::U8FromBits => {
+            FlatEmbed::U8ToBits | FlatEmbed::I8ToBits => {
+                self.u_to_bits(params.pop().unwrap(), 8.into())
+            }
+            FlatEmbed::U16ToBits | FlatEmbed::I16ToBits => {
+                self.u_to_bits(params.pop().unwrap(), 16.into())
+            }
+            FlatEmbed::U32ToBits | FlatEmbed::I32ToBits => {
+                self.u_to_bits(params.pop().unwrap(), 32.into())
+            }
+            FlatEmbed::U64ToBits | FlatEmbed::I64ToBits => {
+                self.u_to_bits(params.pop().unwrap(), 64.into())
+            }
+            FlatEmbed::U128ToBits => self.u_to_bits(params.pop().unwrap(), 128.into()),
+            FlatEmbed::U8FromBits | FlatEmbed::I8FromBits => {
                 vec![self.bits_to_u(params, 8.into())]
             }
-            FlatEmbed::U16FromBits => {
+            FlatEmbed::U16FromBits | FlatEmbed::I16FromBits => {
                 vec![self.bits_to_u(params, 16.into())]
             }
-            FlatEmbed::U32FromBits => {
+            FlatEmbed::U32FromBits | FlatEmbed::I32FromBits => {
                 vec![self.bits_to_u(params, 32.into())]
             }
-            FlatEmbed::U64FromBits => {
+            FlatEmbed::U64FromBits | FlatEmbed::I64FromBits => {
                 vec![self.bits_to_u(params, 64.into())]
             }
+            FlatEmbed::U128FromBits => vec![self.bits_to_u(params, 128.into())],
             FlatEmbed::BitArrayLe => {
                 // get the length of the bit arrays
                 let len = generics[0];
@@ -1156,7 +1173,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         // add all flattened statements, adapt return statements
 
         let statements = funct.statements.into_iter().map(|stat| match stat {
-            FlatStatement::Block(..) => unreachable!(),
+            FlatStatement::Block(..) | FlatStatement::SourceMetadata(..) => unreachable!(),
             FlatStatement::Definition(var, rhs) => {
                 let new_var = self.use_sym();
                 replacement_map.insert(var, new_var);
@@ -1306,9 +1323,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         let left_flattened = self
             .flatten_uint_expression(statements_flattened, left)
             .get_field_unchecked();
-        let right_flattened = self
-            .flatten_uint_expression(statements_flattened, right)
-            .get_field_unchecked();
+        let right_flattened = self.flatten_uint_expression(statements_flattened, right);
+        let right_bits = right_flattened.bits.clone();
+        let right_flattened = right_flattened.get_field_unchecked();
         let n = if left_flattened.is_linear() {
             left_flattened
         } else {
@@ -1334,10 +1351,12 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             solver: Solver::EuclideanDiv,
         }));
 
+        let fits_product = target_bitwidth.fits_product::<T>();
+
         let target_bitwidth = target_bitwidth.to_usize();
 
         // q in range
-        let _ = self.get_bits_unchecked(
+        let q_bits = self.get_bits_unchecked(
             &FlatUExpression::with_field(FlatExpression::from(q)),
             target_bitwidth,
             target_bitwidth,
@@ -1358,7 +1377,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
         let _ = self.get_bits_unchecked(
             &FlatUExpression::with_field(FlatExpression::Add(
                 box FlatExpression::Sub(box r.into(), box d.clone()),
-                box FlatExpression::Number(T::from(2_u128.pow(target_bitwidth as u32))),
+                box FlatExpression::Number(T::from(2).pow(target_bitwidth)),
             )),
             target_bitwidth,
             target_bitwidth,
@@ -1366,12 +1385,70 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             RuntimeError::Sum,
         );
 
-        // q*d == n - r
-        statements_flattened.push_back(FlatStatement::Condition(
-            FlatExpression::Sub(box n, box r.into()),
-            FlatExpression::Mult(box q.into(), box d),
-            RuntimeError::Euclidean,
-        ));
+        if fits_product {
+            // q*d == n - r
+            statements_flattened.push_back(FlatStatement::Condition(
+                FlatExpression::Sub(box n, box r.into()),
+                FlatExpression::Mult(box q.into(), box d),
+                RuntimeError::Euclidean,
+            ));
+        } else {
+            // q*d could overflow the field, so we check it on two limbs of half the bitwidth
+            // q*d == q_0*d_0 + (q_1*d_0 + q_0*d_1) * 2**(w/2) + q_1*d_1 * 2**w
+            // we require q_1*d_1 == 0, so that the remaining terms cannot overflow
+            let limb_bitwidth = target_bitwidth / 2;
+
+            let d_bits = right_bits.unwrap();
+
+            let (q_high, q_low) = q_bits.split_at(limb_bitwidth);
+            let (d_high, d_low) = d_bits.split_at(limb_bitwidth);
+
+            let q_high = flat_expression_from_bits(q_high.to_vec());
+            let q_low = flat_expression_from_bits(q_low.to_vec());
+            let d_high = flat_expression_from_bits(d_high.to_vec());
+            let d_low = flat_expression_from_bits(d_low.to_vec());
+
+            // q_1*d_1 == 0
+            statements_flattened.push_back(FlatStatement::Condition(
+                FlatExpression::Number(T::zero()),
+                FlatExpression::Mult(box q_high.clone(), box d_high.clone()),
+                RuntimeError::Euclidean,
+            ));
+
+            let low = self.define(
+                FlatExpression::Mult(box q_low.clone(), box d_low.clone()),
+                statements_flattened,
+            );
+            let cross_left = self.define(
+                FlatExpression::Mult(box q_high, box d_low),
+                statements_flattened,
+            );
+            let cross_right = self.define(
+                FlatExpression::Mult(box q_low, box d_high),
+                statements_flattened,
+            );
+
+            let shift = T::from(2).pow(limb_bitwidth);
+
+            // q_0*d_0 + (q_1*d_0 + q_0*d_1) * 2**(w/2) == n - r
+            statements_flattened.push_back(FlatStatement::Condition(
+                FlatExpression::Sub(box n, box r.into()),
+                FlatExpression::Add(
+                    box low.into(),
+                    box FlatExpression::Add(
+                        box FlatExpression::Mult(
+                            box FlatExpression::Number(shift.clone()),
+                            box cross_left.into(),
+                        ),
+                        box FlatExpression::Mult(
+                            box FlatExpression::Number(shift),
+                            box cross_right.into(),
+                        ),
+                    ),
+                ),
+                RuntimeError::Euclidean,
+            ));
+        }
 
         (q.into(), r.into())
     }
@@ -1406,10 +1483,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             } // force to be a field element
             UExpressionInner::Identifier(x) => {
                 let field = FlatExpression::Identifier(*self.layout.get(&x.id).unwrap());
-                let bits = self.bits_cache.get(&field).map(|bits| {
-                    assert_eq!(bits.len(), target_bitwidth.to_usize());
-                    bits.clone()
-                });
+                let bits = self
+                    .bits_cache
+                    .get_padded(&field, target_bitwidth.to_usize());
                 FlatUExpression::with_field(field).bits(bits)
             }
             UExpressionInner::Select(e) => self.flatten_select_expression(statements_flattened, e),
@@ -1527,6 +1603,57 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         .collect::<Vec<_>>(),
                 )
             }
+            UExpressionInner::Mult(box left, box right) if !target_bitwidth.fits_product::<T>() => {
+                // the product could overflow the field, so we split both terms into two limbs
+                // `a * b = a_0 * b_0 + (a_1 * b_0 + a_0 * b_1) * 2**(n / 2) mod 2**n`
+                let left_bits = self
+                    .flatten_uint_expression(statements_flattened, left)
+                    .bits
+                    .unwrap();
+                let right_bits = self
+                    .flatten_uint_expression(statements_flattened, right)
+                    .bits
+                    .unwrap();
+
+                let limb_bitwidth = target_bitwidth.to_usize() / 2;
+
+                let (left_high, left_low) = left_bits.split_at(limb_bitwidth);
+                let (right_high, right_low) = right_bits.split_at(limb_bitwidth);
+
+                let left_high = flat_expression_from_bits(left_high.to_vec());
+                let left_low = flat_expression_from_bits(left_low.to_vec());
+                let right_high = flat_expression_from_bits(right_high.to_vec());
+                let right_low = flat_expression_from_bits(right_low.to_vec());
+
+                let low = self.define(
+                    FlatExpression::Mult(box left_low.clone(), box right_low.clone()),
+                    statements_flattened,
+                );
+                let cross_left = self.define(
+                    FlatExpression::Mult(box left_high, box right_low),
+                    statements_flattened,
+                );
+                let cross_right = self.define(
+                    FlatExpression::Mult(box left_low, box right_high),
+                    statements_flattened,
+                );
+
+                let shift = T::from(2).pow(limb_bitwidth);
+
+                FlatUExpression::with_field(FlatExpression::Add(
+                    box low.into(),
+                    box FlatExpression::Add(
+                        box FlatExpression::Mult(
+                            box FlatExpression::Number(shift.clone()),
+                            box cross_left.into(),
+                        ),
+                        box FlatExpression::Mult(
+                            box FlatExpression::Number(shift),
+                            box cross_right.into(),
+                        ),
+                    ),
+                ))
+            }
             UExpressionInner::Mult(box left, box right) => {
                 let left_flattened = self
                     .flatten_uint_expression(statements_flattened, left)
@@ -1893,8 +2020,6 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
             assert_eq!(bits.len(), to);
 
-            self.bits_cache
-                .insert(e.field.clone().unwrap(), bits.clone());
             return bits;
         };
 
@@ -1904,23 +2029,14 @@ impl<'ast, T: Field> Flattener<'ast, T> {
             // constant
 
             let from = std::cmp::max(from, to);
-            let res = match self.bits_cache.entry(e.field.clone().unwrap()) {
-                Entry::Occupied(entry) => {
-                    let res: Vec<_> = entry.get().clone();
-                    // if we already know a decomposition, its number of elements has to be smaller or equal to `to`
-                    assert!(res.len() <= to);
-
-                    // we then pad it with zeroes on the left (big endian) to return `to` bits
-                    if res.len() == to {
-                        res
-                    } else {
-                        (0..to - res.len())
-                            .map(|_| FlatExpression::Number(T::zero()))
-                            .chain(res)
-                            .collect()
-                    }
-                }
-                Entry::Vacant(_) => {
+
+            // a known decomposition can only be reused if it has at most `to` elements, as a larger one does not
+            // guarantee that `e` fits in `to` bits
+            let cached = self.bits_cache.get_padded(e.field.as_ref().unwrap(), to);
+
+            let res = match cached {
+                Some(res) => res,
+                None => {
                     let bits = (0..from).map(|_| self.use_sym()).collect::<Vec<_>>();
                     statements_flattened.push_back(FlatStatement::Directive(FlatDirective::new(
                         bits.clone(),
@@ -1955,9 +2071,12 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
                     assert_eq!(bits.len(), to);
 
-                    self.bits_cache
-                        .insert(e.field.clone().unwrap(), bits.clone());
-                    self.bits_cache.insert(sum, bits.clone());
+                    // only cache the decomposition if it was not truncated, as it would not represent `e` otherwise
+                    if from == to {
+                        self.bits_cache
+                            .insert(e.field.clone().unwrap(), bits.clone());
+                        self.bits_cache.insert(sum, bits.clone());
+                    }
 
                     bits
                 }
@@ -2292,7 +2411,7 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                         .map(|(index, e)| FlatStatement::Definition(Variable::public(index), e)),
                 );
             }
-            ZirStatement::IfElse(condition, consequence, alternative) => {
+            ZirStatement::IfElse(condition, consequence, alternative, isolated) => {
                 let condition_flat =
                     self.flatten_boolean_expression(statements_flattened, condition.clone());
 
@@ -2300,16 +2419,21 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                 statements_flattened
                     .push_back(FlatStatement::Definition(condition_id, condition_flat));
 
-                if self.config.isolate_branches {
+                if self.config.isolate_branches || isolated {
                     let mut consequence_statements = VecDeque::new();
                     let mut alternative_statements = VecDeque::new();
 
+                    self.bits_cache.enter_branch();
                     consequence
                         .into_iter()
                         .for_each(|s| self.flatten_statement(&mut consequence_statements, s));
+                    self.bits_cache.exit_branch();
+
+                    self.bits_cache.enter_branch();
                     alternative
                         .into_iter()
                         .for_each(|s| self.flatten_statement(&mut alternative_statements, s));
+                    self.bits_cache.exit_branch();
 
                     let consequence_statements =
                         self.make_conditional(consequence_statements, condition_id.into());
@@ -2632,10 +2756,15 @@ impl<'ast, T: Field> Flattener<'ast, T> {
                             .collect();
 
                         match embed {
-                            FlatEmbed::U64FromBits
+                            FlatEmbed::U128FromBits
+                            | FlatEmbed::U64FromBits
                             | FlatEmbed::U32FromBits
                             | FlatEmbed::U16FromBits
-                            | FlatEmbed::U8FromBits => {
+                            | FlatEmbed::U8FromBits
+                            | FlatEmbed::I64FromBits
+                            | FlatEmbed::I32FromBits
+                            | FlatEmbed::I16FromBits
+                            | FlatEmbed::I8FromBits => {
                                 let bits = exprs
                                     .into_iter()
                                     .map(|e| {
@@ -2668,6 +2797,9 @@ impl<'ast, T: Field> Flattener<'ast, T> {
 
                 statements_flattened.push_back(FlatStatement::Log(l, expressions));
             }
+            ZirStatement::SourceMetadata(metadata) => {
+                statements_flattened.push_back(FlatStatement::SourceMetadata(metadata));
+            }
         }
     }
 
@@ -3717,4 +3849,127 @@ mod tests {
             ]
         );
     }
+
+    fn bits_directive_count<T: Field>(statements: &FlatStatements<T>) -> usize {
+        statements
+            .iter()
+            .filter(
+                |s| matches!(s, FlatStatement::Directive(d) if matches!(d.solver, Solver::Bits(_))),
+            )
+            .count()
+    }
+
+    #[test]
+    fn comparisons_share_bits() {
+        // x < y
+        // x + 1 < y + 1
+
+        // both comparisons decompose `2**252 + x - y`, so the bits are only computed once
+
+        let mut flattener = Flattener::new(CompileConfig::default());
+        let mut statements_flattened = FlatStatements::new();
+
+        flattener.use_variable(&zir::Variable::field_element("x"));
+        flattener.use_variable(&zir::Variable::field_element("y"));
+
+        let lt = |offset: usize| {
+            BooleanExpression::FieldLt(
+                box FieldElementExpression::Add(
+                    box FieldElementExpression::identifier("x".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(offset)),
+                ),
+                box FieldElementExpression::Add(
+                    box FieldElementExpression::identifier("y".into()),
+                    box FieldElementExpression::Number(Bn128Field::from(offset)),
+                ),
+            )
+        };
+
+        let first = flattener.flatten_boolean_expression(&mut statements_flattened, lt(0));
+        let second = flattener.flatten_boolean_expression(&mut statements_flattened, lt(1));
+
+        assert_eq!(first, second);
+        assert_eq!(bits_directive_count(&statements_flattened), 1);
+    }
+
+    #[test]
+    fn isolated_branches_do_not_share_bits() {
+        // c ? x < y : false
+        // x < y
+
+        // when branches are isolated, the bits found in the branch are not valid outside of it
+
+        let lt = || {
+            BooleanExpression::<Bn128Field>::FieldLt(
+                box FieldElementExpression::identifier("x".into()),
+                box FieldElementExpression::identifier("y".into()),
+            )
+        };
+
+        let conditional = || {
+            BooleanExpression::conditional(
+                BooleanExpression::identifier("c".into()),
+                lt(),
+                BooleanExpression::Value(false),
+            )
+        };
+
+        for (isolate_branches, expected_count) in [(false, 1), (true, 2)] {
+            let mut flattener =
+                Flattener::new(CompileConfig::default().isolate_branches(isolate_branches));
+            let mut statements_flattened = FlatStatements::new();
+
+            flattener.use_variable(&zir::Variable::boolean("c".into()));
+            flattener.use_variable(&zir::Variable::field_element("x"));
+            flattener.use_variable(&zir::Variable::field_element("y"));
+
+            let _ = flattener.flatten_boolean_expression(&mut statements_flattened, conditional());
+            let _ = flattener.flatten_boolean_expression(&mut statements_flattened, lt());
+
+            assert_eq!(bits_directive_count(&statements_flattened), expected_count);
+        }
+    }
+
+    #[test]
+    fn bits_are_reused_at_larger_bitwidths_only() {
+        // decompose `x` on 8 bits, then read it as a `u16` and decompose it on 4 bits
+
+        // the 8 bits are reused, padded with zeroes, for the `u16`, but they cannot prove that `x` fits in 4 bits
+
+        let mut flattener = Flattener::new(CompileConfig::default());
+        let mut statements_flattened = FlatStatements::new();
+
+        flattener.use_variable(&zir::Variable::uint("x".into(), UBitwidth::B8));
+
+        let x = FlatUExpression::with_field(FlatExpression::Identifier(
+            *flattener.layout.get(&"x".into()).unwrap(),
+        ));
+
+        let bits_8 =
+            flattener.get_bits_unchecked(&x, 8, 8, &mut statements_flattened, RuntimeError::Sum);
+        assert_eq!(bits_directive_count(&statements_flattened), 1);
+
+        let x_16 = flattener.flatten_uint_expression(
+            &mut statements_flattened,
+            UExpression::identifier("x".into())
+                .annotate(UBitwidth::B16)
+                .metadata(UMetadata {
+                    max: Bn128Field::from(255),
+                    should_reduce: ShouldReduce::False,
+                }),
+        );
+        assert_eq!(
+            x_16.bits.unwrap(),
+            vec![FlatExpression::Number(Bn128Field::from(0)); 8]
+                .into_iter()
+                .chain(bits_8)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(bits_directive_count(&statements_flattened), 1);
+
+        let bits_4 =
+            flattener.get_bits_unchecked(&x, 4, 4, &mut statements_flattened, RuntimeError::Sum);
+        assert_eq!(bits_4.len(), 4);
+        assert_eq!(bits_directive_count(&statements_flattened), 2);
+    }
 }
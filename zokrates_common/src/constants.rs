@@ -9,3 +9,7 @@ pub const BW6_761: &str = "bw6_761";
 pub const G16: &str = "g16";
 pub const GM17: &str = "gm17";
 pub const MARLIN: &str = "marlin";
+
+pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 64;
+pub const DEFAULT_OPT_LEVEL: u8 = 1;
+pub const MAX_OPT_LEVEL: u8 = 2;
@@ -1,7 +1,8 @@
 pub mod constants;
 pub mod helpers;
 
-use serde::{Deserialize, Serialize};
+use crate::constants::{DEFAULT_MAX_RECURSION_DEPTH, DEFAULT_OPT_LEVEL, MAX_OPT_LEVEL};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::path::PathBuf;
 
 pub trait Resolver<E> {
@@ -12,12 +13,59 @@ pub trait Resolver<E> {
     ) -> Result<(String, PathBuf), E>;
 }
 
-#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy)]
+fn default_max_recursion_depth() -> usize {
+    DEFAULT_MAX_RECURSION_DEPTH
+}
+
+fn default_opt_level() -> u8 {
+    DEFAULT_OPT_LEVEL
+}
+
+fn check_opt_level(level: u8) -> Result<u8, String> {
+    if level > MAX_OPT_LEVEL {
+        return Err(format!(
+            "Optimization level {} is invalid, expected a level between 0 and {}",
+            level, MAX_OPT_LEVEL
+        ));
+    }
+    Ok(level)
+}
+
+fn deserialize_opt_level<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    let level = u8::deserialize(deserializer)?;
+    check_opt_level(level).map_err(serde::de::Error::custom)
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 pub struct CompileConfig {
     #[serde(default)]
     pub isolate_branches: bool,
     #[serde(default)]
     pub debug: bool,
+    #[serde(default = "default_max_recursion_depth")]
+    pub max_recursion_depth: usize,
+    #[serde(default)]
+    pub eliminate_linear_constraints: bool,
+    #[serde(
+        default = "default_opt_level",
+        deserialize_with = "deserialize_opt_level"
+    )]
+    pub opt_level: u8,
+    #[serde(default)]
+    pub source_map: bool,
+}
+
+impl Default for CompileConfig {
+    fn default() -> Self {
+        CompileConfig {
+            isolate_branches: false,
+            debug: false,
+            max_recursion_depth: DEFAULT_MAX_RECURSION_DEPTH,
+            eliminate_linear_constraints: false,
+            opt_level: DEFAULT_OPT_LEVEL,
+            source_map: false,
+        }
+    }
 }
 
 impl CompileConfig {
@@ -30,4 +78,24 @@ impl CompileConfig {
         self.debug = debug;
         self
     }
+
+    pub fn max_recursion_depth(mut self, depth: usize) -> Self {
+        self.max_recursion_depth = depth;
+        self
+    }
+
+    pub fn eliminate_linear_constraints(mut self, flag: bool) -> Self {
+        self.eliminate_linear_constraints = flag;
+        self
+    }
+
+    pub fn opt_level(mut self, level: u8) -> Result<Self, String> {
+        self.opt_level = check_opt_level(level)?;
+        Ok(self)
+    }
+
+    pub fn source_map(mut self, flag: bool) -> Self {
+        self.source_map = flag;
+        self
+    }
 }
@@ -4,8 +4,8 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2018
 use crate::imports::{self, Importer};
-use crate::macros;
-use crate::optimizer::optimize;
+use crate::macros::{self, has_attribute, SHOULD_PANIC_ATTRIBUTE, TEST_ATTRIBUTE};
+use crate::optimizer::{optimize, optimize_to_fixed_point, LinearOptimizer};
 use crate::semantics::{self, Checker};
 use macros::process_macros;
 use std::collections::HashMap;
@@ -14,9 +14,13 @@ use std::io;
 use std::path::{Path, PathBuf};
 use typed_arena::Arena;
 use zokrates_analysis::{self, analyse};
+use zokrates_ast::common::Warnings;
 use zokrates_ast::ir::{self, from_flat::from_flat};
 use zokrates_ast::typed::abi::Abi;
-use zokrates_ast::untyped::{Module, OwnedModuleId, Program};
+use zokrates_ast::untyped::{
+    Module, NodeValue, OwnedModuleId, Program, Symbol, SymbolDeclaration, SymbolDefinition,
+    SymbolImport,
+};
 use zokrates_ast::zir::ZirProgram;
 use zokrates_codegen::from_function_and_config;
 use zokrates_common::{CompileConfig, Resolver};
@@ -27,6 +31,8 @@ use zokrates_pest_ast as pest;
 pub struct CompilationArtifacts<'ast, T, I: IntoIterator<Item = ir::Statement<'ast, T>>> {
     prog: ir::ProgIterator<'ast, T, I>,
     abi: Abi,
+    warnings: Warnings,
+    eliminated_constraints: Option<usize>,
 }
 
 impl<'ast, T, I: IntoIterator<Item = ir::Statement<'ast, T>>> CompilationArtifacts<'ast, T, I> {
@@ -38,6 +44,15 @@ impl<'ast, T, I: IntoIterator<Item = ir::Statement<'ast, T>>> CompilationArtifac
         &self.abi
     }
 
+    pub fn warnings(&self) -> &Warnings {
+        &self.warnings
+    }
+
+    /// The number of linear constraints which were eliminated, if elimination is enabled
+    pub fn eliminated_constraints(&self) -> Option<usize> {
+        self.eliminated_constraints
+    }
+
     pub fn into_inner(self) -> (ir::ProgIterator<'ast, T, I>, Abi) {
         (self.prog, self.abi)
     }
@@ -46,6 +61,8 @@ impl<'ast, T, I: IntoIterator<Item = ir::Statement<'ast, T>>> CompilationArtifac
         CompilationArtifacts {
             prog: self.prog.collect(),
             abi: self.abi,
+            warnings: self.warnings,
+            eliminated_constraints: self.eliminated_constraints,
         }
     }
 }
@@ -92,6 +109,11 @@ impl CompileError {
     pub fn value(&self) -> &CompileErrorInner {
         &self.value
     }
+
+    /// Whether this error is an assertion which fails whatever the inputs of the program
+    pub fn is_assertion_failure(&self) -> bool {
+        matches!(&self.value, CompileErrorInner::AnalysisError(e) if e.is_assertion_failure())
+    }
 }
 
 impl CompileErrors {
@@ -151,7 +173,10 @@ impl fmt::Display for CompileErrorInner {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             CompileErrorInner::ParserError(ref e) => write!(f, "\n\t{}", e),
-            CompileErrorInner::MacroError(ref e) => write!(f, "\n\t{}", e),
+            CompileErrorInner::MacroError(ref e) => {
+                let location = e.pos().map(|p| format!("{}", p)).unwrap_or_default();
+                write!(f, "{}\n\t{}", location, e)
+            }
             CompileErrorInner::SemanticError(ref e) => {
                 let location = e
                     .pos()
@@ -184,8 +209,117 @@ pub fn compile<'ast, T: Field, E: Into<imports::Error>>(
     CompilationArtifacts<'ast, T, impl IntoIterator<Item = ir::Statement<'ast, T>> + 'ast>,
     CompileErrors,
 > {
+    let source = arena.alloc(source);
+
+    log::debug!("Parse program with entry file {}", location.display());
+
+    let program = parse_program::<T, E>(source, location, resolver, arena)?;
+
+    compile_program(program, config)
+}
+
+/// A function marked with `#[test]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFunction {
+    pub id: String,
+    /// Whether the test is expected to fail, as it is marked with `#[should_panic]`
+    pub should_panic: bool,
+}
+
+/// Find the test functions declared in a module
+pub fn find_tests<T: Field>(
+    source: &str,
+    location: &Path,
+) -> Result<Vec<TestFunction>, CompileErrors> {
+    let ast = pest::generate_ast(source)
+        .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).in_file(location)))?;
+
+    let ast = process_macros::<T>(ast)
+        .map_err(|e| CompileErrors::from(CompileErrorInner::from(e).in_file(location)))?;
+
+    Ok(ast
+        .declarations
+        .iter()
+        .filter_map(|d| match d {
+            pest::SymbolDeclaration::Function(f) if has_attribute(f, TEST_ATTRIBUTE) => {
+                Some(TestFunction {
+                    id: f.id.span.as_str().to_string(),
+                    should_panic: has_attribute(f, SHOULD_PANIC_ATTRIBUTE),
+                })
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+/// Compile the test function `test` of the module at `location`, which becomes the entry point of the program
+pub fn compile_test<'ast, T: Field, E: Into<imports::Error>>(
+    source: String,
+    location: FilePath,
+    test: &str,
+    resolver: Option<&dyn Resolver<E>>,
+    config: CompileConfig,
+    arena: &'ast Arena<String>,
+) -> Result<
+    CompilationArtifacts<'ast, T, impl IntoIterator<Item = ir::Statement<'ast, T>> + 'ast>,
+    CompileErrors,
+> {
+    let source = arena.alloc(source);
+
+    log::debug!("Parse program with entry file {}", location.display());
+
+    let mut program = parse_program::<T, E>(source, location.clone(), resolver, arena)?;
+
+    let declaration = program.modules[&location]
+        .symbols
+        .iter()
+        .find(|s| {
+            s.value.id == test
+                && matches!(s.value.symbol, Symbol::Here(SymbolDefinition::Function(..)))
+        })
+        .ok_or_else(|| {
+            CompileErrors::from(
+                CompileErrorInner::from(macros::Error::Attribute(
+                    None,
+                    format!("Test function `{}` not found", test),
+                ))
+                .in_file(&location),
+            )
+        })?;
+
+    let (start, end) = declaration.pos();
+    let id = declaration.value.id;
+
+    // the entry point is a module importing the test as its `main` function, so that the module of the test is unchanged
+    let entry_point = PathBuf::from(format!("{}::{}", location.display(), id));
+
+    program.modules.insert(
+        entry_point.clone(),
+        Module::with_symbols(vec![SymbolDeclaration {
+            id: "main",
+            symbol: Symbol::There(
+                SymbolImport::with_id_in_module(id, location).start_end(start, end),
+            ),
+            is_public: true,
+        }
+        .start_end(start, end)]),
+    );
+    program.main = entry_point;
+
+    compile_program(program, config)
+}
+
+fn compile_program<'ast, T: Field>(
+    program: Program<'ast>,
+    config: CompileConfig,
+) -> Result<
+    CompilationArtifacts<'ast, T, impl IntoIterator<Item = ir::Statement<'ast, T>> + 'ast>,
+    CompileErrors,
+> {
+    let mut warnings = Warnings::default();
+
     let (typed_ast, abi): (zokrates_ast::zir::ZirProgram<'_, T>, _) =
-        check_with_arena(source, location, resolver, &config, arena)?;
+        check_program(program, &config, &mut warnings)?;
 
     // flatten input program
     log::debug!("Flatten");
@@ -195,47 +329,87 @@ pub fn compile<'ast, T: Field, E: Into<imports::Error>>(
     log::debug!("Convert to IR");
     let ir_prog = from_flat(program_flattened);
 
-    // optimize
-    log::debug!("Optimise IR");
-    let optimized_ir_prog = optimize(ir_prog);
-
-    // clean (remove blocks)
-    let clean_ir_prog = optimized_ir_prog.clean();
+    // optimize according to the optimization level, removing blocks
+    let ir_prog = match config.opt_level {
+        0 => {
+            log::debug!("Skip IR optimisation");
+            boxed(ir_prog.clean())
+        }
+        1 => {
+            log::debug!("Optimise IR");
+            boxed(optimize(ir_prog).clean())
+        }
+        _ => {
+            log::debug!("Optimise IR until a fixed point is reached");
+            boxed(optimize_to_fixed_point(ir_prog))
+        }
+    };
+
+    // eliminate linear constraints, which requires the whole program
+    let (ir_prog, eliminated_constraints) =
+        match config.eliminate_linear_constraints || config.opt_level > 1 {
+            true => {
+                log::debug!("Eliminate linear constraints");
+                let (ir_prog, eliminated) = LinearOptimizer::optimize(ir_prog);
+                log::debug!("Eliminated {} linear constraints", eliminated);
+                (boxed(ir_prog), Some(eliminated))
+            }
+            false => (ir_prog, None),
+        };
 
     Ok(CompilationArtifacts {
-        prog: clean_ir_prog,
+        prog: ir_prog,
         abi,
+        warnings,
+        eliminated_constraints,
     })
 }
 
+// erase the type of the statements of a program, so that programs optimised differently have the same type
+#[allow(clippy::type_complexity)]
+fn boxed<'ast, T: 'ast, I: IntoIterator<Item = ir::Statement<'ast, T>>>(
+    p: ir::ProgIterator<'ast, T, I>,
+) -> ir::ProgIterator<'ast, T, Box<dyn Iterator<Item = ir::Statement<'ast, T>> + 'ast>>
+where
+    I::IntoIter: 'ast,
+{
+    ir::ProgIterator::new(
+        p.arguments,
+        Box::new(p.statements.into_iter()),
+        p.return_count,
+    )
+}
+
 pub fn check<T: Field, E: Into<imports::Error>>(
     source: String,
     location: FilePath,
     resolver: Option<&dyn Resolver<E>>,
     config: &CompileConfig,
-) -> Result<(), CompileErrors> {
+) -> Result<Warnings, CompileErrors> {
     let arena = Arena::new();
 
-    check_with_arena::<T, _>(source, location, resolver, config, &arena).map(|_| ())
-}
-
-fn check_with_arena<'ast, T: Field, E: Into<imports::Error>>(
-    source: String,
-    location: FilePath,
-    resolver: Option<&dyn Resolver<E>>,
-    config: &CompileConfig,
-    arena: &'ast Arena<String>,
-) -> Result<(ZirProgram<'ast, T>, Abi), CompileErrors> {
     let source = arena.alloc(source);
 
     log::debug!("Parse program with entry file {}", location.display());
 
-    let compiled = parse_program::<T, E>(source, location, resolver, arena)?;
+    let program = parse_program::<T, E>(source, location, resolver, &arena)?;
 
+    let mut warnings = Warnings::default();
+
+    check_program::<T>(program, config, &mut warnings)?;
+
+    Ok(warnings)
+}
+
+fn check_program<'ast, T: Field>(
+    program: Program<'ast>,
+    config: &CompileConfig,
+    warnings: &mut Warnings,
+) -> Result<(ZirProgram<'ast, T>, Abi), CompileErrors> {
     log::debug!("Check semantics");
 
     // check semantics
-    let typed_ast = Checker::check(compiled)
+    let typed_ast = Checker::check(program, config.source_map, warnings)
         .map_err(|errors| CompileErrors(errors.into_iter().map(CompileError::from).collect()))?;
 
     log::trace!("\n{}", typed_ast);
@@ -245,7 +419,7 @@ fn check_with_arena<'ast, T: Field, E: Into<imports::Error>>(
     log::debug!("Run static analysis");
 
     // analyse (unroll and constant propagation)
-    analyse(typed_ast, config)
+    analyse(typed_ast, config, warnings)
         .map_err(|e| CompileErrors(vec![CompileErrorInner::from(e).in_file(&main_module)]))
 }
 
@@ -351,6 +525,192 @@ mod test {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn source_map() {
+        let source = r#"
+            def square(field x) -> field {
+                return x * x;
+            }
+
+            def main(field a) -> field {
+                field b = square(a);
+                return b + 1;
+            }
+        "#
+        .to_string();
+
+        let arena = Arena::new();
+
+        let prog = compile::<Bn128Field, _>(
+            source,
+            "./path/to/file".into(),
+            None::<&dyn Resolver<io::Error>>,
+            CompileConfig::default().source_map(true),
+            &arena,
+        )
+        .unwrap()
+        .prog()
+        .collect();
+
+        // every statement is traced back to the source, including the ones compiled from inlined calls
+        assert!(prog.statements.iter().all(|s| s.metadata().is_some()));
+        assert!(prog.statements.iter().any(|s| {
+            let metadata = s.metadata().unwrap();
+            metadata.position.line == 3 && metadata.call_stack == vec!["square".to_string()]
+        }));
+        assert!(prog.statements.iter().any(|s| {
+            let metadata = s.metadata().unwrap();
+            metadata.position.line == 8 && metadata.call_stack.is_empty()
+        }));
+    }
+
+    #[test]
+    fn opt_level_zero_keeps_common_subexpressions() {
+        let constraint_count = |source: &str| {
+            let arena = Arena::new();
+
+            let count = compile::<Bn128Field, _>(
+                source.to_string(),
+                "./path/to/file".into(),
+                None::<&dyn Resolver<io::Error>>,
+                CompileConfig::default().opt_level(0).unwrap(),
+                &arena,
+            )
+            .unwrap()
+            .prog()
+            .collect()
+            .constraint_count();
+
+            count
+        };
+
+        let repeated = r#"
+            def main(field a, field b) -> field {
+                field c = a * b * a;
+                field d = a * b * a;
+                return c + d;
+            }
+        "#;
+
+        let reused = r#"
+            def main(field a, field b) -> field {
+                field c = a * b * a;
+                field d = c;
+                return c + d;
+            }
+        "#;
+
+        // `a * b * a` is flattened twice, as common subexpressions are only eliminated from level 1
+        assert!(constraint_count(repeated) > constraint_count(reused));
+    }
+
+    mod tests {
+        use super::*;
+
+        const SOURCE: &str = r#"
+            def main(field a) -> field {
+                return a;
+            }
+
+            #[test]
+            def test_one() {
+                assert(main(1) == 1);
+                return;
+            }
+
+            #[test]
+            #[should_panic]
+            def test_two() {
+                assert(main(1) == 2);
+                return;
+            }
+        "#;
+
+        #[test]
+        fn find() {
+            assert_eq!(
+                find_tests::<Bn128Field>(SOURCE, Path::new("./path/to/file")).unwrap(),
+                vec![
+                    TestFunction {
+                        id: "test_one".into(),
+                        should_panic: false
+                    },
+                    TestFunction {
+                        id: "test_two".into(),
+                        should_panic: true
+                    }
+                ]
+            );
+        }
+
+        #[test]
+        fn compile_as_entry_point() {
+            let arena = Arena::new();
+
+            let artifacts = compile_test::<Bn128Field, io::Error>(
+                SOURCE.to_string(),
+                "./path/to/file".into(),
+                "test_one",
+                None,
+                CompileConfig::default(),
+                &arena,
+            )
+            .unwrap();
+
+            // the test is the entry point instead of `main`, which takes an argument
+            assert!(artifacts.abi().inputs.is_empty());
+        }
+
+        #[test]
+        fn not_found() {
+            let arena = Arena::new();
+
+            let res = compile_test::<Bn128Field, io::Error>(
+                SOURCE.to_string(),
+                "./path/to/file".into(),
+                "test_three",
+                None,
+                CompileConfig::default(),
+                &arena,
+            );
+
+            assert!(res.err().unwrap().0[0]
+                .value()
+                .to_string()
+                .contains("Test function `test_three` not found"));
+        }
+
+        #[test]
+        fn invalid_attributes() {
+            let source = r#"
+                #[should_panic]
+                def foo() {
+                    return;
+                }
+            "#;
+
+            assert!(find_tests::<Bn128Field>(source, Path::new("./path/to/file")).is_err());
+
+            let source = r#"
+                #[test]
+                def foo(field a) {
+                    return;
+                }
+            "#;
+
+            assert!(find_tests::<Bn128Field>(source, Path::new("./path/to/file")).is_err());
+
+            let source = r#"
+                #[inline]
+                def foo() {
+                    return;
+                }
+            "#;
+
+            assert!(find_tests::<Bn128Field>(source, Path::new("./path/to/file")).is_err());
+        }
+    }
+
     mod abi {
         use super::*;
         use zokrates_ast::typed::abi::*;
@@ -365,10 +725,10 @@ mod test {
             //
             // // foo.zok
             // from bar import Bar as BarFoo;
-            // struct Foo { BarFoo b; }
+            // pub struct Foo { BarFoo b; }
             //
             // // bar.zok
-            // struct Bar { field a; }
+            // pub struct Bar { field a; }
 
             // Expected resolved type for FooMain:
             // Foo { Bar b; }
@@ -404,7 +764,7 @@ def main(FooMain f) {
                         Ok((
                             r#"
 from "bar" import Bar as BarFoo;
-struct Foo {
+pub struct Foo {
     BarFoo b;
 }
 "#
@@ -414,7 +774,7 @@ struct Foo {
                     } else if loc == "bar" {
                         Ok((
                             r#"
-struct Bar { field a; }
+pub struct Bar { field a; }
 "#
                             .into(),
                             "bar".into(),
@@ -74,22 +74,111 @@ impl Importer {
         modules: &mut HashMap<OwnedModuleId, Module<'ast>>,
         arena: &'ast Arena<String>,
     ) -> Result<Module<'ast>, CompileErrors> {
-        let symbols: Vec<_> = destination
-            .symbols
-            .into_iter()
-            .map(|s| match s.value.symbol {
+        let mut symbols = vec![];
+        // the symbols brought into scope by `from "x" import *`
+        let mut glob_symbols = vec![];
+        let mut namespaces = vec![];
+
+        for s in destination.symbols {
+            match s.value.symbol {
                 Symbol::Here(SymbolDefinition::Import(import)) => {
-                    Importer::resolve::<T, E>(import, &location, resolver, modules, arena)
+                    symbols.push(Importer::resolve::<T, E>(
+                        import,
+                        s.value.is_public,
+                        &location,
+                        resolver,
+                        modules,
+                        arena,
+                    )?);
                 }
-                _ => Ok(s),
-            })
-            .collect::<Result<_, _>>()?;
+                Symbol::Here(SymbolDefinition::ModuleImport(import)) => {
+                    if let ModuleImportKind::Namespace(alias) = import.value.kind {
+                        let namespace = match alias {
+                            Some(alias) => alias,
+                            None => Importer::module_name(import.value.source, &location)?,
+                        };
+
+                        if namespaces.contains(&namespace) {
+                            return Err(CompileErrorInner::ImportError(
+                                Error::new(format!(
+                                    "Namespace {} is declared more than once",
+                                    namespace
+                                ))
+                                .with_pos(Some(import.pos())),
+                            )
+                            .in_file(&location)
+                            .into());
+                        }
+
+                        namespaces.push(namespace);
+                    }
 
-        Ok(Module::with_symbols(symbols))
+                    let is_glob = import.value.kind == ModuleImportKind::All;
+
+                    let declarations = Importer::resolve_module::<T, E>(
+                        import,
+                        s.value.is_public,
+                        &location,
+                        resolver,
+                        modules,
+                        arena,
+                    )?;
+
+                    if is_glob {
+                        glob_symbols.extend(declarations);
+                    } else {
+                        symbols.extend(declarations);
+                    }
+                }
+                _ => symbols.push(s),
+            }
+        }
+
+        // symbols declared or imported by name shadow the ones imported with `*`, which must not be ambiguous otherwise
+        let mut globs: Vec<(
+            SymbolDeclarationNode<'ast>,
+            (OwnedModuleId, Identifier<'ast>),
+        )> = vec![];
+
+        for s in glob_symbols {
+            if symbols.iter().any(|d| d.value.id == s.value.id) {
+                continue;
+            }
+
+            let origin = match &s.value.symbol {
+                Symbol::There(import) => {
+                    Importer::origin(modules, &import.value.module_id, import.value.symbol_id)
+                }
+                _ => unreachable!(),
+            };
+
+            match globs.iter().find(|(d, _)| d.value.id == s.value.id) {
+                Some((_, o)) if *o == origin => {}
+                Some((_, o)) => {
+                    return Err(CompileErrorInner::ImportError(
+                        Error::new(format!(
+                            "Ambiguous import of {}: it is imported from both {} and {}",
+                            s.value.id,
+                            o.0.display(),
+                            origin.0.display()
+                        ))
+                        .with_pos(Some(s.pos())),
+                    )
+                    .in_file(&location)
+                    .into());
+                }
+                None => globs.push((s, origin)),
+            }
+        }
+
+        Ok(Module::with_symbols(
+            globs.into_iter().map(|(s, _)| s).chain(symbols),
+        ))
     }
 
     fn resolve<'ast, T: Field, E: Into<Error>>(
         import: CanonicalImportNode<'ast>,
+        is_public: bool,
         location: &Path,
         resolver: Option<&dyn Resolver<E>>,
         modules: &mut HashMap<OwnedModuleId, Module<'ast>>,
@@ -119,6 +208,7 @@ impl Importer {
                         SymbolDeclaration {
                             id: symbol.get_alias(),
                             symbol: Symbol::Flat(FlatEmbed::Sha256Round),
+                            is_public,
                         }
                     }
                 }
@@ -140,52 +230,114 @@ impl Importer {
                         SymbolDeclaration {
                             id: symbol.get_alias(),
                             symbol: Symbol::Flat(FlatEmbed::SnarkVerifyBls12377),
+                            is_public,
                         }
                     }
                 }
                 "unpack" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::Unpack),
+                    is_public,
                 },
                 "field_to_bool_unsafe" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::FieldToBoolUnsafe),
+                    is_public,
                 },
                 "bit_array_le" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::BitArrayLe),
+                    is_public,
+                },
+                "u128_to_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::U128ToBits),
+                    is_public,
                 },
                 "u64_to_bits" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::U64ToBits),
+                    is_public,
                 },
                 "u32_to_bits" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::U32ToBits),
+                    is_public,
                 },
                 "u16_to_bits" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::U16ToBits),
+                    is_public,
                 },
                 "u8_to_bits" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::U8ToBits),
+                    is_public,
+                },
+                "u128_from_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::U128FromBits),
+                    is_public,
                 },
                 "u64_from_bits" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::U64FromBits),
+                    is_public,
                 },
                 "u32_from_bits" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::U32FromBits),
+                    is_public,
                 },
                 "u16_from_bits" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::U16FromBits),
+                    is_public,
                 },
                 "u8_from_bits" => SymbolDeclaration {
                     id: symbol.get_alias(),
                     symbol: Symbol::Flat(FlatEmbed::U8FromBits),
+                    is_public,
+                },
+                "i64_to_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::I64ToBits),
+                    is_public,
+                },
+                "i32_to_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::I32ToBits),
+                    is_public,
+                },
+                "i16_to_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::I16ToBits),
+                    is_public,
+                },
+                "i8_to_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::I8ToBits),
+                    is_public,
+                },
+                "i64_from_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::I64FromBits),
+                    is_public,
+                },
+                "i32_from_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::I32FromBits),
+                    is_public,
+                },
+                "i16_from_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::I16FromBits),
+                    is_public,
+                },
+                "i8_from_bits" => SymbolDeclaration {
+                    id: symbol.get_alias(),
+                    symbol: Symbol::Flat(FlatEmbed::I8FromBits),
+                    is_public,
                 },
                 "FIELD_SIZE_IN_BITS" => SymbolDeclaration {
                     id: symbol.get_alias(),
@@ -197,6 +349,7 @@ impl Importer {
                         }
                         .start_end(pos.0, pos.1),
                     )),
+                    is_public,
                 },
                 s => {
                     return Err(CompileErrorInner::ImportError(
@@ -206,67 +359,197 @@ impl Importer {
                     .into());
                 }
             },
-            _ => match resolver {
-                Some(res) => match res.resolve(location.to_path_buf(), module_id.to_path_buf()) {
-                    Ok((source, new_location)) => {
-                        let alias = symbol.alias.unwrap_or(
-                            module_id
-                                .file_stem()
-                                .ok_or_else(|| {
-                                    CompileErrors::from(
-                                        CompileErrorInner::ImportError(Error::new(format!(
-                                            "Could not determine alias for import {}",
-                                            module_id.display()
-                                        )))
-                                        .in_file(location),
-                                    )
-                                })?
-                                .to_str()
-                                .unwrap(),
-                        );
-
-                        match modules.get(&new_location) {
-                            Some(_) => {}
-                            None => {
-                                let source = arena.alloc(source);
-                                let compiled = parse_module::<T, E>(
-                                    source,
-                                    new_location.clone(),
-                                    resolver,
-                                    modules,
-                                    arena,
-                                )?;
-
-                                assert!(modules.insert(new_location.clone(), compiled).is_none());
-                            }
-                        };
+            _ => {
+                let alias = symbol
+                    .alias
+                    .unwrap_or(Importer::module_name(module_id, location)?);
 
-                        SymbolDeclaration {
-                            id: alias,
-                            symbol: Symbol::There(
-                                SymbolImport::with_id_in_module(symbol.id, new_location)
-                                    .start_end(pos.0, pos.1),
-                            ),
-                        }
-                    }
-                    Err(err) => {
-                        return Err(
-                            CompileErrorInner::ImportError(err.into().with_pos(Some(pos)))
-                                .in_file(location)
-                                .into(),
-                        );
-                    }
-                },
-                None => {
-                    return Err(CompileErrorInner::from(Error::new(
-                        "Cannot resolve import without a resolver",
-                    ))
+                let new_location = Importer::load_module::<T, E>(
+                    module_id, pos, location, resolver, modules, arena,
+                )?;
+
+                // symbols of a module which are not `pub` cannot be imported, including the ones it imports itself
+                if modules[&new_location]
+                    .symbols
+                    .iter()
+                    .any(|s| s.value.id == symbol.id && !s.value.is_public)
+                {
+                    return Err(CompileErrorInner::ImportError(
+                        Error::new(format!(
+                            "Cannot import private symbol {} from module {}, it must be declared `pub`",
+                            symbol.id,
+                            module_id.display()
+                        ))
+                        .with_pos(Some(pos)),
+                    )
                     .in_file(location)
                     .into());
                 }
-            },
+
+                SymbolDeclaration {
+                    id: alias,
+                    symbol: Symbol::There(
+                        SymbolImport::with_id_in_module(symbol.id, new_location)
+                            .start_end(pos.0, pos.1),
+                    ),
+                    is_public,
+                }
+            }
         };
 
         Ok(symbol_declaration.start_end(pos.0, pos.1))
     }
+
+    // resolve an import of a whole module into a declaration for each of the symbols it brings into scope
+    fn resolve_module<'ast, T: Field, E: Into<Error>>(
+        import: ModuleImportNode<'ast>,
+        is_public: bool,
+        location: &Path,
+        resolver: Option<&dyn Resolver<E>>,
+        modules: &mut HashMap<OwnedModuleId, Module<'ast>>,
+        arena: &'ast Arena<String>,
+    ) -> Result<Vec<SymbolDeclarationNode<'ast>>, CompileErrors> {
+        let pos = import.pos();
+        let module_id = import.value.source;
+
+        if module_id.to_str().unwrap() == "EMBED" {
+            return Err(CompileErrorInner::ImportError(
+                Error::new("Embeds must be imported by name").with_pos(Some(pos)),
+            )
+            .in_file(location)
+            .into());
+        }
+
+        let new_location =
+            Importer::load_module::<T, E>(module_id, pos, location, resolver, modules, arena)?;
+
+        // the public symbols of the module, each of them once even if it is overloaded
+        // impl blocks and static assertions do not declare a symbol which can be imported
+        let mut ids: Vec<Identifier<'ast>> = vec![];
+        for s in &modules[&new_location].symbols {
+            let is_symbol = !matches!(
+                s.value.symbol,
+                Symbol::Here(SymbolDefinition::Impl(..))
+                    | Symbol::Here(SymbolDefinition::StaticAssertion(..))
+            );
+            if s.value.is_public && is_symbol && !ids.contains(&s.value.id) {
+                ids.push(s.value.id);
+            }
+        }
+
+        let declare = |id: Identifier<'ast>, symbol_id: Identifier<'ast>, is_public: bool| {
+            SymbolDeclaration {
+                id,
+                symbol: Symbol::There(
+                    SymbolImport::with_id_in_module(symbol_id, new_location.clone())
+                        .start_end(pos.0, pos.1),
+                ),
+                is_public,
+            }
+            .start_end(pos.0, pos.1)
+        };
+
+        match import.value.kind {
+            ModuleImportKind::All => Ok(ids
+                .into_iter()
+                .filter(|id| *id != "main")
+                .map(|id| declare(id, id, is_public))
+                .collect()),
+            ModuleImportKind::Namespace(alias) => {
+                let namespace = match alias {
+                    Some(alias) => alias,
+                    None => Importer::module_name(module_id, location)?,
+                };
+
+                // for backward compatibility, the `main` function of the module is also imported under the name of the namespace
+                let main = ids
+                    .contains(&"main")
+                    .then(|| declare(namespace, "main", is_public));
+
+                // the namespaced symbols are only visible in this module
+                Ok(main
+                    .into_iter()
+                    .chain(ids.into_iter().map(|id| {
+                        declare(
+                            arena.alloc(format!("{}::{}", namespace, id)).as_str(),
+                            id,
+                            false,
+                        )
+                    }))
+                    .collect())
+            }
+        }
+    }
+
+    // the name under which a module is imported by default
+    fn module_name<'ast>(
+        module_id: &'ast Path,
+        location: &Path,
+    ) -> Result<Identifier<'ast>, CompileErrors> {
+        module_id
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| {
+                CompileErrorInner::ImportError(Error::new(format!(
+                    "Could not determine alias for import {}",
+                    module_id.display()
+                )))
+                .in_file(location)
+                .into()
+            })
+    }
+
+    // resolve a module and parse it if it was not already, returning its location
+    fn load_module<'ast, T: Field, E: Into<Error>>(
+        module_id: &Path,
+        pos: (Position, Position),
+        location: &Path,
+        resolver: Option<&dyn Resolver<E>>,
+        modules: &mut HashMap<OwnedModuleId, Module<'ast>>,
+        arena: &'ast Arena<String>,
+    ) -> Result<OwnedModuleId, CompileErrors> {
+        let res = resolver.ok_or_else(|| {
+            CompileErrors::from(
+                CompileErrorInner::from(Error::new("Cannot resolve import without a resolver"))
+                    .in_file(location),
+            )
+        })?;
+
+        let (source, new_location) = res
+            .resolve(location.to_path_buf(), module_id.to_path_buf())
+            .map_err(|err| {
+                CompileErrors::from(
+                    CompileErrorInner::ImportError(err.into().with_pos(Some(pos)))
+                        .in_file(location),
+                )
+            })?;
+
+        if !modules.contains_key(&new_location) {
+            let source = arena.alloc(source);
+            let compiled =
+                parse_module::<T, E>(source, new_location.clone(), resolver, modules, arena)?;
+
+            assert!(modules.insert(new_location.clone(), compiled).is_none());
+        }
+
+        Ok(new_location)
+    }
+
+    // the module and name of the definition a symbol of a module refers to, following imports
+    fn origin<'ast>(
+        modules: &HashMap<OwnedModuleId, Module<'ast>>,
+        module_id: &ModuleId,
+        id: Identifier<'ast>,
+    ) -> (OwnedModuleId, Identifier<'ast>) {
+        let declaration = modules
+            .get(module_id)
+            .and_then(|m| m.symbols.iter().find(|s| s.value.id == id));
+
+        match declaration.map(|d| &d.value.symbol) {
+            Some(Symbol::There(import)) => {
+                Importer::origin(modules, &import.value.module_id, import.value.symbol_id)
+            }
+            _ => (module_id.to_path_buf(), id),
+        }
+    }
 }
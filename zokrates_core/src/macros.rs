@@ -1,10 +1,21 @@
 use std::fmt;
+use zokrates_ast::untyped::Position;
 use zokrates_field::Field;
-use zokrates_pest_ast::File;
+use zokrates_pest_ast::{Attribute, File, FunctionDefinition, Span, SymbolDeclaration};
 
 #[derive(Debug)]
 pub enum Error {
     Curve(String, String),
+    Attribute(Option<Position>, String),
+}
+
+impl Error {
+    pub fn pos(&self) -> Option<Position> {
+        match self {
+            Error::Curve(..) => None,
+            Error::Attribute(pos, _) => *pos,
+        }
+    }
 }
 
 impl fmt::Display for Error {
@@ -15,22 +26,124 @@ impl fmt::Display for Error {
                 "When processing macros: curve `{}` is incompatible with curve `{}`",
                 found, expected
             ),
+            Error::Attribute(_, message) => write!(f, "{}", message),
         }
     }
 }
 
+/// The attribute marking a function as a test
+pub const TEST_ATTRIBUTE: &str = "test";
+/// The attribute marking a test as expected to fail
+pub const SHOULD_PANIC_ATTRIBUTE: &str = "should_panic";
+
 pub fn process_macros<T: Field>(file: File) -> Result<File, Error> {
-    match &file.pragma {
-        Some(pragma) => {
-            if T::name() != pragma.curve.name {
-                Err(Error::Curve(
-                    T::name().to_string(),
-                    pragma.curve.name.clone(),
-                ))
-            } else {
-                Ok(file)
+    if let Some(pragma) = &file.pragma {
+        if T::name() != pragma.curve.name {
+            return Err(Error::Curve(
+                T::name().to_string(),
+                pragma.curve.name.clone(),
+            ));
+        }
+    }
+
+    for declaration in &file.declarations {
+        match declaration {
+            SymbolDeclaration::Function(function) => check_attributes(function)?,
+            SymbolDeclaration::Impl(implementation) => {
+                if let Some(attribute) = implementation
+                    .functions
+                    .iter()
+                    .flat_map(|f| &f.attributes)
+                    .next()
+                {
+                    return Err(Error::Attribute(
+                        Some(position(&attribute.span)),
+                        "Attributes are only allowed on functions declared at the module level"
+                            .into(),
+                    ));
+                }
             }
+            _ => {}
+        }
+    }
+
+    Ok(file)
+}
+
+/// Whether a function is marked with the attribute `id`
+pub fn has_attribute(function: &FunctionDefinition, id: &str) -> bool {
+    function.attributes.iter().any(|a| a.id.span.as_str() == id)
+}
+
+fn check_attributes(function: &FunctionDefinition) -> Result<(), Error> {
+    for (index, attribute) in function.attributes.iter().enumerate() {
+        let id = attribute.id.span.as_str();
+
+        if ![TEST_ATTRIBUTE, SHOULD_PANIC_ATTRIBUTE].contains(&id) {
+            return Err(error(attribute, format!("Unknown attribute `{}`", id)));
+        }
+
+        if function.attributes[..index]
+            .iter()
+            .any(|a| a.id.span.as_str() == id)
+        {
+            return Err(error(attribute, format!("Duplicate attribute `{}`", id)));
         }
-        None => Ok(file),
     }
+
+    let is_test = has_attribute(function, TEST_ATTRIBUTE);
+
+    if let Some(attribute) = function
+        .attributes
+        .iter()
+        .find(|a| a.id.span.as_str() == SHOULD_PANIC_ATTRIBUTE)
+    {
+        if !is_test {
+            return Err(error(
+                attribute,
+                format!(
+                    "`#[{}]` can only be used on test functions",
+                    SHOULD_PANIC_ATTRIBUTE
+                ),
+            ));
+        }
+    }
+
+    if is_test {
+        let id = function.id.span.as_str();
+
+        if id == "main" {
+            return Err(Error::Attribute(
+                Some(position(&function.span)),
+                "The main function cannot be a test".into(),
+            ));
+        }
+
+        // tests are run as the entry point of a program without inputs nor outputs
+        if !function.generics.is_empty()
+            || !function.type_generics.is_empty()
+            || function.self_parameter.is_some()
+            || !function.parameters.is_empty()
+            || function.return_type.is_some()
+        {
+            return Err(Error::Attribute(
+                Some(position(&function.span)),
+                format!(
+                    "Test function `{}` must not have generic parameters, parameters, or a return type",
+                    id
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn error(attribute: &Attribute, message: String) -> Error {
+    Error::Attribute(Some(position(&attribute.span)), message)
+}
+
+fn position(span: &Span) -> Position {
+    let (line, col) = span.start_pos().line_col();
+    Position { line, col }
 }
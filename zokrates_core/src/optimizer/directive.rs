@@ -30,13 +30,13 @@ impl<'ast, T: Field> Folder<'ast, T> for DirectiveOptimizer<'ast, T> {
 
     fn fold_statement(&mut self, s: Statement<'ast, T>) -> Vec<Statement<'ast, T>> {
         match s {
-            Statement::Directive(d) => {
+            Statement::Directive(d, metadata) => {
                 let d = self.fold_directive(d);
 
                 match self.calls.entry((d.solver.clone(), d.inputs.clone())) {
                     Entry::Vacant(e) => {
                         e.insert(d.outputs.clone());
-                        vec![Statement::Directive(d)]
+                        vec![Statement::Directive(d, metadata)]
                     }
                     Entry::Occupied(e) => {
                         self.substitution
@@ -0,0 +1,411 @@
+//! Module containing the `LinearOptimizer` to eliminate linear constraints by substitution
+// ```
+// a * b == c
+// a + c == d
+// ```
+// where `c` is not an input or an output, can be replaced by
+// ```
+// a * b == d - a
+// ```
+
+// # Elimination rules
+
+// Unlike the other optimizers, this pass needs the whole program, as a variable is substituted in the statements before
+// the constraint it is eliminated with as well as after it.
+
+// ## Defined variables
+
+// During witness generation, a variable is assigned a value either as an input, as the output of a directive, or by the
+// first constraint of the form `q == 1 * v`. We say that the variable is defined at this statement.
+
+// ## Optimization rules
+
+// For each constraint `c_i` of the form `~one * lin == lin'` in order:
+// - if `c_i` reports an error which can happen during honest execution, for example a failed assertion, it is kept so
+//   that this error is still reported
+// - otherwise, let `l == 0` be the canonical form of `c_i`. We pick a pivot `v` in `l` such that:
+//      - `v` is not `~one`, an input or an output
+//      - if `c_i` defines a variable, `v` is this variable, as it would otherwise not be assigned anymore
+//      - the other variables of `l` are defined before every other statement `v` appears in
+//   If many variables qualify, we pick the one which appears in the fewest statements. We then remove `c_i` and replace `v`
+//   by its value according to `l` everywhere else
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use zokrates_ast::flat::Variable;
+use zokrates_ast::ir::folder::{fold_linear_combination, Folder};
+use zokrates_ast::ir::visitor::Visitor;
+use zokrates_ast::ir::*;
+use zokrates_field::Field;
+
+#[derive(Debug)]
+pub struct LinearOptimizer<'ast, T> {
+    /// The statements of the program, `None` once eliminated
+    statements: Vec<Option<Statement<'ast, T>>>,
+    /// The variables which are available from the start of witness generation
+    inputs: HashSet<Variable>,
+    /// The variables which cannot be eliminated
+    ignore: HashSet<Variable>,
+    /// The index of the statement each variable is defined at
+    definitions: HashMap<Variable, usize>,
+    /// The indices of the statements each variable is used in
+    occurrences: HashMap<Variable, BTreeSet<usize>>,
+}
+
+impl<'ast, T: Field> LinearOptimizer<'ast, T> {
+    /// Eliminate the linear constraints of a program, returning the optimized program and the number of constraints
+    /// which were removed
+    pub fn optimize<I: IntoIterator<Item = Statement<'ast, T>>>(
+        p: ProgIterator<'ast, T, I>,
+    ) -> (Prog<'ast, T>, usize) {
+        let p = p.clean().collect();
+
+        let inputs: HashSet<_> = std::iter::once(Variable::one())
+            .chain(p.arguments.iter().map(|a| a.id))
+            .collect();
+
+        let mut optimizer = LinearOptimizer {
+            statements: vec![],
+            ignore: inputs.iter().cloned().chain(p.returns()).collect(),
+            inputs,
+            definitions: HashMap::new(),
+            occurrences: HashMap::new(),
+        };
+
+        for s in p.statements {
+            optimizer.push(s);
+        }
+
+        let count = optimizer.statements.len();
+
+        for i in 0..count {
+            optimizer.eliminate(i);
+        }
+
+        let statements: Vec<_> = optimizer.statements.into_iter().flatten().collect();
+        let removed = count - statements.len();
+
+        (
+            ProgIterator::new(p.arguments, statements, p.return_count),
+            removed,
+        )
+    }
+
+    fn is_defined_before(&self, v: &Variable, index: usize) -> bool {
+        self.inputs.contains(v)
+            || self
+                .definitions
+                .get(v)
+                .map(|definition| *definition < index)
+                .unwrap_or(false)
+    }
+
+    // add a statement to the program, keeping track of the variables it defines and uses
+    fn push(&mut self, s: Statement<'ast, T>) {
+        let index = self.statements.len();
+
+        match &s {
+            Statement::Constraint(_, lin, ..) => {
+                if let [(v, coefficient)] = lin.0.as_slice() {
+                    if *coefficient == T::from(1)
+                        && !self.inputs.contains(v)
+                        && !self.definitions.contains_key(v)
+                    {
+                        self.definitions.insert(*v, index);
+                    }
+                }
+            }
+            Statement::Directive(d, _) => {
+                for o in &d.outputs {
+                    self.definitions.entry(*o).or_insert(index);
+                }
+            }
+            _ => {}
+        }
+
+        for v in Occurrences::of(&s) {
+            self.occurrences.entry(v).or_default().insert(index);
+        }
+
+        self.statements.push(Some(s));
+    }
+
+    // remove a statement from the program
+    fn remove(&mut self, index: usize) {
+        let s = self.statements[index].take().unwrap();
+
+        for v in Occurrences::of(&s) {
+            self.occurrences.get_mut(&v).unwrap().remove(&index);
+        }
+    }
+
+    // try to eliminate the constraint at a given index
+    fn eliminate(&mut self, index: usize) {
+        let (quad, lin) = match &self.statements[index] {
+            Some(Statement::Constraint(quad, lin, error, _)) => match error {
+                Some(error) if !error.is_malicious() => return,
+                _ => (quad.clone(), lin.clone()),
+            },
+            _ => return,
+        };
+
+        let mut sum = match quad.try_linear() {
+            Ok(l) => (l - lin.clone()).into_canonical(),
+            Err(_) => return,
+        };
+
+        // a constraint which always holds can be removed
+        if sum.0.is_empty() {
+            return self.remove(index);
+        }
+
+        // a constraint which defines a variable can only be used to eliminate this variable
+        let candidates: Vec<_> = match lin.0.as_slice() {
+            [(v, _)] if self.definitions.get(v) == Some(&index) => vec![*v],
+            _ => sum.0.keys().cloned().collect(),
+        };
+
+        let pivot = candidates
+            .into_iter()
+            .filter(|v| !self.ignore.contains(v) && sum.0.contains_key(v))
+            .filter(|v| {
+                self.occurrences[v]
+                    .iter()
+                    .filter(|j| **j != index)
+                    .all(|j| {
+                        sum.0
+                            .keys()
+                            .filter(|x| *x != v)
+                            .all(|x| self.is_defined_before(x, *j))
+                    })
+            })
+            .min_by_key(|v| self.occurrences[v].len());
+
+        let pivot = match pivot {
+            Some(pivot) => pivot,
+            None => return,
+        };
+
+        // `c * pivot + rest == 0`, so `pivot == -rest / c`
+        let coefficient = sum.0.remove(&pivot).unwrap();
+        let value = (LinComb::zero() - LinComb::from(sum)) / &coefficient;
+
+        self.remove(index);
+
+        let mut substitution = Substitution {
+            variable: pivot,
+            value,
+        };
+
+        for j in self.occurrences.remove(&pivot).unwrap() {
+            let s = self.statements[j].take().unwrap();
+
+            let s = substitution.fold_statement(s).pop().unwrap();
+
+            for v in Occurrences::of(&s) {
+                self.occurrences.entry(v).or_default().insert(j);
+            }
+
+            self.statements[j] = Some(s);
+        }
+    }
+}
+
+/// Replace a variable by a linear combination
+struct Substitution<T> {
+    variable: Variable,
+    value: LinComb<T>,
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for Substitution<T> {
+    fn fold_linear_combination(&mut self, lc: LinComb<T>) -> LinComb<T> {
+        match lc.0.iter().any(|(variable, _)| *variable == self.variable) {
+            true => {
+                lc.0.into_iter()
+                    .map(|(variable, coefficient)| match variable == self.variable {
+                        true => self.value.clone() * &coefficient,
+                        false => LinComb::summand(coefficient, variable),
+                    })
+                    .fold(LinComb::zero(), |acc, x| acc + x)
+                    .reduce()
+            }
+            false => fold_linear_combination(self, lc),
+        }
+    }
+}
+
+/// Find the variables used in a statement. The outputs of directives are not included, as they are not used by it
+#[derive(Default)]
+struct Occurrences {
+    variables: HashSet<Variable>,
+}
+
+impl Occurrences {
+    fn of<T: Field>(s: &Statement<T>) -> HashSet<Variable> {
+        let mut occurrences = Occurrences::default();
+        occurrences.visit_statement(s);
+        occurrences.variables
+    }
+}
+
+impl<T: Field> Visitor<T> for Occurrences {
+    fn visit_linear_combination(&mut self, e: &LinComb<T>) {
+        self.variables.extend(e.0.iter().map(|(v, _)| *v));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_ast::flat::Parameter;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn keep_outputs() {
+        // def main(x) -> (1):
+        //    # y, z = solver(x)
+        //    y * z == x
+        //    ~out_0 == y + z
+
+        // the last constraint defines `~out_0`, which is an output, so nothing is eliminated
+
+        let x = Parameter::private(Variable::new(0));
+        let y = Variable::new(1);
+        let z = Variable::new(2);
+        let out = Variable::public(0);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![x],
+            statements: vec![
+                Statement::Directive(
+                    Directive {
+                        inputs: vec![x.id.into()],
+                        outputs: vec![y, z],
+                        solver: Solver::Xor,
+                    },
+                    None,
+                ),
+                Statement::constraint(QuadComb::from_linear_combinations(y.into(), z.into()), x.id),
+                Statement::definition(out, LinComb::from(y) + LinComb::from(z)),
+            ],
+            return_count: 1,
+        };
+
+        let (optimized, removed) = LinearOptimizer::optimize(p.clone());
+
+        assert_eq!(optimized, p);
+        assert_eq!(removed, 0);
+    }
+
+    #[test]
+    fn eliminate_intermediate() {
+        // def main(x) -> (2):
+        //    # y, z = solver(x)
+        //    z == y + x
+        //    ~out_0 == y * z
+        //    ~out_1 == z * z
+
+        // ->
+
+        // def main(x) -> (2):
+        //    # y, z = solver(x)
+        //    ~out_0 == (z - x) * z
+        //    ~out_1 == z * z
+
+        // `y` appears in fewer statements than `z`, so it is the one eliminated
+
+        let x = Parameter::private(Variable::new(0));
+        let y = Variable::new(1);
+        let z = Variable::new(2);
+        let out_0 = Variable::public(0);
+        let out_1 = Variable::public(1);
+
+        let directive = Statement::Directive(
+            Directive {
+                inputs: vec![x.id.into()],
+                outputs: vec![y, z],
+                solver: Solver::Xor,
+            },
+            None,
+        );
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![x],
+            statements: vec![
+                directive.clone(),
+                Statement::constraint(LinComb::from(y) + LinComb::from(x.id), z),
+                Statement::definition(
+                    out_0,
+                    QuadComb::from_linear_combinations(y.into(), z.into()),
+                ),
+                Statement::definition(
+                    out_1,
+                    QuadComb::from_linear_combinations(z.into(), z.into()),
+                ),
+            ],
+            return_count: 2,
+        };
+
+        let expected: Prog<Bn128Field> = Prog {
+            arguments: vec![x],
+            statements: vec![
+                directive,
+                Statement::definition(
+                    out_0,
+                    QuadComb::from_linear_combinations(
+                        (LinComb::from(z) - LinComb::from(x.id)).reduce(),
+                        z.into(),
+                    ),
+                ),
+                Statement::definition(
+                    out_1,
+                    QuadComb::from_linear_combinations(z.into(), z.into()),
+                ),
+            ],
+            return_count: 2,
+        };
+
+        let (optimized, removed) = LinearOptimizer::optimize(p);
+
+        assert_eq!(optimized, expected);
+        assert_eq!(removed, 1);
+    }
+
+    #[test]
+    fn keep_assertions() {
+        // def main(x, y):
+        //    # z = solver(x)
+        //    x + z == y // assertion
+
+        // the constraint is kept as it reports a failed assertion
+
+        let x = Parameter::private(Variable::new(0));
+        let y = Parameter::private(Variable::new(1));
+        let z = Variable::new(2);
+
+        let p: Prog<Bn128Field> = Prog {
+            arguments: vec![x, y],
+            statements: vec![
+                Statement::Directive(
+                    Directive {
+                        inputs: vec![x.id.into()],
+                        outputs: vec![z],
+                        solver: Solver::Bits(1),
+                    },
+                    None,
+                ),
+                Statement::Constraint(
+                    (LinComb::from(x.id) + LinComb::from(z)).into(),
+                    y.id.into(),
+                    Some(RuntimeError::SourceAssertion(Default::default())),
+                    None,
+                ),
+            ],
+            return_count: 0,
+        };
+
+        let (optimized, removed) = LinearOptimizer::optimize(p.clone());
+
+        assert_eq!(optimized, p);
+        assert_eq!(removed, 0);
+    }
+}
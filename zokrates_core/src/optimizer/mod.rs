@@ -7,16 +7,18 @@
 mod canonicalizer;
 mod directive;
 mod duplicate;
+mod linear;
 mod redefinition;
 mod tautology;
 
 use self::canonicalizer::Canonicalizer;
 use self::directive::DirectiveOptimizer;
 use self::duplicate::DuplicateOptimizer;
+pub use self::linear::LinearOptimizer;
 use self::redefinition::RedefinitionOptimizer;
 use self::tautology::TautologyOptimizer;
 
-use zokrates_ast::ir::{ProgIterator, Statement};
+use zokrates_ast::ir::{Prog, ProgIterator, Statement};
 use zokrates_field::Field;
 
 pub fn optimize<'ast, T: Field, I: IntoIterator<Item = Statement<'ast, T>>>(
@@ -59,3 +61,22 @@ pub fn optimize<'ast, T: Field, I: IntoIterator<Item = Statement<'ast, T>>>(
     log::debug!("Done");
     r
 }
+
+/// Run the optimizer repeatedly, as removing a constraint can allow removing others, until no constraint is removed
+pub fn optimize_to_fixed_point<'ast, T: Field, I: IntoIterator<Item = Statement<'ast, T>>>(
+    p: ProgIterator<'ast, T, I>,
+) -> Prog<'ast, T> {
+    let mut p = optimize(p).clean().collect();
+
+    loop {
+        let count = p.statements.len();
+
+        log::debug!("Optimizer: Run again on {} statements", count);
+
+        p = optimize(p).clean().collect();
+
+        if p.statements.len() == count {
+            break p;
+        }
+    }
+}
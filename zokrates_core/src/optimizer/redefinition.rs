@@ -73,18 +73,22 @@ impl<T: Field> RedefinitionOptimizer<T> {
         aggressive: bool,
     ) -> Vec<Statement<'ast, T>> {
         match s {
-            Statement::Constraint(quad, lin, message) => {
+            Statement::Constraint(quad, lin, message, metadata) => {
                 let quad = self.fold_quadratic_combination(quad);
                 let lin = self.fold_linear_combination(lin);
 
                 if lin.is_zero() {
-                    return vec![Statement::Constraint(quad, lin, message)];
+                    return vec![Statement::Constraint(quad, lin, message, metadata)];
                 }
 
                 let (constraint, to_insert, to_ignore) = match self.ignore.contains(&lin.0[0].0)
                     || self.substitution.contains_key(&lin.0[0].0)
                 {
-                    true => (Some(Statement::Constraint(quad, lin, message)), None, None),
+                    true => (
+                        Some(Statement::Constraint(quad, lin, message, metadata)),
+                        None,
+                        None,
+                    ),
                     false => match lin.try_summand() {
                         // if the right side is a single variable
                         Ok((variable, coefficient)) => match quad.try_linear() {
@@ -96,12 +100,17 @@ impl<T: Field> RedefinitionOptimizer<T> {
                                     quad,
                                     LinComb::summand(coefficient, variable),
                                     message,
+                                    metadata,
                                 )),
                                 None,
                                 Some(variable),
                             ),
                         },
-                        Err(l) => (Some(Statement::Constraint(quad, l, message)), None, None),
+                        Err(l) => (
+                            Some(Statement::Constraint(quad, l, message, metadata)),
+                            None,
+                            None,
+                        ),
                     },
                 };
 
@@ -121,7 +130,7 @@ impl<T: Field> RedefinitionOptimizer<T> {
                     _ => vec![],
                 }
             }
-            Statement::Directive(d) => {
+            Statement::Directive(d, metadata) => {
                 let d = self.fold_directive(d);
 
                 // check if the inputs are constants, ie reduce to the form `coeff * ~one`
@@ -171,7 +180,7 @@ impl<T: Field> RedefinitionOptimizer<T> {
                                 self.ignore.insert(o);
                             }
                         }
-                        vec![Statement::Directive(Directive { inputs, ..d })]
+                        vec![Statement::Directive(Directive { inputs, ..d }, metadata)]
                     }
                 }
             }
@@ -196,7 +205,7 @@ impl<'ast, T: Field> Folder<'ast, T> for RedefinitionOptimizer<T> {
                     .into_iter()
                     .filter(|s| match s {
                         // we remove a directive iff it has a single output and this output is in the substitution map, meaning it was propagated
-                        Statement::Directive(d) => {
+                        Statement::Directive(d, _) => {
                             d.outputs.len() > 1 || !self.substitution.contains_key(&d.outputs[0])
                         }
                         _ => true,
@@ -16,15 +16,15 @@ pub struct TautologyOptimizer;
 impl<'ast, T: Field> Folder<'ast, T> for TautologyOptimizer {
     fn fold_statement(&mut self, s: Statement<'ast, T>) -> Vec<Statement<'ast, T>> {
         match s {
-            Statement::Constraint(quad, lin, message) => match quad.try_linear() {
+            Statement::Constraint(quad, lin, message, metadata) => match quad.try_linear() {
                 Ok(l) => {
                     if l == lin {
                         vec![]
                     } else {
-                        vec![Statement::Constraint(l.into(), lin, message)]
+                        vec![Statement::Constraint(l.into(), lin, message, metadata)]
                     }
                 }
-                Err(quad) => vec![Statement::Constraint(quad, lin, message)],
+                Err(quad) => vec![Statement::Constraint(quad, lin, message, metadata)],
             },
             _ => fold_statement(self, s),
         }
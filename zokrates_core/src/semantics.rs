@@ -4,15 +4,18 @@
 //! @author Thibaut Schaeffer <thibaut@schaeff.fr>
 //! @date 2017
 
+use num::ToPrimitive;
 use num_bigint::BigUint;
 use std::collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
 use std::path::PathBuf;
-use zokrates_ast::common::{FormatString, SourceMetadata};
+use zokrates_ast::common::{FormatString, Lint, SourceMetadata, Warning, Warnings};
+use zokrates_ast::typed::folder::{self, Folder};
 use zokrates_ast::typed::types::{GGenericsAssignment, GTupleType, GenericsAssignment};
+use zokrates_ast::typed::Identifier as TypedIdentifier;
 use zokrates_ast::typed::SourceIdentifier;
 use zokrates_ast::typed::*;
-use zokrates_ast::typed::{DeclarationParameter, DeclarationVariable, Variable};
+use zokrates_ast::typed::{Combinator, DeclarationParameter, DeclarationVariable, Variable};
 use zokrates_ast::untyped::Identifier;
 use zokrates_ast::untyped::*;
 use zokrates_field::Field;
@@ -22,9 +25,9 @@ use zokrates_ast::untyped::types::{UnresolvedSignature, UnresolvedType, UserType
 use std::hash::Hash;
 use zokrates_ast::typed::types::{
     check_type, specialize_declaration_type, ArrayType, DeclarationArrayType, DeclarationConstant,
-    DeclarationFunctionKey, DeclarationSignature, DeclarationStructMember, DeclarationStructType,
-    DeclarationTupleType, DeclarationType, GenericIdentifier, StructLocation, StructMember,
-    TupleType,
+    DeclarationEnumType, DeclarationEnumVariant, DeclarationFunctionKey, DeclarationSignature,
+    DeclarationStructMember, DeclarationStructType, DeclarationTupleType, DeclarationType,
+    GenericIdentifier, StructLocation, StructMember, TupleType,
 };
 
 #[derive(PartialEq, Eq, Debug)]
@@ -85,6 +88,138 @@ impl<'ast, T> UserDeclarationType<'ast, T> {
 type TypeMap<'ast, T> = BTreeMap<OwnedModuleId, BTreeMap<UserTypeId, UserDeclarationType<'ast, T>>>;
 type ConstantMap<'ast, T> =
     BTreeMap<OwnedModuleId, BTreeMap<ConstantIdentifier<'ast>, DeclarationType<'ast, T>>>;
+type TraitMap<'ast, T> =
+    BTreeMap<OwnedModuleId, BTreeMap<Identifier<'ast>, TraitDeclaration<'ast, T>>>;
+type TypeGenericFunctionMap<'ast, T> =
+    BTreeMap<OwnedModuleId, BTreeMap<Identifier<'ast>, TypeGenericFunction<'ast, T>>>;
+
+/// The canonical identifier of a trait, which does not depend on the name it is imported as
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TraitIdentifier<'ast> {
+    module: OwnedModuleId,
+    id: Identifier<'ast>,
+}
+
+impl<'ast> TraitIdentifier<'ast> {
+    // the module the implementations of the trait declare their functions in, whichever module they are in. A call to
+    // a function of the trait on a type parameter resolves to the function of this module matching the type
+    fn impl_module(&self) -> OwnedModuleId {
+        self.module.join(format!("trait {}", self.id))
+    }
+}
+
+/// A trait, as the functions its implementations must provide
+#[derive(Debug, Clone)]
+struct TraitDeclaration<'ast, T> {
+    id: TraitIdentifier<'ast>,
+    // the opaque type standing for `Self` in the signatures of the functions
+    placeholder: DeclarationType<'ast, T>,
+    functions: Vec<(Identifier<'ast>, DeclarationSignature<'ast, T>)>,
+    // the functions which take `self`
+    methods: Vec<Identifier<'ast>>,
+}
+
+/// An implementation of a trait for a type
+#[derive(Debug, Clone)]
+struct ImplDeclaration<'ast, T> {
+    trait_id: TraitIdentifier<'ast>,
+    ty: DeclarationType<'ast, T>,
+}
+
+/// A function declared in an `impl` block
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ImplFunction<'ast, T> {
+    // the type of the `impl` block
+    ty: DeclarationType<'ast, T>,
+    key: DeclarationFunctionKey<'ast, T>,
+    // whether the function takes `self`, which makes it callable as a method
+    is_method: bool,
+}
+
+/// The names reserved for the methods called by operators on structs, along with their operator
+const OPERATOR_METHODS: [(&str, &str); 4] = [
+    ("op_add", "+"),
+    ("op_sub", "-"),
+    ("op_mul", "*"),
+    ("op_eq", "=="),
+];
+
+/// A type parameter of a function, such as `H: Hasher`
+#[derive(Debug, Clone)]
+struct TypeParameter<'ast, T> {
+    id: Identifier<'ast>,
+    bound: TraitIdentifier<'ast>,
+    // the opaque type standing for this parameter in the function
+    placeholder: DeclarationType<'ast, T>,
+    // the functions of its trait, called on the opaque type
+    functions: HashSet<DeclarationFunctionKey<'ast, T>>,
+}
+
+/// A parameter of a function which takes a function, such as `f` in `def apply(field x, (field) -> field f)`
+#[derive(Debug, Clone)]
+struct FunctionParameter<'ast, T> {
+    id: Identifier<'ast>,
+    // the position of the parameter among the parameters of the function
+    index: usize,
+    signature: DeclarationSignature<'ast, T>,
+}
+
+/// A function with type parameters or function parameters. Its body is checked once, with opaque types standing for
+/// its type parameters. Both kinds of parameters are resolved for each call when the call is inlined
+#[derive(Debug, Clone)]
+struct TypeGenericFunction<'ast, T> {
+    module: OwnedModuleId,
+    id: Identifier<'ast>,
+    parameters: Vec<TypeParameter<'ast, T>>,
+    function_parameters: Vec<FunctionParameter<'ast, T>>,
+    // the signature of the function without its function parameters
+    signature: DeclarationSignature<'ast, T>,
+}
+
+/// A type argument of a call, which must implement the trait its type parameter is bound by. It is checked once the
+/// symbol making the call is checked, against the implementations declared so far
+#[derive(Debug, Clone)]
+struct TraitBound<'ast, T> {
+    pos: (Position, Position),
+    ty: DeclarationType<'ast, T>,
+    bound: TraitIdentifier<'ast>,
+}
+
+/// An anonymous function, checked after the function it appears in
+#[derive(Debug, Clone)]
+struct Lambda<'ast, T> {
+    key: DeclarationFunctionKey<'ast, T>,
+    node: FunctionNode<'ast>,
+}
+
+/// A variable declared in the function being checked, reported if it is never read
+#[derive(Debug)]
+struct DeclaredVariable<'ast> {
+    id: SourceIdentifier<'ast>,
+    pos: (Position, Position),
+    // the function whose return value defines the variable, if any
+    call: Option<String>,
+    // whether the variable shadows another one
+    shadows: bool,
+    is_used: bool,
+}
+
+/// A value assigned with `<--` in the function being checked, reported if it is never constrained
+#[derive(Debug)]
+struct UnconstrainedAssignment<'ast> {
+    assignee: String,
+    id: TypedIdentifier<'ast>,
+    pos: (Position, Position),
+}
+
+/// A symbol which is not visible to other modules, reported if it is never used in its module
+#[derive(Debug)]
+struct LocalSymbol {
+    module_id: OwnedModuleId,
+    id: String,
+    lint: Lint,
+    pos: (Position, Position),
+}
 
 /// The global state of the program during semantic checks
 #[derive(Debug)]
@@ -98,6 +233,18 @@ struct State<'ast, T> {
     types: TypeMap<'ast, T>,
     // The user-defined constants
     constants: ConstantMap<'ast, T>,
+    /// The traits, their implementations, and the functions with type parameters
+    traits: TraitMap<'ast, T>,
+    impls: Vec<ImplDeclaration<'ast, T>>,
+    type_generic_functions: TypeGenericFunctionMap<'ast, T>,
+    /// The functions declared in `impl` blocks, in all modules
+    impl_functions: HashSet<ImplFunction<'ast, T>>,
+    /// The symbols used in each module
+    used_symbols: HashSet<(OwnedModuleId, String)>,
+    /// The symbols which must be used in their module, as they are not visible to other modules
+    local_symbols: Vec<LocalSymbol>,
+    /// The warnings found in the modules checked so far
+    warnings: Warnings,
 }
 
 /// A symbol for a given name: either a type or a group of functions. Not both!
@@ -105,6 +252,7 @@ struct State<'ast, T> {
 enum SymbolType<'ast, T> {
     Type,
     Constant,
+    TypeGenericFunction,
     Functions(BTreeSet<DeclarationSignature<'ast, T>>),
 }
 
@@ -141,6 +289,19 @@ impl<'ast, T: std::cmp::Ord> SymbolUnifier<'ast, T> {
         }
     }
 
+    fn insert_type_generic_function<S: Into<String>>(&mut self, id: S) -> bool {
+        let e = self.symbols.entry(id.into());
+        match e {
+            // functions with type parameters cannot be overloaded
+            Entry::Occupied(..) => false,
+            // otherwise, we can!
+            Entry::Vacant(v) => {
+                v.insert(SymbolType::TypeGenericFunction);
+                true
+            }
+        }
+    }
+
     fn insert_function<S: Into<String>>(
         &mut self,
         id: S,
@@ -151,8 +312,10 @@ impl<'ast, T: std::cmp::Ord> SymbolUnifier<'ast, T> {
             // if anything is already called `id`, it depends what it is
             Entry::Occupied(mut o) => {
                 match o.get_mut() {
-                    // if it's a Type or a Constant, then we can't introduce a function
-                    SymbolType::Type | SymbolType::Constant => false,
+                    // if it's a Type, a Constant or a function with type parameters, then we can't introduce a function
+                    SymbolType::Type | SymbolType::Constant | SymbolType::TypeGenericFunction => {
+                        false
+                    }
                     // if it's a Function, we can introduce it only if it has a different signature
                     SymbolType::Functions(signatures) => signatures.insert(signature),
                 }
@@ -174,6 +337,77 @@ impl<'ast, T: Field> State<'ast, T> {
             typed_modules: BTreeMap::new(),
             types: BTreeMap::new(),
             constants: BTreeMap::new(),
+            traits: BTreeMap::new(),
+            impls: vec![],
+            type_generic_functions: BTreeMap::new(),
+            impl_functions: HashSet::new(),
+            used_symbols: HashSet::new(),
+            local_symbols: vec![],
+            warnings: Warnings::default(),
+        }
+    }
+
+    // bind `id` to `ty` in the types of `module_id`, returning the type it shadows if any
+    fn bind_type(
+        &mut self,
+        module_id: &ModuleId,
+        id: &str,
+        ty: DeclarationType<'ast, T>,
+    ) -> Option<UserDeclarationType<'ast, T>> {
+        self.types
+            .entry(module_id.to_path_buf())
+            .or_default()
+            .insert(
+                id.to_string(),
+                UserDeclarationType {
+                    generics: vec![],
+                    ty,
+                },
+            )
+    }
+
+    // undo a call to `bind_type`
+    fn unbind_type(
+        &mut self,
+        module_id: &ModuleId,
+        id: &str,
+        shadowed: Option<UserDeclarationType<'ast, T>>,
+    ) {
+        let types = self.types.get_mut(module_id).unwrap();
+        match shadowed {
+            Some(ty) => {
+                types.insert(id.to_string(), ty);
+            }
+            None => {
+                types.remove(id);
+            }
+        }
+    }
+
+    // declare a function created during semantic checks, such as an anonymous function, in the module of its key
+    fn declare_function(&mut self, declaration: TypedFunctionSymbolDeclaration<'ast, T>) {
+        self.typed_modules
+            .entry(declaration.key.module.clone())
+            .or_insert_with(|| TypedModule { symbols: vec![] })
+            .symbols
+            .push(declaration.into());
+    }
+
+    // declare an array combinator used in `module_id`, once per module
+    fn declare_combinator(&mut self, module_id: &ModuleId, combinator: Combinator) {
+        let key = combinator.key(module_id);
+
+        let declared = self
+            .typed_modules
+            .get(&key.module)
+            .map(|m| m.functions_iter().any(|d| d.key == key))
+            .unwrap_or(false);
+
+        if !declared {
+            self.declare_function(TypedFunctionSymbolDeclaration::new(
+                key,
+                TypedFunctionSymbol::Combinator(combinator),
+            ));
         }
     }
 }
@@ -270,9 +504,11 @@ pub struct IdentifierInfo<'ast, T, U> {
     id: U,
     ty: Type<'ast, T>,
     is_mutable: bool,
+    // the index of the declaration of this variable in the function being checked, if its usage is tracked
+    declaration: Option<usize>,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 struct Scope<'ast, T> {
     level: usize,
     map: HashMap<
@@ -327,6 +563,38 @@ pub struct Checker<'ast, T> {
     return_type: Option<DeclarationType<'ast, T>>,
     scope: Scope<'ast, T>,
     functions: HashSet<DeclarationFunctionKey<'ast, T>>,
+    // the payload bindings of the `match` arms being checked, innermost last
+    match_bindings: Vec<(Identifier<'ast>, TypedExpression<'ast, T>)>,
+    // the number of loops enclosing the statement being checked
+    loop_depth: usize,
+    // the functions with type parameters in scope
+    type_generic_functions: HashMap<Identifier<'ast>, TypeGenericFunction<'ast, T>>,
+    // the type parameters of the function being checked
+    type_parameters: HashMap<Identifier<'ast>, TypeParameter<'ast, T>>,
+    // the type arguments of the calls checked so far, whose implementations are yet to be checked
+    trait_bounds: Vec<TraitBound<'ast, T>>,
+    // the functions declared in `impl` blocks
+    impl_functions: HashSet<ImplFunction<'ast, T>>,
+    // whether the function being checked is declared in an `impl` block
+    in_impl: bool,
+    // the number of temporary variables introduced by lowering statements and expressions, used to name them
+    temporary_count: usize,
+    // the functions passed for the function parameters of the function being checked
+    function_parameters: HashMap<Identifier<'ast>, DeclarationFunctionKey<'ast, T>>,
+    // the anonymous functions found so far, yet to be checked
+    lambdas: Vec<Lambda<'ast, T>>,
+    // the array combinators used in the symbol being checked
+    combinators: HashSet<Combinator>,
+    // the names of the symbols used in the module being checked
+    used_symbols: HashSet<String>,
+    // the variables declared in the function being checked
+    variables: Vec<DeclaredVariable<'ast>>,
+    // the values assigned with `<--` in the function being checked
+    unconstrained_assignments: Vec<UnconstrainedAssignment<'ast>>,
+    // the warnings found so far
+    warnings: Warnings,
+    // whether checked statements are preceded by their position in the source
+    source_map: bool,
 }
 
 impl<'ast, T: Field> Checker<'ast, T> {
@@ -335,8 +603,20 @@ impl<'ast, T: Field> Checker<'ast, T> {
     /// # Arguments
     ///
     /// * `prog` - The `Program` to be checked
-    pub fn check(prog: Program<'ast>) -> Result<TypedProgram<'ast, T>, Vec<Error>> {
-        Checker::default().check_program(prog)
+    /// * `source_map` - Whether to keep the position in the source of the statements of the `Program`
+    /// * `warnings` - The collector of the warnings found in the `Program`
+    pub fn check(
+        prog: Program<'ast>,
+        source_map: bool,
+        warnings: &mut Warnings,
+    ) -> Result<TypedProgram<'ast, T>, Vec<Error>> {
+        let mut checker = Checker {
+            source_map,
+            ..Checker::default()
+        };
+        let res = checker.check_program(prog);
+        warnings.extend(checker.warnings);
+        res
     }
 
     fn check_program(
@@ -367,12 +647,66 @@ impl<'ast, T: Field> Checker<'ast, T> {
             },
         )?;
 
+        self.check_local_symbols(&mut state);
+
+        self.warnings.extend(state.warnings);
+
         Ok(TypedProgram {
             main: program.main,
             modules: state.typed_modules,
         })
     }
 
+    // report the functions and imports which are only visible in their module but never used in it. Symbols imported
+    // together, for example with `import "x" as y`, are reported if none of them is used
+    fn check_local_symbols(&mut self, state: &mut State<'ast, T>) {
+        let mut imports: BTreeMap<_, Vec<_>> = BTreeMap::new();
+
+        for s in std::mem::take(&mut state.local_symbols) {
+            let is_used = state
+                .used_symbols
+                .contains(&(s.module_id.clone(), s.id.clone()));
+
+            match s.lint {
+                Lint::UnusedImports => imports
+                    .entry((s.module_id, s.pos.0))
+                    .or_default()
+                    .push((s.id, is_used)),
+                lint if !is_used => self.warnings.push(Warning::new(
+                    lint,
+                    s.module_id,
+                    s.pos.0,
+                    format!("Function `{}` is never used", s.id),
+                )),
+                _ => {}
+            }
+        }
+
+        for ((module_id, position), symbols) in imports {
+            if symbols.iter().any(|(_, is_used)| *is_used) {
+                continue;
+            }
+
+            // symbols imported under a namespace are named `namespace::symbol`
+            let names: BTreeSet<_> = symbols
+                .iter()
+                .map(|(id, _)| id.split("::").next().unwrap())
+                .collect();
+
+            let message = match names.len() {
+                1 => format!("Unused import `{}`", names.into_iter().next().unwrap()),
+                _ => "Unused import".to_string(),
+            };
+
+            self.warnings.push(Warning::new(
+                Lint::UnusedImports,
+                module_id,
+                position,
+                message,
+            ));
+        }
+    }
+
     fn check_type_definition(
         &mut self,
         ty: TypeDefinitionNode<'ast>,
@@ -489,6 +823,13 @@ impl<'ast, T: Field> Checker<'ast, T> {
             DeclarationType::Tuple(ref tuple_ty) => {
                 TupleExpression::try_from_typed(checked_expr, tuple_ty).map(TypedExpression::from)
             }
+            DeclarationType::Enum(ref enum_ty) => {
+                EnumExpression::try_from_typed(checked_expr, enum_ty).map(TypedExpression::from)
+            }
+            DeclarationType::BoundedArray(ref array_ty) => {
+                BoundedArrayExpression::try_from_typed(checked_expr, array_ty)
+                    .map(TypedExpression::from)
+            }
             DeclarationType::Int => Err(checked_expr), // Integers cannot be assigned
         }
         .map_err(|e| ErrorInner {
@@ -556,6 +897,7 @@ impl<'ast, T: Field> Checker<'ast, T> {
 
         for field in s.fields {
             let member_id = field.value.id.to_string();
+            let field_pos = field.pos();
             match self
                 .check_declaration_type(
                     field.value.ty,
@@ -564,6 +906,17 @@ impl<'ast, T: Field> Checker<'ast, T> {
                     &generics_map,
                     &mut used_generics,
                 )
+                .and_then(|t| match has_generic_expression(&t) {
+                    // struct generics are resolved by position, which does not allow for arithmetic on them
+                    true => Err(ErrorInner {
+                        pos: Some(field_pos),
+                        message: format!(
+                            "Arithmetic on generic parameters is not supported in struct members, found `{}`",
+                            t
+                        ),
+                    }),
+                    false => Ok(t),
+                })
                 .map(|t| (member_id, t))
             {
                 Ok(f) => match fields_set.insert(f.0.clone()) {
@@ -604,2258 +957,6137 @@ impl<'ast, T: Field> Checker<'ast, T> {
         ))
     }
 
-    fn check_symbol_declaration(
+    fn check_enum_type_declaration(
         &mut self,
-        declaration: SymbolDeclarationNode<'ast>,
+        id: String,
+        e: EnumDefinitionNode<'ast>,
         module_id: &ModuleId,
-        state: &mut State<'ast, T>,
-        symbols: &mut TypedSymbolDeclarations<'ast, T>,
-        symbol_unifier: &mut SymbolUnifier<'ast, T>,
-    ) -> Result<(), Vec<Error>> {
-        let mut errors: Vec<Error> = vec![];
+        state: &State<'ast, T>,
+    ) -> Result<DeclarationEnumType<'ast, T>, Vec<ErrorInner>> {
+        let pos = e.pos();
+        let e = e.value;
 
-        let pos = declaration.pos();
-        let declaration = declaration.value;
+        let mut errors = vec![];
 
-        match declaration.symbol.clone() {
-            Symbol::Here(SymbolDefinition::Struct(t)) => {
-                match self.check_struct_type_declaration(
-                    declaration.id.to_string(),
-                    t.clone(),
+        if e.variants.is_empty() {
+            return Err(vec![ErrorInner {
+                pos: Some(pos),
+                message: format!("Enum {} must have at least one variant", id),
+            }]);
+        }
+
+        let mut variants: Vec<DeclarationEnumVariant<'ast, T>> = vec![];
+
+        for variant in e.variants {
+            let variant_pos = variant.pos();
+            let variant = variant.value;
+
+            if variants.iter().any(|v| v.id == variant.id) {
+                errors.push(ErrorInner {
+                    pos: Some(variant_pos),
+                    message: format!("Duplicate variant {} in enum definition", variant.id),
+                });
+                continue;
+            }
+
+            let mut types = vec![];
+
+            for ty in variant.types {
+                match self.check_declaration_type(
+                    ty,
                     module_id,
                     state,
+                    &BTreeMap::default(),
+                    &mut HashSet::default(),
                 ) {
-                    Ok(ty) => {
-                        match symbol_unifier.insert_type(declaration.id) {
-                            false => errors.push(
-                                ErrorInner {
-                                    pos: Some(pos),
-                                    message: format!(
-                                        "{} conflicts with another symbol",
-                                        declaration.id
-                                    ),
-                                }
-                                .in_file(module_id),
-                            ),
-                            true => {
-                                // there should be no entry in the map for this type yet
-                                assert!(state
-                                    .types
-                                    .entry(module_id.to_path_buf())
-                                    .or_default()
-                                    .insert(
-                                        declaration.id.to_string(),
-                                        UserDeclarationType {
-                                            generics: ty
-                                                .generics
-                                                .clone()
-                                                .into_iter()
-                                                .map(|g| g.unwrap())
-                                                .collect(),
-                                            ty: DeclarationType::Struct(ty)
-                                        }
-                                    )
-                                    .is_none());
-                            }
-                        };
-                    }
-                    Err(e) => errors.extend(e.into_iter().map(|inner| Error {
-                        inner,
-                        module_id: module_id.to_path_buf(),
-                    })),
+                    Ok(ty) => types.push(ty),
+                    Err(e) => errors.push(e),
                 }
             }
-            Symbol::Here(SymbolDefinition::Constant(c)) => {
-                match self.check_constant_definition(declaration.id, c, module_id, state) {
-                    Ok(c) => {
-                        match symbol_unifier.insert_constant(declaration.id) {
-                            false => errors.push(
-                                ErrorInner {
-                                    pos: Some(pos),
-                                    message: format!(
-                                        "{} conflicts with another symbol",
-                                        declaration.id
-                                    ),
-                                }
-                                .in_file(module_id),
-                            ),
-                            true => {
-                                symbols.push(
-                                    TypedConstantSymbolDeclaration::new(
-                                        CanonicalConstantIdentifier::new(
-                                            declaration.id,
-                                            module_id.into(),
-                                        ),
-                                        TypedConstantSymbol::Here(c.clone()),
-                                    )
-                                    .into(),
-                                );
-                                let id = declaration.id;
 
-                                let info = IdentifierInfo {
-                                    id: CoreIdentifier::Constant(CanonicalConstantIdentifier::new(
-                                        declaration.id,
-                                        module_id.into(),
-                                    )),
-                                    ty: c.get_type(),
-                                    is_mutable: false,
-                                };
-                                assert_eq!(self.scope.level, 0);
-                                assert!(!self.scope.insert(id, info));
-                                assert!(state
-                                    .constants
-                                    .entry(module_id.to_path_buf())
-                                    .or_default()
-                                    .insert(declaration.id, c.ty)
-                                    .is_none());
-                            }
-                        };
+            variants.push(DeclarationEnumVariant::new(variant.id.to_string(), types));
+        }
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(DeclarationEnumType::new(
+            module_id.to_path_buf(),
+            id,
+            variants,
+        ))
+    }
+
+    fn check_trait_definition(
+        &mut self,
+        id: Identifier<'ast>,
+        t: TraitDefinitionNode<'ast>,
+        module_id: &ModuleId,
+        state: &mut State<'ast, T>,
+    ) -> Result<TraitDeclaration<'ast, T>, Vec<ErrorInner>> {
+        let t = t.value;
+
+        let mut errors = vec![];
+        let mut functions: Vec<(Identifier<'ast>, DeclarationSignature<'ast, T>)> = vec![];
+        let mut methods = vec![];
+
+        // `Self` stands for an opaque type in the signatures of the trait
+        let placeholder = type_placeholder(module_id, id, "Self");
+        let shadowed = state.bind_type(module_id, "Self", placeholder.clone());
+
+        for f in t.functions {
+            let pos = f.pos();
+            let f = f.value;
+
+            if functions.iter().any(|(f_id, _)| *f_id == f.id) {
+                errors.push(ErrorInner {
+                    pos: Some(pos),
+                    message: format!("Function {} is already declared in trait {}", f.id, id),
+                });
+                continue;
+            }
+
+            let signature = self.check_signature(f.signature, module_id, state);
+            self.return_type = None;
+
+            match signature {
+                Ok(signature) => {
+                    // the signatures of the implementations must differ, so that they can live side by side
+                    if !signature
+                        .inputs
+                        .iter()
+                        .chain(std::iter::once(&*signature.output))
+                        .any(|ty| contains_type(ty, &placeholder))
+                    {
+                        errors.push(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Function {} of trait {} must use `Self` in its signature",
+                                f.id, id
+                            ),
+                        });
                     }
-                    Err(e) => {
-                        errors.push(e.in_file(module_id));
+
+                    if f.is_method {
+                        methods.push(f.id);
                     }
+
+                    functions.push((f.id, signature));
                 }
+                Err(e) => errors.extend(e),
             }
-            Symbol::Here(SymbolDefinition::Type(t)) => {
-                match self.check_type_definition(t, module_id, state) {
-                    Ok(ty) => {
-                        match symbol_unifier.insert_type(declaration.id) {
-                            false => errors.push(
-                                ErrorInner {
-                                    pos: Some(pos),
-                                    message: format!(
-                                        "{} conflicts with another symbol",
-                                        declaration.id,
-                                    ),
-                                }
-                                .in_file(module_id),
-                            ),
-                            true => {
-                                assert!(state
-                                    .types
-                                    .entry(module_id.to_path_buf())
-                                    .or_default()
-                                    .insert(declaration.id.to_string(), ty)
-                                    .is_none());
-                            }
-                        };
-                    }
-                    Err(e) => {
-                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)));
-                    }
+        }
+
+        state.unbind_type(module_id, "Self", shadowed);
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(TraitDeclaration {
+            id: TraitIdentifier {
+                module: module_id.to_path_buf(),
+                id,
+            },
+            placeholder,
+            functions,
+            methods,
+        })
+    }
+
+    // check an `impl` block, returning the module its functions live in along with the functions
+    fn check_impl_definition(
+        &mut self,
+        i: ImplDefinitionNode<'ast>,
+        module_id: &ModuleId,
+        state: &mut State<'ast, T>,
+    ) -> Result<(OwnedModuleId, Vec<TypedFunctionSymbolDeclaration<'ast, T>>), Vec<ErrorInner>>
+    {
+        let pos = i.pos();
+        let i = i.value;
+
+        if let Some(trait_id) = i.trait_id {
+            self.use_symbol(trait_id);
+        }
+
+        let t = i
+            .trait_id
+            .map(|trait_id| {
+                state
+                    .traits
+                    .get(module_id)
+                    .and_then(|traits| traits.get(trait_id))
+                    .cloned()
+                    .ok_or_else(|| {
+                        vec![ErrorInner {
+                            pos: Some(pos),
+                            message: format!("Undefined trait {}", trait_id),
+                        }]
+                    })
+            })
+            .transpose()?;
+
+        let ty = self
+            .check_declaration_type(
+                i.ty,
+                module_id,
+                state,
+                &BTreeMap::default(),
+                &mut HashSet::default(),
+            )
+            .map_err(|e| vec![e])?;
+
+        let impl_module = match &t {
+            Some(t) => {
+                if state.impls.iter().any(|d| d.trait_id == t.id && d.ty == ty) {
+                    return Err(vec![ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Trait {} is already implemented for type {}",
+                            t.id.id, ty
+                        ),
+                    }]);
                 }
+
+                t.id.impl_module()
             }
-            Symbol::Here(SymbolDefinition::Function(f)) => {
-                match self.check_function(declaration.id, f, module_id, state) {
-                    Ok(funct) => {
-                        match symbol_unifier
-                            .insert_function(declaration.id, funct.signature.clone())
-                        {
-                            false => errors.push(
-                                ErrorInner {
-                                    pos: Some(pos),
-                                    message: format!(
-                                        "{} conflicts with another symbol",
-                                        declaration.id
-                                    ),
-                                }
-                                .in_file(module_id),
+            None => {
+                // functions belong to the type, so they are declared next to it
+                match &ty {
+                    DeclarationType::Struct(s) if s.canonical_location.module == module_id => {}
+                    _ => {
+                        return Err(vec![ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Functions can only be declared on structs defined in the same module, found {}",
+                                ty
                             ),
-                            true => {}
-                        };
+                        }])
+                    }
+                };
 
-                        self.functions.insert(
-                            DeclarationFunctionKey::with_location(
-                                module_id.to_path_buf(),
-                                declaration.id,
-                            )
-                            .signature(funct.signature.clone()),
-                        );
-                        symbols.push(
-                            TypedFunctionSymbolDeclaration::new(
-                                DeclarationFunctionKey::with_location(
-                                    module_id.to_path_buf(),
-                                    declaration.id,
-                                )
-                                .signature(funct.signature.clone()),
-                                TypedFunctionSymbol::Here(funct),
-                            )
-                            .into(),
+                module_id.join(format!("impl {}", ty))
+            }
+        };
+
+        let mut errors = vec![];
+        let mut declared: Vec<(
+            Identifier<'ast>,
+            FunctionNode<'ast>,
+            DeclarationFunctionKey<'ast, T>,
+        )> = vec![];
+
+        // `Self` stands for the type of the block in the functions
+        let shadowed = state.bind_type(module_id, "Self", ty.clone());
+
+        // declare all functions first, so that they can call each other
+        for (id, f) in i.functions {
+            let pos = f.pos();
+
+            if declared.iter().any(|(f_id, _, _)| *f_id == id)
+                || state
+                    .impl_functions
+                    .iter()
+                    .any(|g| g.key.module == impl_module && g.key.id == id && g.ty == ty)
+            {
+                errors.push(ErrorInner {
+                    pos: Some(pos),
+                    message: format!("Function {} is already declared for type {}", id, ty),
+                });
+                continue;
+            }
+
+            if !f.value.signature.type_generics.is_empty() {
+                errors.push(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Function {} is declared in an impl block and cannot have type parameters",
+                        id
+                    ),
+                });
+                continue;
+            }
+
+            let signature = self.check_signature(f.value.signature.clone(), module_id, state);
+            self.return_type = None;
+
+            let signature = match signature {
+                Ok(signature) => signature,
+                Err(e) => {
+                    errors.extend(e);
+                    continue;
+                }
+            };
+
+            let is_method = match &t {
+                Some(t) => match t.functions.iter().find(|(f_id, _)| *f_id == id) {
+                    Some((_, expected)) => {
+                        let expected = substitute_signature(
+                            expected.clone(),
+                            &[(t.placeholder.clone(), ty.clone())],
                         );
+
+                        if signature != expected {
+                            errors.push(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Function {} does not match its declaration in trait {}, expected signature {}, found {}",
+                                    id, t.id.id, expected, signature
+                                ),
+                            });
+                            continue;
+                        }
+
+                        t.methods.contains(&id)
                     }
-                    Err(e) => {
-                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)));
+                    None => {
+                        errors.push(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Function {} is not a member of trait {}",
+                                id, t.id.id
+                            ),
+                        });
+                        continue;
                     }
+                },
+                None => f.value.is_method(),
+            };
+
+            // a method called by an operator takes the right operand as its only argument besides `self`
+            if let Some((_, operator)) = OPERATOR_METHODS.iter().find(|(name, _)| *name == id) {
+                if !is_method || signature.inputs.len() != 2 {
+                    errors.push(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Function {} is called by `{}` and must take `self` and a single other argument",
+                            id, operator
+                        ),
+                    });
+                    continue;
                 }
             }
-            Symbol::There(import) => {
-                let pos = import.pos();
-                let import = import.value;
 
-                match Checker::default().check_module(&import.module_id, state) {
-                    Ok(()) => {
-                        // find candidates in the checked module
-                        let function_candidates: Vec<_> = state
-                            .typed_modules
-                            .get(&import.module_id)
-                            .unwrap()
-                            .functions_iter()
-                            .into_iter()
-                            .filter(|d| d.key.id == import.symbol_id)
-                            .map(|d| DeclarationFunctionKey {
-                                module: import.module_id.to_path_buf(),
-                                id: import.symbol_id,
-                                signature: d.symbol.signature(&state.typed_modules).clone(),
-                            })
-                            .collect();
+            let key =
+                DeclarationFunctionKey::with_location(impl_module.clone(), id).signature(signature);
 
-                        // find candidates in the types
-                        let type_candidate = state
-                            .types
-                            .entry(import.module_id.to_path_buf())
-                            .or_default()
-                            .get(import.symbol_id)
-                            .cloned();
+            let function = ImplFunction {
+                ty: ty.clone(),
+                key: key.clone(),
+                is_method,
+            };
 
-                        // find constant definition candidate
-                        let const_candidate = state
-                            .constants
-                            .entry(import.module_id.to_path_buf())
-                            .or_default()
-                            .iter()
-                            .find(|(i, _)| *i == &import.symbol_id)
-                            .map(|(_, c)| c)
-                            .cloned();
+            self.impl_functions.insert(function.clone());
+            state.impl_functions.insert(function);
 
-                        match (function_candidates.len(), type_candidate, const_candidate) {
-                            (0, Some(t), None) => {
-                                // rename the type to the declared symbol
-                                let t = UserDeclarationType {
-                                    ty: match t.ty {
-                                        DeclarationType::Struct(t) => DeclarationType::Struct(DeclarationStructType {
-                                            location: Some(StructLocation {
-                                                name: declaration.id.into(),
-                                                module: module_id.to_path_buf()
-                                            }),
-                                            ..t
-                                        }),
-                                        _ => t.ty // all other cases
-                                    },
-                                    ..t
-                                };
+            declared.push((id, f, key));
+        }
 
-                                // we imported a type, so the symbol it gets bound to should not already exist
-                                match symbol_unifier.insert_type(declaration.id) {
-                                    false => {
-                                        errors.push(Error {
-                                            module_id: module_id.to_path_buf(),
-                                            inner: ErrorInner {
-                                                pos: Some(pos),
-                                                message: format!(
-                                                    "{} conflicts with another symbol",
-                                                    declaration.id,
-                                                ),
-                                            }});
-                                    }
-                                    true => {}
-                                };
-                                state
-                                    .types
-                                    .entry(module_id.to_path_buf())
-                                    .or_default()
-                                    .insert(declaration.id.to_string(), t);
-                            }
-                            (0, None, Some(ty)) => {
-                                match symbol_unifier.insert_constant(declaration.id) {
-                                    false => {
-                                        errors.push(Error {
-                                            module_id: module_id.to_path_buf(),
-                                            inner: ErrorInner {
-                                                pos: Some(pos),
-                                                message: format!(
-                                                    "{} conflicts with another symbol",
-                                                    declaration.id,
-                                                ),
-                                            }});
-                                    }
-                                    true => {
-                                        let imported_id = CanonicalConstantIdentifier::new(import.symbol_id, import.module_id);
-                                        let id = CanonicalConstantIdentifier::new(declaration.id, module_id.into());
+        if let Some(t) = &t {
+            for (f_id, _) in &t.functions {
+                if !declared.iter().any(|(id, _, _)| id == f_id) {
+                    errors.push(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Missing function {} in implementation of trait {} for type {}",
+                            f_id, t.id.id, ty
+                        ),
+                    });
+                }
+            }
+        }
 
-                                        symbols.push(TypedConstantSymbolDeclaration::new(
-                                            id.clone(),
-                                            TypedConstantSymbol::There(imported_id)
-                                        ).into());
+        let mut functions = vec![];
 
-                                        let id = declaration.id;
-                                        let info = IdentifierInfo {
-                                            id: CoreIdentifier::Constant(CanonicalConstantIdentifier::new(
-                                                declaration.id,
-                                                module_id.into(),
-                                            )),
-                                            ty: zokrates_ast::typed::types::try_from_g_type(ty.clone()).unwrap(),
-                                            is_mutable: false,
-                                        };
-                                        assert_eq!(self.scope.level, 0);
-                                        assert!(!self.scope.insert(id, info));
+        self.in_impl = true;
 
-                                        state
-                                            .constants
-                                            .entry(module_id.to_path_buf())
-                                            .or_default()
-                                            .insert(declaration.id, ty);
-                                    }
-                                };
-                            }
-                            (0, None, None) => {
-                                errors.push(ErrorInner {
-                                    pos: Some(pos),
-                                    message: format!(
-                                        "Could not find symbol {} in module {}",
-                                        import.symbol_id, import.module_id.display(),
-                                    ),
-                                }.in_file(module_id));
-                            }
-                            (_, Some(_), Some(_)) => unreachable!("collision in module we're importing from should have been caught when checking it"),
-                            _ => {
-                                for candidate in function_candidates {
-
-                                    match symbol_unifier.insert_function(declaration.id, candidate.signature.clone()) {
-                                        false => {
-                                            errors.push(ErrorInner {
-                                                pos: Some(pos),
-                                                message: format!(
-                                                    "{} conflicts with another symbol",
-                                                    declaration.id,
-                                                ),
-                                            }.in_file(module_id));
-                                        },
-                                        true => {}
-                                    };
-
-                                    let local_key = candidate.clone().id(declaration.id).module(module_id.to_path_buf());
-
-                                    self.functions.insert(local_key.clone());
-                                    symbols.push(
-                                        TypedFunctionSymbolDeclaration::new(
-                                            local_key,
-                                            TypedFunctionSymbol::There(candidate,
-                                            ),
-                                        ).into()
-                                    );
-                                }
-                            }
-                        };
-                    }
-                    Err(e) => {
-                        errors.extend(e);
-                    }
-                };
+        for (id, f, key) in declared {
+            match self.check_function(id, f, module_id, state) {
+                Ok(funct) => functions.push(TypedFunctionSymbolDeclaration::new(
+                    key,
+                    TypedFunctionSymbol::Here(funct),
+                )),
+                Err(e) => errors.extend(e),
             }
-            Symbol::Flat(funct) => {
-                match symbol_unifier.insert_function(declaration.id, funct.typed_signature()) {
-                    false => {
-                        errors.push(
-                            ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "{} conflicts with another symbol",
-                                    declaration.id
-                                ),
-                            }
-                            .in_file(module_id),
-                        );
-                    }
-                    true => {}
-                };
+        }
 
-                self.functions.insert(
-                    DeclarationFunctionKey::with_location(module_id.to_path_buf(), declaration.id)
-                        .signature(funct.typed_signature()),
-                );
-                symbols.push(
-                    TypedFunctionSymbolDeclaration::new(
-                        DeclarationFunctionKey::with_location(
-                            module_id.to_path_buf(),
-                            declaration.id,
-                        )
-                        .signature(funct.typed_signature()),
-                        TypedFunctionSymbol::Flat(funct),
-                    )
-                    .into(),
-                );
-            }
-            _ => unreachable!(),
-        };
+        self.in_impl = false;
+
+        state.unbind_type(module_id, "Self", shadowed);
 
-        // return if any errors occurred
         if !errors.is_empty() {
             return Err(errors);
         }
 
-        Ok(())
+        if let Some(t) = t {
+            state.impls.push(ImplDeclaration { trait_id: t.id, ty });
+        }
+
+        Ok((impl_module, functions))
     }
 
-    fn check_module(
+    fn check_type_generic_function_declaration(
         &mut self,
+        id: Identifier<'ast>,
+        funct_node: FunctionNode<'ast>,
         module_id: &ModuleId,
         state: &mut State<'ast, T>,
-    ) -> Result<(), Vec<Error>> {
-        let mut checked_symbols = TypedSymbolDeclarations::new();
+    ) -> Result<(TypeGenericFunction<'ast, T>, TypedFunction<'ast, T>), Vec<ErrorInner>> {
+        let mut errors = vec![];
+        let mut parameters: Vec<TypeParameter<'ast, T>> = vec![];
 
-        // check if the module was already removed from the untyped ones
-        let to_insert = match state.modules.remove(module_id) {
-            // if it was, do nothing
-            None => None,
-            // if it was not, check it
-            Some(module) => {
-                // create default entries for this module
-                state.types.entry(module_id.to_path_buf()).or_default();
-                state.constants.entry(module_id.to_path_buf()).or_default();
+        for p in &funct_node.value.signature.type_generics {
+            let pos = Some(p.pos());
+            let p = &p.value;
 
-                // we keep track of the introduced symbols to avoid collisions between types and functions
-                let mut symbol_unifier = SymbolUnifier::default();
+            self.use_symbol(p.bound);
 
-                // we go through symbol declarations and check them
-                for declaration in module.symbols {
-                    self.check_symbol_declaration(
-                        declaration,
-                        module_id,
-                        state,
-                        &mut checked_symbols,
-                        &mut symbol_unifier,
-                    )?
-                }
+            let declared_type = state
+                .types
+                .get(module_id)
+                .map(|types| types.contains_key(p.id))
+                .unwrap_or(false);
 
-                Some(TypedModule {
-                    symbols: checked_symbols,
-                })
-            }
-        };
+            match state
+                .traits
+                .get(module_id)
+                .and_then(|traits| traits.get(p.bound))
+            {
+                None => errors.push(ErrorInner {
+                    pos,
+                    message: format!("Undefined trait {}", p.bound),
+                }),
+                Some(_) if parameters.iter().any(|q| q.id == p.id) => errors.push(ErrorInner {
+                    pos,
+                    message: format!("Type parameter {} is already declared", p.id),
+                }),
+                Some(_) if declared_type => errors.push(ErrorInner {
+                    pos,
+                    message: format!("Type parameter {p} conflicts with type {p}", p = p.id),
+                }),
+                Some(t) => {
+                    let placeholder = type_placeholder(module_id, id, p.id);
+
+                    let functions = t
+                        .functions
+                        .iter()
+                        .map(|(f_id, signature)| {
+                            DeclarationFunctionKey::with_location(t.id.impl_module(), *f_id)
+                                .signature(substitute_signature(
+                                    signature.clone(),
+                                    &[(t.placeholder.clone(), placeholder.clone())],
+                                ))
+                        })
+                        .collect();
 
-        // insert into typed_modules if we checked anything
-        if let Some(typed_module) = to_insert {
-            // there should be no checked module at that key just yet, if there is we have a collision or we checked something twice
-            assert!(state
-                .typed_modules
-                .insert(module_id.to_path_buf(), typed_module)
-                .is_none());
-        };
+                    parameters.push(TypeParameter {
+                        id: p.id,
+                        bound: t.id.clone(),
+                        placeholder,
+                        functions,
+                    });
+                }
+            }
+        }
 
-        Ok(())
-    }
+        // function parameters are bound to functions at each call, so they are not part of the signature of the function
+        let mut funct_node = funct_node;
+        let mut function_arguments = vec![];
+        let mut value_arguments = vec![];
+        let mut value_inputs = vec![];
 
-    fn check_single_main(module: &TypedModule<T>) -> Result<(), ErrorInner> {
-        match module
-            .functions_iter()
+        for (index, (argument, ty)) in std::mem::take(&mut funct_node.value.arguments)
             .into_iter()
-            .filter(|d| d.key.id == "main")
-            .count()
+            .zip(std::mem::take(&mut funct_node.value.signature.inputs))
+            .enumerate()
         {
-            1 => Ok(()),
-            0 => Err(ErrorInner {
-                pos: None,
-                message: "No main function found".into(),
-            }),
-            n => Err(ErrorInner {
-                pos: None,
-                message: format!("Only one main function allowed, found {}", n),
-            }),
+            if let UnresolvedType::Function(..) = ty.value {
+                function_arguments.push((index, argument, ty));
+            } else {
+                value_arguments.push(argument);
+                value_inputs.push(ty);
+            }
         }
-    }
-
-    fn check_for_variable(
-        &mut self,
-        var: VariableNode<'ast>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<Variable<'ast, T>, Vec<ErrorInner>> {
-        let pos = var.pos();
-
-        let var = self.check_variable(var, module_id, types)?;
 
-        match var.get_type() {
-            Type::Uint(UBitwidth::B32) => Ok(()),
-            t => Err(vec![ErrorInner {
-                pos: Some(pos),
-                message: format!("Variable in for loop cannot have type {}", t),
-            }]),
-        }?;
+        for (i, (_, argument, _)) in function_arguments.iter().enumerate() {
+            let argument_id = argument.value.id.value.id;
 
-        Ok(var)
-    }
+            if value_arguments
+                .iter()
+                .map(|a| a.value.id.value.id)
+                .chain(
+                    function_arguments[..i]
+                        .iter()
+                        .map(|(_, a, _)| a.value.id.value.id),
+                )
+                .any(|id| id == argument_id)
+            {
+                errors.push(ErrorInner {
+                    pos: Some(argument.pos()),
+                    message: format!("Duplicate name in function definition: `{}` was previously declared as an argument, a generic parameter or a constant", argument_id),
+                });
+            }
+        }
 
-    fn id_in_this_scope<I: Into<SourceIdentifier<'ast>>>(&self, id: I) -> CoreIdentifier<'ast> {
-        // in the semantic checker, 0 is top level, 1 is function level. For shadowing, we start with 0 at function level
-        // hence the offset of 1
-        assert!(
-            self.scope.level > 0,
-            "CoreIdentifier cannot be declared in the global scope"
-        );
-        CoreIdentifier::from(ShadowedIdentifier::shadow(id.into(), self.scope.level - 1))
-    }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
 
-    fn check_function(
-        &mut self,
-        id: Identifier<'ast>,
-        funct_node: FunctionNode<'ast>,
-        module_id: &ModuleId,
-        state: &State<'ast, T>,
-    ) -> Result<TypedFunction<'ast, T>, Vec<ErrorInner>> {
-        assert!(self.return_type.is_none());
+        funct_node.value.arguments = value_arguments;
+        funct_node.value.signature.inputs = value_inputs;
 
-        self.enter_scope();
+        // type parameters stand for opaque types in the signature
+        let shadowed: Vec<_> = parameters
+            .iter()
+            .map(|p| state.bind_type(module_id, p.id, p.placeholder.clone()))
+            .collect();
 
-        let pos = funct_node.pos();
+        let signature = self.check_signature(funct_node.value.signature.clone(), module_id, state);
+        self.return_type = None;
 
-        let mut errors = vec![];
-        let funct = funct_node.value;
-        let mut signature = None;
+        let function_parameters: Result<Vec<_>, _> = function_arguments
+            .into_iter()
+            .map(|(index, argument, ty)| {
+                self.check_function_type(ty, module_id, state)
+                    .map(|signature| FunctionParameter {
+                        id: argument.value.id.value.id,
+                        index,
+                        signature,
+                    })
+            })
+            .collect();
 
-        assert_eq!(funct.arguments.len(), funct.signature.inputs.len());
+        for (p, shadowed) in parameters.iter().zip(shadowed) {
+            state.unbind_type(module_id, p.id, shadowed);
+        }
 
-        let mut arguments_checked = vec![];
+        let signature = signature?;
+        let function_parameters = function_parameters.map_err(|e| vec![e])?;
 
-        let mut statements_checked = vec![];
+        // type arguments are inferred from the arguments of calls
+        for (p, g) in parameters
+            .iter()
+            .zip(&funct_node.value.signature.type_generics)
+        {
+            if !signature
+                .inputs
+                .iter()
+                .any(|ty| contains_type(ty, &p.placeholder))
+            {
+                errors.push(ErrorInner {
+                    pos: Some(g.pos()),
+                    message: format!(
+                        "Type parameter {} must be used in the type of an argument",
+                        p.id
+                    ),
+                });
+            }
+        }
 
-        match self.check_signature(funct.signature, module_id, state) {
-            Ok(s) => {
-                // initialise generics map
-                let mut generics: GenericsAssignment<'ast, T> = GGenericsAssignment::default();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
 
-                // define variables for the constants
-                for generic in &s.generics {
-                    let generic = match generic.clone().unwrap() {
-                        DeclarationConstant::Generic(g) => g,
-                        _ => unreachable!(),
-                    };
+        let declaration = TypeGenericFunction {
+            module: module_id.to_path_buf(),
+            id,
+            parameters,
+            function_parameters,
+            signature,
+        };
 
-                    // for declaration signatures, generics cannot be ignored
-                    generics.0.insert(
-                        generic.clone(),
-                        UExpression::identifier(self.id_in_this_scope(generic.name()).into())
-                            .annotate(UBitwidth::B32),
-                    );
+        // make the function visible in its own body so that it can call itself
+        self.type_generic_functions.insert(id, declaration.clone());
 
-                    //we don't have to check for conflicts here, because this was done when checking the signature
-                    self.insert_into_scope(generic.name(), Type::Uint(UBitwidth::B32), false);
-                }
+        let key = DeclarationFunctionKey::with_location(module_id.to_path_buf(), id)
+            .signature(declaration.signature.clone());
 
-                for (arg, decl_ty) in funct.arguments.into_iter().zip(s.inputs.iter()) {
-                    let pos = arg.pos();
+        // the body is checked once, with the opaque types. Calls to its function parameters go through keys which
+        // are resolved to the functions passed at each call site
+        self.function_parameters = declaration
+            .function_parameters
+            .iter()
+            .enumerate()
+            .map(|(index, p)| (p.id, key.function_parameter(index, p.signature.clone())))
+            .collect();
 
-                    let arg = arg.value;
+        let impl_functions = self.impl_functions.clone();
 
-                    // parameters defined on a non-entrypoint function should not have visibility modifiers
-                    if (state.main_id != module_id || id != "main") && arg.is_private.is_some() {
-                        errors.push(ErrorInner {
-                            pos: Some(pos),
-                            message:
-                                "Visibility modifiers on arguments are only allowed on the entrypoint function"
-                                    .into(),
-                        });
-                    }
-
-                    let decl_v = DeclarationVariable::new(
-                        self.id_in_this_scope(arg.id.value.id),
-                        decl_ty.clone(),
-                        arg.id.value.is_mutable,
-                    );
-
-                    let is_mutable = arg.id.value.is_mutable;
-
-                    let ty = specialize_declaration_type(decl_v.clone()._type, &generics).unwrap();
-
-                    assert_eq!(self.scope.level, 1);
-
-                    let id = arg.id.value.id;
-                    let info = IdentifierInfo {
-                        id: decl_v.id.id.clone(),
-                        ty,
-                        is_mutable,
-                    };
-                    match self.scope.insert(id, info) {
-                        false => {}
-                        true => {
-                            errors.push(ErrorInner {
-                                pos: Some(pos),
-                                message: format!("Duplicate name in function definition: `{}` was previously declared as an argument, a generic parameter or a constant", arg.id.value.id)
-                            });
-                        }
-                    };
-
-                    arguments_checked.push(DeclarationParameter {
-                        id: decl_v,
-                        private: arg.is_private.unwrap_or(false),
-                    });
-                }
+        // the functions of the trait which take `self` can be called as methods on values of the type parameter
+        for p in &declaration.parameters {
+            let t = &state.traits[&p.bound.module][p.bound.id];
+            self.impl_functions.extend(
+                p.functions
+                    .iter()
+                    .filter(|key| t.methods.contains(&key.id))
+                    .map(|key| ImplFunction {
+                        ty: p.placeholder.clone(),
+                        key: key.clone(),
+                        is_method: true,
+                    }),
+            );
+        }
 
-                let mut found_return = false;
+        self.type_parameters = declaration
+            .parameters
+            .iter()
+            .map(|p| (p.id, p.clone()))
+            .collect();
 
-                for stat in funct.statements.into_iter() {
-                    let pos = Some(stat.pos());
+        let shadowed: Vec<_> = declaration
+            .parameters
+            .iter()
+            .map(|p| state.bind_type(module_id, p.id, p.placeholder.clone()))
+            .collect();
+
+        // the anonymous functions of the body may use the type parameters, so they are checked along with it
+        let res = self
+            .check_function(id, funct_node, module_id, state)
+            .and_then(|funct| {
+                self.check_lambdas(module_id, state)
+                    .map(|lambdas| (funct, lambdas))
+            });
+
+        for (p, shadowed) in declaration.parameters.iter().zip(shadowed) {
+            state.unbind_type(module_id, p.id, shadowed);
+        }
 
-                    if let Statement::Return(..) = stat.value {
-                        if found_return {
-                            errors.push(ErrorInner {
-                                pos,
-                                message: "Expected a single return statement".to_string(),
-                            });
-                        }
+        self.function_parameters.clear();
+        self.type_parameters.clear();
+        self.impl_functions = impl_functions;
 
-                        found_return = true;
-                    }
+        let (funct, lambdas) = res?;
 
-                    match self.check_statement(stat, module_id, &state.types) {
-                        Ok(statement) => {
-                            statements_checked.push(statement);
-                        }
-                        Err(e) => {
-                            errors.extend(e);
-                        }
-                    }
-                }
+        for lambda in lambdas {
+            state.declare_function(lambda);
+        }
 
-                if !found_return {
-                    match (&*s.output).is_empty_tuple() {
-                        true => statements_checked
-                            .push(TypedStatement::Return(TypedExpression::empty_tuple())),
-                        false => {
-                            errors.push(ErrorInner {
-                                pos: Some(pos),
-                                message: "Expected a return statement".to_string(),
-                            });
-                        }
-                    }
-                }
+        Ok((declaration, funct))
+    }
 
-                signature = Some(s);
-            }
-            Err(e) => {
-                errors.extend(e);
-            }
+    // check the type of a function parameter. It cannot depend on generic parameters, as the functions passed for it
+    // are resolved when the call is checked
+    fn check_function_type(
+        &mut self,
+        ty: UnresolvedTypeNode<'ast>,
+        module_id: &ModuleId,
+        state: &State<'ast, T>,
+    ) -> Result<DeclarationSignature<'ast, T>, ErrorInner> {
+        let (inputs, output) = match ty.value {
+            UnresolvedType::Function(inputs, box output) => (inputs, output),
+            _ => unreachable!(),
         };
 
-        self.exit_scope();
-
-        if !errors.is_empty() {
-            return Err(errors);
-        }
+        let inputs = inputs
+            .into_iter()
+            .map(|t| {
+                self.check_declaration_type(
+                    t,
+                    module_id,
+                    state,
+                    &BTreeMap::new(),
+                    &mut HashSet::default(),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        self.return_type = None;
+        let output = self.check_declaration_type(
+            output,
+            module_id,
+            state,
+            &BTreeMap::new(),
+            &mut HashSet::default(),
+        )?;
 
-        Ok(TypedFunction {
-            arguments: arguments_checked,
-            statements: statements_checked,
-            signature: signature.unwrap(),
-        })
+        Ok(DeclarationSignature::new().inputs(inputs).output(output))
     }
 
-    fn check_signature(
+    // check the anonymous functions found so far, as well as the ones they contain in turn. They only see the global
+    // scope, as they cannot capture variables
+    fn check_lambdas(
         &mut self,
-        signature: UnresolvedSignature<'ast>,
         module_id: &ModuleId,
         state: &State<'ast, T>,
-    ) -> Result<DeclarationSignature<'ast, T>, Vec<ErrorInner>> {
+    ) -> Result<Vec<TypedFunctionSymbolDeclaration<'ast, T>>, Vec<ErrorInner>> {
         let mut errors = vec![];
-        let mut inputs = vec![];
-        let mut generics = vec![];
+        let mut declarations = vec![];
+
+        while let Some(lambda) = self.lambdas.pop() {
+            assert_eq!(self.scope.level, 0);
+
+            let mut checker = Checker {
+                scope: self.scope.clone(),
+                functions: self.functions.clone(),
+                type_generic_functions: self.type_generic_functions.clone(),
+                type_parameters: self.type_parameters.clone(),
+                impl_functions: self.impl_functions.clone(),
+                ..Checker::default()
+            };
 
-        let mut generics_map = BTreeMap::new();
+            let res = checker.check_function(lambda.key.id, lambda.node, module_id, state);
 
-        for (index, g) in signature.generics.iter().enumerate() {
-            if state
-                .constants
-                .get(module_id)
-                .and_then(|m| m.get(g.value))
-                .is_some()
-            {
-                errors.push(ErrorInner {
-                    pos: Some(g.pos()),
-                    message: format!(
-                        "Generic parameter {p} conflicts with constant symbol {p}",
-                        p = g.value
-                    ),
-                });
-            } else {
-                match generics_map.insert(g.value, index).is_none() {
-                    true => {
-                        generics.push(Some(DeclarationConstant::Generic(
-                            GenericIdentifier::with_name(g.value).with_index(index),
-                        )));
-                    }
-                    false => {
-                        errors.push(ErrorInner {
-                            pos: Some(g.pos()),
-                            message: format!("Generic parameter {} is already declared", g.value),
-                        });
-                    }
+            self.used_symbols.extend(checker.used_symbols);
+            self.warnings.extend(checker.warnings);
+
+            match res {
+                Ok(funct) => {
+                    self.lambdas.extend(checker.lambdas);
+                    self.trait_bounds.extend(checker.trait_bounds);
+                    self.combinators.extend(checker.combinators);
+                    declarations.push(TypedFunctionSymbolDeclaration::new(
+                        lambda.key,
+                        TypedFunctionSymbol::Here(funct),
+                    ));
                 }
+                Err(e) => errors.extend(e),
             }
         }
 
-        for t in signature.inputs {
-            match self.check_declaration_type(
-                t,
-                module_id,
-                state,
-                &generics_map,
-                &mut HashSet::default(),
-            ) {
-                Ok(t) => {
-                    inputs.push(t);
-                }
-                Err(e) => {
-                    errors.push(e);
-                }
-            }
+        if !errors.is_empty() {
+            return Err(errors);
         }
 
-        match signature
-            .output
-            .map(|o| {
-                self.check_declaration_type(
-                    o,
-                    module_id,
-                    state,
-                    &generics_map,
-                    &mut HashSet::default(),
-                )
+        Ok(declarations)
+    }
+
+    // check that the type arguments of the calls made so far implement the traits their type parameters are bound by
+    fn check_trait_bounds(
+        &mut self,
+        module_id: &ModuleId,
+        state: &State<'ast, T>,
+    ) -> Result<(), Vec<Error>> {
+        let errors: Vec<_> = std::mem::take(&mut self.trait_bounds)
+            .into_iter()
+            .filter(|b| {
+                !state
+                    .impls
+                    .iter()
+                    .any(|i| i.trait_id == b.bound && i.ty == b.ty)
             })
-            .unwrap_or_else(|| Ok(DeclarationType::Tuple(GTupleType::new(vec![]))))
-        {
-            Ok(output) => {
-                if !errors.is_empty() {
-                    return Err(errors);
+            .map(|b| {
+                ErrorInner {
+                    pos: Some(b.pos),
+                    message: format!("Type {} does not implement trait {}", b.ty, b.bound.id),
                 }
+                .in_file(module_id)
+            })
+            .collect();
 
-                self.return_type = Some(output.clone());
-                Ok(DeclarationSignature::new()
-                    .generics(generics)
-                    .inputs(inputs)
-                    .output(output))
-            }
-            Err(e) => {
-                errors.push(e);
-                Err(errors)
-            }
+        if !errors.is_empty() {
+            return Err(errors);
         }
+
+        Ok(())
     }
 
-    fn check_type(
+    fn check_symbol_declaration(
         &mut self,
-        ty: UnresolvedTypeNode<'ast>,
+        declaration: SymbolDeclarationNode<'ast>,
         module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<Type<'ast, T>, ErrorInner> {
-        let pos = ty.pos();
-        let ty = ty.value;
-
-        match ty {
-            UnresolvedType::FieldElement => Ok(Type::FieldElement),
-            UnresolvedType::Boolean => Ok(Type::Boolean),
-            UnresolvedType::Uint(bitwidth) => Ok(Type::uint(bitwidth)),
-            UnresolvedType::Array(t, size) => {
-                let size = self.check_expression(size, module_id, types)?;
-
-                let ty = size.get_type();
+        state: &mut State<'ast, T>,
+        symbols: &mut TypedSymbolDeclarations<'ast, T>,
+        symbol_unifier: &mut SymbolUnifier<'ast, T>,
+    ) -> Result<(), Vec<Error>> {
+        let mut errors: Vec<Error> = vec![];
 
-                let size = match size {
-                    TypedExpression::Uint(e) => match e.bitwidth() {
-                        UBitwidth::B32 => Ok(e),
-                        _ => Err(ErrorInner {
-                            pos: Some(pos),
-                            message: format!(
-                            "Expected array dimension to be a u32 constant, found {} of type {}",
-                            e, ty
-                        ),
-                        }),
-                    },
-                    TypedExpression::Int(v) => {
-                        UExpression::try_from_int(v.clone(), &UBitwidth::B32).map_err(|_| {
-                            ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                            "Expected array dimension to be a u32 constant, found {} of type {}",
-                            v, ty
-                        ),
-                            }
-                        })
-                    }
-                    _ => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Expected array dimension to be a u32 constant, found {} of type {}",
-                            size, ty
-                        ),
-                    }),
-                }?;
+        let pos = declaration.pos();
+        let declaration = declaration.value;
+
+        match declaration.symbol.clone() {
+            Symbol::Here(SymbolDefinition::Struct(t)) => {
+                match self.check_struct_type_declaration(
+                    declaration.id.to_string(),
+                    t.clone(),
+                    module_id,
+                    state,
+                ) {
+                    Ok(ty) => {
+                        match symbol_unifier.insert_type(declaration.id) {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {
+                                // there should be no entry in the map for this type yet
+                                assert!(state
+                                    .types
+                                    .entry(module_id.to_path_buf())
+                                    .or_default()
+                                    .insert(
+                                        declaration.id.to_string(),
+                                        UserDeclarationType {
+                                            generics: ty
+                                                .generics
+                                                .clone()
+                                                .into_iter()
+                                                .map(|g| g.unwrap())
+                                                .collect(),
+                                            ty: DeclarationType::Struct(ty),
+                                        }
+                                    )
+                                    .is_none());
+                            }
+                        };
+                    }
+                    Err(e) => errors.extend(e.into_iter().map(|inner| Error {
+                        inner,
+                        module_id: module_id.to_path_buf(),
+                    })),
+                }
+            }
+            Symbol::Here(SymbolDefinition::Enum(e)) => {
+                match self.check_enum_type_declaration(
+                    declaration.id.to_string(),
+                    e,
+                    module_id,
+                    state,
+                ) {
+                    Ok(ty) => {
+                        match symbol_unifier.insert_type(declaration.id) {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {
+                                // there should be no entry in the map for this type yet
+                                assert!(state
+                                    .types
+                                    .entry(module_id.to_path_buf())
+                                    .or_default()
+                                    .insert(
+                                        declaration.id.to_string(),
+                                        UserDeclarationType {
+                                            generics: vec![],
+                                            ty: DeclarationType::Enum(ty),
+                                        }
+                                    )
+                                    .is_none());
+                            }
+                        };
+                    }
+                    Err(e) => errors.extend(e.into_iter().map(|inner| inner.in_file(module_id))),
+                }
+            }
+            Symbol::Here(SymbolDefinition::Constant(c)) => {
+                match self.check_constant_definition(declaration.id, c, module_id, state) {
+                    Ok(c) => {
+                        match symbol_unifier.insert_constant(declaration.id) {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {
+                                symbols.push(
+                                    TypedConstantSymbolDeclaration::new(
+                                        CanonicalConstantIdentifier::new(
+                                            declaration.id,
+                                            module_id.into(),
+                                        ),
+                                        TypedConstantSymbol::Here(c.clone()),
+                                    )
+                                    .into(),
+                                );
+                                let id = declaration.id;
+
+                                let info = IdentifierInfo {
+                                    id: CoreIdentifier::Constant(CanonicalConstantIdentifier::new(
+                                        declaration.id,
+                                        module_id.into(),
+                                    )),
+                                    ty: c.get_type(),
+                                    is_mutable: false,
+                                    declaration: None,
+                                };
+                                assert_eq!(self.scope.level, 0);
+                                assert!(!self.scope.insert(id, info));
+                                assert!(state
+                                    .constants
+                                    .entry(module_id.to_path_buf())
+                                    .or_default()
+                                    .insert(declaration.id, c.ty)
+                                    .is_none());
+                            }
+                        };
+                    }
+                    Err(e) => {
+                        errors.push(e.in_file(module_id));
+                    }
+                }
+            }
+            Symbol::Here(SymbolDefinition::StaticAssertion(e, message)) => {
+                match self.check_static_assertion(e, message, pos, module_id, &state.types) {
+                    Ok((e, metadata)) => {
+                        symbols.push(TypedSymbolDeclaration::StaticAssertion(e, metadata))
+                    }
+                    Err(e) => {
+                        errors.push(e.in_file(module_id));
+                    }
+                }
+            }
+            Symbol::Here(SymbolDefinition::Type(t)) => {
+                match self.check_type_definition(t, module_id, state) {
+                    Ok(ty) => {
+                        match symbol_unifier.insert_type(declaration.id) {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id,
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {
+                                assert!(state
+                                    .types
+                                    .entry(module_id.to_path_buf())
+                                    .or_default()
+                                    .insert(declaration.id.to_string(), ty)
+                                    .is_none());
+                            }
+                        };
+                    }
+                    Err(e) => {
+                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)));
+                    }
+                }
+            }
+            Symbol::Here(SymbolDefinition::Trait(t)) => {
+                match self.check_trait_definition(declaration.id, t, module_id, state) {
+                    Ok(t) => {
+                        match symbol_unifier.insert_type(declaration.id) {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id,
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {
+                                assert!(state
+                                    .traits
+                                    .entry(module_id.to_path_buf())
+                                    .or_default()
+                                    .insert(declaration.id, t)
+                                    .is_none());
+                            }
+                        };
+                    }
+                    Err(e) => {
+                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)));
+                    }
+                }
+            }
+            Symbol::Here(SymbolDefinition::Impl(i)) => {
+                match self.check_impl_definition(i, module_id, state) {
+                    Ok((impl_module, functions)) => {
+                        // the functions of an `impl` block live in a module of their own, so that they cannot clash with the
+                        // functions of the module
+                        state
+                            .typed_modules
+                            .entry(impl_module)
+                            .or_insert_with(|| TypedModule { symbols: vec![] })
+                            .symbols
+                            .extend(functions.into_iter().map(|f| f.into()));
+                    }
+                    Err(e) => {
+                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)));
+                    }
+                }
+            }
+            Symbol::Here(SymbolDefinition::Function(f))
+                if !f.value.signature.type_generics.is_empty() || f.value.takes_functions() =>
+            {
+                match self.check_type_generic_function_declaration(
+                    declaration.id,
+                    f,
+                    module_id,
+                    state,
+                ) {
+                    Ok((function, funct)) => {
+                        match symbol_unifier.insert_type_generic_function(declaration.id) {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {}
+                        };
+
+                        symbols.push(
+                            TypedFunctionSymbolDeclaration::new(
+                                DeclarationFunctionKey::with_location(
+                                    module_id.to_path_buf(),
+                                    declaration.id,
+                                )
+                                .signature(funct.signature.clone()),
+                                TypedFunctionSymbol::Here(funct),
+                            )
+                            .into(),
+                        );
+
+                        state
+                            .type_generic_functions
+                            .entry(module_id.to_path_buf())
+                            .or_default()
+                            .insert(declaration.id, function);
+                    }
+                    Err(e) => {
+                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)));
+                    }
+                }
+            }
+            Symbol::Here(SymbolDefinition::Function(f)) => {
+                match self.check_function(declaration.id, f, module_id, state) {
+                    Ok(funct) => {
+                        match symbol_unifier
+                            .insert_function(declaration.id, funct.signature.clone())
+                        {
+                            false => errors.push(
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "{} conflicts with another symbol",
+                                        declaration.id
+                                    ),
+                                }
+                                .in_file(module_id),
+                            ),
+                            true => {}
+                        };
+
+                        self.functions.insert(
+                            DeclarationFunctionKey::with_location(
+                                module_id.to_path_buf(),
+                                declaration.id,
+                            )
+                            .signature(funct.signature.clone()),
+                        );
+                        symbols.push(
+                            TypedFunctionSymbolDeclaration::new(
+                                DeclarationFunctionKey::with_location(
+                                    module_id.to_path_buf(),
+                                    declaration.id,
+                                )
+                                .signature(funct.signature.clone()),
+                                TypedFunctionSymbol::Here(funct),
+                            )
+                            .into(),
+                        );
+                    }
+                    Err(e) => {
+                        errors.extend(e.into_iter().map(|inner| inner.in_file(module_id)));
+                    }
+                }
+            }
+            Symbol::There(import) => {
+                let pos = import.pos();
+                let import = import.value;
+
+                match Checker::default().check_module(&import.module_id, state) {
+                    Ok(()) => {
+                        // find candidate among the functions with type parameters
+                        let type_generic_candidate = state
+                            .type_generic_functions
+                            .entry(import.module_id.to_path_buf())
+                            .or_default()
+                            .get(import.symbol_id)
+                            .cloned();
+
+                        // find candidates in the checked module. Functions with function parameters are declared
+                        // there too, but they are called as functions with type parameters
+                        let function_candidates: Vec<_> = state
+                            .typed_modules
+                            .get(&import.module_id)
+                            .unwrap()
+                            .functions_iter()
+                            .into_iter()
+                            .filter(|d| {
+                                d.key.id == import.symbol_id && type_generic_candidate.is_none()
+                            })
+                            .map(|d| DeclarationFunctionKey {
+                                module: import.module_id.to_path_buf(),
+                                id: import.symbol_id,
+                                signature: d.symbol.signature(&state.typed_modules).clone(),
+                            })
+                            .collect();
+
+                        // find candidates in the types
+                        let type_candidate = state
+                            .types
+                            .entry(import.module_id.to_path_buf())
+                            .or_default()
+                            .get(import.symbol_id)
+                            .cloned();
+
+                        // find constant definition candidate
+                        let const_candidate = state
+                            .constants
+                            .entry(import.module_id.to_path_buf())
+                            .or_default()
+                            .iter()
+                            .find(|(i, _)| *i == &import.symbol_id)
+                            .map(|(_, c)| c)
+                            .cloned();
+
+                        // find trait candidate
+                        let trait_candidate = state
+                            .traits
+                            .entry(import.module_id.to_path_buf())
+                            .or_default()
+                            .get(import.symbol_id)
+                            .cloned();
+
+                        match (function_candidates.len(), type_candidate, const_candidate, trait_candidate, type_generic_candidate) {
+                            (0, Some(t), None, _, _) => {
+                                // rename the type to the declared symbol
+                                let t = UserDeclarationType {
+                                    ty: match t.ty {
+                                        DeclarationType::Struct(t) => DeclarationType::Struct(DeclarationStructType {
+                                            location: Some(StructLocation {
+                                                name: declaration.id.into(),
+                                                module: module_id.to_path_buf()
+                                            }),
+                                            ..t
+                                        }),
+                                        _ => t.ty // all other cases
+                                    },
+                                    ..t
+                                };
+
+                                // we imported a type, so the symbol it gets bound to should not already exist
+                                match symbol_unifier.insert_type(declaration.id) {
+                                    false => {
+                                        errors.push(Error {
+                                            module_id: module_id.to_path_buf(),
+                                            inner: ErrorInner {
+                                                pos: Some(pos),
+                                                message: format!(
+                                                    "{} conflicts with another symbol",
+                                                    declaration.id,
+                                                ),
+                                            }});
+                                    }
+                                    true => {}
+                                };
+                                state
+                                    .types
+                                    .entry(module_id.to_path_buf())
+                                    .or_default()
+                                    .insert(declaration.id.to_string(), t);
+                            }
+                            (0, None, Some(ty), _, _) => {
+                                match symbol_unifier.insert_constant(declaration.id) {
+                                    false => {
+                                        errors.push(Error {
+                                            module_id: module_id.to_path_buf(),
+                                            inner: ErrorInner {
+                                                pos: Some(pos),
+                                                message: format!(
+                                                    "{} conflicts with another symbol",
+                                                    declaration.id,
+                                                ),
+                                            }});
+                                    }
+                                    true => {
+                                        let imported_id = CanonicalConstantIdentifier::new(import.symbol_id, import.module_id);
+                                        let id = CanonicalConstantIdentifier::new(declaration.id, module_id.into());
+
+                                        symbols.push(TypedConstantSymbolDeclaration::new(
+                                            id.clone(),
+                                            TypedConstantSymbol::There(imported_id)
+                                        ).into());
+
+                                        let id = declaration.id;
+                                        let info = IdentifierInfo {
+                                            id: CoreIdentifier::Constant(CanonicalConstantIdentifier::new(
+                                                declaration.id,
+                                                module_id.into(),
+                                            )),
+                                            ty: zokrates_ast::typed::types::try_from_g_type(ty.clone()).unwrap(),
+                                            is_mutable: false,
+                                            declaration: None,
+                                        };
+                                        assert_eq!(self.scope.level, 0);
+                                        assert!(!self.scope.insert(id, info));
+
+                                        state
+                                            .constants
+                                            .entry(module_id.to_path_buf())
+                                            .or_default()
+                                            .insert(declaration.id, ty);
+                                    }
+                                };
+                            }
+                            (0, None, None, Some(t), _) => {
+                                match symbol_unifier.insert_type(declaration.id) {
+                                    false => {
+                                        errors.push(ErrorInner {
+                                            pos: Some(pos),
+                                            message: format!(
+                                                "{} conflicts with another symbol",
+                                                declaration.id,
+                                            ),
+                                        }.in_file(module_id));
+                                    }
+                                    true => {
+                                        state
+                                            .traits
+                                            .entry(module_id.to_path_buf())
+                                            .or_default()
+                                            .insert(declaration.id, t);
+                                    }
+                                };
+                            }
+                            (0, None, None, None, Some(f)) => {
+                                match symbol_unifier.insert_type_generic_function(declaration.id) {
+                                    false => {
+                                        errors.push(ErrorInner {
+                                            pos: Some(pos),
+                                            message: format!(
+                                                "{} conflicts with another symbol",
+                                                declaration.id,
+                                            ),
+                                        }.in_file(module_id));
+                                    }
+                                    true => {
+                                        self.type_generic_functions.insert(declaration.id, f);
+                                    }
+                                };
+                            }
+                            (0, None, None, None, None) => {
+                                errors.push(ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "Could not find symbol {} in module {}",
+                                        import.symbol_id, import.module_id.display(),
+                                    ),
+                                }.in_file(module_id));
+                            }
+                            (_, Some(_), Some(_), _, _) => unreachable!("collision in module we're importing from should have been caught when checking it"),
+                            _ => {
+                                for candidate in function_candidates {
+
+                                    match symbol_unifier.insert_function(declaration.id, candidate.signature.clone()) {
+                                        false => {
+                                            errors.push(ErrorInner {
+                                                pos: Some(pos),
+                                                message: format!(
+                                                    "{} conflicts with another symbol",
+                                                    declaration.id,
+                                                ),
+                                            }.in_file(module_id));
+                                        },
+                                        true => {}
+                                    };
+
+                                    let local_key = candidate.clone().id(declaration.id).module(module_id.to_path_buf());
+
+                                    self.functions.insert(local_key.clone());
+                                    symbols.push(
+                                        TypedFunctionSymbolDeclaration::new(
+                                            local_key,
+                                            TypedFunctionSymbol::There(candidate,
+                                            ),
+                                        ).into()
+                                    );
+                                }
+                            }
+                        };
+                    }
+                    Err(e) => {
+                        errors.extend(e);
+                    }
+                };
+            }
+            Symbol::Flat(funct) => {
+                match symbol_unifier.insert_function(declaration.id, funct.typed_signature()) {
+                    false => {
+                        errors.push(
+                            ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "{} conflicts with another symbol",
+                                    declaration.id
+                                ),
+                            }
+                            .in_file(module_id),
+                        );
+                    }
+                    true => {}
+                };
+
+                self.functions.insert(
+                    DeclarationFunctionKey::with_location(module_id.to_path_buf(), declaration.id)
+                        .signature(funct.typed_signature()),
+                );
+                symbols.push(
+                    TypedFunctionSymbolDeclaration::new(
+                        DeclarationFunctionKey::with_location(
+                            module_id.to_path_buf(),
+                            declaration.id,
+                        )
+                        .signature(funct.typed_signature()),
+                        TypedFunctionSymbol::Flat(funct),
+                    )
+                    .into(),
+                );
+            }
+            _ => unreachable!(),
+        };
+
+        // check the anonymous functions this symbol contains
+        match self.check_lambdas(module_id, state) {
+            Ok(lambdas) => {
+                for lambda in lambdas {
+                    state.declare_function(lambda);
+                }
+            }
+            Err(e) => errors.extend(e.into_iter().map(|inner| inner.in_file(module_id))),
+        }
+
+        for combinator in std::mem::take(&mut self.combinators) {
+            state.declare_combinator(module_id, combinator);
+        }
+
+        // return if any errors occurred
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.check_trait_bounds(module_id, state)
+    }
+
+    fn check_module(
+        &mut self,
+        module_id: &ModuleId,
+        state: &mut State<'ast, T>,
+    ) -> Result<(), Vec<Error>> {
+        let mut checked_symbols = TypedSymbolDeclarations::new();
+
+        // check if the module was already removed from the untyped ones
+        let to_insert = match state.modules.remove(module_id) {
+            // if it was, do nothing
+            None => None,
+            // if it was not, check it
+            Some(module) => {
+                // create default entries for this module
+                state.types.entry(module_id.to_path_buf()).or_default();
+                state.constants.entry(module_id.to_path_buf()).or_default();
+
+                // we keep track of the introduced symbols to avoid collisions between types and functions
+                let mut symbol_unifier = SymbolUnifier::default();
+
+                // we go through symbol declarations and check them
+                for declaration in module.symbols {
+                    // imports and functions which are not visible to other modules must be used in this one. The
+                    // imports of the main module are not visible either, as it is not imported
+                    let lint = match &declaration.value.symbol {
+                        _ if declaration.value.id == "main" => None,
+                        Symbol::There(..) | Symbol::Flat(..)
+                            if !declaration.value.is_public || module_id == state.main_id =>
+                        {
+                            Some(Lint::UnusedImports)
+                        }
+                        Symbol::Here(SymbolDefinition::Function(..))
+                            if !declaration.value.is_public =>
+                        {
+                            Some(Lint::UnusedFunctions)
+                        }
+                        _ => None,
+                    };
+
+                    if let Some(lint) = lint {
+                        state.local_symbols.push(LocalSymbol {
+                            module_id: module_id.to_path_buf(),
+                            id: declaration.value.id.to_string(),
+                            lint,
+                            pos: declaration.pos(),
+                        });
+                    }
+
+                    self.check_symbol_declaration(
+                        declaration,
+                        module_id,
+                        state,
+                        &mut checked_symbols,
+                        &mut symbol_unifier,
+                    )?
+                }
+
+                state.used_symbols.extend(
+                    std::mem::take(&mut self.used_symbols)
+                        .into_iter()
+                        .map(|id| (module_id.to_path_buf(), id)),
+                );
+                state.warnings.extend(std::mem::take(&mut self.warnings));
+
+                Some(TypedModule {
+                    symbols: checked_symbols,
+                })
+            }
+        };
+
+        // insert into typed_modules if we checked anything
+        if let Some(typed_module) = to_insert {
+            // there should be no checked module at that key just yet, if there is we have a collision or we checked something twice
+            assert!(state
+                .typed_modules
+                .insert(module_id.to_path_buf(), typed_module)
+                .is_none());
+        };
+
+        Ok(())
+    }
+
+    fn check_single_main(module: &TypedModule<T>) -> Result<(), ErrorInner> {
+        match module
+            .functions_iter()
+            .into_iter()
+            .filter(|d| d.key.id == "main")
+            .count()
+        {
+            1 => Ok(()),
+            0 => Err(ErrorInner {
+                pos: None,
+                message: "No main function found".into(),
+            }),
+            n => Err(ErrorInner {
+                pos: None,
+                message: format!("Only one main function allowed, found {}", n),
+            }),
+        }
+    }
+
+    fn check_for_variable(
+        &mut self,
+        var: VariableNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Variable<'ast, T>, Vec<ErrorInner>> {
+        let pos = var.pos();
+
+        let var = self.check_variable(var, module_id, types)?;
+
+        match var.get_type() {
+            Type::Uint(UBitwidth::B32) => Ok(()),
+            t => Err(vec![ErrorInner {
+                pos: Some(pos),
+                message: format!("Variable in for loop cannot have type {}", t),
+            }]),
+        }?;
+
+        Ok(var)
+    }
+
+    fn id_in_this_scope<I: Into<SourceIdentifier<'ast>>>(&self, id: I) -> CoreIdentifier<'ast> {
+        // in the semantic checker, 0 is top level, 1 is function level. For shadowing, we start with 0 at function level
+        // hence the offset of 1
+        assert!(
+            self.scope.level > 0,
+            "CoreIdentifier cannot be declared in the global scope"
+        );
+        CoreIdentifier::from(ShadowedIdentifier::shadow(id.into(), self.scope.level - 1))
+    }
+
+    fn check_function(
+        &mut self,
+        id: Identifier<'ast>,
+        funct_node: FunctionNode<'ast>,
+        module_id: &ModuleId,
+        state: &State<'ast, T>,
+    ) -> Result<TypedFunction<'ast, T>, Vec<ErrorInner>> {
+        assert!(self.return_type.is_none());
+
+        // functions of `impl` blocks can be called from any module
+        self.impl_functions
+            .extend(state.impl_functions.iter().cloned());
+
+        self.enter_scope();
+
+        let pos = funct_node.pos();
+
+        let mut errors = vec![];
+        let funct = funct_node.value;
+        let mut signature = None;
+
+        assert_eq!(funct.arguments.len(), funct.signature.inputs.len());
+
+        let mut arguments_checked = vec![];
+
+        let mut statements_checked = vec![];
+
+        match self.check_signature(funct.signature, module_id, state) {
+            Ok(s) => {
+                // make the function visible in its own body so that it can call itself. Functions of `impl` blocks are
+                // not called by their name alone, and they are already known
+                if !self.in_impl {
+                    self.functions.insert(
+                        DeclarationFunctionKey::with_location(module_id.to_path_buf(), id)
+                            .signature(s.clone()),
+                    );
+                }
+
+                // initialise generics map
+                let mut generics: GenericsAssignment<'ast, T> = GGenericsAssignment::default();
+
+                // define variables for the constants
+                for generic in &s.generics {
+                    let generic = match generic.clone().unwrap() {
+                        DeclarationConstant::Generic(g) => g,
+                        _ => unreachable!(),
+                    };
+
+                    // for declaration signatures, generics cannot be ignored
+                    generics.0.insert(
+                        generic.clone(),
+                        UExpression::identifier(self.id_in_this_scope(generic.name()).into())
+                            .annotate(UBitwidth::B32),
+                    );
+
+                    //we don't have to check for conflicts here, because this was done when checking the signature
+                    self.insert_into_scope(generic.name(), Type::Uint(UBitwidth::B32), false);
+                }
+
+                for (arg, decl_ty) in funct.arguments.into_iter().zip(s.inputs.iter()) {
+                    let pos = arg.pos();
+
+                    let arg = arg.value;
+
+                    // parameters defined on a non-entrypoint function should not have visibility modifiers
+                    if (state.main_id != module_id || id != "main") && arg.is_private.is_some() {
+                        errors.push(ErrorInner {
+                            pos: Some(pos),
+                            message:
+                                "Visibility modifiers on arguments are only allowed on the entrypoint function"
+                                    .into(),
+                        });
+                    }
+
+                    let decl_v = DeclarationVariable::new(
+                        self.id_in_this_scope(arg.id.value.id),
+                        decl_ty.clone(),
+                        arg.id.value.is_mutable,
+                    );
+
+                    let is_mutable = arg.id.value.is_mutable;
+
+                    let ty = specialize_declaration_type(decl_v.clone()._type, &generics).unwrap();
+
+                    assert_eq!(self.scope.level, 1);
+
+                    let id = arg.id.value.id;
+                    match self.declare_variable(id, ty, is_mutable, arg.id.pos(), None) {
+                        false => {}
+                        true => {
+                            errors.push(ErrorInner {
+                                pos: Some(pos),
+                                message: format!("Duplicate name in function definition: `{}` was previously declared as an argument, a generic parameter or a constant", arg.id.value.id)
+                            });
+                        }
+                    };
+
+                    arguments_checked.push(DeclarationParameter {
+                        id: decl_v,
+                        private: arg.is_private.unwrap_or(false),
+                    });
+                }
+
+                let mut found_return = false;
+
+                for stat in funct.statements.into_iter() {
+                    let pos = Some(stat.pos());
+
+                    if let Statement::Return(..) = stat.value {
+                        if found_return {
+                            errors.push(ErrorInner {
+                                pos,
+                                message: "Expected a single return statement".to_string(),
+                            });
+                        }
+
+                        found_return = true;
+                    }
+
+                    match self.check_lowered_statement(stat, module_id, &state.types) {
+                        Ok(statements) => {
+                            statements_checked.extend(statements);
+                        }
+                        Err(e) => {
+                            errors.extend(e);
+                        }
+                    }
+                }
+
+                if !found_return {
+                    match (&*s.output).is_empty_tuple() {
+                        true => statements_checked
+                            .push(TypedStatement::Return(TypedExpression::empty_tuple())),
+                        false => {
+                            errors.push(ErrorInner {
+                                pos: Some(pos),
+                                message: "Expected a return statement".to_string(),
+                            });
+                        }
+                    }
+                }
+
+                signature = Some(s);
+            }
+            Err(e) => {
+                errors.extend(e);
+            }
+        };
+
+        self.exit_scope();
+
+        let statements_checked = self.check_usage(statements_checked, module_id);
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        self.return_type = None;
+
+        Ok(TypedFunction {
+            arguments: arguments_checked,
+            statements: statements_checked,
+            signature: signature.unwrap(),
+        })
+    }
+
+    // report a condition which does not depend on any variable, as one of its branches is never taken
+    fn check_constant_condition(&mut self, condition: &ExpressionNode<'ast>, module_id: &ModuleId) {
+        if is_literal(&condition.value) {
+            self.warnings.push(Warning::new(
+                Lint::ConstantConditions,
+                module_id,
+                condition.pos().0,
+                format!("Condition `{}` is constant", condition.value),
+            ));
+        }
+    }
+
+    // report the variables of the function being checked which are never read or shadow other ones, as well as the
+    // values assigned with `<--` which are never constrained
+    fn check_usage(
+        &mut self,
+        statements: Vec<TypedStatement<'ast, T>>,
+        module_id: &ModuleId,
+    ) -> Vec<TypedStatement<'ast, T>> {
+        for v in std::mem::take(&mut self.variables) {
+            if v.shadows {
+                self.warnings.push(Warning::new(
+                    Lint::Shadowing,
+                    module_id,
+                    v.pos.0,
+                    format!("Variable `{}` shadows a previous declaration", v.id),
+                ));
+            }
+
+            if !v.is_used && v.id != "self" {
+                self.warnings.push(match v.call {
+                    Some(call) => Warning::new(
+                        Lint::UnusedReturnValues,
+                        module_id,
+                        v.pos.0,
+                        format!(
+                            "Return value of `{}` is never used: `{}` is never read",
+                            call, v.id
+                        ),
+                    ),
+                    None => Warning::new(
+                        Lint::UnusedVariables,
+                        module_id,
+                        v.pos.0,
+                        format!("Variable `{}` is never read", v.id),
+                    ),
+                });
+            }
+        }
+
+        if self.unconstrained_assignments.is_empty() {
+            return statements;
+        }
+
+        let (statements, constrained) = ConstrainedVariables::find(statements);
+
+        for a in std::mem::take(&mut self.unconstrained_assignments) {
+            if !constrained.contains(&a.id) {
+                self.warnings.push(Warning::new(
+                    Lint::UnconstrainedAssignments,
+                    module_id,
+                    a.pos.0,
+                    format!(
+                        "`{}` is assigned with `<--` but never constrained",
+                        a.assignee
+                    ),
+                ));
+            }
+        }
+
+        statements
+    }
+
+    fn check_signature(
+        &mut self,
+        signature: UnresolvedSignature<'ast>,
+        module_id: &ModuleId,
+        state: &State<'ast, T>,
+    ) -> Result<DeclarationSignature<'ast, T>, Vec<ErrorInner>> {
+        let mut errors = vec![];
+        let mut inputs = vec![];
+        let mut generics = vec![];
+
+        let mut generics_map = BTreeMap::new();
+
+        for (index, g) in signature.generics.iter().enumerate() {
+            if state
+                .constants
+                .get(module_id)
+                .and_then(|m| m.get(g.value))
+                .is_some()
+            {
+                errors.push(ErrorInner {
+                    pos: Some(g.pos()),
+                    message: format!(
+                        "Generic parameter {p} conflicts with constant symbol {p}",
+                        p = g.value
+                    ),
+                });
+            } else {
+                match generics_map.insert(g.value, index).is_none() {
+                    true => {
+                        generics.push(Some(DeclarationConstant::Generic(
+                            GenericIdentifier::with_name(g.value).with_index(index),
+                        )));
+                    }
+                    false => {
+                        errors.push(ErrorInner {
+                            pos: Some(g.pos()),
+                            message: format!("Generic parameter {} is already declared", g.value),
+                        });
+                    }
+                }
+            }
+        }
+
+        for t in signature.inputs {
+            match self.check_declaration_type(
+                t,
+                module_id,
+                state,
+                &generics_map,
+                &mut HashSet::default(),
+            ) {
+                Ok(t) => {
+                    inputs.push(t);
+                }
+                Err(e) => {
+                    errors.push(e);
+                }
+            }
+        }
+
+        match signature
+            .output
+            .map(|o| {
+                self.check_declaration_type(
+                    o,
+                    module_id,
+                    state,
+                    &generics_map,
+                    &mut HashSet::default(),
+                )
+            })
+            .unwrap_or_else(|| Ok(DeclarationType::Tuple(GTupleType::new(vec![]))))
+        {
+            Ok(output) => {
+                if !errors.is_empty() {
+                    return Err(errors);
+                }
+
+                self.return_type = Some(output.clone());
+                Ok(DeclarationSignature::new()
+                    .generics(generics)
+                    .inputs(inputs)
+                    .output(output))
+            }
+            Err(e) => {
+                errors.push(e);
+                Err(errors)
+            }
+        }
+    }
+
+    fn check_type(
+        &mut self,
+        ty: UnresolvedTypeNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Type<'ast, T>, ErrorInner> {
+        let pos = ty.pos();
+        let ty = ty.value;
+
+        match ty {
+            UnresolvedType::FieldElement => Ok(Type::FieldElement),
+            UnresolvedType::Boolean => Ok(Type::Boolean),
+            UnresolvedType::Uint(bitwidth) => Ok(Type::uint(bitwidth)),
+            UnresolvedType::Int(bitwidth) => Ok(Type::uint(UBitwidth::from(bitwidth).to_signed())),
+            UnresolvedType::Array(t, size) => {
+                let size = self.check_expression(size, module_id, types)?;
+
+                let ty = size.get_type();
+
+                let size = match size {
+                    TypedExpression::Uint(e) => match e.bitwidth() {
+                        UBitwidth::B32 => Ok(e),
+                        _ => Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                            "Expected array dimension to be a u32 constant, found {} of type {}",
+                            e, ty
+                        ),
+                        }),
+                    },
+                    TypedExpression::Int(v) => {
+                        UExpression::try_from_int(v.clone(), &UBitwidth::B32).map_err(|_| {
+                            ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                            "Expected array dimension to be a u32 constant, found {} of type {}",
+                            v, ty
+                        ),
+                            }
+                        })
+                    }
+                    _ => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected array dimension to be a u32 constant, found {} of type {}",
+                            size, ty
+                        ),
+                    }),
+                }?;
 
                 Ok(Type::Array(ArrayType::new(
                     self.check_type(*t, module_id, types)?,
                     size,
                 )))
             }
-            UnresolvedType::Tuple(elements) => {
-                let checked_elements: Vec<_> = elements
+            UnresolvedType::BoundedArray(t, capacity) => match self.check_type(
+                UnresolvedType::Array(t, capacity).start_end(pos.0, pos.1),
+                module_id,
+                types,
+            )? {
+                Type::Array(array_ty) => Ok(Type::BoundedArray(array_ty)),
+                _ => unreachable!(),
+            },
+            UnresolvedType::Tuple(elements) => {
+                let checked_elements: Vec<_> = elements
+                    .into_iter()
+                    .map(|e| self.check_type(e, module_id, types))
+                    .collect::<Result<_, _>>()?;
+                Ok(Type::Tuple(TupleType::new(checked_elements)))
+            }
+            UnresolvedType::Function(..) => Err(ErrorInner {
+                pos: Some(pos),
+                message: "Function types are only allowed for function parameters".into(),
+            }),
+            UnresolvedType::User(id, generics) => {
+                self.use_symbol(&id);
+
+                let declared_ty =
+                    types
+                        .get(module_id)
+                        .unwrap()
+                        .get(&id)
+                        .cloned()
+                        .ok_or_else(|| ErrorInner {
+                            pos: Some(pos),
+                            message: format!("Undefined type {}", id),
+                        })?;
+
+                let generic_identifiers = declared_ty.declaration_generics();
+
+                let declaration_type = declared_ty.ty;
+
+                // absence of generics is treated as 0 generics, as we do not provide inference for now
+                let generics = generics.unwrap_or_default();
+
+                // check generics
+                match generic_identifiers.len() == generics.len() {
+                    true => {
+                        // build the generic assignment for this type
+                        let assignment = GGenericsAssignment(generics
+                            .into_iter()
+                            .zip(generic_identifiers)
+                            .enumerate()
+                            .map(|(i, (e, g))| match e {
+                                Some(e) => {
+                                    self
+                                        .check_expression(e, module_id, types)
+                                        .and_then(|e| {
+                                            UExpression::try_from_typed(e, &UBitwidth::B32)
+                                                .map(|e| (GenericIdentifier::with_name(g).with_index(i), e))
+                                                .map_err(|e| ErrorInner {
+                                                    pos: Some(pos),
+                                                    message: format!("Expected u32 expression, but got expression of type {}", e.get_type()),
+                                                })
+                                        })
+                                },
+                                None => Err(ErrorInner {
+                                    pos: Some(pos),
+                                    message:
+                                    "Expected u32 constant or identifier, but found `_`. Generic inference is not supported yet."
+                                        .into(),
+                                })
+                            })
+                            .collect::<Result<_, _>>()?);
+
+                        // specialize the declared type using the generic assignment
+                        Ok(specialize_declaration_type(declaration_type, &assignment).unwrap())
+                    }
+                    false => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected {} generic argument{} on type {}, but got {}",
+                            generic_identifiers.len(),
+                            if generic_identifiers.len() == 1 {
+                                ""
+                            } else {
+                                "s"
+                            },
+                            id,
+                            generics.len()
+                        ),
+                    }),
+                }
+            }
+        }
+    }
+    fn check_generic_expression(
+        &mut self,
+        expr: ExpressionNode<'ast>,
+        module_id: &ModuleId,
+        constants_map: &BTreeMap<ConstantIdentifier<'ast>, DeclarationType<'ast, T>>,
+        generics_map: &BTreeMap<Identifier<'ast>, usize>,
+        used_generics: &mut HashSet<Identifier<'ast>>,
+    ) -> Result<DeclarationConstant<'ast, T>, ErrorInner> {
+        let pos = expr.pos();
+
+        match expr.value {
+            Expression::U32Constant(c) => Ok(DeclarationConstant::from(c)),
+            Expression::IntConstant(c) => {
+                if c <= BigUint::from(2u128.pow(32) - 1) {
+                    Ok(DeclarationConstant::from(
+                        u32::from_str_radix(&c.to_str_radix(16), 16).unwrap(),
+                    ))
+                } else {
+                    Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                    "Expected array dimension to be a u32 constant or an identifier, found {}",
+                    Expression::IntConstant(c)
+                ),
+                    })
+                }
+            }
+            Expression::Identifier(name) => {
+                used_generics.insert(name);
+                self.use_symbol(name);
+
+                match (constants_map.get(name), generics_map.get(&name)) {
+                    (Some(ty), None) => {
+                        match ty {
+                            DeclarationType::Uint(UBitwidth::B32) => Ok(DeclarationConstant::Constant(CanonicalConstantIdentifier::new(name, module_id.into()))),
+                            _ => Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Expected array dimension to be a u32 constant or an identifier, found {} of type {}",
+                                    name, ty
+                                ),
+                            })
+                        }
+                    }
+                    (None, Some(index)) => Ok(DeclarationConstant::Generic(GenericIdentifier::with_name(name).with_index(*index))),
+                    _ => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!("Undeclared symbol `{}`", name)
+                    })
+                }
+            }
+            Expression::Add(box e1, box e2) => self.check_generic_operation(
+                e1,
+                e2,
+                |e1, e2| e1 + e2,
+                module_id,
+                constants_map,
+                generics_map,
+                used_generics,
+            ),
+            Expression::Sub(box e1, box e2) => self.check_generic_operation(
+                e1,
+                e2,
+                |e1, e2| e1 - e2,
+                module_id,
+                constants_map,
+                generics_map,
+                used_generics,
+            ),
+            Expression::Mult(box e1, box e2) => self.check_generic_operation(
+                e1,
+                e2,
+                |e1, e2| e1 * e2,
+                module_id,
+                constants_map,
+                generics_map,
+                used_generics,
+            ),
+            Expression::Div(box e1, box e2) => self.check_generic_operation(
+                e1,
+                e2,
+                |e1, e2| e1 / e2,
+                module_id,
+                constants_map,
+                generics_map,
+                used_generics,
+            ),
+            Expression::Rem(box e1, box e2) => self.check_generic_operation(
+                e1,
+                e2,
+                |e1, e2| e1 % e2,
+                module_id,
+                constants_map,
+                generics_map,
+                used_generics,
+            ),
+            e => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected array dimension to be a u32 constant or an identifier, found {}",
+                    e
+                ),
+            }),
+        }
+    }
+
+    // arithmetic on generic values is evaluated when both operands are known
+    #[allow(clippy::too_many_arguments)]
+    fn check_generic_operation<
+        F: Fn(UExpression<'ast, T>, UExpression<'ast, T>) -> UExpression<'ast, T>,
+    >(
+        &mut self,
+        e1: ExpressionNode<'ast>,
+        e2: ExpressionNode<'ast>,
+        op: F,
+        module_id: &ModuleId,
+        constants_map: &BTreeMap<ConstantIdentifier<'ast>, DeclarationType<'ast, T>>,
+        generics_map: &BTreeMap<Identifier<'ast>, usize>,
+        used_generics: &mut HashSet<Identifier<'ast>>,
+    ) -> Result<DeclarationConstant<'ast, T>, ErrorInner> {
+        let pos = e2.pos();
+
+        let e1 = self.check_generic_expression(
+            e1,
+            module_id,
+            constants_map,
+            generics_map,
+            used_generics,
+        )?;
+        let e2 = self.check_generic_expression(
+            e2,
+            module_id,
+            constants_map,
+            generics_map,
+            used_generics,
+        )?;
+
+        let both_concrete = matches!(
+            (&e1, &e2),
+            (
+                DeclarationConstant::Concrete(_),
+                DeclarationConstant::Concrete(_)
+            )
+        );
+
+        match DeclarationConstant::from_uint_expression(op(e1.into(), e2.into())) {
+            // the only operations on concrete values which cannot be evaluated are divisions by zero and overflows
+            DeclarationConstant::Expression(e) if both_concrete => Err(ErrorInner {
+                pos: Some(pos),
+                message: match UExpression::from(e.clone()).into_inner() {
+                    UExpressionInner::Div(..) | UExpressionInner::Rem(..) => {
+                        format!("Division by zero in generic value {}", e)
+                    }
+                    _ => format!("Overflow in generic value {}", e),
+                },
+            }),
+            c => Ok(c),
+        }
+    }
+
+    fn check_declaration_type(
+        &mut self,
+        ty: UnresolvedTypeNode<'ast>,
+        module_id: &ModuleId,
+        state: &State<'ast, T>,
+        generics_map: &BTreeMap<Identifier<'ast>, usize>,
+        used_generics: &mut HashSet<Identifier<'ast>>,
+    ) -> Result<DeclarationType<'ast, T>, ErrorInner> {
+        let pos = ty.pos();
+        let ty = ty.value;
+
+        match ty {
+            UnresolvedType::FieldElement => Ok(DeclarationType::FieldElement),
+            UnresolvedType::Boolean => Ok(DeclarationType::Boolean),
+            UnresolvedType::Uint(bitwidth) => Ok(DeclarationType::uint(bitwidth)),
+            UnresolvedType::Int(bitwidth) => {
+                Ok(DeclarationType::uint(UBitwidth::from(bitwidth).to_signed()))
+            }
+            UnresolvedType::Array(t, size) => {
+                let checked_size = self.check_generic_expression(
+                    size.clone(),
+                    module_id,
+                    state.constants.get(module_id).unwrap_or(&BTreeMap::new()),
+                    generics_map,
+                    used_generics,
+                )?;
+
+                Ok(DeclarationType::Array(DeclarationArrayType::new(
+                    self.check_declaration_type(*t, module_id, state, generics_map, used_generics)?,
+                    checked_size,
+                )))
+            }
+            UnresolvedType::BoundedArray(t, capacity) => match self.check_declaration_type(
+                UnresolvedType::Array(t, capacity).start_end(pos.0, pos.1),
+                module_id,
+                state,
+                generics_map,
+                used_generics,
+            )? {
+                DeclarationType::Array(array_ty) => Ok(DeclarationType::BoundedArray(array_ty)),
+                _ => unreachable!(),
+            },
+            UnresolvedType::Tuple(elements) => {
+                let checked_elements: Vec<_> = elements
+                    .into_iter()
+                    .map(|e| {
+                        self.check_declaration_type(
+                            e,
+                            module_id,
+                            state,
+                            generics_map,
+                            used_generics,
+                        )
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(DeclarationType::Tuple(DeclarationTupleType::new(
+                    checked_elements,
+                )))
+            }
+            UnresolvedType::Function(..) => Err(ErrorInner {
+                pos: Some(pos),
+                message: "Function types are only allowed for function parameters".into(),
+            }),
+            UnresolvedType::User(id, generics) => {
+                self.use_symbol(&id);
+
+                let ty = state
+                    .types
+                    .get(module_id)
+                    .unwrap()
+                    .get(&id)
+                    .cloned()
+                    .ok_or_else(|| ErrorInner {
+                        pos: Some(pos),
+                        message: format!("Undefined type {}", id),
+                    })?;
+
+                let generics = generics.unwrap_or_default();
+                let checked_generics: Vec<_> = generics
+                    .into_iter()
+                    .map(|e| match e {
+                        Some(e) => self
+                            .check_generic_expression(
+                                e,
+                                module_id,
+                                state.constants.get(module_id).unwrap_or(&BTreeMap::new()),
+                                generics_map,
+                                used_generics,
+                            )
+                            .map(Some),
+                        None => Err(ErrorInner {
+                            pos: Some(pos),
+                            message: "Expected u32 constant or identifier, but found `_`".into(),
+                        }),
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                match ty.generics.len() == checked_generics.len() {
+                    true => {
+                        let mut assignment = GGenericsAssignment::default();
+
+                        assignment.0.extend(ty.generics.iter().zip(checked_generics.iter()).map(|(decl_g, g_val)| match decl_g.clone() {
+                            DeclarationConstant::Generic(g) => (g, g_val.clone().unwrap()),
+                            _ => unreachable!("generic on declaration struct types must be generic identifiers")
+                        }));
+
+                        let res = match ty.ty {
+                            // if the type is a struct, we do not specialize in the members.
+                            // we only remap the generics
+                            DeclarationType::Struct(declared_struct_ty) => {
+                                DeclarationType::Struct(DeclarationStructType {
+                                    generics: declared_struct_ty
+                                        .generics
+                                        .into_iter()
+                                        .map(|g| g.map(|g| g.map(&assignment).unwrap()))
+                                        .collect(),
+                                    ..declared_struct_ty
+                                })
+                            }
+                            ty => specialize_declaration_type(ty, &assignment).unwrap(),
+                        };
+
+                        Ok(res)
+                    }
+                    false => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected {} generic argument{} on type {}, but got {}",
+                            ty.generics.len(),
+                            if ty.generics.len() == 1 { "" } else { "s" },
+                            id,
+                            checked_generics.len()
+                        ),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn check_variable(
+        &mut self,
+        v: zokrates_ast::untyped::VariableNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Variable<'ast, T>, Vec<ErrorInner>> {
+        let ty = self
+            .check_type(v.value._type, module_id, types)
+            .map_err(|e| vec![e])?;
+
+        // insert into the scope and ignore whether shadowing happened. Loop variables are not required to be used, as
+        // they also serve as a counter
+        self.insert_into_scope(v.value.id, ty.clone(), v.value.is_mutable);
+
+        Ok(Variable::new(
+            self.id_in_this_scope(v.value.id),
+            ty,
+            v.value.is_mutable,
+        ))
+    }
+
+    fn check_for_loop(
+        &mut self,
+        var: zokrates_ast::untyped::VariableNode<'ast>,
+        range: (ExpressionNode<'ast>, ExpressionNode<'ast>),
+        statements: Vec<StatementNode<'ast>>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedStatement<'ast, T>, Vec<ErrorInner>> {
+        let from = self
+            .check_expression(range.0, module_id, types)
+            .map_err(|e| vec![e])?;
+        let to = self
+            .check_expression(range.1, module_id, types)
+            .map_err(|e| vec![e])?;
+
+        let from = match from {
+            TypedExpression::Uint(from) => match from.bitwidth() {
+                UBitwidth::B32 => Ok(from),
+                bitwidth => Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected lower loop bound to be of type u32, found {}",
+                        Type::<T>::Uint(bitwidth)
+                    ),
+                }),
+            },
+            TypedExpression::Int(v) => {
+                UExpression::try_from_int(v, &UBitwidth::B32).map_err(|_| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected lower loop bound to be of type u32, found {}",
+                        Type::<T>::Int
+                    ),
+                })
+            }
+            from => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected lower loop bound to be of type u32, found {}",
+                    from.get_type()
+                ),
+            }),
+        }
+        .map_err(|e| vec![e])?;
+
+        let to = match to {
+            TypedExpression::Uint(to) => match to.bitwidth() {
+                UBitwidth::B32 => Ok(to),
+                bitwidth => Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected upper loop bound to be of type u32, found {}",
+                        Type::<T>::Uint(bitwidth)
+                    ),
+                }),
+            },
+            TypedExpression::Int(v) => {
+                UExpression::try_from_int(v, &UBitwidth::B32).map_err(|_| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected upper loop bound to be of type u32, found {}",
+                        Type::<T>::Int
+                    ),
+                })
+            }
+            to => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected upper loop bound to be of type u32, found {}",
+                    to.get_type()
+                ),
+            }),
+        }
+        .map_err(|e| vec![e])?;
+
+        let var = self.check_for_variable(var, module_id, types)?;
+
+        let checked_statements = self.check_loop_body(statements, module_id, types)?;
+
+        Ok(TypedStatement::For(var, from, to, checked_statements))
+    }
+
+    fn check_while_loop(
+        &mut self,
+        condition: ExpressionNode<'ast>,
+        bound: ExpressionNode<'ast>,
+        statements: Vec<StatementNode<'ast>>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedStatement<'ast, T>, Vec<ErrorInner>> {
+        let condition = self
+            .check_expression(condition, module_id, types)
+            .map_err(|e| vec![e])?;
+        let bound = self
+            .check_expression(bound, module_id, types)
+            .map_err(|e| vec![e])?;
+
+        let condition = match condition {
+            TypedExpression::Boolean(condition) => Ok(condition),
+            condition => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected while loop condition to be of type bool, found {}",
+                    condition.get_type()
+                ),
+            }),
+        }
+        .map_err(|e| vec![e])?;
+
+        let bound = match bound {
+            TypedExpression::Uint(bound) => match bound.bitwidth() {
+                UBitwidth::B32 => Ok(bound),
+                bitwidth => Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected while loop bound to be of type u32, found {}",
+                        Type::<T>::Uint(bitwidth)
+                    ),
+                }),
+            },
+            TypedExpression::Int(v) => {
+                UExpression::try_from_int(v, &UBitwidth::B32).map_err(|_| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected while loop bound to be of type u32, found {}",
+                        Type::<T>::Int
+                    ),
+                })
+            }
+            bound => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected while loop bound to be of type u32, found {}",
+                    bound.get_type()
+                ),
+            }),
+        }
+        .map_err(|e| vec![e])?;
+
+        let checked_statements = self.check_loop_body(statements, module_id, types)?;
+
+        Ok(TypedStatement::While(
+            condition,
+            bound,
+            checked_statements,
+            RuntimeError::SourceAssertion(
+                SourceMetadata::new(module_id.display().to_string(), pos.0)
+                    .message(Some("Bound of while loop exceeded".to_string())),
+            ),
+        ))
+    }
+
+    fn check_loop_body(
+        &mut self,
+        statements: Vec<StatementNode<'ast>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, Vec<ErrorInner>> {
+        self.loop_depth += 1;
+
+        let res = statements
+            .into_iter()
+            .map(|s| self.check_lowered_statement(s, module_id, types))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|s| s.into_iter().flatten().collect());
+
+        self.loop_depth -= 1;
+
+        res
+    }
+
+    // static assertions are checked when the program is reduced, once all generic parameters are known
+    fn check_static_assertion(
+        &mut self,
+        e: ExpressionNode<'ast>,
+        message: String,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<(BooleanExpression<'ast, T>, SourceMetadata), ErrorInner> {
+        match self.check_expression(e, module_id, types)? {
+            TypedExpression::Boolean(e) => Ok((
+                e,
+                SourceMetadata::new(module_id.display().to_string(), pos.0).message(Some(message)),
+            )),
+            e => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected static assertion {} to be of type bool, found {}",
+                    e,
+                    e.get_type(),
+                ),
+            }),
+        }
+    }
+
+    fn check_block(
+        &mut self,
+        statements: Vec<StatementNode<'ast>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, Vec<ErrorInner>> {
+        self.enter_scope();
+
+        let res = statements
+            .into_iter()
+            .map(|s| self.check_lowered_statement(s, module_id, types))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|s| s.into_iter().flatten().collect());
+
+        self.exit_scope();
+
+        res
+    }
+
+    // destructuring statements and iterations over arrays are lowered to several statements, other statements to a
+    // single statement
+    fn check_lowered_statement(
+        &mut self,
+        stat: StatementNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, Vec<ErrorInner>> {
+        let pos = stat.pos();
+
+        let statements = match stat.value {
+            Statement::Destructuring(pattern, expr) => self
+                .check_destructuring(pattern, expr, pos, module_id, types)
+                .map_err(|e| vec![e]),
+            _ => self
+                .check_statement(stat, module_id, types)
+                .map(|s| vec![s]),
+        }?;
+
+        // with a source map, the statements are marked with their position so that what they compile to can be traced
+        // back to the source
+        Ok(match self.source_map {
+            true => std::iter::once(TypedStatement::SourceMetadata(SourceMetadata::new(
+                module_id.display().to_string(),
+                pos.0,
+            )))
+            .chain(statements)
+            .collect(),
+            false => statements,
+        })
+    }
+
+    // a variable introduced by the checker, whose name cannot clash with source identifiers
+    fn temporary_variable(
+        &mut self,
+        purpose: &str,
+        ty: Type<'ast, T>,
+        is_mutable: bool,
+    ) -> Variable<'ast, T> {
+        let id = self.id_in_this_scope(format!("#{}_{}", purpose, self.temporary_count));
+        self.temporary_count += 1;
+        Variable::new(id, ty, is_mutable)
+    }
+
+    // `for T x in a { ... }` is lowered to a loop over the indices of `a` after type checking
+    fn check_for_each_loop(
+        &mut self,
+        var: VariableNode<'ast>,
+        array: ExpressionNode<'ast>,
+        statements: Vec<StatementNode<'ast>>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedStatement<'ast, T>, Vec<ErrorInner>> {
+        let array = match self
+            .check_expression(array, module_id, types)
+            .map_err(|e| vec![e])?
+        {
+            TypedExpression::Array(array) => array,
+            e => {
+                return Err(vec![ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected an array to iterate over, found `{}` of type `{}`",
+                        e,
+                        e.get_type()
+                    ),
+                }])
+            }
+        };
+
+        self.enter_scope();
+
+        let res = self.check_for_each_body(var, array, statements, module_id, types);
+
+        self.exit_scope();
+
+        res
+    }
+
+    fn check_for_each_body(
+        &mut self,
+        var: VariableNode<'ast>,
+        array: ArrayExpression<'ast, T>,
+        statements: Vec<StatementNode<'ast>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedStatement<'ast, T>, Vec<ErrorInner>> {
+        let var_pos = var.pos();
+
+        let var = self.check_variable(var, module_id, types)?;
+
+        let element_ty = array.inner_type().clone();
+        let array_ty = Type::array((var.get_type(), array.size()));
+
+        let array = match TypedExpression::align_to_type(array.into(), &array_ty) {
+            Ok(TypedExpression::Array(array)) if *array.inner_type() == var.get_type() => array,
+            _ => {
+                return Err(vec![ErrorInner {
+                    pos: Some(var_pos),
+                    message: format!(
+                        "Expected variable in for loop to be of type {}, found {}",
+                        element_ty,
+                        var.get_type()
+                    ),
+                }])
+            }
+        };
+
+        let checked_statements = self.check_loop_body(statements, module_id, types)?;
+
+        Ok(TypedStatement::ForEach(var, array, checked_statements))
+    }
+
+    // `p = e;` defines a temporary variable for `e`, then each variable of `p` from an element or member access on it
+    fn check_destructuring(
+        &mut self,
+        pattern: PatternNode<'ast>,
+        expr: ExpressionNode<'ast>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Vec<TypedStatement<'ast, T>>, ErrorInner> {
+        let checked_expr = match self.pattern_type(&pattern, module_id, types)? {
+            Some(ty) => {
+                let e = self.check_rhs(ty.clone(), expr, module_id, types)?;
+                TypedExpression::align_to_type(e, &ty)
+                    .ok()
+                    .filter(|e| e.get_type() == ty)
+                    .ok_or_else(|| ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected a value of type `{}` to destructure with `{}`",
+                            ty, pattern
+                        ),
+                    })?
+            }
+            None => {
+                let e = self.check_expression(expr, module_id, types)?;
+                let ty = self.refine_pattern_type(&pattern, e.get_type(), module_id, types)?;
+                TypedExpression::align_to_type(e.clone(), &ty).unwrap_or(e)
+            }
+        };
+
+        let ty = checked_expr.get_type();
+
+        if contains_int(&ty) {
+            return Err(ErrorInner {
+                pos: Some(pos),
+                message: format!("Cannot determine type for expression `{}`", checked_expr),
+            });
+        }
+
+        let tmp = self.temporary_variable("destructuring", ty, false);
+
+        let mut statements = vec![TypedStatement::Definition(
+            tmp.clone().into(),
+            checked_expr.into(),
+        )];
+
+        self.check_pattern(
+            pattern,
+            TypedExpression::from(tmp),
+            &mut statements,
+            module_id,
+            types,
+        )?;
+
+        Ok(statements)
+    }
+
+    // the type of the values matched by `pattern`, if it is fully determined by the pattern
+    fn pattern_type(
+        &mut self,
+        pattern: &PatternNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Option<Type<'ast, T>>, ErrorInner> {
+        match &pattern.value {
+            Pattern::Variable(var) => self
+                .check_type(var.value._type.clone(), module_id, types)
+                .map(Some),
+            Pattern::Tuple(elements) => Ok(elements
+                .iter()
+                .map(|e| self.pattern_type(e, module_id, types))
+                .collect::<Result<Option<Vec<_>>, _>>()?
+                .map(|elements| Type::Tuple(TupleType::new(elements)))),
+            Pattern::Wildcard | Pattern::Binding(..) | Pattern::Struct(..) => Ok(None),
+        }
+    }
+
+    // the type `ty` of a value matched by `pattern`, where the values bound to typed variables take the type of the variable
+    fn refine_pattern_type(
+        &mut self,
+        pattern: &PatternNode<'ast>,
+        ty: Type<'ast, T>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Type<'ast, T>, ErrorInner> {
+        match (&pattern.value, ty) {
+            (Pattern::Variable(var), _) => {
+                self.check_type(var.value._type.clone(), module_id, types)
+            }
+            (Pattern::Tuple(elements), Type::Tuple(tuple_ty))
+                if elements.len() == tuple_ty.elements.len() =>
+            {
+                Ok(Type::Tuple(TupleType::new(
+                    elements
+                        .iter()
+                        .zip(tuple_ty.elements)
+                        .map(|(e, ty)| self.refine_pattern_type(e, ty, module_id, types))
+                        .collect::<Result<_, _>>()?,
+                )))
+            }
+            (_, ty) => Ok(ty),
+        }
+    }
+
+    // define the variables of `pattern` from `e`
+    fn check_pattern(
+        &mut self,
+        pattern: PatternNode<'ast>,
+        e: TypedExpression<'ast, T>,
+        statements: &mut Vec<TypedStatement<'ast, T>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<(), ErrorInner> {
+        let pos = pattern.pos();
+
+        match pattern.value {
+            Pattern::Wildcard => Ok(()),
+            Pattern::Variable(var) => {
+                let var_pos = var.pos();
+                let var_ty = self.check_type(var.value._type, module_id, types)?;
+
+                if e.get_type() != var_ty {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expression `{}` of type `{}` cannot be assigned to `{}` of type `{}`",
+                            e,
+                            e.get_type(),
+                            var.value.id,
+                            var_ty
+                        ),
+                    });
+                }
+
+                self.declare_variable(
+                    var.value.id,
+                    var_ty.clone(),
+                    var.value.is_mutable,
+                    var_pos,
+                    None,
+                );
+
+                let var = Variable::new(
+                    self.id_in_this_scope(var.value.id),
+                    var_ty,
+                    var.value.is_mutable,
+                );
+
+                statements.push(TypedStatement::Definition(var.into(), e.into()));
+
+                Ok(())
+            }
+            Pattern::Binding(id, is_mutable) => {
+                let ty = e.get_type();
+
+                self.declare_variable(id, ty.clone(), is_mutable, pos, None);
+
+                let var = Variable::new(self.id_in_this_scope(id), ty, is_mutable);
+
+                statements.push(TypedStatement::Definition(var.into(), e.into()));
+
+                Ok(())
+            }
+            Pattern::Tuple(elements) => {
+                let t = match e {
+                    TypedExpression::Tuple(t) if t.ty().elements.len() == elements.len() => t,
+                    e => {
+                        return Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Expected a tuple of size {}, found `{}` of type `{}`",
+                                elements.len(),
+                                e,
+                                e.get_type()
+                            ),
+                        })
+                    }
+                };
+
+                for (index, p) in elements.into_iter().enumerate() {
+                    let element = tuple_element(t.clone(), index as u32);
+                    self.check_pattern(p, element, statements, module_id, types)?;
+                }
+
+                Ok(())
+            }
+            Pattern::Struct(ty_id, fields) => {
+                self.use_symbol(&ty_id);
+
+                // the type in the pattern must resolve to the type of the destructured expression
+                let pattern_location =
+                    types
+                        .get(module_id)
+                        .unwrap()
+                        .get(&ty_id)
+                        .and_then(|t| match &t.ty {
+                            DeclarationType::Struct(struct_type) => {
+                                Some(struct_type.canonical_location.clone())
+                            }
+                            _ => None,
+                        });
+
+                let s = match e {
+                    TypedExpression::Struct(s)
+                        if pattern_location.as_ref() == Some(&s.ty().canonical_location) =>
+                    {
+                        s
+                    }
+                    e => {
+                        return Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Pattern for struct {} cannot destructure `{}` of type `{}`",
+                                ty_id,
+                                e,
+                                e.get_type()
+                            ),
+                        })
+                    }
+                };
+
+                for member in s.ty().iter() {
+                    if !fields.iter().any(|(id, _)| *id == member.id) {
+                        return Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Pattern for struct {} is missing member {}",
+                                ty_id, member.id
+                            ),
+                        });
+                    }
+                }
+
+                let mut seen = HashSet::new();
+
+                for (id, p) in fields {
+                    if !s.ty().iter().any(|m| m.id == id) {
+                        return Err(ErrorInner {
+                            pos: Some(p.pos()),
+                            message: format!("{} doesn't have member {}", s.get_type(), id),
+                        });
+                    }
+
+                    if !seen.insert(id) {
+                        return Err(ErrorInner {
+                            pos: Some(p.pos()),
+                            message: format!(
+                                "Member {} appears more than once in pattern for struct {}",
+                                id, ty_id
+                            ),
+                        });
+                    }
+
+                    let member = struct_member(s.clone(), id);
+                    self.check_pattern(p, member, statements, module_id, types)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    // the assignee is already checked to be defined and mutable
+    fn check_rhs(
+        &mut self,
+        return_type: Type<'ast, T>,
+        expr: ExpressionNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        match expr.value {
+            // for function calls, check the rhs with the expected type
+            Expression::FunctionCall(box fun_id_expression, generics, arguments) => self
+                .check_function_call_expression(
+                    fun_id_expression,
+                    generics,
+                    arguments,
+                    Some(return_type),
+                    module_id,
+                    types,
+                ),
+            // an empty array is an empty bounded array
+            Expression::InlineArray(elements)
+                if elements.is_empty() && matches!(&return_type, Type::BoundedArray(..)) =>
+            {
+                Ok(zero_value(&return_type))
+            }
+            // otherwise, just check the rhs normally, turning arrays into bounded arrays if needed
+            _ => {
+                let pos = expr.pos();
+                match (return_type, self.check_expression(expr, module_id, types)?) {
+                    (Type::BoundedArray(array_ty), TypedExpression::Array(array)) => {
+                        self.check_bounded_array_value(array, array_ty, pos, module_id)
+                    }
+                    (_, e) => Ok(e),
+                }
+            }
+        }
+    }
+
+    fn check_assembly_statement(
+        &mut self,
+        stat: AssemblyStatementNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<Vec<TypedAssemblyStatement<'ast, T>>, ErrorInner> {
+        let pos = stat.pos();
+
+        match stat.value {
+            AssemblyStatement::Assignment(assignee, expression, constrained) => {
+                let assignee = self.check_assignee(assignee, module_id, types)?;
+                let e = self.check_expression(expression, module_id, types)?;
+
+                let e = FieldElementExpression::try_from_typed(e).map_err(|e| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected right hand side of an assembly assignment to be of type field, found {}",
+                        e.get_type(),
+                    ),
+                })?;
+
+                match constrained {
+                    true => {
+                        let e = FieldElementExpression::block(vec![], e);
+                        match assignee.get_type() {
+                            Type::FieldElement => Ok(vec![
+                                TypedAssemblyStatement::Assignment(
+                                    assignee.clone(),
+                                    e.clone().into(),
+                                ),
+                                TypedAssemblyStatement::Constraint(
+                                    assignee.into(),
+                                    e,
+                                    SourceMetadata::new(module_id.display().to_string(), pos.0),
+                                ),
+                            ]),
+                            ty => Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!("Assignee must be of type field, found {}", ty),
+                            }),
+                        }
+                    }
+                    false => {
+                        let e = FieldElementExpression::block(vec![], e);
+                        Ok(vec![TypedAssemblyStatement::Assignment(assignee, e.into())])
+                    }
+                }
+            }
+            AssemblyStatement::Constraint(lhs, rhs) => {
+                let lhs = self.check_expression(lhs, module_id, types)?;
+                let rhs = self.check_expression(rhs, module_id, types)?;
+
+                let lhs = FieldElementExpression::try_from_typed(lhs).map_err(|e| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected left hand side of a constraint to be of type field, found {}",
+                        e.get_type(),
+                    ),
+                })?;
+
+                let rhs = FieldElementExpression::try_from_typed(rhs).map_err(|e| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected right hand side of a constraint to be of type field, found {}",
+                        e.get_type(),
+                    ),
+                })?;
+
+                Ok(vec![TypedAssemblyStatement::Constraint(
+                    lhs,
+                    rhs,
+                    SourceMetadata::new(module_id.display().to_string(), pos.0),
+                )])
+            }
+        }
+    }
+
+    fn check_statement(
+        &mut self,
+        stat: StatementNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedStatement<'ast, T>, Vec<ErrorInner>> {
+        let pos = stat.pos();
+
+        match stat.value {
+            Statement::Assembly(statements) => {
+                let mut checked_statements = vec![];
+                for s in statements {
+                    let pos = s.pos();
+
+                    // values assigned with `<--` are expected to be constrained later on
+                    let unconstrained = match &s.value {
+                        AssemblyStatement::Assignment(assignee, _, false) => {
+                            Some(assignee.to_string())
+                        }
+                        _ => None,
+                    };
+
+                    let checked = self
+                        .check_assembly_statement(s, module_id, types)
+                        .map_err(|e| vec![e])?;
+
+                    if let (Some(assignee), [TypedAssemblyStatement::Assignment(a, _)]) =
+                        (unconstrained, checked.as_slice())
+                    {
+                        self.unconstrained_assignments
+                            .push(UnconstrainedAssignment {
+                                assignee,
+                                id: assignee_root(a).id.clone(),
+                                pos,
+                            });
+                    }
+
+                    checked_statements.extend(checked);
+                }
+                Ok(TypedStatement::Assembly(checked_statements))
+            }
+            Statement::Log(l, expressions) => {
+                let l = FormatString::from(l);
+
+                let expressions = expressions
+                    .into_iter()
+                    .map(|e| self.check_expression(e, module_id, types))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| vec![e])?;
+
+                let mut errors = vec![];
+
+                for e in &expressions {
+                    if let TypedExpression::Int(e) = e {
+                        errors.push(ErrorInner {
+                            pos: Some(pos),
+                            message: format!("Cannot determine type for expression `{}`", e),
+                        });
+                    }
+                }
+
+                if expressions.len() != l.len() {
+                    errors.push(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Wrong argument count in log call: expected {}, got {}",
+                            l.len(),
+                            expressions.len()
+                        ),
+                    });
+                }
+
+                if !errors.is_empty() {
+                    return Err(errors);
+                }
+
+                Ok(TypedStatement::Log(l, expressions))
+            }
+            Statement::Return(e) => {
+                let mut errors = vec![];
+
+                // we clone the return type because there might be other return statements
+                let return_type = self.return_type.clone().unwrap();
+
+                let e_checked = e
+                    .map(|e| {
+                        match e.value {
+                            Expression::FunctionCall(
+                                box fun_id_expression,
+                                generics,
+                                arguments,
+                            ) => {
+                                let ty = zokrates_ast::typed::types::try_from_g_type(
+                                    return_type.clone(),
+                                )
+                                .map(Some)
+                                .unwrap();
+
+                                self.check_function_call_expression(
+                                    fun_id_expression,
+                                    generics,
+                                    arguments,
+                                    ty,
+                                    module_id,
+                                    types,
+                                )
+                            }
+                            _ => self.check_expression(e, module_id, types),
+                        }
+                        .map_err(|e| vec![e])
+                    })
+                    .unwrap_or_else(|| Ok(TypedExpression::empty_tuple()))?;
+
+                let res = match TypedExpression::align_to_type(e_checked.clone(), &return_type)
+                    .map_err(|e| {
+                        vec![ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Expected return value to be of type `{}`, found `{}` of type `{}`",
+                                e.1,
+                                e.0,
+                                e.0.get_type()
+                            ),
+                        }]
+                    }) {
+                    Ok(e) => {
+                        match e.get_type() == return_type {
+                            true => {}
+                            false => errors.push(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Expected `{}` in return statement, found `{}`",
+                                    return_type,
+                                    e.get_type()
+                                ),
+                            }),
+                        }
+                        TypedStatement::Return(e)
+                    }
+                    Err(err) => {
+                        errors.extend(err);
+                        TypedStatement::Return(e_checked)
+                    }
+                };
+
+                if !errors.is_empty() {
+                    return Err(errors);
+                }
+
+                Ok(res)
+            }
+            Statement::Definition(var, expr) => {
+                // the function whose return value defines the variable, if any
+                let call = match &expr.value {
+                    Expression::FunctionCall(function_id, ..) => Some(function_id.to_string()),
+                    _ => None,
+                };
+
+                let var_pos = var.pos();
+
+                // get the lhs type
+                let var_ty = self
+                    .check_type(var.value._type, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                // check the rhs based on the lhs type
+                let checked_expr = self
+                    .check_rhs(var_ty.clone(), expr, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                // insert the lhs into the scope
+                self.declare_variable(
+                    var.value.id,
+                    var_ty.clone(),
+                    var.value.is_mutable,
+                    var_pos,
+                    call,
+                );
+
+                let var = Variable::new(
+                    self.id_in_this_scope(var.value.id),
+                    var_ty.clone(),
+                    var.value.is_mutable,
+                );
+
+                match var_ty {
+                    Type::FieldElement => FieldElementExpression::try_from_typed(checked_expr)
+                        .map(TypedExpression::from),
+                    Type::Boolean => {
+                        BooleanExpression::try_from_typed(checked_expr).map(TypedExpression::from)
+                    }
+                    Type::Uint(bitwidth) => UExpression::try_from_typed(checked_expr, &bitwidth)
+                        .map(TypedExpression::from),
+                    Type::Array(ref array_ty) => {
+                        ArrayExpression::try_from_typed(checked_expr, array_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::Struct(ref struct_ty) => {
+                        StructExpression::try_from_typed(checked_expr, struct_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::Tuple(ref tuple_ty) => {
+                        TupleExpression::try_from_typed(checked_expr, tuple_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::Enum(ref enum_ty) => {
+                        EnumExpression::try_from_typed(checked_expr, enum_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::BoundedArray(ref array_ty) => {
+                        BoundedArrayExpression::try_from_typed(checked_expr, array_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::Int => Err(checked_expr), // Integers cannot be assigned
+                }
+                .map_err(|e| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expression `{}` of type `{}` cannot be assigned to `{}` of type `{}`",
+                        e,
+                        e.get_type(),
+                        var.clone(),
+                        var_ty
+                    ),
+                })
+                .map(|e| TypedStatement::Definition(var.into(), e.into()))
+                .map_err(|e| vec![e])
+            }
+            Statement::Destructuring(..) => {
+                unreachable!("destructuring is lowered in check_lowered_statement")
+            }
+            Statement::Assignment(assignee, expr) => {
+                // check that the assignee is declared, well formed and mutable
+                let assignee = self
+                    .check_assignee(assignee, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                let assignee_ty = assignee.get_type();
+
+                let checked_expr = self
+                    .check_rhs(assignee_ty.clone(), expr, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                match assignee_ty {
+                    Type::FieldElement => FieldElementExpression::try_from_typed(checked_expr)
+                        .map(TypedExpression::from),
+                    Type::Boolean => {
+                        BooleanExpression::try_from_typed(checked_expr).map(TypedExpression::from)
+                    }
+                    Type::Uint(bitwidth) => UExpression::try_from_typed(checked_expr, &bitwidth)
+                        .map(TypedExpression::from),
+                    Type::Array(ref array_ty) => {
+                        ArrayExpression::try_from_typed(checked_expr, array_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::Struct(ref struct_ty) => {
+                        StructExpression::try_from_typed(checked_expr, struct_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::Tuple(ref tuple_ty) => {
+                        TupleExpression::try_from_typed(checked_expr, tuple_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::Enum(ref enum_ty) => {
+                        EnumExpression::try_from_typed(checked_expr, enum_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::BoundedArray(ref array_ty) => {
+                        BoundedArrayExpression::try_from_typed(checked_expr, array_ty)
+                            .map(TypedExpression::from)
+                    }
+                    Type::Int => Err(checked_expr), // Integers cannot be assigned
+                }
+                .map_err(|e| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expression `{}` of type `{}` cannot be assigned to `{}` of type `{}`",
+                        e,
+                        e.get_type(),
+                        assignee.clone(),
+                        assignee_ty
+                    ),
+                })
+                .map(|e| TypedStatement::Definition(assignee, e.into()))
+                .map_err(|e| vec![e])
+            }
+            Statement::Assertion(e, message) => {
+                let e = self
+                    .check_expression(e, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                match e {
+                    TypedExpression::Boolean(e) => Ok(TypedStatement::Assertion(
+                        e,
+                        RuntimeError::SourceAssertion(
+                            SourceMetadata::new(module_id.display().to_string(), pos.0)
+                                .message(message),
+                        ),
+                    )),
+                    e => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected {} to be of type bool, found {}",
+                            e,
+                            e.get_type(),
+                        ),
+                    }),
+                }
+                .map_err(|e| vec![e])
+            }
+            Statement::StaticAssertion(e, message) => self
+                .check_static_assertion(e, message, pos, module_id, types)
+                .map(|(e, metadata)| TypedStatement::StaticAssertion(e, metadata))
+                .map_err(|e| vec![e]),
+            Statement::For(var, from, to, statements) => {
+                self.enter_scope();
+
+                let res = self.check_for_loop(var, (from, to), statements, pos, module_id, types);
+
+                self.exit_scope();
+
+                res
+            }
+            Statement::ForEach(var, array, statements) => {
+                self.check_for_each_loop(var, array, statements, pos, module_id, types)
+            }
+            Statement::While(condition, bound, statements) => {
+                self.enter_scope();
+
+                let res =
+                    self.check_while_loop(condition, bound, statements, pos, module_id, types);
+
+                self.exit_scope();
+
+                res
+            }
+            Statement::If(condition, consequence, alternative) => {
+                self.check_constant_condition(&condition, module_id);
+
+                let condition = self
+                    .check_expression(condition, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                let condition = match condition {
+                    TypedExpression::Boolean(condition) => Ok(condition),
+                    condition => Err(vec![ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected if statement condition to be of type bool, found {}",
+                            condition.get_type()
+                        ),
+                    }]),
+                }?;
+
+                let consequence = self.check_block(consequence, module_id, types)?;
+                let alternative = self.check_block(alternative, module_id, types)?;
+
+                Ok(TypedStatement::If(condition, consequence, alternative))
+            }
+            Statement::ComptimeIf(condition, consequence, alternative) => {
+                let condition = self
+                    .check_expression(condition, module_id, types)
+                    .map_err(|e| vec![e])?;
+
+                let condition = match condition {
+                    TypedExpression::Boolean(condition) => Ok(condition),
+                    condition => Err(vec![ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected comptime if statement condition to be of type bool, found {}",
+                            condition.get_type()
+                        ),
+                    }]),
+                }?;
+
+                // both branches are checked here, but only the selected one is reduced for a given instantiation
+                let consequence = self.check_block(consequence, module_id, types)?;
+                let alternative = self.check_block(alternative, module_id, types)?;
+
+                Ok(TypedStatement::ComptimeIf(
+                    condition,
+                    consequence,
+                    alternative,
+                    SourceMetadata::new(module_id.display().to_string(), pos.0),
+                ))
+            }
+            Statement::Break if self.loop_depth == 0 => Err(vec![ErrorInner {
+                pos: Some(pos),
+                message: "`break` used outside of a loop".to_string(),
+            }]),
+            Statement::Continue if self.loop_depth == 0 => Err(vec![ErrorInner {
+                pos: Some(pos),
+                message: "`continue` used outside of a loop".to_string(),
+            }]),
+            Statement::Break => Ok(TypedStatement::Break),
+            Statement::Continue => Ok(TypedStatement::Continue),
+        }
+    }
+
+    fn check_assignee(
+        &mut self,
+        assignee: AssigneeNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedAssignee<'ast, T>, ErrorInner> {
+        let pos = assignee.pos();
+        // check that the assignee is declared
+        match assignee.value {
+            Assignee::Identifier(variable_name) => match self.scope.get(variable_name) {
+                Some(info) => match info.is_mutable {
+                    false => Err(ErrorInner {
+                        pos: Some(assignee.pos()),
+                        message: format!("Assignment to an immutable variable `{}`", variable_name),
+                    }),
+                    _ => Ok(TypedAssignee::Identifier(Variable::new(
+                        info.id,
+                        info.ty.clone(),
+                        info.is_mutable,
+                    ))),
+                },
+                None => Err(ErrorInner {
+                    pos: Some(assignee.pos()),
+                    message: format!("Variable `{}` is undeclared", variable_name),
+                }),
+            },
+            Assignee::Select(box assignee, box index) => {
+                let checked_assignee = self.check_assignee(assignee, module_id, types)?;
+
+                let ty = checked_assignee.get_type();
+                match ty {
+                    Type::Array(..) => {
+                        let checked_index = match index {
+                            RangeOrExpression::Expression(e) => {
+                                self.check_expression(e, module_id, types)?
+                            }
+                            r => unimplemented!(
+                                "Using slices in assignments is not supported yet, found {}",
+                                r
+                            ),
+                        };
+
+                        let checked_typed_index =
+                            UExpression::try_from_typed(checked_index, &UBitwidth::B32).map_err(
+                                |e| ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "Expected array {} index to have type u32, found {}",
+                                        checked_assignee,
+                                        e.get_type()
+                                    ),
+                                },
+                            )?;
+
+                        Ok(TypedAssignee::Select(
+                            box checked_assignee,
+                            box checked_typed_index,
+                        ))
+                    }
+                    ty => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Cannot access element at index {} on {} of type {}",
+                            index, checked_assignee, ty,
+                        ),
+                    }),
+                }
+            }
+            Assignee::Member(box assignee, box member) => {
+                let checked_assignee = self.check_assignee(assignee, module_id, types)?;
+
+                let ty = checked_assignee.get_type();
+                match &ty {
+                    Type::Struct(members) => match members.iter().find(|m| m.id == member) {
+                        Some(_) => Ok(TypedAssignee::Member(box checked_assignee, member.into())),
+                        None => Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "{} {{{}}} doesn't have member {}",
+                                ty,
+                                members
+                                    .iter()
+                                    .map(|m| format!("{}: {}", m.id, m.ty))
+                                    .collect::<Vec<_>>()
+                                    .join(", "),
+                                member
+                            ),
+                        }),
+                    },
+                    ty => Err(ErrorInner {
+                        pos: Some(pos),
+
+                        message: format!(
+                            "Cannot access field {} on {} of type {}",
+                            member, checked_assignee, ty,
+                        ),
+                    }),
+                }
+            }
+            Assignee::Element(box assignee, index) => {
+                let checked_assignee = self.check_assignee(assignee, module_id, types)?;
+
+                let ty = checked_assignee.get_type();
+                match &ty {
+                    Type::Tuple(tuple_ty) => match tuple_ty.elements.get(index as usize) {
+                        Some(_) => Ok(TypedAssignee::Element(box checked_assignee, index)),
+                        None => Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Tuple of size {} cannot be accessed at index {}",
+                                tuple_ty.elements.len(),
+                                index
+                            ),
+                        }),
+                    },
+                    ty => Err(ErrorInner {
+                        pos: Some(pos),
+
+                        message: format!(
+                            "Cannot access element {} on {} of type {}",
+                            index, checked_assignee, ty,
+                        ),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn check_spread_or_expression(
+        &mut self,
+        spread_or_expression: SpreadOrExpression<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedExpressionOrSpread<'ast, T>, ErrorInner> {
+        match spread_or_expression {
+            SpreadOrExpression::Spread(s) => {
+                let pos = s.pos();
+
+                let checked_expression =
+                    self.check_expression(s.value.expression, module_id, types)?;
+
+                match checked_expression {
+                    TypedExpression::Array(a) => Ok(TypedExpressionOrSpread::Spread(a.into())),
+                    e => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected spread operator to apply on array, found {}",
+                            e.get_type()
+                        ),
+                    }),
+                }
+            }
+            SpreadOrExpression::Expression(e) => {
+                self.check_expression(e, module_id, types).map(|r| r.into())
+            }
+        }
+    }
+
+    fn check_function_call_expression(
+        &mut self,
+        function_id: ExpressionNode<'ast>,
+        generics: Option<Vec<Option<ExpressionNode<'ast>>>>,
+        arguments: Vec<ExpressionNode<'ast>>,
+        expected_return_type: Option<Type<'ast, T>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let pos = function_id.pos();
+        let (fun_id, receiver) = match function_id.value {
+            Expression::Identifier(id) => {
+                self.use_symbol(id);
+                Ok((id, None))
+            }
+            // `x.f(...)` calls the method `f` of the type of `x`, with `x` as its first argument
+            Expression::Member(box receiver, box id) => Ok((id, Some(receiver))),
+            e => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected function in function call to be an identifier, found `{}`",
+                    e
+                ),
+            }),
+        }?;
+
+        // check the generic arguments, if any
+        let generics_checked: Option<Vec<Option<UExpression<'ast, T>>>> = generics
+            .map(|generics| {
+                generics
+                    .into_iter()
+                    .map(|g| {
+                        g.map(|g| {
+                            let pos = g.pos();
+                            self.check_expression(g, module_id, types).and_then(|g| {
+                                UExpression::try_from_typed(g, &UBitwidth::B32).map_err(|e| {
+                                    ErrorInner {
+                                        pos: Some(pos),
+                                        message: format!(
+                                            "Expected {} to be of type u32, found {}",
+                                            e,
+                                            e.get_type(),
+                                        ),
+                                    }
+                                })
+                            })
+                        })
+                        .transpose()
+                    })
+                    .collect::<Result<_, _>>()
+            })
+            .transpose()?;
+
+        // check the arguments, starting with the receiver of a method call
+        let is_method_call = receiver.is_some();
+        let receiver = receiver
+            .map(|r| self.check_expression(r, module_id, types))
+            .transpose()?;
+
+        // arrays have no methods, but built-in combinators
+        if let Some(TypedExpression::Array(array)) = receiver {
+            if generics_checked.is_some() {
+                return Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Array combinator {} does not take generic arguments",
+                        fun_id
+                    ),
+                });
+            }
+
+            return self.check_array_combinator(fun_id, array, arguments, pos, module_id, types);
+        }
+
+        // bounded arrays have built-in methods
+        if let Some(TypedExpression::BoundedArray(bounded_array)) = &receiver {
+            if generics_checked.is_some() {
+                return Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Bounded array method {} does not take generic arguments",
+                        fun_id
+                    ),
+                });
+            }
+
+            return self.check_bounded_array_method(
+                fun_id,
+                bounded_array.clone(),
+                arguments,
+                pos,
+                module_id,
+                types,
+            );
+        }
+
+        // the arguments passed for function parameters are functions, which are resolved once the types are known
+        let function_parameters: Vec<_> = match self.type_generic_functions.get(fun_id) {
+            Some(function) if !is_method_call && !self.function_parameters.contains_key(fun_id) => {
+                function
+                    .function_parameters
+                    .iter()
+                    .map(|p| p.index)
+                    .collect()
+            }
+            _ => vec![],
+        };
+
+        let (function_arguments, arguments): (Vec<_>, Vec<_>) = arguments
+            .into_iter()
+            .enumerate()
+            .partition(|(index, _)| function_parameters.contains(index));
+
+        let mut arguments_checked: Vec<_> = receiver.into_iter().collect();
+        for (_, arg) in arguments {
+            let arg_checked = self.check_expression(arg, module_id, types)?;
+            arguments_checked.push(arg_checked);
+        }
+
+        let arguments_types: Vec<_> = arguments_checked.iter().map(|a| a.get_type()).collect();
+
+        let query = FunctionQuery::new(
+            fun_id,
+            &generics_checked,
+            &arguments_types,
+            expected_return_type.clone(),
+        );
+
+        let (function_key, function_arguments) = match self
+            .type_generic_functions
+            .get(fun_id)
+            .cloned()
+        {
+            _ if is_method_call => (self.check_method_call(&query, pos)?, vec![]),
+            // a function parameter is called as the function passed for it
+            _ if self.function_parameters.contains_key(fun_id) => {
+                let key = self.function_parameters[fun_id].clone();
+
+                if !query.match_func(&key.clone().id(fun_id)) {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Function definition for function {} with signature {} not found.",
+                            fun_id, query
+                        ),
+                    });
+                }
+
+                (key, vec![])
+            }
+            Some(function) => self.check_type_generic_call(
+                function,
+                &query,
+                function_arguments.into_iter().map(|(_, a)| a).collect(),
+                module_id,
+                types,
+                pos,
+            )?,
+            None => {
+                let functions = self.find_functions(&query);
+
+                match functions.len() {
+                    // the function has to be defined
+                    1 => {
+                        let mut functions = functions;
+
+                        let f = functions.pop().unwrap();
+
+                        (
+                            DeclarationFunctionKey {
+                                module: module_id.to_path_buf(),
+                                id: f.id,
+                                signature: f.signature,
+                            },
+                            vec![],
+                        )
+                    }
+                    0 => return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Function definition for function {} with signature {} not found.",
+                            fun_id, query
+                        ),
+                    }),
+                    n => return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!("Ambiguous call to function {}, {} candidates were found. Please be more explicit.", fun_id, n)
+                    }),
+                }
+            }
+        };
+
+        Self::check_function_call(
+            function_key,
+            generics_checked,
+            arguments_checked,
+            function_arguments,
+            expected_return_type,
+            pos,
+        )
+    }
+
+    // `map`, `fold`, `zip` and `enumerate` are calls to built-in functions, whose body is generated for the types of
+    // the call site when the call is inlined
+    fn check_array_combinator(
+        &mut self,
+        id: Identifier<'ast>,
+        array: ArrayExpression<'ast, T>,
+        arguments: Vec<ExpressionNode<'ast>>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let combinator = Combinator::from_id(id).ok_or_else(|| ErrorInner {
+            pos: Some(pos),
+            message: format!("Method {} not found for type {}", id, array.get_type()),
+        })?;
+
+        let expected_argument_count = match combinator {
+            Combinator::Map | Combinator::Zip => 1,
+            Combinator::Fold => 2,
+            Combinator::Enumerate => 0,
+        };
+
+        if arguments.len() != expected_argument_count {
+            return Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Array combinator {} expects {} argument(s), found {}",
+                    id,
+                    expected_argument_count,
+                    arguments.len()
+                ),
+            });
+        }
+
+        if self.scope.level == 0 {
+            return Err(ErrorInner {
+                pos: Some(pos),
+                message: format!("Array combinator {} can only be used in functions", id),
+            });
+        }
+
+        let element_ty = array.inner_type().clone();
+        let size = array.size();
+
+        // the type of the value returned by the function passed to the combinator
+        let output_type = |key: &DeclarationFunctionKey<'ast, T>, inputs| {
+            key.signature
+                .get_output_type(vec![None; key.signature.generics.len()], inputs)
+                .map_err(|e| ErrorInner {
+                    pos: Some(pos),
+                    message: e.to_string(),
+                })
+        };
+
+        // the array, with integer literals aligned to the type `ty` expected by the function passed to the combinator
+        let align_elements = |array: ArrayExpression<'ast, T>, ty: &DeclarationType<'ast, T>| {
+            let ty = DeclarationType::array((ty.clone(), Combinator::size()));
+
+            match TypedExpression::align_to_type(array.into(), &ty) {
+                Ok(TypedExpression::Array(array)) => Ok(array),
+                Ok(e) | Err((e, _)) => Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected array to be of type `{}`, found `{}` of type `{}`",
+                        ty,
+                        e,
+                        e.get_type()
+                    ),
+                }),
+            }
+        };
+
+        let mut arguments = arguments.into_iter();
+
+        let (arguments, function_arguments, ty) = match combinator {
+            Combinator::Fold => {
+                let init = self.check_expression(arguments.next().unwrap(), module_id, types)?;
+                let key = self.check_function_argument(
+                    id,
+                    arguments.next().unwrap(),
+                    vec![init.get_type(), element_ty.clone()],
+                    None,
+                    module_id,
+                    types,
+                )?;
+
+                let init = TypedExpression::align_to_type(init, &key.signature.inputs[0])
+                    .map_err(|(e, ty)| ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected initial value of fold to be of type `{}`, found `{}` of type `{}`",
+                            ty,
+                            e,
+                            e.get_type()
+                        ),
+                    })?;
+
+                let array = align_elements(array, &key.signature.inputs[1])?;
+
+                let accumulator_ty = init.get_type();
+                let value_ty = output_type(
+                    &key,
+                    vec![accumulator_ty.clone(), array.inner_type().clone()],
+                )?;
+
+                if value_ty != accumulator_ty {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Function {} used in fold must return {}, found {}",
+                            key.id, accumulator_ty, value_ty
+                        ),
+                    });
+                }
+
+                (vec![array.into(), init], vec![key], accumulator_ty)
+            }
+            Combinator::Map => {
+                let key = self.check_function_argument(
+                    id,
+                    arguments.next().unwrap(),
+                    vec![element_ty.clone()],
+                    None,
+                    module_id,
+                    types,
+                )?;
+
+                let array = align_elements(array, &key.signature.inputs[0])?;
+
+                let value_ty = output_type(&key, vec![array.inner_type().clone()])?;
+
+                (vec![array.into()], vec![key], Type::array((value_ty, size)))
+            }
+            Combinator::Zip => {
+                let other =
+                    match self.check_expression(arguments.next().unwrap(), module_id, types)? {
+                        TypedExpression::Array(other) => other,
+                        e => {
+                            return Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Expected an array to zip with, found `{}` of type `{}`",
+                                    e,
+                                    e.get_type()
+                                ),
+                            })
+                        }
+                    };
+
+                if let (UExpressionInner::Value(a), UExpressionInner::Value(b)) =
+                    (size.as_inner(), other.size().as_inner())
+                {
+                    if a != b {
+                        return Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!(
+                                "Cannot zip arrays of different sizes {} and {}",
+                                a, b
+                            ),
+                        });
+                    }
+                }
+
+                let value_ty =
+                    Type::tuple(TupleType::new(vec![element_ty, other.inner_type().clone()]));
+
+                (
+                    vec![array.into(), other.into()],
+                    vec![],
+                    Type::array((value_ty, size)),
+                )
+            }
+            Combinator::Enumerate => {
+                let value_ty =
+                    Type::tuple(TupleType::new(vec![Type::Uint(UBitwidth::B32), element_ty]));
+
+                (vec![array.into()], vec![], Type::array((value_ty, size)))
+            }
+        };
+
+        self.combinators.insert(combinator);
+
+        Ok(Self::function_call_expression(
+            ty,
+            combinator.key(module_id),
+            vec![None],
+            arguments,
+            function_arguments,
+        ))
+    }
+
+    // `len()`, `get(i)` and `push(e)` on a bounded array. Accessing an element past the length of the array or
+    // pushing to a full array fails at runtime. `push` does not mutate the array but returns the new one
+    fn check_bounded_array_method(
+        &mut self,
+        id: Identifier<'ast>,
+        bounded_array: BoundedArrayExpression<'ast, T>,
+        arguments: Vec<ExpressionNode<'ast>>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let expected_argument_count = match id {
+            "len" => 0,
+            "get" | "push" => 1,
+            _ => {
+                return Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Method {} not found for type {}",
+                        id,
+                        bounded_array.get_type()
+                    ),
+                })
+            }
+        };
+
+        if arguments.len() != expected_argument_count {
+            return Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Bounded array method {} expects {} argument(s), found {}",
+                    id,
+                    expected_argument_count,
+                    arguments.len()
+                ),
+            });
+        }
+
+        let mut arguments = arguments.into_iter();
+
+        match id {
+            "len" => Ok(bounded_array.len().into()),
+            "get" => {
+                let index = self.check_expression(arguments.next().unwrap(), module_id, types)?;
+                let index = UExpression::try_from_typed(index, &UBitwidth::B32).map_err(|e| {
+                    ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected bounded array index to have type u32, found {}",
+                            e.get_type()
+                        ),
+                    }
+                })?;
+
+                Ok(array_element(bounded_array.data(), index))
+            }
+            "push" => {
+                let element = self.check_expression(arguments.next().unwrap(), module_id, types)?;
+                let element = TypedExpression::align_to_type(element, bounded_array.inner_type())
+                    .map_err(|(e, ty)| ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected element pushed to a bounded array to be of type {}, found `{}` of type {}",
+                            ty,
+                            e,
+                            e.get_type()
+                        ),
+                    })?;
+
+                let ty = bounded_array.ty().clone();
+
+                Ok(
+                    BoundedArrayExpressionInner::Push(box bounded_array, box element)
+                        .annotate(ty)
+                        .into(),
+                )
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // the bounded array of type `ty` holding the elements of `array`, whose size must not exceed the capacity
+    fn check_bounded_array_value(
+        &self,
+        array: ArrayExpression<'ast, T>,
+        ty: ArrayType<'ast, T>,
+        pos: (Position, Position),
+        module_id: &ModuleId,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let size = array.size();
+        let array =
+            ArrayExpression::try_from_int(array, &ArrayType::new(*ty.ty.clone(), size.clone()))
+                .map_err(|e| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected an array of {} to assign to a bounded array of type {}, found `{}` of type {}",
+                        ty.ty,
+                        Type::BoundedArray(ty.clone()),
+                        e,
+                        e.get_type()
+                    ),
+                })?;
+
+        // when the sizes are not known yet, the capacity is checked during propagation once they are
+        let metadata = match (size.as_inner(), ty.size.as_inner()) {
+            (UExpressionInner::Value(size), UExpressionInner::Value(capacity)) => {
+                if size > capacity {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Cannot assign an array of size {} to a bounded array of capacity {}",
+                            size, capacity
+                        ),
+                    });
+                }
+                None
+            }
+            _ => Some(SourceMetadata::new(module_id.display().to_string(), pos.0)),
+        };
+
+        Ok(BoundedArrayExpressionInner::Value(box array, metadata)
+            .annotate(ty)
+            .into())
+    }
+
+    // the function passed as `argument` to a combinator or a function parameter of `function`, which is called with
+    // arguments of types `inputs`. It is either the name of a function, a function parameter, or an anonymous function
+    fn check_function_argument(
+        &mut self,
+        function: Identifier<'ast>,
+        argument: ExpressionNode<'ast>,
+        inputs: Vec<Type<'ast, T>>,
+        output: Option<Type<'ast, T>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<DeclarationFunctionKey<'ast, T>, ErrorInner> {
+        let pos = argument.pos();
+
+        let id = match argument.value {
+            Expression::Identifier(id) => id,
+            Expression::Lambda(box lambda) => {
+                return self.check_lambda(function, lambda, inputs, output, module_id, types)
+            }
+            // a function imported under a namespace
+            Expression::EnumVariant(namespace, id, arguments)
+                if arguments.is_empty()
+                    && self.find_namespaced_symbol(&namespace, id).is_some() =>
+            {
+                self.find_namespaced_symbol(&namespace, id).unwrap()
+            }
+            e => {
+                return Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected a function name or an anonymous function as argument of {}, found `{}`",
+                        function, e
+                    ),
+                })
+            }
+        };
+
+        self.use_symbol(id);
+
+        let query = FunctionQuery::new(id, &None, &inputs, output);
+
+        if let Some(key) = self.function_parameters.get(id) {
+            return match query.match_func(&key.clone().id(id)) {
+                true => Ok(key.clone()),
+                false => Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Function definition for function {} with signature {} not found.",
+                        id, query
+                    ),
+                }),
+            };
+        }
+
+        let mut functions = self.find_functions(&query);
+
+        match functions.len() {
+            1 => {
+                let f = functions.pop().unwrap();
+
+                Ok(DeclarationFunctionKey {
+                    module: module_id.to_path_buf(),
+                    id: f.id,
+                    signature: f.signature,
+                })
+            }
+            0 => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Function definition for function {} with signature {} not found.",
+                    id, query
+                ),
+            }),
+            n => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Ambiguous call to function {}, {} candidates were found. Please be more explicit.",
+                    id, n
+                ),
+            }),
+        }
+    }
+
+    // an anonymous function passed to `function`. It is declared as a function of its own in a module named after its
+    // position, and checked once the enclosing function is checked
+    fn check_lambda(
+        &mut self,
+        function: Identifier<'ast>,
+        lambda: FunctionNode<'ast>,
+        inputs: Vec<Type<'ast, T>>,
+        output: Option<Type<'ast, T>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<DeclarationFunctionKey<'ast, T>, ErrorInner> {
+        let pos = lambda.pos();
+
+        // the signature of the anonymous function is part of its key, so it must not depend on any value
+        let mut concrete_type = |ty: UnresolvedTypeNode<'ast>| {
+            let ty = self.check_type(ty, module_id, types)?;
+
+            ConcreteType::try_from(ty.clone())
+                .map(DeclarationType::from)
+                .map_err(|_| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Expected the types of an anonymous function to be of known size, found {}",
+                        ty
+                    ),
+                })
+        };
+
+        let signature = DeclarationSignature::new()
+            .inputs(
+                lambda
+                    .value
+                    .signature
+                    .inputs
+                    .clone()
+                    .into_iter()
+                    .map(&mut concrete_type)
+                    .collect::<Result<_, _>>()?,
+            )
+            .output(concrete_type(
+                lambda.value.signature.output.clone().unwrap(),
+            )?);
+
+        let location = module_id.join(format!("lambda@{}", pos.0));
+
+        let key = DeclarationFunctionKey::with_location(location, "lambda").signature(signature);
+
+        let query = FunctionQuery::new("lambda", &None, &inputs, output);
+
+        if !query.match_func(&key) {
+            return Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected a function with signature {} as argument of {}, found an anonymous function with signature {}",
+                    query, function, key.signature
+                ),
+            });
+        }
+
+        self.lambdas.push(Lambda {
+            key: key.clone(),
+            node: lambda,
+        });
+
+        Ok(key)
+    }
+
+    // find the method matching a query whose first input is the type of the receiver
+    fn check_method_call(
+        &self,
+        query: &FunctionQuery<'ast, T>,
+        pos: (Position, Position),
+    ) -> Result<DeclarationFunctionKey<'ast, T>, ErrorInner> {
+        let methods: HashSet<_> = self
+            .impl_functions
+            .iter()
+            .filter(|f| f.is_method)
+            .map(|f| f.key.clone())
+            .collect();
+
+        let mut methods = query.match_funcs(&methods);
+
+        match methods.len() {
+            1 => Ok(methods.pop().unwrap()),
+            0 => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Method {} not found for type {}, with signature {}",
+                    query.id, query.inputs[0], query
+                ),
+            }),
+            n => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Ambiguous call to method {}, {} candidates were found. Please be more explicit.",
+                    query.id, n
+                ),
+            }),
+        }
+    }
+
+    // an operator applied to a struct calls the method reserved for it on its left operand, such as `op_add` for `+`, if
+    // one matches the operands. Otherwise, the operator is checked as usual
+    fn check_operator_overload(
+        &self,
+        name: Identifier<'ast>,
+        e1: &TypedExpression<'ast, T>,
+        e2: &TypedExpression<'ast, T>,
+        pos: (Position, Position),
+    ) -> Option<Result<TypedExpression<'ast, T>, ErrorInner>> {
+        if !matches!(e1, TypedExpression::Struct(..)) {
+            return None;
+        }
+
+        let query = FunctionQuery::new(name, &None, &[e1.get_type(), e2.get_type()], None);
+
+        let methods: HashSet<_> = self
+            .impl_functions
+            .iter()
+            .filter(|f| f.is_method)
+            .map(|f| f.key.clone())
+            .collect();
+
+        if query.match_funcs(&methods).is_empty() {
+            return None;
+        }
+
+        Some(self.check_method_call(&query, pos).and_then(|key| {
+            // `==` and `!=` must remain boolean expressions
+            if name == "op_eq" && *key.signature.output != DeclarationType::Boolean {
+                return Err(ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Method op_eq used by `==` must return bool, found {}",
+                        key.signature.output
+                    ),
+                });
+            }
+
+            Self::check_function_call(key, None, vec![e1.clone(), e2.clone()], vec![], None, pos)
+        }))
+    }
+
+    // build a call to the function at `function_key`, whose signature is known to match the arguments, passing
+    // `function_arguments` for its function parameters
+    fn check_function_call(
+        function_key: DeclarationFunctionKey<'ast, T>,
+        generics_checked: Option<Vec<Option<UExpression<'ast, T>>>>,
+        arguments_checked: Vec<TypedExpression<'ast, T>>,
+        function_arguments: Vec<DeclarationFunctionKey<'ast, T>>,
+        expected_return_type: Option<Type<'ast, T>>,
+        pos: (Position, Position),
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let signature = &function_key.signature;
+
+        let arguments_checked = arguments_checked
+            .into_iter()
+            .zip(signature.inputs.iter())
+            .map(|(a, t)| TypedExpression::align_to_type(a, t))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Expected function call argument to be of type `{}`, found `{}` of type `{}`",
+                    e.1,
+                    e.0,
+                    e.0.get_type()
+                ),
+            })?;
+
+        let generics_checked =
+            generics_checked.unwrap_or_else(|| vec![None; signature.generics.len()]);
+
+        let output_type = expected_return_type.map(Ok).unwrap_or_else(|| {
+            signature
+                .get_output_type(
+                    generics_checked.clone(),
+                    arguments_checked.iter().map(|a| a.get_type()).collect(),
+                )
+                .map_err(|e| ErrorInner {
+                    pos: Some(pos),
+                    message: e.to_string(),
+                })
+        })?;
+
+        Ok(Self::function_call_expression(
+            output_type,
+            function_key,
+            generics_checked,
+            arguments_checked,
+            function_arguments,
+        ))
+    }
+
+    // a call of type `output_type` to the function at `function_key`
+    fn function_call_expression(
+        output_type: Type<'ast, T>,
+        function_key: DeclarationFunctionKey<'ast, T>,
+        generics: Vec<Option<UExpression<'ast, T>>>,
+        arguments: Vec<TypedExpression<'ast, T>>,
+        function_arguments: Vec<DeclarationFunctionKey<'ast, T>>,
+    ) -> TypedExpression<'ast, T> {
+        match output_type {
+            Type::Int => unreachable!(),
+            Type::FieldElement => FieldElementExpression::FunctionCall(
+                FunctionCallExpression::new(function_key, generics, arguments)
+                    .function_arguments(function_arguments),
+            )
+            .into(),
+            Type::Boolean => BooleanExpression::FunctionCall(
+                FunctionCallExpression::new(function_key, generics, arguments)
+                    .function_arguments(function_arguments),
+            )
+            .into(),
+            Type::Uint(bitwidth) => UExpressionInner::FunctionCall(
+                FunctionCallExpression::new(function_key, generics, arguments)
+                    .function_arguments(function_arguments),
+            )
+            .annotate(bitwidth)
+            .into(),
+            Type::Struct(struct_ty) => StructExpressionInner::FunctionCall(
+                FunctionCallExpression::new(function_key, generics, arguments)
+                    .function_arguments(function_arguments),
+            )
+            .annotate(struct_ty)
+            .into(),
+            Type::Array(array_ty) => ArrayExpressionInner::FunctionCall(
+                FunctionCallExpression::new(function_key, generics, arguments)
+                    .function_arguments(function_arguments),
+            )
+            .annotate(*array_ty.ty, *array_ty.size)
+            .into(),
+            Type::Tuple(tuple_ty) => TupleExpressionInner::FunctionCall(
+                FunctionCallExpression::new(function_key, generics, arguments)
+                    .function_arguments(function_arguments),
+            )
+            .annotate(tuple_ty)
+            .into(),
+            Type::Enum(enum_ty) => EnumExpressionInner::FunctionCall(
+                FunctionCallExpression::new(function_key, generics, arguments)
+                    .function_arguments(function_arguments),
+            )
+            .annotate(enum_ty)
+            .into(),
+            Type::BoundedArray(array_ty) => BoundedArrayExpressionInner::FunctionCall(
+                FunctionCallExpression::new(function_key, generics, arguments)
+                    .function_arguments(function_arguments),
+            )
+            .annotate(array_ty)
+            .into(),
+        }
+    }
+
+    // resolve a call to a function with type parameters to the key of its instance, inferring the type arguments from the arguments,
+    // along with the functions passed for its function parameters
+    fn check_type_generic_call(
+        &mut self,
+        function: TypeGenericFunction<'ast, T>,
+        query: &FunctionQuery<'ast, T>,
+        function_arguments: Vec<ExpressionNode<'ast>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+        pos: (Position, Position),
+    ) -> Result<
+        (
+            DeclarationFunctionKey<'ast, T>,
+            Vec<DeclarationFunctionKey<'ast, T>>,
+        ),
+        ErrorInner,
+    > {
+        let not_found = || ErrorInner {
+            pos: Some(pos),
+            message: format!(
+                "Function definition for function {} with signature {} not found.",
+                query.id, query
+            ),
+        };
+
+        if query.inputs.len() != function.signature.inputs.len()
+            || function_arguments.len() != function.function_parameters.len()
+        {
+            return Err(not_found());
+        }
+
+        let arguments = function
+            .parameters
+            .iter()
+            .map(|p| {
+                let ty = function
+                    .signature
+                    .inputs
+                    .iter()
+                    .zip(query.inputs.iter())
+                    .find_map(|(declared, actual)| find_type_argument(declared, actual, &p.placeholder))
+                    .ok_or_else(not_found)?;
+
+                ConcreteType::try_from(ty.clone())
+                    .map(DeclarationType::from)
+                    .map_err(|_| ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected type parameter {} of function {} to be a type of known size, found {}",
+                            p.id, query.id, ty
+                        ),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bindings: Vec<_> = function
+            .parameters
+            .iter()
+            .map(|p| p.placeholder.clone())
+            .zip(arguments.iter().cloned())
+            .collect();
+
+        let function_arguments = function
+            .function_parameters
+            .iter()
+            .zip(function_arguments)
+            .map(|(p, argument)| {
+                let signature = substitute_signature(p.signature.clone(), &bindings);
+
+                let inputs = signature
+                    .inputs
                     .into_iter()
-                    .map(|e| self.check_type(e, module_id, types))
-                    .collect::<Result<_, _>>()?;
-                Ok(Type::Tuple(TupleType::new(checked_elements)))
+                    .map(|t| specialize_declaration_type(t, &GGenericsAssignment::default()))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| not_found())?;
+                let output =
+                    specialize_declaration_type(*signature.output, &GGenericsAssignment::default())
+                        .map_err(|_| not_found())?;
+
+                self.check_function_argument(
+                    query.id,
+                    argument,
+                    inputs,
+                    Some(output),
+                    module_id,
+                    types,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let key = DeclarationFunctionKey::with_location(function.module.clone(), function.id)
+            .signature(substitute_signature(function.signature.clone(), &bindings));
+
+        // the arguments must match the signature of the instance
+        if !query.match_func(&key.clone().id(query.id)) {
+            return Err(not_found());
+        }
+
+        // an opaque type implements the trait its type parameter is bound by. Other types are checked against the
+        // implementations once the symbol is checked
+        for (p, ty) in function.parameters.iter().zip(arguments) {
+            match self.type_parameters.values().find(|q| q.placeholder == ty) {
+                Some(q) if q.bound != p.bound => {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!("Type {} does not implement trait {}", q.id, p.bound.id),
+                    })
+                }
+                Some(_) => {}
+                None => self.trait_bounds.push(TraitBound {
+                    pos,
+                    ty,
+                    bound: p.bound.clone(),
+                }),
             }
-            UnresolvedType::User(id, generics) => {
-                let declared_ty =
-                    types
-                        .get(module_id)
-                        .unwrap()
-                        .get(&id)
-                        .cloned()
-                        .ok_or_else(|| ErrorInner {
-                            pos: Some(pos),
-                            message: format!("Undefined type {}", id),
-                        })?;
+        }
 
-                let generic_identifiers = declared_ty.declaration_generics();
+        Ok((key, function_arguments))
+    }
 
-                let declaration_type = declared_ty.ty;
+    // check a call to a function through a type, such as `H::hash(a, b)` for a function of the trait of a type parameter,
+    // or `Point::new(x, y)` for a function of an `impl` block
+    #[allow(clippy::too_many_arguments)]
+    fn check_path_call_expression(
+        &mut self,
+        ty: UserTypeId,
+        function_id: Identifier<'ast>,
+        functions: &HashSet<DeclarationFunctionKey<'ast, T>>,
+        arguments: Vec<ExpressionNode<'ast>>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+        pos: (Position, Position),
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let arguments_checked = arguments
+            .into_iter()
+            .map(|a| self.check_expression(a, module_id, types))
+            .collect::<Result<Vec<_>, _>>()?;
 
-                // absence of generics is treated as 0 generics, as we do not provide inference for now
-                let generics = generics.unwrap_or_default();
+        let arguments_types: Vec<_> = arguments_checked.iter().map(|a| a.get_type()).collect();
 
-                // check generics
-                match generic_identifiers.len() == generics.len() {
-                    true => {
-                        // build the generic assignment for this type
-                        let assignment = GGenericsAssignment(generics
-                            .into_iter()
-                            .zip(generic_identifiers)
-                            .enumerate()
-                            .map(|(i, (e, g))| match e {
-                                Some(e) => {
-                                    self
-                                        .check_expression(e, module_id, types)
-                                        .and_then(|e| {
-                                            UExpression::try_from_typed(e, &UBitwidth::B32)
-                                                .map(|e| (GenericIdentifier::with_name(g).with_index(i), e))
-                                                .map_err(|e| ErrorInner {
-                                                    pos: Some(pos),
-                                                    message: format!("Expected u32 expression, but got expression of type {}", e.get_type()),
-                                                })
-                                        })
-                                },
-                                None => Err(ErrorInner {
-                                    pos: Some(pos),
-                                    message:
-                                    "Expected u32 constant or identifier, but found `_`. Generic inference is not supported yet."
-                                        .into(),
-                                })
-                            })
-                            .collect::<Result<_, _>>()?);
+        let query = FunctionQuery::new(function_id, &None, &arguments_types, None);
+
+        let mut functions = query.match_funcs(functions);
+
+        match functions.len() {
+            1 => Self::check_function_call(
+                functions.pop().unwrap(),
+                None,
+                arguments_checked,
+                vec![],
+                None,
+                pos,
+            ),
+            _ => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!(
+                    "Function definition for function {}::{} with signature {} not found.",
+                    ty, function_id, query
+                ),
+            }),
+        }
+    }
+
+    fn check_conditional(
+        pos: (Position, Position),
+        condition_checked: TypedExpression<'ast, T>,
+        consequence_checked: TypedExpression<'ast, T>,
+        alternative_checked: TypedExpression<'ast, T>,
+        kind: zokrates_ast::typed::ConditionalKind,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let (consequence_checked, alternative_checked) =
+            TypedExpression::align_without_integers(
+                consequence_checked,
+                alternative_checked,
+            )
+            .map_err(|(e1, e2)| ErrorInner {
+                pos: Some(pos),
+                message: format!("{{consequence}} and {{alternative}} in conditional expression should have the same type, found {}, {}", e1.get_type(), e2.get_type()),
+            })?;
+
+        match condition_checked {
+            TypedExpression::Boolean(condition) => {
+                match (consequence_checked, alternative_checked) {
+                    (TypedExpression::FieldElement(consequence), TypedExpression::FieldElement(alternative)) => {
+                        Ok(FieldElementExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (TypedExpression::Boolean(consequence), TypedExpression::Boolean(alternative)) => {
+                        Ok(BooleanExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (TypedExpression::Array(consequence), TypedExpression::Array(alternative)) => {
+                        Ok(ArrayExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (TypedExpression::Struct(consequence), TypedExpression::Struct(alternative)) => {
+                        Ok(StructExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (TypedExpression::Tuple(consequence), TypedExpression::Tuple(alternative)) => {
+                        Ok(TupleExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (TypedExpression::BoundedArray(consequence), TypedExpression::BoundedArray(alternative)) => {
+                        Ok(BoundedArrayExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (TypedExpression::Enum(consequence), TypedExpression::Enum(alternative)) => {
+                        Ok(EnumExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (TypedExpression::Uint(consequence), TypedExpression::Uint(alternative)) => {
+                        Ok(UExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (TypedExpression::Int(consequence), TypedExpression::Int(alternative)) => {
+                        Ok(IntExpression::conditional(condition, consequence, alternative, kind).into())
+                    },
+                    (c, a) => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!("{{consequence}} and {{alternative}} in conditional expression should have the same type, found {}, {}", c.get_type(), a.get_type())
+                    })
+                }
+            }
+            c => Err(ErrorInner {
+                pos: Some(pos),
+                message: format!("{{condition}} should be a boolean, found {}", c.get_type()),
+            }),
+        }
+    }
+
+    fn check_expression(
+        &mut self,
+        expr: ExpressionNode<'ast>,
+        module_id: &ModuleId,
+        types: &TypeMap<'ast, T>,
+    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+        let pos = expr.pos();
+
+        match expr.value {
+            Expression::IntConstant(v) => Ok(IntExpression::Value(v).into()),
+            Expression::BooleanConstant(b) => Ok(BooleanExpression::Value(b).into()),
+            Expression::Identifier(name) => {
+                // payload bindings of enclosing `match` arms shadow the scope
+                if let Some((_, e)) = self.match_bindings.iter().rev().find(|(id, _)| *id == name) {
+                    return Ok(e.clone());
+                }
+
+                // the name may refer to a constant symbol of the module
+                self.use_symbol(name);
+
+                // check that `id` is defined in the scope
+                match self.scope.get(name) {
+                    Some(info) => {
+                        if let Some(index) = info.declaration {
+                            self.variables[index].is_used = true;
+                        }
+
+                        let id = info.id;
+                        match info.ty.clone() {
+                            Type::Boolean => Ok(BooleanExpression::identifier(id.into()).into()),
+                            Type::Uint(bitwidth) => {
+                                Ok(UExpression::identifier(id.into()).annotate(bitwidth).into())
+                            }
+                            Type::FieldElement => {
+                                Ok(FieldElementExpression::identifier(id.into()).into())
+                            }
+                            Type::Array(array_type) => Ok(ArrayExpression::identifier(id.into())
+                                .annotate(*array_type.ty, *array_type.size)
+                                .into()),
+                            Type::Struct(members) => Ok(StructExpression::identifier(id.into())
+                                .annotate(members)
+                                .into()),
+                            Type::Tuple(tuple_ty) => Ok(TupleExpression::identifier(id.into())
+                                .annotate(tuple_ty)
+                                .into()),
+                            Type::Enum(enum_ty) => Ok(EnumExpression::identifier(id.into())
+                                .annotate(enum_ty)
+                                .into()),
+                            Type::BoundedArray(array_ty) => {
+                                Ok(BoundedArrayExpression::identifier(id.into())
+                                    .annotate(array_ty)
+                                    .into())
+                            }
+                            Type::Int => unreachable!(),
+                        }
+                    }
+                    None => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!("Identifier \"{}\" is undefined", name),
+                    }),
+                }
+            }
+            Expression::Add(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
+
+                if let Some(e) = self.check_operator_overload("op_add", &e1_checked, &e2_checked, pos)
+                {
+                    return e;
+                }
+
+                use self::TypedExpression::*;
+
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
+                    pos: Some(pos),
+                    message: format!("Cannot apply `+` to {}, {}", e1.get_type(), e2.get_type()),
+                })?;
+
+                match (e1_checked, e2_checked) {
+                    (Int(e1), Int(e2)) => Ok(IntExpression::Add(box e1, box e2).into()),
+                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(FieldElementExpression::Add(box e1, box e2).into())
+                    }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.get_type() == e2.get_type() =>
+                    {
+                        Ok((e1 + e2).into())
+                    }
+                    (t1, t2) => Err(ErrorInner {
+                        pos: Some(pos),
+
+                        message: format!(
+                            "Cannot apply `+` to {}, {}",
+                            t1.get_type(),
+                            t2.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::Sub(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-                        // specialize the declared type using the generic assignment
-                        Ok(specialize_declaration_type(declaration_type, &assignment).unwrap())
+                if let Some(e) = self.check_operator_overload("op_sub", &e1_checked, &e2_checked, pos)
+                {
+                    return e;
+                }
+
+                use self::TypedExpression::*;
+
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
+                    pos: Some(pos),
+                    message: format!("Cannot apply `-` to {}, {}", e1.get_type(), e2.get_type()),
+                })?;
+
+                match (e1_checked, e2_checked) {
+                    (Int(e1), Int(e2)) => Ok(IntExpression::Sub(box e1, box e2).into()),
+                    (FieldElement(e1), FieldElement(e2)) => {
+                        Ok(FieldElementExpression::Sub(box e1, box e2).into())
                     }
-                    false => Err(ErrorInner {
+                    (Uint(e1), Uint(e2)) if e1.get_type() == e2.get_type() => Ok((e1 - e2).into()),
+                    (t1, t2) => Err(ErrorInner {
                         pos: Some(pos),
+
                         message: format!(
-                            "Expected {} generic argument{} on type {}, but got {}",
-                            generic_identifiers.len(),
-                            if generic_identifiers.len() == 1 {
-                                ""
-                            } else {
-                                "s"
-                            },
-                            id,
-                            generics.len()
+                            "Expected only field elements, found {}, {}",
+                            t1.get_type(),
+                            t2.get_type()
                         ),
                     }),
                 }
             }
-        }
-    }
-    fn check_generic_expression(
-        &mut self,
-        expr: ExpressionNode<'ast>,
-        module_id: &ModuleId,
-        constants_map: &BTreeMap<ConstantIdentifier<'ast>, DeclarationType<'ast, T>>,
-        generics_map: &BTreeMap<Identifier<'ast>, usize>,
-        used_generics: &mut HashSet<Identifier<'ast>>,
-    ) -> Result<DeclarationConstant<'ast, T>, ErrorInner> {
-        let pos = expr.pos();
+            Expression::Mult(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-        match expr.value {
-            Expression::U32Constant(c) => Ok(DeclarationConstant::from(c)),
-            Expression::IntConstant(c) => {
-                if c <= BigUint::from(2u128.pow(32) - 1) {
-                    Ok(DeclarationConstant::from(
-                        u32::from_str_radix(&c.to_str_radix(16), 16).unwrap(),
-                    ))
-                } else {
-                    Err(ErrorInner {
+                if let Some(e) = self.check_operator_overload("op_mul", &e1_checked, &e2_checked, pos)
+                {
+                    return e;
+                }
+
+                use self::TypedExpression::*;
+
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
+                    pos: Some(pos),
+                    message: format!("Cannot apply `*` to {}, {}", e1.get_type(), e2.get_type()),
+                })?;
+
+                match (e1_checked, e2_checked) {
+                    (Int(e1), Int(e2)) => Ok(IntExpression::Mult(box e1, box e2).into()),
+                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(FieldElementExpression::Mult(box e1, box e2).into())
+                    }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.get_type() == e2.get_type() =>
+                    {
+                        Ok((e1 * e2).into())
+                    }
+                    (t1, t2) => Err(ErrorInner {
                         pos: Some(pos),
+
                         message: format!(
-                    "Expected array dimension to be a u32 constant or an identifier, found {}",
-                    Expression::IntConstant(c)
-                ),
-                    })
+                            "Cannot apply `*` to {}, {}",
+                            t1.get_type(),
+                            t2.get_type()
+                        ),
+                    }),
                 }
             }
-            Expression::Identifier(name) => {
-                used_generics.insert(name);
+            Expression::Div(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-                match (constants_map.get(name), generics_map.get(&name)) {
-                    (Some(ty), None) => {
-                        match ty {
-                            DeclarationType::Uint(UBitwidth::B32) => Ok(DeclarationConstant::Constant(CanonicalConstantIdentifier::new(name, module_id.into()))),
-                            _ => Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Expected array dimension to be a u32 constant or an identifier, found {} of type {}",
-                                    name, ty
-                                ),
-                            })
-                        }
+                use self::TypedExpression::*;
+
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
+                    pos: Some(pos),
+                    message: format!("Cannot apply `/` to {}, {}", e1.get_type(), e2.get_type()),
+                })?;
+
+                match (e1_checked, e2_checked) {
+                    (Int(e1), Int(e2)) => Ok(IntExpression::Div(box e1, box e2).into()),
+                    (FieldElement(e1), FieldElement(e2)) => {
+                        Ok(FieldElementExpression::Div(box e1, box e2).into())
                     }
-                    (None, Some(index)) => Ok(DeclarationConstant::Generic(GenericIdentifier::with_name(name).with_index(*index))),
-                    _ => Err(ErrorInner {
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.get_type() == e2.get_type() =>
+                    {
+                        Ok((e1 / e2).into())
+                    }
+                    (t1, t2) => Err(ErrorInner {
                         pos: Some(pos),
-                        message: format!("Undeclared symbol `{}`", name)
-                    })
+
+                        message: format!(
+                            "Cannot apply `/` to {}, {}",
+                            t1.get_type(),
+                            t2.get_type()
+                        ),
+                    }),
                 }
             }
-            e => Err(ErrorInner {
-                pos: Some(pos),
-                message: format!(
-                    "Expected array dimension to be a u32 constant or an identifier, found {}",
-                    e
-                ),
-            }),
-        }
-    }
-
-    fn check_declaration_type(
-        &mut self,
-        ty: UnresolvedTypeNode<'ast>,
-        module_id: &ModuleId,
-        state: &State<'ast, T>,
-        generics_map: &BTreeMap<Identifier<'ast>, usize>,
-        used_generics: &mut HashSet<Identifier<'ast>>,
-    ) -> Result<DeclarationType<'ast, T>, ErrorInner> {
-        let pos = ty.pos();
-        let ty = ty.value;
+            Expression::Rem(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-        match ty {
-            UnresolvedType::FieldElement => Ok(DeclarationType::FieldElement),
-            UnresolvedType::Boolean => Ok(DeclarationType::Boolean),
-            UnresolvedType::Uint(bitwidth) => Ok(DeclarationType::uint(bitwidth)),
-            UnresolvedType::Array(t, size) => {
-                let checked_size = self.check_generic_expression(
-                    size.clone(),
-                    module_id,
-                    state.constants.get(module_id).unwrap_or(&BTreeMap::new()),
-                    generics_map,
-                    used_generics,
-                )?;
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
+                    pos: Some(pos),
+                    message: format!("Cannot apply `%` to {}, {}", e1.get_type(), e2.get_type()),
+                })?;
 
-                Ok(DeclarationType::Array(DeclarationArrayType::new(
-                    self.check_declaration_type(*t, module_id, state, generics_map, used_generics)?,
-                    checked_size,
-                )))
-            }
-            UnresolvedType::Tuple(elements) => {
-                let checked_elements: Vec<_> = elements
-                    .into_iter()
-                    .map(|e| {
-                        self.check_declaration_type(
-                            e,
-                            module_id,
-                            state,
-                            generics_map,
-                            used_generics,
-                        )
-                    })
-                    .collect::<Result<_, _>>()?;
-                Ok(DeclarationType::Tuple(DeclarationTupleType::new(
-                    checked_elements,
-                )))
-            }
-            UnresolvedType::User(id, generics) => {
-                let ty = state
-                    .types
-                    .get(module_id)
-                    .unwrap()
-                    .get(&id)
-                    .cloned()
-                    .ok_or_else(|| ErrorInner {
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.get_type() == e2.get_type() =>
+                    {
+                        Ok((e1 % e2).into())
+                    }
+                    (t1, t2) => Err(ErrorInner {
                         pos: Some(pos),
-                        message: format!("Undefined type {}", id),
-                    })?;
 
-                let generics = generics.unwrap_or_default();
-                let checked_generics: Vec<_> = generics
-                    .into_iter()
-                    .map(|e| match e {
-                        Some(e) => self
-                            .check_generic_expression(
-                                e,
-                                module_id,
-                                state.constants.get(module_id).unwrap_or(&BTreeMap::new()),
-                                generics_map,
-                                used_generics,
-                            )
-                            .map(Some),
-                        None => Err(ErrorInner {
-                            pos: Some(pos),
-                            message: "Expected u32 constant or identifier, but found `_`".into(),
-                        }),
-                    })
-                    .collect::<Result<_, _>>()?;
+                        message: format!(
+                            "Cannot apply `%` to {}, {}",
+                            t1.get_type(),
+                            t2.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::Pow(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-                match ty.generics.len() == checked_generics.len() {
-                    true => {
-                        let mut assignment = GGenericsAssignment::default();
+                let e1_checked = match FieldElementExpression::try_from_typed(e1_checked) {
+                    Ok(e) => e.into(),
+                    Err(e) => e,
+                };
+                let e2_checked = match UExpression::try_from_typed(e2_checked, &UBitwidth::B32) {
+                    Ok(e) => e.into(),
+                    Err(e) => e,
+                };
 
-                        assignment.0.extend(ty.generics.iter().zip(checked_generics.iter()).map(|(decl_g, g_val)| match decl_g.clone() {
-                            DeclarationConstant::Generic(g) => (g, g_val.clone().unwrap()),
-                            _ => unreachable!("generic on declaration struct types must be generic identifiers")
-                        }));
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::FieldElement(e1), TypedExpression::Uint(e2)) => Ok(
+                        TypedExpression::FieldElement(FieldElementExpression::Pow(box e1, box e2)),
+                    ),
+                    (t1, t2) => Err(ErrorInner {
+                        pos: Some(pos),
 
-                        let res = match ty.ty {
-                            // if the type is a struct, we do not specialize in the members.
-                            // we only remap the generics
-                            DeclarationType::Struct(declared_struct_ty) => {
-                                DeclarationType::Struct(DeclarationStructType {
-                                    generics: declared_struct_ty
-                                        .generics
-                                        .into_iter()
-                                        .map(|g| g.map(|g| g.map(&assignment).unwrap()))
-                                        .collect(),
-                                    ..declared_struct_ty
-                                })
-                            }
-                            ty => specialize_declaration_type(ty, &assignment).unwrap(),
-                        };
+                        message: format!(
+                            "Expected `field` and `u32`, found {}, {}",
+                            t1.get_type(),
+                            t2.get_type()
+                        ),
+                    }),
+                }
+            }
+            // the minimum value of a signed type is only representable as a negative literal
+            Expression::Neg(box ExpressionNode {
+                value: Expression::I8Constant(n),
+                ..
+            }) => signed_constant(n, UBitwidth::I8, true, pos),
+            Expression::Neg(box ExpressionNode {
+                value: Expression::I16Constant(n),
+                ..
+            }) => signed_constant(n, UBitwidth::I16, true, pos),
+            Expression::Neg(box ExpressionNode {
+                value: Expression::I32Constant(n),
+                ..
+            }) => signed_constant(n, UBitwidth::I32, true, pos),
+            Expression::Neg(box ExpressionNode {
+                value: Expression::I64Constant(n),
+                ..
+            }) => signed_constant(n, UBitwidth::I64, true, pos),
+            Expression::Neg(box e) => {
+                let e = self.check_expression(e, module_id, types)?;
 
-                        Ok(res)
+                match e {
+                    TypedExpression::Int(e) => Ok(IntExpression::Neg(box e).into()),
+                    TypedExpression::FieldElement(e) => {
+                        Ok(FieldElementExpression::Neg(box e).into())
                     }
-                    false => Err(ErrorInner {
+                    TypedExpression::Uint(e) => Ok((-e).into()),
+                    e => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Expected {} generic argument{} on type {}, but got {}",
-                            ty.generics.len(),
-                            if ty.generics.len() == 1 { "" } else { "s" },
-                            id,
-                            checked_generics.len()
+                            "Unary operator `-` cannot be applied to {} of type {}",
+                            e,
+                            e.get_type()
                         ),
                     }),
                 }
             }
-        }
-    }
+            Expression::Pos(box e) => {
+                let e = self.check_expression(e, module_id, types)?;
 
-    fn check_variable(
-        &mut self,
-        v: zokrates_ast::untyped::VariableNode<'ast>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<Variable<'ast, T>, Vec<ErrorInner>> {
-        let ty = self
-            .check_type(v.value._type, module_id, types)
-            .map_err(|e| vec![e])?;
+                match e {
+                    TypedExpression::Int(e) => Ok(IntExpression::Pos(box e).into()),
+                    TypedExpression::FieldElement(e) => {
+                        Ok(FieldElementExpression::Pos(box e).into())
+                    }
+                    TypedExpression::Uint(e) => Ok(UExpression::pos(e).into()),
+                    e => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Unary operator `+` cannot be applied to {} of type {}",
+                            e,
+                            e.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::Conditional(box conditional) => {
+                self.check_constant_condition(&conditional.condition, module_id);
 
-        // insert into the scope and ignore whether shadowing happened
-        self.insert_into_scope(v.value.id, ty.clone(), v.value.is_mutable);
+                let condition_checked =
+                    self.check_expression(*conditional.condition, module_id, types)?;
 
-        Ok(Variable::new(
-            self.id_in_this_scope(v.value.id),
-            ty,
-            v.value.is_mutable,
-        ))
-    }
+                if !conditional.consequence_statements.is_empty()
+                    || !conditional.alternative_statements.is_empty()
+                {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: "Statements are not supported in conditional branches".to_string(),
+                    });
+                }
 
-    fn check_for_loop(
-        &mut self,
-        var: zokrates_ast::untyped::VariableNode<'ast>,
-        range: (ExpressionNode<'ast>, ExpressionNode<'ast>),
-        statements: Vec<StatementNode<'ast>>,
-        pos: (Position, Position),
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<TypedStatement<'ast, T>, Vec<ErrorInner>> {
-        let from = self
-            .check_expression(range.0, module_id, types)
-            .map_err(|e| vec![e])?;
-        let to = self
-            .check_expression(range.1, module_id, types)
-            .map_err(|e| vec![e])?;
+                let consequence_checked =
+                    self.check_expression(*conditional.consequence, module_id, types)?;
+                let alternative_checked =
+                    self.check_expression(*conditional.alternative, module_id, types)?;
 
-        let from = match from {
-            TypedExpression::Uint(from) => match from.bitwidth() {
-                UBitwidth::B32 => Ok(from),
-                bitwidth => Err(ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Expected lower loop bound to be of type u32, found {}",
-                        Type::<T>::Uint(bitwidth)
-                    ),
-                }),
-            },
-            TypedExpression::Int(v) => {
-                UExpression::try_from_int(v, &UBitwidth::B32).map_err(|_| ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Expected lower loop bound to be of type u32, found {}",
-                        Type::<T>::Int
-                    ),
-                })
-            }
-            from => Err(ErrorInner {
-                pos: Some(pos),
-                message: format!(
-                    "Expected lower loop bound to be of type u32, found {}",
-                    from.get_type()
-                ),
-            }),
-        }
-        .map_err(|e| vec![e])?;
+                let kind = match conditional.kind {
+                    zokrates_ast::untyped::ConditionalKind::IfElse => {
+                        zokrates_ast::typed::ConditionalKind::IfElse
+                    }
+                    zokrates_ast::untyped::ConditionalKind::Ternary => {
+                        zokrates_ast::typed::ConditionalKind::Ternary
+                    }
+                };
 
-        let to = match to {
-            TypedExpression::Uint(to) => match to.bitwidth() {
-                UBitwidth::B32 => Ok(to),
-                bitwidth => Err(ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Expected upper loop bound to be of type u32, found {}",
-                        Type::<T>::Uint(bitwidth)
-                    ),
-                }),
-            },
-            TypedExpression::Int(v) => {
-                UExpression::try_from_int(v, &UBitwidth::B32).map_err(|_| ErrorInner {
+                Self::check_conditional(
+                    pos,
+                    condition_checked,
+                    consequence_checked,
+                    alternative_checked,
+                    kind,
+                )
+            }
+            Expression::FieldConstant(n) => Ok(FieldElementExpression::Number(
+                T::try_from(n).map_err(|_| ErrorInner {
                     pos: Some(pos),
                     message: format!(
-                        "Expected upper loop bound to be of type u32, found {}",
-                        Type::<T>::Int
+                        "Field constant not in the representable range [{}, {}]",
+                        T::min_value(),
+                        T::max_value()
                     ),
-                })
-            }
-            to => Err(ErrorInner {
-                pos: Some(pos),
-                message: format!(
-                    "Expected upper loop bound to be of type u32, found {}",
-                    to.get_type()
-                ),
-            }),
-        }
-        .map_err(|e| vec![e])?;
-
-        let var = self.check_for_variable(var, module_id, types)?;
-
-        let checked_statements = statements
-            .into_iter()
-            .map(|s| self.check_statement(s, module_id, types))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        Ok(TypedStatement::For(var, from, to, checked_statements))
-    }
-
-    // the assignee is already checked to be defined and mutable
-    fn check_rhs(
-        &mut self,
-        return_type: Type<'ast, T>,
-        expr: ExpressionNode<'ast>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
-        match expr.value {
-            // for function calls, check the rhs with the expected type
+                })?,
+            )
+            .into()),
+            Expression::U8Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(8).into()),
+            Expression::U16Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(16).into()),
+            Expression::U32Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(32).into()),
+            Expression::U64Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(64).into()),
+            Expression::U128Constant(n) => Ok(UExpressionInner::Value(n).annotate(128).into()),
+            Expression::I8Constant(n) => signed_constant(n, UBitwidth::I8, false, pos),
+            Expression::I16Constant(n) => signed_constant(n, UBitwidth::I16, false, pos),
+            Expression::I32Constant(n) => signed_constant(n, UBitwidth::I32, false, pos),
+            Expression::I64Constant(n) => signed_constant(n, UBitwidth::I64, false, pos),
             Expression::FunctionCall(box fun_id_expression, generics, arguments) => self
                 .check_function_call_expression(
                     fun_id_expression,
                     generics,
                     arguments,
-                    Some(return_type),
+                    None,
                     module_id,
                     types,
                 ),
-            // otherwise, just check the rhs normally
-            _ => self.check_expression(expr, module_id, types),
-        }
-    }
-
-    fn check_assembly_statement(
-        &mut self,
-        stat: AssemblyStatementNode<'ast>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<Vec<TypedAssemblyStatement<'ast, T>>, ErrorInner> {
-        let pos = stat.pos();
-
-        match stat.value {
-            AssemblyStatement::Assignment(assignee, expression, constrained) => {
-                let assignee = self.check_assignee(assignee, module_id, types)?;
-                let e = self.check_expression(expression, module_id, types)?;
+            Expression::Lt(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-                let e = FieldElementExpression::try_from_typed(e).map_err(|e| ErrorInner {
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
                     pos: Some(pos),
                     message: format!(
-                        "Expected right hand side of an assembly assignment to be of type field, found {}",
-                        e.get_type(),
+                        "Cannot compare {} of type {} to {} of type {}",
+                        e1,
+                        e1.get_type(),
+                        e2,
+                        e2.get_type()
                     ),
                 })?;
 
-                match constrained {
-                    true => {
-                        let e = FieldElementExpression::block(vec![], e);
-                        match assignee.get_type() {
-                            Type::FieldElement => Ok(vec![
-                                TypedAssemblyStatement::Assignment(
-                                    assignee.clone(),
-                                    e.clone().into(),
-                                ),
-                                TypedAssemblyStatement::Constraint(
-                                    assignee.into(),
-                                    e,
-                                    SourceMetadata::new(module_id.display().to_string(), pos.0),
-                                ),
-                            ]),
-                            ty => Err(ErrorInner {
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(BooleanExpression::FieldLt(box e1, box e2).into())
+                    }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
+                        if e1.get_type() == e2.get_type() {
+                            Ok(BooleanExpression::UintLt(box e1, box e2).into())
+                        } else {
+                            Err(ErrorInner {
                                 pos: Some(pos),
-                                message: format!("Assignee must be of type field, found {}", ty),
-                            }),
+                                message: format!(
+                                    "Cannot compare {} of type {} to {} of type {}",
+                                    e1,
+                                    e1.get_type(),
+                                    e2,
+                                    e2.get_type()
+                                ),
+                            })
                         }
                     }
-                    false => {
-                        let e = FieldElementExpression::block(vec![], e);
-                        Ok(vec![TypedAssemblyStatement::Assignment(assignee, e.into())])
-                    }
+                    (e1, e2) => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Cannot compare {} of type {} to {} of type {}",
+                            e1,
+                            e1.get_type(),
+                            e2,
+                            e2.get_type()
+                        ),
+                    }),
                 }
             }
-            AssemblyStatement::Constraint(lhs, rhs) => {
-                let lhs = self.check_expression(lhs, module_id, types)?;
-                let rhs = self.check_expression(rhs, module_id, types)?;
+            Expression::Le(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-                let lhs = FieldElementExpression::try_from_typed(lhs).map_err(|e| ErrorInner {
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
                     pos: Some(pos),
                     message: format!(
-                        "Expected left hand side of a constraint to be of type field, found {}",
-                        e.get_type(),
+                        "Cannot compare {} of type {} to {} of type {}",
+                        e1,
+                        e1.get_type(),
+                        e2,
+                        e2.get_type()
                     ),
                 })?;
 
-                let rhs = FieldElementExpression::try_from_typed(rhs).map_err(|e| ErrorInner {
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(BooleanExpression::FieldLe(box e1, box e2).into())
+                    }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
+                        if e1.get_type() == e2.get_type() {
+                            Ok(BooleanExpression::UintLe(box e1, box e2).into())
+                        } else {
+                            Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Cannot compare {} of type {} to {} of type {}",
+                                    e1,
+                                    e1.get_type(),
+                                    e2,
+                                    e2.get_type()
+                                ),
+                            })
+                        }
+                    }
+                    (e1, e2) => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Cannot compare {} of type {} to {} of type {}",
+                            e1,
+                            e1.get_type(),
+                            e2,
+                            e2.get_type()
+                        ),
+                    }),
+                }
+            }
+            Expression::Eq(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
+
+                if let Some(e) = self.check_operator_overload("op_eq", &e1_checked, &e2_checked, pos) {
+                    return e;
+                }
+
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
                     pos: Some(pos),
                     message: format!(
-                        "Expected right hand side of a constraint to be of type field, found {}",
-                        e.get_type(),
+                        "Cannot compare {} of type {} to {} of type {}",
+                        e1,
+                        e1.get_type(),
+                        e2,
+                        e2.get_type()
                     ),
                 })?;
 
-                Ok(vec![TypedAssemblyStatement::Constraint(
-                    lhs,
-                    rhs,
-                    SourceMetadata::new(module_id.display().to_string(), pos.0),
-                )])
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(BooleanExpression::FieldEq(EqExpression::new(e1, e2)).into())
+                    }
+                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
+                        Ok(BooleanExpression::BoolEq(EqExpression::new(e1, e2)).into())
+                    }
+                    (TypedExpression::Array(e1), TypedExpression::Array(e2)) => {
+                        Ok(BooleanExpression::ArrayEq(EqExpression::new(e1, e2)).into())
+                    }
+                    (TypedExpression::Struct(e1), TypedExpression::Struct(e2)) => {
+                        Ok(BooleanExpression::StructEq(EqExpression::new(e1, e2)).into())
+                    }
+                    (TypedExpression::Tuple(e1), TypedExpression::Tuple(e2)) => {
+                        Ok(BooleanExpression::TupleEq(EqExpression::new(e1, e2)).into())
+                    }
+                    (TypedExpression::Enum(e1), TypedExpression::Enum(e2)) => {
+                        Ok(BooleanExpression::EnumEq(EqExpression::new(e1, e2)).into())
+                    }
+                    // bounded arrays are compared on their length and elements
+                    (TypedExpression::BoundedArray(e1), TypedExpression::BoundedArray(e2))
+                        if e1.get_type() == e2.get_type() =>
+                    {
+                        Ok(e1.equals(e2).into())
+                    }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.get_type() == e2.get_type() =>
+                    {
+                        Ok(BooleanExpression::UintEq(EqExpression::new(e1, e2)).into())
+                    }
+                    (e1, e2) => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Cannot compare {} of type {} to {} of type {}",
+                            e1,
+                            e1.get_type(),
+                            e2,
+                            e2.get_type()
+                        ),
+                    }),
+                }
             }
-        }
-    }
+            Expression::Ge(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-    fn check_statement(
-        &mut self,
-        stat: StatementNode<'ast>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<TypedStatement<'ast, T>, Vec<ErrorInner>> {
-        let pos = stat.pos();
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Cannot compare {} of type {} to {} of type {}",
+                        e1,
+                        e1.get_type(),
+                        e2,
+                        e2.get_type()
+                    ),
+                })?;
 
-        match stat.value {
-            Statement::Assembly(statements) => {
-                let mut checked_statements = vec![];
-                for s in statements {
-                    checked_statements.extend(
-                        self.check_assembly_statement(s, module_id, types)
-                            .map_err(|e| vec![e])?,
-                    );
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(BooleanExpression::FieldGe(box e1, box e2).into())
+                    }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
+                        if e1.get_type() == e2.get_type() {
+                            Ok(BooleanExpression::UintGe(box e1, box e2).into())
+                        } else {
+                            Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Cannot compare {} of type {} to {} of type {}",
+                                    e1,
+                                    e1.get_type(),
+                                    e2,
+                                    e2.get_type()
+                                ),
+                            })
+                        }
+                    }
+                    (e1, e2) => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Cannot compare {} of type {} to {} of type {}",
+                            e1,
+                            e1.get_type(),
+                            e2,
+                            e2.get_type()
+                        ),
+                    }),
                 }
-                Ok(TypedStatement::Assembly(checked_statements))
             }
-            Statement::Log(l, expressions) => {
-                let l = FormatString::from(l);
-
-                let expressions = expressions
-                    .into_iter()
-                    .map(|e| self.check_expression(e, module_id, types))
-                    .collect::<Result<Vec<_>, _>>()
-                    .map_err(|e| vec![e])?;
+            Expression::Gt(box e1, box e2) => {
+                let e1_checked = self.check_expression(e1, module_id, types)?;
+                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-                let mut errors = vec![];
+                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
+                    e1_checked, e2_checked,
+                )
+                .map_err(|(e1, e2)| ErrorInner {
+                    pos: Some(pos),
+                    message: format!(
+                        "Cannot compare {} of type {} to {} of type {}",
+                        e1,
+                        e1.get_type(),
+                        e2,
+                        e2.get_type()
+                    ),
+                })?;
 
-                for e in &expressions {
-                    if let TypedExpression::Int(e) = e {
-                        errors.push(ErrorInner {
-                            pos: Some(pos),
-                            message: format!("Cannot determine type for expression `{}`", e),
-                        });
+                match (e1_checked, e2_checked) {
+                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(BooleanExpression::FieldGt(box e1, box e2).into())
+                    }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
+                        if e1.get_type() == e2.get_type() {
+                            Ok(BooleanExpression::UintGt(box e1, box e2).into())
+                        } else {
+                            Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Cannot compare {} of type {} to {} of type {}",
+                                    e1,
+                                    e1.get_type(),
+                                    e2,
+                                    e2.get_type()
+                                ),
+                            })
+                        }
                     }
-                }
-
-                if expressions.len() != l.len() {
-                    errors.push(ErrorInner {
+                    (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Wrong argument count in log call: expected {}, got {}",
-                            l.len(),
-                            expressions.len()
+                            "Cannot compare {} of type {} to {} of type {}",
+                            e1,
+                            e1.get_type(),
+                            e2,
+                            e2.get_type()
                         ),
-                    });
+                    }),
                 }
+            }
+            Expression::Select(box array, box index) => {
+                let array = self.check_expression(array, module_id, types)?;
 
-                if !errors.is_empty() {
-                    return Err(errors);
-                }
+                match index {
+                    RangeOrExpression::Range(r) => {
+                        match array {
+                            TypedExpression::Array(array) => {
+                                let array_size = array.size();
 
-                Ok(TypedStatement::Log(l, expressions))
-            }
-            Statement::Return(e) => {
-                let mut errors = vec![];
+                                let inner_type = array.inner_type().clone();
 
-                // we clone the return type because there might be other return statements
-                let return_type = self.return_type.clone().unwrap();
+                                // check that the bounds are valid expressions
+                                let from = r
+                                    .value
+                                    .from
+                                    .map(|e| self.check_expression(e, module_id, types))
+                                    .unwrap_or_else(|| Ok(UExpression::from(0u32).into()))?;
 
-                let e_checked = e
-                    .map(|e| {
-                        match e.value {
-                            Expression::FunctionCall(
-                                box fun_id_expression,
-                                generics,
-                                arguments,
-                            ) => {
-                                let ty = zokrates_ast::typed::types::try_from_g_type(
-                                    return_type.clone(),
-                                )
-                                .map(Some)
-                                .unwrap();
+                                let to = r
+                                    .value
+                                    .to
+                                    .map(|e| self.check_expression(e, module_id, types))
+                                    .unwrap_or_else(|| Ok(array_size.clone().into()))?;
 
-                                self.check_function_call_expression(
-                                    fun_id_expression,
-                                    generics,
-                                    arguments,
-                                    ty,
-                                    module_id,
-                                    types,
+                                let from = UExpression::try_from_typed(from, &UBitwidth::B32).map_err(|e| ErrorInner {
+                                                    pos: Some(pos),
+                                                    message: format!(
+                                                        "Expected the lower bound of the range to be a u32, found {} of type {}",
+                                                        e,
+                                                        e.get_type()
+                                                    ),
+                                                })?;
+
+                                let to = UExpression::try_from_typed(to, &UBitwidth::B32).map_err(|e| ErrorInner {
+                                                    pos: Some(pos),
+                                                    message: format!(
+                                                        "Expected the upper bound of the range to be a u32, found {} of type {}",
+                                                        e,
+                                                        e.get_type()
+                                                    ),
+                                                })?;
+
+                                Ok(ArrayExpressionInner::Slice(
+                                    box array,
+                                    box from.clone(),
+                                    box to.clone(),
                                 )
+                                .annotate(inner_type, UExpression::floor_sub(to, from))
+                                .into())
                             }
-                            _ => self.check_expression(e, module_id, types),
-                        }
-                        .map_err(|e| vec![e])
-                    })
-                    .unwrap_or_else(|| Ok(TypedExpression::empty_tuple()))?;
-
-                let res = match TypedExpression::align_to_type(e_checked.clone(), &return_type)
-                    .map_err(|e| {
-                        vec![ErrorInner {
-                            pos: Some(pos),
-                            message: format!(
-                                "Expected return value to be of type `{}`, found `{}` of type `{}`",
-                                e.1,
-                                e.0,
-                                e.0.get_type()
-                            ),
-                        }]
-                    }) {
-                    Ok(e) => {
-                        match e.get_type() == return_type {
-                            true => {}
-                            false => errors.push(ErrorInner {
+                            e => Err(ErrorInner {
                                 pos: Some(pos),
                                 message: format!(
-                                    "Expected `{}` in return statement, found `{}`",
-                                    return_type,
-                                    e.get_type()
+                                    "Cannot access slice of expression {} of type {}",
+                                    e,
+                                    e.get_type(),
                                 ),
                             }),
                         }
-                        TypedStatement::Return(e)
-                    }
-                    Err(err) => {
-                        errors.extend(err);
-                        TypedStatement::Return(e_checked)
-                    }
-                };
-
-                if !errors.is_empty() {
-                    return Err(errors);
-                }
-
-                Ok(res)
-            }
-            Statement::Definition(var, expr) => {
-                // get the lhs type
-                let var_ty = self
-                    .check_type(var.value._type, module_id, types)
-                    .map_err(|e| vec![e])?;
-
-                // check the rhs based on the lhs type
-                let checked_expr = self
-                    .check_rhs(var_ty.clone(), expr, module_id, types)
-                    .map_err(|e| vec![e])?;
-
-                // insert the lhs into the scope and ignore whether shadowing happened
-                self.insert_into_scope(var.value.id, var_ty.clone(), var.value.is_mutable);
-
-                let var = Variable::new(
-                    self.id_in_this_scope(var.value.id),
-                    var_ty.clone(),
-                    var.value.is_mutable,
-                );
-
-                match var_ty {
-                    Type::FieldElement => FieldElementExpression::try_from_typed(checked_expr)
-                        .map(TypedExpression::from),
-                    Type::Boolean => {
-                        BooleanExpression::try_from_typed(checked_expr).map(TypedExpression::from)
-                    }
-                    Type::Uint(bitwidth) => UExpression::try_from_typed(checked_expr, &bitwidth)
-                        .map(TypedExpression::from),
-                    Type::Array(ref array_ty) => {
-                        ArrayExpression::try_from_typed(checked_expr, array_ty)
-                            .map(TypedExpression::from)
-                    }
-                    Type::Struct(ref struct_ty) => {
-                        StructExpression::try_from_typed(checked_expr, struct_ty)
-                            .map(TypedExpression::from)
-                    }
-                    Type::Tuple(ref tuple_ty) => {
-                        TupleExpression::try_from_typed(checked_expr, tuple_ty)
-                            .map(TypedExpression::from)
                     }
-                    Type::Int => Err(checked_expr), // Integers cannot be assigned
-                }
-                .map_err(|e| ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Expression `{}` of type `{}` cannot be assigned to `{}` of type `{}`",
-                        e,
-                        e.get_type(),
-                        var.clone(),
-                        var_ty
-                    ),
-                })
-                .map(|e| TypedStatement::Definition(var.into(), e.into()))
-                .map_err(|e| vec![e])
-            }
-            Statement::Assignment(assignee, expr) => {
-                // check that the assignee is declared, well formed and mutable
-                let assignee = self
-                    .check_assignee(assignee, module_id, types)
-                    .map_err(|e| vec![e])?;
-
-                let assignee_ty = assignee.get_type();
+                    RangeOrExpression::Expression(index) => {
+                        let index = self.check_expression(index, module_id, types)?;
 
-                let checked_expr = self
-                    .check_rhs(assignee_ty.clone(), expr, module_id, types)
-                    .map_err(|e| vec![e])?;
+                        let index =
+                            UExpression::try_from_typed(index, &UBitwidth::B32).map_err(|e| {
+                                ErrorInner {
+                                    pos: Some(pos),
+                                    message: format!(
+                                        "Expected index to be of type u32, found {}",
+                                        e
+                                    ),
+                                }
+                            })?;
 
-                match assignee_ty {
-                    Type::FieldElement => FieldElementExpression::try_from_typed(checked_expr)
-                        .map(TypedExpression::from),
-                    Type::Boolean => {
-                        BooleanExpression::try_from_typed(checked_expr).map(TypedExpression::from)
-                    }
-                    Type::Uint(bitwidth) => UExpression::try_from_typed(checked_expr, &bitwidth)
-                        .map(TypedExpression::from),
-                    Type::Array(ref array_ty) => {
-                        ArrayExpression::try_from_typed(checked_expr, array_ty)
-                            .map(TypedExpression::from)
-                    }
-                    Type::Struct(ref struct_ty) => {
-                        StructExpression::try_from_typed(checked_expr, struct_ty)
-                            .map(TypedExpression::from)
+                        match array {
+                            TypedExpression::Array(a) => {
+                                match a.inner_type().clone() {
+                                    Type::FieldElement => {
+                                        Ok(FieldElementExpression::select(a, index).into())
+                                    }
+                                    Type::Uint(..) => Ok(UExpression::select(a, index).into()),
+                                    Type::Boolean => Ok(BooleanExpression::select(a, index).into()),
+                                    Type::Array(..) => Ok(ArrayExpression::select(a, index).into()),
+                                    Type::Struct(..) => Ok(StructExpression::select(a, index).into()),
+                                    Type::Tuple(..) => Ok(TupleExpression::select(a, index).into()),
+                                    Type::Enum(..) => Ok(EnumExpression::select(a, index).into()),
+                                    Type::BoundedArray(..) => {
+                                        Ok(BoundedArrayExpression::select(a, index).into())
+                                    }
+                                    Type::Int => unreachable!(),
+                                }
+                            }
+                            a => Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Cannot access element at index {} of type {} on expression {} of type {}",
+                                    index,
+                                    index.get_type(),
+                                    a,
+                                    a.get_type()
+                                ),
+                            }),
+                        }
                     }
-                    Type::Tuple(ref tuple_ty) => {
-                        TupleExpression::try_from_typed(checked_expr, tuple_ty)
-                            .map(TypedExpression::from)
+                }
+            }
+            Expression::Element(box e, index) => {
+                let e = self.check_expression(e, module_id, types)?;
+                match e {
+                    TypedExpression::Tuple(t) => {
+                        let ty = t.ty().elements.get(index as usize);
+
+                        match ty {
+                            Some(ty) => match ty {
+                                Type::Int => unreachable!(),
+                                Type::FieldElement => {
+                                    Ok(FieldElementExpression::element(t, index).into())
+                                }
+                                Type::Boolean => Ok(BooleanExpression::element(t, index).into()),
+                                Type::Uint(..) => Ok(UExpression::element(t, index).into()),
+                                Type::Array(..) => Ok(ArrayExpression::element(t, index).into()),
+                                Type::Struct(..) => Ok(StructExpression::element(t, index).into()),
+                                Type::Tuple(..) => Ok(TupleExpression::element(t, index).into()),
+                                Type::Enum(..) => Ok(EnumExpression::element(t, index).into()),
+                                Type::BoundedArray(..) => {
+                                    Ok(BoundedArrayExpression::element(t, index).into())
+                                }
+                            },
+                            None => Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Tuple of size {} cannot be accessed at index {}",
+                                    t.ty().elements.len(),
+                                    index
+                                ),
+                            }),
+                        }
                     }
-                    Type::Int => Err(checked_expr), // Integers cannot be assigned
+                    e => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Cannot access tuple element {} on expression of type {}",
+                            index,
+                            e.get_type()
+                        ),
+                    }),
                 }
-                .map_err(|e| ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Expression `{}` of type `{}` cannot be assigned to `{}` of type `{}`",
-                        e,
-                        e.get_type(),
-                        assignee.clone(),
-                        assignee_ty
-                    ),
-                })
-                .map(|e| TypedStatement::Definition(assignee, e.into()))
-                .map_err(|e| vec![e])
             }
-            Statement::Assertion(e, message) => {
-                let e = self
-                    .check_expression(e, module_id, types)
-                    .map_err(|e| vec![e])?;
+            Expression::Member(box e, box id) => {
+                let e = self.check_expression(e, module_id, types)?;
 
                 match e {
-                    TypedExpression::Boolean(e) => Ok(TypedStatement::Assertion(
-                        e,
-                        RuntimeError::SourceAssertion(
-                            SourceMetadata::new(module_id.display().to_string(), pos.0)
-                                .message(message),
-                        ),
-                    )),
+                    TypedExpression::Struct(s) => {
+                        // check that the struct has that field and return the type if it does
+                        let ty = s.ty().iter().find(|m| m.id == id).map(|m| *m.ty.clone());
+
+                        match ty {
+                            Some(ty) => match ty {
+                                Type::Int => unreachable!(),
+                                Type::FieldElement => {
+                                    Ok(FieldElementExpression::member(s, id.to_string()).into())
+                                }
+                                Type::Boolean => {
+                                    Ok(BooleanExpression::member(s, id.to_string()).into())
+                                }
+                                Type::Uint(..) => Ok(UExpression::member(s, id.to_string()).into()),
+                                Type::Array(..) => {
+                                    Ok(ArrayExpression::member(s, id.to_string()).into())
+                                }
+                                Type::Struct(..) => {
+                                    Ok(StructExpression::member(s, id.to_string()).into())
+                                }
+                                Type::Tuple(..) => {
+                                    Ok(TupleExpression::member(s, id.to_string()).into())
+                                }
+                                Type::Enum(..) => {
+                                    Ok(EnumExpression::member(s, id.to_string()).into())
+                                }
+                                Type::BoundedArray(..) => {
+                                    Ok(BoundedArrayExpression::member(s, id.to_string()).into())
+                                }
+                            },
+                            None => Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "{} {{{}}} doesn't have member {}",
+                                    s.get_type(),
+                                    s.ty()
+                                        .members
+                                        .iter()
+                                        .map(|m| format!("{}: {}", m.id, m.ty))
+                                        .collect::<Vec<_>>()
+                                        .join(", "),
+                                    id,
+                                ),
+                            }),
+                        }
+                    }
                     e => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Expected {} to be of type bool, found {}",
-                            e,
-                            e.get_type(),
+                            "Cannot access member {} on expression of type {}",
+                            id,
+                            e.get_type()
                         ),
                     }),
                 }
-                .map_err(|e| vec![e])
             }
-            Statement::For(var, from, to, statements) => {
-                self.enter_scope();
+            Expression::InlineArray(expressions_or_spreads) => {
+                // check each expression, getting its type
+                let mut expressions_or_spreads_checked = vec![];
+                for e in expressions_or_spreads {
+                    let e_checked = self.check_spread_or_expression(e, module_id, types)?;
+                    expressions_or_spreads_checked.push(e_checked);
+                }
 
-                let res = self.check_for_loop(var, (from, to), statements, pos, module_id, types);
+                if expressions_or_spreads_checked.is_empty() {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
+                        message: "Empty arrays are not allowed".to_string(),
+                    });
+                }
 
-                self.exit_scope();
+                // we infer the inner type to be the type of the first non-integer element
+                // if there was no such element, then the array only has integers and we use that as the inner type
+                let inferred_type = expressions_or_spreads_checked
+                    .iter()
+                    .filter_map(|e| match e.get_type() {
+                        (Type::Int, _) => None,
+                        (t, _) => Some(t),
+                    })
+                    .next()
+                    .unwrap_or(Type::Int);
 
-                res
-            }
-        }
-    }
+                let unwrapped_expressions_or_spreads = match inferred_type.clone() {
+                    Type::Int => expressions_or_spreads_checked,
+                    t => {
+                        let target_array_ty =
+                            ArrayType::new(t, UExpressionInner::Value(0).annotate(UBitwidth::B32));
 
-    fn check_assignee(
-        &mut self,
-        assignee: AssigneeNode<'ast>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<TypedAssignee<'ast, T>, ErrorInner> {
-        let pos = assignee.pos();
-        // check that the assignee is declared
-        match assignee.value {
-            Assignee::Identifier(variable_name) => match self.scope.get(variable_name) {
-                Some(info) => match info.is_mutable {
-                    false => Err(ErrorInner {
-                        pos: Some(assignee.pos()),
-                        message: format!("Assignment to an immutable variable `{}`", variable_name),
-                    }),
-                    _ => Ok(TypedAssignee::Identifier(Variable::new(
-                        info.id,
-                        info.ty.clone(),
-                        info.is_mutable,
-                    ))),
-                },
-                None => Err(ErrorInner {
-                    pos: Some(assignee.pos()),
-                    message: format!("Variable `{}` is undeclared", variable_name),
-                }),
-            },
-            Assignee::Select(box assignee, box index) => {
-                let checked_assignee = self.check_assignee(assignee, module_id, types)?;
+                        expressions_or_spreads_checked
+                            .into_iter()
+                            .map(|e| {
+                                TypedExpressionOrSpread::align_to_type(e, &target_array_ty).map_err(
+                                    |(e, ty)| ErrorInner {
+                                        pos: Some(pos),
+                                        message: format!("Expected {} to have type {}", e, ty,),
+                                    },
+                                )
+                            })
+                            .collect::<Result<Vec<_>, _>>()?
+                    }
+                };
 
-                let ty = checked_assignee.get_type();
-                match ty {
-                    Type::Array(..) => {
-                        let checked_index = match index {
-                            RangeOrExpression::Expression(e) => {
-                                self.check_expression(e, module_id, types)?
-                            }
-                            r => unimplemented!(
-                                "Using slices in assignments is not supported yet, found {}",
-                                r
-                            ),
-                        };
+                // the size of the inline array is the sum of the size of its elements. However expressed as a u32 expression,
+                // this value can be an tree of height n in the worst case, with n the size of the array (if all elements are
+                // simple values and not spreads, 1 + 1 + 1 + ... 1)
+                // To avoid that, we compute 2 sizes: the sum of all constant sizes as an u32 expression, and the
+                // sum of all non constant sizes as a u32 number. We then return the sum of the two as a u32 expression.
+                // `1 + 1 + ... + 1` is reduced to a single expression, which prevents this blowup
 
-                        let checked_typed_index =
-                            UExpression::try_from_typed(checked_index, &UBitwidth::B32).map_err(
-                                |e| ErrorInner {
-                                    pos: Some(pos),
-                                    message: format!(
-                                        "Expected array {} index to have type u32, found {}",
-                                        checked_assignee,
-                                        e.get_type()
-                                    ),
-                                },
-                            )?;
+                let size: UExpression<'ast, T> = unwrapped_expressions_or_spreads
+                    .iter()
+                    .map(|e| e.size())
+                    .fold(None, |acc, e| match acc {
+                        Some((c_acc, e_acc)) => match e.as_inner() {
+                            UExpressionInner::Value(e) => Some(((c_acc + *e as u32), e_acc)),
+                            _ => Some((c_acc, e_acc + e)),
+                        },
+                        None => match e.as_inner() {
+                            UExpressionInner::Value(e) => Some((*e as u32, 0u32.into())),
+                            _ => Some((0u32, e)),
+                        },
+                    })
+                    .map(|(c_size, e_size)| e_size + c_size.into())
+                    .unwrap_or_else(|| 0u32.into());
 
-                        Ok(TypedAssignee::Select(
-                            box checked_assignee,
-                            box checked_typed_index,
-                        ))
-                    }
-                    ty => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot access element at index {} on {} of type {}",
-                            index, checked_assignee, ty,
-                        ),
-                    }),
-                }
+                Ok(
+                    ArrayExpressionInner::Value(unwrapped_expressions_or_spreads.into())
+                        .annotate(inferred_type, size)
+                        .into(),
+                )
             }
-            Assignee::Member(box assignee, box member) => {
-                let checked_assignee = self.check_assignee(assignee, module_id, types)?;
+            Expression::InlineTuple(elements) => {
+                let elements: Vec<_> = elements
+                    .into_iter()
+                    .map(|e| self.check_expression(e, module_id, types))
+                    .collect::<Result<_, _>>()?;
+                let ty = TupleType::new(elements.iter().map(|e| e.get_type()).collect());
+                Ok(TupleExpressionInner::Value(elements).annotate(ty).into())
+            }
+            Expression::EnumVariant(id, variant_id, arguments) => {
+                // a path on a type parameter refers to a function of its trait
+                if let Some(p) = self.type_parameters.get(id.as_str()).cloned() {
+                    return self.check_path_call_expression(
+                        id,
+                        variant_id,
+                        &p.functions,
+                        arguments,
+                        module_id,
+                        types,
+                        pos,
+                    );
+                }
 
-                let ty = checked_assignee.get_type();
-                match &ty {
-                    Type::Struct(members) => match members.iter().find(|m| m.id == member) {
-                        Some(_) => Ok(TypedAssignee::Member(box checked_assignee, member.into())),
-                        None => Err(ErrorInner {
+                self.use_symbol(&id);
+
+                let ty = types.get(module_id).unwrap().get(&id).cloned();
+
+                // a path on a namespace refers to a constant or a function of the imported module
+                if let Some(path) = self.find_namespaced_symbol(&id, variant_id) {
+                    if ty.is_some() {
+                        return Err(ErrorInner {
                             pos: Some(pos),
                             message: format!(
-                                "{} {{{}}} doesn't have member {}",
-                                ty,
-                                members
-                                    .iter()
-                                    .map(|m| format!("{}: {}", m.id, m.ty))
-                                    .collect::<Vec<_>>()
-                                    .join(", "),
-                                member
+                                "Ambiguous path `{}::{}`: `{}` is both a type and a namespace",
+                                id, variant_id, id
                             ),
-                        }),
-                    },
-                    ty => Err(ErrorInner {
-                        pos: Some(pos),
+                        });
+                    }
 
-                        message: format!(
-                            "Cannot access field {} on {} of type {}",
-                            member, checked_assignee, ty,
+                    let e = match self.scope.get(path) {
+                        Some(..) if arguments.is_empty() => Expression::Identifier(path),
+                        _ => Expression::FunctionCall(
+                            box Expression::Identifier(path).start_end(pos.0, pos.1),
+                            None,
+                            arguments,
                         ),
-                    }),
+                    };
+
+                    return self.check_expression(e.start_end(pos.0, pos.1), module_id, types);
                 }
-            }
-            Assignee::Element(box assignee, index) => {
-                let checked_assignee = self.check_assignee(assignee, module_id, types)?;
 
-                let ty = checked_assignee.get_type();
-                match &ty {
-                    Type::Tuple(tuple_ty) => match tuple_ty.elements.get(index as usize) {
-                        Some(_) => Ok(TypedAssignee::Element(box checked_assignee, index)),
-                        None => Err(ErrorInner {
-                            pos: Some(pos),
-                            message: format!(
-                                "Tuple of size {} cannot be accessed at index {}",
-                                tuple_ty.elements.len(),
-                                index
-                            ),
-                        }),
-                    },
-                    ty => Err(ErrorInner {
+                let ty = match ty {
+                    None if self.is_namespace(&id) => Err(ErrorInner {
                         pos: Some(pos),
-
-                        message: format!(
-                            "Cannot access element {} on {} of type {}",
-                            index, checked_assignee, ty,
-                        ),
+                        message: format!("Symbol `{}` not found in namespace `{}`", variant_id, id),
+                    }),
+                    None => Err(ErrorInner {
+                        pos: Some(pos),
+                        message: format!("Undefined type `{}`", id),
                     }),
+                    Some(ty) => Ok(ty),
+                }?;
+
+                // a path which is not a variant of an enum refers to a function of an `impl` block
+                let is_variant = match &ty.ty {
+                    DeclarationType::Enum(enum_type) => {
+                        enum_type.variant_index(variant_id).is_some()
+                    }
+                    _ => false,
+                };
+
+                if !is_variant {
+                    let functions: HashSet<_> = self
+                        .impl_functions
+                        .iter()
+                        .filter(|f| f.ty == ty.ty)
+                        .map(|f| f.key.clone())
+                        .collect();
+
+                    if functions.iter().any(|key| key.id == variant_id) {
+                        return self.check_path_call_expression(
+                            id, variant_id, &functions, arguments, module_id, types, pos,
+                        );
+                    }
                 }
-            }
-        }
-    }
 
-    fn check_spread_or_expression(
-        &mut self,
-        spread_or_expression: SpreadOrExpression<'ast>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<TypedExpressionOrSpread<'ast, T>, ErrorInner> {
-        match spread_or_expression {
-            SpreadOrExpression::Spread(s) => {
-                let pos = s.pos();
+                let declared_enum_type = match ty.ty {
+                    DeclarationType::Enum(enum_type) => enum_type,
+                    _ => {
+                        return Err(ErrorInner {
+                            pos: Some(pos),
+                            message: format!("Type `{}` is not an enum", id),
+                        })
+                    }
+                };
 
-                let checked_expression =
-                    self.check_expression(s.value.expression, module_id, types)?;
+                let tag = declared_enum_type
+                    .variant_index(variant_id)
+                    .ok_or_else(|| ErrorInner {
+                        pos: Some(pos),
+                        message: format!("Enum {} doesn't have variant {}", id, variant_id),
+                    })?;
 
-                match checked_expression {
-                    TypedExpression::Array(a) => Ok(TypedExpressionOrSpread::Spread(a.into())),
-                    e => Err(ErrorInner {
+                let variant_types = &declared_enum_type.variants[tag as usize].types;
+
+                if variant_types.len() != arguments.len() {
+                    return Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Expected spread operator to apply on array, found {}",
-                            e.get_type()
+                            "Variant {}::{} expects {} argument(s), found {}",
+                            id,
+                            variant_id,
+                            variant_types.len(),
+                            arguments.len()
                         ),
-                    }),
+                    });
                 }
-            }
-            SpreadOrExpression::Expression(e) => {
-                self.check_expression(e, module_id, types).map(|r| r.into())
-            }
-        }
-    }
 
-    fn check_function_call_expression(
-        &mut self,
-        function_id: ExpressionNode<'ast>,
-        generics: Option<Vec<Option<ExpressionNode<'ast>>>>,
-        arguments: Vec<ExpressionNode<'ast>>,
-        expected_return_type: Option<Type<'ast, T>>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
-        let pos = function_id.pos();
-        let fun_id = match function_id.value {
-            Expression::Identifier(id) => Ok(id),
-            e => Err(ErrorInner {
-                pos: Some(pos),
-                message: format!(
-                    "Expected function in function call to be an identifier, found `{}`",
-                    e
-                ),
-            }),
-        }?;
+                let payload = variant_types
+                    .iter()
+                    .zip(arguments)
+                    .map(|(variant_ty, argument)| {
+                        let argument_checked = self.check_expression(argument, module_id, types)?;
 
-        // check the generic arguments, if any
-        let generics_checked: Option<Vec<Option<UExpression<'ast, T>>>> = generics
-            .map(|generics| {
-                generics
-                    .into_iter()
-                    .map(|g| {
-                        g.map(|g| {
-                            let pos = g.pos();
-                            self.check_expression(g, module_id, types).and_then(|g| {
-                                UExpression::try_from_typed(g, &UBitwidth::B32).map_err(|e| {
-                                    ErrorInner {
-                                        pos: Some(pos),
-                                        message: format!(
-                                            "Expected {} to be of type u32, found {}",
-                                            e,
-                                            e.get_type(),
-                                        ),
-                                    }
-                                })
-                            })
-                        })
-                        .transpose()
+                        TypedExpression::align_to_type(argument_checked, variant_ty).map_err(
+                            |(e, ty)| ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Variant {}::{} expects a value of type {}, found {} of type {}",
+                                    id,
+                                    variant_id,
+                                    ty,
+                                    e,
+                                    e.get_type()
+                                ),
+                            },
+                        )
                     })
-                    .collect::<Result<_, _>>()
-            })
-            .transpose()?;
-
-        // check the arguments
-        let mut arguments_checked = vec![];
-        for arg in arguments {
-            let arg_checked = self.check_expression(arg, module_id, types)?;
-            arguments_checked.push(arg_checked);
-        }
-
-        let arguments_types: Vec<_> = arguments_checked.iter().map(|a| a.get_type()).collect();
-
-        let query = FunctionQuery::new(
-            fun_id,
-            &generics_checked,
-            &arguments_types,
-            expected_return_type.clone(),
-        );
-
-        let functions = self.find_functions(&query);
-
-        match functions.len() {
-            // the function has to be defined
-            1 => {
-                let mut functions = functions;
-
-                let f = functions.pop().unwrap();
-
-                let signature = f.signature;
-
-                let arguments_checked = arguments_checked.into_iter().zip(signature.inputs.iter()).map(|(a, t)| TypedExpression::align_to_type(a, t)).collect::<Result<Vec<_>, _>>().map_err(|e| ErrorInner {
-                    pos: Some(pos),
-                    message: format!("Expected function call argument to be of type `{}`, found `{}` of type `{}`", e.1, e.0, e.0.get_type())
-                })?;
-
-                let generics_checked = generics_checked.unwrap_or_else(|| vec![None; signature.generics.len()]);
-
-                let output_type = expected_return_type.map(Ok).unwrap_or_else(|| signature.get_output_type(
-                    generics_checked.clone(),
-                    arguments_checked.iter().map(|a| a.get_type()).collect()
-                ).map_err(|e| ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Failed to infer value for generic parameter `{}`, try providing an explicit value",
-                        e,
-                    ),
-                }))?;
+                    .collect::<Result<_, _>>()?;
 
-                let function_key = DeclarationFunctionKey {
-                    module: module_id.to_path_buf(),
-                    id: f.id,
-                    signature: signature.clone(),
+                let enum_type = match specialize_declaration_type(
+                    DeclarationType::Enum(declared_enum_type),
+                    &GGenericsAssignment::default(),
+                )
+                .unwrap()
+                {
+                    Type::Enum(enum_type) => enum_type,
+                    _ => unreachable!(),
                 };
 
-                match output_type {
-                    Type::Int => unreachable!(),
-                    Type::FieldElement => Ok(FieldElementExpression::function_call(
-                        function_key,
-                        generics_checked,
-                        arguments_checked,
-                    ).into()),
-                    Type::Boolean => Ok(BooleanExpression::function_call(
-                        function_key,
-                        generics_checked,
-                        arguments_checked,
-                    ).into()),
-                    Type::Uint(bitwidth) => Ok(UExpression::function_call(
-                        function_key,
-                        generics_checked,
-                        arguments_checked,
-                    ).annotate(bitwidth).into()),
-                    Type::Struct(struct_ty) => Ok(StructExpression::function_call(
-                        function_key,
-                        generics_checked,
-                        arguments_checked,
-                    ).annotate(struct_ty).into()),
-                    Type::Array(array_ty) => Ok(ArrayExpression::function_call(
-                        function_key,
-                        generics_checked,
-                        arguments_checked,
-                    ).annotate(*array_ty.ty, *array_ty.size).into()),
-                    Type::Tuple(tuple_ty) => Ok(TupleExpression::function_call(
-                        function_key,
-                        generics_checked,
-                        arguments_checked,
-                    ).annotate(tuple_ty).into()),
-                }
+                Ok(EnumExpressionInner::Value(tag, payload)
+                    .annotate(enum_type)
+                    .into())
             }
-            0 => Err(ErrorInner {
-                pos: Some(pos),
-                message: format!(
-                    "Function definition for function {} with signature {} not found.",
-                    fun_id, query
-                ),
-            }),
-            n => Err(ErrorInner {
+            // anonymous functions are not values: they are resolved when passed to a function
+            Expression::Lambda(..) => Err(ErrorInner {
                 pos: Some(pos),
-                message: format!("Ambiguous call to function {}, {} candidates were found. Please be more explicit.", fun_id, n)
-            }),
-        }
-    }
-
-    fn check_expression(
-        &mut self,
-        expr: ExpressionNode<'ast>,
-        module_id: &ModuleId,
-        types: &TypeMap<'ast, T>,
-    ) -> Result<TypedExpression<'ast, T>, ErrorInner> {
-        let pos = expr.pos();
-
-        match expr.value {
-            Expression::IntConstant(v) => Ok(IntExpression::Value(v).into()),
-            Expression::BooleanConstant(b) => Ok(BooleanExpression::Value(b).into()),
-            Expression::Identifier(name) => {
-                // check that `id` is defined in the scope
-                match self.scope.get(name) {
-                    Some(info) => {
-                        let id = info.id;
-                        match info.ty.clone() {
-                            Type::Boolean => Ok(BooleanExpression::identifier(id.into()).into()),
-                            Type::Uint(bitwidth) => {
-                                Ok(UExpression::identifier(id.into()).annotate(bitwidth).into())
-                            }
-                            Type::FieldElement => {
-                                Ok(FieldElementExpression::identifier(id.into()).into())
-                            }
-                            Type::Array(array_type) => Ok(ArrayExpression::identifier(id.into())
-                                .annotate(*array_type.ty, *array_type.size)
-                                .into()),
-                            Type::Struct(members) => Ok(StructExpression::identifier(id.into())
-                                .annotate(members)
-                                .into()),
-                            Type::Tuple(tuple_ty) => Ok(TupleExpression::identifier(id.into())
-                                .annotate(tuple_ty)
-                                .into()),
-                            Type::Int => unreachable!(),
-                        }
-                    }
-                    None => Err(ErrorInner {
+                message: "Anonymous functions can only be passed to function parameters and array combinators".into(),
+            }),
+            Expression::Match(box e, arms) => {
+                let e = self.check_expression(e, module_id, types)?;
+
+                let s = match e {
+                    TypedExpression::Enum(s) => Ok(s),
+                    e => Err(ErrorInner {
                         pos: Some(pos),
-                        message: format!("Identifier \"{}\" is undefined", name),
+                        message: format!(
+                            "Cannot match on expression {} of type {}, expected an enum",
+                            e,
+                            e.get_type()
+                        ),
                     }),
-                }
-            }
-            Expression::Add(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
+                }?;
 
-                use self::TypedExpression::*;
+                let location = s.ty().canonical_location.clone();
+                let variants = s.ty().variants.clone();
 
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
-                )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!("Cannot apply `+` to {}, {}", e1.get_type(), e2.get_type()),
-                })?;
+                let mut covered = vec![false; variants.len()];
+                let mut has_wildcard = false;
+                let mut checked_arms = vec![];
 
-                match (e1_checked, e2_checked) {
-                    (Int(e1), Int(e2)) => Ok(IntExpression::Add(box e1, box e2).into()),
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(FieldElementExpression::Add(box e1, box e2).into())
+                for arm in arms {
+                    if has_wildcard || covered.iter().all(|c| *c) {
+                        return Err(ErrorInner {
+                            pos: Some(arm.expression.pos()),
+                            message: format!("Unreachable match arm {}", arm.pattern),
+                        });
                     }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
-                        if e1.get_type() == e2.get_type() =>
-                    {
-                        Ok((e1 + e2).into())
+
+                    match arm.pattern {
+                        MatchPattern::Wildcard => {
+                            has_wildcard = true;
+                            let e = self.check_expression(arm.expression, module_id, types)?;
+                            checked_arms.push((None, e));
+                        }
+                        MatchPattern::Variant(ty_id, variant_id, bindings) => {
+                            self.use_symbol(&ty_id);
+
+                            let pattern =
+                                MatchPattern::Variant(ty_id.clone(), variant_id, bindings.clone());
+
+                            // the type in the pattern must resolve to the type of the matched expression
+                            let pattern_location =
+                                types.get(module_id).unwrap().get(&ty_id).and_then(|t| {
+                                    match &t.ty {
+                                        DeclarationType::Enum(enum_type) => {
+                                            Some(enum_type.canonical_location.clone())
+                                        }
+                                        _ => None,
+                                    }
+                                });
+
+                            if pattern_location != Some(location.clone()) {
+                                return Err(ErrorInner {
+                                    pos: Some(arm.expression.pos()),
+                                    message: format!(
+                                        "Pattern {} cannot match expression of type {}",
+                                        pattern,
+                                        s.get_type()
+                                    ),
+                                });
+                            }
+
+                            let tag = s.ty().variant_index(variant_id).ok_or_else(|| ErrorInner {
+                                pos: Some(arm.expression.pos()),
+                                message: format!(
+                                    "Enum {} doesn't have variant {}",
+                                    ty_id, variant_id
+                                ),
+                            })?;
+
+                            let variant_types = &variants[tag as usize].types;
+
+                            if covered[tag as usize] {
+                                return Err(ErrorInner {
+                                    pos: Some(arm.expression.pos()),
+                                    message: format!("Unreachable match arm {}", pattern),
+                                });
+                            }
+
+                            if variant_types.len() != bindings.len() {
+                                return Err(ErrorInner {
+                                    pos: Some(arm.expression.pos()),
+                                    message: format!(
+                                        "Pattern {} expects {} binding(s), found {}",
+                                        pattern,
+                                        variant_types.len(),
+                                        bindings.len()
+                                    ),
+                                });
+                            }
+
+                            if let Some(duplicate) = bindings
+                                .iter()
+                                .enumerate()
+                                .find(|(i, b)| bindings[..*i].contains(b))
+                                .map(|(_, b)| b)
+                            {
+                                return Err(ErrorInner {
+                                    pos: Some(arm.expression.pos()),
+                                    message: format!(
+                                        "Duplicate binding {} in pattern {}",
+                                        duplicate, pattern
+                                    ),
+                                });
+                            }
+
+                            // bind the payload of the matched expression while checking the arm
+                            let depth = self.match_bindings.len();
+
+                            let payload = TupleExpression::payload(s.clone(), tag);
+
+                            self.match_bindings.extend(
+                                bindings.into_iter().enumerate().map(|(index, id)| {
+                                    (id, tuple_element(payload.clone(), index as u32))
+                                }),
+                            );
+
+                            let e = self.check_expression(arm.expression, module_id, types);
+
+                            self.match_bindings.truncate(depth);
+
+                            covered[tag as usize] = true;
+                            checked_arms.push((Some(tag), e?));
+                        }
                     }
-                    (t1, t2) => Err(ErrorInner {
-                        pos: Some(pos),
+                }
 
+                if !has_wildcard && covered.iter().any(|c| !c) {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
                         message: format!(
-                            "Cannot apply `+` to {}, {}",
-                            t1.get_type(),
-                            t2.get_type()
+                            "Non-exhaustive match on {}, missing variant(s) {}",
+                            location.name,
+                            variants
+                                .iter()
+                                .zip(covered.iter())
+                                .filter(|(_, c)| !**c)
+                                .map(|(v, _)| format!("{}::{}", location.name, v.id))
+                                .collect::<Vec<_>>()
+                                .join(", ")
                         ),
-                    }),
+                    });
                 }
+
+                // build a chain of conditionals on the variant, starting from the last arm which covers the remaining variants
+                let mut checked_arms = checked_arms.into_iter().rev();
+                let (_, default) = checked_arms.next().unwrap();
+
+                checked_arms.try_fold(default, |alternative, (tag, consequence)| {
+                    // only the last arm can be a wildcard
+                    let tag = tag.unwrap();
+
+                    let condition =
+                        BooleanExpression::IsVariant(VariantExpression::new(s.clone(), tag));
+
+                    Self::check_conditional(
+                        pos,
+                        condition.into(),
+                        consequence,
+                        alternative,
+                        zokrates_ast::typed::ConditionalKind::IfElse,
+                    )
+                })
             }
-            Expression::Sub(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
+            Expression::ByteString(bytes) => {
+                let size = bytes.len() as u32;
+                Ok(ArrayExpressionInner::Bytes(bytes)
+                    .annotate(Type::Uint(UBitwidth::B8), size)
+                    .into())
+            }
+            Expression::ArrayInitializer(box e, box count) => {
+                let e = self.check_expression(e, module_id, types)?;
+                let ty = e.get_type();
 
-                use self::TypedExpression::*;
+                let count = self.check_expression(count, module_id, types)?;
 
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
-                )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!("Cannot apply `-` to {}, {}", e1.get_type(), e2.get_type()),
+                let count = UExpression::try_from_typed(count, &UBitwidth::B32).map_err(|e| {
+                    ErrorInner {
+                        pos: Some(pos),
+                        message: format!(
+                            "Expected array initializer count to be a u32, found {} of type {}",
+                            e,
+                            e.get_type(),
+                        ),
+                    }
                 })?;
 
-                match (e1_checked, e2_checked) {
-                    (Int(e1), Int(e2)) => Ok(IntExpression::Sub(box e1, box e2).into()),
-                    (FieldElement(e1), FieldElement(e2)) => {
-                        Ok(FieldElementExpression::Sub(box e1, box e2).into())
-                    }
-                    (Uint(e1), Uint(e2)) if e1.get_type() == e2.get_type() => Ok((e1 - e2).into()),
-                    (t1, t2) => Err(ErrorInner {
+                Ok(ArrayExpressionInner::Repeat(box e, box count.clone())
+                    .annotate(ty, count)
+                    .into())
+            }
+            Expression::InlineStruct(id, inline_members) => {
+                self.use_symbol(&id);
+
+                let ty = match types.get(module_id).unwrap().get(&id).cloned() {
+                    None => Err(ErrorInner {
                         pos: Some(pos),
+                        message: format!("Undefined type `{}`", id),
+                    }),
+                    Some(ty) => Ok(ty),
+                }?;
+
+                let mut declared_struct_type = match ty.ty {
+                    DeclarationType::Struct(struct_type) => struct_type,
+                    _ => unreachable!(),
+                };
+
+                declared_struct_type.generics = (0..declared_struct_type.generics.len())
+                    .map(|index| {
+                        Some(DeclarationConstant::Generic(
+                            GenericIdentifier::without_name().with_index(index),
+                        ))
+                    })
+                    .collect();
 
+                // check that we provided the required number of values
+                if declared_struct_type.members_count() != inline_members.len() {
+                    return Err(ErrorInner {
+                        pos: Some(pos),
                         message: format!(
-                            "Expected only field elements, found {}, {}",
-                            t1.get_type(),
-                            t2.get_type()
+                            "Inline struct {} does not match {} {{{}}}",
+                            Expression::InlineStruct(id, inline_members),
+                            declared_struct_type,
+                            declared_struct_type
+                                .members
+                                .iter()
+                                .map(|m| format!("{}: {}", m.id, m.ty))
+                                .collect::<Vec<_>>()
+                                .join(", ")
                         ),
-                    }),
+                    });
                 }
-            }
-            Expression::Mult(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
 
-                use self::TypedExpression::*;
+                // check that the mapping of values matches the expected type
+                // put the value into a map, pick members from this map following declared members, and try to parse them
 
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
-                )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!("Cannot apply `*` to {}, {}", e1.get_type(), e2.get_type()),
-                })?;
+                let mut inline_members_map = inline_members
+                    .clone()
+                    .into_iter()
+                    .map(|(id, v)| (id.to_string(), v))
+                    .collect::<BTreeMap<_, _>>();
 
-                match (e1_checked, e2_checked) {
-                    (Int(e1), Int(e2)) => Ok(IntExpression::Mult(box e1, box e2).into()),
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(FieldElementExpression::Mult(box e1, box e2).into())
-                    }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
-                        if e1.get_type() == e2.get_type() =>
-                    {
-                        Ok((e1 * e2).into())
-                    }
-                    (t1, t2) => Err(ErrorInner {
-                        pos: Some(pos),
+                let inferred_values = declared_struct_type
+                    .iter()
+                    .map(
+                        |member| match inline_members_map.remove(member.id.as_str()) {
+                            Some(value) => {
+                                let expression_checked =
+                                    self.check_expression(value, module_id, types)?;
+
+                                let expression_checked =
+                                    TypedExpression::align_to_type(expression_checked, &*member.ty)
+                                        .map_err(|e| ErrorInner {
+                                            pos: Some(pos),
+                                            message: format!(
+                                        "Member {} of struct {} has type {}, found {} of type {}",
+                                        member.id,
+                                        id.clone(),
+                                        e.1,
+                                        e.0,
+                                        e.0.get_type(),
+                                    ),
+                                        })?;
+
+                                Ok(expression_checked)
+                            }
+                            None => Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Member {} of struct {} {{{}}} not found in value {}",
+                                    member.id,
+                                    declared_struct_type,
+                                    declared_struct_type
+                                        .members
+                                        .iter()
+                                        .map(|m| format!("{}: {}", m.id, m.ty))
+                                        .collect::<Vec<_>>()
+                                        .join(", "),
+                                    Expression::InlineStruct(id.clone(), inline_members.clone()),
+                                ),
+                            }),
+                        },
+                    )
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut generics_map = GGenericsAssignment::default();
+
+                let members = declared_struct_type
+                    .iter()
+                    .zip(inferred_values.iter())
+                    .map(|(m, v)| {
+                        if !check_type(&m.ty, &v.get_type(), &mut generics_map) {
+                            Err(ErrorInner {
+                                pos: Some(pos),
+                                message: format!(
+                                    "Value `{}` doesn't match the expected type `{}` because of conflict in generic values",
+                                    Expression::InlineStruct(id.clone(), inline_members.clone()),
+                                    declared_struct_type
+                                ),
+                            })
+                        } else {
+                            Ok(StructMember {
+                                id: m.id.clone(),
+                                ty: box v.get_type().clone(),
+                            })
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let generics = generics_map.0.values().cloned().map(Some).collect();
 
-                        message: format!(
-                            "Cannot apply `*` to {}, {}",
-                            t1.get_type(),
-                            t2.get_type()
-                        ),
-                    }),
-                }
+                let inferred_struct_type = StructType {
+                    canonical_location: declared_struct_type.canonical_location.clone(),
+                    location: declared_struct_type.location,
+                    kind: declared_struct_type.kind,
+                    generics,
+                    members,
+                };
+
+                Ok(StructExpressionInner::Value(inferred_values)
+                    .annotate(inferred_struct_type)
+                    .into())
             }
-            Expression::Div(box e1, box e2) => {
+            Expression::And(box e1, box e2) => {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
 
-                use self::TypedExpression::*;
-
                 let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
                     e1_checked, e2_checked,
                 )
                 .map_err(|(e1, e2)| ErrorInner {
                     pos: Some(pos),
-                    message: format!("Cannot apply `/` to {}, {}", e1.get_type(), e2.get_type()),
+                    message: format!(
+                        "Cannot apply boolean operators to {} and {}",
+                        e1.get_type(),
+                        e2.get_type()
+                    ),
                 })?;
 
                 match (e1_checked, e2_checked) {
-                    (Int(e1), Int(e2)) => Ok(IntExpression::Div(box e1, box e2).into()),
-                    (FieldElement(e1), FieldElement(e2)) => {
-                        Ok(FieldElementExpression::Div(box e1, box e2).into())
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2)) => {
+                        Ok(IntExpression::And(box e1, box e2).into())
                     }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
-                        if e1.get_type() == e2.get_type() =>
-                    {
-                        Ok((e1 / e2).into())
+                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
+                        Ok(BooleanExpression::And(box e1, box e2).into())
                     }
-                    (t1, t2) => Err(ErrorInner {
+                    (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
 
                         message: format!(
-                            "Cannot apply `/` to {}, {}",
-                            t1.get_type(),
-                            t2.get_type()
+                            "Cannot apply boolean operators to {} and {}",
+                            e1.get_type(),
+                            e2.get_type()
                         ),
                     }),
                 }
             }
-            Expression::Rem(box e1, box e2) => {
+            Expression::Or(box e1, box e2) => {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
-
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
-                )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!("Cannot apply `%` to {}, {}", e1.get_type(), e2.get_type()),
-                })?;
-
                 match (e1_checked, e2_checked) {
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
-                        if e1.get_type() == e2.get_type() =>
-                    {
-                        Ok((e1 % e2).into())
+                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
+                        Ok(BooleanExpression::Or(box e1, box e2).into())
                     }
-                    (t1, t2) => Err(ErrorInner {
+                    (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
-
                         message: format!(
-                            "Cannot apply `%` to {}, {}",
-                            t1.get_type(),
-                            t2.get_type()
+                            "Cannot apply `||` to {}, {}",
+                            e1.get_type(),
+                            e2.get_type()
                         ),
                     }),
                 }
             }
-            Expression::Pow(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
-
-                let e1_checked = match FieldElementExpression::try_from_typed(e1_checked) {
-                    Ok(e) => e.into(),
-                    Err(e) => e,
-                };
-                let e2_checked = match UExpression::try_from_typed(e2_checked, &UBitwidth::B32) {
-                    Ok(e) => e.into(),
-                    Err(e) => e,
-                };
+            Expression::LeftShift(box e1, box e2) => {
+                let e1 = self.check_expression(e1, module_id, types)?;
+                let e2 = self.check_expression(e2, module_id, types)?;
 
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::FieldElement(e1), TypedExpression::Uint(e2)) => Ok(
-                        TypedExpression::FieldElement(FieldElementExpression::Pow(box e1, box e2)),
-                    ),
-                    (t1, t2) => Err(ErrorInner {
+                let e2 =
+                    UExpression::try_from_typed(e2, &UBitwidth::B32).map_err(|e| ErrorInner {
                         pos: Some(pos),
-
                         message: format!(
-                            "Expected `field` and `u32`, found {}, {}",
-                            t1.get_type(),
-                            t2.get_type()
+                            "Expected the left shift right operand to have type `u32`, found {}",
+                            e
                         ),
-                    }),
-                }
-            }
-            Expression::Neg(box e) => {
-                let e = self.check_expression(e, module_id, types)?;
+                    })?;
 
-                match e {
-                    TypedExpression::Int(e) => Ok(IntExpression::Neg(box e).into()),
-                    TypedExpression::FieldElement(e) => {
-                        Ok(FieldElementExpression::Neg(box e).into())
+                match e1 {
+                    TypedExpression::Int(e1) => Ok(IntExpression::LeftShift(box e1, box e2).into()),
+                    TypedExpression::Uint(e1) => Ok(UExpression::left_shift(e1, e2).into()),
+                    TypedExpression::FieldElement(e1) => {
+                        Ok(FieldElementExpression::LeftShift(box e1, box e2).into())
                     }
-                    TypedExpression::Uint(e) => Ok((-e).into()),
-                    e => Err(ErrorInner {
+                    e1 => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Unary operator `-` cannot be applied to {} of type {}",
-                            e,
-                            e.get_type()
+                            "Cannot left-shift {} by {}",
+                            e1.get_type(),
+                            e2.get_type()
                         ),
                     }),
                 }
             }
-            Expression::Pos(box e) => {
-                let e = self.check_expression(e, module_id, types)?;
+            Expression::RightShift(box e1, box e2) => {
+                let e1 = self.check_expression(e1, module_id, types)?;
+                let e2 = self.check_expression(e2, module_id, types)?;
 
-                match e {
-                    TypedExpression::Int(e) => Ok(IntExpression::Pos(box e).into()),
-                    TypedExpression::FieldElement(e) => {
-                        Ok(FieldElementExpression::Pos(box e).into())
-                    }
-                    TypedExpression::Uint(e) => Ok(UExpression::pos(e).into()),
-                    e => Err(ErrorInner {
+                let e2 =
+                    UExpression::try_from_typed(e2, &UBitwidth::B32).map_err(|e| ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Unary operator `+` cannot be applied to {} of type {}",
-                            e,
-                            e.get_type()
+                            "Expected the right shift right operand to be of type `u32`, found {}",
+                            e
                         ),
-                    }),
-                }
-            }
-            Expression::Conditional(box conditional) => {
-                let condition_checked =
-                    self.check_expression(*conditional.condition, module_id, types)?;
-
-                if !conditional.consequence_statements.is_empty()
-                    || !conditional.alternative_statements.is_empty()
-                {
-                    return Err(ErrorInner {
-                        pos: Some(pos),
-                        message: "Statements are not supported in conditional branches".to_string(),
-                    });
-                }
-
-                let consequence_checked =
-                    self.check_expression(*conditional.consequence, module_id, types)?;
-                let alternative_checked =
-                    self.check_expression(*conditional.alternative, module_id, types)?;
-
-                let (consequence_checked, alternative_checked) =
-                    TypedExpression::align_without_integers(
-                        consequence_checked,
-                        alternative_checked,
-                    )
-                    .map_err(|(e1, e2)| ErrorInner {
-                        pos: Some(pos),
-                        message: format!("{{consequence}} and {{alternative}} in conditional expression should have the same type, found {}, {}", e1.get_type(), e2.get_type()),
                     })?;
 
-                let kind = match conditional.kind {
-                    zokrates_ast::untyped::ConditionalKind::IfElse => {
-                        zokrates_ast::typed::ConditionalKind::IfElse
-                    }
-                    zokrates_ast::untyped::ConditionalKind::Ternary => {
-                        zokrates_ast::typed::ConditionalKind::Ternary
-                    }
-                };
-
-                match condition_checked {
-                    TypedExpression::Boolean(condition) => {
-                        match (consequence_checked, alternative_checked) {
-                            (TypedExpression::FieldElement(consequence), TypedExpression::FieldElement(alternative)) => {
-                                Ok(FieldElementExpression::conditional(condition, consequence, alternative, kind).into())
-                            },
-                            (TypedExpression::Boolean(consequence), TypedExpression::Boolean(alternative)) => {
-                                Ok(BooleanExpression::conditional(condition, consequence, alternative, kind).into())
-                            },
-                            (TypedExpression::Array(consequence), TypedExpression::Array(alternative)) => {
-                                Ok(ArrayExpression::conditional(condition, consequence, alternative, kind).into())
-                            },
-                            (TypedExpression::Struct(consequence), TypedExpression::Struct(alternative)) => {
-                                Ok(StructExpression::conditional(condition, consequence, alternative, kind).into())
-                            },
-                            (TypedExpression::Tuple(consequence), TypedExpression::Tuple(alternative)) => {
-                                Ok(TupleExpression::conditional(condition, consequence, alternative, kind).into())
-                            },
-                            (TypedExpression::Uint(consequence), TypedExpression::Uint(alternative)) => {
-                                Ok(UExpression::conditional(condition, consequence, alternative, kind).into())
-                            },
-                            (TypedExpression::Int(consequence), TypedExpression::Int(alternative)) => {
-                                Ok(IntExpression::conditional(condition, consequence, alternative, kind).into())
-                            },
-                            (c, a) => Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!("{{consequence}} and {{alternative}} in conditional expression should have the same type, found {}, {}", c.get_type(), a.get_type())
-                            })
-                        }
-                    }
-                    c => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "{{condition}} should be a boolean, found {}",
-                            c.get_type()
-                        ),
-                    }),
-                }
-            }
-            Expression::FieldConstant(n) => Ok(FieldElementExpression::Number(
-                T::try_from(n).map_err(|_| ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Field constant not in the representable range [{}, {}]",
-                        T::min_value(),
-                        T::max_value()
-                    ),
-                })?,
-            )
-            .into()),
-            Expression::U8Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(8).into()),
-            Expression::U16Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(16).into()),
-            Expression::U32Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(32).into()),
-            Expression::U64Constant(n) => Ok(UExpressionInner::Value(n.into()).annotate(64).into()),
-            Expression::FunctionCall(box fun_id_expression, generics, arguments) => self
-                .check_function_call_expression(
-                    fun_id_expression,
-                    generics,
-                    arguments,
-                    None,
-                    module_id,
-                    types,
-                ),
-            Expression::Lt(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
-
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
-                )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Cannot compare {} of type {} to {} of type {}",
-                        e1,
-                        e1.get_type(),
-                        e2,
-                        e2.get_type()
-                    ),
-                })?;
-
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::FieldLt(box e1, box e2).into())
+                match e1 {
+                    TypedExpression::Int(e1) => {
+                        Ok(IntExpression::RightShift(box e1, box e2).into())
                     }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
-                        if e1.get_type() == e2.get_type() {
-                            Ok(BooleanExpression::UintLt(box e1, box e2).into())
-                        } else {
-                            Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Cannot compare {} of type {} to {} of type {}",
-                                    e1,
-                                    e1.get_type(),
-                                    e2,
-                                    e2.get_type()
-                                ),
-                            })
-                        }
+                    TypedExpression::Uint(e1) => Ok(UExpression::right_shift(e1, e2).into()),
+                    TypedExpression::FieldElement(e1) => {
+                        Ok(FieldElementExpression::RightShift(box e1, box e2).into())
                     }
-                    (e1, e2) => Err(ErrorInner {
+                    e1 => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
+                            "Cannot right-shift {} by {}",
                             e1.get_type(),
-                            e2,
                             e2.get_type()
                         ),
                     }),
                 }
             }
-            Expression::Le(box e1, box e2) => {
+            Expression::BitOr(box e1, box e2) => {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
 
@@ -2864,48 +7096,32 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 )
                 .map_err(|(e1, e2)| ErrorInner {
                     pos: Some(pos),
-                    message: format!(
-                        "Cannot compare {} of type {} to {} of type {}",
-                        e1,
-                        e1.get_type(),
-                        e2,
-                        e2.get_type()
-                    ),
+                    message: format!("Cannot apply `|` to {}, {}", e1.get_type(), e2.get_type()),
                 })?;
 
                 match (e1_checked, e2_checked) {
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2)) => {
+                        Ok(IntExpression::Or(box e1, box e2).into())
+                    }
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::FieldLe(box e1, box e2).into())
+                        Ok(FieldElementExpression::Or(box e1, box e2).into())
                     }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
-                        if e1.get_type() == e2.get_type() {
-                            Ok(BooleanExpression::UintLe(box e1, box e2).into())
-                        } else {
-                            Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Cannot compare {} of type {} to {} of type {}",
-                                    e1,
-                                    e1.get_type(),
-                                    e2,
-                                    e2.get_type()
-                                ),
-                            })
-                        }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(UExpression::or(e1, e2).into())
                     }
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
+                            "Cannot apply `|` to {}, {}",
                             e1.get_type(),
-                            e2,
                             e2.get_type()
                         ),
                     }),
                 }
             }
-            Expression::Eq(box e1, box e2) => {
+            Expression::BitAnd(box e1, box e2) => {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
 
@@ -2914,49 +7130,32 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 )
                 .map_err(|(e1, e2)| ErrorInner {
                     pos: Some(pos),
-                    message: format!(
-                        "Cannot compare {} of type {} to {} of type {}",
-                        e1,
-                        e1.get_type(),
-                        e2,
-                        e2.get_type()
-                    ),
+                    message: format!("Cannot apply `&` to {}, {}", e1.get_type(), e2.get_type()),
                 })?;
 
                 match (e1_checked, e2_checked) {
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::FieldEq(EqExpression::new(e1, e2)).into())
-                    }
-                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
-                        Ok(BooleanExpression::BoolEq(EqExpression::new(e1, e2)).into())
-                    }
-                    (TypedExpression::Array(e1), TypedExpression::Array(e2)) => {
-                        Ok(BooleanExpression::ArrayEq(EqExpression::new(e1, e2)).into())
-                    }
-                    (TypedExpression::Struct(e1), TypedExpression::Struct(e2)) => {
-                        Ok(BooleanExpression::StructEq(EqExpression::new(e1, e2)).into())
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2)) => {
+                        Ok(IntExpression::And(box e1, box e2).into())
                     }
-                    (TypedExpression::Tuple(e1), TypedExpression::Tuple(e2)) => {
-                        Ok(BooleanExpression::TupleEq(EqExpression::new(e1, e2)).into())
+                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
+                        Ok(FieldElementExpression::And(box e1, box e2).into())
                     }
                     (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
-                        if e1.get_type() == e2.get_type() =>
+                        if e1.bitwidth() == e2.bitwidth() =>
                     {
-                        Ok(BooleanExpression::UintEq(EqExpression::new(e1, e2)).into())
+                        Ok(UExpression::and(e1, e2).into())
                     }
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
+                            "Cannot apply `&` to {}, {}",
                             e1.get_type(),
-                            e2,
                             e2.get_type()
                         ),
                     }),
                 }
             }
-            Expression::Ge(box e1, box e2) => {
+            Expression::BitXor(box e1, box e2) => {
                 let e1_checked = self.check_expression(e1, module_id, types)?;
                 let e2_checked = self.check_expression(e2, module_id, types)?;
 
@@ -2965,1685 +7164,2427 @@ impl<'ast, T: Field> Checker<'ast, T> {
                 )
                 .map_err(|(e1, e2)| ErrorInner {
                     pos: Some(pos),
-                    message: format!(
-                        "Cannot compare {} of type {} to {} of type {}",
-                        e1,
-                        e1.get_type(),
-                        e2,
-                        e2.get_type()
-                    ),
+                    message: format!("Cannot apply `^` to {}, {}", e1.get_type(), e2.get_type()),
                 })?;
 
                 match (e1_checked, e2_checked) {
+                    (TypedExpression::Int(e1), TypedExpression::Int(e2)) => {
+                        Ok(IntExpression::Xor(box e1, box e2).into())
+                    }
                     (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::FieldGe(box e1, box e2).into())
+                        Ok(FieldElementExpression::Xor(box e1, box e2).into())
                     }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
-                        if e1.get_type() == e2.get_type() {
-                            Ok(BooleanExpression::UintGe(box e1, box e2).into())
-                        } else {
-                            Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Cannot compare {} of type {} to {} of type {}",
-                                    e1,
-                                    e1.get_type(),
-                                    e2,
-                                    e2.get_type()
-                                ),
-                            })
-                        }
+                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
+                        if e1.bitwidth() == e2.bitwidth() =>
+                    {
+                        Ok(UExpression::xor(e1, e2).into())
                     }
                     (e1, e2) => Err(ErrorInner {
                         pos: Some(pos),
                         message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
+                            "Cannot apply `^` to {}, {}",
                             e1.get_type(),
-                            e2,
                             e2.get_type()
                         ),
                     }),
                 }
             }
-            Expression::Gt(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
-
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
-                )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Cannot compare {} of type {} to {} of type {}",
-                        e1,
-                        e1.get_type(),
-                        e2,
-                        e2.get_type()
-                    ),
-                })?;
-
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(BooleanExpression::FieldGt(box e1, box e2).into())
-                    }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2)) => {
-                        if e1.get_type() == e2.get_type() {
-                            Ok(BooleanExpression::UintGt(box e1, box e2).into())
-                        } else {
-                            Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Cannot compare {} of type {} to {} of type {}",
-                                    e1,
-                                    e1.get_type(),
-                                    e2,
-                                    e2.get_type()
-                                ),
-                            })
-                        }
-                    }
-                    (e1, e2) => Err(ErrorInner {
+            Expression::Not(box e) => {
+                let e_checked = self.check_expression(e, module_id, types)?;
+                match e_checked {
+                    TypedExpression::Int(e) => Ok(IntExpression::Not(box e).into()),
+                    TypedExpression::Boolean(e) => Ok(BooleanExpression::Not(box e).into()),
+                    TypedExpression::Uint(e) => Ok((!e).into()),
+                    e => Err(ErrorInner {
                         pos: Some(pos),
-                        message: format!(
-                            "Cannot compare {} of type {} to {} of type {}",
-                            e1,
-                            e1.get_type(),
-                            e2,
-                            e2.get_type()
-                        ),
+                        message: format!("Cannot negate {}", e.get_type()),
                     }),
                 }
             }
-            Expression::Select(box array, box index) => {
-                let array = self.check_expression(array, module_id, types)?;
+        }
+    }
+
+    fn insert_into_scope<I: Clone + Into<SourceIdentifier<'ast>>>(
+        &mut self,
+        id: I,
+        ty: Type<'ast, T>,
+        is_mutable: bool,
+    ) -> bool {
+        let info = IdentifierInfo {
+            id: self.id_in_this_scope(id.clone()),
+            ty,
+            is_mutable,
+            declaration: None,
+        };
+        self.scope.insert(id, info)
+    }
+
+    // insert a variable declared in the source into the scope, keeping track of its usage, and return whether we are
+    // shadowing an existing variable
+    fn declare_variable<I: Clone + Into<SourceIdentifier<'ast>>>(
+        &mut self,
+        id: I,
+        ty: Type<'ast, T>,
+        is_mutable: bool,
+        pos: (Position, Position),
+        call: Option<String>,
+    ) -> bool {
+        let info = IdentifierInfo {
+            id: self.id_in_this_scope(id.clone()),
+            ty,
+            is_mutable,
+            declaration: Some(self.variables.len()),
+        };
+        let shadows = self.scope.insert(id.clone(), info);
+
+        self.variables.push(DeclaredVariable {
+            id: id.into(),
+            pos,
+            call,
+            shadows,
+            is_used: false,
+        });
+
+        shadows
+    }
+
+    // mark a symbol of the module being checked as used
+    fn use_symbol<S: Into<String>>(&mut self, id: S) {
+        self.used_symbols.insert(id.into());
+    }
+
+    // the symbols imported under a namespace with `import "x" as y`, named `y::symbol`
+    fn namespaced_symbols(&self) -> impl Iterator<Item = Identifier<'ast>> + '_ {
+        let constants = self
+            .scope
+            .map
+            .values()
+            .flat_map(|versions| versions.values())
+            .filter_map(|info| match &info.id {
+                CoreIdentifier::Constant(c) => Some(c.id),
+                _ => None,
+            });
+
+        self.functions
+            .iter()
+            .map(|key| key.id)
+            .chain(self.type_generic_functions.keys().copied())
+            .chain(constants)
+            .filter(|id| id.contains("::"))
+    }
+
+    fn find_namespaced_symbol(&self, namespace: &str, id: &str) -> Option<Identifier<'ast>> {
+        let path = format!("{}::{}", namespace, id);
+        self.namespaced_symbols().find(|s| *s == path)
+    }
+
+    fn is_namespace(&self, namespace: &str) -> bool {
+        self.namespaced_symbols()
+            .any(|s| s.split_once("::").map(|(n, _)| n) == Some(namespace))
+    }
+
+    fn find_functions(
+        &self,
+        query: &FunctionQuery<'ast, T>,
+    ) -> Vec<DeclarationFunctionKey<'ast, T>> {
+        query.match_funcs(&self.functions)
+    }
+
+    fn enter_scope(&mut self) {
+        self.scope.enter();
+    }
+
+    fn exit_scope(&mut self) {
+        self.scope.exit();
+    }
+}
+
+// the member `id` of struct `s`
+fn struct_member<'ast, T: Field>(
+    s: StructExpression<'ast, T>,
+    id: &str,
+) -> TypedExpression<'ast, T> {
+    let ty = s.ty().iter().find(|m| m.id == id).map(|m| *m.ty.clone());
+
+    match ty.unwrap() {
+        Type::Int => unreachable!(),
+        Type::FieldElement => FieldElementExpression::member(s, id.to_string()).into(),
+        Type::Boolean => BooleanExpression::member(s, id.to_string()).into(),
+        Type::Uint(..) => UExpression::member(s, id.to_string()).into(),
+        Type::Array(..) => ArrayExpression::member(s, id.to_string()).into(),
+        Type::Struct(..) => StructExpression::member(s, id.to_string()).into(),
+        Type::Tuple(..) => TupleExpression::member(s, id.to_string()).into(),
+        Type::Enum(..) => EnumExpression::member(s, id.to_string()).into(),
+        Type::BoundedArray(..) => BoundedArrayExpression::member(s, id.to_string()).into(),
+    }
+}
+
+// the element `index` of tuple `t`
+fn tuple_element<T: Field>(t: TupleExpression<'_, T>, index: u32) -> TypedExpression<'_, T> {
+    match t.ty().elements[index as usize].clone() {
+        Type::Int => unreachable!(),
+        Type::FieldElement => FieldElementExpression::element(t, index).into(),
+        Type::Boolean => BooleanExpression::element(t, index).into(),
+        Type::Uint(..) => UExpression::element(t, index).into(),
+        Type::Array(..) => ArrayExpression::element(t, index).into(),
+        Type::Struct(..) => StructExpression::element(t, index).into(),
+        Type::Tuple(..) => TupleExpression::element(t, index).into(),
+        Type::Enum(..) => EnumExpression::element(t, index).into(),
+        Type::BoundedArray(..) => BoundedArrayExpression::element(t, index).into(),
+    }
+}
+
+// the element at `index` of array `a`
+fn array_element<'ast, T: Field>(
+    a: ArrayExpression<'ast, T>,
+    index: UExpression<'ast, T>,
+) -> TypedExpression<'ast, T> {
+    match a.inner_type().clone() {
+        Type::Int => unreachable!(),
+        Type::FieldElement => FieldElementExpression::select(a, index).into(),
+        Type::Boolean => BooleanExpression::select(a, index).into(),
+        Type::Uint(..) => UExpression::select(a, index).into(),
+        Type::Array(..) => ArrayExpression::select(a, index).into(),
+        Type::Struct(..) => StructExpression::select(a, index).into(),
+        Type::Tuple(..) => TupleExpression::select(a, index).into(),
+        Type::Enum(..) => EnumExpression::select(a, index).into(),
+        Type::BoundedArray(..) => BoundedArrayExpression::select(a, index).into(),
+    }
+}
+
+// the signed integer of type `bitwidth` with absolute value `n`, in two's complement
+fn signed_constant<'ast, T: Field>(
+    n: BigUint,
+    bitwidth: UBitwidth,
+    negative: bool,
+    pos: (Position, Position),
+) -> Result<TypedExpression<'ast, T>, ErrorInner> {
+    let bits = bitwidth.to_usize();
+    let min = BigUint::from(1u128 << (bits - 1));
+
+    let in_range = if negative { n <= min } else { n < min };
+
+    if !in_range {
+        return Err(ErrorInner {
+            pos: Some(pos),
+            message: format!(
+                "Literal `{}{}` out of range for type `i{}`, expected a value in [-{}, {}]",
+                if negative { "-" } else { "" },
+                n,
+                bits,
+                min,
+                min.clone() - 1u32
+            ),
+        });
+    }
+
+    let n = n.to_u128().unwrap();
+    let value = match negative {
+        true => (bitwidth.max_value() - n + 1) & bitwidth.max_value(),
+        false => n,
+    };
+
+    Ok(UExpressionInner::Value(value).annotate(bitwidth).into())
+}
+
+// whether a type contains integers of undetermined bitwidth
+fn contains_int<T>(ty: &Type<'_, T>) -> bool {
+    match ty {
+        Type::Int => true,
+        Type::Array(array_ty) => contains_int(&array_ty.ty),
+        Type::Tuple(tuple_ty) => tuple_ty.elements.iter().any(contains_int),
+        _ => false,
+    }
+}
+
+// an opaque struct type standing for a type parameter while checking generic code
+fn type_placeholder<'ast, T>(
+    module_id: &ModuleId,
+    owner: &str,
+    id: &str,
+) -> DeclarationType<'ast, T> {
+    DeclarationType::Struct(
+        DeclarationStructType::new(
+            module_id.to_path_buf(),
+            format!("{}::{}", owner, id),
+            vec![],
+            vec![],
+        )
+        .kind(StructKind::TypeParameter),
+    )
+}
+
+fn contains_type<'ast, T: Field>(
+    ty: &DeclarationType<'ast, T>,
+    target: &DeclarationType<'ast, T>,
+) -> bool {
+    ty == target
+        || match ty {
+            DeclarationType::Array(array_ty) | DeclarationType::BoundedArray(array_ty) => {
+                contains_type(&array_ty.ty, target)
+            }
+            DeclarationType::Tuple(tuple_ty) => {
+                tuple_ty.elements.iter().any(|e| contains_type(e, target))
+            }
+            _ => false,
+        }
+}
 
-                match index {
-                    RangeOrExpression::Range(r) => {
-                        match array {
-                            TypedExpression::Array(array) => {
-                                let array_size = array.size();
+// replace types according to `bindings`, in arrays, bounded arrays and tuples
+fn substitute_type<'ast, T: Field>(
+    ty: DeclarationType<'ast, T>,
+    bindings: &[(DeclarationType<'ast, T>, DeclarationType<'ast, T>)],
+) -> DeclarationType<'ast, T> {
+    if let Some((_, to)) = bindings.iter().find(|(from, _)| *from == ty) {
+        return to.clone();
+    }
 
-                                let inner_type = array.inner_type().clone();
+    match ty {
+        DeclarationType::Array(array_ty) => DeclarationType::Array(DeclarationArrayType {
+            size: array_ty.size,
+            ty: box substitute_type(*array_ty.ty, bindings),
+        }),
+        DeclarationType::BoundedArray(array_ty) => {
+            DeclarationType::BoundedArray(DeclarationArrayType {
+                size: array_ty.size,
+                ty: box substitute_type(*array_ty.ty, bindings),
+            })
+        }
+        DeclarationType::Tuple(tuple_ty) => DeclarationType::Tuple(DeclarationTupleType::new(
+            tuple_ty
+                .elements
+                .into_iter()
+                .map(|e| substitute_type(e, bindings))
+                .collect(),
+        )),
+        ty => ty,
+    }
+}
 
-                                // check that the bounds are valid expressions
-                                let from = r
-                                    .value
-                                    .from
-                                    .map(|e| self.check_expression(e, module_id, types))
-                                    .unwrap_or_else(|| Ok(UExpression::from(0u32).into()))?;
+fn substitute_signature<'ast, T: Field>(
+    signature: DeclarationSignature<'ast, T>,
+    bindings: &[(DeclarationType<'ast, T>, DeclarationType<'ast, T>)],
+) -> DeclarationSignature<'ast, T> {
+    DeclarationSignature::new()
+        .generics(signature.generics)
+        .inputs(
+            signature
+                .inputs
+                .into_iter()
+                .map(|ty| substitute_type(ty, bindings))
+                .collect(),
+        )
+        .output(substitute_type(*signature.output, bindings))
+}
 
-                                let to = r
-                                    .value
-                                    .to
-                                    .map(|e| self.check_expression(e, module_id, types))
-                                    .unwrap_or_else(|| Ok(array_size.clone().into()))?;
+// find the type bound to `parameter` by matching the declared type of an argument with its actual type
+fn find_type_argument<'ast, T: Field>(
+    declared: &DeclarationType<'ast, T>,
+    actual: &Type<'ast, T>,
+    parameter: &DeclarationType<'ast, T>,
+) -> Option<Type<'ast, T>> {
+    if declared == parameter {
+        return Some(actual.clone());
+    }
 
-                                let from = UExpression::try_from_typed(from, &UBitwidth::B32).map_err(|e| ErrorInner {
-                                                    pos: Some(pos),
-                                                    message: format!(
-                                                        "Expected the lower bound of the range to be a u32, found {} of type {}",
-                                                        e,
-                                                        e.get_type()
-                                                    ),
-                                                })?;
+    match (declared, actual) {
+        (DeclarationType::Array(declared), Type::Array(actual))
+        | (DeclarationType::BoundedArray(declared), Type::BoundedArray(actual)) => {
+            find_type_argument(&declared.ty, &actual.ty, parameter)
+        }
+        (DeclarationType::Tuple(declared), Type::Tuple(actual)) => declared
+            .elements
+            .iter()
+            .zip(actual.elements.iter())
+            .find_map(|(declared, actual)| find_type_argument(declared, actual, parameter)),
+        _ => None,
+    }
+}
 
-                                let to = UExpression::try_from_typed(to, &UBitwidth::B32).map_err(|e| ErrorInner {
-                                                    pos: Some(pos),
-                                                    message: format!(
-                                                        "Expected the upper bound of the range to be a u32, found {} of type {}",
-                                                        e,
-                                                        e.get_type()
-                                                    ),
-                                                })?;
+fn has_generic_expression<T: Field>(ty: &DeclarationType<'_, T>) -> bool {
+    match ty {
+        DeclarationType::Array(t) => {
+            t.size.is_generic_expression() || has_generic_expression(&t.ty)
+        }
+        DeclarationType::Tuple(t) => t.elements.iter().any(has_generic_expression),
+        DeclarationType::Struct(t) => t
+            .generics
+            .iter()
+            .flatten()
+            .any(|g| g.is_generic_expression()),
+        _ => false,
+    }
+}
 
-                                Ok(ArrayExpressionInner::Slice(
-                                    box array,
-                                    box from.clone(),
-                                    box to.clone(),
-                                )
-                                .annotate(inner_type, UExpression::floor_sub(to, from))
-                                .into())
-                            }
-                            e => Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Cannot access slice of expression {} of type {}",
-                                    e,
-                                    e.get_type(),
-                                ),
-                            }),
-                        }
-                    }
-                    RangeOrExpression::Expression(index) => {
-                        let index = self.check_expression(index, module_id, types)?;
+// whether an expression is made of literals only, so that it has the same value in any context
+fn is_literal(e: &Expression) -> bool {
+    match e {
+        Expression::IntConstant(..)
+        | Expression::FieldConstant(..)
+        | Expression::BooleanConstant(..)
+        | Expression::U8Constant(..)
+        | Expression::U16Constant(..)
+        | Expression::U32Constant(..)
+        | Expression::U64Constant(..)
+        | Expression::U128Constant(..)
+        | Expression::I8Constant(..)
+        | Expression::I16Constant(..)
+        | Expression::I32Constant(..)
+        | Expression::I64Constant(..) => true,
+        Expression::Neg(e) | Expression::Pos(e) | Expression::Not(e) => is_literal(&e.value),
+        Expression::Add(e1, e2)
+        | Expression::Sub(e1, e2)
+        | Expression::Mult(e1, e2)
+        | Expression::Div(e1, e2)
+        | Expression::Rem(e1, e2)
+        | Expression::Pow(e1, e2)
+        | Expression::Lt(e1, e2)
+        | Expression::Le(e1, e2)
+        | Expression::Eq(e1, e2)
+        | Expression::Ge(e1, e2)
+        | Expression::Gt(e1, e2)
+        | Expression::And(e1, e2)
+        | Expression::Or(e1, e2)
+        | Expression::BitXor(e1, e2)
+        | Expression::BitAnd(e1, e2)
+        | Expression::BitOr(e1, e2)
+        | Expression::LeftShift(e1, e2)
+        | Expression::RightShift(e1, e2) => is_literal(&e1.value) && is_literal(&e2.value),
+        _ => false,
+    }
+}
 
-                        let index =
-                            UExpression::try_from_typed(index, &UBitwidth::B32).map_err(|e| {
-                                ErrorInner {
-                                    pos: Some(pos),
-                                    message: format!(
-                                        "Expected index to be of type u32, found {}",
-                                        e
-                                    ),
-                                }
-                            })?;
+// the variable at the root of an assignee
+fn assignee_root<'a, 'ast, T>(a: &'a TypedAssignee<'ast, T>) -> &'a Variable<'ast, T> {
+    match a {
+        TypedAssignee::Identifier(v) => v,
+        TypedAssignee::Select(a, _)
+        | TypedAssignee::Member(a, _)
+        | TypedAssignee::Element(a, _) => assignee_root(a),
+    }
+}
 
-                        match array {
-                            TypedExpression::Array(a) => {
-                                match a.inner_type().clone() {
-                                    Type::FieldElement => {
-                                        Ok(FieldElementExpression::select(a, index).into())
-                                    }
-                                    Type::Uint(..) => Ok(UExpression::select(a, index).into()),
-                                    Type::Boolean => Ok(BooleanExpression::select(a, index).into()),
-                                    Type::Array(..) => Ok(ArrayExpression::select(a, index).into()),
-                                    Type::Struct(..) => Ok(StructExpression::select(a, index).into()),
-                                    Type::Tuple(..) => Ok(TupleExpression::select(a, index).into()),
-                                    Type::Int => unreachable!(),
-                                }
-                            }
-                            a => Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Cannot access element at index {} of type {} on expression {} of type {}",
-                                    index,
-                                    index.get_type(),
-                                    a,
-                                    a.get_type()
-                                ),
-                            }),
-                        }
+/// Find the variables of a function which are constrained, either directly by an assertion, a constraint or a function
+/// call, or through the variables they define
+#[derive(Default)]
+struct ConstrainedVariables<'ast> {
+    constrained: HashSet<TypedIdentifier<'ast>>,
+    // the variables each variable is defined from
+    dependencies: Vec<(TypedIdentifier<'ast>, HashSet<TypedIdentifier<'ast>>)>,
+    // the variables found in the expressions being visited, innermost last
+    found: Vec<HashSet<TypedIdentifier<'ast>>>,
+}
+
+impl<'ast> ConstrainedVariables<'ast> {
+    fn find<T: Field>(
+        statements: Vec<TypedStatement<'ast, T>>,
+    ) -> (Vec<TypedStatement<'ast, T>>, HashSet<TypedIdentifier<'ast>>) {
+        let mut finder = ConstrainedVariables::default();
+
+        let statements = statements
+            .into_iter()
+            .flat_map(|s| finder.fold_statement(s))
+            .collect();
+
+        // a variable which defines a constrained variable is constrained
+        loop {
+            let mut changed = false;
+            for (id, dependencies) in &finder.dependencies {
+                if finder.constrained.contains(id) {
+                    for d in dependencies {
+                        changed |= finder.constrained.insert(d.clone());
                     }
                 }
             }
-            Expression::Element(box e, index) => {
-                let e = self.check_expression(e, module_id, types)?;
-                match e {
-                    TypedExpression::Tuple(t) => {
-                        let ty = t.ty().elements.get(index as usize);
+            if !changed {
+                break;
+            }
+        }
 
-                        match ty {
-                            Some(ty) => match ty {
-                                Type::Int => unreachable!(),
-                                Type::FieldElement => {
-                                    Ok(FieldElementExpression::element(t, index).into())
-                                }
-                                Type::Boolean => Ok(BooleanExpression::element(t, index).into()),
-                                Type::Uint(..) => Ok(UExpression::element(t, index).into()),
-                                Type::Array(..) => Ok(ArrayExpression::element(t, index).into()),
-                                Type::Struct(..) => Ok(StructExpression::element(t, index).into()),
-                                Type::Tuple(..) => Ok(TupleExpression::element(t, index).into()),
-                            },
-                            None => Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Tuple of size {} cannot be accessed at index {}",
-                                    t.ty().elements.len(),
-                                    index
-                                ),
-                            }),
-                        }
-                    }
-                    e => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot access tuple element {} on expression of type {}",
-                            index,
-                            e.get_type()
-                        ),
-                    }),
-                }
+        (statements, finder.constrained)
+    }
+
+    // visit something with a folder, returning the variables found in it
+    fn visit<U, F: FnOnce(&mut Self) -> U>(&mut self, f: F) -> (U, HashSet<TypedIdentifier<'ast>>) {
+        self.found.push(HashSet::new());
+        let res = f(self);
+        (res, self.found.pop().unwrap())
+    }
+}
+
+impl<'ast, T: Field> Folder<'ast, T> for ConstrainedVariables<'ast> {
+    fn fold_name(&mut self, n: TypedIdentifier<'ast>) -> TypedIdentifier<'ast> {
+        for found in &mut self.found {
+            found.insert(n.clone());
+        }
+        n
+    }
+
+    fn fold_statement(&mut self, s: TypedStatement<'ast, T>) -> Vec<TypedStatement<'ast, T>> {
+        match s {
+            TypedStatement::Definition(a, rhs) => {
+                let (rhs, found) = self.visit(|f| f.fold_definition_rhs(rhs));
+                self.dependencies
+                    .push((assignee_root(&a).id.clone(), found));
+                vec![TypedStatement::Definition(a, rhs)]
             }
-            Expression::Member(box e, box id) => {
-                let e = self.check_expression(e, module_id, types)?;
+            TypedStatement::Assertion(e, error) => {
+                let (e, found) = self.visit(|f| f.fold_boolean_expression(e));
+                self.constrained.extend(found);
+                vec![TypedStatement::Assertion(e, error)]
+            }
+            s => folder::fold_statement(self, s),
+        }
+    }
+
+    fn fold_assembly_statement(
+        &mut self,
+        s: TypedAssemblyStatement<'ast, T>,
+    ) -> Vec<TypedAssemblyStatement<'ast, T>> {
+        match s {
+            TypedAssemblyStatement::Assignment(a, e) => {
+                let (e, found) = self.visit(|f| f.fold_expression(e));
+                self.dependencies
+                    .push((assignee_root(&a).id.clone(), found));
+                vec![TypedAssemblyStatement::Assignment(a, e)]
+            }
+            TypedAssemblyStatement::Constraint(left, right, metadata) => {
+                let ((left, right), found) = self.visit(|f| {
+                    (
+                        f.fold_field_expression(left),
+                        f.fold_field_expression(right),
+                    )
+                });
+                self.constrained.extend(found);
+                vec![TypedAssemblyStatement::Constraint(left, right, metadata)]
+            }
+        }
+    }
+
+    // the arguments of a call may be constrained by the function being called
+    fn fold_function_call_expression<
+        E: Id<'ast, T> + From<TypedExpression<'ast, T>> + Expr<'ast, T> + FunctionCall<'ast, T>,
+    >(
+        &mut self,
+        ty: &E::Ty,
+        e: FunctionCallExpression<'ast, T, E>,
+    ) -> FunctionCallOrExpression<'ast, T, E> {
+        let (e, found) = self.visit(|f| folder::fold_function_call_expression(f, ty, e));
+        self.constrained.extend(found);
+        e
+    }
+
+    fn fold_embed_call(&mut self, e: EmbedCall<'ast, T>) -> EmbedCall<'ast, T> {
+        let (e, found) = self.visit(|f| folder::fold_embed_call(f, e));
+        self.constrained.extend(found);
+        e
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lazy_static::lazy_static;
+    use zokrates_ast::typed;
+    use zokrates_ast::untyped;
+    use zokrates_field::Bn128Field;
+
+    lazy_static! {
+        static ref MODULE_ID: OwnedModuleId = OwnedModuleId::from("");
+    }
+    mod constants {
+        use super::*;
+
+        use std::ops::Add;
+
+        #[test]
+        fn field_in_range() {
+            // The value of `P - 1` is a valid field literal
+            let expr = Expression::FieldConstant(Bn128Field::max_value().to_biguint()).mock();
+            assert!(Checker::<Bn128Field>::default()
+                .check_expression(expr, &*MODULE_ID, &TypeMap::new())
+                .is_ok());
+        }
+
+        #[test]
+        fn field_overflow() {
+            // the value of `P` is an invalid field literal
+            let value = Bn128Field::max_value().to_biguint().add(1u32);
+            let expr = Expression::FieldConstant(value).mock();
+
+            assert!(Checker::<Bn128Field>::default()
+                .check_expression(expr, &*MODULE_ID, &TypeMap::new())
+                .is_err());
+        }
+
+        #[test]
+        fn signed_in_range() {
+            // `127i8` and `-128i8` are the bounds of `i8`
+            let max = Expression::I8Constant(127u32.into()).mock();
+            assert_eq!(
+                Checker::<Bn128Field>::default().check_expression(
+                    max,
+                    &*MODULE_ID,
+                    &TypeMap::new()
+                ),
+                Ok(UExpressionInner::Value(127).annotate(UBitwidth::I8).into())
+            );
+
+            let min = Expression::Neg(box Expression::I8Constant(128u32.into()).mock()).mock();
+            assert_eq!(
+                Checker::<Bn128Field>::default().check_expression(
+                    min,
+                    &*MODULE_ID,
+                    &TypeMap::new()
+                ),
+                Ok(UExpressionInner::Value(0x80).annotate(UBitwidth::I8).into())
+            );
+        }
+
+        #[test]
+        fn signed_overflow() {
+            // `128i8` is not a valid `i8` literal
+            let expr = Expression::I8Constant(128u32.into()).mock();
+            assert_eq!(
+                Checker::<Bn128Field>::default()
+                    .check_expression(expr, &*MODULE_ID, &TypeMap::new())
+                    .unwrap_err()
+                    .message,
+                "Literal `128` out of range for type `i8`, expected a value in [-128, 127]"
+            );
+
+            // `-129i8` is not a valid `i8` literal
+            let expr = Expression::Neg(box Expression::I8Constant(129u32.into()).mock()).mock();
+            assert_eq!(
+                Checker::<Bn128Field>::default()
+                    .check_expression(expr, &*MODULE_ID, &TypeMap::new())
+                    .unwrap_err()
+                    .message,
+                "Literal `-129` out of range for type `i8`, expected a value in [-128, 127]"
+            );
+        }
+    }
+
+    mod array {
+        use super::*;
+        use num_bigint::BigUint;
 
-                match e {
-                    TypedExpression::Struct(s) => {
-                        // check that the struct has that field and return the type if it does
-                        let ty = s.ty().iter().find(|m| m.id == id).map(|m| *m.ty.clone());
+        #[test]
+        fn element_type_mismatch() {
+            // having different types in an array isn't allowed
+            // in the case of arrays, lengths do *not* have to match, as at this point they can be
+            // generic, so we cannot tell yet
+            let types = TypeMap::new();
 
-                        match ty {
-                            Some(ty) => match ty {
-                                Type::Int => unreachable!(),
-                                Type::FieldElement => {
-                                    Ok(FieldElementExpression::member(s, id.to_string()).into())
-                                }
-                                Type::Boolean => {
-                                    Ok(BooleanExpression::member(s, id.to_string()).into())
-                                }
-                                Type::Uint(..) => Ok(UExpression::member(s, id.to_string()).into()),
-                                Type::Array(..) => {
-                                    Ok(ArrayExpression::member(s, id.to_string()).into())
-                                }
-                                Type::Struct(..) => {
-                                    Ok(StructExpression::member(s, id.to_string()).into())
-                                }
-                                Type::Tuple(..) => {
-                                    Ok(TupleExpression::member(s, id.to_string()).into())
-                                }
-                            },
-                            None => Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "{} {{{}}} doesn't have member {}",
-                                    s.get_type(),
-                                    s.ty()
-                                        .members
-                                        .iter()
-                                        .map(|m| format!("{}: {}", m.id, m.ty))
-                                        .collect::<Vec<_>>()
-                                        .join(", "),
-                                    id,
-                                ),
-                            }),
-                        }
-                    }
-                    e => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot access member {} on expression of type {}",
-                            id,
-                            e.get_type()
-                        ),
-                    }),
-                }
-            }
-            Expression::InlineArray(expressions_or_spreads) => {
-                // check each expression, getting its type
-                let mut expressions_or_spreads_checked = vec![];
-                for e in expressions_or_spreads {
-                    let e_checked = self.check_spread_or_expression(e, module_id, types)?;
-                    expressions_or_spreads_checked.push(e_checked);
-                }
+            // [3, true]
+            let a = Expression::InlineArray(vec![
+                Expression::IntConstant(3usize.into()).mock().into(),
+                Expression::BooleanConstant(true).mock().into(),
+            ])
+            .mock();
+            assert!(Checker::<Bn128Field>::default()
+                .check_expression(a, &*MODULE_ID, &types)
+                .is_err());
 
-                if expressions_or_spreads_checked.is_empty() {
-                    return Err(ErrorInner {
-                        pos: Some(pos),
-                        message: "Empty arrays are not allowed".to_string(),
-                    });
-                }
+            // [[0f], [0f, 0f]]
+            // accepted at this stage, as we do not check array lengths (as they can be variable)
+            let a = Expression::InlineArray(vec![
+                Expression::InlineArray(vec![Expression::FieldConstant(BigUint::from(0u32))
+                    .mock()
+                    .into()])
+                .mock()
+                .into(),
+                Expression::InlineArray(vec![
+                    Expression::FieldConstant(BigUint::from(0u32)).mock().into(),
+                    Expression::FieldConstant(BigUint::from(0u32)).mock().into(),
+                ])
+                .mock()
+                .into(),
+            ])
+            .mock();
+            assert!(Checker::<Bn128Field>::default()
+                .check_expression(a, &*MODULE_ID, &types)
+                .is_ok());
 
-                // we infer the inner type to be the type of the first non-integer element
-                // if there was no such element, then the array only has integers and we use that as the inner type
-                let inferred_type = expressions_or_spreads_checked
-                    .iter()
-                    .filter_map(|e| match e.get_type() {
-                        (Type::Int, _) => None,
-                        (t, _) => Some(t),
-                    })
-                    .next()
-                    .unwrap_or(Type::Int);
+            // [[0f], true]
+            let a = Expression::InlineArray(vec![
+                Expression::InlineArray(vec![Expression::FieldConstant(BigUint::from(0u32))
+                    .mock()
+                    .into()])
+                .mock()
+                .into(),
+                Expression::InlineArray(vec![Expression::BooleanConstant(true).mock().into()])
+                    .mock()
+                    .into(),
+            ])
+            .mock();
+            assert!(Checker::<Bn128Field>::default()
+                .check_expression(a, &*MODULE_ID, &types)
+                .is_err());
+        }
 
-                let unwrapped_expressions_or_spreads = match inferred_type.clone() {
-                    Type::Int => expressions_or_spreads_checked,
-                    t => {
-                        let target_array_ty =
-                            ArrayType::new(t, UExpressionInner::Value(0).annotate(UBitwidth::B32));
+        #[test]
+        fn byte_string() {
+            // b"ab" is a u8[2]
+            let types = TypeMap::new();
 
-                        expressions_or_spreads_checked
-                            .into_iter()
-                            .map(|e| {
-                                TypedExpressionOrSpread::align_to_type(e, &target_array_ty).map_err(
-                                    |(e, ty)| ErrorInner {
-                                        pos: Some(pos),
-                                        message: format!("Expected {} to have type {}", e, ty,),
-                                    },
-                                )
-                            })
-                            .collect::<Result<Vec<_>, _>>()?
-                    }
-                };
+            let a = Expression::ByteString("ab".into()).mock();
+            assert_eq!(
+                Checker::<Bn128Field>::default().check_expression(a, &*MODULE_ID, &types),
+                Ok(ArrayExpressionInner::Bytes("ab".into())
+                    .annotate(Type::Uint(UBitwidth::B8), 2u32)
+                    .into())
+            );
+        }
 
-                // the size of the inline array is the sum of the size of its elements. However expressed as a u32 expression,
-                // this value can be an tree of height n in the worst case, with n the size of the array (if all elements are
-                // simple values and not spreads, 1 + 1 + 1 + ... 1)
-                // To avoid that, we compute 2 sizes: the sum of all constant sizes as an u32 expression, and the
-                // sum of all non constant sizes as a u32 number. We then return the sum of the two as a u32 expression.
-                // `1 + 1 + ... + 1` is reduced to a single expression, which prevents this blowup
+        #[test]
+        fn bounded_array_type() {
+            // field[..3] holds up to 3 field elements
+            let types = TypeMap::new();
 
-                let size: UExpression<'ast, T> = unwrapped_expressions_or_spreads
-                    .iter()
-                    .map(|e| e.size())
-                    .fold(None, |acc, e| match acc {
-                        Some((c_acc, e_acc)) => match e.as_inner() {
-                            UExpressionInner::Value(e) => Some(((c_acc + *e as u32), e_acc)),
-                            _ => Some((c_acc, e_acc + e)),
-                        },
-                        None => match e.as_inner() {
-                            UExpressionInner::Value(e) => Some((*e as u32, 0u32.into())),
-                            _ => Some((0u32, e)),
-                        },
-                    })
-                    .map(|(c_size, e_size)| e_size + c_size.into())
-                    .unwrap_or_else(|| 0u32.into());
+            let ty = UnresolvedType::bounded_array(
+                UnresolvedType::FieldElement.mock(),
+                Expression::IntConstant(3usize.into()).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<Bn128Field>::default().check_type(ty, &*MODULE_ID, &types),
+                Ok(Type::bounded_array(ArrayType::new(
+                    Type::FieldElement,
+                    3u32
+                )))
+            );
+        }
 
-                Ok(
-                    ArrayExpressionInner::Value(unwrapped_expressions_or_spreads.into())
-                        .annotate(inferred_type, size)
-                        .into(),
+        #[test]
+        fn generic_bounded_array_element() {
+            // def foo<N>(field[N][..3] a) is accepted, as bounded arrays can hold elements of any type
+            let state = State::<Bn128Field>::new(HashMap::new(), (*MODULE_ID).clone());
+
+            let ty = UnresolvedType::bounded_array(
+                UnresolvedType::array(
+                    UnresolvedType::FieldElement.mock(),
+                    Expression::Identifier("N").mock(),
                 )
-            }
-            Expression::InlineTuple(elements) => {
-                let elements: Vec<_> = elements
-                    .into_iter()
-                    .map(|e| self.check_expression(e, module_id, types))
-                    .collect::<Result<_, _>>()?;
-                let ty = TupleType::new(elements.iter().map(|e| e.get_type()).collect());
-                Ok(TupleExpressionInner::Value(elements).annotate(ty).into())
-            }
-            Expression::ArrayInitializer(box e, box count) => {
-                let e = self.check_expression(e, module_id, types)?;
-                let ty = e.get_type();
+                .mock(),
+                Expression::IntConstant(3usize.into()).mock(),
+            )
+            .mock();
+            assert_eq!(
+                Checker::<Bn128Field>::default().check_declaration_type(
+                    ty,
+                    &*MODULE_ID,
+                    &state,
+                    &vec![("N", 0)].into_iter().collect(),
+                    &mut HashSet::new(),
+                ),
+                Ok(DeclarationType::bounded_array(DeclarationArrayType::new(
+                    DeclarationType::array(DeclarationArrayType::new(
+                        DeclarationType::FieldElement,
+                        DeclarationConstant::Generic(
+                            GenericIdentifier::with_name("N").with_index(0)
+                        )
+                    )),
+                    DeclarationConstant::Concrete(3)
+                )))
+            );
+        }
+    }
 
-                let count = self.check_expression(count, module_id, types)?;
+    /// Helper function to create: () { return; }
+    fn function0() -> FunctionNode<'static> {
+        let statements = vec![Statement::Return(None).mock()];
 
-                let count = UExpression::try_from_typed(count, &UBitwidth::B32).map_err(|e| {
-                    ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Expected array initializer count to be a u32, found {} of type {}",
-                            e,
-                            e.get_type(),
-                        ),
-                    }
-                })?;
+        let arguments = vec![];
 
-                Ok(ArrayExpressionInner::Repeat(box e, box count.clone())
-                    .annotate(ty, count)
-                    .into())
-            }
-            Expression::InlineStruct(id, inline_members) => {
-                let ty = match types.get(module_id).unwrap().get(&id).cloned() {
-                    None => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!("Undefined type `{}`", id),
-                    }),
-                    Some(ty) => Ok(ty),
-                }?;
+        let signature = UnresolvedSignature::new();
 
-                let mut declared_struct_type = match ty.ty {
-                    DeclarationType::Struct(struct_type) => struct_type,
-                    _ => unreachable!(),
-                };
+        Function {
+            arguments,
+            statements,
+            signature,
+        }
+        .mock()
+    }
+
+    /// Check a module made of `symbols`, returning the state of the checker
+    fn check(
+        symbols: Vec<SymbolDeclarationNode<'static>>,
+    ) -> Result<State<'static, Bn128Field>, Vec<Error>> {
+        let mut state = State::<Bn128Field>::new(
+            vec![((*MODULE_ID).clone(), Module::with_symbols(symbols))]
+                .into_iter()
+                .collect(),
+            (*MODULE_ID).clone(),
+        );
 
-                declared_struct_type.generics = (0..declared_struct_type.generics.len())
-                    .map(|index| {
-                        Some(DeclarationConstant::Generic(
-                            GenericIdentifier::without_name().with_index(index),
-                        ))
-                    })
-                    .collect();
+        Checker::<Bn128Field>::default()
+            .check_module(&*MODULE_ID, &mut state)
+            .map(|_| state)
+    }
 
-                // check that we provided the required number of values
-                if declared_struct_type.members_count() != inline_members.len() {
-                    return Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Inline struct {} does not match {} {{{}}}",
-                            Expression::InlineStruct(id, inline_members),
-                            declared_struct_type,
-                            declared_struct_type
-                                .members
-                                .iter()
-                                .map(|m| format!("{}: {}", m.id, m.ty))
-                                .collect::<Vec<_>>()
-                                .join(", ")
-                        ),
-                    });
-                }
+    /// Helper function to create: (field a) { return; }
+    fn function1() -> FunctionNode<'static> {
+        let statements = vec![Statement::Return(None).mock()];
 
-                // check that the mapping of values matches the expected type
-                // put the value into a map, pick members from this map following declared members, and try to parse them
+        let arguments = vec![untyped::Parameter::new(
+            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+            None,
+        )
+        .mock()];
 
-                let mut inline_members_map = inline_members
-                    .clone()
-                    .into_iter()
-                    .map(|(id, v)| (id.to_string(), v))
-                    .collect::<BTreeMap<_, _>>();
+        let signature =
+            UnresolvedSignature::new().inputs(vec![UnresolvedType::FieldElement.mock()]);
 
-                let inferred_values = declared_struct_type
-                    .iter()
-                    .map(
-                        |member| match inline_members_map.remove(member.id.as_str()) {
-                            Some(value) => {
-                                let expression_checked =
-                                    self.check_expression(value, module_id, types)?;
+        Function {
+            arguments,
+            statements,
+            signature,
+        }
+        .mock()
+    }
 
-                                let expression_checked =
-                                    TypedExpression::align_to_type(expression_checked, &*member.ty)
-                                        .map_err(|e| ErrorInner {
-                                            pos: Some(pos),
-                                            message: format!(
-                                        "Member {} of struct {} has type {}, found {} of type {}",
-                                        member.id,
-                                        id.clone(),
-                                        e.1,
-                                        e.0,
-                                        e.0.get_type(),
-                                    ),
-                                        })?;
+    mod symbols {
+        use super::*;
 
-                                Ok(expression_checked)
-                            }
-                            None => Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Member {} of struct {} {{{}}} not found in value {}",
-                                    member.id,
-                                    declared_struct_type,
-                                    declared_struct_type
-                                        .members
-                                        .iter()
-                                        .map(|m| format!("{}: {}", m.id, m.ty))
-                                        .collect::<Vec<_>>()
-                                        .join(", "),
-                                    Expression::InlineStruct(id.clone(), inline_members.clone()),
-                                ),
-                            }),
-                        },
-                    )
-                    .collect::<Result<Vec<_>, _>>()?;
+        fn struct0() -> StructDefinitionNode<'static> {
+            StructDefinition {
+                generics: vec![],
+                fields: vec![],
+            }
+            .mock()
+        }
 
-                let mut generics_map = GGenericsAssignment::default();
+        fn struct1() -> StructDefinitionNode<'static> {
+            StructDefinition {
+                generics: vec![],
+                fields: vec![StructDefinitionField {
+                    id: "foo",
+                    ty: UnresolvedType::FieldElement.mock(),
+                }
+                .mock()],
+            }
+            .mock()
+        }
 
-                let members = declared_struct_type
-                    .iter()
-                    .zip(inferred_values.iter())
-                    .map(|(m, v)| {
-                        if !check_type(&m.ty, &v.get_type(), &mut generics_map) {
-                            Err(ErrorInner {
-                                pos: Some(pos),
-                                message: format!(
-                                    "Value `{}` doesn't match the expected type `{}` because of conflict in generic values",
-                                    Expression::InlineStruct(id.clone(), inline_members.clone()),
-                                    declared_struct_type
-                                ),
-                            })
-                        } else {
-                            Ok(StructMember {
-                                id: m.id.clone(),
-                                ty: box v.get_type().clone(),
-                            })
-                        }
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
+        #[test]
+        fn unifier() {
+            // the unifier should only accept either a single type or many functions of different signatures for each symbol
 
-                let generics = generics_map.0.values().cloned().map(Some).collect();
+            let mut unifier = SymbolUnifier::<Bn128Field>::default();
 
-                let inferred_struct_type = StructType {
-                    canonical_location: declared_struct_type.canonical_location.clone(),
-                    location: declared_struct_type.location,
-                    generics,
-                    members,
-                };
+            // the `foo` type
+            assert!(unifier.insert_type("foo"));
+            // the `foo` type annot be declared a second time
+            assert!(!unifier.insert_type("foo"));
+            // the `foo` function cannot be declared as the name is already taken by a type
+            assert!(!unifier.insert_function("foo", DeclarationSignature::new()));
+            // the `bar` type
+            assert!(unifier.insert_function("bar", DeclarationSignature::new()));
+            // a second `bar` function of the same signature cannot be declared
+            assert!(!unifier.insert_function("bar", DeclarationSignature::new()));
+            // a second `bar` function of a different signature can be declared
+            assert!(unifier.insert_function(
+                "bar",
+                DeclarationSignature::new().inputs(vec![DeclarationType::FieldElement])
+            ));
+            // a second `bar` function with a generic parameter, which *could* conflict with an existing one should not be allowed
+            assert!(!unifier.insert_function(
+                "bar",
+                DeclarationSignature::new()
+                    .generics(vec![Some(
+                        GenericIdentifier::with_name("K").with_index(0).into()
+                    )])
+                    .inputs(vec![DeclarationType::FieldElement])
+            ));
+            // a `bar` function with a different signature
+            assert!(unifier.insert_function(
+                "bar",
+                DeclarationSignature::new()
+                    .generics(vec![Some(
+                        GenericIdentifier::with_name("K").with_index(0).into()
+                    )])
+                    .inputs(vec![DeclarationType::array((
+                        DeclarationType::FieldElement,
+                        GenericIdentifier::with_name("K").with_index(0)
+                    ))])
+            ));
+            // a `bar` function with an equivalent signature, just renaming generic parameters
+            assert!(!unifier.insert_function(
+                "bar",
+                DeclarationSignature::new()
+                    .generics(vec![Some(
+                        GenericIdentifier::with_name("L").with_index(0).into()
+                    )])
+                    .inputs(vec![DeclarationType::array((
+                        DeclarationType::FieldElement,
+                        GenericIdentifier::with_name("L").with_index(0)
+                    ))])
+            ));
+            // a `bar` type isn't allowed as the name is already taken by at least one function
+            assert!(!unifier.insert_type("bar"));
+        }
 
-                Ok(StructExpressionInner::Value(inferred_values)
-                    .annotate(inferred_struct_type)
-                    .into())
-            }
-            Expression::And(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
+        #[test]
+        fn imported_function() {
+            // foo.zok
+            // def main() {
+            //   return;
+            // }
 
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
-                )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!(
-                        "Cannot apply boolean operators to {} and {}",
-                        e1.get_type(),
-                        e2.get_type()
-                    ),
-                })?;
+            // bar.zok
+            // from "./foo.zok" import main;
 
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::Int(e1), TypedExpression::Int(e2)) => {
-                        Ok(IntExpression::And(box e1, box e2).into())
-                    }
-                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
-                        Ok(BooleanExpression::And(box e1, box e2).into())
-                    }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
+            // after semantic check, `bar` should import a checked function
 
-                        message: format!(
-                            "Cannot apply boolean operators to {} and {}",
-                            e1.get_type(),
-                            e2.get_type()
-                        ),
-                    }),
+            let foo: Module = Module {
+                symbols: vec![SymbolDeclaration {
+                    id: "main",
+                    symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                    is_public: true,
                 }
-            }
-            Expression::Or(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::Boolean(e1), TypedExpression::Boolean(e2)) => {
-                        Ok(BooleanExpression::Or(box e1, box e2).into())
-                    }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot apply `||` to {}, {}",
-                            e1.get_type(),
-                            e2.get_type()
-                        ),
-                    }),
+                .mock()],
+            };
+
+            let bar: Module = Module {
+                symbols: vec![SymbolDeclaration {
+                    id: "main",
+                    symbol: Symbol::There(SymbolImport::with_id_in_module("main", "foo").mock()),
+                    is_public: true,
                 }
-            }
-            Expression::LeftShift(box e1, box e2) => {
-                let e1 = self.check_expression(e1, module_id, types)?;
-                let e2 = self.check_expression(e2, module_id, types)?;
+                .mock()],
+            };
 
-                let e2 =
-                    UExpression::try_from_typed(e2, &UBitwidth::B32).map_err(|e| ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Expected the left shift right operand to have type `u32`, found {}",
-                            e
-                        ),
-                    })?;
+            let mut state = State::<Bn128Field>::new(
+                vec![("foo".into(), foo), ("bar".into(), bar)]
+                    .into_iter()
+                    .collect(),
+                (*MODULE_ID).clone(),
+            );
 
-                match e1 {
-                    TypedExpression::Int(e1) => Ok(IntExpression::LeftShift(box e1, box e2).into()),
-                    TypedExpression::Uint(e1) => Ok(UExpression::left_shift(e1, e2).into()),
-                    TypedExpression::FieldElement(e1) => {
-                        Ok(FieldElementExpression::LeftShift(box e1, box e2).into())
-                    }
-                    e1 => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot left-shift {} by {}",
-                            e1.get_type(),
-                            e2.get_type()
-                        ),
-                    }),
-                }
-            }
-            Expression::RightShift(box e1, box e2) => {
-                let e1 = self.check_expression(e1, module_id, types)?;
-                let e2 = self.check_expression(e2, module_id, types)?;
+            let mut checker: Checker<Bn128Field> = Checker::default();
 
-                let e2 =
-                    UExpression::try_from_typed(e2, &UBitwidth::B32).map_err(|e| ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Expected the right shift right operand to be of type `u32`, found {}",
-                            e
-                        ),
-                    })?;
+            assert_eq!(
+                checker.check_module(&OwnedTypedModuleId::from("bar"), &mut state),
+                Ok(())
+            );
+            assert_eq!(
+                state.typed_modules.get(&PathBuf::from("bar")),
+                Some(&TypedModule {
+                    symbols: vec![TypedFunctionSymbolDeclaration::new(
+                        DeclarationFunctionKey::with_location("bar", "main")
+                            .signature(DeclarationSignature::new()),
+                        TypedFunctionSymbol::There(
+                            DeclarationFunctionKey::with_location("foo", "main")
+                                .signature(DeclarationSignature::new()),
+                        )
+                    )
+                    .into()]
+                    .into_iter()
+                    .collect(),
+                })
+            );
+        }
 
-                match e1 {
-                    TypedExpression::Int(e1) => {
-                        Ok(IntExpression::RightShift(box e1, box e2).into())
+        #[test]
+        fn duplicate_function_declaration() {
+            // def foo() {
+            //   return;
+            // }
+            // def foo() {
+            //   return;
+            // }
+            //
+            // should fail
+
+            let module = Module {
+                symbols: vec![
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                        is_public: true,
                     }
-                    TypedExpression::Uint(e1) => Ok(UExpression::right_shift(e1, e2).into()),
-                    TypedExpression::FieldElement(e1) => {
-                        Ok(FieldElementExpression::RightShift(box e1, box e2).into())
+                    .mock(),
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                        is_public: true,
                     }
-                    e1 => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot right-shift {} by {}",
-                            e1.get_type(),
-                            e2.get_type()
-                        ),
-                    }),
-                }
-            }
-            Expression::BitOr(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
+                    .mock(),
+                ],
+            };
 
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
-                )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!("Cannot apply `|` to {}, {}", e1.get_type(), e2.get_type()),
-                })?;
+            let mut state = State::<Bn128Field>::new(
+                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                (*MODULE_ID).clone(),
+            );
 
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::Int(e1), TypedExpression::Int(e2)) => {
-                        Ok(IntExpression::Or(box e1, box e2).into())
-                    }
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(FieldElementExpression::Or(box e1, box e2).into())
-                    }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
-                        if e1.bitwidth() == e2.bitwidth() =>
-                    {
-                        Ok(UExpression::or(e1, e2).into())
-                    }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot apply `|` to {}, {}",
-                            e1.get_type(),
-                            e2.get_type()
-                        ),
-                    }),
-                }
-            }
-            Expression::BitAnd(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            assert_eq!(
+                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
+                    .inner
+                    .message,
+                "foo conflicts with another symbol"
+            );
+        }
 
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
+        #[test]
+        fn duplicate_function_declaration_generic() {
+            // def foo<P>(field[P] a) {
+            //   return;
+            // }
+            // def foo(field[3] a) {
+            //   return;
+            // }
+            //
+            // should succeed as P could be different from 3
+
+            let mut f0 = function0();
+
+            f0.value.arguments = vec![untyped::Parameter::new(
+                untyped::Variable::immutable(
+                    "a",
+                    UnresolvedType::array(
+                        UnresolvedType::FieldElement.mock(),
+                        Expression::Identifier("P").mock(),
+                    )
+                    .mock(),
                 )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!("Cannot apply `&` to {}, {}", e1.get_type(), e2.get_type()),
-                })?;
+                .mock(),
+                None,
+            )
+            .mock()];
+            f0.value.signature = UnresolvedSignature::new()
+                .generics(vec!["P".mock()])
+                .inputs(vec![UnresolvedType::array(
+                    UnresolvedType::FieldElement.mock(),
+                    Expression::Identifier("P").mock(),
+                )
+                .mock()]);
 
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::Int(e1), TypedExpression::Int(e2)) => {
-                        Ok(IntExpression::And(box e1, box e2).into())
-                    }
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(FieldElementExpression::And(box e1, box e2).into())
-                    }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
-                        if e1.bitwidth() == e2.bitwidth() =>
-                    {
-                        Ok(UExpression::and(e1, e2).into())
-                    }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot apply `&` to {}, {}",
-                            e1.get_type(),
-                            e2.get_type()
-                        ),
-                    }),
-                }
-            }
-            Expression::BitXor(box e1, box e2) => {
-                let e1_checked = self.check_expression(e1, module_id, types)?;
-                let e2_checked = self.check_expression(e2, module_id, types)?;
+            let mut f1 = function0();
 
-                let (e1_checked, e2_checked) = TypedExpression::align_without_integers(
-                    e1_checked, e2_checked,
+            f1.value.arguments = vec![untyped::Parameter::new(
+                untyped::Variable::immutable(
+                    "a",
+                    UnresolvedType::array(
+                        UnresolvedType::FieldElement.mock(),
+                        Expression::U32Constant(3).mock(),
+                    )
+                    .mock(),
                 )
-                .map_err(|(e1, e2)| ErrorInner {
-                    pos: Some(pos),
-                    message: format!("Cannot apply `^` to {}, {}", e1.get_type(), e2.get_type()),
-                })?;
+                .mock(),
+                None,
+            )
+            .mock()];
+            f1.value.signature = UnresolvedSignature::new().inputs(vec![UnresolvedType::array(
+                UnresolvedType::FieldElement.mock(),
+                Expression::U32Constant(3).mock(),
+            )
+            .mock()]);
 
-                match (e1_checked, e2_checked) {
-                    (TypedExpression::Int(e1), TypedExpression::Int(e2)) => {
-                        Ok(IntExpression::Xor(box e1, box e2).into())
-                    }
-                    (TypedExpression::FieldElement(e1), TypedExpression::FieldElement(e2)) => {
-                        Ok(FieldElementExpression::Xor(box e1, box e2).into())
+            let module = Module {
+                symbols: vec![
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Function(f0)),
+                        is_public: true,
                     }
-                    (TypedExpression::Uint(e1), TypedExpression::Uint(e2))
-                        if e1.bitwidth() == e2.bitwidth() =>
-                    {
-                        Ok(UExpression::xor(e1, e2).into())
+                    .mock(),
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Function(f1)),
+                        is_public: true,
                     }
-                    (e1, e2) => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!(
-                            "Cannot apply `^` to {}, {}",
-                            e1.get_type(),
-                            e2.get_type()
-                        ),
-                    }),
-                }
-            }
-            Expression::Not(box e) => {
-                let e_checked = self.check_expression(e, module_id, types)?;
-                match e_checked {
-                    TypedExpression::Int(e) => Ok(IntExpression::Not(box e).into()),
-                    TypedExpression::Boolean(e) => Ok(BooleanExpression::Not(box e).into()),
-                    TypedExpression::Uint(e) => Ok((!e).into()),
-                    e => Err(ErrorInner {
-                        pos: Some(pos),
-                        message: format!("Cannot negate {}", e.get_type()),
-                    }),
-                }
-            }
-        }
-    }
-
-    fn insert_into_scope<I: Clone + Into<SourceIdentifier<'ast>>>(
-        &mut self,
-        id: I,
-        ty: Type<'ast, T>,
-        is_mutable: bool,
-    ) -> bool {
-        let info = IdentifierInfo {
-            id: self.id_in_this_scope(id.clone()),
-            ty,
-            is_mutable,
-        };
-        self.scope.insert(id, info)
-    }
+                    .mock(),
+                ],
+            };
 
-    fn find_functions(
-        &self,
-        query: &FunctionQuery<'ast, T>,
-    ) -> Vec<DeclarationFunctionKey<'ast, T>> {
-        query.match_funcs(&self.functions)
-    }
+            let mut state = State::new(
+                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                (*MODULE_ID).clone(),
+            );
 
-    fn enter_scope(&mut self) {
-        self.scope.enter();
-    }
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            assert!(checker.check_module(&*MODULE_ID, &mut state).is_ok());
+        }
 
-    fn exit_scope(&mut self) {
-        self.scope.exit();
-    }
-}
+        mod generics {
+            use super::*;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+            #[test]
+            fn unused_generic() {
+                // def foo<P>() {
+                //   return;
+                // }
+                // def main() {
+                //   return;
+                // }
+                //
+                // should succeed
 
-    use lazy_static::lazy_static;
-    use zokrates_ast::typed;
-    use zokrates_ast::untyped;
-    use zokrates_field::Bn128Field;
+                let mut foo = function0();
 
-    lazy_static! {
-        static ref MODULE_ID: OwnedModuleId = OwnedModuleId::from("");
-    }
-    mod constants {
-        use super::*;
+                foo.value.signature = UnresolvedSignature::new().generics(vec!["P".mock()]);
 
-        use std::ops::Add;
+                let module = Module {
+                    symbols: vec![
+                        SymbolDeclaration {
+                            id: "foo",
+                            symbol: Symbol::Here(SymbolDefinition::Function(foo)),
+                            is_public: true,
+                        }
+                        .mock(),
+                        SymbolDeclaration {
+                            id: "main",
+                            symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                            is_public: true,
+                        }
+                        .mock(),
+                    ],
+                };
 
-        #[test]
-        fn field_in_range() {
-            // The value of `P - 1` is a valid field literal
-            let expr = Expression::FieldConstant(Bn128Field::max_value().to_biguint()).mock();
-            assert!(Checker::<Bn128Field>::default()
-                .check_expression(expr, &*MODULE_ID, &TypeMap::new())
-                .is_ok());
-        }
+                let mut state = State::new(
+                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                    (*MODULE_ID).clone(),
+                );
 
-        #[test]
-        fn field_overflow() {
-            // the value of `P` is an invalid field literal
-            let value = Bn128Field::max_value().to_biguint().add(1u32);
-            let expr = Expression::FieldConstant(value).mock();
+                let mut checker: Checker<Bn128Field> = Checker::default();
+                assert!(checker.check_module(&*MODULE_ID, &mut state).is_ok());
+            }
 
-            assert!(Checker::<Bn128Field>::default()
-                .check_expression(expr, &*MODULE_ID, &TypeMap::new())
-                .is_err());
-        }
-    }
+            #[test]
+            fn undeclared_generic() {
+                // def foo(field[P] a) {
+                //   return;
+                // }
+                // def main() {
+                //   return;
+                // }
+                //
+                // should fail
 
-    mod array {
-        use super::*;
-        use num_bigint::BigUint;
+                let mut foo = function0();
 
-        #[test]
-        fn element_type_mismatch() {
-            // having different types in an array isn't allowed
-            // in the case of arrays, lengths do *not* have to match, as at this point they can be
-            // generic, so we cannot tell yet
-            let types = TypeMap::new();
+                foo.value.arguments = vec![untyped::Parameter::private(
+                    untyped::Variable::immutable(
+                        "a",
+                        UnresolvedType::array(
+                            UnresolvedType::FieldElement.mock(),
+                            Expression::Identifier("P").mock(),
+                        )
+                        .mock(),
+                    )
+                    .mock(),
+                )
+                .mock()];
+                foo.value.signature =
+                    UnresolvedSignature::new().inputs(vec![UnresolvedType::array(
+                        UnresolvedType::FieldElement.mock(),
+                        Expression::Identifier("P").mock(),
+                    )
+                    .mock()]);
 
-            // [3, true]
-            let a = Expression::InlineArray(vec![
-                Expression::IntConstant(3usize.into()).mock().into(),
-                Expression::BooleanConstant(true).mock().into(),
-            ])
-            .mock();
-            assert!(Checker::<Bn128Field>::default()
-                .check_expression(a, &*MODULE_ID, &types)
-                .is_err());
+                let module = Module {
+                    symbols: vec![
+                        SymbolDeclaration {
+                            id: "foo",
+                            symbol: Symbol::Here(SymbolDefinition::Function(foo)),
+                            is_public: true,
+                        }
+                        .mock(),
+                        SymbolDeclaration {
+                            id: "main",
+                            symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                            is_public: true,
+                        }
+                        .mock(),
+                    ],
+                };
 
-            // [[0f], [0f, 0f]]
-            // accepted at this stage, as we do not check array lengths (as they can be variable)
-            let a = Expression::InlineArray(vec![
-                Expression::InlineArray(vec![Expression::FieldConstant(BigUint::from(0u32))
-                    .mock()
-                    .into()])
-                .mock()
-                .into(),
-                Expression::InlineArray(vec![
-                    Expression::FieldConstant(BigUint::from(0u32)).mock().into(),
-                    Expression::FieldConstant(BigUint::from(0u32)).mock().into(),
-                ])
-                .mock()
-                .into(),
-            ])
-            .mock();
-            assert!(Checker::<Bn128Field>::default()
-                .check_expression(a, &*MODULE_ID, &types)
-                .is_ok());
+                let mut state = State::new(
+                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                    (*MODULE_ID).clone(),
+                );
 
-            // [[0f], true]
-            let a = Expression::InlineArray(vec![
-                Expression::InlineArray(vec![Expression::FieldConstant(BigUint::from(0u32))
-                    .mock()
-                    .into()])
-                .mock()
-                .into(),
-                Expression::InlineArray(vec![Expression::BooleanConstant(true).mock().into()])
-                    .mock()
-                    .into(),
-            ])
-            .mock();
-            assert!(Checker::<Bn128Field>::default()
-                .check_expression(a, &*MODULE_ID, &types)
-                .is_err());
+                let mut checker: Checker<Bn128Field> = Checker::default();
+                assert_eq!(
+                    checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
+                        .inner
+                        .message,
+                    "Undeclared symbol `P`"
+                );
+            }
         }
-    }
 
-    /// Helper function to create: () { return; }
-    fn function0() -> FunctionNode<'static> {
-        let statements = vec![Statement::Return(None).mock()];
+        #[test]
+        fn overloaded_function_declaration() {
+            // def foo() {
+            //   return;
+            // }
+            // def foo(field a) {
+            //   return;
+            // }
+            //
+            // should succeed as overloading is allowed
+
+            let module = Module {
+                symbols: vec![
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                        is_public: true,
+                    }
+                    .mock(),
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Function(function1())),
+                        is_public: true,
+                    }
+                    .mock(),
+                ],
+            };
 
-        let arguments = vec![];
+            let mut state = State::<Bn128Field>::new(
+                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                (*MODULE_ID).clone(),
+            );
 
-        let signature = UnresolvedSignature::new();
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            assert_eq!(checker.check_module(&*MODULE_ID, &mut state), Ok(()));
+            assert!(state
+                .typed_modules
+                .get(&*MODULE_ID)
+                .unwrap()
+                .functions_iter()
+                .any(|d| d.key
+                    == DeclarationFunctionKey::with_location((*MODULE_ID).clone(), "foo")
+                        .signature(DeclarationSignature::new())));
 
-        Function {
-            arguments,
-            statements,
-            signature,
+            assert!(state
+                .typed_modules
+                .get(&*MODULE_ID)
+                .unwrap()
+                .functions_iter()
+                .any(|d| d.key
+                    == DeclarationFunctionKey::with_location((*MODULE_ID).clone(), "foo")
+                        .signature(
+                            DeclarationSignature::new().inputs(vec![DeclarationType::FieldElement])
+                        )));
         }
-        .mock()
-    }
 
-    /// Helper function to create: (field a) { return; }
-    fn function1() -> FunctionNode<'static> {
-        let statements = vec![Statement::Return(None).mock()];
+        #[test]
+        fn duplicate_type_declaration() {
+            // struct Foo {}
+            // struct Foo { foo: field; }
+            //
+            // should fail
 
-        let arguments = vec![untyped::Parameter::new(
-            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-            None,
-        )
-        .mock()];
+            let module: Module = Module {
+                symbols: vec![
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Struct(struct0())),
+                        is_public: true,
+                    }
+                    .mock(),
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Struct(struct1())),
+                        is_public: true,
+                    }
+                    .mock(),
+                ],
+            };
 
-        let signature =
-            UnresolvedSignature::new().inputs(vec![UnresolvedType::FieldElement.mock()]);
+            let mut state = State::<Bn128Field>::new(
+                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                (*MODULE_ID).clone(),
+            );
 
-        Function {
-            arguments,
-            statements,
-            signature,
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            assert_eq!(
+                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
+                    .inner
+                    .message,
+                "foo conflicts with another symbol"
+            );
         }
-        .mock()
-    }
 
-    mod symbols {
-        use super::*;
+        #[test]
+        fn type_function_conflict() {
+            // struct foo {}
+            // def foo() {
+            //   return;
+            // }
+            //
+            // should fail
 
-        fn struct0() -> StructDefinitionNode<'static> {
-            StructDefinition {
-                generics: vec![],
-                fields: vec![],
-            }
-            .mock()
-        }
+            let module = Module {
+                symbols: vec![
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                        is_public: true,
+                    }
+                    .mock(),
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Struct(
+                            StructDefinition {
+                                generics: vec![],
+                                fields: vec![],
+                            }
+                            .mock(),
+                        )),
+                        is_public: true,
+                    }
+                    .mock(),
+                ],
+            };
 
-        fn struct1() -> StructDefinitionNode<'static> {
-            StructDefinition {
-                generics: vec![],
-                fields: vec![StructDefinitionField {
-                    id: "foo",
-                    ty: UnresolvedType::FieldElement.mock(),
-                }
-                .mock()],
-            }
-            .mock()
+            let mut state = State::<Bn128Field>::new(
+                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                (*MODULE_ID).clone(),
+            );
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            assert_eq!(
+                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
+                    .inner
+                    .message,
+                "foo conflicts with another symbol"
+            );
         }
 
         #[test]
-        fn unifier() {
-            // the unifier should only accept either a single type or many functions of different signatures for each symbol
+        fn type_imported_function_conflict() {
+            // import first
 
-            let mut unifier = SymbolUnifier::<Bn128Field>::default();
+            // // bar.code
+            // def main() { return; }
+            //
+            // // main.code
+            // import main from "bar" as foo;
+            // struct foo {}
+            //
+            // should fail
 
-            // the `foo` type
-            assert!(unifier.insert_type("foo"));
-            // the `foo` type annot be declared a second time
-            assert!(!unifier.insert_type("foo"));
-            // the `foo` function cannot be declared as the name is already taken by a type
-            assert!(!unifier.insert_function("foo", DeclarationSignature::new()));
-            // the `bar` type
-            assert!(unifier.insert_function("bar", DeclarationSignature::new()));
-            // a second `bar` function of the same signature cannot be declared
-            assert!(!unifier.insert_function("bar", DeclarationSignature::new()));
-            // a second `bar` function of a different signature can be declared
-            assert!(unifier.insert_function(
-                "bar",
-                DeclarationSignature::new().inputs(vec![DeclarationType::FieldElement])
-            ));
-            // a second `bar` function with a generic parameter, which *could* conflict with an existing one should not be allowed
-            assert!(!unifier.insert_function(
-                "bar",
-                DeclarationSignature::new()
-                    .generics(vec![Some(
-                        GenericIdentifier::with_name("K").with_index(0).into()
-                    )])
-                    .inputs(vec![DeclarationType::FieldElement])
-            ));
-            // a `bar` function with a different signature
-            assert!(unifier.insert_function(
-                "bar",
-                DeclarationSignature::new()
-                    .generics(vec![Some(
-                        GenericIdentifier::with_name("K").with_index(0).into()
-                    )])
-                    .inputs(vec![DeclarationType::array((
-                        DeclarationType::FieldElement,
-                        GenericIdentifier::with_name("K").with_index(0)
-                    ))])
-            ));
-            // a `bar` function with an equivalent signature, just renaming generic parameters
-            assert!(!unifier.insert_function(
-                "bar",
-                DeclarationSignature::new()
-                    .generics(vec![Some(
-                        GenericIdentifier::with_name("L").with_index(0).into()
-                    )])
-                    .inputs(vec![DeclarationType::array((
-                        DeclarationType::FieldElement,
-                        GenericIdentifier::with_name("L").with_index(0)
-                    ))])
-            ));
-            // a `bar` type isn't allowed as the name is already taken by at least one function
-            assert!(!unifier.insert_type("bar"));
-        }
+            let bar = Module::with_symbols(vec![SymbolDeclaration {
+                id: "main",
+                symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                is_public: true,
+            }
+            .mock()]);
 
-        #[test]
-        fn imported_function() {
-            // foo.zok
-            // def main() {
-            //   return;
-            // }
+            let main = Module {
+                symbols: vec![
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::There(
+                            SymbolImport::with_id_in_module("main", "bar").mock(),
+                        ),
+                        is_public: true,
+                    }
+                    .mock(),
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Struct(struct0())),
+                        is_public: true,
+                    }
+                    .mock(),
+                ],
+            };
+
+            let mut state = State::<Bn128Field>::new(
+                vec![((*MODULE_ID).clone(), main), ("bar".into(), bar)]
+                    .into_iter()
+                    .collect(),
+                (*MODULE_ID).clone(),
+            );
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            assert_eq!(
+                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
+                    .inner
+                    .message,
+                "foo conflicts with another symbol"
+            );
 
-            // bar.zok
-            // from "./foo.zok" import main;
+            // type declaration first
 
-            // after semantic check, `bar` should import a checked function
+            // // bar.code
+            // def main() { return; }
+            //
+            // // main.code
+            // struct foo {}
+            // import main from "bar" as foo;
+            //
+            // should fail
 
-            let foo: Module = Module {
-                symbols: vec![SymbolDeclaration {
-                    id: "main",
-                    symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-                }
-                .mock()],
-            };
+            let bar = Module::with_symbols(vec![SymbolDeclaration {
+                id: "main",
+                symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                is_public: true,
+            }
+            .mock()]);
 
-            let bar: Module = Module {
-                symbols: vec![SymbolDeclaration {
-                    id: "main",
-                    symbol: Symbol::There(SymbolImport::with_id_in_module("main", "foo").mock()),
-                }
-                .mock()],
+            let main = Module {
+                symbols: vec![
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::Here(SymbolDefinition::Struct(struct0())),
+                        is_public: true,
+                    }
+                    .mock(),
+                    SymbolDeclaration {
+                        id: "foo",
+                        symbol: Symbol::There(
+                            SymbolImport::with_id_in_module("main", "bar").mock(),
+                        ),
+                        is_public: true,
+                    }
+                    .mock(),
+                ],
             };
 
             let mut state = State::<Bn128Field>::new(
-                vec![("foo".into(), foo), ("bar".into(), bar)]
+                vec![((*MODULE_ID).clone(), main), ("bar".into(), bar)]
                     .into_iter()
                     .collect(),
                 (*MODULE_ID).clone(),
             );
 
             let mut checker: Checker<Bn128Field> = Checker::default();
+            assert_eq!(
+                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
+                    .inner
+                    .message,
+                "foo conflicts with another symbol"
+            );
+        }
+    }
+
+    fn new_with_args<'ast, T: Field>(
+        scope: Scope<'ast, T>,
+        functions: HashSet<DeclarationFunctionKey<'ast, T>>,
+    ) -> Checker<'ast, T> {
+        Checker {
+            scope,
+            functions,
+            return_type: None,
+            match_bindings: vec![],
+            loop_depth: 0,
+            ..Checker::default()
+        }
+    }
+
+    // checking function signatures
+    mod signature {
+        use super::*;
+
+        #[test]
+        fn undeclared_generic() {
+            let modules = Modules::new();
+            let state = State::new(modules, (*MODULE_ID).clone());
 
+            let signature = UnresolvedSignature::new().inputs(vec![UnresolvedType::Array(
+                box UnresolvedType::FieldElement.mock(),
+                Expression::Identifier("K").mock(),
+            )
+            .mock()]);
             assert_eq!(
-                checker.check_module(&OwnedTypedModuleId::from("bar"), &mut state),
-                Ok(())
+                Checker::<Bn128Field>::default().check_signature(signature, &*MODULE_ID, &state),
+                Err(vec![ErrorInner {
+                    pos: Some((Position::mock(), Position::mock())),
+                    message: "Undeclared symbol `K`".to_string()
+                }])
             );
+        }
+
+        #[test]
+        fn success() {
+            // <K, L, M>(field[L][K]) -> field[L][K]
+            let modules = Modules::new();
+            let state = State::new(modules, (*MODULE_ID).clone());
+
+            let signature = UnresolvedSignature::new()
+                .generics(vec!["K".mock(), "L".mock(), "M".mock()])
+                .inputs(vec![UnresolvedType::Array(
+                    box UnresolvedType::Array(
+                        box UnresolvedType::FieldElement.mock(),
+                        Expression::Identifier("K").mock(),
+                    )
+                    .mock(),
+                    Expression::Identifier("L").mock(),
+                )
+                .mock()])
+                .output(
+                    UnresolvedType::Array(
+                        box UnresolvedType::Array(
+                            box UnresolvedType::FieldElement.mock(),
+                            Expression::Identifier("L").mock(),
+                        )
+                        .mock(),
+                        Expression::Identifier("K").mock(),
+                    )
+                    .mock(),
+                );
             assert_eq!(
-                state.typed_modules.get(&PathBuf::from("bar")),
-                Some(&TypedModule {
-                    symbols: vec![TypedFunctionSymbolDeclaration::new(
-                        DeclarationFunctionKey::with_location("bar", "main")
-                            .signature(DeclarationSignature::new()),
-                        TypedFunctionSymbol::There(
-                            DeclarationFunctionKey::with_location("foo", "main")
-                                .signature(DeclarationSignature::new()),
+                Checker::<Bn128Field>::default().check_signature(signature, &*MODULE_ID, &state),
+                Ok(DeclarationSignature::new()
+                    .inputs(vec![DeclarationType::array((
+                        DeclarationType::array((
+                            DeclarationType::FieldElement,
+                            GenericIdentifier::with_name("K").with_index(0)
+                        )),
+                        GenericIdentifier::with_name("L").with_index(1)
+                    ))])
+                    .output(DeclarationType::array((
+                        DeclarationType::array((
+                            DeclarationType::FieldElement,
+                            GenericIdentifier::with_name("L").with_index(1)
+                        )),
+                        GenericIdentifier::with_name("K").with_index(0)
+                    ))))
+            );
+        }
+
+        #[test]
+        fn generic_arithmetic() {
+            // <K>(field[K * 2]) -> field[2 + 3]
+            let modules = Modules::new();
+            let state = State::new(modules, (*MODULE_ID).clone());
+
+            let signature = UnresolvedSignature::new()
+                .generics(vec!["K".mock()])
+                .inputs(vec![UnresolvedType::Array(
+                    box UnresolvedType::FieldElement.mock(),
+                    Expression::Mult(
+                        box Expression::Identifier("K").mock(),
+                        box Expression::IntConstant(2usize.into()).mock(),
+                    )
+                    .mock(),
+                )
+                .mock()])
+                .output(
+                    UnresolvedType::Array(
+                        box UnresolvedType::FieldElement.mock(),
+                        Expression::Add(
+                            box Expression::IntConstant(2usize.into()).mock(),
+                            box Expression::IntConstant(3usize.into()).mock(),
                         )
+                        .mock(),
                     )
-                    .into()]
-                    .into_iter()
-                    .collect(),
-                })
+                    .mock(),
+                );
+            assert_eq!(
+                Checker::<Bn128Field>::default().check_signature(signature, &*MODULE_ID, &state),
+                Ok(DeclarationSignature::new()
+                    .inputs(vec![DeclarationType::array((
+                        DeclarationType::FieldElement,
+                        DeclarationConstant::from_uint_expression(
+                            UExpression::from(DeclarationConstant::from(
+                                GenericIdentifier::with_name("K").with_index(0)
+                            )) * 2u32.into()
+                        )
+                    ))])
+                    .output(DeclarationType::array((
+                        DeclarationType::FieldElement,
+                        5u32
+                    ))))
             );
         }
 
         #[test]
-        fn duplicate_function_declaration() {
-            // def foo() {
-            //   return;
-            // }
-            // def foo() {
-            //   return;
-            // }
-            //
-            // should fail
+        fn generic_division_by_zero() {
+            // (field[2 / 0])
+            let modules = Modules::new();
+            let state = State::new(modules, (*MODULE_ID).clone());
+
+            let signature = UnresolvedSignature::new().inputs(vec![UnresolvedType::Array(
+                box UnresolvedType::FieldElement.mock(),
+                Expression::Div(
+                    box Expression::IntConstant(2usize.into()).mock(),
+                    box Expression::IntConstant(0usize.into()).mock(),
+                )
+                .mock(),
+            )
+            .mock()]);
+            assert_eq!(
+                Checker::<Bn128Field>::default().check_signature(signature, &*MODULE_ID, &state),
+                Err(vec![ErrorInner {
+                    pos: Some((Position::mock(), Position::mock())),
+                    message: "Division by zero in generic value (2 / 0)".to_string()
+                }])
+            );
+        }
+    }
+
+    #[test]
+    fn undefined_variable_in_statement() {
+        // field a = b;
+        // b undefined
+
+        let statement: StatementNode = Statement::Definition(
+            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+            Expression::Identifier("b").mock(),
+        )
+        .mock();
+
+        let mut checker: Checker<Bn128Field> = Checker::default();
+        checker.enter_scope();
+
+        assert_eq!(
+            checker.check_statement(statement, &*MODULE_ID, &TypeMap::new()),
+            Err(vec![ErrorInner {
+                pos: Some((Position::mock(), Position::mock())),
+                message: "Identifier \"b\" is undefined".into()
+            }])
+        );
+    }
+
+    #[test]
+    fn defined_variable_in_statement() {
+        // field a = b;
+        // b defined
+        let statement: StatementNode = Statement::Definition(
+            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+            Expression::Identifier("b").mock(),
+        )
+        .mock();
+
+        let mut scope = Scope::default();
+        scope.insert(
+            "b",
+            IdentifierInfo {
+                id: "b".into(),
+                ty: Type::FieldElement,
+                is_mutable: false,
+                declaration: None,
+            },
+        );
+
+        let mut checker: Checker<Bn128Field> = new_with_args(scope, HashSet::new());
+        checker.enter_scope();
+        assert_eq!(
+            checker.check_statement(statement, &*MODULE_ID, &TypeMap::new()),
+            Ok(TypedStatement::definition(
+                typed::Variable::field_element("a").into(),
+                FieldElementExpression::identifier("b".into()).into()
+            ))
+        );
+    }
 
-            let module = Module {
-                symbols: vec![
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-                    }
-                    .mock(),
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-                    }
-                    .mock(),
-                ],
-            };
+    #[test]
+    fn statement_with_source_map() {
+        // field a = b;
+        // b defined
+        // with a source map, the statement is preceded by its position
+        let statement: StatementNode = Statement::Definition(
+            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+            Expression::Identifier("b").mock(),
+        )
+        .mock();
 
-            let mut state = State::<Bn128Field>::new(
-                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                (*MODULE_ID).clone(),
-            );
+        let mut scope = Scope::default();
+        scope.insert(
+            "b",
+            IdentifierInfo {
+                id: "b".into(),
+                ty: Type::FieldElement,
+                is_mutable: false,
+                declaration: None,
+            },
+        );
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            assert_eq!(
-                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
-                    .inner
-                    .message,
-                "foo conflicts with another symbol"
-            );
+        let mut checker: Checker<Bn128Field> = Checker {
+            source_map: true,
+            ..new_with_args(scope, HashSet::new())
+        };
+        checker.enter_scope();
+        assert_eq!(
+            checker.check_lowered_statement(statement, &*MODULE_ID, &TypeMap::new()),
+            Ok(vec![
+                TypedStatement::SourceMetadata(SourceMetadata::new(
+                    MODULE_ID.display().to_string(),
+                    Position::mock()
+                )),
+                TypedStatement::definition(
+                    typed::Variable::field_element("a").into(),
+                    FieldElementExpression::identifier("b".into()).into()
+                )
+            ])
+        );
+    }
+
+    #[test]
+    fn declared_in_other_function() {
+        // def foo() {
+        //   field a = 1;
+        //   return;
+        // }
+        // def bar() {
+        //   return a;
+        // }
+        //
+        // should fail
+        let foo_args = vec![];
+        let foo_statements = vec![
+            Statement::Definition(
+                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                Expression::IntConstant(1usize.into()).mock(),
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
+        let foo = Function {
+            arguments: foo_args,
+            statements: foo_statements,
+            signature: UnresolvedSignature::new(),
         }
+        .mock();
 
-        #[test]
-        fn duplicate_function_declaration_generic() {
-            // def foo<P>(field[P] a) {
-            //   return;
-            // }
-            // def foo(field[3] a) {
-            //   return;
-            // }
-            //
-            // should succeed as P could be different from 3
+        let bar_args = vec![];
+        let bar_statements =
+            vec![Statement::Return(Some(Expression::Identifier("a").mock())).mock()];
 
-            let mut f0 = function0();
+        let bar = Function {
+            arguments: bar_args,
+            statements: bar_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![])
+                .output(UnresolvedType::FieldElement.mock()),
+        }
+        .mock();
 
-            f0.value.arguments = vec![untyped::Parameter::new(
-                untyped::Variable::immutable(
-                    "a",
-                    UnresolvedType::array(
-                        UnresolvedType::FieldElement.mock(),
-                        Expression::Identifier("P").mock(),
-                    )
-                    .mock(),
-                )
-                .mock(),
-                None,
-            )
-            .mock()];
-            f0.value.signature = UnresolvedSignature::new()
-                .generics(vec!["P".mock()])
-                .inputs(vec![UnresolvedType::array(
-                    UnresolvedType::FieldElement.mock(),
-                    Expression::Identifier("P").mock(),
-                )
-                .mock()]);
+        let symbols = vec![
+            SymbolDeclaration {
+                id: "foo",
+                symbol: Symbol::Here(SymbolDefinition::Function(foo)),
+                is_public: true,
+            }
+            .mock(),
+            SymbolDeclaration {
+                id: "bar",
+                symbol: Symbol::Here(SymbolDefinition::Function(bar)),
+                is_public: true,
+            }
+            .mock(),
+        ];
+        let module = Module { symbols };
 
-            let mut f1 = function0();
+        let mut state = State::<Bn128Field>::new(
+            vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+            (*MODULE_ID).clone(),
+        );
 
-            f1.value.arguments = vec![untyped::Parameter::new(
-                untyped::Variable::immutable(
-                    "a",
-                    UnresolvedType::array(
-                        UnresolvedType::FieldElement.mock(),
-                        Expression::U32Constant(3).mock(),
-                    )
-                    .mock(),
-                )
-                .mock(),
-                None,
-            )
-            .mock()];
-            f1.value.signature = UnresolvedSignature::new().inputs(vec![UnresolvedType::array(
-                UnresolvedType::FieldElement.mock(),
-                Expression::U32Constant(3).mock(),
-            )
-            .mock()]);
+        let mut checker: Checker<Bn128Field> = Checker::default();
+        assert_eq!(
+            checker.check_module(&*MODULE_ID, &mut state),
+            Err(vec![Error {
+                inner: ErrorInner {
+                    pos: Some((Position::mock(), Position::mock())),
+                    message: "Identifier \"a\" is undefined".into()
+                },
+                module_id: (*MODULE_ID).clone()
+            }])
+        );
+    }
 
-            let module = Module {
-                symbols: vec![
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Function(f0)),
-                    }
-                    .mock(),
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Function(f1)),
-                    }
-                    .mock(),
-                ],
-            };
+    #[test]
+    fn declared_in_two_scopes() {
+        // def foo() {
+        //   field a = 1;
+        //   return;
+        // }
+        // def bar() {
+        //   field a = 2;
+        //   return;
+        // }
+        // def main() {
+        //   return 1;
+        // }
+        // should pass
+        let foo_args = vec![];
+        let foo_statements = vec![
+            Statement::Definition(
+                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                Expression::IntConstant(1usize.into()).mock(),
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
 
-            let mut state = State::new(
-                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                (*MODULE_ID).clone(),
-            );
+        let foo = Function {
+            arguments: foo_args,
+            statements: foo_statements,
+            signature: UnresolvedSignature::new(),
+        }
+        .mock();
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            assert!(checker.check_module(&*MODULE_ID, &mut state).is_ok());
+        let bar_args = vec![];
+        let bar_statements = vec![
+            Statement::Definition(
+                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                Expression::IntConstant(2usize.into()).mock(),
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
+        let bar = Function {
+            arguments: bar_args,
+            statements: bar_statements,
+            signature: UnresolvedSignature::new(),
         }
+        .mock();
 
-        mod generics {
-            use super::*;
+        let main_args = vec![];
+        let main_statements =
+            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
 
-            #[test]
-            fn unused_generic() {
-                // def foo<P>() {
-                //   return;
-                // }
-                // def main() {
-                //   return;
-                // }
-                //
-                // should succeed
+        let main = Function {
+            arguments: main_args,
+            statements: main_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![])
+                .output(UnresolvedType::FieldElement.mock()),
+        }
+        .mock();
 
-                let mut foo = function0();
+        let symbols = vec![
+            SymbolDeclaration {
+                id: "foo",
+                symbol: Symbol::Here(SymbolDefinition::Function(foo)),
+                is_public: true,
+            }
+            .mock(),
+            SymbolDeclaration {
+                id: "bar",
+                symbol: Symbol::Here(SymbolDefinition::Function(bar)),
+                is_public: true,
+            }
+            .mock(),
+            SymbolDeclaration {
+                id: "main",
+                symbol: Symbol::Here(SymbolDefinition::Function(main)),
+                is_public: true,
+            }
+            .mock(),
+        ];
+        let module = Module { symbols };
 
-                foo.value.signature = UnresolvedSignature::new().generics(vec!["P".mock()]);
+        let mut state = State::<Bn128Field>::new(
+            vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+            (*MODULE_ID).clone(),
+        );
 
-                let module = Module {
-                    symbols: vec![
-                        SymbolDeclaration {
-                            id: "foo",
-                            symbol: Symbol::Here(SymbolDefinition::Function(foo)),
-                        }
-                        .mock(),
-                        SymbolDeclaration {
-                            id: "main",
-                            symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-                        }
-                        .mock(),
-                    ],
-                };
+        let mut checker: Checker<Bn128Field> = Checker::default();
+        assert!(checker.check_module(&*MODULE_ID, &mut state).is_ok());
+    }
 
-                let mut state = State::new(
-                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                    (*MODULE_ID).clone(),
-                );
+    #[test]
+    fn for_index_after_end() {
+        // def foo() {
+        //   for u32 i in 0..10 { }
+        //   return i;
+        // }
+        // should fail
+        let foo_statements: Vec<StatementNode> = vec![
+            Statement::For(
+                untyped::Variable::immutable("i", UnresolvedType::Uint(32).mock()).mock(),
+                Expression::IntConstant(0usize.into()).mock(),
+                Expression::IntConstant(10usize.into()).mock(),
+                vec![],
+            )
+            .mock(),
+            Statement::Return(Some(Expression::Identifier("i").mock())).mock(),
+        ];
+        let foo = Function {
+            arguments: vec![],
+            statements: foo_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![])
+                .output(UnresolvedType::FieldElement.mock()),
+        }
+        .mock();
 
-                let mut checker: Checker<Bn128Field> = Checker::default();
-                assert!(checker.check_module(&*MODULE_ID, &mut state).is_ok());
-            }
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
 
-            #[test]
-            fn undeclared_generic() {
-                // def foo(field[P] a) {
-                //   return;
-                // }
-                // def main() {
-                //   return;
-                // }
-                //
-                // should fail
+        let mut checker: Checker<Bn128Field> = Checker::default();
+        assert_eq!(
+            checker.check_function("foo", foo, &*MODULE_ID, &state),
+            Err(vec![ErrorInner {
+                pos: Some((Position::mock(), Position::mock())),
+                message: "Identifier \"i\" is undefined".into()
+            }])
+        );
+    }
 
-                let mut foo = function0();
+    #[test]
+    fn for_index_in_for() {
+        // def foo() {
+        //   for u32 i in 0..10 {
+        //     field a = i;
+        //   }
+        //   return;
+        // }
+        // should pass
 
-                foo.value.arguments = vec![untyped::Parameter::private(
-                    untyped::Variable::immutable(
-                        "a",
-                        UnresolvedType::array(
-                            UnresolvedType::FieldElement.mock(),
-                            Expression::Identifier("P").mock(),
-                        )
-                        .mock(),
-                    )
-                    .mock(),
-                )
-                .mock()];
-                foo.value.signature =
-                    UnresolvedSignature::new().inputs(vec![UnresolvedType::array(
-                        UnresolvedType::FieldElement.mock(),
-                        Expression::Identifier("P").mock(),
-                    )
-                    .mock()]);
+        let for_statements = vec![Statement::Definition(
+            untyped::Variable::immutable("a", UnresolvedType::Uint(32).mock()).mock(),
+            Expression::Identifier("i").mock(),
+        )
+        .mock()];
 
-                let module = Module {
-                    symbols: vec![
-                        SymbolDeclaration {
-                            id: "foo",
-                            symbol: Symbol::Here(SymbolDefinition::Function(foo)),
-                        }
-                        .mock(),
-                        SymbolDeclaration {
-                            id: "main",
-                            symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-                        }
-                        .mock(),
-                    ],
-                };
+        let foo_statements = vec![
+            Statement::For(
+                untyped::Variable::immutable("i", UnresolvedType::Uint(32).mock()).mock(),
+                Expression::IntConstant(0usize.into()).mock(),
+                Expression::IntConstant(10usize.into()).mock(),
+                for_statements,
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
 
-                let mut state = State::new(
-                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                    (*MODULE_ID).clone(),
-                );
+        let for_statements_checked = vec![TypedStatement::definition(
+            typed::Variable::uint(
+                CoreIdentifier::Source(ShadowedIdentifier::shadow("a".into(), 1)),
+                UBitwidth::B32,
+            )
+            .into(),
+            UExpression::identifier(
+                CoreIdentifier::Source(ShadowedIdentifier::shadow("i".into(), 1)).into(),
+            )
+            .annotate(UBitwidth::B32)
+            .into(),
+        )];
 
-                let mut checker: Checker<Bn128Field> = Checker::default();
-                assert_eq!(
-                    checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
-                        .inner
-                        .message,
-                    "Undeclared symbol `P`"
-                );
-            }
-        }
+        let foo_statements_checked = vec![
+            TypedStatement::For(
+                typed::Variable::uint(
+                    CoreIdentifier::Source(ShadowedIdentifier::shadow("i".into(), 1)),
+                    UBitwidth::B32,
+                ),
+                0u32.into(),
+                10u32.into(),
+                for_statements_checked,
+            ),
+            TypedStatement::Return(TypedExpression::empty_tuple()),
+        ];
 
-        #[test]
-        fn overloaded_function_declaration() {
-            // def foo() {
-            //   return;
-            // }
-            // def foo(field a) {
-            //   return;
-            // }
-            //
-            // should succeed as overloading is allowed
+        let foo = Function {
+            arguments: vec![],
+            statements: foo_statements,
+            signature: UnresolvedSignature::new(),
+        }
+        .mock();
 
-            let module = Module {
-                symbols: vec![
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-                    }
-                    .mock(),
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Function(function1())),
-                    }
-                    .mock(),
-                ],
-            };
+        let foo_checked = TypedFunction {
+            arguments: vec![],
+            statements: foo_statements_checked,
+            signature: DeclarationSignature::default(),
+        };
 
-            let mut state = State::<Bn128Field>::new(
-                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                (*MODULE_ID).clone(),
-            );
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            assert_eq!(checker.check_module(&*MODULE_ID, &mut state), Ok(()));
-            assert!(state
-                .typed_modules
-                .get(&*MODULE_ID)
-                .unwrap()
-                .functions_iter()
-                .any(|d| d.key
-                    == DeclarationFunctionKey::with_location((*MODULE_ID).clone(), "foo")
-                        .signature(DeclarationSignature::new())));
+        let mut checker: Checker<Bn128Field> = Checker::default();
+        assert_eq!(
+            checker.check_function("foo", foo, &*MODULE_ID, &state),
+            Ok(foo_checked)
+        );
+    }
 
-            assert!(state
-                .typed_modules
-                .get(&*MODULE_ID)
-                .unwrap()
-                .functions_iter()
-                .any(|d| d.key
-                    == DeclarationFunctionKey::with_location((*MODULE_ID).clone(), "foo")
-                        .signature(
-                            DeclarationSignature::new().inputs(vec![DeclarationType::FieldElement])
-                        )));
+    #[test]
+    fn break_outside_loop() {
+        // def foo() {
+        //   break;
+        //   return;
+        // }
+        // should fail
+        let foo_statements: Vec<StatementNode> =
+            vec![Statement::Break.mock(), Statement::Return(None).mock()];
+
+        let foo = Function {
+            arguments: vec![],
+            statements: foo_statements,
+            signature: UnresolvedSignature::new(),
         }
+        .mock();
 
-        #[test]
-        fn duplicate_type_declaration() {
-            // struct Foo {}
-            // struct Foo { foo: field; }
-            //
-            // should fail
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
 
-            let module: Module = Module {
-                symbols: vec![
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Struct(struct0())),
-                    }
-                    .mock(),
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Struct(struct1())),
-                    }
-                    .mock(),
-                ],
-            };
+        let mut checker: Checker<Bn128Field> = Checker::default();
+        assert_eq!(
+            checker.check_function("foo", foo, &*MODULE_ID, &state),
+            Err(vec![ErrorInner {
+                pos: Some((Position::mock(), Position::mock())),
+                message: "`break` used outside of a loop".into()
+            }])
+        );
+    }
 
-            let mut state = State::<Bn128Field>::new(
-                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                (*MODULE_ID).clone(),
-            );
+    #[test]
+    fn while_condition_not_bool() {
+        // def foo() {
+        //   while 1 bounded 10 {}
+        //   return;
+        // }
+        // should fail
+        let foo_statements: Vec<StatementNode> = vec![
+            Statement::While(
+                Expression::IntConstant(1usize.into()).mock(),
+                Expression::IntConstant(10usize.into()).mock(),
+                vec![],
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            assert_eq!(
-                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
-                    .inner
-                    .message,
-                "foo conflicts with another symbol"
-            );
+        let foo = Function {
+            arguments: vec![],
+            statements: foo_statements,
+            signature: UnresolvedSignature::new(),
         }
+        .mock();
 
-        #[test]
-        fn type_function_conflict() {
-            // struct foo {}
-            // def foo() {
-            //   return;
-            // }
-            //
-            // should fail
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
 
-            let module = Module {
-                symbols: vec![
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-                    }
-                    .mock(),
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Struct(
-                            StructDefinition {
-                                generics: vec![],
-                                fields: vec![],
-                            }
-                            .mock(),
-                        )),
-                    }
-                    .mock(),
-                ],
-            };
+        let mut checker: Checker<Bn128Field> = Checker::default();
+        assert_eq!(
+            checker.check_function("foo", foo, &*MODULE_ID, &state),
+            Err(vec![ErrorInner {
+                pos: Some((Position::mock(), Position::mock())),
+                message: "Expected while loop condition to be of type bool, found {integer}".into()
+            }])
+        );
+    }
 
-            let mut state = State::<Bn128Field>::new(
-                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                (*MODULE_ID).clone(),
-            );
+    #[test]
+    fn break_in_if_in_while() {
+        // def foo() {
+        //   while true bounded 10 {
+        //     if true { break; } else { continue; }
+        //   }
+        //   return;
+        // }
+        // should pass
+        let foo_statements: Vec<StatementNode> = vec![
+            Statement::While(
+                Expression::BooleanConstant(true).mock(),
+                Expression::IntConstant(10usize.into()).mock(),
+                vec![Statement::If(
+                    Expression::BooleanConstant(true).mock(),
+                    vec![Statement::Break.mock()],
+                    vec![Statement::Continue.mock()],
+                )
+                .mock()],
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            assert_eq!(
-                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
-                    .inner
-                    .message,
-                "foo conflicts with another symbol"
-            );
+        let foo = Function {
+            arguments: vec![],
+            statements: foo_statements,
+            signature: UnresolvedSignature::new(),
         }
+        .mock();
 
-        #[test]
-        fn type_imported_function_conflict() {
-            // import first
+        let foo_checked = TypedFunction {
+            arguments: vec![],
+            statements: vec![
+                TypedStatement::While(
+                    BooleanExpression::Value(true),
+                    10u32.into(),
+                    vec![TypedStatement::If(
+                        BooleanExpression::Value(true),
+                        vec![TypedStatement::Break],
+                        vec![TypedStatement::Continue],
+                    )],
+                    RuntimeError::SourceAssertion(
+                        SourceMetadata::new(MODULE_ID.display().to_string(), Position::mock())
+                            .message(Some("Bound of while loop exceeded".to_string())),
+                    ),
+                ),
+                TypedStatement::Return(TypedExpression::empty_tuple()),
+            ],
+            signature: DeclarationSignature::default(),
+        };
 
-            // // bar.code
-            // def main() { return; }
-            //
-            // // main.code
-            // import main from "bar" as foo;
-            // struct foo {}
-            //
-            // should fail
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
 
-            let bar = Module::with_symbols(vec![SymbolDeclaration {
-                id: "main",
-                symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-            }
-            .mock()]);
+        let mut checker: Checker<Bn128Field> = Checker::default();
+        assert_eq!(
+            checker.check_function("foo", foo, &*MODULE_ID, &state),
+            Ok(foo_checked)
+        );
+    }
 
-            let main = Module {
-                symbols: vec![
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::There(
-                            SymbolImport::with_id_in_module("main", "bar").mock(),
-                        ),
-                    }
-                    .mock(),
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Struct(struct0())),
-                    }
+    #[test]
+    fn arity_mismatch() {
+        // def foo() -> bool {
+        //   return true;
+        // }
+        // def bar() {
+        //   field a = foo();
+        //   return;
+        // }
+        // should fail
+        let bar_statements: Vec<StatementNode> = vec![
+            Statement::Definition(
+                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                Expression::FunctionCall(box Expression::Identifier("foo").mock(), None, vec![])
                     .mock(),
-                ],
-            };
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
 
-            let mut state = State::<Bn128Field>::new(
-                vec![((*MODULE_ID).clone(), main), ("bar".into(), bar)]
-                    .into_iter()
-                    .collect(),
-                (*MODULE_ID).clone(),
-            );
+        let foo = DeclarationFunctionKey {
+            module: (*MODULE_ID).clone(),
+            id: "foo",
+            signature: DeclarationSignature::new()
+                .inputs(vec![])
+                .output(DeclarationType::Boolean),
+        };
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            assert_eq!(
-                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
-                    .inner
-                    .message,
-                "foo conflicts with another symbol"
-            );
+        let functions = vec![foo].into_iter().collect();
+
+        let bar = Function {
+            arguments: vec![],
+            statements: bar_statements,
+            signature: UnresolvedSignature::new(),
+        }
+        .mock();
+
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
+
+        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), functions);
+        assert_eq!(
+            checker.check_function("bar", bar, &*MODULE_ID, &state),
+            Err(vec![ErrorInner {
+                pos: Some((Position::mock(), Position::mock())),
+                message:
+                    "Function definition for function foo with signature () -> field not found."
+                        .into()
+            }])
+        );
+    }
+
+    #[test]
+    fn function_undefined_in_definition() {
+        // def bar() {
+        //   field a = foo();
+        //   return;
+        // }
+        // should fail
+        let bar_statements: Vec<StatementNode> = vec![
+            Statement::Definition(
+                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                Expression::FunctionCall(box Expression::Identifier("foo").mock(), None, vec![])
+                    .mock(),
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
 
-            // type declaration first
+        let bar = Function {
+            arguments: vec![],
+            statements: bar_statements,
+            signature: UnresolvedSignature::new(),
+        }
+        .mock();
 
-            // // bar.code
-            // def main() { return; }
-            //
-            // // main.code
-            // struct foo {}
-            // import main from "bar" as foo;
-            //
-            // should fail
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
 
-            let bar = Module::with_symbols(vec![SymbolDeclaration {
-                id: "main",
-                symbol: Symbol::Here(SymbolDefinition::Function(function0())),
-            }
-            .mock()]);
+        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
+        assert_eq!(
+            checker.check_function("bar", bar, &*MODULE_ID, &state),
+            Err(vec![ErrorInner {
+                pos: Some((Position::mock(), Position::mock())),
 
-            let main = Module {
-                symbols: vec![
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::Here(SymbolDefinition::Struct(struct0())),
-                    }
-                    .mock(),
-                    SymbolDeclaration {
-                        id: "foo",
-                        symbol: Symbol::There(
-                            SymbolImport::with_id_in_module("main", "bar").mock(),
-                        ),
-                    }
-                    .mock(),
-                ],
-            };
+                message:
+                    "Function definition for function foo with signature () -> field not found."
+                        .into()
+            }])
+        );
+    }
 
-            let mut state = State::<Bn128Field>::new(
-                vec![((*MODULE_ID).clone(), main), ("bar".into(), bar)]
-                    .into_iter()
-                    .collect(),
-                (*MODULE_ID).clone(),
-            );
+    #[test]
+    fn function_calls_itself() {
+        // def foo(field a) -> field {
+        //   return foo(a);
+        // }
+        // should succeed, the depth of the recursion is checked later on
+        let foo_statements: Vec<StatementNode> = vec![Statement::Return(Some(
+            Expression::FunctionCall(
+                box Expression::Identifier("foo").mock(),
+                None,
+                vec![Expression::Identifier("a").mock()],
+            )
+            .mock(),
+        ))
+        .mock()];
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            assert_eq!(
-                checker.check_module(&*MODULE_ID, &mut state).unwrap_err()[0]
-                    .inner
-                    .message,
-                "foo conflicts with another symbol"
-            );
+        let foo = Function {
+            arguments: vec![untyped::Parameter::new(
+                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                None,
+            )
+            .mock()],
+            statements: foo_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![UnresolvedType::FieldElement.mock()])
+                .output(UnresolvedType::FieldElement.mock()),
         }
-    }
+        .mock();
 
-    fn new_with_args<'ast, T: Field>(
-        scope: Scope<'ast, T>,
-        functions: HashSet<DeclarationFunctionKey<'ast, T>>,
-    ) -> Checker<'ast, T> {
-        Checker {
-            scope,
-            functions,
-            return_type: None,
-        }
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
+
+        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
+        assert!(checker
+            .check_function("foo", foo, &*MODULE_ID, &state)
+            .is_ok());
     }
 
-    // checking function signatures
-    mod signature {
-        use super::*;
+    #[test]
+    fn undeclared_variable() {
+        // def foo() -> field {
+        //   return 1;
+        // }
+        // def main() {
+        //   a = foo();
+        //   return;
+        // }
+        // should fail
 
-        #[test]
-        fn undeclared_generic() {
-            let modules = Modules::new();
-            let state = State::new(modules, (*MODULE_ID).clone());
+        let foo_statements: Vec<StatementNode> =
+            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
 
-            let signature = UnresolvedSignature::new().inputs(vec![UnresolvedType::Array(
-                box UnresolvedType::FieldElement.mock(),
-                Expression::Identifier("K").mock(),
-            )
-            .mock()]);
-            assert_eq!(
-                Checker::<Bn128Field>::default().check_signature(signature, &*MODULE_ID, &state),
-                Err(vec![ErrorInner {
-                    pos: Some((Position::mock(), Position::mock())),
-                    message: "Undeclared symbol `K`".to_string()
-                }])
-            );
+        let foo = Function {
+            arguments: vec![],
+            statements: foo_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![])
+                .output(UnresolvedType::FieldElement.mock()),
         }
+        .mock();
 
-        #[test]
-        fn success() {
-            // <K, L, M>(field[L][K]) -> field[L][K]
-            let modules = Modules::new();
-            let state = State::new(modules, (*MODULE_ID).clone());
-
-            let signature = UnresolvedSignature::new()
-                .generics(vec!["K".mock(), "L".mock(), "M".mock()])
-                .inputs(vec![UnresolvedType::Array(
-                    box UnresolvedType::Array(
-                        box UnresolvedType::FieldElement.mock(),
-                        Expression::Identifier("K").mock(),
-                    )
-                    .mock(),
-                    Expression::Identifier("L").mock(),
-                )
-                .mock()])
-                .output(
-                    UnresolvedType::Array(
-                        box UnresolvedType::Array(
-                            box UnresolvedType::FieldElement.mock(),
-                            Expression::Identifier("L").mock(),
-                        )
-                        .mock(),
-                        Expression::Identifier("K").mock(),
-                    )
+        let main_statements: Vec<StatementNode> = vec![
+            Statement::Assignment(
+                Assignee::Identifier("a").mock(),
+                Expression::FunctionCall(box Expression::Identifier("foo").mock(), None, vec![])
                     .mock(),
-                );
-            assert_eq!(
-                Checker::<Bn128Field>::default().check_signature(signature, &*MODULE_ID, &state),
-                Ok(DeclarationSignature::new()
-                    .inputs(vec![DeclarationType::array((
-                        DeclarationType::array((
-                            DeclarationType::FieldElement,
-                            GenericIdentifier::with_name("K").with_index(0)
-                        )),
-                        GenericIdentifier::with_name("L").with_index(1)
-                    ))])
-                    .output(DeclarationType::array((
-                        DeclarationType::array((
-                            DeclarationType::FieldElement,
-                            GenericIdentifier::with_name("L").with_index(1)
-                        )),
-                        GenericIdentifier::with_name("K").with_index(0)
-                    ))))
-            );
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
+
+        let main = Function {
+            arguments: vec![],
+            statements: main_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![])
+                .output(UnresolvedType::Tuple(vec![]).mock()),
         }
+        .mock();
+
+        let module = Module {
+            symbols: vec![
+                SymbolDeclaration {
+                    id: "foo",
+                    symbol: Symbol::Here(SymbolDefinition::Function(foo)),
+                    is_public: true,
+                }
+                .mock(),
+                SymbolDeclaration {
+                    id: "main",
+                    symbol: Symbol::Here(SymbolDefinition::Function(main)),
+                    is_public: true,
+                }
+                .mock(),
+            ],
+        };
+
+        let mut state = State::<Bn128Field>::new(
+            vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+            (*MODULE_ID).clone(),
+        );
+
+        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
+        assert_eq!(
+            checker.check_module(&*MODULE_ID, &mut state),
+            Err(vec![Error {
+                inner: ErrorInner {
+                    pos: Some((Position::mock(), Position::mock())),
+                    message: "Variable `a` is undeclared".into()
+                },
+                module_id: (*MODULE_ID).clone()
+            }])
+        );
     }
 
     #[test]
-    fn undefined_variable_in_statement() {
-        // field a = b;
-        // b undefined
+    fn assign_to_select() {
+        // def foo() -> field {
+        //   return 1;
+        // }
+        // def main() {
+        //   field[1] mut a = [0];
+        //   a[0] = foo();
+        //   return;
+        // }
+        // should succeed
 
-        let statement: StatementNode = Statement::Definition(
-            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-            Expression::Identifier("b").mock(),
-        )
+        let foo_statements: Vec<StatementNode> =
+            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
+
+        let foo = Function {
+            arguments: vec![],
+            statements: foo_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![])
+                .output(UnresolvedType::FieldElement.mock()),
+        }
         .mock();
 
-        let mut checker: Checker<Bn128Field> = Checker::default();
-        checker.enter_scope();
-
-        assert_eq!(
-            checker.check_statement(statement, &*MODULE_ID, &TypeMap::new()),
-            Err(vec![ErrorInner {
-                pos: Some((Position::mock(), Position::mock())),
-                message: "Identifier \"b\" is undefined".into()
-            }])
-        );
-    }
+        let main_statements: Vec<StatementNode> = vec![
+            Statement::Definition(
+                untyped::Variable::mutable(
+                    "a",
+                    UnresolvedType::array(
+                        UnresolvedType::FieldElement.mock(),
+                        Expression::IntConstant(1usize.into()).mock(),
+                    )
+                    .mock(),
+                )
+                .mock(),
+                Expression::InlineArray(vec![untyped::SpreadOrExpression::Expression(
+                    Expression::IntConstant(0usize.into()).mock(),
+                )])
+                .mock(),
+            )
+            .mock(),
+            Statement::Assignment(
+                Assignee::Select(
+                    box Assignee::Identifier("a").mock(),
+                    box RangeOrExpression::Expression(
+                        untyped::Expression::IntConstant(0usize.into()).mock(),
+                    ),
+                )
+                .mock(),
+                Expression::FunctionCall(box Expression::Identifier("foo").mock(), None, vec![])
+                    .mock(),
+            )
+            .mock(),
+            Statement::Return(None).mock(),
+        ];
 
-    #[test]
-    fn defined_variable_in_statement() {
-        // field a = b;
-        // b defined
-        let statement: StatementNode = Statement::Definition(
-            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-            Expression::Identifier("b").mock(),
-        )
+        let main = Function {
+            arguments: vec![],
+            statements: main_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![])
+                .output(UnresolvedType::Tuple(vec![]).mock()),
+        }
         .mock();
 
-        let mut scope = Scope::default();
-        scope.insert(
-            "b",
-            IdentifierInfo {
-                id: "b".into(),
-                ty: Type::FieldElement,
-                is_mutable: false,
-            },
-        );
+        let module = Module {
+            symbols: vec![
+                SymbolDeclaration {
+                    id: "foo",
+                    symbol: Symbol::Here(SymbolDefinition::Function(foo)),
+                    is_public: true,
+                }
+                .mock(),
+                SymbolDeclaration {
+                    id: "main",
+                    symbol: Symbol::Here(SymbolDefinition::Function(main)),
+                    is_public: true,
+                }
+                .mock(),
+            ],
+        };
 
-        let mut checker: Checker<Bn128Field> = new_with_args(scope, HashSet::new());
-        checker.enter_scope();
-        assert_eq!(
-            checker.check_statement(statement, &*MODULE_ID, &TypeMap::new()),
-            Ok(TypedStatement::definition(
-                typed::Variable::field_element("a").into(),
-                FieldElementExpression::identifier("b".into()).into()
-            ))
+        let mut state = State::<Bn128Field>::new(
+            vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+            (*MODULE_ID).clone(),
         );
+
+        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
+        assert!(checker.check_module(&*MODULE_ID, &mut state).is_ok());
     }
 
     #[test]
-    fn declared_in_other_function() {
-        // def foo() {
-        //   field a = 1;
-        //   return;
-        // }
+    fn function_undefined() {
         // def bar() {
-        //   return a;
+        //   assert(1 == foo());
+        //   return;
         // }
-        //
         // should fail
-        let foo_args = vec![];
-        let foo_statements = vec![
-            Statement::Definition(
-                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-                Expression::IntConstant(1usize.into()).mock(),
+
+        let bar_statements: Vec<StatementNode> = vec![
+            Statement::Assertion(
+                Expression::Eq(
+                    box Expression::IntConstant(1usize.into()).mock(),
+                    box Expression::FunctionCall(
+                        box Expression::Identifier("foo").mock(),
+                        None,
+                        vec![],
+                    )
+                    .mock(),
+                )
+                .mock(),
+                None,
             )
             .mock(),
             Statement::Return(None).mock(),
         ];
-        let foo = Function {
-            arguments: foo_args,
-            statements: foo_statements,
+
+        let bar = Function {
+            arguments: vec![],
+            statements: bar_statements,
             signature: UnresolvedSignature::new(),
         }
         .mock();
 
-        let bar_args = vec![];
-        let bar_statements =
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
+
+        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
+        assert_eq!(
+            checker.check_function("bar", bar, &*MODULE_ID, &state),
+            Err(vec![ErrorInner {
+                pos: Some((Position::mock(), Position::mock())),
+
+                message: "Function definition for function foo with signature () -> _ not found."
+                    .into()
+            }])
+        );
+    }
+
+    #[test]
+    fn return_undefined() {
+        // def bar() {
+        //   return a;
+        // }
+        // should fail
+        let bar_statements: Vec<StatementNode> =
             vec![Statement::Return(Some(Expression::Identifier("a").mock())).mock()];
 
         let bar = Function {
-            arguments: bar_args,
+            arguments: vec![],
             statements: bar_statements,
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
@@ -4651,92 +9592,91 @@ mod tests {
         }
         .mock();
 
-        let symbols = vec![
-            SymbolDeclaration {
-                id: "foo",
-                symbol: Symbol::Here(SymbolDefinition::Function(foo)),
-            }
-            .mock(),
-            SymbolDeclaration {
-                id: "bar",
-                symbol: Symbol::Here(SymbolDefinition::Function(bar)),
-            }
-            .mock(),
-        ];
-        let module = Module { symbols };
-
-        let mut state = State::<Bn128Field>::new(
-            vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-            (*MODULE_ID).clone(),
-        );
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
 
-        let mut checker: Checker<Bn128Field> = Checker::default();
+        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
         assert_eq!(
-            checker.check_module(&*MODULE_ID, &mut state),
-            Err(vec![Error {
-                inner: ErrorInner {
-                    pos: Some((Position::mock(), Position::mock())),
-                    message: "Identifier \"a\" is undefined".into()
-                },
-                module_id: (*MODULE_ID).clone()
+            checker.check_function("bar", bar, &*MODULE_ID, &state),
+            Err(vec![ErrorInner {
+                pos: Some((Position::mock(), Position::mock())),
+                message: "Identifier \"a\" is undefined".into()
             }])
         );
     }
 
     #[test]
-    fn declared_in_two_scopes() {
-        // def foo() {
-        //   field a = 1;
-        //   return;
-        // }
-        // def bar() {
-        //   field a = 2;
+    fn duplicate_argument_name() {
+        // def main(field a, bool a) {
         //   return;
         // }
-        // def main() {
-        //   return 1;
-        // }
-        // should pass
-        let foo_args = vec![];
-        let foo_statements = vec![
-            Statement::Definition(
+        //
+        // should fail
+
+        let mut f = function0();
+        f.value.arguments = vec![
+            untyped::Parameter::private(
                 untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-                Expression::IntConstant(1usize.into()).mock(),
             )
             .mock(),
-            Statement::Return(None).mock(),
+            untyped::Parameter::private(
+                untyped::Variable::immutable("a", UnresolvedType::Boolean.mock()).mock(),
+            )
+            .mock(),
         ];
+        f.value.signature = UnresolvedSignature::new().inputs(vec![
+            UnresolvedType::FieldElement.mock(),
+            UnresolvedType::Boolean.mock(),
+        ]);
 
-        let foo = Function {
-            arguments: foo_args,
-            statements: foo_statements,
-            signature: UnresolvedSignature::new(),
-        }
-        .mock();
+        let modules = Modules::new();
+        let state = State::new(modules, (*MODULE_ID).clone());
+
+        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
+        assert_eq!(
+            checker
+                .check_function("main", f, &*MODULE_ID, &state)
+                .unwrap_err()[0]
+                .message,
+            "Duplicate name in function definition: `a` was previously declared as an argument, a generic parameter or a constant"
+        );
+    }
+
+    #[test]
+    fn duplicate_main_function() {
+        // def main(field a) -> field {
+        //   return 1;
+        // }
+        // def main() -> field {
+        //   return 1;
+        // }
+        //
+        // should fail
+        let main1_statements: Vec<StatementNode> =
+            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
+
+        let main1_arguments = vec![zokrates_ast::untyped::Parameter::public(
+            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+        )
+        .mock()];
 
-        let bar_args = vec![];
-        let bar_statements = vec![
-            Statement::Definition(
-                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-                Expression::IntConstant(2usize.into()).mock(),
-            )
-            .mock(),
-            Statement::Return(None).mock(),
-        ];
-        let bar = Function {
-            arguments: bar_args,
-            statements: bar_statements,
-            signature: UnresolvedSignature::new(),
+        let main2_statements: Vec<StatementNode> =
+            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
+
+        let main2_arguments = vec![];
+
+        let main1 = Function {
+            arguments: main1_arguments,
+            statements: main1_statements,
+            signature: UnresolvedSignature::new()
+                .inputs(vec![UnresolvedType::FieldElement.mock()])
+                .output(UnresolvedType::FieldElement.mock()),
         }
         .mock();
 
-        let main_args = vec![];
-        let main_statements =
-            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
-
-        let main = Function {
-            arguments: main_args,
-            statements: main_statements,
+        let main2 = Function {
+            arguments: main2_arguments,
+            statements: main2_statements,
             signature: UnresolvedSignature::new()
                 .inputs(vec![])
                 .output(UnresolvedType::FieldElement.mock()),
@@ -4745,791 +9685,970 @@ mod tests {
 
         let symbols = vec![
             SymbolDeclaration {
-                id: "foo",
-                symbol: Symbol::Here(SymbolDefinition::Function(foo)),
-            }
-            .mock(),
-            SymbolDeclaration {
-                id: "bar",
-                symbol: Symbol::Here(SymbolDefinition::Function(bar)),
+                id: "main",
+                symbol: Symbol::Here(SymbolDefinition::Function(main1)),
+                is_public: true,
             }
             .mock(),
             SymbolDeclaration {
                 id: "main",
-                symbol: Symbol::Here(SymbolDefinition::Function(main)),
+                symbol: Symbol::Here(SymbolDefinition::Function(main2)),
+                is_public: true,
             }
             .mock(),
         ];
-        let module = Module { symbols };
 
-        let mut state = State::<Bn128Field>::new(
-            vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-            (*MODULE_ID).clone(),
-        );
+        let main_module = Module { symbols };
+
+        let program = Program {
+            modules: vec![((*MODULE_ID).clone(), main_module)]
+                .into_iter()
+                .collect(),
+            main: (*MODULE_ID).clone(),
+        };
 
         let mut checker: Checker<Bn128Field> = Checker::default();
-        assert!(checker.check_module(&*MODULE_ID, &mut state).is_ok());
+        assert_eq!(
+            checker.check_program(program),
+            Err(vec![Error {
+                inner: ErrorInner {
+                    pos: None,
+                    message: "Only one main function allowed, found 2".into()
+                },
+                module_id: (*MODULE_ID).clone()
+            }])
+        );
     }
 
-    #[test]
-    fn for_index_after_end() {
-        // def foo() {
-        //   for u32 i in 0..10 { }
-        //   return i;
-        // }
-        // should fail
-        let foo_statements: Vec<StatementNode> = vec![
-            Statement::For(
-                untyped::Variable::immutable("i", UnresolvedType::Uint(32).mock()).mock(),
-                Expression::IntConstant(0usize.into()).mock(),
-                Expression::IntConstant(10usize.into()).mock(),
-                vec![],
-            )
-            .mock(),
-            Statement::Return(Some(Expression::Identifier("i").mock())).mock(),
-        ];
-        let foo = Function {
-            arguments: vec![],
-            statements: foo_statements,
-            signature: UnresolvedSignature::new()
-                .inputs(vec![])
-                .output(UnresolvedType::FieldElement.mock()),
+    mod shadowing {
+
+        use super::*;
+
+        #[test]
+        fn same_type() {
+            // field a = 2;
+            // field a = 2;
+            //
+            // should succeed
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+            let _: Result<TypedStatement<Bn128Field>, Vec<ErrorInner>> = checker.check_statement(
+                Statement::Definition(
+                    untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                    untyped::Expression::IntConstant(2usize.into()).mock(),
+                )
+                .mock(),
+                &*MODULE_ID,
+                &TypeMap::new(),
+            );
+            let s2_checked: Result<TypedStatement<Bn128Field>, Vec<ErrorInner>> = checker
+                .check_statement(
+                    Statement::Definition(
+                        untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock())
+                            .mock(),
+                        untyped::Expression::IntConstant(2usize.into()).mock(),
+                    )
+                    .mock(),
+                    &*MODULE_ID,
+                    &TypeMap::new(),
+                );
+            assert!(s2_checked.is_ok());
         }
-        .mock();
 
-        let modules = Modules::new();
-        let state = State::new(modules, (*MODULE_ID).clone());
+        #[test]
+        fn different_type() {
+            // field a = 2;
+            // bool a = true;
+            //
+            // should succeed
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+            let _: Result<TypedStatement<Bn128Field>, Vec<ErrorInner>> = checker.check_statement(
+                Statement::Definition(
+                    untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                    untyped::Expression::IntConstant(2usize.into()).mock(),
+                )
+                .mock(),
+                &*MODULE_ID,
+                &TypeMap::new(),
+            );
+            let s2_checked: Result<TypedStatement<Bn128Field>, Vec<ErrorInner>> = checker
+                .check_statement(
+                    Statement::Definition(
+                        untyped::Variable::immutable("a", UnresolvedType::Boolean.mock()).mock(),
+                        untyped::Expression::BooleanConstant(true).mock(),
+                    )
+                    .mock(),
+                    &*MODULE_ID,
+                    &TypeMap::new(),
+                );
+            assert!(s2_checked.is_ok());
+            assert_eq!(checker.scope.get("a").unwrap().ty, DeclarationType::Boolean);
+        }
+
+        #[test]
+        fn scopes() {
+            // field mut a = 2;
+            // for uint i in 0..0 {
+            //    a = 3
+            //    field a = 4
+            // }
+            // a = 5
+            //
+            // should be turned into
+            //
+            // field mut a_0 = 2;
+            // for uint i_1 in 0..0 {
+            //    a_0 = 3
+            //    field a_1 = 4
+            // }
+            // a_0 = 5
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+
+            let statements = vec![
+                Statement::Definition(
+                    untyped::Variable::mutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                    untyped::Expression::IntConstant(2usize.into()).mock(),
+                )
+                .mock(),
+                Statement::For(
+                    untyped::Variable::immutable("i", UnresolvedType::Uint(32).mock()).mock(),
+                    untyped::Expression::U32Constant(0).mock(),
+                    untyped::Expression::U32Constant(0).mock(),
+                    vec![
+                        Statement::Assignment(
+                            untyped::Assignee::Identifier("a").mock(),
+                            untyped::Expression::IntConstant(3usize.into()).mock(),
+                        )
+                        .mock(),
+                        Statement::Definition(
+                            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock())
+                                .mock(),
+                            untyped::Expression::IntConstant(4usize.into()).mock(),
+                        )
+                        .mock(),
+                    ],
+                )
+                .mock(),
+                Statement::Assignment(
+                    untyped::Assignee::Identifier("a").mock(),
+                    untyped::Expression::IntConstant(5usize.into()).mock(),
+                )
+                .mock(),
+            ];
+
+            let expected = vec![
+                TypedStatement::definition(
+                    typed::Variable::new(
+                        CoreIdentifier::from(ShadowedIdentifier::shadow("a".into(), 0)),
+                        Type::FieldElement,
+                        true,
+                    )
+                    .into(),
+                    FieldElementExpression::Number(2u32.into()).into(),
+                ),
+                TypedStatement::For(
+                    typed::Variable::new(
+                        CoreIdentifier::from(ShadowedIdentifier::shadow("i".into(), 1)),
+                        Type::Uint(UBitwidth::B32),
+                        false,
+                    ),
+                    0u32.into(),
+                    0u32.into(),
+                    vec![
+                        TypedStatement::definition(
+                            typed::Variable::new(
+                                CoreIdentifier::from(ShadowedIdentifier::shadow("a".into(), 0)),
+                                Type::FieldElement,
+                                true,
+                            )
+                            .into(),
+                            FieldElementExpression::Number(3u32.into()).into(),
+                        ),
+                        TypedStatement::definition(
+                            typed::Variable::new(
+                                CoreIdentifier::from(ShadowedIdentifier::shadow("a".into(), 1)),
+                                Type::FieldElement,
+                                false,
+                            )
+                            .into(),
+                            FieldElementExpression::Number(4u32.into()).into(),
+                        ),
+                    ],
+                ),
+                TypedStatement::definition(
+                    typed::Variable::new(
+                        CoreIdentifier::from(ShadowedIdentifier::shadow("a".into(), 0)),
+                        Type::FieldElement,
+                        true,
+                    )
+                    .into(),
+                    FieldElementExpression::Number(5u32.into()).into(),
+                ),
+            ];
+
+            checker.enter_scope();
+            let checked: Vec<_> = statements
+                .into_iter()
+                .map(|s| {
+                    checker
+                        .check_statement(s, &*MODULE_ID, &TypeMap::default())
+                        .unwrap()
+                })
+                .collect();
 
-        let mut checker: Checker<Bn128Field> = Checker::default();
-        assert_eq!(
-            checker.check_function("foo", foo, &*MODULE_ID, &state),
-            Err(vec![ErrorInner {
-                pos: Some((Position::mock(), Position::mock())),
-                message: "Identifier \"i\" is undefined".into()
-            }])
-        );
+            assert_eq!(checked, expected);
+        }
     }
 
-    #[test]
-    fn for_index_in_for() {
-        // def foo() {
-        //   for u32 i in 0..10 {
-        //     field a = i;
-        //   }
-        //   return;
-        // }
-        // should pass
+    mod structs {
+        use super::*;
+        use zokrates_ast::typed::types::StructMember;
 
-        let for_statements = vec![Statement::Definition(
-            untyped::Variable::immutable("a", UnresolvedType::Uint(32).mock()).mock(),
-            Expression::Identifier("i").mock(),
-        )
-        .mock()];
+        /// solver function to create a module at location "" with a single symbol `Foo { foo: field }`
+        fn create_module_with_foo(
+            s: StructDefinition<'static>,
+        ) -> (Checker<Bn128Field>, State<Bn128Field>) {
+            let module: Module = Module {
+                symbols: vec![SymbolDeclaration {
+                    id: "Foo",
+                    symbol: Symbol::Here(SymbolDefinition::Struct(s.mock())),
+                    is_public: true,
+                }
+                .mock()],
+            };
 
-        let foo_statements = vec![
-            Statement::For(
-                untyped::Variable::immutable("i", UnresolvedType::Uint(32).mock()).mock(),
-                Expression::IntConstant(0usize.into()).mock(),
-                Expression::IntConstant(10usize.into()).mock(),
-                for_statements,
-            )
-            .mock(),
-            Statement::Return(None).mock(),
-        ];
+            let mut state = State::<Bn128Field>::new(
+                vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                (*MODULE_ID).clone(),
+            );
 
-        let for_statements_checked = vec![TypedStatement::definition(
-            typed::Variable::uint(
-                CoreIdentifier::Source(ShadowedIdentifier::shadow("a".into(), 1)),
-                UBitwidth::B32,
-            )
-            .into(),
-            UExpression::identifier(
-                CoreIdentifier::Source(ShadowedIdentifier::shadow("i".into(), 1)).into(),
-            )
-            .annotate(UBitwidth::B32)
-            .into(),
-        )];
+            let mut checker: Checker<Bn128Field> = Checker::default();
 
-        let foo_statements_checked = vec![
-            TypedStatement::For(
-                typed::Variable::uint(
-                    CoreIdentifier::Source(ShadowedIdentifier::shadow("i".into(), 1)),
-                    UBitwidth::B32,
-                ),
-                0u32.into(),
-                10u32.into(),
-                for_statements_checked,
-            ),
-            TypedStatement::Return(TypedExpression::empty_tuple()),
-        ];
+            checker.check_module(&*MODULE_ID, &mut state).unwrap();
 
-        let foo = Function {
-            arguments: vec![],
-            statements: foo_statements,
-            signature: UnresolvedSignature::new(),
+            (checker, state)
         }
-        .mock();
 
-        let foo_checked = TypedFunction {
-            arguments: vec![],
-            statements: foo_statements_checked,
-            signature: DeclarationSignature::default(),
-        };
+        /// tests about declaring a type
+        mod declaration {
+            use super::*;
 
-        let modules = Modules::new();
-        let state = State::new(modules, (*MODULE_ID).clone());
+            #[test]
+            fn empty_def() {
+                // an empty struct should be allowed to be defined
+                let modules = Modules::new();
+                let state = State::new(modules, (*MODULE_ID).clone());
 
-        let mut checker: Checker<Bn128Field> = Checker::default();
-        assert_eq!(
-            checker.check_function("foo", foo, &*MODULE_ID, &state),
-            Ok(foo_checked)
-        );
-    }
+                let declaration: StructDefinitionNode = StructDefinition {
+                    generics: vec![],
+                    fields: vec![],
+                }
+                .mock();
 
-    #[test]
-    fn arity_mismatch() {
-        // def foo() -> bool {
-        //   return true;
-        // }
-        // def bar() {
-        //   field a = foo();
-        //   return;
-        // }
-        // should fail
-        let bar_statements: Vec<StatementNode> = vec![
-            Statement::Definition(
-                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-                Expression::FunctionCall(box Expression::Identifier("foo").mock(), None, vec![])
-                    .mock(),
-            )
-            .mock(),
-            Statement::Return(None).mock(),
-        ];
+                let expected_type =
+                    DeclarationStructType::new("".into(), "Foo".into(), vec![], vec![]);
 
-        let foo = DeclarationFunctionKey {
-            module: (*MODULE_ID).clone(),
-            id: "foo",
-            signature: DeclarationSignature::new()
-                .inputs(vec![])
-                .output(DeclarationType::Boolean),
-        };
+                assert_eq!(
+                    Checker::<Bn128Field>::default().check_struct_type_declaration(
+                        "Foo".into(),
+                        declaration,
+                        &*MODULE_ID,
+                        &state
+                    ),
+                    Ok(expected_type)
+                );
+            }
 
-        let functions = vec![foo].into_iter().collect();
+            #[test]
+            fn valid_def() {
+                // a valid struct should be allowed to be defined
+                let modules = Modules::new();
+                let state = State::new(modules, (*MODULE_ID).clone());
 
-        let bar = Function {
-            arguments: vec![],
-            statements: bar_statements,
-            signature: UnresolvedSignature::new(),
-        }
-        .mock();
+                let declaration: StructDefinitionNode = StructDefinition {
+                    generics: vec![],
+                    fields: vec![
+                        StructDefinitionField {
+                            id: "foo",
+                            ty: UnresolvedType::FieldElement.mock(),
+                        }
+                        .mock(),
+                        StructDefinitionField {
+                            id: "bar",
+                            ty: UnresolvedType::Boolean.mock(),
+                        }
+                        .mock(),
+                    ],
+                }
+                .mock();
 
-        let modules = Modules::new();
-        let state = State::new(modules, (*MODULE_ID).clone());
+                let expected_type = DeclarationStructType::new(
+                    "".into(),
+                    "Foo".into(),
+                    vec![],
+                    vec![
+                        DeclarationStructMember::new("foo".into(), DeclarationType::FieldElement),
+                        DeclarationStructMember::new("bar".into(), DeclarationType::Boolean),
+                    ],
+                );
 
-        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), functions);
-        assert_eq!(
-            checker.check_function("bar", bar, &*MODULE_ID, &state),
-            Err(vec![ErrorInner {
-                pos: Some((Position::mock(), Position::mock())),
-                message:
-                    "Function definition for function foo with signature () -> field not found."
-                        .into()
-            }])
-        );
-    }
+                assert_eq!(
+                    Checker::<Bn128Field>::default().check_struct_type_declaration(
+                        "Foo".into(),
+                        declaration,
+                        &*MODULE_ID,
+                        &state
+                    ),
+                    Ok(expected_type)
+                );
+            }
+
+            #[test]
+            fn duplicate_member_def() {
+                // definition of a struct with a duplicate member should be rejected
+                let modules = Modules::new();
+                let state = State::new(modules, (*MODULE_ID).clone());
+
+                let declaration: StructDefinitionNode = StructDefinition {
+                    generics: vec![],
+                    fields: vec![
+                        StructDefinitionField {
+                            id: "foo",
+                            ty: UnresolvedType::FieldElement.mock(),
+                        }
+                        .mock(),
+                        StructDefinitionField {
+                            id: "foo",
+                            ty: UnresolvedType::Boolean.mock(),
+                        }
+                        .mock(),
+                    ],
+                }
+                .mock();
+
+                assert_eq!(
+                    Checker::<Bn128Field>::default()
+                        .check_struct_type_declaration(
+                            "Foo".into(),
+                            declaration,
+                            &*MODULE_ID,
+                            &state
+                        )
+                        .unwrap_err()[0]
+                        .message,
+                    "Duplicate key foo in struct definition"
+                );
+            }
 
-    #[test]
-    fn function_undefined_in_definition() {
-        // def bar() {
-        //   field a = foo();
-        //   return;
-        // }
-        // should fail
-        let bar_statements: Vec<StatementNode> = vec![
-            Statement::Definition(
-                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-                Expression::FunctionCall(box Expression::Identifier("foo").mock(), None, vec![])
-                    .mock(),
-            )
-            .mock(),
-            Statement::Return(None).mock(),
-        ];
+            #[test]
+            fn recursive() {
+                // a struct wrapping another struct should be allowed to be defined
 
-        let bar = Function {
-            arguments: vec![],
-            statements: bar_statements,
-            signature: UnresolvedSignature::new(),
-        }
-        .mock();
+                // struct Foo = { foo: field; }
+                // struct Bar = { foo: Foo; }
 
-        let modules = Modules::new();
-        let state = State::new(modules, (*MODULE_ID).clone());
+                let module: Module = Module {
+                    symbols: vec![
+                        SymbolDeclaration {
+                            id: "Foo",
+                            symbol: Symbol::Here(SymbolDefinition::Struct(
+                                StructDefinition {
+                                    generics: vec![],
+                                    fields: vec![StructDefinitionField {
+                                        id: "foo",
+                                        ty: UnresolvedType::FieldElement.mock(),
+                                    }
+                                    .mock()],
+                                }
+                                .mock(),
+                            )),
+                            is_public: true,
+                        }
+                        .mock(),
+                        SymbolDeclaration {
+                            id: "Bar",
+                            symbol: Symbol::Here(SymbolDefinition::Struct(
+                                StructDefinition {
+                                    generics: vec![],
+                                    fields: vec![StructDefinitionField {
+                                        id: "foo",
+                                        ty: UnresolvedType::User("Foo".into(), None).mock(),
+                                    }
+                                    .mock()],
+                                }
+                                .mock(),
+                            )),
+                            is_public: true,
+                        }
+                        .mock(),
+                    ],
+                };
 
-        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
-        assert_eq!(
-            checker.check_function("bar", bar, &*MODULE_ID, &state),
-            Err(vec![ErrorInner {
-                pos: Some((Position::mock(), Position::mock())),
+                let mut state = State::<Bn128Field>::new(
+                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                    (*MODULE_ID).clone(),
+                );
 
-                message:
-                    "Function definition for function foo with signature () -> field not found."
-                        .into()
-            }])
-        );
-    }
+                assert!(Checker::default()
+                    .check_module(&*MODULE_ID, &mut state)
+                    .is_ok());
+                assert_eq!(
+                    state
+                        .types
+                        .get(&*MODULE_ID)
+                        .unwrap()
+                        .get(&"Bar".to_string())
+                        .map(|ty| &ty.ty)
+                        .unwrap(),
+                    &DeclarationType::Struct(DeclarationStructType::new(
+                        (*MODULE_ID).clone(),
+                        "Bar".into(),
+                        vec![],
+                        vec![DeclarationStructMember::new(
+                            "foo".into(),
+                            DeclarationType::Struct(DeclarationStructType::new(
+                                (*MODULE_ID).clone(),
+                                "Foo".into(),
+                                vec![],
+                                vec![DeclarationStructMember::new(
+                                    "foo".into(),
+                                    DeclarationType::FieldElement
+                                )]
+                            ))
+                        )]
+                    ))
+                );
+            }
 
-    #[test]
-    fn undeclared_variable() {
-        // def foo() -> field {
-        //   return 1;
-        // }
-        // def main() {
-        //   a = foo();
-        //   return;
-        // }
-        // should fail
+            #[test]
+            fn recursive_undefined() {
+                // a struct wrapping an undefined struct should be rejected
 
-        let foo_statements: Vec<StatementNode> =
-            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
+                // struct Bar = { foo: Foo; }
 
-        let foo = Function {
-            arguments: vec![],
-            statements: foo_statements,
-            signature: UnresolvedSignature::new()
-                .inputs(vec![])
-                .output(UnresolvedType::FieldElement.mock()),
-        }
-        .mock();
+                let module: Module = Module {
+                    symbols: vec![SymbolDeclaration {
+                        id: "Bar",
+                        symbol: Symbol::Here(SymbolDefinition::Struct(
+                            StructDefinition {
+                                generics: vec![],
+                                fields: vec![StructDefinitionField {
+                                    id: "foo",
+                                    ty: UnresolvedType::User("Foo".into(), None).mock(),
+                                }
+                                .mock()],
+                            }
+                            .mock(),
+                        )),
+                        is_public: true,
+                    }
+                    .mock()],
+                };
 
-        let main_statements: Vec<StatementNode> = vec![
-            Statement::Assignment(
-                Assignee::Identifier("a").mock(),
-                Expression::FunctionCall(box Expression::Identifier("foo").mock(), None, vec![])
-                    .mock(),
-            )
-            .mock(),
-            Statement::Return(None).mock(),
-        ];
+                let mut state = State::<Bn128Field>::new(
+                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                    (*MODULE_ID).clone(),
+                );
 
-        let main = Function {
-            arguments: vec![],
-            statements: main_statements,
-            signature: UnresolvedSignature::new()
-                .inputs(vec![])
-                .output(UnresolvedType::Tuple(vec![]).mock()),
-        }
-        .mock();
+                assert!(Checker::default()
+                    .check_module(&*MODULE_ID, &mut state)
+                    .is_err());
+            }
 
-        let module = Module {
-            symbols: vec![
-                SymbolDeclaration {
-                    id: "foo",
-                    symbol: Symbol::Here(SymbolDefinition::Function(foo)),
-                }
-                .mock(),
-                SymbolDeclaration {
-                    id: "main",
-                    symbol: Symbol::Here(SymbolDefinition::Function(main)),
-                }
-                .mock(),
-            ],
-        };
+            #[test]
+            fn self_referential() {
+                // a struct wrapping itself should be rejected
 
-        let mut state = State::<Bn128Field>::new(
-            vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-            (*MODULE_ID).clone(),
-        );
+                // struct Foo = { foo: Foo; }
 
-        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
-        assert_eq!(
-            checker.check_module(&*MODULE_ID, &mut state),
-            Err(vec![Error {
-                inner: ErrorInner {
-                    pos: Some((Position::mock(), Position::mock())),
-                    message: "Variable `a` is undeclared".into()
-                },
-                module_id: (*MODULE_ID).clone()
-            }])
-        );
-    }
+                let module: Module = Module {
+                    symbols: vec![SymbolDeclaration {
+                        id: "Foo",
+                        symbol: Symbol::Here(SymbolDefinition::Struct(
+                            StructDefinition {
+                                generics: vec![],
+                                fields: vec![StructDefinitionField {
+                                    id: "foo",
+                                    ty: UnresolvedType::User("Foo".into(), None).mock(),
+                                }
+                                .mock()],
+                            }
+                            .mock(),
+                        )),
+                        is_public: true,
+                    }
+                    .mock()],
+                };
 
-    #[test]
-    fn assign_to_select() {
-        // def foo() -> field {
-        //   return 1;
-        // }
-        // def main() {
-        //   field[1] mut a = [0];
-        //   a[0] = foo();
-        //   return;
-        // }
-        // should succeed
+                let mut state = State::<Bn128Field>::new(
+                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                    (*MODULE_ID).clone(),
+                );
 
-        let foo_statements: Vec<StatementNode> =
-            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
+                assert!(Checker::default()
+                    .check_module(&*MODULE_ID, &mut state)
+                    .is_err());
+            }
+
+            #[test]
+            fn cyclic() {
+                // A wrapping B wrapping A should be rejected
 
-        let foo = Function {
-            arguments: vec![],
-            statements: foo_statements,
-            signature: UnresolvedSignature::new()
-                .inputs(vec![])
-                .output(UnresolvedType::FieldElement.mock()),
-        }
-        .mock();
+                // struct Foo = { bar: Bar; }
+                // struct Bar = { foo: Foo; }
 
-        let main_statements: Vec<StatementNode> = vec![
-            Statement::Definition(
-                untyped::Variable::mutable(
-                    "a",
-                    UnresolvedType::array(
-                        UnresolvedType::FieldElement.mock(),
-                        Expression::IntConstant(1usize.into()).mock(),
-                    )
-                    .mock(),
-                )
-                .mock(),
-                Expression::InlineArray(vec![untyped::SpreadOrExpression::Expression(
-                    Expression::IntConstant(0usize.into()).mock(),
-                )])
-                .mock(),
-            )
-            .mock(),
-            Statement::Assignment(
-                Assignee::Select(
-                    box Assignee::Identifier("a").mock(),
-                    box RangeOrExpression::Expression(
-                        untyped::Expression::IntConstant(0usize.into()).mock(),
-                    ),
-                )
-                .mock(),
-                Expression::FunctionCall(box Expression::Identifier("foo").mock(), None, vec![])
-                    .mock(),
-            )
-            .mock(),
-            Statement::Return(None).mock(),
-        ];
+                let module: Module = Module {
+                    symbols: vec![
+                        SymbolDeclaration {
+                            id: "Foo",
+                            symbol: Symbol::Here(SymbolDefinition::Struct(
+                                StructDefinition {
+                                    generics: vec![],
+                                    fields: vec![StructDefinitionField {
+                                        id: "bar",
+                                        ty: UnresolvedType::User("Bar".into(), None).mock(),
+                                    }
+                                    .mock()],
+                                }
+                                .mock(),
+                            )),
+                            is_public: true,
+                        }
+                        .mock(),
+                        SymbolDeclaration {
+                            id: "Bar",
+                            symbol: Symbol::Here(SymbolDefinition::Struct(
+                                StructDefinition {
+                                    generics: vec![],
+                                    fields: vec![StructDefinitionField {
+                                        id: "foo",
+                                        ty: UnresolvedType::User("Foo".into(), None).mock(),
+                                    }
+                                    .mock()],
+                                }
+                                .mock(),
+                            )),
+                            is_public: true,
+                        }
+                        .mock(),
+                    ],
+                };
 
-        let main = Function {
-            arguments: vec![],
-            statements: main_statements,
-            signature: UnresolvedSignature::new()
-                .inputs(vec![])
-                .output(UnresolvedType::Tuple(vec![]).mock()),
+                let mut state = State::<Bn128Field>::new(
+                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
+                    (*MODULE_ID).clone(),
+                );
+
+                assert!(Checker::default()
+                    .check_module(&*MODULE_ID, &mut state)
+                    .is_err());
+            }
         }
-        .mock();
 
-        let module = Module {
-            symbols: vec![
-                SymbolDeclaration {
-                    id: "foo",
-                    symbol: Symbol::Here(SymbolDefinition::Function(foo)),
-                }
-                .mock(),
-                SymbolDeclaration {
-                    id: "main",
-                    symbol: Symbol::Here(SymbolDefinition::Function(main)),
-                }
-                .mock(),
-            ],
-        };
+        /// tests about using the defined type identifier
+        mod usage {
+            use super::*;
 
-        let mut state = State::<Bn128Field>::new(
-            vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-            (*MODULE_ID).clone(),
-        );
+            #[test]
+            fn ty() {
+                // a defined type can be checked
+                // Foo { foo: field; }
+                // Foo
 
-        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
-        assert!(checker.check_module(&*MODULE_ID, &mut state).is_ok());
-    }
+                // an undefined type cannot be checked
+                // Bar
 
-    #[test]
-    fn function_undefined() {
-        // def bar() {
-        //   assert(1 == foo());
-        //   return;
-        // }
-        // should fail
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
+                    generics: vec![],
+                    fields: vec![StructDefinitionField {
+                        id: "foo",
+                        ty: UnresolvedType::FieldElement.mock(),
+                    }
+                    .mock()],
+                });
 
-        let bar_statements: Vec<StatementNode> = vec![
-            Statement::Assertion(
-                Expression::Eq(
-                    box Expression::IntConstant(1usize.into()).mock(),
-                    box Expression::FunctionCall(
-                        box Expression::Identifier("foo").mock(),
-                        None,
+                assert_eq!(
+                    checker.check_type(
+                        UnresolvedType::User("Foo".into(), None).mock(),
+                        &*MODULE_ID,
+                        &state.types
+                    ),
+                    Ok(Type::Struct(StructType::new(
+                        "".into(),
+                        "Foo".into(),
                         vec![],
-                    )
-                    .mock(),
-                )
-                .mock(),
-                None,
-            )
-            .mock(),
-            Statement::Return(None).mock(),
-        ];
+                        vec![StructMember::new("foo".into(), Type::FieldElement)]
+                    )))
+                );
 
-        let bar = Function {
-            arguments: vec![],
-            statements: bar_statements,
-            signature: UnresolvedSignature::new(),
+                assert_eq!(
+                    checker
+                        .check_type(
+                            UnresolvedType::User("Bar".into(), None).mock(),
+                            &*MODULE_ID,
+                            &state.types
+                        )
+                        .unwrap_err()
+                        .message,
+                    "Undefined type Bar"
+                );
+            }
         }
-        .mock();
-
-        let modules = Modules::new();
-        let state = State::new(modules, (*MODULE_ID).clone());
 
-        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
-        assert_eq!(
-            checker.check_function("bar", bar, &*MODULE_ID, &state),
-            Err(vec![ErrorInner {
-                pos: Some((Position::mock(), Position::mock())),
+        /// tests about accessing members
+        mod member {
+            use super::*;
 
-                message: "Function definition for function foo with signature () -> _ not found."
-                    .into()
-            }])
-        );
-    }
+            #[test]
+            fn valid() {
+                // accessing a member on a struct should succeed and return the right type
 
-    #[test]
-    fn return_undefined() {
-        // def bar() {
-        //   return a;
-        // }
-        // should fail
-        let bar_statements: Vec<StatementNode> =
-            vec![Statement::Return(Some(Expression::Identifier("a").mock())).mock()];
+                // struct Foo = { foo: field; }
+                // Foo { foo: 42 }.foo
 
-        let bar = Function {
-            arguments: vec![],
-            statements: bar_statements,
-            signature: UnresolvedSignature::new()
-                .inputs(vec![])
-                .output(UnresolvedType::FieldElement.mock()),
-        }
-        .mock();
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
+                    generics: vec![],
+                    fields: vec![StructDefinitionField {
+                        id: "foo",
+                        ty: UnresolvedType::FieldElement.mock(),
+                    }
+                    .mock()],
+                });
 
-        let modules = Modules::new();
-        let state = State::new(modules, (*MODULE_ID).clone());
+                assert_eq!(
+                    checker.check_expression(
+                        Expression::Member(
+                            box Expression::InlineStruct(
+                                "Foo".into(),
+                                vec![("foo", Expression::IntConstant(42usize.into()).mock())]
+                            )
+                            .mock(),
+                            "foo".into()
+                        )
+                        .mock(),
+                        &*MODULE_ID,
+                        &state.types
+                    ),
+                    Ok(FieldElementExpression::member(
+                        StructExpressionInner::Value(vec![FieldElementExpression::Number(
+                            Bn128Field::from(42u32)
+                        )
+                        .into()])
+                        .annotate(StructType::new(
+                            "".into(),
+                            "Foo".into(),
+                            vec![],
+                            vec![StructMember::new("foo".into(), Type::FieldElement)]
+                        )),
+                        "foo".into()
+                    )
+                    .into())
+                );
+            }
 
-        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
-        assert_eq!(
-            checker.check_function("bar", bar, &*MODULE_ID, &state),
-            Err(vec![ErrorInner {
-                pos: Some((Position::mock(), Position::mock())),
-                message: "Identifier \"a\" is undefined".into()
-            }])
-        );
-    }
+            #[test]
+            fn invalid() {
+                // accessing an undefined member on a struct should fail
 
-    #[test]
-    fn duplicate_argument_name() {
-        // def main(field a, bool a) {
-        //   return;
-        // }
-        //
-        // should fail
+                // struct Foo = { foo: field; }
+                // Foo { foo: 42 }.bar
 
-        let mut f = function0();
-        f.value.arguments = vec![
-            untyped::Parameter::private(
-                untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-            )
-            .mock(),
-            untyped::Parameter::private(
-                untyped::Variable::immutable("a", UnresolvedType::Boolean.mock()).mock(),
-            )
-            .mock(),
-        ];
-        f.value.signature = UnresolvedSignature::new().inputs(vec![
-            UnresolvedType::FieldElement.mock(),
-            UnresolvedType::Boolean.mock(),
-        ]);
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
+                    generics: vec![],
+                    fields: vec![StructDefinitionField {
+                        id: "foo",
+                        ty: UnresolvedType::FieldElement.mock(),
+                    }
+                    .mock()],
+                });
 
-        let modules = Modules::new();
-        let state = State::new(modules, (*MODULE_ID).clone());
+                assert_eq!(
+                    checker
+                        .check_expression(
+                            Expression::Member(
+                                box Expression::InlineStruct(
+                                    "Foo".into(),
+                                    vec![("foo", Expression::IntConstant(42usize.into()).mock())]
+                                )
+                                .mock(),
+                                "bar".into()
+                            )
+                            .mock(),
+                            &*MODULE_ID,
+                            &state.types
+                        )
+                        .unwrap_err()
+                        .message,
+                    "Foo {foo: field} doesn\'t have member bar"
+                );
+            }
+        }
 
-        let mut checker: Checker<Bn128Field> = new_with_args(Scope::default(), HashSet::new());
-        assert_eq!(
-            checker
-                .check_function("main", f, &*MODULE_ID, &state)
-                .unwrap_err()[0]
-                .message,
-            "Duplicate name in function definition: `a` was previously declared as an argument, a generic parameter or a constant"
-        );
-    }
+        /// tests about defining struct instance inline
+        mod value {
+            use super::*;
 
-    #[test]
-    fn duplicate_main_function() {
-        // def main(field a) -> field {
-        //   return 1;
-        // }
-        // def main() -> field {
-        //   return 1;
-        // }
-        //
-        // should fail
-        let main1_statements: Vec<StatementNode> =
-            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
+            #[test]
+            fn wrong_name() {
+                // a A value cannot be defined with B as id, even if A and B have the same members
 
-        let main1_arguments = vec![zokrates_ast::untyped::Parameter::public(
-            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-        )
-        .mock()];
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
+                    generics: vec![],
+                    fields: vec![StructDefinitionField {
+                        id: "foo",
+                        ty: UnresolvedType::FieldElement.mock(),
+                    }
+                    .mock()],
+                });
 
-        let main2_statements: Vec<StatementNode> =
-            vec![Statement::Return(Some(Expression::IntConstant(1usize.into()).mock())).mock()];
+                assert_eq!(
+                    checker
+                        .check_expression(
+                            Expression::InlineStruct(
+                                "Bar".into(),
+                                vec![("foo", Expression::IntConstant(42usize.into()).mock())]
+                            )
+                            .mock(),
+                            &*MODULE_ID,
+                            &state.types
+                        )
+                        .unwrap_err()
+                        .message,
+                    "Undefined type `Bar`"
+                );
+            }
 
-        let main2_arguments = vec![];
+            #[test]
+            fn valid() {
+                // a A value can be defined with members ordered as in the declaration of A
 
-        let main1 = Function {
-            arguments: main1_arguments,
-            statements: main1_statements,
-            signature: UnresolvedSignature::new()
-                .inputs(vec![UnresolvedType::FieldElement.mock()])
-                .output(UnresolvedType::FieldElement.mock()),
-        }
-        .mock();
+                // struct Foo { foo: field; bar: bool; }
+                // Foo foo = Foo { foo: 42, bar: true };
 
-        let main2 = Function {
-            arguments: main2_arguments,
-            statements: main2_statements,
-            signature: UnresolvedSignature::new()
-                .inputs(vec![])
-                .output(UnresolvedType::FieldElement.mock()),
-        }
-        .mock();
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
+                    generics: vec![],
+                    fields: vec![
+                        StructDefinitionField {
+                            id: "foo",
+                            ty: UnresolvedType::FieldElement.mock(),
+                        }
+                        .mock(),
+                        StructDefinitionField {
+                            id: "bar",
+                            ty: UnresolvedType::Boolean.mock(),
+                        }
+                        .mock(),
+                    ],
+                });
 
-        let symbols = vec![
-            SymbolDeclaration {
-                id: "main",
-                symbol: Symbol::Here(SymbolDefinition::Function(main1)),
-            }
-            .mock(),
-            SymbolDeclaration {
-                id: "main",
-                symbol: Symbol::Here(SymbolDefinition::Function(main2)),
+                assert_eq!(
+                    checker.check_expression(
+                        Expression::InlineStruct(
+                            "Foo".into(),
+                            vec![
+                                ("foo", Expression::IntConstant(42usize.into()).mock()),
+                                ("bar", Expression::BooleanConstant(true).mock())
+                            ]
+                        )
+                        .mock(),
+                        &*MODULE_ID,
+                        &state.types
+                    ),
+                    Ok(StructExpressionInner::Value(vec![
+                        FieldElementExpression::Number(Bn128Field::from(42u32)).into(),
+                        BooleanExpression::Value(true).into()
+                    ])
+                    .annotate(StructType::new(
+                        "".into(),
+                        "Foo".into(),
+                        vec![],
+                        vec![
+                            StructMember::new("foo".into(), Type::FieldElement),
+                            StructMember::new("bar".into(), Type::Boolean)
+                        ]
+                    ))
+                    .into())
+                );
             }
-            .mock(),
-        ];
 
-        let main_module = Module { symbols };
+            #[test]
+            fn shuffled() {
+                // a A value can be defined with shuffled members compared to the declaration of A
 
-        let program = Program {
-            modules: vec![((*MODULE_ID).clone(), main_module)]
-                .into_iter()
-                .collect(),
-            main: (*MODULE_ID).clone(),
-        };
+                // struct Foo { foo: field; bar: bool; }
+                // Foo foo = Foo { bar: true, foo: 42 };
 
-        let mut checker: Checker<Bn128Field> = Checker::default();
-        assert_eq!(
-            checker.check_program(program),
-            Err(vec![Error {
-                inner: ErrorInner {
-                    pos: None,
-                    message: "Only one main function allowed, found 2".into()
-                },
-                module_id: (*MODULE_ID).clone()
-            }])
-        );
-    }
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
+                    generics: vec![],
+                    fields: vec![
+                        StructDefinitionField {
+                            id: "foo",
+                            ty: UnresolvedType::FieldElement.mock(),
+                        }
+                        .mock(),
+                        StructDefinitionField {
+                            id: "bar",
+                            ty: UnresolvedType::Boolean.mock(),
+                        }
+                        .mock(),
+                    ],
+                });
+
+                assert_eq!(
+                    checker.check_expression(
+                        Expression::InlineStruct(
+                            "Foo".into(),
+                            vec![
+                                ("bar", Expression::BooleanConstant(true).mock()),
+                                ("foo", Expression::IntConstant(42usize.into()).mock())
+                            ]
+                        )
+                        .mock(),
+                        &*MODULE_ID,
+                        &state.types
+                    ),
+                    Ok(StructExpressionInner::Value(vec![
+                        FieldElementExpression::Number(Bn128Field::from(42u32)).into(),
+                        BooleanExpression::Value(true).into()
+                    ])
+                    .annotate(StructType::new(
+                        "".into(),
+                        "Foo".into(),
+                        vec![],
+                        vec![
+                            StructMember::new("foo".into(), Type::FieldElement),
+                            StructMember::new("bar".into(), Type::Boolean)
+                        ]
+                    ))
+                    .into())
+                );
+            }
 
-    mod shadowing {
+            #[test]
+            fn subset() {
+                // a A value cannot be defined with A as id if members are a subset of the declaration
 
-        use super::*;
+                // struct Foo { foo: field; bar: bool; }
+                // Foo foo = Foo { foo: 42 };
 
-        #[test]
-        fn same_type() {
-            // field a = 2;
-            // field a = 2;
-            //
-            // should succeed
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
+                    generics: vec![],
+                    fields: vec![
+                        StructDefinitionField {
+                            id: "foo",
+                            ty: UnresolvedType::FieldElement.mock(),
+                        }
+                        .mock(),
+                        StructDefinitionField {
+                            id: "bar",
+                            ty: UnresolvedType::Boolean.mock(),
+                        }
+                        .mock(),
+                    ],
+                });
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            checker.enter_scope();
-            let _: Result<TypedStatement<Bn128Field>, Vec<ErrorInner>> = checker.check_statement(
-                Statement::Definition(
-                    untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-                    untyped::Expression::IntConstant(2usize.into()).mock(),
-                )
-                .mock(),
-                &*MODULE_ID,
-                &TypeMap::new(),
-            );
-            let s2_checked: Result<TypedStatement<Bn128Field>, Vec<ErrorInner>> = checker
-                .check_statement(
-                    Statement::Definition(
-                        untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock())
+                assert_eq!(
+                    checker
+                        .check_expression(
+                            Expression::InlineStruct(
+                                "Foo".into(),
+                                vec![("foo", Expression::IntConstant(42usize.into()).mock())]
+                            )
                             .mock(),
-                        untyped::Expression::IntConstant(2usize.into()).mock(),
-                    )
-                    .mock(),
-                    &*MODULE_ID,
-                    &TypeMap::new(),
-                );
-            assert!(s2_checked.is_ok());
-        }
-
-        #[test]
-        fn different_type() {
-            // field a = 2;
-            // bool a = true;
-            //
-            // should succeed
-
-            let mut checker: Checker<Bn128Field> = Checker::default();
-            checker.enter_scope();
-            let _: Result<TypedStatement<Bn128Field>, Vec<ErrorInner>> = checker.check_statement(
-                Statement::Definition(
-                    untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
-                    untyped::Expression::IntConstant(2usize.into()).mock(),
-                )
-                .mock(),
-                &*MODULE_ID,
-                &TypeMap::new(),
-            );
-            let s2_checked: Result<TypedStatement<Bn128Field>, Vec<ErrorInner>> = checker
-                .check_statement(
-                    Statement::Definition(
-                        untyped::Variable::immutable("a", UnresolvedType::Boolean.mock()).mock(),
-                        untyped::Expression::BooleanConstant(true).mock(),
-                    )
-                    .mock(),
-                    &*MODULE_ID,
-                    &TypeMap::new(),
+                            &*MODULE_ID,
+                            &state.types
+                        )
+                        .unwrap_err()
+                        .message,
+                    "Inline struct Foo {foo: 42} does not match Foo {foo: field, bar: bool}"
                 );
-            assert!(s2_checked.is_ok());
-            assert_eq!(checker.scope.get("a").unwrap().ty, DeclarationType::Boolean);
-        }
+            }
 
-        #[test]
-        fn scopes() {
-            // field mut a = 2;
-            // for uint i in 0..0 {
-            //    a = 3
-            //    field a = 4
-            // }
-            // a = 5
-            //
-            // should be turned into
-            //
-            // field mut a_0 = 2;
-            // for uint i_1 in 0..0 {
-            //    a_0 = 3
-            //    field a_1 = 4
-            // }
-            // a_0 = 5
+            #[test]
+            fn invalid() {
+                // a A value cannot be defined with A as id if members are different ids than the declaration
+                // a A value cannot be defined with A as id if members are different types than the declaration
 
-            let mut checker: Checker<Bn128Field> = Checker::default();
+                // struct Foo { foo: field; bar: bool; }
+                // Foo { foo: 42, baz: bool } // error
+                // Foo { foo: 42, baz: 42 } // error
 
-            let statements = vec![
-                Statement::Definition(
-                    untyped::Variable::mutable("a", UnresolvedType::FieldElement.mock()).mock(),
-                    untyped::Expression::IntConstant(2usize.into()).mock(),
-                )
-                .mock(),
-                Statement::For(
-                    untyped::Variable::immutable("i", UnresolvedType::Uint(32).mock()).mock(),
-                    untyped::Expression::U32Constant(0).mock(),
-                    untyped::Expression::U32Constant(0).mock(),
-                    vec![
-                        Statement::Assignment(
-                            untyped::Assignee::Identifier("a").mock(),
-                            untyped::Expression::IntConstant(3usize.into()).mock(),
-                        )
+                let (mut checker, state) = create_module_with_foo(StructDefinition {
+                    generics: vec![],
+                    fields: vec![
+                        StructDefinitionField {
+                            id: "foo",
+                            ty: UnresolvedType::FieldElement.mock(),
+                        }
                         .mock(),
-                        Statement::Definition(
-                            untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock())
-                                .mock(),
-                            untyped::Expression::IntConstant(4usize.into()).mock(),
-                        )
+                        StructDefinitionField {
+                            id: "bar",
+                            ty: UnresolvedType::Boolean.mock(),
+                        }
                         .mock(),
                     ],
-                )
-                .mock(),
-                Statement::Assignment(
-                    untyped::Assignee::Identifier("a").mock(),
-                    untyped::Expression::IntConstant(5usize.into()).mock(),
-                )
-                .mock(),
-            ];
+                });
 
-            let expected = vec![
-                TypedStatement::definition(
-                    typed::Variable::new(
-                        CoreIdentifier::from(ShadowedIdentifier::shadow("a".into(), 0)),
-                        Type::FieldElement,
-                        true,
-                    )
-                    .into(),
-                    FieldElementExpression::Number(2u32.into()).into(),
-                ),
-                TypedStatement::For(
-                    typed::Variable::new(
-                        CoreIdentifier::from(ShadowedIdentifier::shadow("i".into(), 1)),
-                        Type::Uint(UBitwidth::B32),
-                        false,
-                    ),
-                    0u32.into(),
-                    0u32.into(),
-                    vec![
-                        TypedStatement::definition(
-                            typed::Variable::new(
-                                CoreIdentifier::from(ShadowedIdentifier::shadow("a".into(), 0)),
-                                Type::FieldElement,
-                                true,
-                            )
-                            .into(),
-                            FieldElementExpression::Number(3u32.into()).into(),
-                        ),
-                        TypedStatement::definition(
-                            typed::Variable::new(
-                                CoreIdentifier::from(ShadowedIdentifier::shadow("a".into(), 1)),
-                                Type::FieldElement,
-                                false,
+                assert_eq!(
+                    checker
+                        .check_expression(
+                            Expression::InlineStruct(
+                                "Foo".into(),
+                                vec![(
+                                    "baz",
+                                    Expression::BooleanConstant(true).mock()
+                                ),(
+                                    "foo",
+                                    Expression::IntConstant(42usize.into()).mock()
+                                )]
                             )
-                            .into(),
-                            FieldElementExpression::Number(4u32.into()).into(),
-                        ),
-                    ],
-                ),
-                TypedStatement::definition(
-                    typed::Variable::new(
-                        CoreIdentifier::from(ShadowedIdentifier::shadow("a".into(), 0)),
-                        Type::FieldElement,
-                        true,
-                    )
-                    .into(),
-                    FieldElementExpression::Number(5u32.into()).into(),
-                ),
-            ];
+                            .mock(),
+                            &*MODULE_ID,
+                            &state.types
+                        ).unwrap_err()
+                        .message,
+                    "Member bar of struct Foo {foo: field, bar: bool} not found in value Foo {baz: true, foo: 42}"
+                );
 
-            checker.enter_scope();
-            let checked: Vec<_> = statements
-                .into_iter()
-                .map(|s| {
+                assert_eq!(
                     checker
-                        .check_statement(s, &*MODULE_ID, &TypeMap::default())
-                        .unwrap()
-                })
-                .collect();
-
-            assert_eq!(checked, expected);
+                        .check_expression(
+                            Expression::InlineStruct(
+                                "Foo".into(),
+                                vec![
+                                    ("bar", Expression::IntConstant(42usize.into()).mock()),
+                                    ("foo", Expression::IntConstant(42usize.into()).mock())
+                                ]
+                            )
+                            .mock(),
+                            &*MODULE_ID,
+                            &state.types
+                        )
+                        .unwrap_err()
+                        .message,
+                    "Member bar of struct Foo has type bool, found 42 of type {integer}"
+                );
+            }
         }
     }
 
-    mod structs {
+    mod enums {
         use super::*;
-        use zokrates_ast::typed::types::StructMember;
+        use zokrates_ast::typed::types::{self, EnumType};
 
-        /// solver function to create a module at location "" with a single symbol `Foo { foo: field }`
+        /// solver function to create a module at location "" with a single enum `Foo`
         fn create_module_with_foo(
-            s: StructDefinition<'static>,
+            e: EnumDefinition<'static>,
         ) -> (Checker<Bn128Field>, State<Bn128Field>) {
             let module: Module = Module {
                 symbols: vec![SymbolDeclaration {
                     id: "Foo",
-                    symbol: Symbol::Here(SymbolDefinition::Struct(s.mock())),
+                    symbol: Symbol::Here(SymbolDefinition::Enum(e.mock())),
+                    is_public: true,
                 }
                 .mock()],
             };
@@ -5546,700 +10665,1346 @@ mod tests {
             (checker, state)
         }
 
-        /// tests about declaring a type
-        mod declaration {
-            use super::*;
+        /// `enum Foo { A(field, bool), B(field, field), C }`
+        fn foo() -> EnumDefinition<'static> {
+            EnumDefinition {
+                variants: vec![
+                    EnumVariant {
+                        id: "A",
+                        types: vec![
+                            UnresolvedType::FieldElement.mock(),
+                            UnresolvedType::Boolean.mock(),
+                        ],
+                    }
+                    .mock(),
+                    EnumVariant {
+                        id: "B",
+                        types: vec![
+                            UnresolvedType::FieldElement.mock(),
+                            UnresolvedType::FieldElement.mock(),
+                        ],
+                    }
+                    .mock(),
+                    EnumVariant {
+                        id: "C",
+                        types: vec![],
+                    }
+                    .mock(),
+                ],
+            }
+        }
 
-            #[test]
-            fn empty_def() {
-                // an empty struct should be allowed to be defined
-                let modules = Modules::new();
-                let state = State::new(modules, (*MODULE_ID).clone());
+        fn arm(pattern: MatchPattern<'static>, e: Expression<'static>) -> MatchArm<'static> {
+            MatchArm {
+                pattern,
+                expression: e.mock(),
+            }
+        }
 
-                let declaration: StructDefinitionNode = StructDefinition {
-                    generics: vec![],
-                    fields: vec![],
-                }
-                .mock();
+        #[test]
+        fn declaration() {
+            let modules = Modules::new();
+            let state = State::new(modules, (*MODULE_ID).clone());
+
+            let expected_type = DeclarationEnumType::new(
+                "".into(),
+                "Foo".into(),
+                vec![
+                    DeclarationEnumVariant::new(
+                        "A".into(),
+                        vec![DeclarationType::FieldElement, DeclarationType::Boolean],
+                    ),
+                    DeclarationEnumVariant::new(
+                        "B".into(),
+                        vec![DeclarationType::FieldElement, DeclarationType::FieldElement],
+                    ),
+                    DeclarationEnumVariant::new("C".into(), vec![]),
+                ],
+            );
+
+            let checked = Checker::<Bn128Field>::default()
+                .check_enum_type_declaration("Foo".into(), foo().mock(), &*MODULE_ID, &state)
+                .unwrap();
+
+            // enum types are compared by location only, so compare the variants as well
+            assert_eq!(checked, expected_type);
+            assert_eq!(checked.variants, expected_type.variants);
+        }
+
+        #[test]
+        fn duplicate_variant() {
+            let modules = Modules::new();
+            let state = State::new(modules, (*MODULE_ID).clone());
+
+            let declaration: EnumDefinitionNode = EnumDefinition {
+                variants: vec![
+                    EnumVariant {
+                        id: "A",
+                        types: vec![],
+                    }
+                    .mock(),
+                    EnumVariant {
+                        id: "A",
+                        types: vec![UnresolvedType::Boolean.mock()],
+                    }
+                    .mock(),
+                ],
+            }
+            .mock();
+
+            assert_eq!(
+                Checker::<Bn128Field>::default()
+                    .check_enum_type_declaration("Foo".into(), declaration, &*MODULE_ID, &state)
+                    .unwrap_err()[0]
+                    .message,
+                "Duplicate variant A in enum definition"
+            );
+        }
+
+        #[test]
+        fn empty() {
+            let modules = Modules::new();
+            let state = State::new(modules, (*MODULE_ID).clone());
+
+            assert_eq!(
+                Checker::<Bn128Field>::default()
+                    .check_enum_type_declaration(
+                        "Foo".into(),
+                        EnumDefinition { variants: vec![] }.mock(),
+                        &*MODULE_ID,
+                        &state
+                    )
+                    .unwrap_err()[0]
+                    .message,
+                "Enum Foo must have at least one variant"
+            );
+        }
+
+        #[test]
+        fn constructor() {
+            // the payload is aligned to the types of the variant
+            let (mut checker, state) = create_module_with_foo(foo());
+
+            assert_eq!(
+                checker.check_expression(
+                    Expression::EnumVariant(
+                        "Foo".into(),
+                        "B",
+                        vec![
+                            Expression::IntConstant(1usize.into()).mock(),
+                            Expression::FieldConstant(2usize.into()).mock()
+                        ]
+                    )
+                    .mock(),
+                    &*MODULE_ID,
+                    &state.types
+                ),
+                Ok(EnumExpressionInner::Value(
+                    1,
+                    vec![
+                        FieldElementExpression::Number(Bn128Field::from(1u32)).into(),
+                        FieldElementExpression::Number(Bn128Field::from(2u32)).into(),
+                    ]
+                )
+                .annotate(EnumType::new(
+                    "".into(),
+                    "Foo".into(),
+                    vec![
+                        types::EnumVariant::new(
+                            "A".into(),
+                            vec![Type::FieldElement, Type::Boolean]
+                        ),
+                        types::EnumVariant::new(
+                            "B".into(),
+                            vec![Type::FieldElement, Type::FieldElement]
+                        ),
+                        types::EnumVariant::new("C".into(), vec![]),
+                    ]
+                ))
+                .into())
+            );
+        }
+
+        #[test]
+        fn constructor_wrong_arity() {
+            let (mut checker, state) = create_module_with_foo(foo());
+
+            assert_eq!(
+                checker
+                    .check_expression(
+                        Expression::EnumVariant(
+                            "Foo".into(),
+                            "A",
+                            vec![Expression::IntConstant(42usize.into()).mock()]
+                        )
+                        .mock(),
+                        &*MODULE_ID,
+                        &state.types
+                    )
+                    .unwrap_err()
+                    .message,
+                "Variant Foo::A expects 2 argument(s), found 1"
+            );
+        }
+
+        #[test]
+        fn match_with_bindings() {
+            // match Foo::B(1, 2) { Foo::A(x, _b) => x, Foo::B(x, y) => x + y, Foo::C => 0 }
+            let (mut checker, state) = create_module_with_foo(foo());
+
+            let e = Expression::Match(
+                box Expression::EnumVariant(
+                    "Foo".into(),
+                    "B",
+                    vec![
+                        Expression::IntConstant(1usize.into()).mock(),
+                        Expression::IntConstant(2usize.into()).mock(),
+                    ],
+                )
+                .mock(),
+                vec![
+                    arm(
+                        MatchPattern::Variant("Foo".into(), "A", vec!["x", "b"]),
+                        Expression::Identifier("x"),
+                    ),
+                    arm(
+                        MatchPattern::Variant("Foo".into(), "B", vec!["x", "y"]),
+                        Expression::Add(
+                            box Expression::Identifier("x").mock(),
+                            box Expression::Identifier("y").mock(),
+                        ),
+                    ),
+                    arm(
+                        MatchPattern::Variant("Foo".into(), "C", vec![]),
+                        Expression::FieldConstant(0usize.into()),
+                    ),
+                ],
+            )
+            .mock();
+
+            assert_eq!(
+                checker
+                    .check_expression(e, &*MODULE_ID, &state.types)
+                    .unwrap()
+                    .get_type(),
+                Type::FieldElement
+            );
+
+            // bindings do not leak out of the arms
+            assert!(checker.match_bindings.is_empty());
+        }
+
+        #[test]
+        fn non_exhaustive_match() {
+            let (mut checker, state) = create_module_with_foo(foo());
+
+            let e = Expression::Match(
+                box Expression::EnumVariant("Foo".into(), "C", vec![]).mock(),
+                vec![arm(
+                    MatchPattern::Variant("Foo".into(), "C", vec![]),
+                    Expression::BooleanConstant(true),
+                )],
+            )
+            .mock();
+
+            assert_eq!(
+                checker
+                    .check_expression(e, &*MODULE_ID, &state.types)
+                    .unwrap_err()
+                    .message,
+                "Non-exhaustive match on Foo, missing variant(s) Foo::A, Foo::B"
+            );
+        }
+
+        #[test]
+        fn unreachable_arm() {
+            let (mut checker, state) = create_module_with_foo(foo());
+
+            let e = Expression::Match(
+                box Expression::EnumVariant("Foo".into(), "C", vec![]).mock(),
+                vec![
+                    arm(MatchPattern::Wildcard, Expression::BooleanConstant(true)),
+                    arm(
+                        MatchPattern::Variant("Foo".into(), "C", vec![]),
+                        Expression::BooleanConstant(false),
+                    ),
+                ],
+            )
+            .mock();
+
+            assert_eq!(
+                checker
+                    .check_expression(e, &*MODULE_ID, &state.types)
+                    .unwrap_err()
+                    .message,
+                "Unreachable match arm Foo::C"
+            );
+        }
+
+        #[test]
+        fn wrong_binding_count() {
+            let (mut checker, state) = create_module_with_foo(foo());
+
+            let e = Expression::Match(
+                box Expression::EnumVariant("Foo".into(), "C", vec![]).mock(),
+                vec![
+                    arm(
+                        MatchPattern::Variant("Foo".into(), "A", vec!["x"]),
+                        Expression::BooleanConstant(true),
+                    ),
+                    arm(MatchPattern::Wildcard, Expression::BooleanConstant(false)),
+                ],
+            )
+            .mock();
+
+            assert_eq!(
+                checker
+                    .check_expression(e, &*MODULE_ID, &state.types)
+                    .unwrap_err()
+                    .message,
+                "Pattern Foo::A(x) expects 2 binding(s), found 1"
+            );
+        }
+    }
+
+    mod methods {
+        use super::*;
+
+        /// `struct Foo { field foo; }`
+        fn foo() -> SymbolDeclarationNode<'static> {
+            SymbolDeclaration {
+                id: "Foo",
+                symbol: Symbol::Here(SymbolDefinition::Struct(
+                    StructDefinition {
+                        generics: vec![],
+                        fields: vec![StructDefinitionField {
+                            id: "foo",
+                            ty: UnresolvedType::FieldElement.mock(),
+                        }
+                        .mock()],
+                    }
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
+
+        /// `impl <ty> { def get(self) -> field { return self.foo; } }`
+        fn impl_get(ty: UnresolvedType<'static>) -> SymbolDeclarationNode<'static> {
+            let get = Function {
+                arguments: vec![untyped::Parameter::new(
+                    untyped::Variable::immutable(
+                        "self",
+                        UnresolvedType::User("Self".into(), None).mock(),
+                    )
+                    .mock(),
+                    None,
+                )
+                .mock()],
+                statements: vec![Statement::Return(Some(
+                    Expression::Member(box Expression::Identifier("self").mock(), "foo".into())
+                        .mock(),
+                ))
+                .mock()],
+                signature: UnresolvedSignature::new()
+                    .inputs(vec![UnresolvedType::User("Self".into(), None).mock()])
+                    .output(UnresolvedType::FieldElement.mock()),
+            }
+            .mock();
+
+            SymbolDeclaration {
+                id: "",
+                symbol: Symbol::Here(SymbolDefinition::Impl(
+                    ImplDefinition {
+                        trait_id: None,
+                        ty: ty.mock(),
+                        functions: vec![("get", get)],
+                    }
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
+
+        /// `def main(Foo f) -> field { return f.get(); }`
+        fn main() -> SymbolDeclarationNode<'static> {
+            SymbolDeclaration {
+                id: "main",
+                symbol: Symbol::Here(SymbolDefinition::Function(
+                    Function {
+                        arguments: vec![untyped::Parameter::new(
+                            untyped::Variable::immutable(
+                                "f",
+                                UnresolvedType::User("Foo".into(), None).mock(),
+                            )
+                            .mock(),
+                            None,
+                        )
+                        .mock()],
+                        statements: vec![Statement::Return(Some(
+                            Expression::FunctionCall(
+                                box Expression::Member(
+                                    box Expression::Identifier("f").mock(),
+                                    "get".into(),
+                                )
+                                .mock(),
+                                None,
+                                vec![],
+                            )
+                            .mock(),
+                        ))
+                        .mock()],
+                        signature: UnresolvedSignature::new()
+                            .inputs(vec![UnresolvedType::User("Foo".into(), None).mock()])
+                            .output(UnresolvedType::FieldElement.mock()),
+                    }
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
+
+        #[test]
+        fn method_call() {
+            // the method lives in a module of its own, so that it does not clash with a function `get` of the module
+            let state = check(vec![
+                foo(),
+                impl_get(UnresolvedType::User("Foo".into(), None)),
+                main(),
+            ])
+            .unwrap();
+
+            let foo_type = DeclarationType::Struct(DeclarationStructType::new(
+                "".into(),
+                "Foo".into(),
+                vec![],
+                vec![DeclarationStructMember::new(
+                    "foo".into(),
+                    DeclarationType::FieldElement,
+                )],
+            ));
+
+            let expected_key =
+                DeclarationFunctionKey::with_location(MODULE_ID.join("impl Foo"), "get").signature(
+                    DeclarationSignature::new()
+                        .inputs(vec![foo_type])
+                        .output(DeclarationType::FieldElement),
+                );
+
+            assert!(state
+                .typed_modules
+                .get(&MODULE_ID.join("impl Foo"))
+                .unwrap()
+                .functions_iter()
+                .any(|d| d.key == expected_key));
+        }
+
+        #[test]
+        fn undefined_method() {
+            let errors = check(vec![foo(), main()]).unwrap_err();
+
+            assert_eq!(
+                errors[0].inner.message,
+                "Method get not found for type Foo, with signature (Foo) -> field"
+            );
+        }
+
+        #[test]
+        fn duplicate_method() {
+            let errors = check(vec![
+                foo(),
+                impl_get(UnresolvedType::User("Foo".into(), None)),
+                impl_get(UnresolvedType::User("Foo".into(), None)),
+            ])
+            .unwrap_err();
+
+            assert_eq!(
+                errors[0].inner.message,
+                "Function get is already declared for type Foo"
+            );
+        }
 
-                let expected_type =
-                    DeclarationStructType::new("".into(), "Foo".into(), vec![], vec![]);
+        #[test]
+        fn method_on_basic_type() {
+            let errors = check(vec![impl_get(UnresolvedType::FieldElement)]).unwrap_err();
 
-                assert_eq!(
-                    Checker::<Bn128Field>::default().check_struct_type_declaration(
-                        "Foo".into(),
-                        declaration,
-                        &*MODULE_ID,
-                        &state
-                    ),
-                    Ok(expected_type)
-                );
-            }
+            assert_eq!(
+                errors[0].inner.message,
+                "Functions can only be declared on structs defined in the same module, found field"
+            );
+        }
 
-            #[test]
-            fn valid_def() {
-                // a valid struct should be allowed to be defined
-                let modules = Modules::new();
-                let state = State::new(modules, (*MODULE_ID).clone());
+        /// `impl Foo { def <name>(self, Foo other) -> <output> { return <value>; } }`
+        fn impl_operator(
+            name: &'static str,
+            output: UnresolvedType<'static>,
+            value: Expression<'static>,
+        ) -> SymbolDeclarationNode<'static> {
+            let foo_type = || UnresolvedType::User("Foo".into(), None).mock();
+
+            let f = Function {
+                arguments: vec![
+                    untyped::Parameter::new(
+                        untyped::Variable::immutable("self", foo_type()).mock(),
+                        None,
+                    )
+                    .mock(),
+                    untyped::Parameter::new(
+                        untyped::Variable::immutable("other", foo_type()).mock(),
+                        None,
+                    )
+                    .mock(),
+                ],
+                statements: vec![Statement::Return(Some(value.mock())).mock()],
+                signature: UnresolvedSignature::new()
+                    .inputs(vec![foo_type(), foo_type()])
+                    .output(output.mock()),
+            }
+            .mock();
 
-                let declaration: StructDefinitionNode = StructDefinition {
-                    generics: vec![],
-                    fields: vec![
-                        StructDefinitionField {
-                            id: "foo",
-                            ty: UnresolvedType::FieldElement.mock(),
-                        }
-                        .mock(),
-                        StructDefinitionField {
-                            id: "bar",
-                            ty: UnresolvedType::Boolean.mock(),
-                        }
-                        .mock(),
-                    ],
-                }
-                .mock();
+            SymbolDeclaration {
+                id: "",
+                symbol: Symbol::Here(SymbolDefinition::Impl(
+                    ImplDefinition {
+                        trait_id: None,
+                        ty: foo_type(),
+                        functions: vec![(name, f)],
+                    }
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
 
-                let expected_type = DeclarationStructType::new(
-                    "".into(),
-                    "Foo".into(),
-                    vec![],
-                    vec![
-                        DeclarationStructMember::new("foo".into(), DeclarationType::FieldElement),
-                        DeclarationStructMember::new("bar".into(), DeclarationType::Boolean),
-                    ],
-                );
+        /// `def main(Foo f) -> <output> { return <value>; }`
+        fn main_returning(
+            output: UnresolvedType<'static>,
+            value: Expression<'static>,
+        ) -> SymbolDeclarationNode<'static> {
+            SymbolDeclaration {
+                id: "main",
+                symbol: Symbol::Here(SymbolDefinition::Function(
+                    Function {
+                        arguments: vec![untyped::Parameter::new(
+                            untyped::Variable::immutable(
+                                "f",
+                                UnresolvedType::User("Foo".into(), None).mock(),
+                            )
+                            .mock(),
+                            None,
+                        )
+                        .mock()],
+                        statements: vec![Statement::Return(Some(value.mock())).mock()],
+                        signature: UnresolvedSignature::new()
+                            .inputs(vec![UnresolvedType::User("Foo".into(), None).mock()])
+                            .output(output.mock()),
+                    }
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
 
-                assert_eq!(
-                    Checker::<Bn128Field>::default().check_struct_type_declaration(
-                        "Foo".into(),
-                        declaration,
-                        &*MODULE_ID,
-                        &state
+        #[test]
+        fn operator_overload() {
+            // `f + f` calls `Foo::op_add`
+            let state = check(vec![
+                foo(),
+                impl_operator(
+                    "op_add",
+                    UnresolvedType::User("Foo".into(), None),
+                    Expression::Identifier("other"),
+                ),
+                main_returning(
+                    UnresolvedType::User("Foo".into(), None),
+                    Expression::Add(
+                        box Expression::Identifier("f").mock(),
+                        box Expression::Identifier("f").mock(),
                     ),
-                    Ok(expected_type)
-                );
-            }
+                ),
+            ])
+            .unwrap();
 
-            #[test]
-            fn duplicate_member_def() {
-                // definition of a struct with a duplicate member should be rejected
-                let modules = Modules::new();
-                let state = State::new(modules, (*MODULE_ID).clone());
+            let main = state
+                .typed_modules
+                .get(&*MODULE_ID)
+                .unwrap()
+                .functions_iter()
+                .find(|d| d.key.id == "main")
+                .unwrap()
+                .clone();
+
+            match main.symbol {
+                TypedFunctionSymbol::Here(f) => match &f.statements[0] {
+                    TypedStatement::Return(TypedExpression::Struct(e)) => {
+                        assert!(matches!(
+                            e.as_inner(),
+                            StructExpressionInner::FunctionCall(..)
+                        ))
+                    }
+                    s => panic!("expected a struct return, found {}", s),
+                },
+                _ => unreachable!(),
+            }
+        }
 
-                let declaration: StructDefinitionNode = StructDefinition {
-                    generics: vec![],
-                    fields: vec![
-                        StructDefinitionField {
-                            id: "foo",
-                            ty: UnresolvedType::FieldElement.mock(),
-                        }
-                        .mock(),
-                        StructDefinitionField {
-                            id: "foo",
-                            ty: UnresolvedType::Boolean.mock(),
-                        }
-                        .mock(),
-                    ],
-                }
-                .mock();
+        #[test]
+        fn operator_without_overload() {
+            let errors = check(vec![
+                foo(),
+                main_returning(
+                    UnresolvedType::User("Foo".into(), None),
+                    Expression::Add(
+                        box Expression::Identifier("f").mock(),
+                        box Expression::Identifier("f").mock(),
+                    ),
+                ),
+            ])
+            .unwrap_err();
 
-                assert_eq!(
-                    Checker::<Bn128Field>::default()
-                        .check_struct_type_declaration(
-                            "Foo".into(),
-                            declaration,
-                            &*MODULE_ID,
-                            &state
-                        )
-                        .unwrap_err()[0]
-                        .message,
-                    "Duplicate key foo in struct definition"
-                );
-            }
+            assert_eq!(errors[0].inner.message, "Cannot apply `+` to Foo, Foo");
+        }
 
-            #[test]
-            fn recursive() {
-                // a struct wrapping another struct should be allowed to be defined
+        #[test]
+        fn ordinary_method_does_not_overload() {
+            // a method named `add` is not called by `+`
+            let errors = check(vec![
+                foo(),
+                impl_operator(
+                    "add",
+                    UnresolvedType::User("Foo".into(), None),
+                    Expression::Identifier("other"),
+                ),
+                main_returning(
+                    UnresolvedType::User("Foo".into(), None),
+                    Expression::Add(
+                        box Expression::Identifier("f").mock(),
+                        box Expression::Identifier("f").mock(),
+                    ),
+                ),
+            ])
+            .unwrap_err();
 
-                // struct Foo = { foo: field; }
-                // struct Bar = { foo: Foo; }
+            assert_eq!(errors[0].inner.message, "Cannot apply `+` to Foo, Foo");
+        }
 
-                let module: Module = Module {
-                    symbols: vec![
-                        SymbolDeclaration {
-                            id: "Foo",
-                            symbol: Symbol::Here(SymbolDefinition::Struct(
-                                StructDefinition {
-                                    generics: vec![],
-                                    fields: vec![StructDefinitionField {
-                                        id: "foo",
-                                        ty: UnresolvedType::FieldElement.mock(),
-                                    }
-                                    .mock()],
-                                }
-                                .mock(),
-                            )),
-                        }
-                        .mock(),
-                        SymbolDeclaration {
-                            id: "Bar",
-                            symbol: Symbol::Here(SymbolDefinition::Struct(
-                                StructDefinition {
-                                    generics: vec![],
-                                    fields: vec![StructDefinitionField {
-                                        id: "foo",
-                                        ty: UnresolvedType::User("Foo".into(), None).mock(),
-                                    }
-                                    .mock()],
-                                }
-                                .mock(),
-                            )),
-                        }
-                        .mock(),
-                    ],
-                };
+        #[test]
+        fn eq_overload_must_return_bool() {
+            let errors = check(vec![
+                foo(),
+                impl_operator(
+                    "op_eq",
+                    UnresolvedType::FieldElement,
+                    Expression::IntConstant(1usize.into()),
+                ),
+                main_returning(
+                    UnresolvedType::Boolean,
+                    Expression::Eq(
+                        box Expression::Identifier("f").mock(),
+                        box Expression::Identifier("f").mock(),
+                    ),
+                ),
+            ])
+            .unwrap_err();
 
-                let mut state = State::<Bn128Field>::new(
-                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                    (*MODULE_ID).clone(),
-                );
+            assert_eq!(
+                errors[0].inner.message,
+                "Method op_eq used by `==` must return bool, found field"
+            );
+        }
+    }
 
-                assert!(Checker::default()
-                    .check_module(&*MODULE_ID, &mut state)
-                    .is_ok());
-                assert_eq!(
-                    state
-                        .types
-                        .get(&*MODULE_ID)
-                        .unwrap()
-                        .get(&"Bar".to_string())
-                        .map(|ty| &ty.ty)
-                        .unwrap(),
-                    &DeclarationType::Struct(DeclarationStructType::new(
-                        (*MODULE_ID).clone(),
-                        "Bar".into(),
-                        vec![],
-                        vec![DeclarationStructMember::new(
-                            "foo".into(),
-                            DeclarationType::Struct(DeclarationStructType::new(
-                                (*MODULE_ID).clone(),
-                                "Foo".into(),
-                                vec![],
-                                vec![DeclarationStructMember::new(
-                                    "foo".into(),
-                                    DeclarationType::FieldElement
-                                )]
-                            ))
-                        )]
-                    ))
-                );
-            }
+    mod traits {
+        use super::*;
 
-            #[test]
-            fn recursive_undefined() {
-                // a struct wrapping an undefined struct should be rejected
+        fn user(id: &str) -> UnresolvedTypeNode<'static> {
+            UnresolvedType::User(id.into(), None).mock()
+        }
 
-                // struct Bar = { foo: Foo; }
+        fn parameter(ty: UnresolvedTypeNode<'static>) -> untyped::ParameterNode<'static> {
+            untyped::Parameter::new(untyped::Variable::immutable("a", ty).mock(), None).mock()
+        }
 
-                let module: Module = Module {
-                    symbols: vec![SymbolDeclaration {
-                        id: "Bar",
-                        symbol: Symbol::Here(SymbolDefinition::Struct(
-                            StructDefinition {
-                                generics: vec![],
-                                fields: vec![StructDefinitionField {
-                                    id: "foo",
-                                    ty: UnresolvedType::User("Foo".into(), None).mock(),
-                                }
-                                .mock()],
-                            }
-                            .mock(),
-                        )),
+        /// `trait Double { def double(Self a) -> Self; }`
+        fn double_trait(signature: UnresolvedSignature<'static>) -> SymbolDeclarationNode<'static> {
+            SymbolDeclaration {
+                id: "Double",
+                symbol: Symbol::Here(SymbolDefinition::Trait(
+                    TraitDefinition {
+                        functions: vec![TraitFunction {
+                            id: "double",
+                            is_method: false,
+                            signature,
+                        }
+                        .mock()],
                     }
-                    .mock()],
-                };
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
 
-                let mut state = State::<Bn128Field>::new(
-                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                    (*MODULE_ID).clone(),
-                );
+        fn self_signature() -> UnresolvedSignature<'static> {
+            UnresolvedSignature::new()
+                .inputs(vec![user("Self")])
+                .output(user("Self"))
+        }
 
-                assert!(Checker::default()
-                    .check_module(&*MODULE_ID, &mut state)
-                    .is_err());
+        /// `impl Double for field { def double(field a) -> <output> { return <value>; } }`
+        fn double_impl(
+            output: UnresolvedType<'static>,
+            value: Expression<'static>,
+        ) -> SymbolDeclarationNode<'static> {
+            let double = Function {
+                arguments: vec![parameter(UnresolvedType::FieldElement.mock())],
+                statements: vec![Statement::Return(Some(value.mock())).mock()],
+                signature: UnresolvedSignature::new()
+                    .inputs(vec![UnresolvedType::FieldElement.mock()])
+                    .output(output.mock()),
             }
+            .mock();
 
-            #[test]
-            fn self_referential() {
-                // a struct wrapping itself should be rejected
+            SymbolDeclaration {
+                id: "",
+                symbol: Symbol::Here(SymbolDefinition::Impl(
+                    ImplDefinition {
+                        trait_id: Some("Double"),
+                        ty: UnresolvedType::FieldElement.mock(),
+                        functions: vec![("double", double)],
+                    }
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
 
-                // struct Foo = { foo: Foo; }
+        fn a_plus_a() -> Expression<'static> {
+            Expression::Add(
+                box Expression::Identifier("a").mock(),
+                box Expression::Identifier("a").mock(),
+            )
+        }
 
-                let module: Module = Module {
-                    symbols: vec![SymbolDeclaration {
-                        id: "Foo",
-                        symbol: Symbol::Here(SymbolDefinition::Struct(
-                            StructDefinition {
-                                generics: vec![],
-                                fields: vec![StructDefinitionField {
-                                    id: "foo",
-                                    ty: UnresolvedType::User("Foo".into(), None).mock(),
-                                }
-                                .mock()],
+        /// `def twice<H: Double>(H a) -> H { return H::double(H::double(a)); }`
+        fn twice() -> SymbolDeclarationNode<'static> {
+            let double = |e: ExpressionNode<'static>| {
+                Expression::EnumVariant("H".into(), "double", vec![e]).mock()
+            };
+
+            SymbolDeclaration {
+                id: "twice",
+                symbol: Symbol::Here(SymbolDefinition::Function(
+                    Function {
+                        arguments: vec![parameter(user("H"))],
+                        statements: vec![Statement::Return(Some(double(double(
+                            Expression::Identifier("a").mock(),
+                        ))))
+                        .mock()],
+                        signature: UnresolvedSignature::new()
+                            .type_generics(vec![TypeGeneric {
+                                id: "H",
+                                bound: "Double",
                             }
+                            .mock()])
+                            .inputs(vec![user("H")])
+                            .output(user("H")),
+                    }
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
+
+        /// `def main(field a) -> field { return twice(a); }`
+        fn main() -> SymbolDeclarationNode<'static> {
+            SymbolDeclaration {
+                id: "main",
+                symbol: Symbol::Here(SymbolDefinition::Function(
+                    Function {
+                        arguments: vec![parameter(UnresolvedType::FieldElement.mock())],
+                        statements: vec![Statement::Return(Some(
+                            Expression::FunctionCall(
+                                box Expression::Identifier("twice").mock(),
+                                None,
+                                vec![Expression::Identifier("a").mock()],
+                            )
                             .mock(),
-                        )),
+                        ))
+                        .mock()],
+                        signature: UnresolvedSignature::new()
+                            .inputs(vec![UnresolvedType::FieldElement.mock()])
+                            .output(UnresolvedType::FieldElement.mock()),
                     }
-                    .mock()],
-                };
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
 
-                let mut state = State::<Bn128Field>::new(
-                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                    (*MODULE_ID).clone(),
-                );
+        #[test]
+        fn declare_once() {
+            // `twice` is checked once, with an opaque type for `H`, and specialized for `H = field` when inlined
+            let state = check(vec![
+                double_trait(self_signature()),
+                double_impl(UnresolvedType::FieldElement, a_plus_a()),
+                twice(),
+                main(),
+            ])
+            .unwrap();
 
-                assert!(Checker::default()
-                    .check_module(&*MODULE_ID, &mut state)
-                    .is_err());
-            }
+            let declarations: Vec<_> = state
+                .typed_modules
+                .get(&*MODULE_ID)
+                .unwrap()
+                .functions_iter()
+                .filter(|d| d.key.id == "twice")
+                .collect();
 
-            #[test]
-            fn cyclic() {
-                // A wrapping B wrapping A should be rejected
+            assert_eq!(declarations.len(), 1);
+            assert!(matches!(
+                &declarations[0].key.signature.inputs[..],
+                [DeclarationType::Struct(s)] if s.is_type_parameter()
+            ));
+        }
 
-                // struct Foo = { bar: Bar; }
-                // struct Bar = { foo: Foo; }
+        #[test]
+        fn missing_impl() {
+            let errors = check(vec![double_trait(self_signature()), twice(), main()]).unwrap_err();
 
-                let module: Module = Module {
-                    symbols: vec![
-                        SymbolDeclaration {
-                            id: "Foo",
-                            symbol: Symbol::Here(SymbolDefinition::Struct(
-                                StructDefinition {
-                                    generics: vec![],
-                                    fields: vec![StructDefinitionField {
-                                        id: "bar",
-                                        ty: UnresolvedType::User("Bar".into(), None).mock(),
-                                    }
-                                    .mock()],
-                                }
-                                .mock(),
-                            )),
-                        }
-                        .mock(),
-                        SymbolDeclaration {
-                            id: "Bar",
-                            symbol: Symbol::Here(SymbolDefinition::Struct(
-                                StructDefinition {
-                                    generics: vec![],
-                                    fields: vec![StructDefinitionField {
-                                        id: "foo",
-                                        ty: UnresolvedType::User("Foo".into(), None).mock(),
-                                    }
-                                    .mock()],
-                                }
-                                .mock(),
-                            )),
-                        }
-                        .mock(),
-                    ],
-                };
+            assert_eq!(
+                errors[0].inner.message,
+                "Type field does not implement trait Double"
+            );
+        }
 
-                let mut state = State::<Bn128Field>::new(
-                    vec![((*MODULE_ID).clone(), module)].into_iter().collect(),
-                    (*MODULE_ID).clone(),
-                );
+        #[test]
+        fn impl_signature_mismatch() {
+            let errors = check(vec![
+                double_trait(self_signature()),
+                double_impl(UnresolvedType::Boolean, Expression::BooleanConstant(true)),
+            ])
+            .unwrap_err();
 
-                assert!(Checker::default()
-                    .check_module(&*MODULE_ID, &mut state)
-                    .is_err());
-            }
+            assert_eq!(
+                errors[0].inner.message,
+                "Function double does not match its declaration in trait Double, expected signature (field) -> field, found (field) -> bool"
+            );
         }
 
-        /// tests about using the defined type identifier
-        mod usage {
-            use super::*;
+        #[test]
+        fn trait_function_without_self() {
+            let errors = check(vec![double_trait(
+                UnresolvedSignature::new()
+                    .inputs(vec![UnresolvedType::FieldElement.mock()])
+                    .output(UnresolvedType::FieldElement.mock()),
+            )])
+            .unwrap_err();
 
-            #[test]
-            fn ty() {
-                // a defined type can be checked
-                // Foo { foo: field; }
-                // Foo
+            assert_eq!(
+                errors[0].inner.message,
+                "Function double of trait Double must use `Self` in its signature"
+            );
+        }
+    }
 
-                // an undefined type cannot be checked
-                // Bar
+    mod destructuring {
+        use super::*;
 
-                let (mut checker, state) = create_module_with_foo(StructDefinition {
-                    generics: vec![],
-                    fields: vec![StructDefinitionField {
-                        id: "foo",
-                        ty: UnresolvedType::FieldElement.mock(),
-                    }
-                    .mock()],
-                });
+        fn destructure(
+            checker: &mut Checker<'static, Bn128Field>,
+            pattern: untyped::Pattern<'static>,
+            expr: Expression<'static>,
+        ) -> Result<Vec<TypedStatement<'static, Bn128Field>>, Vec<ErrorInner>> {
+            checker.check_lowered_statement(
+                Statement::Destructuring(pattern.mock(), expr.mock()).mock(),
+                &*MODULE_ID,
+                &TypeMap::new(),
+            )
+        }
 
-                assert_eq!(
-                    checker.check_type(
-                        UnresolvedType::User("Foo".into(), None).mock(),
-                        &*MODULE_ID,
-                        &state.types
-                    ),
-                    Ok(Type::Struct(StructType::new(
-                        "".into(),
-                        "Foo".into(),
-                        vec![],
-                        vec![StructMember::new("foo".into(), Type::FieldElement)]
-                    )))
-                );
+        #[test]
+        fn tuple() {
+            // (field a, (mut b, _)) = (1, (true, 2u32));
+            //
+            // should succeed, defining a temporary variable and `a` and `b`
 
-                assert_eq!(
-                    checker
-                        .check_type(
-                            UnresolvedType::User("Bar".into(), None).mock(),
-                            &*MODULE_ID,
-                            &state.types
-                        )
-                        .unwrap_err()
-                        .message,
-                    "Undefined type Bar"
-                );
-            }
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+
+            let statements = destructure(
+                &mut checker,
+                untyped::Pattern::Tuple(vec![
+                    untyped::Pattern::Variable(
+                        untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock())
+                            .mock(),
+                    )
+                    .mock(),
+                    untyped::Pattern::Tuple(vec![
+                        untyped::Pattern::Binding("b", true).mock(),
+                        untyped::Pattern::Wildcard.mock(),
+                    ])
+                    .mock(),
+                ]),
+                Expression::InlineTuple(vec![
+                    Expression::IntConstant(1usize.into()).mock(),
+                    Expression::InlineTuple(vec![
+                        Expression::BooleanConstant(true).mock(),
+                        Expression::U32Constant(2).mock(),
+                    ])
+                    .mock(),
+                ]),
+            )
+            .unwrap();
+
+            assert_eq!(statements.len(), 3);
+            assert_eq!(checker.scope.get("a").unwrap().ty, Type::FieldElement);
+            assert_eq!(checker.scope.get("b").unwrap().ty, Type::Boolean);
+            assert!(checker.scope.get("b").unwrap().is_mutable);
         }
 
-        /// tests about accessing members
-        mod member {
-            use super::*;
+        #[test]
+        fn wrong_size() {
+            // (field a, field b) = (1,);
+            //
+            // should fail
 
-            #[test]
-            fn valid() {
-                // accessing a member on a struct should succeed and return the right type
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
 
-                // struct Foo = { foo: field; }
-                // Foo { foo: 42 }.foo
+            let field = |id| {
+                untyped::Pattern::Variable(
+                    untyped::Variable::immutable(id, UnresolvedType::FieldElement.mock()).mock(),
+                )
+                .mock()
+            };
 
-                let (mut checker, state) = create_module_with_foo(StructDefinition {
-                    generics: vec![],
-                    fields: vec![StructDefinitionField {
-                        id: "foo",
-                        ty: UnresolvedType::FieldElement.mock(),
-                    }
-                    .mock()],
-                });
+            let errors = destructure(
+                &mut checker,
+                untyped::Pattern::Tuple(vec![field("a"), field("b")]),
+                Expression::InlineTuple(vec![Expression::IntConstant(1usize.into()).mock()]),
+            )
+            .unwrap_err();
 
-                assert_eq!(
-                    checker.check_expression(
-                        Expression::Member(
-                            box Expression::InlineStruct(
-                                "Foo".into(),
-                                vec![("foo", Expression::IntConstant(42usize.into()).mock())]
-                            )
-                            .mock(),
-                            "foo".into()
-                        )
-                        .mock(),
-                        &*MODULE_ID,
-                        &state.types
-                    ),
-                    Ok(FieldElementExpression::member(
-                        StructExpressionInner::Value(vec![FieldElementExpression::Number(
-                            Bn128Field::from(42u32)
-                        )
-                        .into()])
-                        .annotate(StructType::new(
-                            "".into(),
-                            "Foo".into(),
-                            vec![],
-                            vec![StructMember::new("foo".into(), Type::FieldElement)]
-                        )),
-                        "foo".into()
-                    )
-                    .into())
-                );
-            }
+            assert_eq!(
+                errors[0].message,
+                "Expected a value of type `(field, field)` to destructure with `(field a, field b)`"
+            );
+        }
 
-            #[test]
-            fn invalid() {
-                // accessing an undefined member on a struct should fail
+        #[test]
+        fn undetermined_type() {
+            // (a, b) = (1, 2);
+            //
+            // should fail as the types of `a` and `b` cannot be inferred
 
-                // struct Foo = { foo: field; }
-                // Foo { foo: 42 }.bar
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
 
-                let (mut checker, state) = create_module_with_foo(StructDefinition {
-                    generics: vec![],
-                    fields: vec![StructDefinitionField {
-                        id: "foo",
-                        ty: UnresolvedType::FieldElement.mock(),
-                    }
-                    .mock()],
-                });
+            let errors = destructure(
+                &mut checker,
+                untyped::Pattern::Tuple(vec![
+                    untyped::Pattern::Binding("a", false).mock(),
+                    untyped::Pattern::Binding("b", false).mock(),
+                ]),
+                Expression::InlineTuple(vec![
+                    Expression::IntConstant(1usize.into()).mock(),
+                    Expression::IntConstant(2usize.into()).mock(),
+                ]),
+            )
+            .unwrap_err();
 
-                assert_eq!(
-                    checker
-                        .check_expression(
-                            Expression::Member(
-                                box Expression::InlineStruct(
-                                    "Foo".into(),
-                                    vec![("foo", Expression::IntConstant(42usize.into()).mock())]
-                                )
-                                .mock(),
-                                "bar".into()
-                            )
-                            .mock(),
-                            &*MODULE_ID,
-                            &state.types
-                        )
-                        .unwrap_err()
-                        .message,
-                    "Foo {foo: field} doesn\'t have member bar"
-                );
-            }
+            assert_eq!(
+                errors[0].message,
+                "Cannot determine type for expression `(1, 2)`"
+            );
         }
+    }
 
-        /// tests about defining struct instance inline
-        mod value {
-            use super::*;
+    mod array_iteration {
+        use super::*;
 
-            #[test]
-            fn wrong_name() {
-                // a A value cannot be defined with B as id, even if A and B have the same members
+        /// `[1, 2, 3]`
+        fn array() -> ExpressionNode<'static> {
+            Expression::InlineArray(
+                (1..4usize)
+                    .map(|i| Expression::FieldConstant(i.into()).mock().into())
+                    .collect(),
+            )
+            .mock()
+        }
 
-                let (mut checker, state) = create_module_with_foo(StructDefinition {
-                    generics: vec![],
-                    fields: vec![StructDefinitionField {
-                        id: "foo",
-                        ty: UnresolvedType::FieldElement.mock(),
-                    }
-                    .mock()],
-                });
+        #[test]
+        fn for_each() {
+            // for field x in [1, 2, 3] {}
+            //
+            // should succeed, keeping the loop over the array for later lowering
 
-                assert_eq!(
-                    checker
-                        .check_expression(
-                            Expression::InlineStruct(
-                                "Bar".into(),
-                                vec![("foo", Expression::IntConstant(42usize.into()).mock())]
-                            )
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+
+            let statements = checker
+                .check_lowered_statement(
+                    Statement::ForEach(
+                        untyped::Variable::immutable("x", UnresolvedType::FieldElement.mock())
                             .mock(),
-                            &*MODULE_ID,
-                            &state.types
-                        )
-                        .unwrap_err()
-                        .message,
-                    "Undefined type `Bar`"
-                );
-            }
+                        array(),
+                        vec![],
+                    )
+                    .mock(),
+                    &*MODULE_ID,
+                    &TypeMap::new(),
+                )
+                .unwrap();
 
-            #[test]
-            fn valid() {
-                // a A value can be defined with members ordered as in the declaration of A
+            assert_eq!(statements.len(), 1);
+            assert!(matches!(statements[0], TypedStatement::ForEach(..)));
+            // the loop variable does not leak out of the loop
+            assert!(checker.scope.get("x").is_none());
+        }
 
-                // struct Foo { foo: field; bar: bool; }
-                // Foo foo = Foo { foo: 42, bar: true };
+        #[test]
+        fn for_each_wrong_type() {
+            // for bool x in [1, 2, 3] {}
+            //
+            // should fail
 
-                let (mut checker, state) = create_module_with_foo(StructDefinition {
-                    generics: vec![],
-                    fields: vec![
-                        StructDefinitionField {
-                            id: "foo",
-                            ty: UnresolvedType::FieldElement.mock(),
-                        }
-                        .mock(),
-                        StructDefinitionField {
-                            id: "bar",
-                            ty: UnresolvedType::Boolean.mock(),
-                        }
-                        .mock(),
-                    ],
-                });
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
 
-                assert_eq!(
-                    checker.check_expression(
-                        Expression::InlineStruct(
-                            "Foo".into(),
-                            vec![
-                                ("foo", Expression::IntConstant(42usize.into()).mock()),
-                                ("bar", Expression::BooleanConstant(true).mock())
-                            ]
-                        )
-                        .mock(),
-                        &*MODULE_ID,
-                        &state.types
-                    ),
-                    Ok(StructExpressionInner::Value(vec![
-                        FieldElementExpression::Number(Bn128Field::from(42u32)).into(),
-                        BooleanExpression::Value(true).into()
-                    ])
-                    .annotate(StructType::new(
-                        "".into(),
-                        "Foo".into(),
+            let errors = checker
+                .check_lowered_statement(
+                    Statement::ForEach(
+                        untyped::Variable::immutable("x", UnresolvedType::Boolean.mock()).mock(),
+                        array(),
                         vec![],
-                        vec![
-                            StructMember::new("foo".into(), Type::FieldElement),
-                            StructMember::new("bar".into(), Type::Boolean)
-                        ]
-                    ))
-                    .into())
-                );
-            }
+                    )
+                    .mock(),
+                    &*MODULE_ID,
+                    &TypeMap::new(),
+                )
+                .unwrap_err();
 
-            #[test]
-            fn shuffled() {
-                // a A value can be defined with shuffled members compared to the declaration of A
+            assert_eq!(
+                errors[0].message,
+                "Expected variable in for loop to be of type field, found bool"
+            );
+        }
 
-                // struct Foo { foo: field; bar: bool; }
-                // Foo foo = Foo { bar: true, foo: 42 };
+        #[test]
+        fn combinator_arguments() {
+            // [1, 2, 3].map()
+            //
+            // should fail
 
-                let (mut checker, state) = create_module_with_foo(StructDefinition {
-                    generics: vec![],
-                    fields: vec![
-                        StructDefinitionField {
-                            id: "foo",
-                            ty: UnresolvedType::FieldElement.mock(),
-                        }
-                        .mock(),
-                        StructDefinitionField {
-                            id: "bar",
-                            ty: UnresolvedType::Boolean.mock(),
-                        }
-                        .mock(),
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+
+            let e = Expression::FunctionCall(
+                box Expression::Member(box array(), "map".into()).mock(),
+                None,
+                vec![],
+            )
+            .mock();
+
+            assert_eq!(
+                checker
+                    .check_expression(e, &*MODULE_ID, &TypeMap::new())
+                    .unwrap_err()
+                    .message,
+                "Array combinator map expects 1 argument(s), found 0"
+            );
+        }
+
+        #[test]
+        fn combinator_function_argument() {
+            // [1, 2, 3].map(1)
+            //
+            // should fail
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+
+            let e = Expression::FunctionCall(
+                box Expression::Member(box array(), "map".into()).mock(),
+                None,
+                vec![Expression::FieldConstant(1usize.into()).mock()],
+            )
+            .mock();
+
+            assert_eq!(
+                checker
+                    .check_expression(e, &*MODULE_ID, &TypeMap::new())
+                    .unwrap_err()
+                    .message,
+                "Expected a function name or an anonymous function as argument of map, found `1`"
+            );
+        }
+    }
+
+    mod lambdas {
+        use super::*;
+
+        /// `(<ty> a) -> field { return a; }`
+        fn lambda(ty: UnresolvedType<'static>) -> ExpressionNode<'static> {
+            Expression::Lambda(
+                box untyped::Function {
+                    arguments: vec![untyped::Parameter::new(
+                        untyped::Variable::immutable("a", ty.clone().mock()).mock(),
+                        None,
+                    )
+                    .mock()],
+                    statements: vec![
+                        Statement::Return(Some(Expression::Identifier("a").mock())).mock()
                     ],
-                });
+                    signature: UnresolvedSignature::new()
+                        .inputs(vec![ty.mock()])
+                        .output(UnresolvedType::FieldElement.mock()),
+                }
+                .mock(),
+            )
+            .mock()
+        }
 
-                assert_eq!(
-                    checker.check_expression(
-                        Expression::InlineStruct(
-                            "Foo".into(),
-                            vec![
-                                ("bar", Expression::BooleanConstant(true).mock()),
-                                ("foo", Expression::IntConstant(42usize.into()).mock())
-                            ]
-                        )
-                        .mock(),
-                        &*MODULE_ID,
-                        &state.types
-                    ),
-                    Ok(StructExpressionInner::Value(vec![
-                        FieldElementExpression::Number(Bn128Field::from(42u32)).into(),
-                        BooleanExpression::Value(true).into()
-                    ])
-                    .annotate(StructType::new(
-                        "".into(),
-                        "Foo".into(),
-                        vec![],
-                        vec![
-                            StructMember::new("foo".into(), Type::FieldElement),
-                            StructMember::new("bar".into(), Type::Boolean)
-                        ]
-                    ))
-                    .into())
-                );
+        /// `[1, 2, 3].map(<f>)`
+        fn map(f: ExpressionNode<'static>) -> ExpressionNode<'static> {
+            Expression::FunctionCall(
+                box Expression::Member(
+                    box Expression::InlineArray(
+                        (1..4usize)
+                            .map(|i| Expression::FieldConstant(i.into()).mock().into())
+                            .collect(),
+                    )
+                    .mock(),
+                    "map".into(),
+                )
+                .mock(),
+                None,
+                vec![f],
+            )
+            .mock()
+        }
+
+        fn function(
+            id: &'static str,
+            arguments: Vec<untyped::ParameterNode<'static>>,
+            inputs: Vec<UnresolvedTypeNode<'static>>,
+            value: ExpressionNode<'static>,
+        ) -> SymbolDeclarationNode<'static> {
+            SymbolDeclaration {
+                id,
+                symbol: Symbol::Here(SymbolDefinition::Function(
+                    untyped::Function {
+                        arguments,
+                        statements: vec![Statement::Return(Some(value)).mock()],
+                        signature: UnresolvedSignature::new()
+                            .inputs(inputs)
+                            .output(UnresolvedType::FieldElement.mock()),
+                    }
+                    .mock(),
+                )),
+                is_public: true,
+            }
+            .mock()
+        }
+
+        fn parameter(
+            id: &'static str,
+            ty: UnresolvedTypeNode<'static>,
+        ) -> untyped::ParameterNode<'static> {
+            untyped::Parameter::new(untyped::Variable::immutable(id, ty).mock(), None).mock()
+        }
+
+        fn call(
+            id: &'static str,
+            arguments: Vec<ExpressionNode<'static>>,
+        ) -> ExpressionNode<'static> {
+            Expression::FunctionCall(box Expression::Identifier(id).mock(), None, arguments).mock()
+        }
+
+        fn returned_call<'ast>(
+            module: &TypedModule<'ast, Bn128Field>,
+            id: &str,
+        ) -> FunctionCallExpression<'ast, Bn128Field, FieldElementExpression<'ast, Bn128Field>>
+        {
+            match &module
+                .functions_iter()
+                .find(|d| d.key.id == id)
+                .unwrap()
+                .symbol
+            {
+                TypedFunctionSymbol::Here(f) => match &f.statements[0] {
+                    TypedStatement::Return(TypedExpression::FieldElement(
+                        FieldElementExpression::FunctionCall(call),
+                    )) => call.clone(),
+                    s => panic!("expected a call to be returned, found {}", s),
+                },
+                _ => unreachable!(),
             }
+        }
 
-            #[test]
-            fn subset() {
-                // a A value cannot be defined with A as id if members are a subset of the declaration
+        #[test]
+        fn function_parameter() {
+            // def apply(field a, (field) -> field f) -> field { return f(a); }
+            // def main(field a) -> field { return apply(a, (field a) -> field { return a; }); }
+            //
+            // should check `apply` once, calling its function parameter through a key which is resolved when the
+            // call to `apply` is inlined
 
-                // struct Foo { foo: field; bar: bool; }
-                // Foo foo = Foo { foo: 42 };
+            let function_type = UnresolvedType::Function(
+                vec![UnresolvedType::FieldElement.mock()],
+                box UnresolvedType::FieldElement.mock(),
+            )
+            .mock();
 
-                let (mut checker, state) = create_module_with_foo(StructDefinition {
-                    generics: vec![],
-                    fields: vec![
-                        StructDefinitionField {
-                            id: "foo",
-                            ty: UnresolvedType::FieldElement.mock(),
-                        }
-                        .mock(),
-                        StructDefinitionField {
-                            id: "bar",
-                            ty: UnresolvedType::Boolean.mock(),
-                        }
-                        .mock(),
+            let state = check(vec![
+                function(
+                    "apply",
+                    vec![
+                        parameter("a", UnresolvedType::FieldElement.mock()),
+                        parameter("f", function_type.clone()),
                     ],
-                });
+                    vec![UnresolvedType::FieldElement.mock(), function_type],
+                    call("f", vec![Expression::Identifier("a").mock()]),
+                ),
+                function(
+                    "main",
+                    vec![parameter("a", UnresolvedType::FieldElement.mock())],
+                    vec![UnresolvedType::FieldElement.mock()],
+                    call(
+                        "apply",
+                        vec![
+                            Expression::Identifier("a").mock(),
+                            lambda(UnresolvedType::FieldElement),
+                        ],
+                    ),
+                ),
+            ])
+            .unwrap();
 
-                assert_eq!(
-                    checker
-                        .check_expression(
-                            Expression::InlineStruct(
-                                "Foo".into(),
-                                vec![("foo", Expression::IntConstant(42usize.into()).mock())]
-                            )
-                            .mock(),
-                            &*MODULE_ID,
-                            &state.types
-                        )
-                        .unwrap_err()
-                        .message,
-                    "Inline struct Foo {foo: 42} does not match Foo {foo: field, bar: bool}"
-                );
-            }
+            let module = state.typed_modules.get(&*MODULE_ID).unwrap();
 
-            #[test]
-            fn invalid() {
-                // a A value cannot be defined with A as id if members are different ids than the declaration
-                // a A value cannot be defined with A as id if members are different types than the declaration
+            let signature = DeclarationSignature::new()
+                .inputs(vec![DeclarationType::FieldElement])
+                .output(DeclarationType::FieldElement);
+            let apply_key = DeclarationFunctionKey::with_location((*MODULE_ID).clone(), "apply")
+                .signature(signature.clone());
 
-                // struct Foo { foo: field; bar: bool; }
-                // Foo { foo: 42, baz: bool } // error
-                // Foo { foo: 42, baz: 42 } // error
+            assert_eq!(
+                module
+                    .functions_iter()
+                    .filter(|d| d.key.id == "apply")
+                    .map(|d| d.key.clone())
+                    .collect::<Vec<_>>(),
+                vec![apply_key.clone()]
+            );
+            assert_eq!(
+                *returned_call(module, "apply").function_key,
+                apply_key.function_parameter(0, signature.clone())
+            );
 
-                let (mut checker, state) = create_module_with_foo(StructDefinition {
-                    generics: vec![],
-                    fields: vec![
-                        StructDefinitionField {
-                            id: "foo",
-                            ty: UnresolvedType::FieldElement.mock(),
-                        }
-                        .mock(),
-                        StructDefinitionField {
-                            id: "bar",
-                            ty: UnresolvedType::Boolean.mock(),
-                        }
-                        .mock(),
-                    ],
-                });
+            let call = returned_call(module, "main");
+            assert_eq!(*call.function_key, apply_key);
+            assert_eq!(call.function_arguments.len(), 1);
+            assert_eq!(call.function_arguments[0].id, "lambda");
+            assert_eq!(call.function_arguments[0].signature, signature);
+        }
 
-                assert_eq!(
-                    checker
-                        .check_expression(
-                            Expression::InlineStruct(
-                                "Foo".into(),
-                                vec![(
-                                    "baz",
-                                    Expression::BooleanConstant(true).mock()
-                                ),(
-                                    "foo",
-                                    Expression::IntConstant(42usize.into()).mock()
-                                )]
-                            )
-                            .mock(),
-                            &*MODULE_ID,
-                            &state.types
-                        ).unwrap_err()
-                        .message,
-                    "Member bar of struct Foo {foo: field, bar: bool} not found in value Foo {baz: true, foo: 42}"
-                );
+        #[test]
+        fn map_with_lambda() {
+            // [1, 2, 3].map((field a) -> field { return a; })
+            //
+            // should succeed, deferring the check of the anonymous function
 
-                assert_eq!(
-                    checker
-                        .check_expression(
-                            Expression::InlineStruct(
-                                "Foo".into(),
-                                vec![
-                                    ("bar", Expression::IntConstant(42usize.into()).mock()),
-                                    ("foo", Expression::IntConstant(42usize.into()).mock())
-                                ]
-                            )
-                            .mock(),
-                            &*MODULE_ID,
-                            &state.types
-                        )
-                        .unwrap_err()
-                        .message,
-                    "Member bar of struct Foo has type bool, found 42 of type {integer}"
-                );
-            }
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+
+            let e = map(lambda(UnresolvedType::FieldElement));
+
+            assert!(checker
+                .check_expression(e, &*MODULE_ID, &TypeMap::new())
+                .is_ok());
+            assert!(checker.combinators.contains(&Combinator::Map));
+            assert_eq!(checker.lambdas.len(), 1);
+            assert_eq!(
+                checker.lambdas[0].key.signature,
+                DeclarationSignature::new()
+                    .inputs(vec![DeclarationType::FieldElement])
+                    .output(DeclarationType::FieldElement)
+            );
+        }
+
+        #[test]
+        fn map_with_lambda_wrong_signature() {
+            // [1, 2, 3].map((bool a) -> field { return a; })
+            //
+            // should fail
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+
+            let e = map(lambda(UnresolvedType::Boolean));
+
+            assert_eq!(
+                checker
+                    .check_expression(e, &*MODULE_ID, &TypeMap::new())
+                    .unwrap_err()
+                    .message,
+                "Expected a function with signature (field) -> _ as argument of map, found an anonymous function with signature (bool) -> field"
+            );
+        }
+
+        #[test]
+        fn lambda_as_value() {
+            // field f = (field a) -> field { return a; };
+            //
+            // should fail
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+            checker.enter_scope();
+
+            assert_eq!(
+                checker
+                    .check_expression(
+                        lambda(UnresolvedType::FieldElement),
+                        &*MODULE_ID,
+                        &TypeMap::new()
+                    )
+                    .unwrap_err()
+                    .message,
+                "Anonymous functions can only be passed to function parameters and array combinators"
+            );
+        }
+
+        #[test]
+        fn function_type_outside_parameters() {
+            // (field) -> field f = ...;
+            //
+            // should fail
+
+            let mut checker: Checker<Bn128Field> = Checker::default();
+
+            let ty = UnresolvedType::Function(
+                vec![UnresolvedType::FieldElement.mock()],
+                box UnresolvedType::FieldElement.mock(),
+            )
+            .mock();
+
+            assert_eq!(
+                checker
+                    .check_type(ty, &*MODULE_ID, &TypeMap::new())
+                    .unwrap_err()
+                    .message,
+                "Function types are only allowed for function parameters"
+            );
         }
     }
 
@@ -6306,16 +12071,19 @@ mod tests {
                 untyped::SymbolDeclaration {
                     id: "foo",
                     symbol: Symbol::Here(SymbolDefinition::Function(foo_field)),
+                    is_public: true,
                 }
                 .mock(),
                 untyped::SymbolDeclaration {
                     id: "foo",
                     symbol: Symbol::Here(SymbolDefinition::Function(foo_u32)),
+                    is_public: true,
                 }
                 .mock(),
                 untyped::SymbolDeclaration {
                     id: "main",
                     symbol: Symbol::Here(SymbolDefinition::Function(main)),
+                    is_public: true,
                 }
                 .mock(),
             ]);
@@ -6490,4 +12258,147 @@ mod tests {
             );
         }
     }
+
+    mod warnings {
+        use super::*;
+
+        fn check(
+            statements: Vec<StatementNode<'static>>,
+            symbols: Vec<SymbolDeclarationNode<'static>>,
+        ) -> Vec<(Lint, String)> {
+            let mut main = function0();
+            main.value.statements = statements;
+
+            let mut state = super::check(
+                symbols
+                    .into_iter()
+                    .chain(std::iter::once(
+                        untyped::SymbolDeclaration {
+                            id: "main",
+                            symbol: Symbol::Here(SymbolDefinition::Function(main)),
+                            is_public: true,
+                        }
+                        .mock(),
+                    ))
+                    .collect(),
+            )
+            .unwrap();
+
+            // the symbols which are never used are only reported once the whole program is checked
+            let mut checker = Checker::<Bn128Field>::default();
+            checker.check_local_symbols(&mut state);
+
+            checker
+                .warnings
+                .into_iter()
+                .chain(state.warnings)
+                .map(|w| (w.lint, w.message))
+                .collect()
+        }
+
+        #[test]
+        fn unused_variable() {
+            // def main() {
+            //   field a = 1;
+            //   return;
+            // }
+            //
+            // should warn about `a`
+
+            let statements = vec![
+                Statement::Definition(
+                    untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                    Expression::IntConstant(1usize.into()).mock(),
+                )
+                .mock(),
+                Statement::Return(None).mock(),
+            ];
+
+            assert_eq!(
+                check(statements, vec![]),
+                vec![(
+                    Lint::UnusedVariables,
+                    "Variable `a` is never read".to_string()
+                )]
+            );
+        }
+
+        #[test]
+        fn shadowing() {
+            // def main() {
+            //   field a = 1;
+            //   field a = a;
+            //   assert(a == 1);
+            //   return;
+            // }
+            //
+            // should warn about the second declaration of `a` only, as the first one is read
+
+            let statements = vec![
+                Statement::Definition(
+                    untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                    Expression::IntConstant(1usize.into()).mock(),
+                )
+                .mock(),
+                Statement::Definition(
+                    untyped::Variable::immutable("a", UnresolvedType::FieldElement.mock()).mock(),
+                    Expression::Identifier("a").mock(),
+                )
+                .mock(),
+                Statement::Assertion(
+                    Expression::Eq(
+                        box Expression::Identifier("a").mock(),
+                        box Expression::IntConstant(1usize.into()).mock(),
+                    )
+                    .mock(),
+                    None,
+                )
+                .mock(),
+                Statement::Return(None).mock(),
+            ];
+
+            assert_eq!(
+                check(statements, vec![]),
+                vec![(
+                    Lint::Shadowing,
+                    "Variable `a` shadows a previous declaration".to_string()
+                )]
+            );
+        }
+
+        #[test]
+        fn unused_private_function() {
+            // def foo() {
+            //   return;
+            // }
+            //
+            // def main() {
+            //   return;
+            // }
+            //
+            // should warn about `foo` if it is private only
+
+            let foo = |is_public| {
+                untyped::SymbolDeclaration {
+                    id: "foo",
+                    symbol: Symbol::Here(SymbolDefinition::Function(function0())),
+                    is_public,
+                }
+                .mock()
+            };
+
+            assert_eq!(
+                check(vec![Statement::Return(None).mock()], vec![foo(false)]),
+                vec![(
+                    Lint::UnusedFunctions,
+                    "Function `foo` is never used".to_string()
+                )]
+            );
+
+            assert_eq!(
+                check(vec![Statement::Return(None).mock()], vec![foo(true)]),
+                vec![]
+            );
+        }
+    }
 }
@@ -56,7 +56,7 @@ impl Interpreter {
         for statement in program.statements.into_iter() {
             match statement {
                 Statement::Block(..) => unreachable!(),
-                Statement::Constraint(quad, lin, error) => match lin.is_assignee(&witness) {
+                Statement::Constraint(quad, lin, error, _) => match lin.is_assignee(&witness) {
                     true => {
                         let val = evaluate_quad(&witness, &quad).unwrap();
                         witness.insert(lin.0.get(0).unwrap().0, val);
@@ -69,7 +69,7 @@ impl Interpreter {
                         }
                     }
                 },
-                Statement::Directive(ref d) => {
+                Statement::Directive(ref d, _) => {
                     let mut inputs: Vec<_> = d
                         .inputs
                         .iter()
@@ -91,7 +91,7 @@ impl Interpreter {
                         witness.insert(*o, res[i].clone());
                     }
                 }
-                Statement::Log(l, expressions) => {
+                Statement::Log(l, expressions, _) => {
                     let mut parts = l.parts.into_iter();
 
                     write!(log_stream, "{}", parts.next().unwrap())
@@ -14,6 +14,10 @@ declare module "zokrates-js" {
   export interface CompileConfig {
     isolate_branches?: boolean;
     debug?: boolean;
+    max_recursion_depth?: number;
+    eliminate_linear_constraints?: boolean;
+    opt_level?: 0 | 1 | 2;
+    source_map?: boolean;
   }
 
   export interface CompileOptions {
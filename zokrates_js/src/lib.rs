@@ -236,7 +236,9 @@ mod internal {
             .get("snarkjs")
             .map(|v| *v == serde_json::Value::Bool(true))
             .unwrap_or(false);
-        let config: CompileConfig = serde_json::from_value(config).unwrap_or_default();
+        let config: CompileConfig = serde_json::from_value(config).map_err(|err| {
+            JsValue::from_str(&format!("Could not deserialize `config`: {}", err))
+        })?;
 
         let fmt_error = |e: &CompileError| format!("{}:{}", e.file().display(), e.value());
 
@@ -268,7 +270,10 @@ mod internal {
             buffer.into_inner()
         });
         let mut buffer = Cursor::new(vec![]);
-        let _ = program.serialize(&mut buffer);
+        let _ = match config.source_map {
+            true => program.serialize_with_source_map(&mut buffer),
+            false => program.serialize(&mut buffer),
+        };
 
         Ok(CompilationResult {
             abi,
@@ -55,6 +55,23 @@ describe("tests", () => {
       assert.ok(artifacts.snarkjs.program !== undefined);
     });
 
+    it("should compile with optimization level", () => {
+      const code =
+        "def main(private field a) -> field { field b = a; return b; }";
+      const optimized = zokratesProvider.compile(code);
+      const unoptimized = zokratesProvider.compile(code, {
+        config: { opt_level: 0 },
+      });
+      assert.ok(unoptimized.constraintCount > optimized.constraintCount);
+    });
+
+    it("should throw on invalid optimization level", () => {
+      assert.throws(() => {
+        const code = "def main() {}";
+        zokratesProvider.compile(code, { config: { opt_level: 3 } });
+      });
+    });
+
     it("should throw on invalid code", () => {
       assert.throws(() => zokratesProvider.compile(":-)"));
     });
@@ -70,7 +87,7 @@ describe("tests", () => {
       const options = {
         resolveCallback: (_, path) => {
           return {
-            source: "def main() -> field { return 1; }",
+            source: "pub def main() -> field { return 1; }",
             location: path,
           };
         },
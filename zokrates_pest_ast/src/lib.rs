@@ -8,19 +8,27 @@ use zokrates_parser::Rule;
 extern crate lazy_static;
 
 pub use ast::{
-    Access, Arguments, ArrayAccess, ArrayInitializerExpression, ArrayType, AssemblyStatement,
-    AssemblyStatementInner, AssertionStatement, Assignee, AssigneeAccess, AssignmentOperator,
-    BasicOrStructOrTupleType, BasicType, BinaryExpression, BinaryOperator, CallAccess,
-    ConstantDefinition, ConstantGenericValue, DecimalLiteralExpression, DecimalNumber,
-    DecimalSuffix, DefinitionStatement, ExplicitGenerics, Expression, FieldType, File,
-    FromExpression, FunctionDefinition, HexLiteralExpression, HexNumberExpression,
-    IdentifierExpression, IdentifierOrDecimal, IfElseExpression, ImportDirective, ImportSymbol,
-    InlineArrayExpression, InlineStructExpression, InlineStructMember, InlineTupleExpression,
-    IterationStatement, LiteralExpression, LogStatement, Parameter, PostfixExpression, Range,
-    RangeOrExpression, ReturnStatement, Span, Spread, SpreadOrExpression, Statement,
-    StructDefinition, StructField, SymbolDeclaration, TernaryExpression, ToExpression, Type,
-    TypeDefinition, TypedIdentifier, TypedIdentifierOrAssignee, UnaryExpression, UnaryOperator,
-    Underscore, Visibility,
+    Access, Arguments, ArrayAccess, ArrayDimension, ArrayInitializerExpression,
+    ArrayIterationStatement, ArrayType, AssemblyStatement, AssemblyStatementInner,
+    AssertionStatement, Assignee, AssigneeAccess, AssignmentOperator, Attribute,
+    BasicOrStructOrTupleType, BasicType, BinaryExpression, BinaryOperator, Bounded, BreakStatement,
+    ByteStringExpression, ByteStringLiteralExpression, CallAccess, ComptimeElseClause,
+    ComptimeIfStatement, ConstantDefinition, ConstantGenericValue, ContinueStatement,
+    DecimalLiteralExpression, DecimalNumber, DecimalSuffix, DefinitionStatement,
+    DestructuringPattern, DestructuringStatement, ElseClause, EnumDefinition, EnumVariant,
+    EnumVariantExpression, ExplicitGenerics, Expression, FieldType, File, FromExpression,
+    FunctionDefinition, FunctionType, FunctionTypeInputs, HexLiteralExpression,
+    HexNumberExpression, HexString, HexStringLiteralExpression, IdentifierExpression,
+    IdentifierOrDecimal, IfElseExpression, IfStatement, ImplDefinition, ImplTrait, ImportAll,
+    ImportDirective, ImportSymbol, InlineArrayExpression, InlineStructExpression,
+    InlineStructMember, InlineTupleExpression, IterationStatement, LambdaExpression,
+    LiteralExpression, LogStatement, MatchArm, MatchExpression, MatchPattern, MatchVariantPattern,
+    Parameter, Pattern, PatternBinding, PostfixExpression, Pub, Range, RangeOrExpression,
+    ReturnStatement, SelfParameter, Span, Spread, SpreadOrExpression, Statement, StatementBlock,
+    StaticAssertStatement, StructDefinition, StructField, StructPattern, StructPatternField,
+    SymbolDeclaration, TernaryExpression, ToExpression, TraitDefinition, TraitFunction,
+    TuplePattern, Type, TypeDefinition, TypeGeneric, TypedIdentifier, TypedIdentifierOrAssignee,
+    UnaryExpression, UnaryOperator, Underscore, Visibility, WhileStatement,
 };
 
 mod ast {
@@ -107,6 +115,30 @@ mod ast {
         PREC_CLIMBER.climb(pair.into_inner(), build_factor, infix_rule)
     }
 
+    // Create an Expression from a `constant_generics_expression`, in the same way as `climb`
+    fn climb_constant_generics(pair: Pair<Rule>) -> Box<Expression> {
+        PREC_CLIMBER.climb(
+            pair.into_inner(),
+            build_constant_generics_factor,
+            infix_rule,
+        )
+    }
+
+    // Create an Expression from a term of a `constant_generics_expression`
+    // Precondition: `pair` MUST be a `literal`, an `identifier` or an `expression`
+    fn build_constant_generics_factor(pair: Pair<Rule>) -> Box<Expression> {
+        Box::new(match pair.as_rule() {
+            Rule::literal => {
+                Expression::Literal(LiteralExpression::from_pest(&mut Pairs::single(pair)).unwrap())
+            }
+            Rule::identifier => Expression::Identifier(
+                IdentifierExpression::from_pest(&mut Pairs::single(pair)).unwrap(),
+            ),
+            Rule::expression => *climb(pair),
+            _ => unreachable!(),
+        })
+    }
+
     // Create an Expression from a `unaried_term`.
     // Precondition: `pair` MUST be a `unaried_term`
     fn build_factor(pair: Pair<Rule>) -> Box<Expression> {
@@ -149,13 +181,18 @@ mod ast {
         Import(ImportDirective<'ast>),
         Constant(ConstantDefinition<'ast>),
         Struct(StructDefinition<'ast>),
+        Enum(EnumDefinition<'ast>),
         Type(TypeDefinition<'ast>),
+        StaticAssertion(StaticAssertStatement<'ast>),
+        Trait(TraitDefinition<'ast>),
+        Impl(ImplDefinition<'ast>),
         Function(FunctionDefinition<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::ty_struct_definition))]
     pub struct StructDefinition<'ast> {
+        pub public: Option<Pub>,
         pub id: IdentifierExpression<'ast>,
         pub generics: Vec<IdentifierExpression<'ast>>,
         pub fields: Vec<StructField<'ast>>,
@@ -171,11 +208,82 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_enum_definition))]
+    pub struct EnumDefinition<'ast> {
+        pub public: Option<Pub>,
+        pub id: IdentifierExpression<'ast>,
+        pub variants: Vec<EnumVariant<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::enum_variant))]
+    pub struct EnumVariant<'ast> {
+        pub id: IdentifierExpression<'ast>,
+        pub types: Vec<Type<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::trait_definition))]
+    pub struct TraitDefinition<'ast> {
+        pub public: Option<Pub>,
+        pub id: IdentifierExpression<'ast>,
+        pub functions: Vec<TraitFunction<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::trait_function))]
+    pub struct TraitFunction<'ast> {
+        pub id: IdentifierExpression<'ast>,
+        pub self_parameter: Option<SelfParameter<'ast>>,
+        pub parameters: Vec<Parameter<'ast>>,
+        pub return_type: Option<Type<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::impl_definition))]
+    pub struct ImplDefinition<'ast> {
+        pub trait_id: Option<ImplTrait<'ast>>,
+        pub ty: Type<'ast>,
+        pub functions: Vec<FunctionDefinition<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::impl_trait))]
+    pub struct ImplTrait<'ast> {
+        pub id: IdentifierExpression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::type_generic))]
+    pub struct TypeGeneric<'ast> {
+        pub id: IdentifierExpression<'ast>,
+        pub bound: IdentifierExpression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::function_definition))]
     pub struct FunctionDefinition<'ast> {
+        pub attributes: Vec<Attribute<'ast>>,
+        pub public: Option<Pub>,
         pub id: IdentifierExpression<'ast>,
+        pub type_generics: Vec<TypeGeneric<'ast>>,
         pub generics: Vec<IdentifierExpression<'ast>>,
+        pub self_parameter: Option<SelfParameter<'ast>>,
         pub parameters: Vec<Parameter<'ast>>,
         pub return_type: Option<Type<'ast>>,
         pub statements: Vec<Statement<'ast>>,
@@ -183,9 +291,18 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::attribute))]
+    pub struct Attribute<'ast> {
+        pub id: IdentifierExpression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::const_definition))]
     pub struct ConstantDefinition<'ast> {
+        pub public: Option<Pub>,
         pub id: TypedIdentifier<'ast>,
         pub expression: Expression<'ast>,
         #[pest_ast(outer())]
@@ -195,6 +312,7 @@ mod ast {
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::type_definition))]
     pub struct TypeDefinition<'ast> {
+        pub public: Option<Pub>,
         pub id: IdentifierExpression<'ast>,
         pub generics: Vec<IdentifierExpression<'ast>>,
         pub ty: Type<'ast>,
@@ -212,6 +330,7 @@ mod ast {
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
     #[pest_ast(rule(Rule::main_import_directive))]
     pub struct MainImportDirective<'ast> {
+        pub public: Option<Pub>,
         pub source: QString<'ast>,
         pub alias: Option<IdentifierExpression<'ast>>,
         #[pest_ast(outer())]
@@ -227,10 +346,16 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::import_all))]
+    pub struct ImportAll {}
+
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
     #[pest_ast(rule(Rule::from_import_directive))]
     pub struct FromImportDirective<'ast> {
+        pub public: Option<Pub>,
         pub source: QString<'ast>,
+        pub all: Option<ImportAll>,
         pub symbols: Vec<ImportSymbol<'ast>>,
         #[pest_ast(outer())]
         pub span: Span<'ast>,
@@ -243,6 +368,7 @@ mod ast {
         Array(ArrayType<'ast>),
         Struct(StructType<'ast>),
         Tuple(TupleType<'ast>),
+        Function(FunctionType<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
@@ -254,6 +380,11 @@ mod ast {
         U16(U16Type<'ast>),
         U32(U32Type<'ast>),
         U64(U64Type<'ast>),
+        U128(U128Type<'ast>),
+        I8(I8Type<'ast>),
+        I16(I16Type<'ast>),
+        I32(I32Type<'ast>),
+        I64(I64Type<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
@@ -267,11 +398,24 @@ mod ast {
     #[pest_ast(rule(Rule::ty_array))]
     pub struct ArrayType<'ast> {
         pub ty: BasicOrStructOrTupleType<'ast>,
-        pub dimensions: Vec<Expression<'ast>>,
+        pub dimensions: Vec<ArrayDimension<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_array_dimension))]
+    pub struct ArrayDimension<'ast> {
+        pub bounded: Option<Bounded>,
+        pub size: Expression<'ast>,
         #[pest_ast(outer())]
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::ty_array_bounded))]
+    pub struct Bounded;
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::ty_basic_or_struct_or_tuple))]
     pub enum BasicOrStructOrTupleType<'ast> {
@@ -316,6 +460,41 @@ mod ast {
     }
 
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::ty_u128))]
+    pub struct U128Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::ty_i8))]
+    pub struct I8Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::ty_i16))]
+    pub struct I16Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::ty_i32))]
+    pub struct I32Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::ty_i64))]
+    pub struct I64Type<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::ty_struct))]
     pub struct StructType<'ast> {
         pub id: IdentifierExpression<'ast>,
@@ -332,6 +511,23 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_function))]
+    pub struct FunctionType<'ast> {
+        pub inputs: FunctionTypeInputs<'ast>,
+        pub output: Box<Type<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::ty_function_inputs))]
+    pub struct FunctionTypeInputs<'ast> {
+        pub types: Vec<Type<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::parameter))]
     pub struct Parameter<'ast> {
@@ -343,6 +539,14 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::self_parameter))]
+    pub struct SelfParameter<'ast> {
+        pub mutable: Option<Mutable>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
     #[pest_ast(rule(Rule::vis))]
     pub enum Visibility {
@@ -364,8 +568,16 @@ mod ast {
     pub enum Statement<'ast> {
         Return(ReturnStatement<'ast>),
         Definition(DefinitionStatement<'ast>),
+        Destructuring(DestructuringStatement<'ast>),
         Assertion(AssertionStatement<'ast>),
+        StaticAssertion(StaticAssertStatement<'ast>),
         Iteration(IterationStatement<'ast>),
+        ArrayIteration(ArrayIterationStatement<'ast>),
+        While(WhileStatement<'ast>),
+        If(IfStatement<'ast>),
+        ComptimeIf(ComptimeIfStatement<'ast>),
+        Break(BreakStatement<'ast>),
+        Continue(ContinueStatement<'ast>),
         Log(LogStatement<'ast>),
         Assembly(AssemblyStatement<'ast>),
     }
@@ -388,6 +600,69 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::destructuring_statement))]
+    pub struct DestructuringStatement<'ast> {
+        pub pattern: DestructuringPattern<'ast>,
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::destructuring_pattern))]
+    pub enum DestructuringPattern<'ast> {
+        Tuple(TuplePattern<'ast>),
+        Struct(StructPattern<'ast>),
+    }
+
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::pattern))]
+    pub enum Pattern<'ast> {
+        Tuple(TuplePattern<'ast>),
+        Struct(StructPattern<'ast>),
+        Wildcard(Underscore<'ast>),
+        TypedIdentifier(TypedIdentifier<'ast>),
+        Binding(PatternBinding<'ast>),
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::pattern_binding))]
+    pub struct PatternBinding<'ast> {
+        pub mutable: Option<Mutable>,
+        pub identifier: IdentifierExpression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::tuple_pattern))]
+    pub struct TuplePattern<'ast> {
+        pub elements: Vec<Pattern<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::struct_pattern))]
+    pub struct StructPattern<'ast> {
+        pub ty: IdentifierExpression<'ast>,
+        pub fields: Vec<StructPatternField<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::struct_pattern_field))]
+    pub struct StructPatternField<'ast> {
+        pub mutable: Option<Mutable>,
+        pub id: IdentifierExpression<'ast>,
+        pub pattern: Option<Pattern<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
     #[pest_ast(rule(Rule::string))]
     pub struct RawString<'ast> {
@@ -414,6 +689,15 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::static_assert_statement))]
+    pub struct StaticAssertStatement<'ast> {
+        pub expression: Expression<'ast>,
+        pub message: QString<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::iteration_statement))]
     pub struct IterationStatement<'ast> {
@@ -425,6 +709,82 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::array_iteration_statement))]
+    pub struct ArrayIterationStatement<'ast> {
+        pub element: TypedIdentifier<'ast>,
+        pub array: Expression<'ast>,
+        pub statements: Vec<Statement<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::while_statement))]
+    pub struct WhileStatement<'ast> {
+        pub condition: Expression<'ast>,
+        pub bound: Expression<'ast>,
+        pub statements: Vec<Statement<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::if_statement))]
+    pub struct IfStatement<'ast> {
+        pub condition: Expression<'ast>,
+        pub consequence: StatementBlock<'ast>,
+        pub alternative: Option<ElseClause<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::else_clause))]
+    pub enum ElseClause<'ast> {
+        If(Box<IfStatement<'ast>>),
+        Block(StatementBlock<'ast>),
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::comptime_if_statement))]
+    pub struct ComptimeIfStatement<'ast> {
+        pub condition: Expression<'ast>,
+        pub consequence: StatementBlock<'ast>,
+        pub alternative: Option<ComptimeElseClause<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::comptime_else_clause))]
+    pub enum ComptimeElseClause<'ast> {
+        If(Box<ComptimeIfStatement<'ast>>),
+        Block(StatementBlock<'ast>),
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::statement_block))]
+    pub struct StatementBlock<'ast> {
+        pub statements: Vec<Statement<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::break_statement))]
+    pub struct BreakStatement<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::continue_statement))]
+    pub struct ContinueStatement<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::return_statement))]
     pub struct ReturnStatement<'ast> {
@@ -509,6 +869,8 @@ mod ast {
     pub enum Expression<'ast> {
         Ternary(TernaryExpression<'ast>),
         IfElse(IfElseExpression<'ast>),
+        Match(MatchExpression<'ast>),
+        Lambda(LambdaExpression<'ast>),
         Binary(BinaryExpression<'ast>),
         Unary(UnaryExpression<'ast>),
         Postfix(PostfixExpression<'ast>),
@@ -518,6 +880,8 @@ mod ast {
         InlineStruct(InlineStructExpression<'ast>),
         InlineTuple(InlineTupleExpression<'ast>),
         ArrayInitializer(ArrayInitializerExpression<'ast>),
+        EnumVariant(EnumVariantExpression<'ast>),
+        ByteString(ByteStringExpression<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Clone)]
@@ -526,6 +890,10 @@ mod ast {
         Expression(Expression<'ast>),
         InlineStruct(InlineStructExpression<'ast>),
         IfElse(IfElseExpression<'ast>),
+        Match(MatchExpression<'ast>),
+        Lambda(LambdaExpression<'ast>),
+        EnumVariant(EnumVariantExpression<'ast>),
+        ByteString(ByteStringExpression<'ast>),
         Primary(PrimaryExpression<'ast>),
         InlineArray(InlineArrayExpression<'ast>),
         InlineTuple(InlineTupleExpression<'ast>),
@@ -643,6 +1011,10 @@ mod ast {
             match t {
                 Term::Expression(e) => e,
                 Term::IfElse(e) => Expression::IfElse(e),
+                Term::Match(e) => Expression::Match(e),
+                Term::Lambda(e) => Expression::Lambda(e),
+                Term::EnumVariant(e) => Expression::EnumVariant(e),
+                Term::ByteString(e) => Expression::ByteString(e),
                 Term::Primary(e) => e.into(),
                 Term::InlineArray(e) => Expression::InlineArray(e),
                 Term::InlineTuple(e) => Expression::InlineTuple(e),
@@ -699,6 +1071,7 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[allow(clippy::large_enum_variant)]
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::range_or_expression))]
     pub enum RangeOrExpression<'ast> {
@@ -777,6 +1150,10 @@ mod ast {
     #[pest_ast(rule(Rule::_mut))]
     pub struct Mutable {}
 
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::_pub))]
+    pub struct Pub {}
+
     #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::typed_identifier))]
     pub struct TypedIdentifier<'ast> {
@@ -813,7 +1190,7 @@ mod ast {
         pub span: Span<'ast>,
     }
 
-    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::explicit_generics))]
     pub struct ExplicitGenerics<'ast> {
         pub values: Vec<ConstantGenericValue<'ast>>,
@@ -821,9 +1198,10 @@ mod ast {
         pub span: Span<'ast>,
     }
 
-    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[derive(Debug, FromPest, PartialEq, Clone)]
     #[pest_ast(rule(Rule::constant_generics_value))]
     pub enum ConstantGenericValue<'ast> {
+        Expression(Expression<'ast>),
         Value(LiteralExpression<'ast>),
         Identifier(IdentifierExpression<'ast>),
         Underscore(Underscore<'ast>),
@@ -902,6 +1280,62 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::lambda_expression))]
+    pub struct LambdaExpression<'ast> {
+        pub parameters: Vec<Parameter<'ast>>,
+        pub return_type: Type<'ast>,
+        pub statements: Vec<Statement<'ast>>,
+        pub expression: Box<Expression<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::enum_variant_expression))]
+    pub struct EnumVariantExpression<'ast> {
+        pub ty: IdentifierExpression<'ast>,
+        pub variant: IdentifierExpression<'ast>,
+        pub arguments: Vec<Expression<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::match_expression))]
+    pub struct MatchExpression<'ast> {
+        pub expression: Box<Expression<'ast>>,
+        pub arms: Vec<MatchArm<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Clone)]
+    #[pest_ast(rule(Rule::match_arm))]
+    pub struct MatchArm<'ast> {
+        pub pattern: MatchPattern<'ast>,
+        pub expression: Expression<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::match_pattern))]
+    pub enum MatchPattern<'ast> {
+        Wildcard(Underscore<'ast>),
+        Variant(MatchVariantPattern<'ast>),
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::match_variant_pattern))]
+    pub struct MatchVariantPattern<'ast> {
+        pub ty: IdentifierExpression<'ast>,
+        pub variant: IdentifierExpression<'ast>,
+        pub bindings: Vec<IdentifierExpression<'ast>>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     impl<'ast> Expression<'ast> {
         pub fn if_else(
             condition: Box<Expression<'ast>>,
@@ -954,11 +1388,15 @@ mod ast {
                 Expression::Literal(c) => c.span(),
                 Expression::Ternary(t) => &t.span,
                 Expression::IfElse(ie) => &ie.span,
+                Expression::Match(m) => &m.span,
+                Expression::Lambda(l) => &l.span,
                 Expression::Postfix(p) => &p.span,
                 Expression::InlineArray(a) => &a.span,
                 Expression::InlineStruct(s) => &s.span,
                 Expression::InlineTuple(t) => &t.span,
                 Expression::ArrayInitializer(a) => &a.span,
+                Expression::EnumVariant(e) => &e.span,
+                Expression::ByteString(b) => b.span(),
                 Expression::Unary(u) => &u.span,
             }
         }
@@ -982,6 +1420,10 @@ mod ast {
                     *pest = clone;
                     Ok(*climb(pair))
                 }
+                Rule::constant_generics_expression => {
+                    *pest = clone;
+                    Ok(*climb_constant_generics(pair))
+                }
                 _ => Err(ConversionError::NoMatch),
             }
         }
@@ -1012,6 +1454,11 @@ mod ast {
         U16(U16Suffix<'ast>),
         U32(U32Suffix<'ast>),
         U64(U64Suffix<'ast>),
+        U128(U128Suffix<'ast>),
+        I8(I8Suffix<'ast>),
+        I16(I16Suffix<'ast>),
+        I32(I32Suffix<'ast>),
+        I64(I64Suffix<'ast>),
         Field(FieldSuffix<'ast>),
     }
 
@@ -1043,6 +1490,41 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::decimal_suffix_u128))]
+    pub struct U128Suffix<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::decimal_suffix_i8))]
+    pub struct I8Suffix<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::decimal_suffix_i16))]
+    pub struct I16Suffix<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::decimal_suffix_i32))]
+    pub struct I32Suffix<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::decimal_suffix_i64))]
+    pub struct I64Suffix<'ast> {
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
     #[pest_ast(rule(Rule::decimal_suffix_field))]
     pub struct FieldSuffix<'ast> {
@@ -1083,6 +1565,47 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::byte_string_expression))]
+    pub enum ByteStringExpression<'ast> {
+        Bytes(ByteStringLiteralExpression<'ast>),
+        Hex(HexStringLiteralExpression<'ast>),
+    }
+
+    impl<'ast> ByteStringExpression<'ast> {
+        pub fn span(&self) -> &Span<'ast> {
+            match self {
+                ByteStringExpression::Bytes(b) => &b.span,
+                ByteStringExpression::Hex(h) => &h.span,
+            }
+        }
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::byte_string_literal))]
+    pub struct ByteStringLiteralExpression<'ast> {
+        pub value: QString<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::hex_string_literal))]
+    pub struct HexStringLiteralExpression<'ast> {
+        pub value: HexString<'ast>,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::hex_string))]
+    pub struct HexString<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
     #[pest_ast(rule(Rule::hex_number))]
     pub enum HexNumberExpression<'ast> {
@@ -1090,6 +1613,7 @@ mod ast {
         U16(U16NumberExpression<'ast>),
         U32(U32NumberExpression<'ast>),
         U64(U64NumberExpression<'ast>),
+        U128(U128NumberExpression<'ast>),
     }
 
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
@@ -1128,6 +1652,15 @@ mod ast {
         pub span: Span<'ast>,
     }
 
+    #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
+    #[pest_ast(rule(Rule::hex_number_u128))]
+    pub struct U128NumberExpression<'ast> {
+        #[pest_ast(outer(with(span_into_str)))]
+        pub value: String,
+        #[pest_ast(outer())]
+        pub span: Span<'ast>,
+    }
+
     #[derive(Debug, FromPest, PartialEq, Eq, Clone)]
     #[pest_ast(rule(Rule::identifier))]
     pub struct IdentifierExpression<'ast> {
@@ -1237,6 +1770,7 @@ mod tests {
                 pragma: None,
                 declarations: vec![
                     SymbolDeclaration::Import(ImportDirective::Main(MainImportDirective {
+                        public: None,
                         source: QString {
                             raw: RawString {
                                 value: String::from("foo"),
@@ -1248,7 +1782,11 @@ mod tests {
                         span: Span::new(source, 9, 21).unwrap()
                     })),
                     SymbolDeclaration::Function(FunctionDefinition {
+                        attributes: vec![],
+                        public: None,
+                        type_generics: vec![],
                         generics: vec![],
+                        self_parameter: None,
                         id: IdentifierExpression {
                             value: String::from("main"),
                             span: Span::new(source, 36, 40).unwrap()
@@ -1305,6 +1843,7 @@ mod tests {
                 pragma: None,
                 declarations: vec![
                     SymbolDeclaration::Import(ImportDirective::Main(MainImportDirective {
+                        public: None,
                         source: QString {
                             raw: RawString {
                                 value: String::from("foo"),
@@ -1316,7 +1855,11 @@ mod tests {
                         span: Span::new(source, 9, 21).unwrap()
                     })),
                     SymbolDeclaration::Function(FunctionDefinition {
+                        attributes: vec![],
+                        public: None,
+                        type_generics: vec![],
                         generics: vec![],
+                        self_parameter: None,
                         id: IdentifierExpression {
                             value: String::from("main"),
                             span: Span::new(source, 36, 40).unwrap()
@@ -1397,6 +1940,7 @@ mod tests {
                 pragma: None,
                 declarations: vec![
                     SymbolDeclaration::Import(ImportDirective::Main(MainImportDirective {
+                        public: None,
                         source: QString {
                             raw: RawString {
                                 value: String::from("foo"),
@@ -1408,7 +1952,11 @@ mod tests {
                         span: Span::new(source, 9, 21).unwrap()
                     })),
                     SymbolDeclaration::Function(FunctionDefinition {
+                        attributes: vec![],
+                        public: None,
+                        type_generics: vec![],
                         generics: vec![],
+                        self_parameter: None,
                         id: IdentifierExpression {
                             value: String::from("main"),
                             span: Span::new(source, 36, 40).unwrap()
@@ -1468,7 +2016,11 @@ mod tests {
             Ok(File {
                 pragma: None,
                 declarations: vec![SymbolDeclaration::Function(FunctionDefinition {
+                    attributes: vec![],
+                    public: None,
+                    type_generics: vec![],
                     generics: vec![],
+                    self_parameter: None,
                     id: IdentifierExpression {
                         value: String::from("main"),
                         span: Span::new(source, 4, 8).unwrap()
@@ -1535,6 +2087,51 @@ mod tests {
             (field, field) c = foo();
             return [(Foo {a: a.0},); 2];
         }
+"#;
+        let res = generate_ast(source);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn enums() {
+        let source = r#"enum Shape {
+            Circle(field),
+            Rect(field, field),
+            Empty,
+        }
+
+        def main(Shape s) -> field {
+            Shape t = Shape::Rect(1, 2);
+            Shape u = Shape::Empty;
+            return match s {
+                Shape::Circle(r) => r * r,
+                Shape::Rect(w, h) => w * h,
+                _ => 0,
+            };
+        }
+"#;
+        let res = generate_ast(source);
+        assert!(res.is_ok());
+    }
+
+    #[test]
+    fn control_flow() {
+        let source = r#"def main(field[4] a) -> field {
+            field mut sum = 0;
+            u32 mut i = 0;
+            while i < 4 bounded 4 {
+                if a[i] == 0 {
+                    break;
+                } else if a[i] == 1 {
+                    i = i + 1;
+                    continue;
+                } else {
+                    sum = sum + a[i];
+                }
+                i = i + 1;
+            }
+            return sum;
+        }
 "#;
         let res = generate_ast(source);
         assert!(res.is_ok());